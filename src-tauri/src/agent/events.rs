@@ -46,6 +46,16 @@ pub enum AgentEvent {
         affected_resources: Vec<String>,
         warning: Option<String>,
         alternatives: Vec<CommandAlternative>,
+        /// Unix millis after which the command is cancelled and can't be approved
+        expires_at: i64,
+    },
+
+    /// Nobody answered a confirmation in time, so its command was cancelled
+    ConfirmationExpired {
+        session_id: String,
+        query_id: String,
+        confirmation_id: String,
+        command: String,
     },
 
     /// Command execution started (emitted alongside terminal:block_created)
@@ -218,6 +228,12 @@ pub struct AgentQueryRequest {
     pub streaming: bool,
     /// Optional query ID - if provided, backend uses it; otherwise generates one
     pub query_id: Option<String>,
+    /// Preview the agent's plan without running any commands
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Most tool-calling rounds for this query, overriding the default
+    #[serde(default)]
+    pub max_iterations: Option<usize>,
 }
 
 /// Response to a confirmation request
@@ -305,9 +321,11 @@ mod tests {
                 description: "Interactive mode".to_string(),
                 is_safer: true,
             }],
+            expires_at: 1_700_000_300_000,
         };
         let json = serde_json::to_value(&event).unwrap();
         assert_eq!(json["type"], "confirmationRequired");
+        assert_eq!(json["expires_at"], 1_700_000_300_000i64);
         assert_eq!(json["confirmation_id"], "conf-1");
         assert_eq!(json["alternatives"].as_array().unwrap().len(), 1);
         // CommandAlternative has its own rename_all = "camelCase"
@@ -466,6 +484,7 @@ mod tests {
         assert!(req.streaming);
         assert!(req.context_block_ids.is_none());
         assert!(req.query_id.is_none());
+        assert!(!req.dry_run);
     }
 
     // === ConfirmationResponse deserialization ===
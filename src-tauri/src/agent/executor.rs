@@ -6,19 +6,24 @@
 
 use std::process::Stdio;
 use std::sync::Arc;
+use std::time::Duration;
 
+use rig::tool::Tool;
 use serde::{Deserialize, Serialize};
-use tauri::AppHandle;
+use tauri::{AppHandle, Manager};
 use tokio::process::Command;
 use tokio::sync::{mpsc, RwLock};
+use tokio_util::sync::CancellationToken;
 
-use crate::ai::{create_provider, AiSettings, CompletionRequest};
+use crate::ai::{create_provider, AiProviderType, AiSettings, CompletionRequest, OllamaProvider};
 use crate::commands::terminal::TerminalSessions;
+use crate::models::system::ContainerSystem;
 
 use super::events::{AgentEvent, AgentErrorType, ChunkType, QueryCompletionStatus};
 use super::providers::get_agent_preamble;
-use super::safety::{DangerClassifier, DangerLevel};
-use super::session::TerminalContext;
+use super::safety::{DangerLevel, DangerRule};
+use super::session::{AgentSessionManager, ConfirmationReceiver, TerminalContext};
+use super::tools::{ShellExecuteArgs, ShellExecuteTool};
 
 /// Parsed AI response containing commands to execute
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -47,7 +52,17 @@ pub struct CommandResult {
 }
 
 /// Maximum number of tool-calling turns per query
-const MAX_MULTI_TURN: usize = 10;
+/// Default for `ExecutorConfig::max_iterations`
+pub const MAX_ITERATIONS: usize = 10;
+
+/// Default for `ExecutorConfig::max_duration`
+pub const MAX_QUERY_DURATION: Duration = Duration::from_secs(5 * 60);
+
+/// Default for `ExecutorConfig::max_tool_output_lines`
+pub const MAX_TOOL_OUTPUT_LINES: usize = 200;
+
+/// Default for `ExecutorConfig::confirmation_timeout`
+pub const CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(5 * 60);
 
 /// Execute a shell command and return the result
 pub async fn execute_shell_command(command: &str, cwd: &str) -> CommandResult {
@@ -141,7 +156,21 @@ pub type ExecutorResult<T> = Result<T, ExecutorError>;
 pub struct ExecutorConfig {
     pub ai_settings: AiSettings,
     pub auto_execute_safe: bool,
-    pub max_turns: usize,
+    /// Highest danger level run without confirmation; dangerous commands always confirm
+    pub auto_approve_level: DangerLevel,
+    /// Most tool-calling rounds before the agent is stopped with a partial answer
+    pub max_iterations: usize,
+    /// Wall-clock limit for a whole query, however productive each step is
+    pub max_duration: Duration,
+    /// Show the commands the agent would run without executing them
+    pub dry_run: bool,
+    /// User-defined rules consulted by the danger classifier
+    pub danger_rules: Vec<DangerRule>,
+    /// Lines of command output the model sees per tool call; longer output
+    /// keeps its first and last lines and omits the middle
+    pub max_tool_output_lines: usize,
+    /// How long a dangerous command waits for confirmation before it's cancelled
+    pub confirmation_timeout: Duration,
 }
 
 impl Default for ExecutorConfig {
@@ -149,7 +178,13 @@ impl Default for ExecutorConfig {
         Self {
             ai_settings: AiSettings::default(),
             auto_execute_safe: true,
-            max_turns: MAX_MULTI_TURN,
+            auto_approve_level: DangerLevel::Moderate,
+            max_iterations: MAX_ITERATIONS,
+            max_duration: MAX_QUERY_DURATION,
+            dry_run: false,
+            danger_rules: Vec::new(),
+            max_tool_output_lines: MAX_TOOL_OUTPUT_LINES,
+            confirmation_timeout: CONFIRMATION_TIMEOUT,
         }
     }
 }
@@ -160,19 +195,20 @@ impl Default for ExecutorConfig {
 /// 1. Gets the AI provider
 /// 2. Sends the query with terminal context
 /// 3. Parses the JSON response for commands
-/// 4. Executes safe commands automatically
+/// 4. Runs the commands in the terminal through `ShellExecuteTool`, asking the
+///    user to confirm any above the auto-approve level
 /// 5. Emits response events
 pub async fn run_agent_query(
     query: String,
     query_id: String,
     agent_session_id: String,
-    _terminal_session_id: String,
+    terminal_session_id: String,
     config: ExecutorConfig,
-    _terminal_sessions: Arc<TerminalSessions>,
+    terminal_sessions: Arc<TerminalSessions>,
     context: Arc<RwLock<TerminalContext>>,
     event_tx: mpsc::Sender<AgentEvent>,
-    _confirmation_rx: mpsc::Receiver<bool>,
-    _cancel_rx: mpsc::Receiver<()>,
+    confirmation_rx: ConfirmationReceiver,
+    cancel: CancellationToken,
 ) -> ExecutorResult<()> {
     // Emit thinking event
     let _ = event_tx
@@ -187,7 +223,6 @@ pub async fn run_agent_query(
 
     // Build context summary for the prompt
     let ctx = context.read().await;
-    let cwd = ctx.cwd.clone();
     let context_info = format!(
         "Current working directory: {}\nShell: {}\nOS: {}\nUser: {}@{}\n{}{}",
         ctx.cwd,
@@ -226,87 +261,91 @@ pub async fn run_agent_query(
         json_mode: false,
     };
 
-    // Execute the completion
-    match provider.get_completion(completion_request).await {
+    // Execute the completion; cancelling drops the request while it is in flight
+    let completion = tokio::select! {
+        completion = provider.get_completion(completion_request) => completion,
+        _ = cancel.cancelled() => {
+            send_query_cancelled(&event_tx, agent_session_id, query_id).await;
+            return Ok(());
+        }
+    };
+
+    match completion {
         Ok(response) => {
             // Try to parse the response as JSON with commands
             match parse_agent_response(&response.content) {
                 Ok(agent_response) => {
                     let mut output_parts: Vec<String> = Vec::new();
                     let mut all_success = true;
-                    let classifier = DangerClassifier::new();
 
                     // Add the thought/explanation
                     if !agent_response.thought.is_empty() {
                         output_parts.push(format!("💭 {}\n", agent_response.thought));
                     }
 
-                    // Execute commands if present
+                    // Run commands in the user's terminal (local, SSH or container)
+                    // exactly as the tool loop does, including confirmations
                     if !agent_response.commands.is_empty() && config.auto_execute_safe {
+                        let shell_tool = ShellExecuteTool::new(
+                            terminal_session_id.clone(),
+                            agent_session_id.clone(),
+                            terminal_sessions.clone(),
+                            event_tx.clone(),
+                            confirmation_rx.clone(),
+                            context.clone(),
+                            config.auto_approve_level,
+                        )
+                        .with_dry_run(config.dry_run)
+                        .with_danger_rules(&config.danger_rules)
+                        .with_max_output_lines(config.max_tool_output_lines)
+                        .with_confirmation_timeout(config.confirmation_timeout);
+                        shell_tool.set_query_id(query_id.clone()).await;
+
                         for cmd_info in &agent_response.commands {
-                            // Classify the command
-                            let classification = classifier.classify(&cmd_info.command);
-
-                            // Only auto-execute safe and moderate commands
-                            if classification.level == DangerLevel::Safe
-                                || classification.level == DangerLevel::Moderate
-                            {
-                                // Emit command start info
-                                output_parts.push(format!(
-                                    "\n📎 {}\n$ {}\n",
-                                    cmd_info.explanation, cmd_info.command
-                                ));
-
-                                // Send progress chunk
-                                let _ = event_tx
-                                    .send(AgentEvent::ResponseChunk {
-                                        session_id: agent_session_id.clone(),
-                                        query_id: query_id.clone(),
-                                        chunk_type: ChunkType::Command,
-                                        content: format!(
-                                            "💭 {}\n📎 {}\n$ {}\n",
-                                            agent_response.thought,
-                                            cmd_info.explanation,
-                                            cmd_info.command
-                                        ),
-                                        is_final: false,
-                                    })
-                                    .await;
-
-                                // Execute the command
-                                let result = execute_shell_command(&cmd_info.command, &cwd).await;
-
-                                // Add output to response
-                                if !result.stdout.is_empty() {
-                                    output_parts.push(format!("{}\n", result.stdout));
+                            output_parts.push(format!(
+                                "\n📎 {}\n$ {}\n",
+                                cmd_info.explanation, cmd_info.command
+                            ));
+
+                            let args = ShellExecuteArgs {
+                                command: cmd_info.command.clone(),
+                                explanation: Some(cmd_info.explanation.clone()),
+                            };
+                            let result = tokio::select! {
+                                result = shell_tool.call(args) => result,
+                                _ = cancel.cancelled() => {
+                                    send_query_cancelled(&event_tx, agent_session_id, query_id).await;
+                                    return Ok(());
                                 }
-                                if !result.stderr.is_empty() {
-                                    output_parts.push(format!(
-                                        "⚠️ stderr:\n{}\n",
-                                        result.stderr
-                                    ));
+                            };
+
+                            match result {
+                                Ok(result) if result.executed => {
+                                    if !result.output.is_empty() {
+                                        output_parts.push(format!("{}\n", result.output));
+                                    }
+                                    match result.exit_code {
+                                        Some(0) => output_parts.push("✅ Exit code: 0\n".to_string()),
+                                        Some(code) => {
+                                            output_parts.push(format!("❌ Exit code: {}\n", code));
+                                            all_success = false;
+                                        }
+                                        None => {}
+                                    }
                                 }
-
-                                if result.success {
-                                    output_parts.push(format!(
-                                        "✅ Exit code: {}\n",
-                                        result.exit_code
-                                    ));
-                                } else {
-                                    output_parts.push(format!(
-                                        "❌ Exit code: {}\n",
-                                        result.exit_code
-                                    ));
+                                Ok(result) => {
+                                    if let Some(reason) = result.blocked_reason {
+                                        output_parts.push(format!("⚠️ Not run: {}\n", reason));
+                                        all_success = false;
+                                    } else if !result.output.is_empty() {
+                                        // Dry run
+                                        output_parts.push(format!("{}\n", result.output));
+                                    }
+                                }
+                                Err(e) => {
+                                    output_parts.push(format!("❌ {}\n", e));
                                     all_success = false;
                                 }
-                            } else {
-                                // Command requires confirmation - don't execute
-                                output_parts.push(format!(
-                                    "\n⚠️ Command requires confirmation ({}): {}\n📝 {}\n❓ Please run this command manually if you want to proceed.\n",
-                                    classification.level,
-                                    cmd_info.command,
-                                    classification.explanation
-                                ));
                             }
                         }
                     } else if !agent_response.commands.is_empty() {
@@ -409,6 +448,23 @@ pub async fn run_agent_query(
     }
 }
 
+/// Tell the frontend a query stopped because the user cancelled it
+async fn send_query_cancelled(
+    event_tx: &mpsc::Sender<AgentEvent>,
+    session_id: String,
+    query_id: String,
+) {
+    let _ = event_tx
+        .send(AgentEvent::QueryCompleted {
+            session_id,
+            query_id,
+            status: QueryCompletionStatus::Cancelled,
+            summary: Some("Query cancelled by user".to_string()),
+            blocks_created: vec![],
+        })
+        .await;
+}
+
 /// Simple agent execution for context-aware queries
 pub async fn run_agent_simple(
     query: String,
@@ -448,6 +504,34 @@ pub async fn run_agent_simple(
         .map_err(ExecutorError::ProviderError)
 }
 
+/// Whether the provider exposes a native tool-calling API that Rig can drive
+///
+/// OpenAI-style providers (OpenAI, Azure, Groq, DeepSeek, Mistral) use function
+/// calling, Anthropic uses tool_use and Gemini uses function declarations.
+/// Bedrock has no Rig client, so it always goes through the JSON path.
+pub fn provider_has_tool_api(provider: AiProviderType) -> bool {
+    !matches!(provider, AiProviderType::Bedrock)
+}
+
+/// Check whether the configured provider and model can run the tool loop
+///
+/// For Ollama, tool support depends on the model, so ask the server whether
+/// the selected model advertises the `tools` capability.
+pub async fn supports_tool_calling(settings: &AiSettings) -> bool {
+    if !provider_has_tool_api(settings.provider) {
+        return false;
+    }
+
+    match settings.provider {
+        AiProviderType::Ollama => {
+            OllamaProvider::new(&settings.endpoint_url, &settings.model_name)
+                .supports_tools()
+                .await
+        }
+        _ => true,
+    }
+}
+
 /// Run a multi-turn agentic loop with tool use
 ///
 /// This function uses the Rig framework to handle multi-turn tool execution
@@ -459,30 +543,62 @@ pub async fn run_agent_simple(
 ///
 /// The actual tool implementations (ShellExecuteTool, StateQueryTool) are
 /// defined in agent/tools/ and already implement rig::tool::Tool.
+///
+/// Providers or models without tool support fall back to the single-turn
+/// JSON workflow in `run_agent_query`.
 pub async fn run_agentic_loop(
-    _app: &AppHandle,
+    app: &AppHandle,
     agent_session_id: &str,
     query_id: &str,
     query: &str,
     terminal_session_id: &str,
-    settings: &AiSettings,
+    config: ExecutorConfig,
     terminal_sessions: Arc<TerminalSessions>,
     context: Arc<RwLock<TerminalContext>>,
     event_tx: mpsc::Sender<AgentEvent>,
+    system: Option<ContainerSystem>,
+    cancel: CancellationToken,
 ) -> ExecutorResult<()> {
+    let settings = &config.ai_settings;
     tracing::info!(
         "Starting Rig-based agentic loop with provider: {:?}, model: {}",
         settings.provider,
         settings.model_name
     );
 
-    // Create a confirmation channel for this query
-    // Note: The session manager should provide this, but for now we create a dummy one
-    let (_confirm_tx, confirm_rx) = mpsc::channel(1);
+    // Answers to confirmations arrive through the session, from respond_to_confirmation
+    let confirm_rx = app
+        .state::<AgentSessionManager>()
+        .get_confirmation_receiver(agent_session_id)
+        .await
+        .ok_or_else(|| {
+            ExecutorError::SessionError(format!("Session {} not found", agent_session_id))
+        })?;
+
+    if !supports_tool_calling(settings).await {
+        tracing::info!(
+            "Model {} does not support tool calling, using single-turn JSON mode",
+            settings.model_name
+        );
+
+        return run_agent_query(
+            query.to_string(),
+            query_id.to_string(),
+            agent_session_id.to_string(),
+            terminal_session_id.to_string(),
+            config,
+            terminal_sessions,
+            context,
+            event_tx,
+            confirm_rx,
+            cancel,
+        )
+        .await;
+    }
 
     // Use the Rig-based executor
     let result = super::rig_executor::run_rig_agent(
-        settings,
+        &config,
         query,
         terminal_session_id,
         agent_session_id,
@@ -491,6 +607,8 @@ pub async fn run_agentic_loop(
         context,
         event_tx,
         confirm_rx,
+        system,
+        cancel,
     )
     .await;
 
@@ -514,6 +632,22 @@ mod tests {
     fn test_executor_config_default() {
         let config = ExecutorConfig::default();
         assert!(config.auto_execute_safe);
-        assert_eq!(config.max_turns, MAX_MULTI_TURN);
+        assert_eq!(config.auto_approve_level, DangerLevel::Moderate);
+        assert!(!config.dry_run);
+        assert_eq!(config.max_iterations, MAX_ITERATIONS);
+        assert_eq!(config.max_duration, MAX_QUERY_DURATION);
+        assert_eq!(config.max_tool_output_lines, MAX_TOOL_OUTPUT_LINES);
+        assert_eq!(config.confirmation_timeout, CONFIRMATION_TIMEOUT);
+    }
+
+    #[test]
+    fn test_provider_has_tool_api() {
+        assert!(provider_has_tool_api(AiProviderType::OpenAi));
+        assert!(provider_has_tool_api(AiProviderType::AzureOpenAi));
+        assert!(provider_has_tool_api(AiProviderType::Groq));
+        assert!(provider_has_tool_api(AiProviderType::DeepSeek));
+        assert!(provider_has_tool_api(AiProviderType::Mistral));
+        assert!(provider_has_tool_api(AiProviderType::Ollama));
+        assert!(!provider_has_tool_api(AiProviderType::Bedrock));
     }
 }
@@ -2,11 +2,12 @@
 //!
 //! This module provides an AI agent system for the terminal with support for:
 //! - Single-turn JSON-based command execution
-//! - Multi-turn agentic loops with Anthropic tool_use API
+//! - Multi-turn agentic loops with native tool calling (with a JSON fallback)
 //! - Natural language to shell command translation
 //! - Multi-step workflows with command output analysis
 //! - Intelligent command execution with safety checks
 //! - Conversation memory via input summarization
+//! - Redacted transcript export of agent sessions
 
 pub mod events;
 pub mod executor;
@@ -17,12 +18,13 @@ pub mod safety;
 pub mod session;
 pub mod summarizer;
 pub mod tools;
+pub mod transcript;
 
 // Re-export commonly used types
 pub use events::{AgentCommand, AgentEvent};
 pub use executor::{run_agent_query, run_agent_simple, run_agentic_loop, ExecutorConfig, ExecutorError};
 pub use providers::create_agent;
 pub use pty_bridge::{CommandExecution, PtyBridge};
-pub use safety::{DangerClassification, DangerClassifier, DangerLevel};
+pub use safety::{DangerClassification, DangerClassifier, DangerLevel, DangerRule};
 pub use session::{AgentSession, AgentSessionManager, ConversationMessage, TerminalContext};
 pub use summarizer::{summarize_user_input, InputSummary};
@@ -6,19 +6,25 @@
 
 use std::sync::Arc;
 
-use futures::StreamExt;
-use rig::agent::{AgentBuilder, MultiTurnStreamItem};
-use rig::client::{CompletionClient, ProviderClient};
+use futures::{Stream, StreamExt};
+use rig::agent::{AgentBuilder, MultiTurnStreamItem, StreamingError};
+use rig::client::{CompletionClient, Nothing};
+use rig::completion::PromptError;
 use rig::providers::{anthropic, azure, deepseek, gemini, groq, mistral, ollama, openai};
 use rig::streaming::{StreamedAssistantContent, StreamingPrompt};
 use tokio::sync::{mpsc, RwLock};
+use tokio_util::sync::CancellationToken;
 
 use crate::agent::events::{AgentEvent, ChunkType, QueryCompletionStatus};
-use crate::agent::session::{ConversationTurn, TerminalContext, TurnToolCall};
-use crate::agent::summarizer::{summarize_user_input, InputSummary};
-use crate::agent::tools::{HistoryQueryTool, ShellExecuteTool, StateQueryTool};
-use crate::ai::{AiProviderType, AiSettings};
+use crate::agent::executor::ExecutorConfig;
+use crate::agent::session::{ConfirmationReceiver, ConversationTurn, TerminalContext, TurnToolCall};
+use crate::agent::summarizer::summarize_user_input;
+use crate::agent::tools::{
+    HistoryQueryTool, InspectContainerTool, ReadContainerLogsTool, ShellExecuteTool, StateQueryTool,
+};
+use crate::ai::AiProviderType;
 use crate::commands::terminal::TerminalSessions;
+use crate::models::system::ContainerSystem;
 
 /// Get the system prompt for agentic terminal assistance
 fn get_agentic_preamble(context: &TerminalContext) -> String {
@@ -168,6 +174,15 @@ For the `query_history` tool:
 * ALWAYS check history before re-running commands - use query_type="get_output" to retrieve previous results
 * This is more efficient than re-running commands when you need data from earlier in the session
 
+For the `read_container_logs` tool:
+* Use this to read a container's logs instead of running `docker logs` or `podman logs` in the terminal
+* Start with the default tail and only ask for more lines if what you need is missing
+* Use `since` (e.g. "10m") to focus on recent activity
+
+For the `inspect_container` tool:
+* Use this to look up a container's ports, environment, mounts, networks, state or config instead of running `docker inspect`
+* Ask only for the `fields` you need; the field names in the result are the only ones that exist
+
 # Running terminal commands
 Terminal commands are one of the most powerful tools available to you.
 
@@ -297,6 +312,138 @@ User: {username}@{hostname}
     )
 }
 
+/// Emit thinking events for streamed text, collecting it into `partial` and
+/// the final answer into `final_response`
+async fn process_stream_item<R>(
+    item: MultiTurnStreamItem<R>,
+    event_tx: &mpsc::Sender<AgentEvent>,
+    agent_session_id: &str,
+    query_id: &str,
+    partial: &mut String,
+    final_response: &mut String,
+) {
+    match item {
+        MultiTurnStreamItem::StreamAssistantItem(content) => {
+            // Extract text from streaming content and emit as thinking
+            let thinking_text = match &content {
+                StreamedAssistantContent::Text(text) => {
+                    partial.push_str(&text.text);
+                    Some(text.text.clone())
+                }
+                StreamedAssistantContent::Reasoning(reasoning) => {
+                    // reasoning.reasoning is Vec<String>, join them
+                    Some(reasoning.reasoning.join(" "))
+                }
+                StreamedAssistantContent::ReasoningDelta { reasoning, .. } => {
+                    Some(reasoning.clone())
+                }
+                StreamedAssistantContent::ToolCall(tc) => {
+                    // Log tool call for debugging
+                    tracing::debug!(
+                        "[Agent] Tool call: {} with args: {:?}",
+                        tc.function.name,
+                        tc.function.arguments
+                    );
+                    None
+                }
+                StreamedAssistantContent::ToolCallDelta { .. } => None,
+                StreamedAssistantContent::Final(_) => None,
+            };
+
+            if let Some(text) = thinking_text {
+                if !text.is_empty() {
+                    let _ = event_tx
+                        .send(AgentEvent::ResponseChunk {
+                            session_id: agent_session_id.to_string(),
+                            query_id: query_id.to_string(),
+                            chunk_type: ChunkType::Thinking,
+                            content: text,
+                            is_final: false,
+                        })
+                        .await;
+                }
+            }
+        }
+        MultiTurnStreamItem::StreamUserItem(_) => {
+            // Tool results - already handled by ShellExecuteTool via command_history
+        }
+        MultiTurnStreamItem::FinalResponse(response) => {
+            *final_response = response.response().to_string();
+        }
+        _ => {
+            // Handle any future variants added to the non-exhaustive enum
+        }
+    }
+}
+
+/// How a streamed agent run ended
+#[derive(Debug, PartialEq)]
+enum AgentRun {
+    /// The model gave its final answer
+    Finished(String),
+    /// The run hit `max_iterations` or `max_duration`; `partial` is the text
+    /// streamed before it was stopped
+    Stopped { reason: String, partial: String },
+    /// The user cancelled the query
+    Cancelled { partial: String },
+}
+
+/// Drive a multi-turn agent stream until it finishes, fails, runs out of
+/// turns, passes `deadline` or is cancelled. Returning drops the stream, which
+/// aborts any model request still in flight.
+async fn consume_stream<R, S>(
+    mut stream: S,
+    max_iterations: usize,
+    deadline: tokio::time::Instant,
+    cancel: &CancellationToken,
+    event_tx: &mpsc::Sender<AgentEvent>,
+    agent_session_id: &str,
+    query_id: &str,
+) -> Result<AgentRun, String>
+where
+    S: Stream<Item = Result<MultiTurnStreamItem<R>, StreamingError>> + Unpin,
+{
+    let mut partial = String::new();
+    let mut final_response = String::new();
+    loop {
+        let next = tokio::select! {
+            biased;
+            _ = cancel.cancelled() => return Ok(AgentRun::Cancelled { partial }),
+            next = tokio::time::timeout_at(deadline, stream.next()) => next,
+        };
+        let item = match next {
+            Ok(Some(item)) => item,
+            Ok(None) => return Ok(AgentRun::Finished(final_response)),
+            Err(_) => {
+                return Ok(AgentRun::Stopped {
+                    reason: "it ran past its time limit".to_string(),
+                    partial,
+                })
+            }
+        };
+        match item {
+            Ok(item) => {
+                process_stream_item(
+                    item,
+                    event_tx,
+                    agent_session_id,
+                    query_id,
+                    &mut partial,
+                    &mut final_response,
+                )
+                .await;
+            }
+            Err(StreamingError::Prompt(e)) if matches!(*e, PromptError::MaxDepthError { .. }) => {
+                return Ok(AgentRun::Stopped {
+                    reason: format!("it reached the limit of {} steps", max_iterations),
+                    partial,
+                });
+            }
+            Err(e) => return Err(format!("Streaming error: {}", e)),
+        }
+    }
+}
+
 /// Run an agent query using Rig's built-in tool execution
 ///
 /// This function:
@@ -305,7 +452,7 @@ User: {username}@{hostname}
 /// 3. Lets Rig handle the multi-turn tool execution automatically
 /// 4. Emits events for frontend updates
 pub async fn run_rig_agent(
-    settings: &AiSettings,
+    config: &ExecutorConfig,
     query: &str,
     terminal_session_id: &str,
     agent_session_id: &str,
@@ -313,8 +460,13 @@ pub async fn run_rig_agent(
     terminal_sessions: Arc<TerminalSessions>,
     context: Arc<RwLock<TerminalContext>>,
     event_tx: mpsc::Sender<AgentEvent>,
-    confirmation_rx: mpsc::Receiver<bool>,
+    confirmation_rx: ConfirmationReceiver,
+    system: Option<ContainerSystem>,
+    cancel: CancellationToken,
 ) -> Result<String, String> {
+    let settings = &config.ai_settings;
+    let deadline = tokio::time::Instant::now() + config.max_duration;
+
     // Emit thinking event
     let _ = event_tx
         .send(AgentEvent::Thinking {
@@ -323,40 +475,27 @@ pub async fn run_rig_agent(
         })
         .await;
 
-    // Summarize the user input and store it for conversation memory
-    // This happens BEFORE running the agent so the preamble includes the summary
-    if settings.memory_enabled {
-        let summary_result = summarize_user_input(settings, query).await;
-        match summary_result {
-            Ok(summary) => {
-                tracing::info!(
-                    "[Agent] Input summarized: '{}' ({} -> {} chars)",
-                    summary.summary,
-                    summary.original_length,
-                    summary.summary.len()
-                );
-                // Store the summary in context for future reference
-                let mut ctx = context.write().await;
-                ctx.add_input_summary(summary);
-                drop(ctx);
-            }
-            Err(e) => {
-                tracing::warn!("[Agent] Failed to summarize input: {}", e);
-                // Still store a truncated version as fallback
-                let fallback_summary = InputSummary {
-                    summary: if query.len() > 200 {
-                        format!("{}...", &query[..200])
-                    } else {
-                        query.to_string()
-                    },
-                    timestamp: chrono::Utc::now().timestamp_millis(),
-                    original_length: query.len(),
-                };
-                let mut ctx = context.write().await;
-                ctx.add_input_summary(fallback_summary);
-                drop(ctx);
-            }
+    // Summarize the user input and store it for conversation memory.
+    // This happens BEFORE running the agent so the preamble includes the summary;
+    // with memory disabled the summarizer only truncates, without a model call.
+    let summary = tokio::select! {
+        summary = summarize_user_input(settings, query) => summary,
+        _ = cancel.cancelled() => Err("cancelled".to_string()),
+    };
+    match summary {
+        Ok(summary) => {
+            tracing::info!(
+                "[Agent] Input summarized: '{}' ({} -> {} chars)",
+                summary.summary,
+                summary.original_length,
+                summary.summary.len()
+            );
+            // Store the summary in context for future reference
+            let mut ctx = context.write().await;
+            ctx.add_input_summary(summary);
+            drop(ctx);
         }
+        Err(e) => tracing::warn!("[Agent] Failed to summarize input: {}", e),
     }
 
     // Create tools with all required state
@@ -367,14 +506,20 @@ pub async fn run_rig_agent(
         event_tx.clone(),
         confirmation_rx,
         context.clone(),
-        true, // auto_execute safe commands
-    );
+        config.auto_approve_level,
+    )
+    .with_dry_run(config.dry_run)
+    .with_danger_rules(&config.danger_rules)
+    .with_max_output_lines(config.max_tool_output_lines)
+    .with_confirmation_timeout(config.confirmation_timeout);
 
     // Set the query ID so the tool can emit proper events
     shell_tool.set_query_id(query_id.to_string()).await;
 
     let state_tool = StateQueryTool::new(context.clone());
     let history_tool = HistoryQueryTool::new(context.clone());
+    let logs_tool = ReadContainerLogsTool::new(system.clone()).with_dry_run(config.dry_run);
+    let inspect_tool = InspectContainerTool::new(system).with_dry_run(config.dry_run);
 
     // Build context for system prompt
     let ctx = context.read().await;
@@ -382,7 +527,7 @@ pub async fn run_rig_agent(
 
     // Log available tools and history count for debugging
     tracing::info!(
-        "[Agent] Tools: execute_shell, query_state, query_history | Command history entries: {}",
+        "[Agent] Tools: execute_shell, query_state, query_history, read_container_logs, inspect_container | Command history entries: {}",
         ctx.command_history.len()
     );
     if !ctx.command_history.is_empty() {
@@ -403,63 +548,8 @@ pub async fn run_rig_agent(
         ctx.command_history.len()
     };
 
-    // Helper to process streaming items and emit thinking events
-    async fn process_stream_item<R>(
-        item: MultiTurnStreamItem<R>,
-        event_tx: &mpsc::Sender<AgentEvent>,
-        agent_session_id: &str,
-        query_id: &str,
-        final_response: &mut String,
-    ) {
-        match item {
-            MultiTurnStreamItem::StreamAssistantItem(content) => {
-                // Extract text from streaming content and emit as thinking
-                let thinking_text = match &content {
-                    StreamedAssistantContent::Text(text) => Some(text.text.clone()),
-                    StreamedAssistantContent::Reasoning(reasoning) => {
-                        // reasoning.reasoning is Vec<String>, join them
-                        Some(reasoning.reasoning.join(" "))
-                    }
-                    StreamedAssistantContent::ReasoningDelta { reasoning, .. } => {
-                        Some(reasoning.clone())
-                    }
-                    StreamedAssistantContent::ToolCall(tc) => {
-                        // Log tool call for debugging
-                        tracing::debug!("[Agent] Tool call: {} with args: {:?}", tc.function.name, tc.function.arguments);
-                        None
-                    }
-                    StreamedAssistantContent::ToolCallDelta { .. } => None,
-                    StreamedAssistantContent::Final(_) => None,
-                };
-
-                if let Some(text) = thinking_text {
-                    if !text.is_empty() {
-                        let _ = event_tx
-                            .send(AgentEvent::ResponseChunk {
-                                session_id: agent_session_id.to_string(),
-                                query_id: query_id.to_string(),
-                                chunk_type: ChunkType::Thinking,
-                                content: text,
-                                is_final: false,
-                            })
-                            .await;
-                    }
-                }
-            }
-            MultiTurnStreamItem::StreamUserItem(_) => {
-                // Tool results - already handled by ShellExecuteTool via command_history
-            }
-            MultiTurnStreamItem::FinalResponse(response) => {
-                *final_response = response.response().to_string();
-            }
-            _ => {
-                // Handle any future variants added to the non-exhaustive enum
-            }
-        }
-    }
-
     // Create Rig agent based on provider and execute with streaming
-    let result: Result<String, String> = match settings.provider {
+    let result: Result<AgentRun, String> = match settings.provider {
         AiProviderType::Anthropic => {
             // Create Anthropic client with explicit type annotation for HTTP client
             let client: anthropic::Client = anthropic::Client::new(
@@ -471,31 +561,26 @@ pub async fn run_rig_agent(
                 .tool(shell_tool)
                 .tool(state_tool)
                 .tool(history_tool)
+                .tool(logs_tool)
+                .tool(inspect_tool)
                 .build();
 
             // Use streaming API to capture intermediate reasoning
             // stream_prompt().multi_turn().await returns the stream directly
-            let mut stream = agent.stream_prompt(query).multi_turn(10).await;
-
-            let mut final_response = String::new();
-            while let Some(item_result) = stream.next().await {
-                match item_result {
-                    Ok(item) => {
-                        process_stream_item(
-                            item,
-                            &event_tx,
-                            agent_session_id,
-                            query_id,
-                            &mut final_response,
-                        )
-                        .await;
-                    }
-                    Err(e) => {
-                        return Err(format!("Streaming error: {}", e));
-                    }
-                }
-            }
-            Ok(final_response)
+            let stream = agent
+                .stream_prompt(query)
+                .multi_turn(config.max_iterations)
+                .await;
+            consume_stream(
+                stream,
+                config.max_iterations,
+                deadline,
+                &cancel,
+                &event_tx,
+                agent_session_id,
+                query_id,
+            )
+            .await
         }
         AiProviderType::OpenAi => {
             // Create OpenAI client with explicit type annotation for HTTP client
@@ -508,65 +593,59 @@ pub async fn run_rig_agent(
                 .tool(shell_tool)
                 .tool(state_tool)
                 .tool(history_tool)
+                .tool(logs_tool)
+                .tool(inspect_tool)
                 .build();
 
             // Use streaming API to capture intermediate reasoning
-            let mut stream = agent.stream_prompt(query).multi_turn(10).await;
-
-            let mut final_response = String::new();
-            while let Some(item_result) = stream.next().await {
-                match item_result {
-                    Ok(item) => {
-                        process_stream_item(
-                            item,
-                            &event_tx,
-                            agent_session_id,
-                            query_id,
-                            &mut final_response,
-                        )
-                        .await;
-                    }
-                    Err(e) => {
-                        return Err(format!("Streaming error: {}", e));
-                    }
-                }
-            }
-            Ok(final_response)
+            let stream = agent
+                .stream_prompt(query)
+                .multi_turn(config.max_iterations)
+                .await;
+            consume_stream(
+                stream,
+                config.max_iterations,
+                deadline,
+                &cancel,
+                &event_tx,
+                agent_session_id,
+                query_id,
+            )
+            .await
         }
         AiProviderType::Ollama => {
-            // Ollama is natively supported in rig-core, defaults to localhost:11434
-            // Uses ProviderClient::from_env() since Ollama doesn't need an API key
-            let client: ollama::Client = ProviderClient::from_env();
+            // Ollama is natively supported in rig-core and doesn't need an API key.
+            // Point it at the configured endpoint so it matches the tool-support probe.
+            let client: ollama::Client = ollama::Client::builder()
+                .api_key(Nothing)
+                .base_url(settings.endpoint_url.trim_end_matches('/'))
+                .build()
+                .map_err(|e| format!("Failed to create Ollama client: {}", e))?;
             let model = client.completion_model(&settings.model_name);
             let agent = AgentBuilder::new(model)
                 .preamble(&preamble)
                 .tool(shell_tool)
                 .tool(state_tool)
                 .tool(history_tool)
+                .tool(logs_tool)
+                .tool(inspect_tool)
                 .build();
 
             // Use streaming API to capture intermediate reasoning
-            let mut stream = agent.stream_prompt(query).multi_turn(10).await;
-
-            let mut final_response = String::new();
-            while let Some(item_result) = stream.next().await {
-                match item_result {
-                    Ok(item) => {
-                        process_stream_item(
-                            item,
-                            &event_tx,
-                            agent_session_id,
-                            query_id,
-                            &mut final_response,
-                        )
-                        .await;
-                    }
-                    Err(e) => {
-                        return Err(format!("Streaming error: {}", e));
-                    }
-                }
-            }
-            Ok(final_response)
+            let stream = agent
+                .stream_prompt(query)
+                .multi_turn(config.max_iterations)
+                .await;
+            consume_stream(
+                stream,
+                config.max_iterations,
+                deadline,
+                &cancel,
+                &event_tx,
+                agent_session_id,
+                query_id,
+            )
+            .await
         }
         AiProviderType::AzureOpenAi => {
             let api_key = settings.api_key.clone().unwrap_or_default();
@@ -583,29 +662,24 @@ pub async fn run_rig_agent(
                 .tool(shell_tool)
                 .tool(state_tool)
                 .tool(history_tool)
+                .tool(logs_tool)
+                .tool(inspect_tool)
                 .build();
 
-            let mut stream = agent.stream_prompt(query).multi_turn(10).await;
-
-            let mut final_response = String::new();
-            while let Some(item_result) = stream.next().await {
-                match item_result {
-                    Ok(item) => {
-                        process_stream_item(
-                            item,
-                            &event_tx,
-                            agent_session_id,
-                            query_id,
-                            &mut final_response,
-                        )
-                        .await;
-                    }
-                    Err(e) => {
-                        return Err(format!("Streaming error: {}", e));
-                    }
-                }
-            }
-            Ok(final_response)
+            let stream = agent
+                .stream_prompt(query)
+                .multi_turn(config.max_iterations)
+                .await;
+            consume_stream(
+                stream,
+                config.max_iterations,
+                deadline,
+                &cancel,
+                &event_tx,
+                agent_session_id,
+                query_id,
+            )
+            .await
         }
         AiProviderType::Groq => {
             let client: groq::Client = groq::Client::new(
@@ -617,29 +691,24 @@ pub async fn run_rig_agent(
                 .tool(shell_tool)
                 .tool(state_tool)
                 .tool(history_tool)
+                .tool(logs_tool)
+                .tool(inspect_tool)
                 .build();
 
-            let mut stream = agent.stream_prompt(query).multi_turn(10).await;
-
-            let mut final_response = String::new();
-            while let Some(item_result) = stream.next().await {
-                match item_result {
-                    Ok(item) => {
-                        process_stream_item(
-                            item,
-                            &event_tx,
-                            agent_session_id,
-                            query_id,
-                            &mut final_response,
-                        )
-                        .await;
-                    }
-                    Err(e) => {
-                        return Err(format!("Streaming error: {}", e));
-                    }
-                }
-            }
-            Ok(final_response)
+            let stream = agent
+                .stream_prompt(query)
+                .multi_turn(config.max_iterations)
+                .await;
+            consume_stream(
+                stream,
+                config.max_iterations,
+                deadline,
+                &cancel,
+                &event_tx,
+                agent_session_id,
+                query_id,
+            )
+            .await
         }
         AiProviderType::Gemini => {
             let client: gemini::Client = gemini::Client::new(
@@ -651,29 +720,24 @@ pub async fn run_rig_agent(
                 .tool(shell_tool)
                 .tool(state_tool)
                 .tool(history_tool)
+                .tool(logs_tool)
+                .tool(inspect_tool)
                 .build();
 
-            let mut stream = agent.stream_prompt(query).multi_turn(10).await;
-
-            let mut final_response = String::new();
-            while let Some(item_result) = stream.next().await {
-                match item_result {
-                    Ok(item) => {
-                        process_stream_item(
-                            item,
-                            &event_tx,
-                            agent_session_id,
-                            query_id,
-                            &mut final_response,
-                        )
-                        .await;
-                    }
-                    Err(e) => {
-                        return Err(format!("Streaming error: {}", e));
-                    }
-                }
-            }
-            Ok(final_response)
+            let stream = agent
+                .stream_prompt(query)
+                .multi_turn(config.max_iterations)
+                .await;
+            consume_stream(
+                stream,
+                config.max_iterations,
+                deadline,
+                &cancel,
+                &event_tx,
+                agent_session_id,
+                query_id,
+            )
+            .await
         }
         AiProviderType::DeepSeek => {
             let client: deepseek::Client = deepseek::Client::new(
@@ -685,29 +749,24 @@ pub async fn run_rig_agent(
                 .tool(shell_tool)
                 .tool(state_tool)
                 .tool(history_tool)
+                .tool(logs_tool)
+                .tool(inspect_tool)
                 .build();
 
-            let mut stream = agent.stream_prompt(query).multi_turn(10).await;
-
-            let mut final_response = String::new();
-            while let Some(item_result) = stream.next().await {
-                match item_result {
-                    Ok(item) => {
-                        process_stream_item(
-                            item,
-                            &event_tx,
-                            agent_session_id,
-                            query_id,
-                            &mut final_response,
-                        )
-                        .await;
-                    }
-                    Err(e) => {
-                        return Err(format!("Streaming error: {}", e));
-                    }
-                }
-            }
-            Ok(final_response)
+            let stream = agent
+                .stream_prompt(query)
+                .multi_turn(config.max_iterations)
+                .await;
+            consume_stream(
+                stream,
+                config.max_iterations,
+                deadline,
+                &cancel,
+                &event_tx,
+                agent_session_id,
+                query_id,
+            )
+            .await
         }
         AiProviderType::Mistral => {
             let client: mistral::Client = mistral::Client::new(
@@ -719,29 +778,28 @@ pub async fn run_rig_agent(
                 .tool(shell_tool)
                 .tool(state_tool)
                 .tool(history_tool)
+                .tool(logs_tool)
+                .tool(inspect_tool)
                 .build();
 
-            let mut stream = agent.stream_prompt(query).multi_turn(10).await;
-
-            let mut final_response = String::new();
-            while let Some(item_result) = stream.next().await {
-                match item_result {
-                    Ok(item) => {
-                        process_stream_item(
-                            item,
-                            &event_tx,
-                            agent_session_id,
-                            query_id,
-                            &mut final_response,
-                        )
-                        .await;
-                    }
-                    Err(e) => {
-                        return Err(format!("Streaming error: {}", e));
-                    }
-                }
-            }
-            Ok(final_response)
+            let stream = agent
+                .stream_prompt(query)
+                .multi_turn(config.max_iterations)
+                .await;
+            consume_stream(
+                stream,
+                config.max_iterations,
+                deadline,
+                &cancel,
+                &event_tx,
+                agent_session_id,
+                query_id,
+            )
+            .await
+        }
+        AiProviderType::Bedrock => {
+            // Rig has no Bedrock client, so tool-calling agent mode isn't available
+            Err("Agent mode is not supported for AWS Bedrock yet. Use shell suggestions instead.".to_string())
         }
     };
 
@@ -791,7 +849,15 @@ pub async fn run_rig_agent(
         let turn = ConversationTurn {
             user_input: query.to_string(),
             tool_calls: new_commands,
-            ai_response: result.as_ref().ok().cloned(),
+            ai_response: match &result {
+                Ok(AgentRun::Finished(response)) => Some(response.clone()),
+                Ok(AgentRun::Stopped { partial, .. } | AgentRun::Cancelled { partial })
+                    if !partial.is_empty() =>
+                {
+                    Some(partial.clone())
+                }
+                _ => None,
+            },
             timestamp: chrono::Utc::now().timestamp_millis(),
         };
 
@@ -805,7 +871,7 @@ pub async fn run_rig_agent(
 
     // Emit response events based on result
     match &result {
-        Ok(response) => {
+        Ok(AgentRun::Finished(response)) => {
             // NOTE: We don't emit a final ResponseChunk here because the content
             // was already streamed via StreamAssistantItem during the agent loop.
             // Emitting it again would cause duplication.
@@ -821,6 +887,47 @@ pub async fn run_rig_agent(
                 })
                 .await;
         }
+        Ok(AgentRun::Stopped { reason, partial }) => {
+            tracing::warn!("[Agent] Stopped query {} because {}", query_id, reason);
+            let _ = event_tx
+                .send(AgentEvent::ResponseChunk {
+                    session_id: agent_session_id.to_string(),
+                    query_id: query_id.to_string(),
+                    chunk_type: ChunkType::Warning,
+                    content: format!(
+                        "The agent was stopped because {}. The results so far are shown above; ask again to continue.",
+                        reason
+                    ),
+                    is_final: true,
+                })
+                .await;
+
+            let _ = event_tx
+                .send(AgentEvent::QueryCompleted {
+                    session_id: agent_session_id.to_string(),
+                    query_id: query_id.to_string(),
+                    status: QueryCompletionStatus::PartialSuccess,
+                    summary: Some(partial.clone()),
+                    blocks_created: vec![],
+                })
+                .await;
+        }
+        Ok(AgentRun::Cancelled { partial }) => {
+            tracing::info!("[Agent] Query {} cancelled by user", query_id);
+            let _ = event_tx
+                .send(AgentEvent::QueryCompleted {
+                    session_id: agent_session_id.to_string(),
+                    query_id: query_id.to_string(),
+                    status: QueryCompletionStatus::Cancelled,
+                    summary: Some(if partial.is_empty() {
+                        "Query cancelled by user".to_string()
+                    } else {
+                        partial.clone()
+                    }),
+                    blocks_created: vec![],
+                })
+                .await;
+        }
         Err(error) => {
             // Emit error event
             let _ = event_tx
@@ -847,5 +954,120 @@ pub async fn run_rig_agent(
         }
     }
 
-    result
+    result.map(|run| match run {
+        AgentRun::Finished(response) => response,
+        AgentRun::Stopped { partial, .. } | AgentRun::Cancelled { partial } => partial,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::stream;
+    use rig::completion::{Message, Usage};
+
+    type Item = Result<MultiTurnStreamItem<()>, StreamingError>;
+
+    fn text(text: &str) -> Item {
+        Ok(MultiTurnStreamItem::StreamAssistantItem(
+            StreamedAssistantContent::text(text),
+        ))
+    }
+
+    async fn consume(
+        items: impl Stream<Item = Item> + Unpin,
+        deadline: tokio::time::Instant,
+    ) -> Result<AgentRun, String> {
+        consume_with(items, deadline, &CancellationToken::new()).await
+    }
+
+    async fn consume_with(
+        items: impl Stream<Item = Item> + Unpin,
+        deadline: tokio::time::Instant,
+        cancel: &CancellationToken,
+    ) -> Result<AgentRun, String> {
+        let (event_tx, _event_rx) = mpsc::channel(16);
+        consume_stream(items, 10, deadline, cancel, &event_tx, "session", "query").await
+    }
+
+    fn later() -> tokio::time::Instant {
+        tokio::time::Instant::now() + std::time::Duration::from_secs(60)
+    }
+
+    #[tokio::test]
+    async fn test_consume_stream_returns_final_response() {
+        let items = stream::iter(vec![
+            text("Checking"),
+            Ok(MultiTurnStreamItem::final_response(
+                "All good",
+                Usage::new(),
+            )),
+        ]);
+        assert_eq!(
+            consume(items, later()).await,
+            Ok(AgentRun::Finished("All good".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_consume_stream_stops_at_iteration_limit() {
+        let max_depth = Err(StreamingError::Prompt(Box::new(
+            PromptError::MaxDepthError {
+                max_depth: 10,
+                chat_history: Box::new(vec![]),
+                prompt: Box::new(Message::user("again")),
+            },
+        )));
+        let items = stream::iter(vec![text("Still looking"), max_depth]);
+
+        match consume(items, later()).await {
+            Ok(AgentRun::Stopped { reason, partial }) => {
+                assert!(reason.contains("10 steps"));
+                assert_eq!(partial, "Still looking");
+            }
+            other => panic!("expected a stopped run, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_consume_stream_stops_at_deadline() {
+        let items = stream::iter(vec![text("Working")]).chain(stream::pending());
+        match consume(
+            items,
+            tokio::time::Instant::now() + std::time::Duration::from_millis(20),
+        )
+        .await
+        {
+            Ok(AgentRun::Stopped { reason, partial }) => {
+                assert!(reason.contains("time limit"));
+                assert_eq!(partial, "Working");
+            }
+            other => panic!("expected a stopped run, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_consume_stream_stops_when_cancelled() {
+        // A model call that never returns, like a slow local model
+        let items = stream::iter(vec![text("Thinking")]).chain(stream::pending());
+        let cancel = CancellationToken::new();
+        let trigger = cancel.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            trigger.cancel();
+        });
+
+        let run = tokio::time::timeout(
+            std::time::Duration::from_secs(5),
+            consume_with(items, later(), &cancel),
+        )
+        .await
+        .expect("cancelling should end the run promptly");
+        assert_eq!(
+            run,
+            Ok(AgentRun::Cancelled {
+                partial: "Thinking".to_string()
+            })
+        );
+    }
 }
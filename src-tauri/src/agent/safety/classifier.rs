@@ -26,6 +26,13 @@ impl DangerLevel {
         matches!(self, DangerLevel::Dangerous | DangerLevel::Critical)
     }
 
+    /// Whether a command at this level may run without asking when the user
+    /// auto-approves commands up to `auto_approve_level`. Dangerous and critical
+    /// commands always ask, whatever the setting.
+    pub fn is_auto_approved(&self, auto_approve_level: DangerLevel) -> bool {
+        !self.requires_confirmation() && *self <= auto_approve_level
+    }
+
     /// Get a description of the danger level
     pub fn description(&self) -> &'static str {
         match self {
@@ -48,6 +55,89 @@ impl std::fmt::Display for DangerLevel {
     }
 }
 
+impl std::str::FromStr for DangerLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "safe" => Ok(DangerLevel::Safe),
+            "moderate" => Ok(DangerLevel::Moderate),
+            "dangerous" => Ok(DangerLevel::Dangerous),
+            "critical" => Ok(DangerLevel::Critical),
+            other => Err(format!("Unknown danger level: {}", other)),
+        }
+    }
+}
+
+/// User-defined danger rule, stored in the database
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DangerRule {
+    /// Rule ID (generated when the rule is added)
+    #[serde(default)]
+    pub id: String,
+    /// Literal substring, or a regular expression when `is_regex` is set
+    pub pattern: String,
+    /// Whether `pattern` is a regular expression
+    #[serde(default)]
+    pub is_regex: bool,
+    /// Danger level applied when the rule matches
+    pub level: DangerLevel,
+    /// Warning shown to the user when the rule matches
+    pub warning: String,
+}
+
+impl DangerRule {
+    /// Check that the pattern is non-empty and, for regex rules, compiles
+    pub fn validate(&self) -> Result<(), String> {
+        if self.pattern.trim().is_empty() {
+            return Err("Pattern must not be empty".to_string());
+        }
+        if self.is_regex {
+            Regex::new(&self.pattern).map_err(|e| format!("Invalid regex: {}", e))?;
+        }
+        Ok(())
+    }
+}
+
+/// Compiled form of a `DangerRule`
+struct UserRule {
+    matcher: RuleMatcher,
+    pattern: String,
+    level: DangerLevel,
+    warning: String,
+}
+
+enum RuleMatcher {
+    Literal(String),
+    Regex(Regex),
+}
+
+impl UserRule {
+    fn compile(rule: &DangerRule) -> Result<Self, String> {
+        rule.validate()?;
+        let matcher = if rule.is_regex {
+            RuleMatcher::Regex(Regex::new(&rule.pattern).map_err(|e| e.to_string())?)
+        } else {
+            RuleMatcher::Literal(rule.pattern.clone())
+        };
+
+        Ok(Self {
+            matcher,
+            pattern: rule.pattern.clone(),
+            level: rule.level,
+            warning: rule.warning.clone(),
+        })
+    }
+
+    fn matches(&self, command: &str) -> bool {
+        match &self.matcher {
+            RuleMatcher::Literal(literal) => command.contains(literal.as_str()),
+            RuleMatcher::Regex(regex) => regex.is_match(command),
+        }
+    }
+}
+
 /// Result of command danger classification
 #[derive(Debug, Clone)]
 pub struct DangerClassification {
@@ -200,6 +290,16 @@ static DANGEROUS_PATTERNS: Lazy<Vec<DangerPattern>> = Lazy::new(|| {
             "Force container removal",
             DangerLevel::Dangerous,
         ),
+        DangerPattern::new(
+            r"nerdctl\s+rm\s+(-[a-zA-Z]*f[a-zA-Z]*)",
+            "Force container removal",
+            DangerLevel::Dangerous,
+        ),
+        DangerPattern::new(
+            r"container\s+remove\s+--force",
+            "Force container removal",
+            DangerLevel::Dangerous,
+        ),
         DangerPattern::new(
             r"git\s+push\s+.*--force",
             "Force git push",
@@ -351,6 +451,8 @@ static MODERATE_PATTERNS: Lazy<Vec<DangerPattern>> = Lazy::new(|| {
 pub struct DangerClassifier {
     /// Additional user-defined patterns
     custom_patterns: Vec<DangerPattern>,
+    /// Rules loaded from the database
+    user_rules: Vec<UserRule>,
 }
 
 impl Default for DangerClassifier {
@@ -364,7 +466,30 @@ impl DangerClassifier {
     pub fn new() -> Self {
         Self {
             custom_patterns: Vec::new(),
+            user_rules: Vec::new(),
+        }
+    }
+
+    /// Create a classifier that also consults the given user rules
+    ///
+    /// Invalid rules are skipped with a warning.
+    pub fn with_rules(rules: &[DangerRule]) -> Self {
+        let mut classifier = Self::new();
+        for rule in rules {
+            if let Err(e) = classifier.add_rule(rule) {
+                tracing::warn!("Skipping danger rule '{}': {}", rule.pattern, e);
+            }
         }
+        classifier
+    }
+
+    /// Add a user rule
+    ///
+    /// Literal rules match as a case-sensitive substring of the command; regex
+    /// rules match the command as written. Rules can only raise the level.
+    pub fn add_rule(&mut self, rule: &DangerRule) -> Result<(), String> {
+        self.user_rules.push(UserRule::compile(rule)?);
+        Ok(())
     }
 
     /// Add a custom pattern
@@ -378,8 +503,53 @@ impl DangerClassifier {
         }
     }
 
-    /// Classify a command's danger level
+    /// Classify a command's danger level. Chained and piped commands are also
+    /// checked segment by segment so a harmless prefix can't hide a dangerous
+    /// command, and the highest level found wins.
     pub fn classify(&self, command: &str) -> DangerClassification {
+        let mut highest_level = DangerLevel::Safe;
+        let mut matched_patterns: Vec<String> = Vec::new();
+        let mut explanations: Vec<&str> = Vec::new();
+
+        // The whole line still matters for patterns that span a pipe
+        let mut parts = vec![command.trim().to_string()];
+        for segment in split_segments(command) {
+            if !parts.contains(&segment) {
+                parts.push(segment);
+            }
+        }
+
+        for part in &parts {
+            let (level, patterns, reasons) = self.classify_segment(part);
+            highest_level = highest_level.max(level);
+            for pattern in patterns {
+                if !matched_patterns.contains(&pattern) {
+                    matched_patterns.push(pattern);
+                }
+            }
+            for reason in reasons {
+                if !explanations.contains(&reason) {
+                    explanations.push(reason);
+                }
+            }
+        }
+
+        // What a substitution runs isn't known until the shell expands it
+        if has_command_substitution(command) {
+            highest_level = highest_level.max(DangerLevel::Moderate);
+            explanations.push("Command substitution (effect can't be checked in advance)");
+        }
+
+        DangerClassification {
+            level: highest_level,
+            explanation: explanations.join("; "),
+            matched_patterns,
+            affected_resources: extract_resources(command),
+        }
+    }
+
+    /// Match a single command (or the whole line) against every pattern and rule
+    fn classify_segment(&self, command: &str) -> (DangerLevel, Vec<String>, Vec<&str>) {
         let command_lower = command.to_lowercase();
         let mut matched_patterns = Vec::new();
         let mut highest_level = DangerLevel::Safe;
@@ -433,24 +603,216 @@ impl DangerClassifier {
             }
         }
 
-        DangerClassification {
-            level: highest_level,
-            explanation: explanations.join("; "),
-            matched_patterns,
-            affected_resources: extract_resources(command),
+        // Check user rules against the original command
+        for rule in &self.user_rules {
+            if rule.matches(command) {
+                if rule.level > highest_level {
+                    highest_level = rule.level;
+                }
+                matched_patterns.push(rule.pattern.clone());
+                explanations.push(rule.warning.as_str());
+            }
         }
+
+        (highest_level, matched_patterns, explanations)
     }
 }
 
-/// Extract file paths and other resources from a command
+/// Split a command line into the raw text of each simple command, breaking on
+/// `;`, `&&`, `||`, `|`, `&` and newlines outside of quotes
+fn split_segments(command: &str) -> Vec<String> {
+    let mut segments = Vec::new();
+    let mut current = String::new();
+    let mut quote: Option<char> = None;
+
+    for c in command.chars() {
+        match quote {
+            Some(q) => {
+                if c == q {
+                    quote = None;
+                }
+                current.push(c);
+            }
+            None => match c {
+                '\'' | '"' => {
+                    quote = Some(c);
+                    current.push(c);
+                }
+                ';' | '|' | '&' | '\n' => {
+                    let segment = current.trim();
+                    if !segment.is_empty() {
+                        segments.push(segment.to_string());
+                    }
+                    current.clear();
+                }
+                c => current.push(c),
+            },
+        }
+    }
+
+    let segment = current.trim();
+    if !segment.is_empty() {
+        segments.push(segment.to_string());
+    }
+    segments
+}
+
+/// Whether the command uses `$(...)` or backticks outside of single quotes
+fn has_command_substitution(command: &str) -> bool {
+    let mut quote: Option<char> = None;
+    let mut prev = None;
+
+    for c in command.chars() {
+        match (quote, c) {
+            (Some('\''), '\'') => quote = None,
+            (Some('\''), _) => {}
+            (Some('"'), '"') => quote = None,
+            (None, '\'' | '"') => quote = Some(c),
+            (_, '`') => return true,
+            (_, '(') if prev == Some('$') => return true,
+            _ => {}
+        }
+        prev = Some(c);
+    }
+    false
+}
+
+/// Words that look like file paths: absolute, relative, home or Windows paths
+static PATH_WORD: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:/\S+|\.+/\S+|~\S*|[a-zA-Z]:\\\S+)$").expect("Invalid path regex")
+});
+
+/// Extract what a command would act on: the operands of `rm`, the containers,
+/// images, volumes and networks named to `docker`/`podman` removals, and any
+/// file paths in other commands
 fn extract_resources(command: &str) -> Vec<String> {
-    let path_pattern = Regex::new(r"(?:^|\s)(/[^\s]+|\.+/[^\s]+|~[^\s]*|[a-zA-Z]:\\[^\s]+)")
-        .expect("Invalid path regex");
+    let mut resources: Vec<String> = Vec::new();
 
-    path_pattern
-        .captures_iter(command)
-        .filter_map(|cap| cap.get(1).map(|m| m.as_str().to_string()))
-        .collect()
+    for words in split_commands(command) {
+        let targets = command_targets(&words).unwrap_or_else(|| {
+            words
+                .iter()
+                .filter(|w| PATH_WORD.is_match(w))
+                .cloned()
+                .collect()
+        });
+        for target in targets {
+            if !resources.contains(&target) {
+                resources.push(target);
+            }
+        }
+    }
+
+    resources
+}
+
+/// Split a command line into simple commands, each a list of words. Quotes
+/// group words and are removed; `;`, `|` and `&` separate commands.
+fn split_commands(command: &str) -> Vec<Vec<String>> {
+    let mut commands = Vec::new();
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut quote: Option<char> = None;
+
+    for c in command.chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => word.push(c),
+            None => match c {
+                '\'' | '"' => {
+                    quote = Some(c);
+                    in_word = true;
+                }
+                ';' | '|' | '&' => {
+                    if in_word {
+                        words.push(std::mem::take(&mut word));
+                        in_word = false;
+                    }
+                    if !words.is_empty() {
+                        commands.push(std::mem::take(&mut words));
+                    }
+                }
+                c if c.is_whitespace() => {
+                    if in_word {
+                        words.push(std::mem::take(&mut word));
+                        in_word = false;
+                    }
+                }
+                c => {
+                    word.push(c);
+                    in_word = true;
+                }
+            },
+        }
+    }
+
+    if in_word {
+        words.push(word);
+    }
+    if !words.is_empty() {
+        commands.push(words);
+    }
+    commands
+}
+
+/// Targets of a simple command whose verb we know how to read, or `None` to
+/// fall back to picking out paths
+fn command_targets(words: &[String]) -> Option<Vec<String>> {
+    // Look past sudo and leading environment assignments to the real program
+    let start = words
+        .iter()
+        .position(|w| (w.starts_with('-') || !w.contains('=')) && w != "sudo")?;
+    let (program, args) = words[start..].split_first()?;
+
+    match program.rsplit('/').next().unwrap_or(program) {
+        "rm" | "rmdir" | "shred" | "unlink" => Some(operands(args)),
+        "docker" | "podman" | "nerdctl" => container_targets(args),
+        _ => None,
+    }
+}
+
+/// Names passed to a container CLI removal: `rm`, `rmi`, or `rm`/`remove`
+/// under `container`, `image`, `volume`, `network` or `pod`
+fn container_targets(args: &[String]) -> Option<Vec<String>> {
+    let verb = args.iter().position(|a| !a.starts_with('-'))?;
+    let rest = &args[verb + 1..];
+
+    match args[verb].as_str() {
+        "rm" | "rmi" => Some(operands(rest)),
+        "container" | "image" | "volume" | "network" | "pod" => {
+            let sub = rest.iter().position(|a| !a.starts_with('-'))?;
+            match rest[sub].as_str() {
+                "rm" | "remove" => Some(operands(&rest[sub + 1..])),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Non-option arguments, skipping redirections; everything after `--` counts
+fn operands(args: &[String]) -> Vec<String> {
+    let mut operands = Vec::new();
+    let mut options_done = false;
+    let mut skip_next = false;
+
+    for arg in args {
+        if skip_next {
+            skip_next = false;
+        } else if options_done {
+            operands.push(arg.clone());
+        } else if arg == "--" {
+            options_done = true;
+        } else if arg.contains('>') || arg.starts_with('<') {
+            // `> file` names the redirect target in the next word
+            skip_next = arg.ends_with('>') || arg == "<";
+        } else if !arg.starts_with('-') || arg == "-" {
+            operands.push(arg.clone());
+        }
+    }
+
+    operands
 }
 
 #[cfg(test)]
@@ -483,6 +845,9 @@ mod tests {
             "sudo apt update",
             "git push --force",
             "chmod -R 777 /var",
+            "docker rm -f web db cache",
+            "nerdctl rm -f web db",
+            "container remove --force web db",
         ];
 
         for cmd in dangerous_commands {
@@ -532,6 +897,17 @@ mod tests {
         assert!(DangerLevel::Critical.requires_confirmation());
     }
 
+    #[test]
+    fn test_danger_level_is_auto_approved() {
+        assert!(DangerLevel::Safe.is_auto_approved(DangerLevel::Safe));
+        assert!(!DangerLevel::Moderate.is_auto_approved(DangerLevel::Safe));
+        assert!(DangerLevel::Moderate.is_auto_approved(DangerLevel::Moderate));
+
+        // Dangerous commands confirm even when the setting would allow them
+        assert!(!DangerLevel::Dangerous.is_auto_approved(DangerLevel::Critical));
+        assert!(!DangerLevel::Critical.is_auto_approved(DangerLevel::Critical));
+    }
+
     #[test]
     fn test_danger_level_description() {
         assert_eq!(DangerLevel::Safe.description(), "Safe to execute");
@@ -631,6 +1007,9 @@ mod tests {
         let classifier = DangerClassifier::new();
         let result = classifier.classify("rm -rf /tmp/test");
         assert!(result.affected_resources.contains(&"/tmp/test".to_string()));
+
+        let result = classifier.classify("docker volume rm pgdata");
+        assert_eq!(result.affected_resources, vec!["pgdata"]);
     }
 
     #[test]
@@ -660,6 +1039,77 @@ mod tests {
         assert_eq!(result.level, DangerLevel::Critical);
     }
 
+    fn make_rule(pattern: &str, is_regex: bool, level: DangerLevel) -> DangerRule {
+        DangerRule {
+            id: "rule-1".to_string(),
+            pattern: pattern.to_string(),
+            is_regex,
+            level,
+            warning: "Wipes staging".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_user_rule_literal() {
+        let classifier =
+            DangerClassifier::with_rules(&[make_rule("wipe-staging", false, DangerLevel::Dangerous)]);
+
+        let result = classifier.classify("./bin/wipe-staging --all");
+        assert_eq!(result.level, DangerLevel::Dangerous);
+        assert!(result.explanation.contains("Wipes staging"));
+        assert_eq!(classifier.classify("ls").level, DangerLevel::Safe);
+    }
+
+    #[test]
+    fn test_user_rule_regex() {
+        let classifier = DangerClassifier::with_rules(&[make_rule(
+            r"^deploy\s+--env\s+prod",
+            true,
+            DangerLevel::Critical,
+        )]);
+
+        assert_eq!(
+            classifier.classify("deploy --env prod").level,
+            DangerLevel::Critical
+        );
+        assert_eq!(
+            classifier.classify("deploy --env dev").level,
+            DangerLevel::Safe
+        );
+    }
+
+    #[test]
+    fn test_user_rule_cannot_lower_level() {
+        let classifier =
+            DangerClassifier::with_rules(&[make_rule("rm -rf", false, DangerLevel::Safe)]);
+
+        let result = classifier.classify("rm -rf ./build");
+        assert_eq!(result.level, DangerLevel::Dangerous);
+    }
+
+    #[test]
+    fn test_invalid_user_rule_rejected() {
+        assert!(make_rule("(unclosed", true, DangerLevel::Dangerous).validate().is_err());
+        assert!(make_rule("  ", false, DangerLevel::Dangerous).validate().is_err());
+
+        let classifier =
+            DangerClassifier::with_rules(&[make_rule("(unclosed", true, DangerLevel::Dangerous)]);
+        assert_eq!(classifier.classify("(unclosed").level, DangerLevel::Safe);
+    }
+
+    #[test]
+    fn test_danger_level_from_str() {
+        for level in [
+            DangerLevel::Safe,
+            DangerLevel::Moderate,
+            DangerLevel::Dangerous,
+            DangerLevel::Critical,
+        ] {
+            assert_eq!(level.to_string().parse::<DangerLevel>().unwrap(), level);
+        }
+        assert!("extreme".parse::<DangerLevel>().is_err());
+    }
+
     #[test]
     fn test_empty_command() {
         let classifier = DangerClassifier::new();
@@ -684,4 +1134,94 @@ mod tests {
         let resources = extract_resources("ls");
         assert!(resources.is_empty());
     }
+
+    #[test]
+    fn test_extract_resources_docker_rm() {
+        assert_eq!(extract_resources("docker rm a b c"), vec!["a", "b", "c"]);
+        assert_eq!(extract_resources("sudo podman rm -f web db"), vec!["web", "db"]);
+        assert_eq!(extract_resources("docker container rm -v cache"), vec!["cache"]);
+    }
+
+    #[test]
+    fn test_extract_resources_rm_paths() {
+        assert_eq!(extract_resources("rm -rf /tmp/x /tmp/y"), vec!["/tmp/x", "/tmp/y"]);
+        assert_eq!(extract_resources("rm -rf /"), vec!["/"]);
+        assert_eq!(extract_resources("rm \"my file.txt\" -- -odd"), vec!["my file.txt", "-odd"]);
+        assert_eq!(extract_resources("rm notes.txt 2> /dev/null"), vec!["notes.txt"]);
+    }
+
+    #[test]
+    fn test_extract_resources_images_and_volumes() {
+        assert_eq!(extract_resources("docker rmi nginx:latest redis"), vec!["nginx:latest", "redis"]);
+        assert_eq!(extract_resources("docker image rm --force alpine"), vec!["alpine"]);
+        assert_eq!(extract_resources("docker volume rm pgdata"), vec!["pgdata"]);
+        assert_eq!(extract_resources("podman network remove backend"), vec!["backend"]);
+    }
+
+    #[test]
+    fn test_extract_resources_chained_commands() {
+        assert_eq!(
+            extract_resources("docker stop web && docker rm web; rm -rf /srv/web"),
+            vec!["web", "/srv/web"]
+        );
+        // Other container subcommands fall back to paths
+        assert_eq!(extract_resources("docker cp web:/etc/nginx ./nginx"), vec!["./nginx"]);
+    }
+
+    #[test]
+    fn test_chained_dangerous_commands_are_not_safe() {
+        let classifier = DangerClassifier::new();
+
+        let result = classifier.classify("rm -rf / && echo done");
+        assert_eq!(result.level, DangerLevel::Critical);
+
+        let result = classifier.classify("true && rm -rf /");
+        assert_eq!(result.level, DangerLevel::Critical);
+
+        let result = classifier.classify("echo hi; sudo rm -rf /var");
+        assert_eq!(result.level, DangerLevel::Dangerous);
+
+        let result = classifier.classify("ls || rm -rf ~");
+        assert_eq!(result.level, DangerLevel::Critical);
+
+        let result = classifier.classify("cat list.txt | xargs rm -rf");
+        assert_eq!(result.level, DangerLevel::Dangerous);
+    }
+
+    #[test]
+    fn test_chained_safe_commands_stay_safe() {
+        let classifier = DangerClassifier::new();
+        let result = classifier.classify("docker ps -a | grep web && echo ok");
+        assert_eq!(result.level, DangerLevel::Safe);
+        // Separators inside quotes don't split the command
+        let result = classifier.classify("echo 'a; rm -rf /'");
+        assert_ne!(result.level, DangerLevel::Critical);
+    }
+
+    #[test]
+    fn test_command_substitution_is_at_least_moderate() {
+        let classifier = DangerClassifier::new();
+
+        let result = classifier.classify("echo $(whoami)");
+        assert_eq!(result.level, DangerLevel::Moderate);
+
+        let result = classifier.classify("echo `hostname`");
+        assert_eq!(result.level, DangerLevel::Moderate);
+
+        let result = classifier.classify("echo $(rm -rf /srv/data)");
+        assert_eq!(result.level, DangerLevel::Dangerous);
+
+        // Single quotes keep it literal
+        let result = classifier.classify("echo '$(whoami)'");
+        assert_eq!(result.level, DangerLevel::Safe);
+    }
+
+    #[test]
+    fn test_split_segments() {
+        assert_eq!(
+            split_segments("a && b || c | d; e & f"),
+            vec!["a", "b", "c", "d", "e", "f"]
+        );
+        assert_eq!(split_segments("echo 'x; y' && z"), vec!["echo 'x; y'", "z"]);
+    }
 }
@@ -4,4 +4,4 @@
 
 mod classifier;
 
-pub use classifier::{DangerClassification, DangerClassifier, DangerLevel};
+pub use classifier::{DangerClassification, DangerClassifier, DangerLevel, DangerRule};
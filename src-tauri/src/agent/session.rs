@@ -7,12 +7,14 @@ use std::sync::atomic::{AtomicI64, Ordering};
 use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
-use tokio::sync::{mpsc, RwLock};
+use tokio::sync::{mpsc, Mutex, RwLock};
+use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
 use super::events::{AgentEvent, CommandAlternative};
 use super::safety::DangerLevel;
 use super::summarizer::InputSummary;
+use crate::commands::terminal::strip_ansi;
 
 /// Maximum number of messages to retain in conversation history
 const MAX_HISTORY_SIZE: usize = 50;
@@ -29,6 +31,25 @@ const MAX_INPUT_SUMMARIES: usize = 20;
 /// Maximum conversation turns to retain
 const MAX_CONVERSATION_TURNS: usize = 10;
 
+/// Reduce captured terminal output to the text a user would read: ANSI/VT
+/// escape sequences and control characters are dropped, and a line rewritten
+/// with carriage returns (progress bars) keeps only its final state
+pub fn clean_terminal_output(raw: &str) -> String {
+    strip_ansi(raw)
+        .split('\n')
+        .map(|line| {
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            line.rsplit('\r')
+                .find(|segment| !segment.is_empty())
+                .unwrap_or("")
+                .chars()
+                .filter(|c| *c == '\t' || !c.is_control())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 /// A tool call within a conversation turn
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -177,12 +198,12 @@ impl TerminalContext {
         }
     }
 
-    /// Append output line to recent output buffer
+    /// Append output line to recent output buffer, without escape sequences
     pub fn append_output(&mut self, line: &str) {
         if self.recent_output.len() >= MAX_RECENT_OUTPUT_LINES {
             self.recent_output.pop_front();
         }
-        self.recent_output.push_back(line.to_string());
+        self.recent_output.push_back(clean_terminal_output(line));
     }
 
     /// Get recent output as a single string
@@ -197,12 +218,15 @@ impl TerminalContext {
             .join("\n")
     }
 
-    /// Add a command execution result to the history
+    /// Add a command execution result to the history, without escape sequences
     pub fn add_command_result(&mut self, entry: CommandHistoryEntry) {
         if self.command_history.len() >= MAX_COMMAND_HISTORY {
             self.command_history.pop_front();
         }
-        self.command_history.push_back(entry);
+        self.command_history.push_back(CommandHistoryEntry {
+            output: clean_terminal_output(&entry.output),
+            ..entry
+        });
     }
 
     /// Get command history entries (most recent last)
@@ -415,6 +439,43 @@ pub struct PendingConfirmation {
     pub expires_at: i64,
 }
 
+impl PendingConfirmation {
+    /// Build the pending confirmation announced by a `ConfirmationRequired` event
+    pub fn from_event(event: &AgentEvent) -> Option<Self> {
+        match event {
+            AgentEvent::ConfirmationRequired {
+                query_id,
+                confirmation_id,
+                command,
+                explanation,
+                risk_level,
+                affected_resources,
+                warning,
+                alternatives,
+                expires_at,
+                ..
+            } => Some(Self {
+                id: confirmation_id.clone(),
+                query_id: query_id.clone(),
+                command: command.clone(),
+                explanation: explanation.clone(),
+                danger_level: risk_level.parse().unwrap_or(DangerLevel::Dangerous),
+                affected_resources: affected_resources.clone(),
+                warning: warning.clone(),
+                alternatives: alternatives.clone(),
+                created_at: chrono::Utc::now().timestamp_millis(),
+                expires_at: *expires_at,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Whether the confirmation can no longer be answered at `now` (Unix millis)
+    pub fn is_expired(&self, now: i64) -> bool {
+        now >= self.expires_at
+    }
+}
+
 /// Agent session state
 #[derive(Debug, Clone)]
 pub struct AgentSession {
@@ -556,13 +617,19 @@ impl AgentSession {
     }
 }
 
+/// Receiving end of a session's confirmation answers (true = approved)
+pub type ConfirmationReceiver = Arc<Mutex<mpsc::Receiver<bool>>>;
+
 /// Internal state for a session including channels
 struct SessionState {
     session: AgentSession,
     context: Arc<RwLock<TerminalContext>>,
     event_tx: mpsc::Sender<AgentEvent>,
     confirmation_tx: mpsc::Sender<bool>,
-    cancel_tx: mpsc::Sender<()>,
+    /// Handed to each query's shell tool, which waits on it for answers
+    confirmation_rx: ConfirmationReceiver,
+    /// Cancels the query currently running on this session
+    cancel_token: CancellationToken,
 }
 
 /// Manages all active agent sessions
@@ -591,18 +658,12 @@ impl AgentSessionManager {
     pub async fn create_session(
         &self,
         terminal_session_id: String,
-    ) -> (
-        AgentSession,
-        mpsc::Receiver<AgentEvent>,
-        mpsc::Receiver<bool>,
-        mpsc::Receiver<()>,
-    ) {
+    ) -> (AgentSession, mpsc::Receiver<AgentEvent>) {
         let session = AgentSession::new(terminal_session_id.clone());
         let session_id = session.id.clone();
 
         let (event_tx, event_rx) = mpsc::channel(256);
         let (confirmation_tx, confirmation_rx) = mpsc::channel(1);
-        let (cancel_tx, cancel_rx) = mpsc::channel(1);
 
         // Create shared context for the agentic loop
         let context = Arc::new(RwLock::new(session.terminal_context.clone()));
@@ -612,7 +673,8 @@ impl AgentSessionManager {
             context,
             event_tx,
             confirmation_tx,
-            cancel_tx,
+            confirmation_rx: Arc::new(Mutex::new(confirmation_rx)),
+            cancel_token: CancellationToken::new(),
         };
 
         self.sessions.write().await.insert(session_id.clone(), state);
@@ -621,7 +683,7 @@ impl AgentSessionManager {
             .await
             .insert(terminal_session_id, session_id);
 
-        (session, event_rx, confirmation_rx, cancel_rx)
+        (session, event_rx)
     }
 
     /// Get a session by ID
@@ -674,25 +736,88 @@ impl AgentSessionManager {
         }
     }
 
-    /// Send a confirmation response
-    pub async fn send_confirmation(&self, session_id: &str, confirmed: bool) -> Result<(), String> {
-        let sessions = self.sessions.read().await;
-        if let Some(state) = sessions.get(session_id) {
-            state
-                .confirmation_tx
-                .send(confirmed)
-                .await
-                .map_err(|e| e.to_string())
-        } else {
-            Err("Session not found".to_string())
+    /// Answer the confirmation a session is waiting on
+    ///
+    /// Fails if `confirmation_id` isn't the pending confirmation, or if it has
+    /// expired: its command was already cancelled and must not run this late.
+    pub async fn send_confirmation(
+        &self,
+        session_id: &str,
+        confirmation_id: &str,
+        confirmed: bool,
+    ) -> Result<(), String> {
+        let mut sessions = self.sessions.write().await;
+        let state = sessions
+            .get_mut(session_id)
+            .ok_or_else(|| "Session not found".to_string())?;
+
+        match state.session.pending_confirmation.take() {
+            Some(pending) if pending.id == confirmation_id => {
+                if pending.is_expired(chrono::Utc::now().timestamp_millis()) {
+                    return Err(format!("Confirmation {} has expired", confirmation_id));
+                }
+                state
+                    .confirmation_tx
+                    .try_send(confirmed)
+                    .map_err(|e| e.to_string())
+            }
+            other => {
+                state.session.pending_confirmation = other;
+                Err(format!("No pending confirmation {}", confirmation_id))
+            }
         }
     }
 
-    /// Send a cancel signal
+    /// Keep a session's pending confirmation in step with the events its
+    /// queries emit: set when one is required, cleared when it expires
+    pub async fn track_confirmation(&self, session_id: &str, event: &AgentEvent) {
+        let mut sessions = self.sessions.write().await;
+        let Some(state) = sessions.get_mut(session_id) else {
+            return;
+        };
+
+        match event {
+            AgentEvent::ConfirmationRequired { .. } => {
+                if let Some(pending) = PendingConfirmation::from_event(event) {
+                    state.session.set_pending_confirmation(pending);
+                }
+            }
+            AgentEvent::ConfirmationExpired { confirmation_id, .. }
+                if state
+                    .session
+                    .pending_confirmation
+                    .as_ref()
+                    .is_some_and(|p| &p.id == confirmation_id) =>
+            {
+                state.session.clear_pending_confirmation();
+            }
+            _ => {}
+        }
+    }
+
+    /// Get the receiver a session's shell tool waits on for confirmation answers
+    pub async fn get_confirmation_receiver(&self, session_id: &str) -> Option<ConfirmationReceiver> {
+        self.sessions
+            .read()
+            .await
+            .get(session_id)
+            .map(|s| s.confirmation_rx.clone())
+    }
+
+    /// Start a query on a session, returning the token that cancels it
+    pub async fn begin_query(&self, session_id: &str) -> Option<CancellationToken> {
+        let mut sessions = self.sessions.write().await;
+        let state = sessions.get_mut(session_id)?;
+        state.cancel_token = CancellationToken::new();
+        Some(state.cancel_token.clone())
+    }
+
+    /// Cancel the query running on a session, aborting any model call in flight
     pub async fn cancel_session(&self, session_id: &str) -> Result<(), String> {
         let sessions = self.sessions.read().await;
         if let Some(state) = sessions.get(session_id) {
-            state.cancel_tx.send(()).await.map_err(|e| e.to_string())
+            state.cancel_token.cancel();
+            Ok(())
         } else {
             Err("Session not found".to_string())
         }
@@ -751,6 +876,64 @@ impl AgentSessionManager {
             .map(|s| s.event_tx.clone())
     }
 
+    /// Load previously persisted conversation history into a session
+    pub async fn restore_history(
+        &self,
+        session_id: &str,
+        messages: Vec<ConversationMessage>,
+        turns: Vec<ConversationTurn>,
+    ) -> Result<(), String> {
+        let mut sessions = self.sessions.write().await;
+        let state = sessions
+            .get_mut(session_id)
+            .ok_or_else(|| "Session not found".to_string())?;
+
+        for message in messages {
+            state.session.add_message(message);
+        }
+
+        let mut ctx = state.context.write().await;
+        for turn in turns {
+            ctx.add_conversation_turn(turn);
+        }
+        Ok(())
+    }
+
+    /// Record a finished query as user/assistant messages in the session history
+    pub async fn record_exchange(
+        &self,
+        session_id: &str,
+        user_input: String,
+        response: Option<String>,
+    ) -> Result<Vec<ConversationMessage>, String> {
+        let mut sessions = self.sessions.write().await;
+        let state = sessions
+            .get_mut(session_id)
+            .ok_or_else(|| "Session not found".to_string())?;
+
+        let mut recorded = vec![state.session.create_user_message(user_input)];
+        if let Some(response) = response {
+            recorded.push(state.session.create_assistant_message(response, None));
+        }
+        Ok(recorded)
+    }
+
+    /// Clear the conversation history of a session
+    pub async fn clear_history(&self, session_id: &str) -> Result<(), String> {
+        let mut sessions = self.sessions.write().await;
+        let state = sessions
+            .get_mut(session_id)
+            .ok_or_else(|| "Session not found".to_string())?;
+
+        state.session.history.clear();
+        state.session.input_summaries.clear();
+
+        let mut ctx = state.context.write().await;
+        ctx.conversation_turns.clear();
+        ctx.input_summaries.clear();
+        Ok(())
+    }
+
     /// Get the shared context for a session (for use in agentic loop)
     pub async fn get_context(
         &self,
@@ -1266,8 +1449,7 @@ mod tests {
     #[tokio::test]
     async fn test_session_manager_create_session() {
         let manager = AgentSessionManager::new();
-        let (session, _events_rx, _confirm_rx, _cancel_rx) =
-            manager.create_session("term-1".to_string()).await;
+        let (session, _events_rx) = manager.create_session("term-1".to_string()).await;
 
         assert_eq!(session.terminal_session_id, "term-1");
         assert!(!session.id.is_empty());
@@ -1279,8 +1461,7 @@ mod tests {
     #[tokio::test]
     async fn test_session_manager_get_by_terminal() {
         let manager = AgentSessionManager::new();
-        let (session, _events_rx, _confirm_rx, _cancel_rx) =
-            manager.create_session("term-1".to_string()).await;
+        let (session, _events_rx) = manager.create_session("term-1".to_string()).await;
 
         let by_terminal = manager.get_session_by_terminal("term-1").await;
         assert!(by_terminal.is_some());
@@ -1290,8 +1471,7 @@ mod tests {
     #[tokio::test]
     async fn test_session_manager_remove_session() {
         let manager = AgentSessionManager::new();
-        let (session, _events_rx, _confirm_rx, _cancel_rx) =
-            manager.create_session("term-1".to_string()).await;
+        let (session, _events_rx) = manager.create_session("term-1".to_string()).await;
 
         manager.remove_session(&session.id).await;
 
@@ -1302,8 +1482,7 @@ mod tests {
     #[tokio::test]
     async fn test_session_manager_update_session() {
         let manager = AgentSessionManager::new();
-        let (mut session, _events_rx, _confirm_rx, _cancel_rx) =
-            manager.create_session("term-1".to_string()).await;
+        let (mut session, _events_rx) = manager.create_session("term-1".to_string()).await;
 
         session.create_user_message("hello".to_string());
         manager.update_session(session.clone()).await.unwrap();
@@ -1312,11 +1491,25 @@ mod tests {
         assert_eq!(updated.history.len(), 1);
     }
 
+    #[tokio::test]
+    async fn test_session_manager_cancel_query() {
+        let manager = AgentSessionManager::new();
+        let (session, _events_rx) = manager.create_session("term-1".to_string()).await;
+
+        let token = manager.begin_query(&session.id).await.unwrap();
+        manager.cancel_session(&session.id).await.unwrap();
+        assert!(token.is_cancelled());
+
+        // The next query starts with a fresh token
+        let next = manager.begin_query(&session.id).await.unwrap();
+        assert!(!next.is_cancelled());
+        assert!(manager.begin_query("missing").await.is_none());
+    }
+
     #[tokio::test]
     async fn test_session_manager_append_output() {
         let manager = AgentSessionManager::new();
-        let (session, _events_rx, _confirm_rx, _cancel_rx) =
-            manager.create_session("term-1".to_string()).await;
+        let (session, _events_rx) = manager.create_session("term-1".to_string()).await;
 
         manager
             .append_output(&session.id, "hello world")
@@ -1328,6 +1521,126 @@ mod tests {
         assert!(ctx_read.recent_output.contains(&"hello world".to_string()));
     }
 
+    #[tokio::test]
+    async fn test_session_manager_restore_and_clear_history() {
+        let manager = AgentSessionManager::new();
+        let (session, _events_rx) = manager.create_session("term-1".to_string()).await;
+
+        let mut previous = AgentSession::new("term-1".to_string());
+        let message = previous.create_user_message("earlier".to_string());
+        manager
+            .restore_history(&session.id, vec![message], vec![make_conversation_turn("earlier")])
+            .await
+            .unwrap();
+
+        assert_eq!(manager.get_session(&session.id).await.unwrap().history.len(), 1);
+        let ctx = manager.get_context(&session.id).await.unwrap();
+        assert_eq!(ctx.read().await.conversation_turns.len(), 1);
+
+        manager.clear_history(&session.id).await.unwrap();
+        assert!(manager.get_session(&session.id).await.unwrap().history.is_empty());
+        assert!(ctx.read().await.conversation_turns.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_session_manager_record_exchange() {
+        let manager = AgentSessionManager::new();
+        let (session, _events_rx) = manager.create_session("term-1".to_string()).await;
+
+        let recorded = manager
+            .record_exchange(&session.id, "list files".to_string(), Some("done".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(recorded.len(), 2);
+        assert!(matches!(recorded[1].role, MessageRole::Assistant));
+
+        let recorded = manager
+            .record_exchange(&session.id, "again".to_string(), None)
+            .await
+            .unwrap();
+        assert_eq!(recorded.len(), 1);
+        assert_eq!(manager.get_session(&session.id).await.unwrap().history.len(), 3);
+    }
+
+    fn confirmation_required(confirmation_id: &str, expires_at: i64) -> AgentEvent {
+        AgentEvent::ConfirmationRequired {
+            session_id: "sess-1".to_string(),
+            query_id: "q-1".to_string(),
+            confirmation_id: confirmation_id.to_string(),
+            command: "rm -rf /tmp/test".to_string(),
+            explanation: "Delete temp directory".to_string(),
+            risk_level: "dangerous".to_string(),
+            affected_resources: vec!["/tmp/test".to_string()],
+            warning: None,
+            alternatives: vec![],
+            expires_at,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_session_manager_approve_confirmation() {
+        let manager = AgentSessionManager::new();
+        let (session, _events_rx) = manager.create_session("term-1".to_string()).await;
+        let expires_at = chrono::Utc::now().timestamp_millis() + 60_000;
+
+        manager
+            .track_confirmation(&session.id, &confirmation_required("conf-1", expires_at))
+            .await;
+        let pending = manager.get_session(&session.id).await.unwrap().pending_confirmation;
+        assert_eq!(pending.unwrap().danger_level, DangerLevel::Dangerous);
+
+        assert!(manager.send_confirmation(&session.id, "other", true).await.is_err());
+        manager.send_confirmation(&session.id, "conf-1", true).await.unwrap();
+
+        let rx = manager.get_confirmation_receiver(&session.id).await.unwrap();
+        assert_eq!(rx.lock().await.try_recv(), Ok(true));
+        assert!(manager.get_session(&session.id).await.unwrap().pending_confirmation.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_session_manager_expired_confirmation_cannot_be_approved() {
+        let manager = AgentSessionManager::new();
+        let (session, _events_rx) = manager.create_session("term-1".to_string()).await;
+        let expired_at = chrono::Utc::now().timestamp_millis() - 1;
+
+        manager
+            .track_confirmation(&session.id, &confirmation_required("conf-1", expired_at))
+            .await;
+        let err = manager.send_confirmation(&session.id, "conf-1", true).await.unwrap_err();
+        assert!(err.contains("expired"));
+
+        // Nothing reaches the waiting tool, and the confirmation is gone
+        let rx = manager.get_confirmation_receiver(&session.id).await.unwrap();
+        assert!(rx.lock().await.try_recv().is_err());
+        assert!(manager.get_session(&session.id).await.unwrap().pending_confirmation.is_none());
+        assert!(manager.send_confirmation(&session.id, "conf-1", true).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_session_manager_confirmation_expired_event_clears_pending() {
+        let manager = AgentSessionManager::new();
+        let (session, _events_rx) = manager.create_session("term-1".to_string()).await;
+        let expires_at = chrono::Utc::now().timestamp_millis() + 60_000;
+
+        manager
+            .track_confirmation(&session.id, &confirmation_required("conf-1", expires_at))
+            .await;
+        manager
+            .track_confirmation(
+                &session.id,
+                &AgentEvent::ConfirmationExpired {
+                    session_id: session.id.clone(),
+                    query_id: "q-1".to_string(),
+                    confirmation_id: "conf-1".to_string(),
+                    command: "rm -rf /tmp/test".to_string(),
+                },
+            )
+            .await;
+
+        assert!(manager.get_session(&session.id).await.unwrap().pending_confirmation.is_none());
+        assert!(manager.send_confirmation(&session.id, "conf-1", true).await.is_err());
+    }
+
     // === Serialization tests ===
 
     #[test]
@@ -1381,4 +1694,25 @@ mod tests {
         assert!(json.contains("\"toolName\""));
         assert!(json.contains("\"argumentsSummary\""));
     }
+
+    #[test]
+    fn test_clean_terminal_output_strips_colors_and_progress() {
+        let raw = "\x1b[1;32mPulling\x1b[0m nginx\r\n\
+                   layer  10%\rlayer  55%\rlayer 100%\r\n\
+                   \x1b]0;user@host: ~\x07done\x1b[K\n";
+        assert_eq!(
+            clean_terminal_output(raw),
+            "Pulling nginx\nlayer 100%\ndone\n"
+        );
+    }
+
+    #[test]
+    fn test_context_stores_cleaned_output() {
+        let mut ctx = TerminalContext::default();
+        ctx.append_output("\x1b[31merror\x1b[0m: failed");
+        ctx.add_command_result(make_command_entry("ls", "\x1b[34mdir\x1b[0m\r\n", 0));
+
+        assert_eq!(ctx.get_recent_output(1), "error: failed");
+        assert_eq!(ctx.command_history[0].output, "dir\n");
+    }
 }
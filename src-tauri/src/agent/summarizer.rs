@@ -4,20 +4,16 @@
 //! for conversation memory. Uses a smaller/cheaper model to compress
 //! potentially large user inputs (like pasted logs) into concise summaries.
 
-use rig::agent::AgentBuilder;
-use rig::client::{CompletionClient, ProviderClient};
-use rig::completion::Prompt;
-use rig::providers::{anthropic, azure, deepseek, gemini, groq, mistral, ollama, openai};
 use serde::{Deserialize, Serialize};
 
-use crate::ai::{AiProviderType, AiSettings};
-
-/// Minimum input length to trigger summarization (shorter inputs stored as-is)
-const MIN_SUMMARIZATION_LENGTH: usize = 100;
+use crate::ai::{create_provider, AiSettings, CompletionRequest};
 
 /// Maximum length for truncated fallback when summarization fails
 const FALLBACK_TRUNCATION_LENGTH: usize = 200;
 
+/// Longest input sent to the summary model; the rest is cut off
+const MAX_SUMMARIZED_INPUT_LENGTH: usize = 10000;
+
 /// Summary of a user input
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -58,8 +54,9 @@ Examples:
 /// Summarize a user input using the configured summary model
 ///
 /// Returns an InputSummary containing the compressed version of the input.
-/// For short inputs (< MIN_SUMMARIZATION_LENGTH chars), returns the input as-is.
-/// Falls back to truncation if summarization fails.
+/// Inputs shorter than `summary_min_input_length` are returned as-is. With
+/// memory disabled, or if summarization fails, the input is truncated instead
+/// so no extra model call holds up the agent.
 pub async fn summarize_user_input(
     settings: &AiSettings,
     user_input: &str,
@@ -68,7 +65,7 @@ pub async fn summarize_user_input(
     let original_length = user_input.len();
 
     // Short inputs don't need summarization
-    if original_length < MIN_SUMMARIZATION_LENGTH {
+    if original_length < settings.summary_min_input_length as usize {
         return Ok(InputSummary {
             summary: user_input.to_string(),
             timestamp: now,
@@ -76,6 +73,14 @@ pub async fn summarize_user_input(
         });
     }
 
+    if !settings.memory_enabled {
+        return Ok(InputSummary {
+            summary: truncate_input(user_input),
+            timestamp: now,
+            original_length,
+        });
+    }
+
     // Attempt to summarize using the LLM
     let summary_result = call_summary_model(settings, user_input).await;
 
@@ -108,138 +113,29 @@ pub async fn summarize_user_input(
     }
 }
 
-/// Call the summary model to compress the input
+/// Call the summary model to compress the input, on its own provider instance
+/// so the main model's settings are left alone
 async fn call_summary_model(settings: &AiSettings, user_input: &str) -> Result<String, String> {
-    let summary_model = settings.get_effective_summary_model();
-
-    // Prepare the prompt - include system instructions in the user prompt
-    // since we're using simple completion without AgentBuilder
-    let prompt = format!(
-        "{}\n\nSummarize this user input in 1-2 sentences:\n\n{}",
-        SUMMARIZER_SYSTEM_PROMPT,
-        // Truncate extremely long inputs to avoid context limits
-        if user_input.len() > 10000 {
-            &user_input[..10000]
-        } else {
-            user_input
-        }
-    );
-
-    match settings.provider {
-        AiProviderType::Anthropic => {
-            let client: anthropic::Client = anthropic::Client::new(
-                &settings.api_key.clone().unwrap_or_default(),
-            )
-            .map_err(|e| format!("Failed to create Anthropic client: {}", e))?;
-
-            let model = client.completion_model(&summary_model);
-            let agent = AgentBuilder::new(model).build();
-
-            agent
-                .prompt(&prompt)
-                .await
-                .map_err(|e| format!("Anthropic summarization failed: {}", e))
-        }
-        AiProviderType::OpenAi => {
-            let client: openai::Client = openai::Client::new(
-                &settings.api_key.clone().unwrap_or_default(),
-            )
-            .map_err(|e| format!("Failed to create OpenAI client: {}", e))?;
-
-            let model = client.completion_model(&summary_model);
-            let agent = AgentBuilder::new(model).build();
-
-            agent
-                .prompt(&prompt)
-                .await
-                .map_err(|e| format!("OpenAI summarization failed: {}", e))
-        }
-        AiProviderType::Ollama => {
-            let client: ollama::Client = ProviderClient::from_env();
-
-            let model = client.completion_model(&summary_model);
-            let agent = AgentBuilder::new(model).build();
-
-            agent
-                .prompt(&prompt)
-                .await
-                .map_err(|e| format!("Ollama summarization failed: {}", e))
-        }
-        AiProviderType::AzureOpenAi => {
-            let api_key = settings.api_key.clone().unwrap_or_default();
-            let api_ver = settings.api_version.as_deref().unwrap_or("2024-10-21");
-            let client = azure::Client::<reqwest::Client>::builder()
-                .api_key(azure::AzureOpenAIAuth::ApiKey(api_key))
-                .azure_endpoint(settings.endpoint_url.clone())
-                .api_version(api_ver)
-                .build()
-                .map_err(|e| format!("Failed to create Azure OpenAI client: {}", e))?;
-
-            let model = client.completion_model(&summary_model);
-            let agent = AgentBuilder::new(model).build();
-
-            agent
-                .prompt(&prompt)
-                .await
-                .map_err(|e| format!("Azure OpenAI summarization failed: {}", e))
-        }
-        AiProviderType::Groq => {
-            let client: groq::Client = groq::Client::new(
-                &settings.api_key.clone().unwrap_or_default(),
-            )
-            .map_err(|e| format!("Failed to create Groq client: {}", e))?;
-
-            let model = client.completion_model(&summary_model);
-            let agent = AgentBuilder::new(model).build();
-
-            agent
-                .prompt(&prompt)
-                .await
-                .map_err(|e| format!("Groq summarization failed: {}", e))
-        }
-        AiProviderType::Gemini => {
-            let client: gemini::Client = gemini::Client::new(
-                &settings.api_key.clone().unwrap_or_default(),
-            )
-            .map_err(|e| format!("Failed to create Gemini client: {}", e))?;
-
-            let model = client.completion_model(&summary_model);
-            let agent = AgentBuilder::new(model).build();
-
-            agent
-                .prompt(&prompt)
-                .await
-                .map_err(|e| format!("Gemini summarization failed: {}", e))
-        }
-        AiProviderType::DeepSeek => {
-            let client: deepseek::Client = deepseek::Client::new(
-                &settings.api_key.clone().unwrap_or_default(),
-            )
-            .map_err(|e| format!("Failed to create DeepSeek client: {}", e))?;
-
-            let model = client.completion_model(&summary_model);
-            let agent = AgentBuilder::new(model).build();
-
-            agent
-                .prompt(&prompt)
-                .await
-                .map_err(|e| format!("DeepSeek summarization failed: {}", e))
-        }
-        AiProviderType::Mistral => {
-            let client: mistral::Client = mistral::Client::new(
-                &settings.api_key.clone().unwrap_or_default(),
-            )
-            .map_err(|e| format!("Failed to create Mistral client: {}", e))?;
-
-            let model = client.completion_model(&summary_model);
-            let agent = AgentBuilder::new(model).build();
-
-            agent
-                .prompt(&prompt)
-                .await
-                .map_err(|e| format!("Mistral summarization failed: {}", e))
-        }
-    }
+    let provider = create_provider(&settings.summary_settings());
+
+    let request = CompletionRequest {
+        prompt: format!(
+            "Summarize this user input in 1-2 sentences:\n\n{}",
+            // Truncate extremely long inputs to avoid context limits
+            char_prefix(user_input, MAX_SUMMARIZED_INPUT_LENGTH)
+        ),
+        system_prompt: Some(SUMMARIZER_SYSTEM_PROMPT.to_string()),
+        context: None,
+        temperature: None,
+        max_tokens: Some(settings.summary_max_tokens),
+        json_mode: false,
+    };
+
+    provider
+        .get_completion(request)
+        .await
+        .map(|response| response.content.trim().to_string())
+        .map_err(|e| format!("{} summarization failed: {}", settings.provider, e))
 }
 
 /// Truncate input as a fallback when summarization fails
@@ -249,7 +145,7 @@ fn truncate_input(input: &str) -> String {
     }
 
     // Try to truncate at a word boundary
-    let truncated = &input[..FALLBACK_TRUNCATION_LENGTH];
+    let truncated = char_prefix(input, FALLBACK_TRUNCATION_LENGTH);
     if let Some(last_space) = truncated.rfind(' ') {
         format!("{}...", &truncated[..last_space])
     } else {
@@ -257,6 +153,18 @@ fn truncate_input(input: &str) -> String {
     }
 }
 
+/// Longest prefix of `input` that fits in `max_len` bytes without splitting a character
+fn char_prefix(input: &str, max_len: usize) -> &str {
+    if input.len() <= max_len {
+        return input;
+    }
+    let mut end = max_len;
+    while !input.is_char_boundary(end) {
+        end -= 1;
+    }
+    &input[..end]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -275,12 +183,44 @@ mod tests {
         assert!(truncated.ends_with("..."));
     }
 
-    #[test]
-    fn test_input_summary_short_passthrough() {
-        // Short inputs should pass through without summarization
-        // (This is a sync test; async summarization is tested separately)
+    #[tokio::test]
+    async fn test_input_summary_short_passthrough() {
+        // Short inputs pass through without a model call
+        let settings = AiSettings::default();
         let input = "list containers";
-        assert!(input.len() < MIN_SUMMARIZATION_LENGTH);
+        let summary = summarize_user_input(&settings, input).await.unwrap();
+        assert_eq!(summary.summary, input);
+        assert_eq!(summary.original_length, input.len());
+    }
+
+    #[tokio::test]
+    async fn test_summary_without_memory_truncates() {
+        let settings = AiSettings {
+            memory_enabled: false,
+            endpoint_url: "http://127.0.0.1:9".to_string(),
+            ..AiSettings::default()
+        };
+        let input = "log line ".repeat(100);
+        let summary = summarize_user_input(&settings, &input).await.unwrap();
+        assert_eq!(summary.summary, truncate_input(&input));
+        assert_eq!(summary.original_length, input.len());
+
+        // Below the threshold the input is kept whole
+        let settings = AiSettings {
+            summary_min_input_length: 1000,
+            ..settings
+        };
+        let summary = summarize_user_input(&settings, &input).await.unwrap();
+        assert_eq!(summary.summary, input);
+    }
+
+    #[test]
+    fn test_truncate_input_multibyte() {
+        // 'é' is two bytes, so the byte limit falls inside a character
+        let input = format!("x{}", "é".repeat(FALLBACK_TRUNCATION_LENGTH));
+        let truncated = truncate_input(&input);
+        assert!(truncated.ends_with("..."));
+        assert!(truncated.len() <= FALLBACK_TRUNCATION_LENGTH + 3);
     }
 
     #[test]
@@ -316,11 +256,6 @@ mod tests {
         assert_eq!(truncate_input(""), "");
     }
 
-    #[test]
-    fn test_min_summarization_length_constant() {
-        assert_eq!(MIN_SUMMARIZATION_LENGTH, 100);
-    }
-
     #[test]
     fn test_fallback_truncation_length_constant() {
         assert_eq!(FALLBACK_TRUNCATION_LENGTH, 200);
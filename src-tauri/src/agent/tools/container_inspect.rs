@@ -0,0 +1,317 @@
+//! Container Inspect Tool
+//!
+//! Tool for reading a container's configuration as structured JSON, so the
+//! agent doesn't have to parse `docker inspect` output and guess field names.
+
+use rig::completion::ToolDefinition;
+use rig::tool::Tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use thiserror::Error;
+
+use crate::commands::container::fetch_container_details;
+use crate::models::container::{validate_container_name, ContainerDetails};
+use crate::models::system::ContainerSystem;
+use crate::runtime::CommandBuilder;
+
+/// Fields the agent can ask for, and where they sit in the serialized details
+const FIELDS: &[(&str, &str)] = &[
+    ("state", "/state"),
+    ("ports", "/networkSettings/portBindings"),
+    ("networks", "/networkSettings/networks"),
+    ("env", "/environmentVariables"),
+    ("mounts", "/volumes"),
+    ("labels", "/labels"),
+    ("resources", "/resourceLimits"),
+    ("restart_policy", "/restartPolicy"),
+    ("health_check", "/healthCheck"),
+    ("config", "/config"),
+    ("host_config", "/hostConfig"),
+];
+
+/// Error type for container inspect tool
+#[derive(Debug, Error)]
+pub enum ContainerInspectError {
+    #[error("Failed to inspect container: {0}")]
+    InspectFailed(String),
+}
+
+/// Arguments for the container inspect tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ContainerInspectArgs {
+    /// Container name or ID
+    pub container: String,
+    /// Fields to return, e.g. ["ports", "env"] (default: all)
+    #[serde(default)]
+    pub fields: Option<Vec<String>>,
+}
+
+/// Result of inspecting a container
+#[derive(Debug, Serialize)]
+pub struct ContainerInspectResult {
+    pub value: Value,
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ContainerInspectResult {
+    fn failed(error: String) -> Self {
+        Self {
+            value: Value::Null,
+            success: false,
+            error: Some(error),
+        }
+    }
+}
+
+/// Tool for inspecting containers on the terminal's system
+pub struct InspectContainerTool {
+    /// System the terminal session runs on, if it is still known
+    system: Option<ContainerSystem>,
+    /// Report the command instead of running it
+    dry_run: bool,
+}
+
+impl InspectContainerTool {
+    /// Create a new container inspect tool for containers on `system`
+    pub fn new(system: Option<ContainerSystem>) -> Self {
+        Self {
+            system,
+            dry_run: false,
+        }
+    }
+
+    /// Only report the inspect command instead of running it
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+}
+
+impl Tool for InspectContainerTool {
+    const NAME: &'static str = "inspect_container";
+
+    type Args = ContainerInspectArgs;
+    type Output = ContainerInspectResult;
+    type Error = ContainerInspectError;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        let fields: Vec<&str> = FIELDS.iter().map(|(name, _)| *name).collect();
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: "Get a container's configuration as structured JSON: state, published ports, networks, environment, mounts, labels, resource limits, restart policy, health check and config. Use this instead of running 'docker inspect' and request only the fields you need.".to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "container": {
+                        "type": "string",
+                        "description": "Container name or ID"
+                    },
+                    "fields": {
+                        "type": "array",
+                        "items": { "type": "string", "enum": fields },
+                        "description": "Fields to return (default: all)"
+                    }
+                },
+                "required": ["container"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let Some(system) = &self.system else {
+            return Ok(ContainerInspectResult::failed(
+                "The terminal's system is not known, so its containers can't be reached"
+                    .to_string(),
+            ));
+        };
+
+        // The name comes from the model and ends up in a shell command
+        if let Err(e) = validate_container_name(&args.container) {
+            return Ok(ContainerInspectResult::failed(e.to_string()));
+        }
+
+        if self.dry_run {
+            let command = CommandBuilder::inspect_container(system.primary_runtime, &args.container);
+            return Ok(ContainerInspectResult {
+                value: Value::String(format!("would execute: {}", command)),
+                success: true,
+                error: None,
+            });
+        }
+
+        let details =
+            match fetch_container_details(system, &args.container, system.primary_runtime).await {
+                Ok(details) => details,
+                Err(e) => return Ok(ContainerInspectResult::failed(e.to_string())),
+            };
+
+        Ok(match select_fields(&details, args.fields.as_deref()) {
+            Ok(value) => ContainerInspectResult {
+                value,
+                success: true,
+                error: None,
+            },
+            Err(e) => ContainerInspectResult::failed(e),
+        })
+    }
+}
+
+/// Pick the requested fields (all when `fields` is None), dropping empty values
+fn select_fields(details: &ContainerDetails, fields: Option<&[String]>) -> Result<Value, String> {
+    let all = serde_json::to_value(details).map_err(|e| e.to_string())?;
+
+    let mut selected = Map::new();
+    for &(name, pointer) in FIELDS {
+        let requested = fields.is_none_or(|fields| fields.iter().any(|f| f == name));
+        if !requested {
+            continue;
+        }
+        if let Some(value) = all.pointer(pointer).cloned().and_then(compact) {
+            selected.insert(name.to_string(), value);
+        }
+    }
+
+    if let Some(unknown) = fields
+        .into_iter()
+        .flatten()
+        .find(|f| !FIELDS.iter().any(|(name, _)| name == f))
+    {
+        let names: Vec<&str> = FIELDS.iter().map(|(name, _)| *name).collect();
+        return Err(format!(
+            "Unknown field '{}', expected one of: {}",
+            unknown,
+            names.join(", ")
+        ));
+    }
+
+    Ok(Value::Object(selected))
+}
+
+/// Remove nulls, empty strings and empty collections; None if nothing is left
+fn compact(value: Value) -> Option<Value> {
+    match value {
+        Value::Null => None,
+        Value::String(s) if s.is_empty() => None,
+        Value::Array(items) => {
+            let items: Vec<Value> = items.into_iter().filter_map(compact).collect();
+            (!items.is_empty()).then_some(Value::Array(items))
+        }
+        Value::Object(map) => {
+            let map: Map<String, Value> = map
+                .into_iter()
+                .filter_map(|(k, v)| compact(v).map(|v| (k, v)))
+                .collect();
+            (!map.is_empty()).then_some(Value::Object(map))
+        }
+        value => Some(value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::container::ContainerRuntime;
+    use crate::models::system::{ConnectionType, SystemId};
+    use crate::runtime::OutputParser;
+
+    fn details() -> ContainerDetails {
+        let inspect = serde_json::json!([{
+            "State": { "Status": "running", "Pid": 42, "ExitCode": 0, "Error": "" },
+            "Config": { "Env": ["PORT=8080"] },
+            "HostConfig": { "RestartPolicy": { "Name": "always" } },
+            "NetworkSettings": {
+                "Ports": { "80/tcp": [{ "HostIp": "0.0.0.0", "HostPort": "8080" }] }
+            }
+        }]);
+        OutputParser::parse_container_details(&inspect.to_string(), ContainerRuntime::Docker)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_select_fields_filters_and_compacts() {
+        let value =
+            select_fields(&details(), Some(&["env".to_string(), "ports".to_string()])).unwrap();
+        assert_eq!(value["env"], serde_json::json!({ "PORT": "8080" }));
+        assert_eq!(value["ports"][0]["hostPort"], 8080);
+        assert_eq!(value["ports"][0]["containerPort"], 80);
+        assert!(value.get("restart_policy").is_none());
+        assert!(value.get("state").is_none());
+
+        let value = select_fields(&details(), None).unwrap();
+        assert_eq!(value["state"]["pid"], 42);
+        assert!(value["state"].get("error").is_none());
+        assert!(value.get("mounts").is_none());
+        assert!(value.get("health_check").is_none());
+    }
+
+    #[test]
+    fn test_select_fields_rejects_unknown_field() {
+        let err = select_fields(&details(), Some(&["Mounts".to_string()])).unwrap_err();
+        assert!(err.contains("Unknown field 'Mounts'"));
+        assert!(err.contains("mounts"));
+    }
+
+    fn local_system() -> ContainerSystem {
+        ContainerSystem {
+            id: SystemId("local".to_string()),
+            name: "Local".to_string(),
+            hostname: "localhost".to_string(),
+            connection_type: ConnectionType::Local,
+            primary_runtime: ContainerRuntime::Docker,
+            available_runtimes: Default::default(),
+            ssh_config: None,
+            auto_connect: false,
+            tags: Vec::new(),
+            command_timeout_secs: None,
+            last_connected_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_injected_container_name_is_refused() {
+        let tool = InspectContainerTool::new(Some(local_system())).with_dry_run(true);
+        let result = tool
+            .call(ContainerInspectArgs {
+                container: "web$(reboot)".to_string(),
+                fields: None,
+            })
+            .await
+            .unwrap();
+
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("Invalid container name"));
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_reports_command() {
+        let tool = InspectContainerTool::new(Some(local_system())).with_dry_run(true);
+        let result = tool
+            .call(ContainerInspectArgs {
+                container: "web".to_string(),
+                fields: None,
+            })
+            .await
+            .unwrap();
+
+        assert!(result.success);
+        assert_eq!(result.value, "would execute: docker inspect 'web'");
+    }
+
+    #[tokio::test]
+    async fn test_inspect_without_system() {
+        let tool = InspectContainerTool::new(None);
+        let result = tool
+            .call(ContainerInspectArgs {
+                container: "web".to_string(),
+                fields: None,
+            })
+            .await
+            .unwrap();
+
+        assert!(!result.success);
+        assert!(result.error.is_some());
+    }
+}
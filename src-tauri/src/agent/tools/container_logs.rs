@@ -0,0 +1,297 @@
+//! Container Logs Tool
+//!
+//! Tool for reading a container's logs through its runtime, without typing a
+//! `docker logs` command into the user's terminal.
+
+use rig::completion::ToolDefinition;
+use rig::tool::Tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::commands::container::fetch_container_logs;
+use crate::commands::terminal::strip_ansi;
+use crate::models::container::validate_container_name;
+use crate::models::system::ContainerSystem;
+use crate::runtime::CommandBuilder;
+
+/// Lines returned when the agent doesn't ask for a number
+const DEFAULT_TAIL: u32 = 100;
+
+/// Most lines the agent may ask for in one call
+const MAX_TAIL: u32 = 1000;
+
+/// Most bytes of log text returned; older lines are dropped beyond this
+const MAX_OUTPUT_BYTES: usize = 32 * 1024;
+
+/// Error type for container logs tool
+#[derive(Debug, Error)]
+pub enum ContainerLogsError {
+    #[error("Failed to read logs: {0}")]
+    ReadFailed(String),
+}
+
+/// Arguments for the container logs tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ContainerLogsArgs {
+    /// Container name or ID
+    pub container: String,
+    /// Number of lines from the end of the logs (default: 100, max: 1000)
+    #[serde(default)]
+    pub tail: Option<u32>,
+    /// Only show logs since a duration (e.g. "10m") or RFC3339 timestamp
+    #[serde(default)]
+    pub since: Option<String>,
+}
+
+/// Result of reading container logs
+#[derive(Debug, Serialize)]
+pub struct ContainerLogsResult {
+    pub result: String,
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Number of log lines returned
+    pub count: usize,
+}
+
+impl ContainerLogsResult {
+    fn failed(error: String) -> Self {
+        Self {
+            result: String::new(),
+            success: false,
+            error: Some(error),
+            count: 0,
+        }
+    }
+}
+
+/// Tool for reading container logs on the terminal's system
+pub struct ReadContainerLogsTool {
+    /// System the terminal session runs on, if it is still known
+    system: Option<ContainerSystem>,
+    /// Report the command instead of running it
+    dry_run: bool,
+}
+
+impl ReadContainerLogsTool {
+    /// Create a new container logs tool for containers on `system`
+    pub fn new(system: Option<ContainerSystem>) -> Self {
+        Self {
+            system,
+            dry_run: false,
+        }
+    }
+
+    /// Only report the logs command instead of running it
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+}
+
+impl Tool for ReadContainerLogsTool {
+    const NAME: &'static str = "read_container_logs";
+
+    type Args = ContainerLogsArgs;
+    type Output = ContainerLogsResult;
+    type Error = ContainerLogsError;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: "Read the logs of a container on the terminal's system, using its container runtime directly. Returns plain text without running anything in the user's terminal. Use this instead of running 'docker logs' when you need to inspect a container's output.".to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "container": {
+                        "type": "string",
+                        "description": "Container name or ID"
+                    },
+                    "tail": {
+                        "type": "integer",
+                        "description": "Number of lines from the end of the logs (default: 100, max: 1000)"
+                    },
+                    "since": {
+                        "type": "string",
+                        "description": "Only show logs since a duration like '10m' or an RFC3339 timestamp"
+                    }
+                },
+                "required": ["container"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let Some(system) = &self.system else {
+            return Ok(ContainerLogsResult::failed(
+                "The terminal's system is not known, so its containers can't be reached"
+                    .to_string(),
+            ));
+        };
+
+        // The name comes from the model and ends up in a shell command
+        if let Err(e) = validate_container_name(&args.container) {
+            return Ok(ContainerLogsResult::failed(e.to_string()));
+        }
+
+        let tail = args.tail.unwrap_or(DEFAULT_TAIL).min(MAX_TAIL);
+        if self.dry_run {
+            let command = CommandBuilder::container_logs(
+                system.primary_runtime,
+                &args.container,
+                Some(tail),
+                args.since.as_deref(),
+                None,
+                false,
+            );
+            return Ok(ContainerLogsResult {
+                result: format!("would execute: {}", command),
+                success: true,
+                error: None,
+                count: 0,
+            });
+        }
+
+        let logs = match fetch_container_logs(
+            system,
+            &args.container,
+            system.primary_runtime,
+            Some(tail),
+            args.since.as_deref(),
+            None,
+            false,
+        )
+        .await
+        {
+            Ok(logs) => logs,
+            Err(e) => return Ok(ContainerLogsResult::failed(e.to_string())),
+        };
+
+        let result = clean_logs(&logs);
+        Ok(ContainerLogsResult {
+            count: result.lines().count(),
+            result,
+            success: true,
+            error: None,
+        })
+    }
+}
+
+/// Strip escapes and carriage returns, keeping only the newest `MAX_OUTPUT_BYTES`
+fn clean_logs(logs: &str) -> String {
+    let text = strip_ansi(logs).replace('\r', "");
+    if text.len() <= MAX_OUTPUT_BYTES {
+        return text;
+    }
+
+    // Cut on a line boundary so the first line returned is whole, moving
+    // past any multi-byte character the byte offset lands inside first
+    let mut start = text.len() - MAX_OUTPUT_BYTES;
+    while !text.is_char_boundary(start) {
+        start += 1;
+    }
+    let start = text[start..]
+        .find('\n')
+        .map(|i| start + i + 1)
+        .unwrap_or(text.len());
+    text[start..].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::container::ContainerRuntime;
+    use crate::models::system::{ConnectionType, SystemId};
+
+    fn local_system() -> ContainerSystem {
+        ContainerSystem {
+            id: SystemId("local".to_string()),
+            name: "Local".to_string(),
+            hostname: "localhost".to_string(),
+            connection_type: ConnectionType::Local,
+            primary_runtime: ContainerRuntime::Docker,
+            available_runtimes: Default::default(),
+            ssh_config: None,
+            auto_connect: false,
+            tags: Vec::new(),
+            command_timeout_secs: None,
+            last_connected_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_logs_without_system() {
+        let tool = ReadContainerLogsTool::new(None);
+        let result = tool
+            .call(ContainerLogsArgs {
+                container: "web".to_string(),
+                tail: None,
+                since: None,
+            })
+            .await
+            .unwrap();
+
+        assert!(!result.success);
+        assert!(result.error.is_some());
+        assert_eq!(result.count, 0);
+    }
+
+    #[tokio::test]
+    async fn test_injected_container_name_is_refused() {
+        let system = local_system();
+        let tool = ReadContainerLogsTool::new(Some(system)).with_dry_run(true);
+        let result = tool
+            .call(ContainerLogsArgs {
+                container: "web; curl x|sh".to_string(),
+                tail: None,
+                since: None,
+            })
+            .await
+            .unwrap();
+
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("Invalid container name"));
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_reports_command() {
+        let system = local_system();
+        let tool = ReadContainerLogsTool::new(Some(system)).with_dry_run(true);
+        let result = tool
+            .call(ContainerLogsArgs {
+                container: "web".to_string(),
+                tail: Some(20),
+                since: None,
+            })
+            .await
+            .unwrap();
+
+        assert!(result.success);
+        assert!(result.result.starts_with("would execute: "));
+        assert!(result.result.contains("--tail 20 'web'"));
+    }
+
+    #[test]
+    fn test_clean_logs_strips_escapes_and_keeps_newest_lines() {
+        assert_eq!(
+            clean_logs("\x1b[32mready\x1b[0m\r\nlistening\n"),
+            "ready\nlistening\n"
+        );
+
+        let line = format!("{}\n", "x".repeat(99));
+        let logs = format!("first\n{}", line.repeat(MAX_OUTPUT_BYTES / line.len() + 1));
+        let cleaned = clean_logs(&logs);
+        assert!(cleaned.len() <= MAX_OUTPUT_BYTES);
+        assert!(!cleaned.contains("first"));
+        assert!(cleaned.lines().all(|l| l.len() == 99));
+    }
+
+    #[test]
+    fn test_clean_logs_cuts_non_ascii_logs_on_a_char_boundary() {
+        let logs = "é\n".repeat(40 * 1024 / 3);
+        let cleaned = clean_logs(&logs);
+        assert!(cleaned.len() <= MAX_OUTPUT_BYTES);
+        assert!(cleaned.lines().all(|l| l == "é"));
+    }
+}
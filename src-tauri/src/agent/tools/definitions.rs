@@ -44,6 +44,29 @@ pub struct QueryHistoryInput {
     pub limit: Option<usize>,
 }
 
+/// Input for read_container_logs tool
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReadContainerLogsInput {
+    /// Container name or ID
+    pub container: String,
+    /// Number of lines from the end of the logs (default: 100)
+    #[serde(default)]
+    pub tail: Option<u32>,
+    /// Only show logs since a duration (e.g. "10m") or RFC3339 timestamp
+    #[serde(default)]
+    pub since: Option<String>,
+}
+
+/// Input for inspect_container tool
+#[derive(Debug, Clone, Deserialize)]
+pub struct InspectContainerInput {
+    /// Container name or ID
+    pub container: String,
+    /// Fields to return, e.g. ["ports", "env"] (default: all)
+    #[serde(default)]
+    pub fields: Option<Vec<String>>,
+}
+
 /// Build the tool definitions for the AI agent
 pub fn build_tool_definitions() -> Vec<ToolDefinition> {
     vec![
@@ -116,6 +139,60 @@ pub fn build_tool_definitions() -> Vec<ToolDefinition> {
                 "required": ["query_type"]
             }),
         },
+        ToolDefinition {
+            name: "read_container_logs".to_string(),
+            description: concat!(
+                "Read a container's logs through its container runtime and return them as plain text. ",
+                "Use this instead of running 'docker logs' in the terminal. ",
+                "Keep 'tail' small and raise it only if the lines you need are missing."
+            ).to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "container": {
+                        "type": "string",
+                        "description": "Container name or ID"
+                    },
+                    "tail": {
+                        "type": "integer",
+                        "description": "Number of lines from the end of the logs (default: 100, max: 1000)"
+                    },
+                    "since": {
+                        "type": "string",
+                        "description": "Only show logs since a duration like '10m' or an RFC3339 timestamp"
+                    }
+                },
+                "required": ["container"]
+            }),
+        },
+        ToolDefinition {
+            name: "inspect_container".to_string(),
+            description: concat!(
+                "Get a container's configuration as structured JSON instead of parsing 'docker inspect' output. ",
+                "Request only the fields you need to keep the result small."
+            ).to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "container": {
+                        "type": "string",
+                        "description": "Container name or ID"
+                    },
+                    "fields": {
+                        "type": "array",
+                        "items": {
+                            "type": "string",
+                            "enum": [
+                                "state", "ports", "networks", "env", "mounts", "labels",
+                                "resources", "restart_policy", "health_check", "config", "host_config"
+                            ]
+                        },
+                        "description": "Fields to return (default: all)"
+                    }
+                },
+                "required": ["container"]
+            }),
+        },
     ]
 }
 
@@ -131,10 +208,12 @@ mod tests {
     #[test]
     fn test_build_tool_definitions() {
         let tools = build_tool_definitions();
-        assert_eq!(tools.len(), 3);
+        assert_eq!(tools.len(), 5);
         assert_eq!(tools[0].name, "execute_shell");
         assert_eq!(tools[1].name, "query_state");
         assert_eq!(tools[2].name, "query_history");
+        assert_eq!(tools[3].name, "read_container_logs");
+        assert_eq!(tools[4].name, "inspect_container");
     }
 
     #[test]
@@ -169,4 +248,24 @@ mod tests {
         assert_eq!(input.value, None);
         assert_eq!(input.limit, None);
     }
+
+    #[test]
+    fn test_read_container_logs_input_parsing() {
+        let json = r#"{"container": "web", "tail": 50, "since": "10m"}"#;
+        let input: ReadContainerLogsInput = serde_json::from_str(json).unwrap();
+        assert_eq!(input.container, "web");
+        assert_eq!(input.tail, Some(50));
+        assert_eq!(input.since, Some("10m".to_string()));
+    }
+
+    #[test]
+    fn test_inspect_container_input_parsing() {
+        let json = r#"{"container": "web", "fields": ["ports", "env"]}"#;
+        let input: InspectContainerInput = serde_json::from_str(json).unwrap();
+        assert_eq!(input.container, "web");
+        assert_eq!(
+            input.fields,
+            Some(vec!["ports".to_string(), "env".to_string()])
+        );
+    }
 }
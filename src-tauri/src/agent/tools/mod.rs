@@ -3,12 +3,16 @@
 //! Tools for the terminal agent, including Rig.rs tools and
 //! tool definitions for Anthropic's native tool_use API.
 
+mod container_inspect;
+mod container_logs;
 pub mod definitions;
 mod history_query;
 mod shell_execute;
 mod state_query;
 
-pub use definitions::{build_tool_definitions, ExecuteShellInput, QueryHistoryInput, QueryStateInput, ToolDefinition};
+pub use container_inspect::InspectContainerTool;
+pub use container_logs::ReadContainerLogsTool;
+pub use definitions::{build_tool_definitions, ExecuteShellInput, InspectContainerInput, QueryHistoryInput, QueryStateInput, ReadContainerLogsInput, ToolDefinition};
 pub use history_query::HistoryQueryTool;
-pub use shell_execute::ShellExecuteTool;
+pub use shell_execute::{ShellExecuteArgs, ShellExecuteTool};
 pub use state_query::StateQueryTool;
@@ -3,7 +3,7 @@
 //! Tool for executing shell commands via the terminal PTY.
 
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use regex::Regex;
 use rig::completion::ToolDefinition;
@@ -14,8 +14,12 @@ use thiserror::Error;
 use tokio::sync::{mpsc, RwLock};
 
 use crate::agent::events::AgentEvent;
-use crate::agent::safety::{DangerClassification, DangerClassifier};
-use crate::agent::session::{generate_block_id, CommandHistoryEntry, TerminalContext};
+use crate::agent::executor::{CONFIRMATION_TIMEOUT, MAX_TOOL_OUTPUT_LINES};
+use crate::agent::safety::{DangerClassification, DangerClassifier, DangerLevel, DangerRule};
+use crate::agent::session::{
+    clean_terminal_output, generate_block_id, CommandHistoryEntry, ConfirmationReceiver,
+    TerminalContext,
+};
 use crate::commands::terminal::{TerminalInput, TerminalSessions};
 
 /// Information about a container exec command
@@ -130,6 +134,24 @@ fn detect_container_exec(cmd: &str) -> Option<ContainerExecInfo> {
     None
 }
 
+/// Keep the first and last `max_lines / 2` lines of long output, replacing the
+/// rest with a `... <X lines omitted> ...` marker
+pub fn truncate_output(output: &str, max_lines: usize) -> String {
+    let lines: Vec<&str> = output.lines().collect();
+    if lines.len() <= max_lines {
+        return output.to_string();
+    }
+
+    let tail = max_lines / 2;
+    let head = max_lines - tail;
+    format!(
+        "{}\n... <{} lines omitted> ...\n{}",
+        lines[..head].join("\n"),
+        lines.len() - head - tail,
+        lines[lines.len() - tail..].join("\n")
+    )
+}
+
 /// Check if a command is an exit command that would leave a container shell
 fn is_exit_command(cmd: &str) -> bool {
     let trimmed = cmd.trim();
@@ -191,7 +213,9 @@ pub struct ShellExecuteTool {
     /// Channel to send agent events
     event_tx: mpsc::Sender<AgentEvent>,
     /// Channel to receive confirmation responses
-    confirmation_rx: Arc<tokio::sync::Mutex<mpsc::Receiver<bool>>>,
+    confirmation_rx: ConfirmationReceiver,
+    /// How long a confirmation waits for an answer before its command is cancelled
+    confirmation_timeout: Duration,
     /// Danger classifier
     classifier: DangerClassifier,
     /// Terminal context for output capture
@@ -200,8 +224,12 @@ pub struct ShellExecuteTool {
     agent_session_id: String,
     /// Current query ID
     query_id: Arc<RwLock<String>>,
-    /// Whether auto-execute is enabled for safe commands
-    auto_execute: bool,
+    /// Highest danger level run without confirmation
+    auto_approve_level: DangerLevel,
+    /// Report commands instead of running them
+    dry_run: bool,
+    /// Lines of output returned to the model; the terminal and history keep it all
+    max_output_lines: usize,
 }
 
 impl ShellExecuteTool {
@@ -211,23 +239,50 @@ impl ShellExecuteTool {
         agent_session_id: String,
         terminal_sessions: Arc<TerminalSessions>,
         event_tx: mpsc::Sender<AgentEvent>,
-        confirmation_rx: mpsc::Receiver<bool>,
+        confirmation_rx: ConfirmationReceiver,
         context: Arc<RwLock<TerminalContext>>,
-        auto_execute: bool,
+        auto_approve_level: DangerLevel,
     ) -> Self {
         Self {
             terminal_session_id,
             terminal_sessions,
             event_tx,
-            confirmation_rx: Arc::new(tokio::sync::Mutex::new(confirmation_rx)),
+            confirmation_rx,
+            confirmation_timeout: CONFIRMATION_TIMEOUT,
             classifier: DangerClassifier::new(),
             context,
             agent_session_id,
             query_id: Arc::new(RwLock::new(String::new())),
-            auto_execute,
+            auto_approve_level,
+            dry_run: false,
+            max_output_lines: MAX_TOOL_OUTPUT_LINES,
         }
     }
 
+    /// Classify commands with the user's danger rules in addition to the built-ins
+    pub fn with_danger_rules(mut self, rules: &[DangerRule]) -> Self {
+        self.classifier = DangerClassifier::with_rules(rules);
+        self
+    }
+
+    /// Enable dry-run mode: commands are reported but never sent to the terminal
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Cancel commands whose confirmation goes unanswered for `timeout`
+    pub fn with_confirmation_timeout(mut self, timeout: Duration) -> Self {
+        self.confirmation_timeout = timeout;
+        self
+    }
+
+    /// Limit the output returned to the model to `max_lines` lines
+    pub fn with_max_output_lines(mut self, max_lines: usize) -> Self {
+        self.max_output_lines = max_lines;
+        self
+    }
+
     /// Set the current query ID
     pub async fn set_query_id(&self, query_id: String) {
         *self.query_id.write().await = query_id;
@@ -498,6 +553,59 @@ impl ShellExecuteTool {
         result
     }
 
+    /// Emit the usual command events for a command that is only previewed
+    async fn dry_run_result(
+        &self,
+        command: &str,
+        query_id: &str,
+        start: Instant,
+        danger_level: String,
+    ) -> ShellExecuteResult {
+        let block_id = generate_block_id();
+        let output = format!("would execute: {}", command);
+
+        let _ = self
+            .event_tx
+            .send(AgentEvent::CommandStarted {
+                session_id: self.agent_session_id.clone(),
+                query_id: query_id.to_string(),
+                block_id,
+                command: command.to_string(),
+            })
+            .await;
+
+        let _ = self
+            .event_tx
+            .send(AgentEvent::CommandOutput {
+                session_id: self.agent_session_id.clone(),
+                query_id: query_id.to_string(),
+                block_id,
+                payload: output.clone(),
+            })
+            .await;
+
+        let duration_ms = start.elapsed().as_millis() as u64;
+        let _ = self
+            .event_tx
+            .send(AgentEvent::CommandCompleted {
+                session_id: self.agent_session_id.clone(),
+                query_id: query_id.to_string(),
+                block_id,
+                exit_code: 0,
+                duration_ms,
+            })
+            .await;
+
+        ShellExecuteResult {
+            output,
+            exit_code: None,
+            executed: false,
+            blocked_reason: None,
+            duration_ms,
+            danger_level,
+        }
+    }
+
     /// Request confirmation for a dangerous command
     async fn request_confirmation(
         &self,
@@ -506,6 +614,13 @@ impl ShellExecuteTool {
     ) -> Result<bool, String> {
         let query_id = self.query_id.read().await.clone();
         let confirmation_id = uuid::Uuid::new_v4().to_string();
+        let expires_at = chrono::Utc::now().timestamp_millis()
+            + self.confirmation_timeout.as_millis() as i64;
+
+        // Drop any answer left over from a confirmation that expired just as it
+        // was approved, so it can't approve this one
+        let mut rx = self.confirmation_rx.lock().await;
+        while rx.try_recv().is_ok() {}
 
         // Emit confirmation required event
         self.event_tx
@@ -523,18 +638,29 @@ impl ShellExecuteTool {
                     classification.level.description()
                 )),
                 alternatives: vec![], // Could add safer alternatives here
+                expires_at,
             })
             .await
             .map_err(|e| e.to_string())?;
 
-        // Wait for confirmation with timeout
-        let timeout = tokio::time::Duration::from_secs(300); // 5 minutes
-        let mut rx = self.confirmation_rx.lock().await;
-
-        match tokio::time::timeout(timeout, rx.recv()).await {
+        match tokio::time::timeout(self.confirmation_timeout, rx.recv()).await {
             Ok(Some(confirmed)) => Ok(confirmed),
             Ok(None) => Err("Confirmation channel closed".to_string()),
-            Err(_) => Err("Confirmation timeout".to_string()),
+            Err(_) => {
+                let _ = self
+                    .event_tx
+                    .send(AgentEvent::ConfirmationExpired {
+                        session_id: self.agent_session_id.clone(),
+                        query_id,
+                        confirmation_id,
+                        command: command.to_string(),
+                    })
+                    .await;
+                Err(format!(
+                    "No answer within {}s, so the command was cancelled",
+                    self.confirmation_timeout.as_secs()
+                ))
+            }
         }
     }
 }
@@ -590,73 +716,41 @@ impl Tool for ShellExecuteTool {
             })
             .await;
 
-        // Check if confirmation is required
-        if classification.requires_confirmation() {
-            // Emit command proposed event
-            let _ = self
-                .event_tx
-                .send(AgentEvent::CommandProposed {
-                    session_id: self.agent_session_id.clone(),
-                    query_id: query_id.clone(),
-                    command: args.command.clone(),
-                    explanation: args.explanation.clone().unwrap_or_else(|| classification.explanation.clone()),
-                    danger_level: danger_level.clone(),
-                    requires_confirmation: true,
-                    affected_resources: classification.affected_resources.clone(),
-                })
-                .await;
+        if self.dry_run {
+            return Ok(self.dry_run_result(&args.command, &query_id, start, danger_level).await);
+        }
 
-            // Request confirmation
-            match self.request_confirmation(&args.command, &classification).await {
-                Ok(true) => {
-                    // User confirmed, proceed
-                }
-                Ok(false) => {
-                    return Ok(ShellExecuteResult {
-                        output: String::new(),
-                        exit_code: None,
-                        executed: false,
-                        blocked_reason: Some("User rejected the command".to_string()),
-                        duration_ms: start.elapsed().as_millis() as u64,
-                        danger_level,
-                    });
-                }
-                Err(e) => {
-                    return Ok(ShellExecuteResult {
-                        output: String::new(),
-                        exit_code: None,
-                        executed: false,
-                        blocked_reason: Some(format!("Confirmation failed: {}", e)),
-                        duration_ms: start.elapsed().as_millis() as u64,
-                        danger_level,
-                    });
-                }
-            }
-        } else if !self.auto_execute {
-            // Even for safe commands, if auto_execute is off, we need confirmation
-            // This is a more restrictive mode
-            match self.request_confirmation(&args.command, &classification).await {
-                Ok(true) => {}
-                Ok(false) => {
-                    return Ok(ShellExecuteResult {
-                        output: String::new(),
-                        exit_code: None,
-                        executed: false,
-                        blocked_reason: Some("User rejected the command".to_string()),
-                        duration_ms: start.elapsed().as_millis() as u64,
-                        danger_level,
-                    });
-                }
-                Err(e) => {
-                    return Ok(ShellExecuteResult {
-                        output: String::new(),
-                        exit_code: None,
-                        executed: false,
-                        blocked_reason: Some(format!("Confirmation failed: {}", e)),
-                        duration_ms: start.elapsed().as_millis() as u64,
-                        danger_level,
-                    });
-                }
+        // Commands above the auto-approve level (and every dangerous one) wait
+        // for the user; the rest are still proposed so the UI shows what ran
+        let requires_confirmation = !classification.level.is_auto_approved(self.auto_approve_level);
+        let _ = self
+            .event_tx
+            .send(AgentEvent::CommandProposed {
+                session_id: self.agent_session_id.clone(),
+                query_id: query_id.clone(),
+                command: args.command.clone(),
+                explanation: args.explanation.clone().unwrap_or_else(|| classification.explanation.clone()),
+                danger_level: danger_level.clone(),
+                requires_confirmation,
+                affected_resources: classification.affected_resources.clone(),
+            })
+            .await;
+
+        if requires_confirmation {
+            let blocked_reason = match self.request_confirmation(&args.command, &classification).await {
+                Ok(true) => None,
+                Ok(false) => Some("User rejected the command".to_string()),
+                Err(e) => Some(format!("Confirmation failed: {}", e)),
+            };
+            if blocked_reason.is_some() {
+                return Ok(ShellExecuteResult {
+                    output: String::new(),
+                    exit_code: None,
+                    executed: false,
+                    blocked_reason,
+                    duration_ms: start.elapsed().as_millis() as u64,
+                    danger_level,
+                });
             }
         }
 
@@ -698,7 +792,7 @@ impl Tool for ShellExecuteTool {
                     let mut ctx = self.context.write().await;
                     ctx.last_exit_code = exit_code;
 
-                    // Save command execution to history (use raw output for AI context)
+                    // Save command execution to history (cleaned of escapes for AI context)
                     ctx.add_command_result(CommandHistoryEntry {
                         id: uuid::Uuid::new_v4().to_string(),
                         command: args.command.clone(),
@@ -723,10 +817,14 @@ impl Tool for ShellExecuteTool {
                     }
                 }
 
-                // Return RAW output to AI - this preserves accurate data
-                // (e.g., version numbers like "0.0.18" stay intact)
+                // Return the raw output to AI with only escape sequences removed -
+                // unlike vt100 rendering this keeps data like "0.0.18" intact -
+                // trimmed so long output can't fill the model's context window
                 Ok(ShellExecuteResult {
-                    output: raw_output,
+                    output: truncate_output(
+                        &clean_terminal_output(&raw_output),
+                        self.max_output_lines,
+                    ),
                     exit_code,
                     executed: true,
                     blocked_reason: None,
@@ -760,3 +858,28 @@ impl Tool for ShellExecuteTool {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_output_keeps_head_and_tail() {
+        let output: String = (1..=10_000).map(|i| format!("line {}\n", i)).collect();
+        let truncated = truncate_output(&output, 200);
+
+        let lines: Vec<&str> = truncated.lines().collect();
+        assert_eq!(lines.len(), 201);
+        assert_eq!(lines[0], "line 1");
+        assert_eq!(lines[99], "line 100");
+        assert_eq!(lines[100], "... <9800 lines omitted> ...");
+        assert_eq!(lines[101], "line 9901");
+        assert_eq!(lines[200], "line 10000");
+    }
+
+    #[test]
+    fn test_truncate_output_leaves_short_output_alone() {
+        let output = "a\nb\nc\n";
+        assert_eq!(truncate_output(output, 3), output);
+    }
+}
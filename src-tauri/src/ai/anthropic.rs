@@ -3,7 +3,11 @@ use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use tracing::info;
 
-use super::provider::{AiModel, AiProvider, CompletionRequest, CompletionResponse, ShellCommandResponse};
+use super::provider::{
+    http_client, request_error, send_with_retry, AiModel, AiProvider, CompletionRequest,
+    CompletionResponse, HttpProvider, ShellCommandResponse, TokenUsage, DEFAULT_MAX_RETRIES,
+    DEFAULT_REQUEST_TIMEOUT_SECS,
+};
 use super::settings::AiProviderType;
 
 /// Anthropic API provider
@@ -11,14 +15,16 @@ pub struct AnthropicProvider {
     client: Client,
     api_key: String,
     model: String,
+    max_retries: u32,
 }
 
 impl AnthropicProvider {
     pub fn new(api_key: &str, model: &str) -> Self {
         Self {
-            client: Client::new(),
+            client: http_client(DEFAULT_REQUEST_TIMEOUT_SECS),
             api_key: api_key.to_string(),
             model: model.to_string(),
+            max_retries: DEFAULT_MAX_RETRIES,
         }
     }
 
@@ -42,6 +48,8 @@ impl AnthropicProvider {
                 context_window: Some(200_000),
                 parameter_size: None,
                 quantization_level: None,
+                size: None,
+                family: None,
             },
             AiModel {
                 id: "claude-3-5-sonnet-20241022".to_string(),
@@ -50,6 +58,8 @@ impl AnthropicProvider {
                 context_window: Some(200_000),
                 parameter_size: None,
                 quantization_level: None,
+                size: None,
+                family: None,
             },
             AiModel {
                 id: "claude-3-5-haiku-20241022".to_string(),
@@ -58,6 +68,8 @@ impl AnthropicProvider {
                 context_window: Some(200_000),
                 parameter_size: None,
                 quantization_level: None,
+                size: None,
+                family: None,
             },
             AiModel {
                 id: "claude-3-opus-20240229".to_string(),
@@ -66,6 +78,8 @@ impl AnthropicProvider {
                 context_window: Some(200_000),
                 parameter_size: None,
                 quantization_level: None,
+                size: None,
+                family: None,
             },
         ]
     }
@@ -201,6 +215,16 @@ pub struct ToolCall {
     pub input: serde_json::Value,
 }
 
+impl HttpProvider for AnthropicProvider {
+    fn client_mut(&mut self) -> &mut Client {
+        &mut self.client
+    }
+
+    fn max_retries_mut(&mut self) -> &mut u32 {
+        &mut self.max_retries
+    }
+}
+
 #[async_trait]
 impl AiProvider for AnthropicProvider {
     fn provider_type(&self) -> AiProviderType {
@@ -225,16 +249,16 @@ impl AiProvider for AnthropicProvider {
             tools: None,
         };
 
-        let response = self
-            .client
-            .post(&url)
-            .header("x-api-key", &self.api_key)
-            .header("anthropic-version", self.api_version())
-            .header("Content-Type", "application/json")
-            .json(&anthropic_request)
-            .send()
-            .await
-            .map_err(|e| format!("Failed to send request to Anthropic: {}", e))?;
+        let response = send_with_retry("Anthropic", self.max_retries, || {
+            self.client
+                .post(&url)
+                .header("x-api-key", &self.api_key)
+                .header("anthropic-version", self.api_version())
+                .header("Content-Type", "application/json")
+                .json(&anthropic_request)
+        })
+        .await
+        .map_err(|e| request_error("Failed to send request to", "Anthropic", e))?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -258,9 +282,10 @@ impl AiProvider for AnthropicProvider {
             .collect::<Vec<_>>()
             .join("");
 
-        let tokens_used = anthropic_response
+        let usage = anthropic_response
             .usage
-            .map(|u| u.input_tokens + u.output_tokens);
+            .map(|u| TokenUsage::new(u.input_tokens, u.output_tokens));
+        let tokens_used = usage.map(|u| u.total_tokens);
 
         // Try to parse structured response if in JSON mode
         // Anthropic doesn't have native JSON mode, so we rely on prompt engineering
@@ -273,6 +298,7 @@ impl AiProvider for AnthropicProvider {
         Ok(CompletionResponse {
             content,
             tokens_used,
+            usage,
             structured,
         })
     }
@@ -303,6 +329,8 @@ impl AiProvider for AnthropicProvider {
                                 context_window: Some(200_000), // All Claude 3+ models have 200K
                                 parameter_size: None,
                                 quantization_level: None,
+                                size: None,
+                                family: None,
                             })
                             .collect();
 
@@ -328,7 +356,7 @@ impl AiProvider for AnthropicProvider {
         self.test_connection().await.is_ok()
     }
 
-    async fn test_connection(&self) -> Result<(), String> {
+    async fn test_connection(&self) -> Result<Option<TokenUsage>, String> {
         // Send a minimal request to test the API key
         let url = format!("{}/v1/messages", self.base_url());
 
@@ -354,10 +382,15 @@ impl AiProvider for AnthropicProvider {
             .timeout(std::time::Duration::from_secs(10))
             .send()
             .await
-            .map_err(|e| format!("Failed to connect to Anthropic: {}", e))?;
+            .map_err(|e| request_error("Failed to connect to", "Anthropic", e))?;
 
         if response.status().is_success() {
-            Ok(())
+            Ok(response
+                .json::<AnthropicResponse>()
+                .await
+                .ok()
+                .and_then(|r| r.usage)
+                .map(|u| TokenUsage::new(u.input_tokens, u.output_tokens)))
         } else if response.status().as_u16() == 401 {
             Err("Invalid API key".to_string())
         } else {
@@ -405,16 +438,16 @@ impl AnthropicProvider {
 
         info!("Sending tool completion request to Anthropic with {} tools", tools.len());
 
-        let response = self
-            .client
-            .post(&url)
-            .header("x-api-key", &self.api_key)
-            .header("anthropic-version", self.api_version())
-            .header("Content-Type", "application/json")
-            .json(&body)
-            .send()
-            .await
-            .map_err(|e| format!("Failed to send request to Anthropic: {}", e))?;
+        let response = send_with_retry("Anthropic", self.max_retries, || {
+            self.client
+                .post(&url)
+                .header("x-api-key", &self.api_key)
+                .header("anthropic-version", self.api_version())
+                .header("Content-Type", "application/json")
+                .json(&body)
+        })
+        .await
+        .map_err(|e| request_error("Failed to send request to", "Anthropic", e))?;
 
         if !response.status().is_success() {
             let status = response.status();
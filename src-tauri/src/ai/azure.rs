@@ -4,7 +4,9 @@ use serde::{Deserialize, Serialize};
 use tracing::info;
 
 use super::provider::{
-    AiModel, AiProvider, CompletionRequest, CompletionResponse, ShellCommandResponse,
+    http_client, request_error, send_with_retry, AiModel, AiProvider, CompletionRequest,
+    CompletionResponse, HttpProvider, ShellCommandResponse, TokenUsage, DEFAULT_MAX_RETRIES,
+    DEFAULT_REQUEST_TIMEOUT_SECS,
 };
 use super::settings::AiProviderType;
 
@@ -24,6 +26,7 @@ pub struct AzureProvider {
     deployment: String,
     endpoint_url: String,
     api_version: String,
+    max_retries: u32,
 }
 
 impl AzureProvider {
@@ -33,11 +36,12 @@ impl AzureProvider {
             .unwrap_or(DEFAULT_API_VERSION);
 
         Self {
-            client: Client::new(),
+            client: http_client(DEFAULT_REQUEST_TIMEOUT_SECS),
             api_key: api_key.to_string(),
             deployment: deployment.to_string(),
             endpoint_url: endpoint_url.trim_end_matches('/').to_string(),
             api_version: version.to_string(),
+            max_retries: DEFAULT_MAX_RETRIES,
         }
     }
 
@@ -92,6 +96,10 @@ struct AzureChoice {
 
 #[derive(Debug, Deserialize)]
 struct AzureUsage {
+    #[serde(default)]
+    prompt_tokens: i32,
+    #[serde(default)]
+    completion_tokens: i32,
     total_tokens: i32,
 }
 
@@ -107,6 +115,16 @@ struct AzureDeployment {
     status: String,
 }
 
+impl HttpProvider for AzureProvider {
+    fn client_mut(&mut self) -> &mut Client {
+        &mut self.client
+    }
+
+    fn max_retries_mut(&mut self) -> &mut u32 {
+        &mut self.max_retries
+    }
+}
+
 #[async_trait]
 impl AiProvider for AzureProvider {
     fn provider_type(&self) -> AiProviderType {
@@ -152,15 +170,15 @@ impl AiProvider for AzureProvider {
             },
         };
 
-        let response = self
-            .client
-            .post(&url)
-            .header("api-key", &self.api_key)
-            .header("Content-Type", "application/json")
-            .json(&azure_request)
-            .send()
-            .await
-            .map_err(|e| format!("Failed to send request to Azure OpenAI: {}", e))?;
+        let response = send_with_retry("Azure OpenAI", self.max_retries, || {
+            self.client
+                .post(&url)
+                .header("api-key", &self.api_key)
+                .header("Content-Type", "application/json")
+                .json(&azure_request)
+        })
+        .await
+        .map_err(|e| request_error("Failed to send request to", "Azure OpenAI", e))?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -190,7 +208,10 @@ impl AiProvider for AzureProvider {
 
         Ok(CompletionResponse {
             content,
-            tokens_used: azure_response.usage.map(|u| u.total_tokens),
+            tokens_used: azure_response.usage.as_ref().map(|u| u.total_tokens),
+            usage: azure_response
+                .usage
+                .map(|u| TokenUsage::new(u.prompt_tokens, u.completion_tokens)),
             structured,
         })
     }
@@ -227,6 +248,8 @@ impl AiProvider for AzureProvider {
                             context_window: None,
                             parameter_size: None,
                             quantization_level: None,
+                            size: None,
+                            family: None,
                         })
                         .collect();
 
@@ -250,7 +273,7 @@ impl AiProvider for AzureProvider {
         self.test_connection().await.is_ok()
     }
 
-    async fn test_connection(&self) -> Result<(), String> {
+    async fn test_connection(&self) -> Result<Option<TokenUsage>, String> {
         if self.endpoint_url.is_empty() {
             return Err("Azure endpoint URL is required".to_string());
         }
@@ -280,10 +303,15 @@ impl AiProvider for AzureProvider {
             .timeout(std::time::Duration::from_secs(15))
             .send()
             .await
-            .map_err(|e| format!("Failed to connect to Azure OpenAI: {}", e))?;
+            .map_err(|e| request_error("Failed to connect to", "Azure OpenAI", e))?;
 
         if response.status().is_success() {
-            Ok(())
+            Ok(response
+                .json::<AzureChatResponse>()
+                .await
+                .ok()
+                .and_then(|r| r.usage)
+                .map(|u| TokenUsage::new(u.prompt_tokens, u.completion_tokens)))
         } else if response.status().as_u16() == 401 {
             Err("Invalid API key".to_string())
         } else if response.status().as_u16() == 404 {
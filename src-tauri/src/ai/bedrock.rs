@@ -0,0 +1,641 @@
+use async_trait::async_trait;
+use data_encoding::HEXLOWER;
+use hmac::{Hmac, Mac};
+use reqwest::{Client, Url};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::info;
+
+use super::provider::{
+    http_client, request_error, send_with_retry, AiModel, AiProvider, CompletionRequest,
+    CompletionResponse, HttpProvider, ShellCommandResponse, TokenUsage, DEFAULT_MAX_RETRIES,
+    DEFAULT_REQUEST_TIMEOUT_SECS,
+};
+use super::settings::AiProviderType;
+
+/// Service name used in the SigV4 credential scope
+const SIGNING_SERVICE: &str = "bedrock";
+
+/// Region used when none is configured
+const DEFAULT_REGION: &str = "us-east-1";
+
+/// Model used for the connection test when no model is configured
+const DEFAULT_TEST_MODEL: &str = "anthropic.claude-3-haiku-20240307-v1:0";
+
+/// AWS Bedrock provider.
+///
+/// Uses the Bedrock Runtime Converse API, which has one request shape for
+/// every model family (Claude, Titan, Llama, Mistral):
+/// - Auth via AWS SigV4 request signing
+/// - Credentials come from the API key as `ACCESS_KEY_ID:SECRET_ACCESS_KEY`
+///   (optionally followed by `:SESSION_TOKEN`) so they go through the same
+///   keyring flow as every other provider
+/// - The endpoint URL setting holds either a region (`eu-west-1`) or a full
+///   `https://bedrock-runtime.<region>.amazonaws.com` URL
+pub struct BedrockProvider {
+    client: Client,
+    credentials: Option<AwsCredentials>,
+    model: String,
+    region: String,
+    endpoint_url: String,
+    max_retries: u32,
+}
+
+/// Static AWS credentials parsed from the API key
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct AwsCredentials {
+    access_key_id: String,
+    secret_access_key: String,
+    session_token: Option<String>,
+}
+
+impl AwsCredentials {
+    /// Parse `ACCESS_KEY_ID:SECRET_ACCESS_KEY[:SESSION_TOKEN]`
+    fn parse(api_key: &str) -> Option<Self> {
+        let mut parts = api_key.trim().splitn(3, ':');
+        let access_key_id = parts.next()?.trim();
+        let secret_access_key = parts.next()?.trim();
+        if access_key_id.is_empty() || secret_access_key.is_empty() {
+            return None;
+        }
+        let session_token = parts
+            .next()
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty());
+
+        Some(Self {
+            access_key_id: access_key_id.to_string(),
+            secret_access_key: secret_access_key.to_string(),
+            session_token,
+        })
+    }
+}
+
+impl BedrockProvider {
+    pub fn new(api_key: &str, model: &str, endpoint_url: &str) -> Self {
+        let (region, endpoint_url) = Self::resolve_endpoint(endpoint_url);
+
+        Self {
+            client: http_client(DEFAULT_REQUEST_TIMEOUT_SECS),
+            credentials: AwsCredentials::parse(api_key),
+            model: model.to_string(),
+            region,
+            endpoint_url,
+            max_retries: DEFAULT_MAX_RETRIES,
+        }
+    }
+
+    /// Turn the endpoint setting into a (region, runtime base URL) pair
+    fn resolve_endpoint(endpoint_url: &str) -> (String, String) {
+        let trimmed = endpoint_url.trim().trim_end_matches('/');
+
+        if trimmed.is_empty() {
+            return (
+                DEFAULT_REGION.to_string(),
+                format!("https://bedrock-runtime.{}.amazonaws.com", DEFAULT_REGION),
+            );
+        }
+
+        if !trimmed.contains("://") {
+            // Bare region, e.g. "eu-central-1"
+            return (
+                trimmed.to_string(),
+                format!("https://bedrock-runtime.{}.amazonaws.com", trimmed),
+            );
+        }
+
+        // Full URL: pick the region out of bedrock-runtime.<region>.amazonaws.com
+        let host = Url::parse(trimmed)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_string))
+            .unwrap_or_default();
+        let region = host
+            .split('.')
+            .skip_while(|label| !label.starts_with("bedrock"))
+            .nth(1)
+            .filter(|label| *label != "amazonaws")
+            .unwrap_or(DEFAULT_REGION)
+            .to_string();
+
+        (region, trimmed.to_string())
+    }
+
+    fn curated_models() -> Vec<AiModel> {
+        let models = [
+            ("anthropic.claude-3-5-sonnet-20241022-v2:0", "Claude 3.5 Sonnet v2", 200_000),
+            ("anthropic.claude-3-5-haiku-20241022-v1:0", "Claude 3.5 Haiku", 200_000),
+            ("anthropic.claude-3-haiku-20240307-v1:0", "Claude 3 Haiku", 200_000),
+            ("amazon.titan-text-premier-v1:0", "Titan Text Premier", 32_000),
+            ("amazon.titan-text-express-v1", "Titan Text Express", 8_000),
+            ("meta.llama3-1-70b-instruct-v1:0", "Llama 3.1 70B Instruct", 128_000),
+            ("mistral.mistral-large-2407-v1:0", "Mistral Large (24.07)", 128_000),
+        ];
+
+        models
+            .into_iter()
+            .map(|(id, name, context)| AiModel {
+                id: id.to_string(),
+                name: name.to_string(),
+                provider: AiProviderType::Bedrock,
+                context_window: Some(context),
+                parameter_size: None,
+                quantization_level: None,
+                size: None,
+                family: None,
+            })
+            .collect()
+    }
+
+    /// URL of the Converse API for `model`, and the `host` header it is signed with
+    /// (including the port when it isn't the scheme's default)
+    fn converse_target(endpoint_url: &str, model: &str) -> Result<(Url, String), String> {
+        let url = Url::parse(&format!(
+            "{}/model/{}/converse",
+            endpoint_url.trim_end_matches('/'),
+            uri_encode(model, true)
+        ))
+        .map_err(|e| format!("Invalid Bedrock endpoint URL '{}': {}", endpoint_url, e))?;
+
+        let host = match (url.host_str(), url.port()) {
+            (Some(host), Some(port)) => format!("{}:{}", host, port),
+            (Some(host), None) => host.to_string(),
+            (None, _) => {
+                return Err(format!("Bedrock endpoint URL '{}' has no host", endpoint_url))
+            }
+        };
+        Ok((url, host))
+    }
+
+    /// Send a signed Converse request for the given model
+    async fn converse(
+        &self,
+        model: &str,
+        body: &ConverseRequest,
+    ) -> Result<ConverseResponse, String> {
+        let credentials = self.credentials.as_ref().ok_or_else(|| {
+            "AWS credentials missing: set the API key to ACCESS_KEY_ID:SECRET_ACCESS_KEY"
+                .to_string()
+        })?;
+
+        let (url, host) = Self::converse_target(&self.endpoint_url, model)?;
+        let path = url.path().to_string();
+        let payload = serde_json::to_vec(body)
+            .map_err(|e| format!("Failed to serialize Bedrock request: {}", e))?;
+
+        let amz_date = chrono::Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+        let authorization = sign_request(
+            "POST",
+            &host,
+            &path,
+            "",
+            &payload,
+            &amz_date,
+            &self.region,
+            SIGNING_SERVICE,
+            credentials,
+        );
+
+        // The signature stays valid for several minutes, so retries can reuse it
+        let response = send_with_retry("Bedrock", self.max_retries, || {
+            let builder = self
+                .client
+                .post(url.clone())
+                .header("Content-Type", "application/json")
+                .header("x-amz-date", &amz_date)
+                .header("Authorization", &authorization)
+                .body(payload.clone());
+            match &credentials.session_token {
+                Some(token) => builder.header("x-amz-security-token", token),
+                None => builder,
+            }
+        })
+        .await
+        .map_err(|e| request_error("Failed to send request to", "Bedrock", e))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(format!("Bedrock returned error {}: {}", status, body));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse Bedrock response: {}", e))
+    }
+}
+
+/// Build the SigV4 `Authorization` header value for a request.
+///
+/// Signs `host` and `x-amz-date` (plus `x-amz-security-token` when a session
+/// token is present). `path` must be the path exactly as sent on the wire;
+/// non-S3 services expect each segment to be encoded once more in the
+/// canonical request.
+#[allow(clippy::too_many_arguments)]
+fn sign_request(
+    method: &str,
+    host: &str,
+    path: &str,
+    query: &str,
+    payload: &[u8],
+    amz_date: &str,
+    region: &str,
+    service: &str,
+    credentials: &AwsCredentials,
+) -> String {
+    let date_stamp = &amz_date[..8];
+    let payload_hash = HEXLOWER.encode(&Sha256::digest(payload));
+
+    let mut canonical_headers = format!("host:{}\nx-amz-date:{}\n", host, amz_date);
+    let mut signed_headers = "host;x-amz-date".to_string();
+    if let Some(token) = &credentials.session_token {
+        canonical_headers.push_str(&format!("x-amz-security-token:{}\n", token));
+        signed_headers.push_str(";x-amz-security-token");
+    }
+
+    let canonical_uri = path
+        .split('/')
+        .map(|segment| uri_encode(segment, true))
+        .collect::<Vec<_>>()
+        .join("/");
+
+    let canonical_request = format!(
+        "{}\n{}\n{}\n{}\n{}\n{}",
+        method, canonical_uri, query, canonical_headers, signed_headers, payload_hash
+    );
+
+    let scope = format!("{}/{}/{}/aws4_request", date_stamp, region, service);
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+        amz_date,
+        scope,
+        HEXLOWER.encode(&Sha256::digest(canonical_request.as_bytes()))
+    );
+
+    let k_date = hmac_sha256(
+        format!("AWS4{}", credentials.secret_access_key).as_bytes(),
+        date_stamp.as_bytes(),
+    );
+    let k_region = hmac_sha256(&k_date, region.as_bytes());
+    let k_service = hmac_sha256(&k_region, service.as_bytes());
+    let k_signing = hmac_sha256(&k_service, b"aws4_request");
+    let signature = HEXLOWER.encode(&hmac_sha256(&k_signing, string_to_sign.as_bytes()));
+
+    format!(
+        "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+        credentials.access_key_id, scope, signed_headers, signature
+    )
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// RFC 3986 percent-encoding as required by SigV4 (unreserved characters kept)
+fn uri_encode(input: &str, encode_slash: bool) -> String {
+    let mut encoded = String::with_capacity(input.len());
+    for byte in input.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            b'/' if !encode_slash => encoded.push('/'),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+// --- Bedrock Converse API types ---
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ConverseRequest {
+    messages: Vec<ConverseMessage>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    system: Vec<ConverseText>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    inference_config: Option<InferenceConfig>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ConverseMessage {
+    role: String,
+    content: Vec<ConverseContent>,
+}
+
+#[derive(Debug, Serialize)]
+struct ConverseText {
+    text: String,
+}
+
+/// Content block; non-text blocks (tool use, images) are ignored
+#[derive(Debug, Serialize, Deserialize)]
+struct ConverseContent {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    text: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct InferenceConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_tokens: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ConverseResponse {
+    output: ConverseOutput,
+    #[serde(default)]
+    usage: Option<ConverseUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ConverseOutput {
+    message: Option<ConverseMessage>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ConverseUsage {
+    #[serde(default)]
+    input_tokens: i32,
+    #[serde(default)]
+    output_tokens: i32,
+    total_tokens: Option<i32>,
+}
+
+impl HttpProvider for BedrockProvider {
+    fn client_mut(&mut self) -> &mut Client {
+        &mut self.client
+    }
+
+    fn max_retries_mut(&mut self) -> &mut u32 {
+        &mut self.max_retries
+    }
+}
+
+#[async_trait]
+impl AiProvider for BedrockProvider {
+    fn provider_type(&self) -> AiProviderType {
+        AiProviderType::Bedrock
+    }
+
+    async fn get_completion(
+        &self,
+        request: CompletionRequest,
+    ) -> Result<CompletionResponse, String> {
+        let json_mode = request.json_mode;
+
+        info!(
+            "Sending completion request to Bedrock model '{}' in {} (json_mode={})",
+            self.model, self.region, json_mode
+        );
+
+        let converse_request = ConverseRequest {
+            messages: vec![ConverseMessage {
+                role: "user".to_string(),
+                content: vec![ConverseContent {
+                    text: Some(request.prompt),
+                }],
+            }],
+            system: request
+                .system_prompt
+                .map(|text| vec![ConverseText { text }])
+                .unwrap_or_default(),
+            inference_config: Some(InferenceConfig {
+                max_tokens: request.max_tokens,
+                temperature: request.temperature,
+            }),
+        };
+
+        let response = self.converse(&self.model, &converse_request).await?;
+
+        let content = response
+            .output
+            .message
+            .map(|m| {
+                m.content
+                    .into_iter()
+                    .filter_map(|c| c.text)
+                    .collect::<Vec<_>>()
+                    .join("")
+            })
+            .unwrap_or_default();
+
+        // Bedrock has no native JSON mode across model families; rely on the prompt
+        let structured = if json_mode {
+            serde_json::from_str::<ShellCommandResponse>(&content).ok()
+        } else {
+            None
+        };
+
+        Ok(CompletionResponse {
+            content,
+            tokens_used: response.usage.as_ref().and_then(|u| u.total_tokens),
+            usage: response
+                .usage
+                .map(|u| TokenUsage::new(u.input_tokens, u.output_tokens)),
+            structured,
+        })
+    }
+
+    async fn list_models(&self) -> Result<Vec<AiModel>, String> {
+        // Model access is granted per account and region, so listing foundation
+        // models doesn't say what can actually be invoked; offer the common IDs.
+        Ok(Self::curated_models())
+    }
+
+    async fn is_available(&self) -> bool {
+        self.credentials.is_some()
+    }
+
+    async fn test_connection(&self) -> Result<Option<TokenUsage>, String> {
+        let model = if self.model.is_empty() {
+            DEFAULT_TEST_MODEL
+        } else {
+            &self.model
+        };
+
+        // Minimal invoke: one token is enough to validate credentials and model access
+        let request = ConverseRequest {
+            messages: vec![ConverseMessage {
+                role: "user".to_string(),
+                content: vec![ConverseContent {
+                    text: Some("ping".to_string()),
+                }],
+            }],
+            system: Vec::new(),
+            inference_config: Some(InferenceConfig {
+                max_tokens: Some(1),
+                temperature: None,
+            }),
+        };
+
+        match self.converse(model, &request).await {
+            Ok(response) => Ok(response
+                .usage
+                .map(|u| TokenUsage::new(u.input_tokens, u.output_tokens))),
+            Err(e) if e.contains(" 403 ") || e.contains("UnrecognizedClientException") => {
+                Err("Invalid AWS credentials".to_string())
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_credentials() {
+        let creds = AwsCredentials::parse("AKIDEXAMPLE:secret/key+1").unwrap();
+        assert_eq!(creds.access_key_id, "AKIDEXAMPLE");
+        assert_eq!(creds.secret_access_key, "secret/key+1");
+        assert!(creds.session_token.is_none());
+
+        let creds = AwsCredentials::parse("AKID:secret:token").unwrap();
+        assert_eq!(creds.session_token.as_deref(), Some("token"));
+
+        assert!(AwsCredentials::parse("").is_none());
+        assert!(AwsCredentials::parse("AKIDONLY").is_none());
+        assert!(AwsCredentials::parse("AKID:").is_none());
+    }
+
+    #[test]
+    fn test_resolve_endpoint() {
+        assert_eq!(
+            BedrockProvider::resolve_endpoint(""),
+            (
+                "us-east-1".to_string(),
+                "https://bedrock-runtime.us-east-1.amazonaws.com".to_string()
+            )
+        );
+        assert_eq!(
+            BedrockProvider::resolve_endpoint("eu-central-1"),
+            (
+                "eu-central-1".to_string(),
+                "https://bedrock-runtime.eu-central-1.amazonaws.com".to_string()
+            )
+        );
+        assert_eq!(
+            BedrockProvider::resolve_endpoint("https://bedrock-runtime.ap-south-1.amazonaws.com/"),
+            (
+                "ap-south-1".to_string(),
+                "https://bedrock-runtime.ap-south-1.amazonaws.com".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_converse_target() {
+        let (url, host) = BedrockProvider::converse_target(
+            "https://bedrock-runtime.us-east-1.amazonaws.com",
+            "anthropic.claude-3-haiku-20240307-v1:0",
+        )
+        .unwrap();
+        assert_eq!(host, "bedrock-runtime.us-east-1.amazonaws.com");
+        assert_eq!(
+            url.as_str(),
+            "https://bedrock-runtime.us-east-1.amazonaws.com/model/anthropic.claude-3-haiku-20240307-v1%3A0/converse"
+        );
+
+        // A proxy with a path prefix, a port and a trailing slash
+        let (url, host) =
+            BedrockProvider::converse_target("http://localhost:4566/bedrock/", "m").unwrap();
+        assert_eq!(host, "localhost:4566");
+        assert_eq!(url.path(), "/bedrock/model/m/converse");
+
+        assert!(BedrockProvider::converse_target("not a url", "m").is_err());
+    }
+
+    #[test]
+    fn test_uri_encode() {
+        assert_eq!(
+            uri_encode("anthropic.claude-3-haiku-20240307-v1:0", true),
+            "anthropic.claude-3-haiku-20240307-v1%3A0"
+        );
+        assert_eq!(uri_encode("a b/c", false), "a%20b/c");
+        assert_eq!(uri_encode("%3A", true), "%253A");
+    }
+
+    #[test]
+    fn test_sign_request_matches_aws_test_suite() {
+        // "get-vanilla" case from the AWS SigV4 test suite
+        let credentials = AwsCredentials {
+            access_key_id: "AKIDEXAMPLE".to_string(),
+            secret_access_key: "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY".to_string(),
+            session_token: None,
+        };
+
+        let authorization = sign_request(
+            "GET",
+            "example.amazonaws.com",
+            "/",
+            "",
+            b"",
+            "20150830T123600Z",
+            "us-east-1",
+            "service",
+            &credentials,
+        );
+
+        assert_eq!(
+            authorization,
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/service/aws4_request, \
+             SignedHeaders=host;x-amz-date, \
+             Signature=5fa00fa31553b73ebf1942676e86291e8372ff2a2260956d9b8aae1d763fbf31"
+        );
+    }
+
+    #[test]
+    fn test_converse_request_serialization() {
+        let request = ConverseRequest {
+            messages: vec![ConverseMessage {
+                role: "user".to_string(),
+                content: vec![ConverseContent {
+                    text: Some("hi".to_string()),
+                }],
+            }],
+            system: Vec::new(),
+            inference_config: Some(InferenceConfig {
+                max_tokens: Some(1),
+                temperature: None,
+            }),
+        };
+
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["messages"][0]["content"][0]["text"], "hi");
+        assert_eq!(json["inferenceConfig"]["maxTokens"], 1);
+        assert!(json.get("system").is_none());
+    }
+
+    #[test]
+    fn test_converse_response_parsing() {
+        let body = r#"{
+            "output": {"message": {"role": "assistant", "content": [{"text": "docker ps"}]}},
+            "stopReason": "end_turn",
+            "usage": {"inputTokens": 10, "outputTokens": 3, "totalTokens": 13}
+        }"#;
+        let response: ConverseResponse = serde_json::from_str(body).unwrap();
+        let message = response.output.message.unwrap();
+        assert_eq!(message.content[0].text.as_deref(), Some("docker ps"));
+        let usage = response.usage.unwrap();
+        assert_eq!((usage.input_tokens, usage.output_tokens), (10, 3));
+        assert_eq!(usage.total_tokens, Some(13));
+    }
+
+    #[test]
+    fn test_curated_models_are_bedrock() {
+        let models = BedrockProvider::curated_models();
+        assert!(!models.is_empty());
+        assert!(models.iter().all(|m| m.provider == AiProviderType::Bedrock));
+        assert!(models.iter().any(|m| m.id.starts_with("anthropic.claude")));
+        assert!(models.iter().any(|m| m.id.starts_with("amazon.titan")));
+    }
+}
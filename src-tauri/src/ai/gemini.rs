@@ -4,7 +4,9 @@ use serde::{Deserialize, Serialize};
 use tracing::info;
 
 use super::provider::{
-    AiModel, AiProvider, CompletionRequest, CompletionResponse, ShellCommandResponse,
+    http_client, request_error, send_with_retry, AiModel, AiProvider, CompletionRequest,
+    CompletionResponse, HttpProvider, ShellCommandResponse, TokenUsage, DEFAULT_MAX_RETRIES,
+    DEFAULT_REQUEST_TIMEOUT_SECS,
 };
 use super::settings::AiProviderType;
 
@@ -19,6 +21,7 @@ pub struct GeminiProvider {
     api_key: String,
     model: String,
     endpoint_url: String,
+    max_retries: u32,
 }
 
 impl GeminiProvider {
@@ -30,10 +33,11 @@ impl GeminiProvider {
         };
 
         Self {
-            client: Client::new(),
+            client: http_client(DEFAULT_REQUEST_TIMEOUT_SECS),
             api_key: api_key.to_string(),
             model: model.to_string(),
             endpoint_url: url,
+            max_retries: DEFAULT_MAX_RETRIES,
         }
     }
 
@@ -46,6 +50,8 @@ impl GeminiProvider {
                 context_window: Some(1_048_576),
                 parameter_size: None,
                 quantization_level: None,
+                size: None,
+                family: None,
             },
             AiModel {
                 id: "gemini-2.0-flash-lite".to_string(),
@@ -54,6 +60,8 @@ impl GeminiProvider {
                 context_window: Some(1_048_576),
                 parameter_size: None,
                 quantization_level: None,
+                size: None,
+                family: None,
             },
             AiModel {
                 id: "gemini-1.5-pro".to_string(),
@@ -62,6 +70,8 @@ impl GeminiProvider {
                 context_window: Some(2_097_152),
                 parameter_size: None,
                 quantization_level: None,
+                size: None,
+                family: None,
             },
             AiModel {
                 id: "gemini-1.5-flash".to_string(),
@@ -70,6 +80,8 @@ impl GeminiProvider {
                 context_window: Some(1_048_576),
                 parameter_size: None,
                 quantization_level: None,
+                size: None,
+                family: None,
             },
         ]
     }
@@ -114,7 +126,7 @@ struct GenerationConfig {
 struct GeminiResponse {
     #[serde(default)]
     candidates: Vec<GeminiCandidate>,
-    #[serde(default)]
+    #[serde(default, rename = "usageMetadata")]
     usage_metadata: Option<GeminiUsage>,
 }
 
@@ -126,6 +138,8 @@ struct GeminiCandidate {
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct GeminiUsage {
+    prompt_token_count: Option<i32>,
+    candidates_token_count: Option<i32>,
     total_token_count: Option<i32>,
 }
 
@@ -145,6 +159,16 @@ struct GeminiModelInfo {
     supported_generation_methods: Vec<String>,
 }
 
+impl HttpProvider for GeminiProvider {
+    fn client_mut(&mut self) -> &mut Client {
+        &mut self.client
+    }
+
+    fn max_retries_mut(&mut self) -> &mut u32 {
+        &mut self.max_retries
+    }
+}
+
 #[async_trait]
 impl AiProvider for GeminiProvider {
     fn provider_type(&self) -> AiProviderType {
@@ -194,14 +218,14 @@ impl AiProvider for GeminiProvider {
             generation_config,
         };
 
-        let response = self
-            .client
-            .post(&url)
-            .header("Content-Type", "application/json")
-            .json(&gemini_request)
-            .send()
-            .await
-            .map_err(|e| format!("Failed to send request to Gemini: {}", e))?;
+        let response = send_with_retry("Gemini", self.max_retries, || {
+            self.client
+                .post(&url)
+                .header("Content-Type", "application/json")
+                .json(&gemini_request)
+        })
+        .await
+        .map_err(|e| request_error("Failed to send request to", "Gemini", e))?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -231,7 +255,13 @@ impl AiProvider for GeminiProvider {
             content,
             tokens_used: gemini_response
                 .usage_metadata
+                .as_ref()
                 .and_then(|u| u.total_token_count),
+            usage: gemini_response.usage_metadata.map(|u| TokenUsage {
+                prompt_tokens: u.prompt_token_count.unwrap_or(0),
+                completion_tokens: u.candidates_token_count.unwrap_or(0),
+                total_tokens: u.total_token_count.unwrap_or(0),
+            }),
             structured,
         })
     }
@@ -272,6 +302,8 @@ impl AiProvider for GeminiProvider {
                                     context_window: m.input_token_limit,
                                     parameter_size: None,
                                     quantization_level: None,
+                                    size: None,
+                                    family: None,
                                 }
                             })
                             .collect();
@@ -297,7 +329,7 @@ impl AiProvider for GeminiProvider {
         self.test_connection().await.is_ok()
     }
 
-    async fn test_connection(&self) -> Result<(), String> {
+    async fn test_connection(&self) -> Result<Option<TokenUsage>, String> {
         let url = format!(
             "{}/v1beta/models?key={}",
             self.endpoint_url, self.api_key
@@ -309,10 +341,10 @@ impl AiProvider for GeminiProvider {
             .timeout(std::time::Duration::from_secs(10))
             .send()
             .await
-            .map_err(|e| format!("Failed to connect to Gemini: {}", e))?;
+            .map_err(|e| request_error("Failed to connect to", "Gemini", e))?;
 
         if response.status().is_success() {
-            Ok(())
+            Ok(None)
         } else if response.status().as_u16() == 400 || response.status().as_u16() == 403 {
             Err("Invalid API key".to_string())
         } else {
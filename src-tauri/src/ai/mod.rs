@@ -2,10 +2,11 @@
 //!
 //! This module provides a multi-provider AI abstraction for shell command suggestions.
 //! Supports Ollama (local), OpenAI, Anthropic, Azure OpenAI, Groq, Google Gemini,
-//! DeepSeek, and Mistral.
+//! DeepSeek, Mistral, and AWS Bedrock.
 
 mod anthropic;
 mod azure;
+mod bedrock;
 mod gemini;
 mod ollama;
 mod openai;
@@ -21,6 +22,7 @@ pub use anthropic::{
 
 // Provider implementations
 pub use azure::AzureProvider;
+pub use bedrock::BedrockProvider;
 pub use gemini::GeminiProvider;
 pub use ollama::OllamaProvider;
 pub use openai::OpenAiProvider;
@@ -29,12 +31,14 @@ pub use openai_compat::OpenAiCompatProvider;
 // Common provider types
 pub use provider::{
     get_shell_system_prompt, strip_markdown, AiModel, AiProvider, CommandAlternative,
-    CompletionRequest, CompletionResponse, ShellCommandResponse, SHELL_COMMAND_JSON_SCHEMA,
+    CompletionRequest, CompletionResponse, ShellCommandResponse, TokenUsage,
+    SHELL_COMMAND_JSON_SCHEMA, TIMEOUT_ERROR,
 };
 
 // Settings
 pub use settings::{AiProviderType, AiSettings};
 
+use provider::HttpProvider;
 use std::sync::Arc;
 
 /// Create an AI provider based on settings
@@ -42,50 +46,75 @@ pub fn create_provider(settings: &AiSettings) -> Arc<dyn AiProvider> {
     let api_key = settings.api_key.as_deref().unwrap_or("");
 
     match settings.provider {
-        AiProviderType::Ollama => Arc::new(OllamaProvider::new(
-            &settings.endpoint_url,
-            &settings.model_name,
-        )),
-        AiProviderType::OpenAi => Arc::new(OpenAiProvider::new(
-            api_key,
-            &settings.model_name,
-            &settings.endpoint_url,
-        )),
-        AiProviderType::Anthropic => Arc::new(AnthropicProvider::new(
-            api_key,
-            &settings.model_name,
-        )),
-        AiProviderType::AzureOpenAi => Arc::new(AzureProvider::new(
-            api_key,
-            &settings.model_name,
-            &settings.endpoint_url,
-            settings.api_version.as_deref(),
-        )),
-        AiProviderType::Groq => Arc::new(OpenAiCompatProvider::new(
-            AiProviderType::Groq,
-            api_key,
-            &settings.model_name,
-            &settings.endpoint_url,
-            openai_compat::groq_models(),
-        )),
-        AiProviderType::Gemini => Arc::new(GeminiProvider::new(
-            api_key,
-            &settings.model_name,
-            &settings.endpoint_url,
-        )),
-        AiProviderType::DeepSeek => Arc::new(OpenAiCompatProvider::new(
-            AiProviderType::DeepSeek,
-            api_key,
-            &settings.model_name,
-            &settings.endpoint_url,
-            openai_compat::deepseek_models(),
-        )),
-        AiProviderType::Mistral => Arc::new(OpenAiCompatProvider::new(
-            AiProviderType::Mistral,
-            api_key,
-            &settings.model_name,
-            &settings.endpoint_url,
-            openai_compat::mistral_models(),
-        )),
+        AiProviderType::Ollama => configured(
+            OllamaProvider::new(&settings.endpoint_url, &settings.model_name),
+            settings,
+        ),
+        AiProviderType::OpenAi => configured(
+            OpenAiProvider::new(api_key, &settings.model_name, &settings.endpoint_url),
+            settings,
+        ),
+        AiProviderType::Anthropic => {
+            configured(AnthropicProvider::new(api_key, &settings.model_name), settings)
+        }
+        AiProviderType::AzureOpenAi => configured(
+            AzureProvider::new(
+                api_key,
+                &settings.model_name,
+                &settings.endpoint_url,
+                settings.api_version.as_deref(),
+            ),
+            settings,
+        ),
+        AiProviderType::Groq => configured(
+            OpenAiCompatProvider::new(
+                AiProviderType::Groq,
+                api_key,
+                &settings.model_name,
+                &settings.endpoint_url,
+                openai_compat::groq_models(),
+            ),
+            settings,
+        ),
+        AiProviderType::Gemini => configured(
+            GeminiProvider::new(api_key, &settings.model_name, &settings.endpoint_url),
+            settings,
+        ),
+        AiProviderType::DeepSeek => configured(
+            OpenAiCompatProvider::new(
+                AiProviderType::DeepSeek,
+                api_key,
+                &settings.model_name,
+                &settings.endpoint_url,
+                openai_compat::deepseek_models(),
+            ),
+            settings,
+        ),
+        AiProviderType::Mistral => configured(
+            OpenAiCompatProvider::new(
+                AiProviderType::Mistral,
+                api_key,
+                &settings.model_name,
+                &settings.endpoint_url,
+                openai_compat::mistral_models(),
+            ),
+            settings,
+        ),
+        AiProviderType::Bedrock => configured(
+            BedrockProvider::new(api_key, &settings.model_name, &settings.endpoint_url),
+            settings,
+        ),
     }
 }
+
+/// Apply the retry and timeout settings every HTTP provider shares
+fn configured<P>(provider: P, settings: &AiSettings) -> Arc<dyn AiProvider>
+where
+    P: HttpProvider + AiProvider + 'static,
+{
+    Arc::new(
+        provider
+            .with_max_retries(settings.max_retries)
+            .with_request_timeout(settings.request_timeout_secs),
+    )
+}
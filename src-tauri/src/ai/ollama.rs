@@ -1,9 +1,16 @@
 use async_trait::async_trait;
+use futures_util::StreamExt;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
 use tracing::info;
 
-use super::provider::{AiModel, AiProvider, CompletionRequest, CompletionResponse, ShellCommandResponse};
+use super::provider::{
+    drain_lines, http_client, request_error, send_with_retry, AiModel, AiProvider,
+    CompletionRequest, CompletionResponse, HttpProvider, ShellCommandResponse, TokenUsage,
+    DEFAULT_MAX_RETRIES, DEFAULT_REQUEST_TIMEOUT_SECS,
+};
 use super::settings::AiProviderType;
 
 /// Ollama API provider
@@ -11,17 +18,44 @@ pub struct OllamaProvider {
     client: Client,
     base_url: String,
     model: String,
+    max_retries: u32,
 }
 
 impl OllamaProvider {
     pub fn new(endpoint_url: &str, model: &str) -> Self {
         Self {
-            client: Client::new(),
+            client: http_client(DEFAULT_REQUEST_TIMEOUT_SECS),
             base_url: endpoint_url.trim_end_matches('/').to_string(),
             model: model.to_string(),
+            max_retries: DEFAULT_MAX_RETRIES,
         }
     }
 
+    /// Popular models to offer when the server can't be reached; they still
+    /// need to be pulled before use
+    fn curated_models(&self) -> Vec<AiModel> {
+        [
+            ("llama3.2", "Llama 3.2", 131_072, "3.2B", "llama"),
+            ("qwen2.5-coder", "Qwen 2.5 Coder", 32_768, "7.6B", "qwen2"),
+            ("gemma3", "Gemma 3", 131_072, "4.3B", "gemma3"),
+            ("mistral", "Mistral", 32_768, "7.2B", "llama"),
+        ]
+        .into_iter()
+        .map(
+            |(id, name, context_window, parameter_size, family)| AiModel {
+                id: id.to_string(),
+                name: name.to_string(),
+                provider: AiProviderType::Ollama,
+                context_window: Some(context_window),
+                parameter_size: Some(parameter_size.to_string()),
+                quantization_level: None,
+                size: None,
+                family: Some(family.to_string()),
+            },
+        )
+        .collect()
+    }
+
     /// Fetch detailed model info from /api/show endpoint
     async fn get_model_info(
         &self,
@@ -90,21 +124,81 @@ impl OllamaProvider {
         Ok((context_window, param_size, quant_level))
     }
 
-    /// Pull/download a model from Ollama registry
-    pub async fn pull_model(&self, model_name: &str) -> Result<String, String> {
+    /// Check whether the configured model advertises tool calling via /api/show
+    ///
+    /// Newer Ollama versions report a `capabilities` list; older ones only expose
+    /// the prompt template, which references `.Tools` when tools are supported.
+    pub async fn supports_tools(&self) -> bool {
+        let url = format!("{}/api/show", self.base_url);
+        let request = OllamaShowRequest {
+            name: self.model.clone(),
+        };
+
+        let response = match self
+            .client
+            .post(&url)
+            .json(&request)
+            .timeout(std::time::Duration::from_secs(10))
+            .send()
+            .await
+        {
+            Ok(response) if response.status().is_success() => response,
+            _ => return false,
+        };
+
+        match response.json::<OllamaShowResponse>().await {
+            Ok(show_response) => match show_response.capabilities {
+                Some(capabilities) => capabilities.iter().any(|c| c == "tools"),
+                None => show_response
+                    .template
+                    .is_some_and(|template| template.contains(".Tools")),
+            },
+            Err(_) => false,
+        }
+    }
+
+    /// Build a /api/generate request body
+    fn generate_request(&self, request: CompletionRequest, stream: bool) -> OllamaGenerateRequest {
+        OllamaGenerateRequest {
+            model: self.model.clone(),
+            prompt: request.prompt,
+            system: request.system_prompt,
+            stream,
+            options: Some(OllamaOptions {
+                temperature: request.temperature,
+                num_predict: request.max_tokens,
+            }),
+            format: if request.json_mode {
+                Some(serde_json::json!("json"))
+            } else {
+                None
+            },
+        }
+    }
+
+    /// Pull a model, calling `on_progress` with each NDJSON progress update
+    /// Ollama streams. Resolves with the final status once the pull finishes;
+    /// cancelling `cancel` aborts the download.
+    pub async fn pull_model(
+        &self,
+        model_name: &str,
+        cancel: CancellationToken,
+        mut on_progress: impl FnMut(&OllamaPullProgress),
+    ) -> Result<String, String> {
         let url = format!("{}/api/pull", self.base_url);
         let request = OllamaPullRequest {
             name: model_name.to_string(),
-            stream: false, // Non-streaming for simplicity
+            stream: true,
         };
 
         info!("Pulling model from Ollama: {}", model_name);
 
+        // No overall timeout: large models take long, and the client's read
+        // timeout still catches a stalled download
         let response = self
             .client
             .post(&url)
             .json(&request)
-            .timeout(std::time::Duration::from_secs(3600)) // 1 hour timeout for large models
             .send()
             .await
             .map_err(|e| format!("Failed to pull model: {}", e))?;
@@ -115,13 +209,39 @@ impl OllamaProvider {
             return Err(format!("Ollama returned error {}: {}", status, body));
         }
 
-        // Parse the final status
-        let pull_response: OllamaPullProgress = response
-            .json()
-            .await
-            .map_err(|e| format!("Failed to parse pull response: {}", e))?;
+        let mut stream = response.bytes_stream();
+        let mut buffer = Vec::new();
+        let mut status = String::new();
 
-        Ok(pull_response.status)
+        loop {
+            let chunk = tokio::select! {
+                chunk = stream.next() => chunk,
+                _ = cancel.cancelled() => {
+                    return Err(format!("Pull of {} was cancelled", model_name));
+                }
+            };
+            let Some(chunk) = chunk else { break };
+            let chunk = chunk.map_err(|e| format!("Ollama pull stream error: {}", e))?;
+            buffer.extend_from_slice(&chunk);
+
+            for line in drain_lines(&mut buffer) {
+                let progress: OllamaPullProgress = serde_json::from_str(&line)
+                    .map_err(|e| format!("Failed to parse pull response: {}", e))?;
+                if let Some(error) = progress.error {
+                    return Err(format!("Failed to pull {}: {}", model_name, error));
+                }
+                on_progress(&progress);
+                status = progress.status;
+            }
+        }
+
+        if status != "success" {
+            return Err(format!(
+                "Pull of {} ended before it finished (last status: {})",
+                model_name, status
+            ));
+        }
+        Ok(status)
     }
 
     /// Delete a model from Ollama
@@ -181,6 +301,21 @@ struct OllamaGenerateResponse {
     response: String,
     #[serde(default)]
     eval_count: Option<i32>,
+    #[serde(default)]
+    prompt_eval_count: Option<i32>,
+}
+
+/// One NDJSON line from a streaming /api/generate response
+#[derive(Debug, Deserialize)]
+struct OllamaStreamChunk {
+    #[serde(default)]
+    response: String,
+    #[serde(default)]
+    done: bool,
+    #[serde(default)]
+    eval_count: Option<i32>,
+    #[serde(default)]
+    prompt_eval_count: Option<i32>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -192,8 +327,15 @@ struct OllamaTagsResponse {
 struct OllamaModel {
     name: String,
     #[serde(default)]
-    #[allow(dead_code)]
     size: Option<u64>,
+    #[serde(default)]
+    details: Option<OllamaModelDetails>,
+}
+
+/// Ollama reports prompt and generated token counts as `prompt_eval_count`/`eval_count`.
+/// `prompt_eval_count` is omitted when the prompt was served from cache.
+fn ollama_usage(prompt_eval_count: Option<i32>, eval_count: Option<i32>) -> Option<TokenUsage> {
+    eval_count.map(|completion| TokenUsage::new(prompt_eval_count.unwrap_or(0), completion))
 }
 
 // Types for /api/show endpoint
@@ -208,10 +350,16 @@ struct OllamaShowResponse {
     model_info: Option<serde_json::Value>,
     #[serde(default)]
     details: Option<OllamaModelDetails>,
+    #[serde(default)]
+    capabilities: Option<Vec<String>>,
+    #[serde(default)]
+    template: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
 struct OllamaModelDetails {
+    #[serde(default)]
+    family: Option<String>,
     #[serde(default)]
     parameter_size: Option<String>,
     #[serde(default)]
@@ -227,6 +375,7 @@ struct OllamaPullRequest {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OllamaPullProgress {
+    #[serde(default)]
     pub status: String,
     #[serde(default)]
     pub digest: Option<String>,
@@ -234,6 +383,21 @@ pub struct OllamaPullProgress {
     pub total: Option<u64>,
     #[serde(default)]
     pub completed: Option<u64>,
+    /// Set instead of a status when the pull fails mid-stream
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+impl OllamaPullProgress {
+    /// Percent of the current layer downloaded, when Ollama reports sizes
+    pub fn percent(&self) -> Option<f32> {
+        match (self.completed, self.total) {
+            (Some(completed), Some(total)) if total > 0 => {
+                Some((completed as f64 / total as f64 * 100.0) as f32)
+            }
+            _ => None,
+        }
+    }
 }
 
 // Types for /api/delete endpoint
@@ -242,6 +406,16 @@ struct OllamaDeleteRequest {
     name: String,
 }
 
+impl HttpProvider for OllamaProvider {
+    fn client_mut(&mut self) -> &mut Client {
+        &mut self.client
+    }
+
+    fn max_retries_mut(&mut self) -> &mut u32 {
+        &mut self.max_retries
+    }
+}
+
 #[async_trait]
 impl AiProvider for OllamaProvider {
     fn provider_type(&self) -> AiProviderType {
@@ -254,29 +428,15 @@ impl AiProvider for OllamaProvider {
 
         info!("Sending completion request to Ollama: {} (json_mode={})", url, json_mode);
 
-        let ollama_request = OllamaGenerateRequest {
-            model: self.model.clone(),
-            prompt: request.prompt,
-            system: request.system_prompt,
-            stream: false,
-            options: Some(OllamaOptions {
-                temperature: request.temperature,
-                num_predict: request.max_tokens,
-            }),
-            format: if json_mode {
-                Some(serde_json::json!("json"))
-            } else {
-                None
-            },
-        };
+        let ollama_request = self.generate_request(request, false);
 
-        let response = self
-            .client
-            .post(&url)
-            .json(&ollama_request)
-            .send()
-            .await
-            .map_err(|e| format!("Failed to send request to Ollama: {}", e))?;
+        let response = send_with_retry("Ollama", self.max_retries, || {
+            self.client
+                .post(&url)
+                .json(&ollama_request)
+        })
+        .await
+        .map_err(|e| request_error("Failed to send request to", "Ollama", e))?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -299,6 +459,73 @@ impl AiProvider for OllamaProvider {
         Ok(CompletionResponse {
             content: ollama_response.response,
             tokens_used: ollama_response.eval_count,
+            usage: ollama_usage(ollama_response.prompt_eval_count, ollama_response.eval_count),
+            structured,
+        })
+    }
+
+    async fn complete_stream(
+        &self,
+        request: CompletionRequest,
+        tx: mpsc::Sender<String>,
+    ) -> Result<CompletionResponse, String> {
+        let url = format!("{}/api/generate", self.base_url);
+        let json_mode = request.json_mode;
+
+        info!("Streaming completion from Ollama: {} (json_mode={})", url, json_mode);
+
+        let ollama_request = self.generate_request(request, true);
+
+        let response = send_with_retry("Ollama", self.max_retries, || {
+            self.client
+                .post(&url)
+                .json(&ollama_request)
+        })
+        .await
+        .map_err(|e| request_error("Failed to send request to", "Ollama", e))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(format!("Ollama returned error {}: {}", status, body));
+        }
+
+        let mut stream = response.bytes_stream();
+        let mut buffer = Vec::new();
+        let mut content = String::new();
+        let mut tokens_used = None;
+        let mut usage = None;
+
+        'outer: while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(|e| format!("Ollama stream error: {}", e))?;
+            buffer.extend_from_slice(&chunk);
+
+            for line in drain_lines(&mut buffer) {
+                let parsed: OllamaStreamChunk = serde_json::from_str(&line)
+                    .map_err(|e| format!("Failed to parse Ollama stream chunk: {}", e))?;
+
+                if !parsed.response.is_empty() {
+                    content.push_str(&parsed.response);
+                    let _ = tx.send(parsed.response).await;
+                }
+                if parsed.done {
+                    tokens_used = parsed.eval_count;
+                    usage = ollama_usage(parsed.prompt_eval_count, parsed.eval_count);
+                    break 'outer;
+                }
+            }
+        }
+
+        let structured = if json_mode {
+            serde_json::from_str::<ShellCommandResponse>(&content).ok()
+        } else {
+            None
+        };
+
+        Ok(CompletionResponse {
+            content,
+            tokens_used,
+            usage,
             structured,
         })
     }
@@ -306,12 +533,13 @@ impl AiProvider for OllamaProvider {
     async fn list_models(&self) -> Result<Vec<AiModel>, String> {
         let url = format!("{}/api/tags", self.base_url);
 
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| format!("Failed to get models from Ollama: {}", e))?;
+        let response = match self.client.get(&url).send().await {
+            Ok(response) => response,
+            Err(e) => {
+                info!("Ollama is unreachable, using curated model list: {}", e);
+                return Ok(self.curated_models());
+            }
+        };
 
         if !response.status().is_success() {
             let status = response.status();
@@ -324,19 +552,22 @@ impl AiProvider for OllamaProvider {
             .await
             .map_err(|e| format!("Failed to parse Ollama models response: {}", e))?;
 
-        // Fetch detailed info for each model
+        // Fetch detailed info for each model; /api/tags already has the basics
         let mut models = Vec::new();
         for m in tags_response.models {
             let (context_window, parameter_size, quantization_level) =
                 self.get_model_info(&m.name).await.unwrap_or((None, None, None));
+            let details = m.details.unwrap_or_default();
 
             models.push(AiModel {
                 id: m.name.clone(),
                 name: m.name,
                 provider: AiProviderType::Ollama,
                 context_window,
-                parameter_size,
-                quantization_level,
+                parameter_size: parameter_size.or(details.parameter_size),
+                quantization_level: quantization_level.or(details.quantization_level),
+                size: m.size,
+                family: details.family,
             });
         }
 
@@ -347,7 +578,7 @@ impl AiProvider for OllamaProvider {
         self.test_connection().await.is_ok()
     }
 
-    async fn test_connection(&self) -> Result<(), String> {
+    async fn test_connection(&self) -> Result<Option<TokenUsage>, String> {
         let url = format!("{}/api/tags", self.base_url);
 
         let response = self
@@ -356,10 +587,10 @@ impl AiProvider for OllamaProvider {
             .timeout(std::time::Duration::from_secs(5))
             .send()
             .await
-            .map_err(|e| format!("Failed to connect to Ollama: {}", e))?;
+            .map_err(|e| request_error("Failed to connect to", "Ollama", e))?;
 
         if response.status().is_success() {
-            Ok(())
+            Ok(None)
         } else {
             Err(format!("Ollama returned status: {}", response.status()))
         }
@@ -369,3 +600,60 @@ impl AiProvider for OllamaProvider {
 // Note: Tool calling is now handled by the Rig framework in agent/rig_executor.rs
 // The manual tool types have been removed as they're no longer needed.
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tags_response() {
+        let tags: OllamaTagsResponse = serde_json::from_str(
+            r#"{"models":[{
+                "name":"llama3.2:latest","model":"llama3.2:latest","size":2019393189,
+                "digest":"a80c4f17acd5","details":{"format":"gguf","family":"llama",
+                "families":["llama"],"parameter_size":"3.2B","quantization_level":"Q4_K_M"}
+            },{"name":"custom"}]}"#,
+        )
+        .unwrap();
+
+        let llama = &tags.models[0];
+        assert_eq!(llama.size, Some(2_019_393_189));
+        let details = llama.details.as_ref().unwrap();
+        assert_eq!(details.family.as_deref(), Some("llama"));
+        assert_eq!(details.parameter_size.as_deref(), Some("3.2B"));
+        assert!(tags.models[1].details.is_none());
+    }
+
+    #[test]
+    fn test_pull_progress_percent() {
+        let progress: OllamaPullProgress = serde_json::from_str(
+            r#"{"status":"pulling dde5aa3fc5ff","digest":"sha256:dde5aa3fc5ff","total":2019377376,"completed":504844344}"#,
+        )
+        .unwrap();
+        assert_eq!(progress.percent().map(|p| p.round()), Some(25.0));
+
+        let progress: OllamaPullProgress =
+            serde_json::from_str(r#"{"status":"verifying sha256 digest"}"#).unwrap();
+        assert_eq!(progress.percent(), None);
+
+        let progress: OllamaPullProgress =
+            serde_json::from_str(r#"{"error":"pull model manifest: file does not exist"}"#)
+                .unwrap();
+        assert!(progress.error.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_list_models_falls_back_when_unreachable() {
+        // Bind and release a port so nothing is listening on it
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let provider = OllamaProvider::new(&format!("http://127.0.0.1:{}", port), "llama3.2");
+
+        let models = provider.list_models().await.unwrap();
+        assert!(!models.is_empty());
+        assert!(models.iter().any(|m| m.id == "llama3.2"));
+        assert!(models.iter().all(|m| m.size.is_none()));
+    }
+}
@@ -1,9 +1,15 @@
 use async_trait::async_trait;
+use futures_util::StreamExt;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
 use tracing::info;
 
-use super::provider::{AiModel, AiProvider, CompletionRequest, CompletionResponse, ShellCommandResponse};
+use super::provider::{
+    drain_lines, http_client, request_error, send_with_retry, AiModel, AiProvider,
+    CompletionRequest, CompletionResponse, HttpProvider, ShellCommandResponse, TokenUsage,
+    DEFAULT_MAX_RETRIES, DEFAULT_REQUEST_TIMEOUT_SECS,
+};
 use super::settings::AiProviderType;
 
 /// OpenAI API provider
@@ -12,6 +18,7 @@ pub struct OpenAiProvider {
     api_key: String,
     model: String,
     endpoint_url: String,
+    max_retries: u32,
 }
 
 impl OpenAiProvider {
@@ -24,10 +31,11 @@ impl OpenAiProvider {
         };
 
         Self {
-            client: Client::new(),
+            client: http_client(DEFAULT_REQUEST_TIMEOUT_SECS),
             api_key: api_key.to_string(),
             model: model.to_string(),
             endpoint_url: url,
+            max_retries: DEFAULT_MAX_RETRIES,
         }
     }
 
@@ -36,6 +44,40 @@ impl OpenAiProvider {
         &self.endpoint_url
     }
 
+    /// Build a chat completions request body
+    fn chat_request(&self, request: CompletionRequest, stream: bool) -> OpenAiChatRequest {
+        let mut messages = Vec::new();
+
+        // Add system message if provided
+        if let Some(system_prompt) = request.system_prompt {
+            messages.push(OpenAiMessage {
+                role: "system".to_string(),
+                content: system_prompt,
+            });
+        }
+
+        // Add user message
+        messages.push(OpenAiMessage {
+            role: "user".to_string(),
+            content: request.prompt,
+        });
+
+        OpenAiChatRequest {
+            model: self.model.clone(),
+            messages,
+            temperature: request.temperature,
+            max_tokens: request.max_tokens,
+            response_format: if request.json_mode {
+                Some(ResponseFormat {
+                    format_type: "json_object".to_string(),
+                })
+            } else {
+                None
+            },
+            stream: stream.then_some(true),
+        }
+    }
+
     /// Return a curated list of recommended models as fallback
     fn curated_models(&self) -> Vec<AiModel> {
         vec![
@@ -46,6 +88,8 @@ impl OpenAiProvider {
                 context_window: Some(128_000),
                 parameter_size: None,
                 quantization_level: None,
+                size: None,
+                family: None,
             },
             AiModel {
                 id: "gpt-4o-mini".to_string(),
@@ -54,6 +98,8 @@ impl OpenAiProvider {
                 context_window: Some(128_000),
                 parameter_size: None,
                 quantization_level: None,
+                size: None,
+                family: None,
             },
             AiModel {
                 id: "gpt-4-turbo".to_string(),
@@ -62,6 +108,8 @@ impl OpenAiProvider {
                 context_window: Some(128_000),
                 parameter_size: None,
                 quantization_level: None,
+                size: None,
+                family: None,
             },
             AiModel {
                 id: "gpt-3.5-turbo".to_string(),
@@ -70,6 +118,8 @@ impl OpenAiProvider {
                 context_window: Some(16_385),
                 parameter_size: None,
                 quantization_level: None,
+                size: None,
+                family: None,
             },
         ]
     }
@@ -88,6 +138,9 @@ struct OpenAiChatRequest {
     /// Response format for JSON mode
     #[serde(skip_serializing_if = "Option::is_none")]
     response_format: Option<ResponseFormat>,
+    /// Stream the response as server-sent events
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream: Option<bool>,
 }
 
 /// OpenAI response format for JSON mode
@@ -115,8 +168,31 @@ struct OpenAiChoice {
     message: OpenAiMessage,
 }
 
+/// One `data:` event from a streaming chat completion
+#[derive(Debug, Deserialize)]
+struct OpenAiStreamChunk {
+    #[serde(default)]
+    choices: Vec<OpenAiStreamChoice>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAiStreamChoice {
+    #[serde(default)]
+    delta: OpenAiDelta,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct OpenAiDelta {
+    #[serde(default)]
+    content: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 struct OpenAiUsage {
+    #[serde(default)]
+    prompt_tokens: i32,
+    #[serde(default)]
+    completion_tokens: i32,
     total_tokens: i32,
 }
 
@@ -177,6 +253,16 @@ fn get_known_context_window(id: &str) -> Option<i64> {
     }
 }
 
+impl HttpProvider for OpenAiProvider {
+    fn client_mut(&mut self) -> &mut Client {
+        &mut self.client
+    }
+
+    fn max_retries_mut(&mut self) -> &mut u32 {
+        &mut self.max_retries
+    }
+}
+
 #[async_trait]
 impl AiProvider for OpenAiProvider {
     fn provider_type(&self) -> AiProviderType {
@@ -189,45 +275,17 @@ impl AiProvider for OpenAiProvider {
 
         info!("Sending completion request to OpenAI (json_mode={})", json_mode);
 
-        let mut messages = Vec::new();
-
-        // Add system message if provided
-        if let Some(system_prompt) = request.system_prompt {
-            messages.push(OpenAiMessage {
-                role: "system".to_string(),
-                content: system_prompt,
-            });
-        }
+        let openai_request = self.chat_request(request, false);
 
-        // Add user message
-        messages.push(OpenAiMessage {
-            role: "user".to_string(),
-            content: request.prompt,
-        });
-
-        let openai_request = OpenAiChatRequest {
-            model: self.model.clone(),
-            messages,
-            temperature: request.temperature,
-            max_tokens: request.max_tokens,
-            response_format: if json_mode {
-                Some(ResponseFormat {
-                    format_type: "json_object".to_string(),
-                })
-            } else {
-                None
-            },
-        };
-
-        let response = self
-            .client
-            .post(&url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .header("Content-Type", "application/json")
-            .json(&openai_request)
-            .send()
-            .await
-            .map_err(|e| format!("Failed to send request to OpenAI: {}", e))?;
+        let response = send_with_retry("OpenAI", self.max_retries, || {
+            self.client
+                .post(&url)
+                .header("Authorization", format!("Bearer {}", self.api_key))
+                .header("Content-Type", "application/json")
+                .json(&openai_request)
+        })
+        .await
+        .map_err(|e| request_error("Failed to send request to", "OpenAI", e))?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -255,7 +313,80 @@ impl AiProvider for OpenAiProvider {
 
         Ok(CompletionResponse {
             content,
-            tokens_used: openai_response.usage.map(|u| u.total_tokens),
+            tokens_used: openai_response.usage.as_ref().map(|u| u.total_tokens),
+            usage: openai_response
+                .usage
+                .map(|u| TokenUsage::new(u.prompt_tokens, u.completion_tokens)),
+            structured,
+        })
+    }
+
+    async fn complete_stream(
+        &self,
+        request: CompletionRequest,
+        tx: mpsc::Sender<String>,
+    ) -> Result<CompletionResponse, String> {
+        let url = format!("{}/v1/chat/completions", self.base_url());
+        let json_mode = request.json_mode;
+
+        info!("Streaming completion from OpenAI (json_mode={})", json_mode);
+
+        let openai_request = self.chat_request(request, true);
+
+        let response = send_with_retry("OpenAI", self.max_retries, || {
+            self.client
+                .post(&url)
+                .header("Authorization", format!("Bearer {}", self.api_key))
+                .header("Content-Type", "application/json")
+                .json(&openai_request)
+        })
+        .await
+        .map_err(|e| request_error("Failed to send request to", "OpenAI", e))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(format!("OpenAI returned error {}: {}", status, body));
+        }
+
+        let mut stream = response.bytes_stream();
+        let mut buffer = Vec::new();
+        let mut content = String::new();
+
+        'outer: while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(|e| format!("OpenAI stream error: {}", e))?;
+            buffer.extend_from_slice(&chunk);
+
+            for line in drain_lines(&mut buffer) {
+                // SSE: only `data:` lines carry payloads; comments and other fields are ignored
+                let Some(data) = line.strip_prefix("data:").map(str::trim) else {
+                    continue;
+                };
+                if data == "[DONE]" {
+                    break 'outer;
+                }
+
+                let parsed: OpenAiStreamChunk = serde_json::from_str(data)
+                    .map_err(|e| format!("Failed to parse OpenAI stream chunk: {}", e))?;
+                for token in parsed.choices.into_iter().filter_map(|c| c.delta.content) {
+                    if !token.is_empty() {
+                        content.push_str(&token);
+                        let _ = tx.send(token).await;
+                    }
+                }
+            }
+        }
+
+        let structured = if json_mode {
+            serde_json::from_str::<ShellCommandResponse>(&content).ok()
+        } else {
+            None
+        };
+
+        Ok(CompletionResponse {
+            content,
+            tokens_used: None,
+            usage: None,
             structured,
         })
     }
@@ -286,6 +417,8 @@ impl AiProvider for OpenAiProvider {
                                 context_window: get_known_context_window(&m.id),
                                 parameter_size: None,
                                 quantization_level: None,
+                                size: None,
+                                family: None,
                             })
                             .collect();
 
@@ -327,7 +460,7 @@ impl AiProvider for OpenAiProvider {
         self.test_connection().await.is_ok()
     }
 
-    async fn test_connection(&self) -> Result<(), String> {
+    async fn test_connection(&self) -> Result<Option<TokenUsage>, String> {
         let url = format!("{}/v1/models", self.base_url());
 
         let response = self
@@ -337,10 +470,10 @@ impl AiProvider for OpenAiProvider {
             .timeout(std::time::Duration::from_secs(10))
             .send()
             .await
-            .map_err(|e| format!("Failed to connect to OpenAI: {}", e))?;
+            .map_err(|e| request_error("Failed to connect to", "OpenAI", e))?;
 
         if response.status().is_success() {
-            Ok(())
+            Ok(None)
         } else if response.status().as_u16() == 401 {
             Err("Invalid API key".to_string())
         } else {
@@ -4,7 +4,9 @@ use serde::{Deserialize, Serialize};
 use tracing::info;
 
 use super::provider::{
-    AiModel, AiProvider, CompletionRequest, CompletionResponse, ShellCommandResponse,
+    http_client, request_error, send_with_retry, AiModel, AiProvider, CompletionRequest,
+    CompletionResponse, HttpProvider, ShellCommandResponse, TokenUsage, DEFAULT_MAX_RETRIES,
+    DEFAULT_REQUEST_TIMEOUT_SECS,
 };
 use super::settings::AiProviderType;
 
@@ -18,6 +20,7 @@ pub struct OpenAiCompatProvider {
     endpoint_url: String,
     provider_type: AiProviderType,
     curated_models: Vec<AiModel>,
+    max_retries: u32,
 }
 
 impl OpenAiCompatProvider {
@@ -29,12 +32,13 @@ impl OpenAiCompatProvider {
         curated_models: Vec<AiModel>,
     ) -> Self {
         Self {
-            client: Client::new(),
+            client: http_client(DEFAULT_REQUEST_TIMEOUT_SECS),
             api_key: api_key.to_string(),
             model: model.to_string(),
             endpoint_url: endpoint_url.trim_end_matches('/').to_string(),
             provider_type,
             curated_models,
+            max_retries: DEFAULT_MAX_RETRIES,
         }
     }
 
@@ -54,6 +58,8 @@ pub fn groq_models() -> Vec<AiModel> {
             context_window: Some(128_000),
             parameter_size: Some("70B".to_string()),
             quantization_level: None,
+            size: None,
+            family: None,
         },
         AiModel {
             id: "llama-3.1-8b-instant".to_string(),
@@ -62,6 +68,8 @@ pub fn groq_models() -> Vec<AiModel> {
             context_window: Some(128_000),
             parameter_size: Some("8B".to_string()),
             quantization_level: None,
+            size: None,
+            family: None,
         },
         AiModel {
             id: "mixtral-8x7b-32768".to_string(),
@@ -70,6 +78,8 @@ pub fn groq_models() -> Vec<AiModel> {
             context_window: Some(32_768),
             parameter_size: Some("46.7B".to_string()),
             quantization_level: None,
+            size: None,
+            family: None,
         },
         AiModel {
             id: "gemma2-9b-it".to_string(),
@@ -78,6 +88,8 @@ pub fn groq_models() -> Vec<AiModel> {
             context_window: Some(8_192),
             parameter_size: Some("9B".to_string()),
             quantization_level: None,
+            size: None,
+            family: None,
         },
     ]
 }
@@ -91,6 +103,8 @@ pub fn deepseek_models() -> Vec<AiModel> {
             context_window: Some(64_000),
             parameter_size: None,
             quantization_level: None,
+            size: None,
+            family: None,
         },
         AiModel {
             id: "deepseek-reasoner".to_string(),
@@ -99,6 +113,8 @@ pub fn deepseek_models() -> Vec<AiModel> {
             context_window: Some(64_000),
             parameter_size: None,
             quantization_level: None,
+            size: None,
+            family: None,
         },
     ]
 }
@@ -112,6 +128,8 @@ pub fn mistral_models() -> Vec<AiModel> {
             context_window: Some(128_000),
             parameter_size: None,
             quantization_level: None,
+            size: None,
+            family: None,
         },
         AiModel {
             id: "mistral-small-latest".to_string(),
@@ -120,6 +138,8 @@ pub fn mistral_models() -> Vec<AiModel> {
             context_window: Some(128_000),
             parameter_size: None,
             quantization_level: None,
+            size: None,
+            family: None,
         },
         AiModel {
             id: "open-mistral-nemo".to_string(),
@@ -128,6 +148,8 @@ pub fn mistral_models() -> Vec<AiModel> {
             context_window: Some(128_000),
             parameter_size: Some("12B".to_string()),
             quantization_level: None,
+            size: None,
+            family: None,
         },
         AiModel {
             id: "codestral-latest".to_string(),
@@ -136,6 +158,8 @@ pub fn mistral_models() -> Vec<AiModel> {
             context_window: Some(32_000),
             parameter_size: None,
             quantization_level: None,
+            size: None,
+            family: None,
         },
     ]
 }
@@ -180,6 +204,10 @@ struct ChatChoice {
 
 #[derive(Debug, Deserialize)]
 struct ChatUsage {
+    #[serde(default)]
+    prompt_tokens: i32,
+    #[serde(default)]
+    completion_tokens: i32,
     total_tokens: i32,
 }
 
@@ -193,6 +221,16 @@ struct ModelInfo {
     id: String,
 }
 
+impl HttpProvider for OpenAiCompatProvider {
+    fn client_mut(&mut self) -> &mut Client {
+        &mut self.client
+    }
+
+    fn max_retries_mut(&mut self) -> &mut u32 {
+        &mut self.max_retries
+    }
+}
+
 #[async_trait]
 impl AiProvider for OpenAiCompatProvider {
     fn provider_type(&self) -> AiProviderType {
@@ -239,15 +277,21 @@ impl AiProvider for OpenAiCompatProvider {
             },
         };
 
-        let response = self
-            .client
-            .post(&url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .header("Content-Type", "application/json")
-            .json(&chat_request)
-            .send()
-            .await
-            .map_err(|e| format!("Failed to send request to {}: {}", self.provider_type, e))?;
+        let response = send_with_retry(&self.provider_type.to_string(), self.max_retries, || {
+            self.client
+                .post(&url)
+                .header("Authorization", format!("Bearer {}", self.api_key))
+                .header("Content-Type", "application/json")
+                .json(&chat_request)
+        })
+        .await
+        .map_err(|e| {
+            request_error(
+                "Failed to send request to",
+                &self.provider_type.to_string(),
+                e,
+            )
+        })?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -274,7 +318,10 @@ impl AiProvider for OpenAiCompatProvider {
 
         Ok(CompletionResponse {
             content,
-            tokens_used: chat_response.usage.map(|u| u.total_tokens),
+            tokens_used: chat_response.usage.as_ref().map(|u| u.total_tokens),
+            usage: chat_response
+                .usage
+                .map(|u| TokenUsage::new(u.prompt_tokens, u.completion_tokens)),
             structured,
         })
     }
@@ -303,6 +350,8 @@ impl AiProvider for OpenAiCompatProvider {
                                 context_window: None,
                                 parameter_size: None,
                                 quantization_level: None,
+                                size: None,
+                                family: None,
                             })
                             .collect();
 
@@ -333,7 +382,7 @@ impl AiProvider for OpenAiCompatProvider {
         self.test_connection().await.is_ok()
     }
 
-    async fn test_connection(&self) -> Result<(), String> {
+    async fn test_connection(&self) -> Result<Option<TokenUsage>, String> {
         let url = format!("{}/v1/models", self.base_url());
 
         let response = self
@@ -343,10 +392,12 @@ impl AiProvider for OpenAiCompatProvider {
             .timeout(std::time::Duration::from_secs(10))
             .send()
             .await
-            .map_err(|e| format!("Failed to connect to {}: {}", self.provider_type, e))?;
+            .map_err(|e| {
+                request_error("Failed to connect to", &self.provider_type.to_string(), e)
+            })?;
 
         if response.status().is_success() {
-            Ok(())
+            Ok(None)
         } else if response.status().as_u16() == 401 {
             Err("Invalid API key".to_string())
         } else {
@@ -1,5 +1,12 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
 use async_trait::async_trait;
+use reqwest::{Client, RequestBuilder, Response, StatusCode};
 use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use tracing::warn;
 
 use super::settings::AiProviderType;
 
@@ -35,6 +42,12 @@ pub struct AiModel {
     pub parameter_size: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub quantization_level: Option<String>,
+    /// Download size in bytes, for locally installed models
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+    /// Model family (e.g. "llama", "qwen2"), for locally installed models
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub family: Option<String>,
 }
 
 /// Request for AI completion
@@ -50,11 +63,32 @@ pub struct CompletionRequest {
     pub json_mode: bool,
 }
 
+/// Token counts reported by the provider for one completion
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenUsage {
+    pub prompt_tokens: i32,
+    pub completion_tokens: i32,
+    pub total_tokens: i32,
+}
+
+impl TokenUsage {
+    pub fn new(prompt_tokens: i32, completion_tokens: i32) -> Self {
+        Self {
+            prompt_tokens,
+            completion_tokens,
+            total_tokens: prompt_tokens + completion_tokens,
+        }
+    }
+}
+
 /// Response from AI completion
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CompletionResponse {
     pub content: String,
     pub tokens_used: Option<i32>,
+    /// Prompt/completion token breakdown, when the provider reports it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage: Option<TokenUsage>,
     /// Parsed structured response (when json_mode is enabled)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub structured: Option<ShellCommandResponse>,
@@ -69,6 +103,21 @@ pub trait AiProvider: Send + Sync {
     /// Get a completion from the AI
     async fn get_completion(&self, request: CompletionRequest) -> Result<CompletionResponse, String>;
 
+    /// Get a completion, sending partial tokens over `tx` as they arrive.
+    /// Returns the fully assembled response once the stream ends.
+    ///
+    /// Providers without a streaming endpoint fall back to `get_completion`
+    /// and send the whole content as a single chunk.
+    async fn complete_stream(
+        &self,
+        request: CompletionRequest,
+        tx: mpsc::Sender<String>,
+    ) -> Result<CompletionResponse, String> {
+        let response = self.get_completion(request).await?;
+        let _ = tx.send(response.content.clone()).await;
+        Ok(response)
+    }
+
     /// List available models
     async fn list_models(&self) -> Result<Vec<AiModel>, String>;
 
@@ -76,7 +125,10 @@ pub trait AiProvider: Send + Sync {
     async fn is_available(&self) -> bool;
 
     /// Test the connection to the provider
-    async fn test_connection(&self) -> Result<(), String>;
+    ///
+    /// Returns the token usage of the test request when it was a (billed)
+    /// completion, `None` when the provider was checked without one.
+    async fn test_connection(&self) -> Result<Option<TokenUsage>, String>;
 }
 
 /// JSON schema for shell command responses (used in prompts and Ollama format)
@@ -160,10 +212,226 @@ Rules:
     }
 }
 
+/// Default number of retries for rate-limited or failed completions
+pub(super) const DEFAULT_MAX_RETRIES: u32 = 2;
+
+/// Default seconds a provider may send nothing before a request fails
+pub(super) const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 120;
+
+/// Start of the error for requests a provider didn't answer in time; commands
+/// report these with the `Timeout` error kind
+pub const TIMEOUT_ERROR: &str = "AI request timed out";
+
+/// HTTP client whose requests fail once the server sends nothing for `secs`
+/// seconds. Streamed completions stay open as long as tokens keep arriving.
+pub(super) fn http_client(secs: u64) -> Client {
+    let timeout = Duration::from_secs(secs);
+    Client::builder()
+        .connect_timeout(timeout)
+        .read_timeout(timeout)
+        .build()
+        .unwrap_or_default()
+}
+
+/// Request settings shared by every provider that talks HTTP, so
+/// `create_provider` applies them from `AiSettings` the same way to each
+pub(super) trait HttpProvider: Sized {
+    /// Client the provider sends its requests with
+    fn client_mut(&mut self) -> &mut Client;
+
+    /// Retry limit the provider passes to `send_with_retry`
+    fn max_retries_mut(&mut self) -> &mut u32;
+
+    /// Fail requests that get nothing from the server for `secs` seconds
+    fn with_request_timeout(mut self, secs: u64) -> Self {
+        *self.client_mut() = http_client(secs);
+        self
+    }
+
+    /// Retry rate-limited (429) and server-error (5xx) completions up to `max_retries` times
+    fn with_max_retries(mut self, max_retries: u32) -> Self {
+        *self.max_retries_mut() = max_retries;
+        self
+    }
+}
+
+/// Describe a failed request, e.g. "Failed to send request to Ollama: ...".
+/// Timeouts start with [`TIMEOUT_ERROR`] instead.
+pub(super) fn request_error(action: &str, provider: &str, error: reqwest::Error) -> String {
+    if error.is_timeout() {
+        format!("{}: {} did not respond in time", TIMEOUT_ERROR, provider)
+    } else {
+        format!("{} {}: {}", action, provider, error)
+    }
+}
+
+/// First retry delay; doubles on each further attempt
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+
+/// Upper bound for a single retry delay (also caps `Retry-After`)
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// Send a request, retrying on rate limits (429) and server errors (5xx).
+///
+/// `build` is called once per attempt since a `RequestBuilder` can't be reused.
+/// Waits for `Retry-After` when the server sends one, otherwise backs off
+/// exponentially with jitter. Any other status (including 400/401) is returned
+/// immediately so the caller's error handling sees it unchanged; transport
+/// errors are not retried either.
+pub(super) async fn send_with_retry<F>(
+    provider: &str,
+    max_retries: u32,
+    build: F,
+) -> Result<Response, reqwest::Error>
+where
+    F: Fn() -> RequestBuilder,
+{
+    let mut attempt = 0;
+    loop {
+        let response = build().send().await?;
+        let status = response.status();
+
+        if !is_retryable(status) || attempt >= max_retries {
+            return Ok(response);
+        }
+
+        let delay = retry_after(&response).unwrap_or_else(|| backoff_delay(attempt));
+        attempt += 1;
+        warn!(
+            "{} returned {}, retrying in {:?} (attempt {}/{})",
+            provider, status, delay, attempt, max_retries
+        );
+        tokio::time::sleep(delay).await;
+    }
+}
+
+fn is_retryable(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// Parse `Retry-After` as delay-seconds or an HTTP date
+fn retry_after(response: &Response) -> Option<Duration> {
+    let value = response.headers().get(reqwest::header::RETRY_AFTER)?.to_str().ok()?;
+    parse_retry_after(value, chrono::Utc::now())
+}
+
+fn parse_retry_after(value: &str, now: chrono::DateTime<chrono::Utc>) -> Option<Duration> {
+    let value = value.trim();
+    let delay = if let Ok(seconds) = value.parse::<u64>() {
+        Duration::from_secs(seconds)
+    } else {
+        let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+        (date.with_timezone(&chrono::Utc) - now).to_std().unwrap_or(Duration::ZERO)
+    };
+    Some(delay.min(RETRY_MAX_DELAY))
+}
+
+/// Exponential backoff (base * 2^attempt, capped) with up to 50% random jitter
+fn backoff_delay(attempt: u32) -> Duration {
+    let exponential = RETRY_BASE_DELAY
+        .saturating_mul(2u32.saturating_pow(attempt))
+        .min(RETRY_MAX_DELAY);
+    let jitter_ms = random_u64() % (exponential.as_millis() as u64 / 2 + 1);
+    exponential + Duration::from_millis(jitter_ms)
+}
+
+/// Cheap randomness without pulling in a RNG crate; good enough for jitter
+fn random_u64() -> u64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u128(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or_default(),
+    );
+    hasher.finish()
+}
+
+/// Take every complete line out of `buffer`, leaving a trailing partial line
+/// in place. Used to reassemble NDJSON/SSE streams that arrive in arbitrary
+/// chunks; works on bytes so multi-byte characters split across chunks survive.
+pub(super) fn drain_lines(buffer: &mut Vec<u8>) -> Vec<String> {
+    let Some(last_newline) = buffer.iter().rposition(|&b| b == b'\n') else {
+        return Vec::new();
+    };
+
+    let rest = buffer.split_off(last_newline + 1);
+    let complete = std::mem::replace(buffer, rest);
+
+    String::from_utf8_lossy(&complete)
+        .lines()
+        .map(|line| line.trim_end_matches('\r').to_string())
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    // === retry tests ===
+
+    #[test]
+    fn test_retryable_statuses() {
+        assert!(is_retryable(StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_retryable(StatusCode::INTERNAL_SERVER_ERROR));
+        assert!(is_retryable(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(!is_retryable(StatusCode::BAD_REQUEST));
+        assert!(!is_retryable(StatusCode::UNAUTHORIZED));
+        assert!(!is_retryable(StatusCode::OK));
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        let now = chrono::DateTime::parse_from_rfc3339("2015-10-21T07:28:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+
+        assert_eq!(parse_retry_after("3", now), Some(Duration::from_secs(3)));
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:10 GMT", now),
+            Some(Duration::from_secs(10))
+        );
+        // Dates in the past mean "retry now"
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:27:00 GMT", now), Some(Duration::ZERO));
+        // Capped to the max delay
+        assert_eq!(parse_retry_after("3600", now), Some(RETRY_MAX_DELAY));
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+
+    #[test]
+    fn test_backoff_delay_grows_with_jitter() {
+        for attempt in 0..3 {
+            let base = RETRY_BASE_DELAY * 2u32.pow(attempt);
+            let delay = backoff_delay(attempt);
+            assert!(delay >= base && delay <= base + base / 2, "attempt {}: {:?}", attempt, delay);
+        }
+        assert!(backoff_delay(20) <= RETRY_MAX_DELAY + RETRY_MAX_DELAY / 2);
+    }
+
+    // === drain_lines tests ===
+
+    #[test]
+    fn test_drain_lines_keeps_partial_line() {
+        let mut buffer = b"data: one\r\ndata: tw".to_vec();
+        assert_eq!(drain_lines(&mut buffer), vec!["data: one"]);
+        assert_eq!(buffer, b"data: tw");
+
+        buffer.extend_from_slice(b"o\n\n");
+        assert_eq!(drain_lines(&mut buffer), vec!["data: two"]);
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_drain_lines_multibyte_split_across_chunks() {
+        let text = "caf\u{e9}\n".as_bytes();
+        let mut buffer = text[..4].to_vec();
+        assert!(drain_lines(&mut buffer).is_empty());
+
+        buffer.extend_from_slice(&text[4..]);
+        assert_eq!(drain_lines(&mut buffer), vec!["caf\u{e9}"]);
+    }
+
     // === strip_markdown tests ===
 
     #[test]
@@ -273,6 +541,8 @@ mod tests {
             context_window: Some(128000),
             parameter_size: None,
             quantization_level: None,
+            size: None,
+            family: None,
         };
 
         let json = serde_json::to_value(&model).unwrap();
@@ -305,12 +575,16 @@ mod tests {
         let response = CompletionResponse {
             content: "docker ps -a".to_string(),
             tokens_used: Some(42),
+            usage: Some(TokenUsage::new(30, 12)),
             structured: None,
         };
 
         let json = serde_json::to_value(&response).unwrap();
         assert_eq!(json["content"], "docker ps -a");
         assert_eq!(json["tokens_used"], 42);
+        assert_eq!(json["usage"]["prompt_tokens"], 30);
+        assert_eq!(json["usage"]["completion_tokens"], 12);
+        assert_eq!(json["usage"]["total_tokens"], 42);
     }
 
     #[test]
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::models::error::FieldError;
+
 /// AI provider type
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum AiProviderType {
@@ -19,6 +21,15 @@ pub enum AiProviderType {
     DeepSeek,
     #[serde(rename = "mistral")]
     Mistral,
+    #[serde(rename = "bedrock")]
+    Bedrock,
+}
+
+impl AiProviderType {
+    /// Whether requests need an API key (for Bedrock, the AWS credentials)
+    pub fn requires_api_key(self) -> bool {
+        !matches!(self, Self::Ollama)
+    }
 }
 
 impl Default for AiProviderType {
@@ -38,6 +49,7 @@ impl std::fmt::Display for AiProviderType {
             Self::Gemini => write!(f, "gemini"),
             Self::DeepSeek => write!(f, "deepseek"),
             Self::Mistral => write!(f, "mistral"),
+            Self::Bedrock => write!(f, "bedrock"),
         }
     }
 }
@@ -58,8 +70,30 @@ pub struct AiSettings {
     pub summary_model: Option<String>,
     /// Max tokens for each summary (default: 100)
     pub summary_max_tokens: i32,
+    /// Inputs shorter than this many bytes are remembered as-is, without a summary call
+    #[serde(default = "default_summary_min_input_length")]
+    pub summary_min_input_length: u32,
     /// API version for Azure OpenAI (e.g., "2024-10-21")
     pub api_version: Option<String>,
+    /// How many times to retry a completion on rate limits (429) or server errors (5xx)
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    /// Seconds the provider may send nothing before a request fails. Local
+    /// Ollama loading or running a large model may need this raised.
+    #[serde(default = "default_request_timeout_secs")]
+    pub request_timeout_secs: u64,
+}
+
+fn default_max_retries() -> u32 {
+    super::provider::DEFAULT_MAX_RETRIES
+}
+
+fn default_request_timeout_secs() -> u64 {
+    super::provider::DEFAULT_REQUEST_TIMEOUT_SECS
+}
+
+fn default_summary_min_input_length() -> u32 {
+    100
 }
 
 impl Default for AiSettings {
@@ -74,7 +108,10 @@ impl Default for AiSettings {
             memory_enabled: true,
             summary_model: None,
             summary_max_tokens: 100,
+            summary_min_input_length: default_summary_min_input_length(),
             api_version: None,
+            max_retries: default_max_retries(),
+            request_timeout_secs: default_request_timeout_secs(),
         }
     }
 }
@@ -91,6 +128,7 @@ impl AiSettings {
             AiProviderType::Gemini => "gemini",
             AiProviderType::DeepSeek => "deepseek",
             AiProviderType::Mistral => "mistral",
+            AiProviderType::Bedrock => "bedrock",
         }
     }
 
@@ -104,6 +142,7 @@ impl AiSettings {
             "gemini" => AiProviderType::Gemini,
             "deepseek" => AiProviderType::DeepSeek,
             "mistral" => AiProviderType::Mistral,
+            "bedrock" => AiProviderType::Bedrock,
             _ => AiProviderType::Ollama,
         }
     }
@@ -124,10 +163,84 @@ impl AiSettings {
             AiProviderType::Gemini => "gemini-2.0-flash-lite".to_string(),
             AiProviderType::DeepSeek => "deepseek-chat".to_string(),
             AiProviderType::Mistral => "mistral-small-latest".to_string(),
+            AiProviderType::Bedrock => "anthropic.claude-3-haiku-20240307-v1:0".to_string(),
+        }
+    }
+
+    /// Check the settings a user submitted, reporting every invalid field
+    pub fn validate(&self) -> Result<(), Vec<FieldError>> {
+        let mut errors = Vec::new();
+
+        if self.model_name.trim().is_empty() {
+            errors.push(FieldError::new("model_name", "Model name is required"));
+        }
+
+        // Anthropic ignores the endpoint and Bedrock also takes a bare region
+        let endpoint = self.endpoint_url.trim();
+        let check_endpoint = match self.provider {
+            AiProviderType::Anthropic => !endpoint.is_empty(),
+            AiProviderType::Bedrock => endpoint.contains("://"),
+            _ => true,
+        };
+        if check_endpoint {
+            if let Err(message) = check_endpoint_url(endpoint) {
+                errors.push(FieldError::new("endpoint_url", message));
+            }
+        }
+
+        if !(0.0..=2.0).contains(&self.temperature) {
+            errors.push(FieldError::new(
+                "temperature",
+                "Temperature must be between 0.0 and 2.0",
+            ));
+        }
+
+        if self.max_tokens <= 0 {
+            errors.push(FieldError::new("max_tokens", "Max tokens must be positive"));
+        }
+
+        if self.provider.requires_api_key()
+            && self.api_key.as_deref().is_none_or(|k| k.trim().is_empty())
+        {
+            errors.push(FieldError::new(
+                "api_key",
+                format!("An API key is required for {}", self.provider),
+            ));
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Settings for the provider instance that writes summaries: the summary
+    /// model and token limit on the same account, with no retries so a failing
+    /// summary falls back to truncation instead of delaying the agent
+    pub fn summary_settings(&self) -> AiSettings {
+        AiSettings {
+            model_name: self.get_effective_summary_model(),
+            max_tokens: self.summary_max_tokens,
+            max_retries: 0,
+            ..self.clone()
         }
     }
 }
 
+/// An endpoint must be an absolute http(s) URL with a host
+fn check_endpoint_url(endpoint: &str) -> Result<(), String> {
+    if endpoint.is_empty() {
+        return Err("Endpoint URL is required".to_string());
+    }
+    let url = reqwest::Url::parse(endpoint)
+        .map_err(|e| format!("Endpoint URL is not a valid URL: {}", e))?;
+    if !matches!(url.scheme(), "http" | "https") || url.host_str().is_none() {
+        return Err("Endpoint URL must start with http:// or https://".to_string());
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -144,7 +257,10 @@ mod tests {
         assert!(settings.memory_enabled);
         assert!(settings.summary_model.is_none());
         assert_eq!(settings.summary_max_tokens, 100);
+        assert_eq!(settings.summary_min_input_length, 100);
         assert!(settings.api_version.is_none());
+        assert_eq!(settings.max_retries, 2);
+        assert_eq!(settings.request_timeout_secs, 120);
     }
 
     #[test]
@@ -163,6 +279,7 @@ mod tests {
         assert_eq!(format!("{}", AiProviderType::Gemini), "gemini");
         assert_eq!(format!("{}", AiProviderType::DeepSeek), "deepseek");
         assert_eq!(format!("{}", AiProviderType::Mistral), "mistral");
+        assert_eq!(format!("{}", AiProviderType::Bedrock), "bedrock");
     }
 
     #[test]
@@ -187,6 +304,7 @@ mod tests {
         assert_eq!(AiSettings::str_to_provider("gemini"), AiProviderType::Gemini);
         assert_eq!(AiSettings::str_to_provider("deepseek"), AiProviderType::DeepSeek);
         assert_eq!(AiSettings::str_to_provider("mistral"), AiProviderType::Mistral);
+        assert_eq!(AiSettings::str_to_provider("bedrock"), AiProviderType::Bedrock);
     }
 
     #[test]
@@ -207,6 +325,7 @@ mod tests {
             AiProviderType::Gemini,
             AiProviderType::DeepSeek,
             AiProviderType::Mistral,
+            AiProviderType::Bedrock,
         ];
 
         for provider in providers {
@@ -253,6 +372,27 @@ mod tests {
 
         settings.provider = AiProviderType::Mistral;
         assert_eq!(settings.get_effective_summary_model(), "mistral-small-latest");
+
+        settings.provider = AiProviderType::Bedrock;
+        assert_eq!(settings.get_effective_summary_model(), "anthropic.claude-3-haiku-20240307-v1:0");
+    }
+
+    #[test]
+    fn test_summary_settings_use_summary_model() {
+        let settings = AiSettings {
+            provider: AiProviderType::OpenAi,
+            model_name: "gpt-4o".to_string(),
+            summary_model: Some("gpt-4o-mini".to_string()),
+            summary_max_tokens: 80,
+            max_retries: 3,
+            ..AiSettings::default()
+        };
+        let summary = settings.summary_settings();
+        assert_eq!(summary.model_name, "gpt-4o-mini");
+        assert_eq!(summary.max_tokens, 80);
+        assert_eq!(summary.max_retries, 0);
+        assert_eq!(summary.provider, AiProviderType::OpenAi);
+        assert_eq!(summary.endpoint_url, settings.endpoint_url);
     }
 
     #[test]
@@ -279,7 +419,10 @@ mod tests {
             memory_enabled: true,
             summary_model: Some("gpt-4o-mini".to_string()),
             summary_max_tokens: 200,
+            summary_min_input_length: 500,
             api_version: None,
+            max_retries: 5,
+            request_timeout_secs: 600,
         };
 
         let json = serde_json::to_string(&settings).unwrap();
@@ -290,5 +433,85 @@ mod tests {
         assert_eq!(deserialized.model_name, "gpt-4o");
         assert_eq!(deserialized.max_tokens, 1024);
         assert!(deserialized.memory_enabled);
+        assert_eq!(deserialized.summary_min_input_length, 500);
+        assert_eq!(deserialized.max_retries, 5);
+        assert_eq!(deserialized.request_timeout_secs, 600);
+    }
+
+    #[test]
+    fn test_settings_without_max_retries_defaults() {
+        let mut json = serde_json::to_value(AiSettings::default()).unwrap();
+        json.as_object_mut().unwrap().remove("max_retries");
+        json.as_object_mut().unwrap().remove("request_timeout_secs");
+
+        let settings: AiSettings = serde_json::from_value(json).unwrap();
+        assert_eq!(settings.max_retries, 2);
+        assert_eq!(settings.request_timeout_secs, 120);
+    }
+
+    fn invalid_fields(settings: &AiSettings) -> Vec<String> {
+        settings
+            .validate()
+            .err()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|e| e.field)
+            .collect()
+    }
+
+    #[test]
+    fn test_validate_accepts_defaults() {
+        // Ollama needs no API key
+        assert!(AiSettings::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_reports_each_invalid_field() {
+        let settings = AiSettings {
+            provider: AiProviderType::OpenAi,
+            api_key: Some("  ".to_string()),
+            model_name: " ".to_string(),
+            endpoint_url: "htps//api.openai.com".to_string(),
+            temperature: 2.5,
+            max_tokens: 0,
+            ..AiSettings::default()
+        };
+        assert_eq!(
+            invalid_fields(&settings),
+            vec!["model_name", "endpoint_url", "temperature", "max_tokens", "api_key"]
+        );
+    }
+
+    #[test]
+    fn test_validate_endpoint_per_provider() {
+        let ollama = AiSettings {
+            endpoint_url: "ftp://localhost:11434".to_string(),
+            ..AiSettings::default()
+        };
+        assert_eq!(invalid_fields(&ollama), vec!["endpoint_url"]);
+
+        let azure = AiSettings {
+            provider: AiProviderType::AzureOpenAi,
+            api_key: Some("key".to_string()),
+            endpoint_url: String::new(),
+            ..AiSettings::default()
+        };
+        assert_eq!(invalid_fields(&azure), vec!["endpoint_url"]);
+
+        let anthropic = AiSettings {
+            provider: AiProviderType::Anthropic,
+            api_key: Some("key".to_string()),
+            endpoint_url: String::new(),
+            ..AiSettings::default()
+        };
+        assert!(anthropic.validate().is_ok());
+
+        let bedrock = AiSettings {
+            provider: AiProviderType::Bedrock,
+            api_key: Some("AKIA:secret".to_string()),
+            endpoint_url: "eu-central-1".to_string(),
+            ..AiSettings::default()
+        };
+        assert!(bedrock.validate().is_ok());
     }
 }
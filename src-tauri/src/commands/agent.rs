@@ -3,26 +3,33 @@
 //! Tauri commands for the AI agent system.
 
 
-use tauri::{AppHandle, Emitter, State};
+use tauri::{AppHandle, Emitter, Manager, State};
 
 use crate::agent::events::{AgentEvent, AgentQueryRequest, ConfirmationResponse};
-use crate::agent::session::AgentSessionManager;
-use crate::commands::terminal::TerminalSessions;
+use crate::agent::safety::DangerRule;
+use crate::agent::session::{AgentSessionManager, ConversationMessage, ConversationTurn};
+use crate::agent::transcript::{Transcript, TranscriptFormat};
+use crate::commands::terminal::{runtime_binary, shell_name, TerminalSessions};
 use crate::database;
 use crate::models::agent::{AgentError, AgentPreferences, AgentSessionInfo, ContextSummary};
+use crate::models::error::AppError;
 use crate::state::AppState;
 
 /// Start a new agent session linked to a terminal session
 ///
 /// If `container_id` is provided, the agent context will be set to container environment
 /// (Linux shell) so the AI knows it's inside a container and suggests appropriate commands.
+/// Conversation history persisted for the same system, container and shell is restored
+/// into the new session, so it survives closing the terminal and restarting the app.
 #[tauri::command]
 pub async fn start_agent_session(
     app: AppHandle,
+    state: State<'_, AppState>,
     agent_sessions: State<'_, AgentSessionManager>,
+    terminal_sessions: State<'_, TerminalSessions>,
     terminal_session_id: String,
     container_id: Option<String>,
-) -> Result<AgentSessionInfo, String> {
+) -> Result<AgentSessionInfo, AppError> {
     // Check if session already exists for this terminal
     if let Some(existing) = agent_sessions
         .get_session_by_terminal(&terminal_session_id)
@@ -39,21 +46,31 @@ pub async fn start_agent_session(
     }
 
     // Create new session
-    let (session, mut event_rx, _confirmation_rx, _cancel_rx) = agent_sessions
+    let (session, mut event_rx) = agent_sessions
         .create_session(terminal_session_id.clone())
         .await;
 
     let session_id = session.id.clone();
 
-    // If this is a container terminal, set container context so AI knows it's inside a container
-    if let Some(cid) = container_id {
+    // If this is a container terminal, set container context so AI knows it's inside a container.
+    // Terminals opened into a container know the runtime and shell they exec'd.
+    let container = match terminal_sessions.container(&terminal_session_id) {
+        Some(c) => Some((
+            c.container_id,
+            runtime_binary(c.runtime),
+            shell_name(&c.shell).to_string(),
+        )),
+        None => container_id.map(|cid| (cid, "docker", "sh".to_string())),
+    };
+    let conversation_key = conversation_key(
+        &terminal_sessions,
+        &terminal_session_id,
+        container.as_ref().map(|(cid, _, _)| cid.as_str()),
+    );
+    if let Some((cid, runtime, shell)) = container {
         if let Some(ctx_arc) = agent_sessions.get_context(&session_id).await {
             let mut ctx = ctx_arc.write().await;
-            ctx.enter_container(
-                cid.clone(),
-                "docker".to_string(), // Default runtime
-                "sh".to_string(),     // Default shell for containers
-            );
+            ctx.enter_container(cid.clone(), runtime.to_string(), shell);
             tracing::info!(
                 "Agent session {} initialized with container context: {}",
                 session_id,
@@ -62,6 +79,42 @@ pub async fn start_agent_session(
         }
     }
 
+    // Restore persisted history for this conversation and register the new session
+    let history = {
+        let db = state
+            .db
+            .lock()
+            .map_err(|e| AgentError::Internal(e.to_string()).to_string())?;
+
+        if let Err(e) = database::upsert_agent_session(
+            &db,
+            &session_id,
+            &terminal_session_id,
+            &conversation_key,
+            session.created_at,
+        ) {
+            tracing::warn!("Failed to persist agent session {}: {}", session_id, e);
+        }
+        database::get_agent_history(&db, &conversation_key)
+    };
+
+    match history {
+        Ok(history) if !history.messages.is_empty() || !history.turns.is_empty() => {
+            tracing::info!(
+                "Restoring {} messages and {} turns into agent session {}",
+                history.messages.len(),
+                history.turns.len(),
+                session_id
+            );
+            agent_sessions
+                .restore_history(&session_id, history.messages, history.turns)
+                .await
+                .map_err(|e| AgentError::Internal(e).to_string())?;
+        }
+        Ok(_) => {}
+        Err(e) => tracing::warn!("Failed to load agent history: {}", e),
+    }
+
     let session_info = AgentSessionInfo {
         id: session.id.clone(),
         terminal_session_id: session.terminal_session_id.clone(),
@@ -75,11 +128,16 @@ pub async fn start_agent_session(
     let app_handle = app.clone();
     tokio::spawn(async move {
         while let Some(event) = event_rx.recv().await {
+            app_handle
+                .state::<AgentSessionManager>()
+                .track_confirmation(&session_id, &event)
+                .await;
             let event_name = match &event {
                 AgentEvent::Thinking { .. } => "agent:thinking",
                 AgentEvent::ResponseChunk { .. } => "agent:response-chunk",
                 AgentEvent::CommandProposed { .. } => "agent:command-proposed",
                 AgentEvent::ConfirmationRequired { .. } => "agent:confirmation-required",
+                AgentEvent::ConfirmationExpired { .. } => "agent:confirmation-expired",
                 AgentEvent::CommandStarted { .. } => "agent:command-started",
                 AgentEvent::CommandOutput { .. } => "agent:command-output",
                 AgentEvent::CommandCompleted { .. } => "agent:command-completed",
@@ -103,7 +161,7 @@ pub async fn start_agent_session(
 pub async fn get_agent_session(
     agent_sessions: State<'_, AgentSessionManager>,
     session_id: String,
-) -> Result<Option<AgentSessionInfo>, String> {
+) -> Result<Option<AgentSessionInfo>, AppError> {
     let session = agent_sessions.get_session(&session_id).await;
 
     Ok(session.map(|s| AgentSessionInfo {
@@ -121,7 +179,7 @@ pub async fn get_agent_session(
 pub async fn get_agent_session_by_terminal(
     agent_sessions: State<'_, AgentSessionManager>,
     terminal_session_id: String,
-) -> Result<Option<AgentSessionInfo>, String> {
+) -> Result<Option<AgentSessionInfo>, AppError> {
     let session = agent_sessions
         .get_session_by_terminal(&terminal_session_id)
         .await;
@@ -144,7 +202,8 @@ pub async fn get_agent_session_by_terminal(
 /// - Ollama: Uses tool calling (for compatible models like llama3.1+, mistral)
 ///
 /// Commands are executed ONE AT A TIME, and the AI sees the output before
-/// deciding the next command.
+/// deciding the next command. With `dry_run` set, commands are only reported
+/// ("would execute: ...") so the user can review the plan first.
 #[tauri::command]
 pub async fn submit_agent_query(
     app: AppHandle,
@@ -152,8 +211,8 @@ pub async fn submit_agent_query(
     agent_sessions: State<'_, AgentSessionManager>,
     terminal_sessions: State<'_, TerminalSessions>,
     request: AgentQueryRequest,
-) -> Result<String, String> {
-    use crate::agent::executor::run_agentic_loop;
+) -> Result<String, AppError> {
+    use crate::agent::executor::{run_agentic_loop, ExecutorConfig};
     use std::sync::Arc;
 
     // Get the agent session
@@ -165,19 +224,35 @@ pub async fn submit_agent_query(
     // Use provided query ID or generate one
     let query_id = request.query_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
 
-    // Get AI settings
-    let settings = {
+    // Get AI settings, user danger rules and agent preferences
+    let (settings, danger_rules, preferences) = {
         let db = state
             .db
             .lock()
             .map_err(|e| AgentError::Internal(e.to_string()).to_string())?;
-        super::ai::load_ai_settings_with_key(&db, &state)
-            .map_err(|e| AgentError::DatabaseError(e).to_string())?
+        let settings = super::ai::load_ai_settings_with_key(&db, &state)
+            .map_err(|e| AgentError::DatabaseError(e).to_string())?;
+        let danger_rules = database::get_danger_rules(&db)
+            .map_err(|e| AgentError::DatabaseError(e.to_string()).to_string())?;
+        let preferences = database::get_agent_preferences(&db)
+            .map_err(|e| AgentError::DatabaseError(e).to_string())?;
+        (settings, danger_rules, preferences)
     };
 
     // Get the terminal session ID from the agent session
     let terminal_session_id = session.terminal_session_id.clone();
 
+    // Fresh cancellation token for this query, fired by cancel_agent_query
+    let cancel = agent_sessions
+        .begin_query(&request.session_id)
+        .await
+        .ok_or_else(|| AgentError::SessionNotFound(request.session_id.clone()).to_string())?;
+
+    // The system the terminal runs on, for tools that talk to its runtime directly
+    let system = terminal_sessions
+        .system_id(&terminal_session_id)
+        .and_then(|id| state.get_system(&id));
+
     // Get event sender for the session
     let event_tx = agent_sessions
         .get_event_sender(&request.session_id)
@@ -198,11 +273,31 @@ pub async fn submit_agent_query(
     let query = request.query.clone();
     let query_id_clone = query_id.clone();
     let app_clone = app.clone();
+    let defaults = ExecutorConfig::default();
+    let config = ExecutorConfig {
+        ai_settings: settings,
+        dry_run: request.dry_run,
+        danger_rules,
+        max_iterations: request
+            .max_iterations
+            .map_or(defaults.max_iterations, |n| n.max(1)),
+        auto_approve_level: preferences.auto_approve_level,
+        confirmation_timeout: std::time::Duration::from_secs(
+            preferences.confirmation_timeout_secs.max(1) as u64,
+        ),
+        ..defaults
+    };
+    let last_turn_timestamp = context
+        .read()
+        .await
+        .conversation_turns
+        .back()
+        .map(|t| t.timestamp);
 
     tracing::info!(
         "Starting agentic query - Provider: {:?}, Model: {}",
-        settings.provider,
-        settings.model_name
+        config.ai_settings.provider,
+        config.ai_settings.model_name
     );
 
     // Use the multi-turn agentic loop for ALL providers
@@ -213,10 +308,12 @@ pub async fn submit_agent_query(
             &query_id_clone,
             &query,
             &terminal_session_id,
-            &settings,
+            config,
             terminal_sessions_arc,
-            context,
+            context.clone(),
             event_tx.clone(),
+            system,
+            cancel,
         )
         .await
         {
@@ -237,34 +334,103 @@ pub async fn submit_agent_query(
                     .await;
             }
         }
+
+        // Persist the exchange so it survives app restarts
+        let turn = context
+            .read()
+            .await
+            .conversation_turns
+            .back()
+            .filter(|t| Some(t.timestamp) != last_turn_timestamp)
+            .cloned();
+        let agent_sessions = app_clone.state::<AgentSessionManager>();
+        match agent_sessions
+            .record_exchange(
+                &session_id,
+                query,
+                turn.as_ref().and_then(|t| t.ai_response.clone()),
+            )
+            .await
+        {
+            Ok(messages) => {
+                persist_agent_exchange(&app_clone, &session_id, &messages, turn.as_ref())
+            }
+            Err(e) => tracing::warn!("Failed to record agent exchange: {}", e),
+        }
     });
 
     Ok(query_id)
 }
 
+/// Key agent history is stored under, so a new terminal on the same system,
+/// container and shell picks up the earlier conversation. Terminals whose
+/// system is unknown keep their history to themselves.
+fn conversation_key(
+    terminal_sessions: &TerminalSessions,
+    terminal_session_id: &str,
+    container_id: Option<&str>,
+) -> String {
+    let Some(system_id) = terminal_sessions.system_id(terminal_session_id) else {
+        return terminal_session_id.to_string();
+    };
+    let shell = terminal_sessions
+        .shell(terminal_session_id)
+        .map(|s| shell_name(&s).to_string())
+        .unwrap_or_default();
+    match container_id {
+        Some(cid) => format!("{}/{}/{}", system_id, cid, shell),
+        None => format!("{}/{}", system_id, shell),
+    }
+}
+
+/// Write a finished query's messages and turn to the database
+fn persist_agent_exchange(
+    app: &AppHandle,
+    session_id: &str,
+    messages: &[ConversationMessage],
+    turn: Option<&ConversationTurn>,
+) {
+    let state = app.state::<AppState>();
+    let db = match state.db.lock() {
+        Ok(db) => db,
+        Err(e) => {
+            tracing::warn!("Failed to lock database for agent history: {}", e);
+            return;
+        }
+    };
+
+    let result = messages
+        .iter()
+        .try_for_each(|m| database::append_agent_message(&db, session_id, m))
+        .and_then(|_| match turn {
+            Some(turn) => database::append_agent_turn(&db, session_id, turn),
+            None => Ok(()),
+        });
+
+    if let Err(e) = result {
+        tracing::warn!("Failed to persist agent history for {}: {}", session_id, e);
+    }
+}
+
 /// Respond to a confirmation request
+///
+/// Fails if the confirmation has expired; its command was cancelled by then.
 #[tauri::command]
 pub async fn respond_to_confirmation(
     agent_sessions: State<'_, AgentSessionManager>,
     session_id: String,
     response: ConfirmationResponse,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     let confirmed = matches!(
         response.action,
         crate::agent::events::ConfirmationAction::Approve
     );
 
     agent_sessions
-        .send_confirmation(&session_id, confirmed)
+        .send_confirmation(&session_id, &response.confirmation_id, confirmed)
         .await
         .map_err(|e| AgentError::Internal(e).to_string())?;
 
-    // Clear pending confirmation
-    if let Some(mut session) = agent_sessions.get_session(&session_id).await {
-        session.clear_pending_confirmation();
-        let _ = agent_sessions.update_session(session).await;
-    }
-
     Ok(())
 }
 
@@ -273,26 +439,13 @@ pub async fn respond_to_confirmation(
 pub async fn cancel_agent_query(
     agent_sessions: State<'_, AgentSessionManager>,
     session_id: String,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     agent_sessions
         .cancel_session(&session_id)
         .await
         .map_err(|e| AgentError::Internal(e).to_string())?;
 
-    // Send cancel event
-    let _ = agent_sessions
-        .send_event(
-            &session_id,
-            AgentEvent::QueryCompleted {
-                session_id: session_id.clone(),
-                query_id: String::new(),
-                status: crate::agent::events::QueryCompletionStatus::Cancelled,
-                summary: Some("Query cancelled by user".to_string()),
-                blocks_created: vec![],
-            },
-        )
-        .await;
-
+    // The running query reports its own cancellation, with its query ID
     Ok(())
 }
 
@@ -301,11 +454,59 @@ pub async fn cancel_agent_query(
 pub async fn close_agent_session(
     agent_sessions: State<'_, AgentSessionManager>,
     session_id: String,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     agent_sessions.remove_session(&session_id).await;
     Ok(())
 }
 
+/// Clear an agent session's conversation history, in memory and in the database
+#[tauri::command]
+pub async fn clear_agent_history(
+    state: State<'_, AppState>,
+    agent_sessions: State<'_, AgentSessionManager>,
+    session_id: String,
+) -> Result<(), AppError> {
+    agent_sessions
+        .clear_history(&session_id)
+        .await
+        .map_err(|_| AgentError::SessionNotFound(session_id.clone()).to_string())?;
+
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| AgentError::Internal(e.to_string()).to_string())?;
+
+    Ok(database::delete_agent_history(&db, &session_id)
+        .map(|_| ())
+        .map_err(|e| AgentError::DatabaseError(e.to_string()).to_string())?)
+}
+
+/// Export an agent session's conversation as a Markdown or JSON transcript,
+/// with anything that looks like a secret redacted
+#[tauri::command]
+pub async fn export_agent_transcript(
+    agent_sessions: State<'_, AgentSessionManager>,
+    session_id: String,
+    format: Option<TranscriptFormat>,
+) -> Result<String, AppError> {
+    let session = agent_sessions
+        .get_session(&session_id)
+        .await
+        .ok_or_else(|| AgentError::SessionNotFound(session_id.clone()).to_string())?;
+    let context = agent_sessions
+        .get_context(&session_id)
+        .await
+        .ok_or_else(|| AgentError::SessionNotFound(session_id.clone()).to_string())?;
+
+    let transcript = Transcript::from_session(&session, &*context.read().await);
+
+    match format.unwrap_or_default() {
+        TranscriptFormat::Markdown => Ok(transcript.to_markdown()),
+        TranscriptFormat::Json => Ok(serde_json::to_string_pretty(&transcript)
+            .map_err(|e| AgentError::Internal(e.to_string()).to_string())?),
+    }
+}
+
 /// Update terminal context for agent session
 #[tauri::command]
 pub async fn update_agent_context(
@@ -314,7 +515,7 @@ pub async fn update_agent_context(
     cwd: Option<String>,
     git_branch: Option<String>,
     last_exit_code: Option<i32>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     let mut session = agent_sessions
         .get_session(&session_id)
         .await
@@ -344,7 +545,7 @@ pub async fn append_agent_output(
     agent_sessions: State<'_, AgentSessionManager>,
     session_id: String,
     output: String,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     agent_sessions
         .append_output(&session_id, &output)
         .await
@@ -358,7 +559,7 @@ pub async fn append_agent_output(
 pub async fn get_agent_context_summary(
     agent_sessions: State<'_, AgentSessionManager>,
     session_id: String,
-) -> Result<ContextSummary, String> {
+) -> Result<ContextSummary, AppError> {
     let session = agent_sessions
         .get_session(&session_id)
         .await
@@ -381,13 +582,14 @@ pub async fn get_agent_context_summary(
 #[tauri::command]
 pub async fn get_agent_preferences(
     state: State<'_, AppState>,
-) -> Result<AgentPreferences, String> {
+) -> Result<AgentPreferences, AppError> {
     let db = state
         .db
         .lock()
         .map_err(|e| AgentError::Internal(e.to_string()).to_string())?;
 
-    database::get_agent_preferences(&db).map_err(|e| AgentError::DatabaseError(e).to_string())
+    Ok(database::get_agent_preferences(&db)
+        .map_err(|e| AgentError::DatabaseError(e).to_string())?)
 }
 
 /// Update agent preferences
@@ -395,12 +597,57 @@ pub async fn get_agent_preferences(
 pub async fn update_agent_preferences(
     state: State<'_, AppState>,
     preferences: AgentPreferences,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| AgentError::Internal(e.to_string()).to_string())?;
+
+    Ok(database::update_agent_preferences(&db, &preferences)
+        .map_err(|e| AgentError::DatabaseError(e).to_string())?)
+}
+
+/// Get user-defined danger rules for the agent's command classifier
+#[tauri::command]
+pub async fn get_danger_rules(state: State<'_, AppState>) -> Result<Vec<DangerRule>, AppError> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| AgentError::Internal(e.to_string()).to_string())?;
+
+    Ok(database::get_danger_rules(&db)
+        .map_err(|e| AgentError::DatabaseError(e.to_string()).to_string())?)
+}
+
+/// Add a danger rule; commands matching it need at least the rule's danger level
+#[tauri::command]
+pub async fn add_danger_rule(
+    state: State<'_, AppState>,
+    mut rule: DangerRule,
+) -> Result<DangerRule, AppError> {
+    rule.validate()?;
+    if rule.id.is_empty() {
+        rule.id = uuid::Uuid::new_v4().to_string();
+    }
+
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| AgentError::Internal(e.to_string()).to_string())?;
+
+    database::insert_danger_rule(&db, &rule)
+        .map_err(|e| AgentError::DatabaseError(e.to_string()).to_string())?;
+    Ok(rule)
+}
+
+/// Remove a danger rule
+#[tauri::command]
+pub async fn remove_danger_rule(state: State<'_, AppState>, id: String) -> Result<bool, AppError> {
     let db = state
         .db
         .lock()
         .map_err(|e| AgentError::Internal(e.to_string()).to_string())?;
 
-    database::update_agent_preferences(&db, &preferences)
-        .map_err(|e| AgentError::DatabaseError(e).to_string())
+    Ok(database::delete_danger_rule(&db, &id)
+        .map_err(|e| AgentError::DatabaseError(e.to_string()).to_string())?)
 }
@@ -1,14 +1,22 @@
 use serde::{Deserialize, Serialize};
-use tauri::State;
+use tauri::{AppHandle, Emitter, State};
+use tokio::sync::mpsc;
 use tracing::info;
 
 use crate::ai::{
     create_provider, get_shell_system_prompt, AiModel, AiProviderType, AiSettings,
-    CompletionRequest, OllamaProvider, ShellCommandResponse,
+    CompletionRequest, CompletionResponse, OllamaProvider, ShellCommandResponse, TokenUsage,
+    TIMEOUT_ERROR,
 };
+use crate::commands::exec::RunningExecutions;
 use crate::database::{get_ai_settings, upsert_ai_settings};
+use crate::models::error::{AppError, ContainerError, ErrorKind};
 use crate::AppState;
 
+/// Request timeout for connection tests and model listing from the settings UI,
+/// so a wrong endpoint fails in seconds rather than after the full timeout
+const TEST_CONNECTION_TIMEOUT_SECS: u64 = 15;
+
 /// Load AI settings from DB and hydrate the API key from the in-memory cache on desktop.
 pub(crate) fn load_ai_settings_with_key(db: &rusqlite::Connection, app_state: &crate::AppState) -> Result<AiSettings, String> {
     let mut settings = get_ai_settings(db).map_err(|e| format!("Database error: {}", e))?;
@@ -27,6 +35,36 @@ pub(crate) fn load_ai_settings_with_key(db: &rusqlite::Connection, app_state: &c
     Ok(settings)
 }
 
+/// The API key saved for a provider. On desktop it lives in the keychain vault
+/// (cached in memory at startup); on Android it stays in the database.
+fn stored_api_key(app_state: &AppState, provider: AiProviderType) -> Result<Option<String>, String> {
+    #[cfg(not(target_os = "android"))]
+    {
+        Ok(app_state.get_cached_ai_api_key(&provider.to_string()))
+    }
+
+    #[cfg(target_os = "android")]
+    {
+        let db = app_state.db.lock().map_err(|e| e.to_string())?;
+        let settings = get_ai_settings(&db).map_err(|e| format!("Database error: {}", e))?;
+        Ok(settings
+            .api_key
+            .filter(|key| !key.is_empty() && settings.provider == provider))
+    }
+}
+
+/// Use the key typed into the settings UI, or the saved one when it's left blank
+fn api_key_or_stored(
+    app_state: &AppState,
+    provider: AiProviderType,
+    api_key: Option<String>,
+) -> Result<Option<String>, String> {
+    match api_key.filter(|key| !key.is_empty()) {
+        Some(key) => Ok(Some(key)),
+        None => stored_api_key(app_state, provider),
+    }
+}
+
 /// Get the default endpoint URL for a provider
 fn default_endpoint(provider: AiProviderType) -> String {
     match provider {
@@ -38,14 +76,16 @@ fn default_endpoint(provider: AiProviderType) -> String {
         AiProviderType::Gemini => "https://generativelanguage.googleapis.com".to_string(),
         AiProviderType::DeepSeek => "https://api.deepseek.com".to_string(),
         AiProviderType::Mistral => "https://api.mistral.ai".to_string(),
+        AiProviderType::Bedrock => "us-east-1".to_string(), // region, see BedrockProvider
     }
 }
 
-/// Response for AI settings
+/// Response for AI settings. The API key itself never leaves the backend;
+/// the frontend only learns whether one is saved.
 #[derive(Debug, Serialize)]
 pub struct AiSettingsResponse {
     pub provider: String,
-    pub api_key: Option<String>,
+    pub has_api_key: bool,
     pub model_name: String,
     pub endpoint_url: String,
     pub temperature: f32,
@@ -53,14 +93,17 @@ pub struct AiSettingsResponse {
     pub memory_enabled: bool,
     pub summary_model: Option<String>,
     pub summary_max_tokens: i32,
+    pub summary_min_input_length: u32,
     pub api_version: Option<String>,
+    pub max_retries: u32,
+    pub request_timeout_secs: u64,
 }
 
 impl From<AiSettings> for AiSettingsResponse {
     fn from(settings: AiSettings) -> Self {
         Self {
             provider: settings.provider.to_string(),
-            api_key: settings.api_key,
+            has_api_key: settings.api_key.is_some_and(|key| !key.is_empty()),
             model_name: settings.model_name,
             endpoint_url: settings.endpoint_url,
             temperature: settings.temperature,
@@ -68,7 +111,10 @@ impl From<AiSettings> for AiSettingsResponse {
             memory_enabled: settings.memory_enabled,
             summary_model: settings.summary_model,
             summary_max_tokens: settings.summary_max_tokens,
+            summary_min_input_length: settings.summary_min_input_length,
             api_version: settings.api_version,
+            max_retries: settings.max_retries,
+            request_timeout_secs: settings.request_timeout_secs,
         }
     }
 }
@@ -77,6 +123,7 @@ impl From<AiSettings> for AiSettingsResponse {
 #[derive(Debug, Deserialize)]
 pub struct UpdateAiSettingsRequest {
     pub provider: String,
+    /// New API key; `None` keeps the saved one and an empty string removes it
     pub api_key: Option<String>,
     pub model_name: String,
     pub endpoint_url: String,
@@ -85,7 +132,13 @@ pub struct UpdateAiSettingsRequest {
     pub memory_enabled: bool,
     pub summary_model: Option<String>,
     pub summary_max_tokens: i32,
+    #[serde(default)]
+    pub summary_min_input_length: Option<u32>,
     pub api_version: Option<String>,
+    #[serde(default)]
+    pub max_retries: Option<u32>,
+    #[serde(default)]
+    pub request_timeout_secs: Option<u64>,
 }
 
 impl From<UpdateAiSettingsRequest> for AiSettings {
@@ -100,7 +153,14 @@ impl From<UpdateAiSettingsRequest> for AiSettings {
             memory_enabled: req.memory_enabled,
             summary_model: req.summary_model,
             summary_max_tokens: req.summary_max_tokens,
+            summary_min_input_length: req
+                .summary_min_input_length
+                .unwrap_or(AiSettings::default().summary_min_input_length),
             api_version: req.api_version,
+            max_retries: req.max_retries.unwrap_or(AiSettings::default().max_retries),
+            request_timeout_secs: req
+                .request_timeout_secs
+                .unwrap_or(AiSettings::default().request_timeout_secs),
         }
     }
 }
@@ -116,38 +176,49 @@ pub struct ShellSuggestionRequest {
 
 /// Get current AI settings
 #[tauri::command]
-pub async fn get_ai_settings_cmd(state: State<'_, AppState>) -> Result<AiSettingsResponse, String> {
+pub async fn get_ai_settings_cmd(state: State<'_, AppState>) -> Result<AiSettingsResponse, AppError> {
     let db = state.db.lock().map_err(|e| e.to_string())?;
     let settings = load_ai_settings_with_key(&db, &state)?;
     Ok(settings.into())
 }
 
-/// Update AI settings
+/// Update AI settings, rejecting them with per-field errors if they're invalid
 #[tauri::command]
 pub async fn update_ai_settings_cmd(
     request: UpdateAiSettingsRequest,
     state: State<'_, AppState>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     info!("Updating AI settings: provider={}", request.provider);
 
     let mut settings: AiSettings = request.into();
+    if settings.api_key.is_none() {
+        settings.api_key = stored_api_key(&state, settings.provider)?;
+    }
+    settings
+        .validate()
+        .map_err(|errors| AppError::invalid_fields("Invalid AI settings", errors))?;
 
-    // Desktop: store API key in cache + vault, not in DB
+    // Desktop: store API key in cache + vault, not in DB. The vault is only
+    // rewritten when the key changed, since each write may prompt the OS keychain.
     #[cfg(not(target_os = "android"))]
     {
         let provider_str = settings.provider_to_str();
-        match &settings.api_key {
+        let cached = state.get_cached_ai_api_key(provider_str);
+        match settings.api_key.take() {
             Some(key) if !key.is_empty() => {
-                state.cache_ai_api_key(provider_str, key.clone());
-                state.flush_vault()
-                    .map_err(|e| format!("Failed to flush vault: {}", e))?;
-                settings.api_key = None; // Don't persist in DB
+                if cached.as_deref() != Some(key.as_str()) {
+                    state.cache_ai_api_key(provider_str, key);
+                    state.flush_vault()
+                        .map_err(|e| format!("Failed to flush vault: {}", e))?;
+                }
             }
             _ => {
                 // User cleared the key — remove from cache/vault
-                state.remove_cached_ai_api_key(provider_str);
-                state.flush_vault()
-                    .map_err(|e| format!("Failed to flush vault: {}", e))?;
+                if cached.is_some() {
+                    state.remove_cached_ai_api_key(provider_str);
+                    state.flush_vault()
+                        .map_err(|e| format!("Failed to flush vault: {}", e))?;
+                }
             }
         }
     }
@@ -160,29 +231,30 @@ pub async fn update_ai_settings_cmd(
 
 /// List available AI models for the current provider
 #[tauri::command]
-pub async fn list_ai_models(state: State<'_, AppState>) -> Result<Vec<AiModel>, String> {
+pub async fn list_ai_models(state: State<'_, AppState>) -> Result<Vec<AiModel>, AppError> {
     let settings = {
         let db = state.db.lock().map_err(|e| e.to_string())?;
         load_ai_settings_with_key(&db, &state)?
     };
 
     let provider = create_provider(&settings);
-    provider.list_models().await
+    provider.list_models().await.map_err(provider_error)
 }
 
 /// List available AI models for a specific provider (for settings UI)
 #[tauri::command]
 pub async fn list_models_for_provider(
+    state: State<'_, AppState>,
     provider_type: String,
     api_key: Option<String>,
     endpoint_url: Option<String>,
     api_version: Option<String>,
-) -> Result<Vec<AiModel>, String> {
+) -> Result<Vec<AiModel>, AppError> {
     let provider_enum = AiSettings::str_to_provider(&provider_type);
 
     let settings = AiSettings {
         provider: provider_enum,
-        api_key,
+        api_key: api_key_or_stored(&state, provider_enum, api_key)?,
         model_name: String::new(),
         endpoint_url: endpoint_url.unwrap_or_else(|| default_endpoint(provider_enum)),
         temperature: 0.3,
@@ -190,16 +262,22 @@ pub async fn list_models_for_provider(
         memory_enabled: true,
         summary_model: None,
         summary_max_tokens: 100,
+        summary_min_input_length: 100,
         api_version,
+        max_retries: 0, // settings UI probes should fail fast
+        request_timeout_secs: TEST_CONNECTION_TIMEOUT_SECS,
     };
 
     let provider = create_provider(&settings);
-    provider.list_models().await
+    provider.list_models().await.map_err(provider_error)
 }
 
-/// Test AI connection
+/// Test AI connection.
+///
+/// Returns the token usage of the test request when it was a completion, so
+/// users can see that billing works (`None` if no completion was needed).
 #[tauri::command]
-pub async fn test_ai_connection(state: State<'_, AppState>) -> Result<(), String> {
+pub async fn test_ai_connection(state: State<'_, AppState>) -> Result<Option<TokenUsage>, AppError> {
     let settings = {
         let db = state.db.lock().map_err(|e| e.to_string())?;
         load_ai_settings_with_key(&db, &state)?
@@ -207,37 +285,58 @@ pub async fn test_ai_connection(state: State<'_, AppState>) -> Result<(), String
 
     info!("Testing AI connection for provider: {}", settings.provider);
 
+    let settings = AiSettings {
+        request_timeout_secs: settings
+            .request_timeout_secs
+            .min(TEST_CONNECTION_TIMEOUT_SECS),
+        ..settings
+    };
     let provider = create_provider(&settings);
-    provider.test_connection().await
+    provider.test_connection().await.map_err(provider_error)
+}
+
+/// Surface provider timeouts as `ErrorKind::Timeout` so the frontend can
+/// suggest raising the request timeout instead of showing a generic failure
+fn provider_error(message: String) -> AppError {
+    if message.starts_with(TIMEOUT_ERROR) {
+        AppError::new(ErrorKind::Timeout, message)
+    } else {
+        message.into()
+    }
 }
 
 /// Test connection with specific settings (for settings UI)
 #[tauri::command]
 pub async fn test_ai_connection_with_settings(
+    state: State<'_, AppState>,
     provider_type: String,
     api_key: Option<String>,
     endpoint_url: Option<String>,
     api_version: Option<String>,
-) -> Result<(), String> {
+    model_name: Option<String>,
+) -> Result<Option<TokenUsage>, AppError> {
     let provider_enum = AiSettings::str_to_provider(&provider_type);
 
     info!("Testing AI connection for provider: {}", provider_enum);
 
     let settings = AiSettings {
         provider: provider_enum,
-        api_key,
-        model_name: String::new(),
+        api_key: api_key_or_stored(&state, provider_enum, api_key)?,
+        model_name: model_name.unwrap_or_default(),
         endpoint_url: endpoint_url.unwrap_or_else(|| default_endpoint(provider_enum)),
         temperature: 0.3,
         max_tokens: 256,
         memory_enabled: true,
         summary_model: None,
         summary_max_tokens: 100,
+        summary_min_input_length: 100,
         api_version,
+        max_retries: 0, // settings UI probes should fail fast
+        request_timeout_secs: TEST_CONNECTION_TIMEOUT_SECS,
     };
 
     let provider = create_provider(&settings);
-    provider.test_connection().await
+    provider.test_connection().await.map_err(provider_error)
 }
 
 /// Get a shell command suggestion from the AI
@@ -245,7 +344,7 @@ pub async fn test_ai_connection_with_settings(
 pub async fn get_shell_suggestion(
     request: ShellSuggestionRequest,
     state: State<'_, AppState>,
-) -> Result<ShellCommandResponse, String> {
+) -> Result<ShellCommandResponse, AppError> {
     let settings = {
         let db = state.db.lock().map_err(|e| e.to_string())?;
         load_ai_settings_with_key(&db, &state)?
@@ -263,9 +362,71 @@ pub async fn get_shell_suggestion(
         return Err(format!(
             "{} is not available. Please check your settings.",
             settings.provider
-        ));
+        ).into());
+    }
+
+    let completion_request = shell_completion_request(request, &settings);
+    let response = provider
+        .get_completion(completion_request)
+        .await
+        .map_err(provider_error)?;
+
+    Ok(parse_shell_response(response)?)
+}
+
+/// Get a shell command suggestion, emitting partial tokens as `ai:token` events
+/// (`{ requestId, token }`) while the completion streams in.
+#[tauri::command]
+pub async fn get_shell_suggestion_stream(
+    app: AppHandle,
+    request: ShellSuggestionRequest,
+    request_id: String,
+    state: State<'_, AppState>,
+) -> Result<ShellCommandResponse, AppError> {
+    let settings = {
+        let db = state.db.lock().map_err(|e| e.to_string())?;
+        load_ai_settings_with_key(&db, &state)?
+    };
+
+    info!(
+        "Streaming shell suggestion from {}: {}",
+        settings.provider, request.query
+    );
+
+    let provider = create_provider(&settings);
+
+    if !provider.is_available().await {
+        return Err(format!(
+            "{} is not available. Please check your settings.",
+            settings.provider
+        ).into());
     }
 
+    let completion_request = shell_completion_request(request, &settings);
+
+    // Forward tokens to the frontend as they arrive
+    let (tx, mut rx) = mpsc::channel::<String>(256);
+    let forwarder = tokio::spawn(async move {
+        while let Some(token) = rx.recv().await {
+            let _ = app.emit(
+                "ai:token",
+                serde_json::json!({
+                    "requestId": request_id,
+                    "token": token
+                }),
+            );
+        }
+    });
+
+    let result = provider.complete_stream(completion_request, tx).await;
+    // The sender is dropped with the stream, so the forwarder drains and exits
+    let _ = forwarder.await;
+
+    Ok(parse_shell_response(result.map_err(provider_error)?)?)
+}
+
+/// Build the JSON-mode completion request for a shell suggestion
+fn shell_completion_request(request: ShellSuggestionRequest, settings: &AiSettings) -> CompletionRequest {
     let os = request.os.as_deref().unwrap_or("linux");
     let shell = request.shell.as_deref().unwrap_or("bash");
 
@@ -281,18 +442,18 @@ pub async fn get_shell_suggestion(
         format!("User request: {}", request.query)
     };
 
-    let completion_request = CompletionRequest {
+    CompletionRequest {
         prompt: user_prompt,
         system_prompt: Some(system_prompt),
         context: request.context,
         temperature: Some(settings.temperature),
         max_tokens: Some(settings.max_tokens),
         json_mode: true,
-    };
-
-    let response = provider.get_completion(completion_request).await?;
+    }
+}
 
-    // Return structured response if available, otherwise try to parse from content
+/// Return the structured response if the provider parsed one, otherwise parse the content
+fn parse_shell_response(response: CompletionResponse) -> Result<ShellCommandResponse, String> {
     if let Some(structured) = response.structured {
         Ok(structured)
     } else {
@@ -302,18 +463,50 @@ pub async fn get_shell_suggestion(
     }
 }
 
+/// Event carrying each progress update of a pull started by `pull_ollama_model`
+pub const OLLAMA_PULL_PROGRESS_EVENT: &str = "ollama:pull-progress";
+
 /// Pull/download a model from Ollama
+///
+/// Resolves with Ollama's final status once the model is downloaded. Progress is
+/// emitted as `ollama:pull-progress` events tagged with `pull_id`, which is chosen
+/// by the caller and also stops the pull when passed to `cancel_execution`.
 #[tauri::command]
 pub async fn pull_ollama_model(
+    app: AppHandle,
+    executions: State<'_, RunningExecutions>,
     model_name: String,
     endpoint_url: Option<String>,
-) -> Result<String, String> {
+    pull_id: String,
+) -> Result<String, AppError> {
     let url = endpoint_url.unwrap_or_else(|| "http://localhost:11434".to_string());
 
     info!("Pulling Ollama model: {} from {}", model_name, url);
 
     let provider = OllamaProvider::new(&url, &model_name);
-    provider.pull_model(&model_name).await
+    let token = executions.start(&pull_id);
+    let result = provider
+        .pull_model(&model_name, token.clone(), |progress| {
+            let _ = app.emit(
+                OLLAMA_PULL_PROGRESS_EVENT,
+                serde_json::json!({
+                    "pullId": pull_id,
+                    "model": model_name,
+                    "status": progress.status,
+                    "digest": progress.digest,
+                    "completed": progress.completed,
+                    "total": progress.total,
+                    "percent": progress.percent(),
+                }),
+            );
+        })
+        .await;
+    executions.finish(&pull_id);
+
+    match result {
+        Err(e) if token.is_cancelled() => Err(ContainerError::Cancelled(e).into()),
+        result => Ok(result?),
+    }
 }
 
 /// Delete a model from Ollama
@@ -321,11 +514,69 @@ pub async fn pull_ollama_model(
 pub async fn delete_ollama_model(
     model_name: String,
     endpoint_url: Option<String>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     let url = endpoint_url.unwrap_or_else(|| "http://localhost:11434".to_string());
 
     info!("Deleting Ollama model: {} from {}", model_name, url);
 
     let provider = OllamaProvider::new(&url, &model_name);
-    provider.delete_model(&model_name).await
+    Ok(provider.delete_model(&model_name).await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_short_request_timeout_is_a_timeout_error() {
+        // Accept the connection but never answer, like a model that is still loading
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = std::thread::spawn(move || listener.accept().map(|(stream, _)| stream));
+
+        let settings = AiSettings {
+            endpoint_url: format!("http://127.0.0.1:{}", port),
+            model_name: "llama3.2".to_string(),
+            max_retries: 0,
+            request_timeout_secs: 1,
+            ..AiSettings::default()
+        };
+        let request = CompletionRequest {
+            prompt: "ping".to_string(),
+            system_prompt: None,
+            context: None,
+            temperature: None,
+            max_tokens: Some(1),
+            json_mode: false,
+        };
+        let err = create_provider(&settings)
+            .get_completion(request)
+            .await
+            .map_err(provider_error)
+            .unwrap_err();
+        drop(server.join());
+
+        assert_eq!(err.kind, ErrorKind::Timeout);
+        assert!(err.message.starts_with(TIMEOUT_ERROR));
+    }
+
+    #[test]
+    fn test_provider_error_keeps_other_failures_internal() {
+        let err = provider_error("Failed to connect to Ollama: refused".to_string());
+        assert_eq!(err.kind, ErrorKind::Internal);
+    }
+
+    #[test]
+    fn test_settings_response_never_contains_api_key() {
+        let settings = AiSettings {
+            api_key: Some("sk-secret-value".to_string()),
+            ..AiSettings::default()
+        };
+        let json = serde_json::to_string(&AiSettingsResponse::from(settings)).unwrap();
+        assert!(!json.contains("sk-secret-value"));
+        assert!(json.contains("\"has_api_key\":true"));
+
+        let json = serde_json::to_string(&AiSettingsResponse::from(AiSettings::default())).unwrap();
+        assert!(json.contains("\"has_api_key\":false"));
+    }
 }
@@ -1,17 +1,58 @@
+use std::collections::HashMap;
+use std::time::Instant;
+
+use serde::Serialize;
 use tauri::State;
 
+use crate::agent::safety::{DangerClassifier, DangerLevel};
+use crate::database;
+use crate::executor::{get_executor_for_system, ExecContext};
 use crate::models::command_template::{
-    CommandTemplate, CreateCommandTemplateRequest, UpdateCommandTemplateRequest,
+    has_unresolved_variables, substitute_variables, CommandTemplate,
+    CreateCommandTemplateRequest, TemplateImportResult, TemplateSortBy,
+    UpdateCommandTemplateRequest, VariableValidationError,
 };
-use crate::models::error::ContainerError;
+use crate::models::error::{AppError, ContainerError};
 use crate::state::AppState;
 
-/// List all command templates
+/// A template step with variables substituted, classified before it runs
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlannedStep {
+    pub index: usize,
+    pub description: String,
+    pub command: String,
+    pub danger_level: DangerLevel,
+    pub danger_explanation: String,
+}
+
+/// Output of one executed template step
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StepResult {
+    pub index: usize,
+    pub description: String,
+    pub command: String,
+    pub exit_code: i32,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+/// Outcome of running a template's steps; `failed_step` is the index of the step that stopped the run
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateRunResult {
+    pub steps: Vec<StepResult>,
+    pub failed_step: Option<usize>,
+}
+
+/// List all command templates, sorted by name unless `sort_by` says otherwise
 #[tauri::command]
 pub fn list_command_templates(
     state: State<'_, AppState>,
-) -> Result<Vec<CommandTemplate>, ContainerError> {
-    state.list_command_templates()
+    sort_by: Option<TemplateSortBy>,
+) -> Result<Vec<CommandTemplate>, AppError> {
+    Ok(state.list_command_templates_sorted(sort_by.unwrap_or_default())?)
 }
 
 /// Get a single command template by ID
@@ -19,8 +60,8 @@ pub fn list_command_templates(
 pub fn get_command_template(
     state: State<'_, AppState>,
     id: String,
-) -> Result<Option<CommandTemplate>, ContainerError> {
-    state.get_command_template(&id)
+) -> Result<Option<CommandTemplate>, AppError> {
+    Ok(state.get_command_template(&id)?)
 }
 
 /// Create a new command template
@@ -28,8 +69,8 @@ pub fn get_command_template(
 pub fn create_command_template(
     state: State<'_, AppState>,
     request: CreateCommandTemplateRequest,
-) -> Result<CommandTemplate, ContainerError> {
-    state.create_command_template(request)
+) -> Result<CommandTemplate, AppError> {
+    Ok(state.create_command_template(request)?)
 }
 
 /// Update an existing command template
@@ -37,8 +78,8 @@ pub fn create_command_template(
 pub fn update_command_template(
     state: State<'_, AppState>,
     request: UpdateCommandTemplateRequest,
-) -> Result<CommandTemplate, ContainerError> {
-    state.update_command_template(request)
+) -> Result<CommandTemplate, AppError> {
+    Ok(state.update_command_template(request)?)
 }
 
 /// Delete a command template (only non-built-in templates can be deleted)
@@ -46,8 +87,8 @@ pub fn update_command_template(
 pub fn delete_command_template(
     state: State<'_, AppState>,
     id: String,
-) -> Result<bool, ContainerError> {
-    state.delete_command_template(&id)
+) -> Result<bool, AppError> {
+    Ok(state.delete_command_template(&id)?)
 }
 
 /// Toggle the favorite status of a command template
@@ -55,8 +96,17 @@ pub fn delete_command_template(
 pub fn toggle_command_favorite(
     state: State<'_, AppState>,
     id: String,
-) -> Result<CommandTemplate, ContainerError> {
-    state.toggle_command_favorite(&id)
+) -> Result<CommandTemplate, AppError> {
+    Ok(state.toggle_command_favorite(&id)?)
+}
+
+/// Record that a command template was executed
+#[tauri::command]
+pub fn record_template_use(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<CommandTemplate, AppError> {
+    Ok(state.record_template_use(&id)?)
 }
 
 /// Duplicate a command template
@@ -64,6 +114,176 @@ pub fn toggle_command_favorite(
 pub fn duplicate_command_template(
     state: State<'_, AppState>,
     id: String,
-) -> Result<CommandTemplate, ContainerError> {
-    state.duplicate_command_template(&id)
+) -> Result<CommandTemplate, AppError> {
+    Ok(state.duplicate_command_template(&id)?)
+}
+
+/// Validate variable values for a template before they are substituted into its command
+#[tauri::command]
+pub fn validate_template_variables(
+    state: State<'_, AppState>,
+    template_id: String,
+    values: HashMap<String, String>,
+) -> Result<Vec<VariableValidationError>, AppError> {
+    let template = state
+        .get_command_template(&template_id)?
+        .ok_or_else(|| ContainerError::NotFound {
+            resource: "CommandTemplate".to_string(),
+            id: template_id,
+        })?;
+    Ok(template.validate_variables(&values))
+}
+
+/// Export custom command templates (all, or the given IDs) as a JSON document
+#[tauri::command]
+pub fn export_command_templates(
+    state: State<'_, AppState>,
+    ids: Option<Vec<String>>,
+) -> Result<String, AppError> {
+    Ok(state.export_command_templates(ids)?)
+}
+
+/// Import command templates from a JSON document produced by export
+#[tauri::command]
+pub fn import_command_templates(
+    state: State<'_, AppState>,
+    json: String,
+    overwrite: bool,
+) -> Result<TemplateImportResult, AppError> {
+    Ok(state.import_command_templates(&json, overwrite)?)
+}
+
+/// Substitute variables into every step of a template and classify each step on its own,
+/// so one dangerous step in a composite template can't hide behind the others
+fn plan_steps(
+    state: &AppState,
+    template_id: &str,
+    values: &HashMap<String, String>,
+) -> Result<Vec<PlannedStep>, ContainerError> {
+    let template = state
+        .get_command_template(template_id)?
+        .ok_or_else(|| ContainerError::NotFound {
+            resource: "CommandTemplate".to_string(),
+            id: template_id.to_string(),
+        })?;
+
+    let errors = template.validate_variables(values);
+    if !errors.is_empty() {
+        let details: Vec<String> = errors
+            .iter()
+            .map(|e| format!("{}: {}", e.name, e.message))
+            .collect();
+        return Err(ContainerError::InvalidConfiguration(details.join("; ")));
+    }
+
+    let rules = {
+        let db = state.db.lock().map_err(|_| ContainerError::DatabaseError {
+            message: "Failed to acquire database lock".to_string(),
+        })?;
+        database::get_danger_rules(&db).map_err(|e| ContainerError::DatabaseError {
+            message: e.to_string(),
+        })?
+    };
+    let classifier = DangerClassifier::with_rules(&rules);
+
+    template
+        .execution_steps()
+        .into_iter()
+        .enumerate()
+        .map(|(index, step)| {
+            let command = substitute_variables(&step.command, values);
+            if has_unresolved_variables(&command) {
+                return Err(ContainerError::InvalidConfiguration(format!(
+                    "Step {} has unresolved variables: {}",
+                    index + 1,
+                    command
+                )));
+            }
+            let danger = classifier.classify(&command);
+            Ok(PlannedStep {
+                index,
+                description: step.description,
+                command,
+                danger_level: danger.level,
+                danger_explanation: danger.explanation,
+            })
+        })
+        .collect()
+}
+
+/// Preview the commands a template will run, with each step's danger level
+#[tauri::command]
+pub fn plan_template_steps(
+    state: State<'_, AppState>,
+    template_id: String,
+    values: HashMap<String, String>,
+) -> Result<Vec<PlannedStep>, AppError> {
+    Ok(plan_steps(&state, &template_id, &values)?)
+}
+
+/// Run a template's steps in order on a system, stopping at the first step that exits non-zero
+///
+/// Steps that need confirmation are refused unless `confirmed` is set, which
+/// the frontend does after showing the plan from `plan_template_steps`. Every
+/// step runs with the same working directory and environment from `context`.
+#[tauri::command]
+pub async fn run_template_steps(
+    state: State<'_, AppState>,
+    system_id: String,
+    template_id: String,
+    values: HashMap<String, String>,
+    confirmed: bool,
+    context: Option<ExecContext>,
+) -> Result<TemplateRunResult, AppError> {
+    let system = state
+        .get_system(&system_id)
+        .ok_or_else(|| ContainerError::SystemNotFound(system_id.clone()))?;
+
+    let plan = plan_steps(&state, &template_id, &values)?;
+    if !confirmed {
+        if let Some(step) = plan.iter().find(|s| s.danger_level.requires_confirmation()) {
+            return Err(ContainerError::PermissionDenied(format!(
+                "Step {} ({}) needs confirmation: {}",
+                step.index + 1,
+                step.description,
+                step.danger_explanation
+            )).into());
+        }
+    }
+
+    state.record_template_use(&template_id)?;
+
+    let context = context.unwrap_or_default();
+    let executor = get_executor_for_system(&system);
+    let mut steps = Vec::with_capacity(plan.len());
+    for step in plan {
+        let started = Instant::now();
+        let output = executor.execute_in_dir(&step.command, &context).await?;
+        state.record_command_history(
+            &system_id,
+            &step.command,
+            Some(output.exit_code),
+            started.elapsed(),
+        );
+        let failed = !output.success();
+        steps.push(StepResult {
+            index: step.index,
+            description: step.description,
+            command: step.command,
+            exit_code: output.exit_code,
+            stdout: output.stdout,
+            stderr: output.stderr,
+        });
+        if failed {
+            return Ok(TemplateRunResult {
+                failed_step: Some(step.index),
+                steps,
+            });
+        }
+    }
+
+    Ok(TemplateRunResult {
+        steps,
+        failed_step: None,
+    })
 }
@@ -1,19 +1,37 @@
-use tauri::State;
+use std::collections::HashMap;
 
+use futures::stream::{self, StreamExt};
+use tauri::{AppHandle, Emitter, State};
+use tokio_util::sync::CancellationToken;
+
+use super::exec::RunningExecutions;
+use crate::agent::safety::DangerClassifier;
+use crate::database;
 use crate::executor::local::LocalExecutor;
-use crate::executor::CommandExecutor;
-use crate::models::container::{Container, ContainerAction, ContainerDetails, ContainerRuntime};
-use crate::models::error::ContainerError;
-use crate::models::system::ConnectionType;
+use crate::executor::{
+    get_executor_for_system, CommandExecutor, CommandResult, ExecContext, OutputChunk,
+};
+use crate::models::container::{
+    is_restart_policy, is_valid_log_time, validate_container_name, Container, ContainerAction,
+    ContainerActionResult, ContainerCreateSpec, ContainerDetails, ContainerListFilter,
+    ContainerLogOptions, ContainerRuntime,
+};
+use crate::models::error::{AppError, ContainerError};
+use crate::models::system::{ConnectionType, ContainerSystem};
 use crate::runtime::{CommandBuilder, OutputParser};
 use crate::state::AppState;
 
 /// List all containers for a system across all available runtimes
+///
+/// `filter` is applied by the runtime where it supports `--filter`, and after
+/// parsing for Apple's; without it every container is returned.
 #[tauri::command]
 pub async fn list_containers(
     state: State<'_, AppState>,
     system_id: String,
-) -> Result<Vec<Container>, ContainerError> {
+    filter: Option<ContainerListFilter>,
+) -> Result<Vec<Container>, AppError> {
+    let filter = filter.unwrap_or_default();
     let system = state
         .get_system(&system_id)
         .ok_or_else(|| ContainerError::SystemNotFound(system_id.clone()))?;
@@ -24,7 +42,7 @@ pub async fn list_containers(
         return Err(ContainerError::ConnectionFailed(
             system.hostname.clone(),
             "System is not connected".to_string(),
-        ));
+        ).into());
     }
 
     let mut all_containers = Vec::new();
@@ -34,14 +52,16 @@ pub async fn list_containers(
         ConnectionType::Local => Box::new(LocalExecutor::new()),
         ConnectionType::Remote => {
             // For remote, we use the SSH pool
-            return list_containers_remote(&system_id, &system.available_runtimes).await;
+            return Ok(
+                list_containers_remote(&system_id, &system.available_runtimes, &filter).await?,
+            );
         }
     };
 
     // Fetch from all available runtimes
     for runtime in &system.available_runtimes {
         // First get container IDs from docker ps
-        let command = CommandBuilder::list_containers(*runtime);
+        let command = CommandBuilder::list_containers_filtered(*runtime, &filter);
 
         match executor.execute(&command).await {
             Ok(result) if result.success() => {
@@ -67,7 +87,11 @@ pub async fn list_containers(
                                     *runtime,
                                     &system_id,
                                 ) {
-                                    Ok(containers) => {
+                                    Ok(mut containers) => {
+                                        // Apple's list command can't filter
+                                        if *runtime == ContainerRuntime::Apple {
+                                            containers.retain(|c| filter.matches(c));
+                                        }
                                         all_containers.extend(containers);
                                     }
                                     Err(e) => {
@@ -110,12 +134,13 @@ pub async fn list_containers(
 async fn list_containers_remote(
     system_id: &str,
     runtimes: &std::collections::HashSet<ContainerRuntime>,
+    filter: &ContainerListFilter,
 ) -> Result<Vec<Container>, ContainerError> {
     let mut all_containers = Vec::new();
 
     for runtime in runtimes {
         // First get container IDs from docker ps
-        let command = CommandBuilder::list_containers(*runtime);
+        let command = CommandBuilder::list_containers_filtered(*runtime, filter);
 
         match crate::ssh::execute_on_system(system_id, &command).await {
             Ok(result) if result.success() => {
@@ -142,7 +167,11 @@ async fn list_containers_remote(
                                     *runtime,
                                     system_id,
                                 ) {
-                                    Ok(containers) => {
+                                    Ok(mut containers) => {
+                                        // Apple's list command can't filter
+                                        if *runtime == ContainerRuntime::Apple {
+                                            containers.retain(|c| filter.matches(c));
+                                        }
                                         all_containers.extend(containers);
                                     }
                                     Err(e) => {
@@ -192,6 +221,9 @@ async fn list_containers_remote(
 }
 
 /// Perform an action on a container (start, stop, restart, pause, unpause, remove)
+///
+/// `context` lets the caller pick the working directory and extra environment
+/// (e.g. `DOCKER_HOST`) the runtime CLI runs with.
 #[tauri::command]
 pub async fn perform_container_action(
     state: State<'_, AppState>,
@@ -199,27 +231,25 @@ pub async fn perform_container_action(
     container_id: String,
     action: ContainerAction,
     runtime: ContainerRuntime,
-) -> Result<(), ContainerError> {
+    context: Option<ExecContext>,
+) -> Result<(), AppError> {
     let system = state
         .get_system(&system_id)
         .ok_or_else(|| ContainerError::SystemNotFound(system_id.clone()))?;
 
     let command = CommandBuilder::container_action(runtime, action, &container_id);
 
-    let result = match system.connection_type {
-        ConnectionType::Local => {
-            let executor = LocalExecutor::new();
-            executor.execute(&command).await?
-        }
-        ConnectionType::Remote => crate::ssh::execute_on_system(&system_id, &command).await?,
-    };
+    let executor = get_executor_for_system(&system);
+    let result = executor
+        .execute_in_dir(&command, &context.unwrap_or_default())
+        .await?;
 
     if !result.success() {
         return Err(ContainerError::CommandExecutionFailed {
             command,
             exit_code: result.exit_code,
             stderr: result.stderr,
-        });
+        }.into());
     }
 
     tracing::info!(
@@ -232,28 +262,361 @@ pub async fn perform_container_action(
     Ok(())
 }
 
+/// Create a container from a structured spec, starting it unless `spec.detach`
+/// is false, and return its ID
+#[tauri::command]
+pub async fn create_container(
+    state: State<'_, AppState>,
+    system_id: String,
+    runtime: ContainerRuntime,
+    spec: ContainerCreateSpec,
+    context: Option<ExecContext>,
+) -> Result<String, AppError> {
+    let system = state
+        .get_system(&system_id)
+        .ok_or_else(|| ContainerError::SystemNotFound(system_id.clone()))?;
+
+    spec.validate(runtime)?;
+    let command = CommandBuilder::create_container(runtime, &spec);
+
+    let executor = get_executor_for_system(&system);
+    let result = executor
+        .execute_in_dir(&command, &context.unwrap_or_default())
+        .await?;
+
+    if !result.success() {
+        return Err(ContainerError::CommandExecutionFailed {
+            command,
+            exit_code: result.exit_code,
+            stderr: result.stderr,
+        }
+        .into());
+    }
+
+    // Pull progress can come first; the ID is the last line
+    let container_id = result
+        .stdout
+        .lines()
+        .map(str::trim)
+        .rfind(|line| !line.is_empty())
+        .unwrap_or_default()
+        .to_string();
+
+    tracing::info!(
+        "Created container {} from {} (runtime: {:?})",
+        container_id,
+        spec.image,
+        runtime
+    );
+
+    Ok(container_id)
+}
+
+/// Rename a container and return it as `list_containers` would
+#[tauri::command]
+pub async fn rename_container(
+    state: State<'_, AppState>,
+    system_id: String,
+    container_id: String,
+    new_name: String,
+    runtime: ContainerRuntime,
+) -> Result<Container, AppError> {
+    let system = state
+        .get_system(&system_id)
+        .ok_or_else(|| ContainerError::SystemNotFound(system_id.clone()))?;
+
+    validate_container_name(&new_name)?;
+    let command =
+        CommandBuilder::rename_container(runtime, &container_id, &new_name).ok_or_else(|| {
+            ContainerError::UnsupportedOperation(format!("{:?} can't rename containers", runtime))
+        })?;
+
+    let executor = get_executor_for_system(&system);
+    run_container_action(executor.as_ref(), command, &ExecContext::default()).await?;
+    tracing::info!("Renamed container {} to {}", container_id, new_name);
+
+    // The old name no longer resolves
+    Ok(fetch_container(&system, &new_name, runtime).await?)
+}
+
+/// Change a container's restart policy (no, always, unless-stopped or
+/// on-failure, with `max_retries` for the latter) and return the container
+#[tauri::command]
+pub async fn update_container_restart_policy(
+    state: State<'_, AppState>,
+    system_id: String,
+    container_id: String,
+    policy: String,
+    max_retries: Option<u32>,
+    runtime: ContainerRuntime,
+) -> Result<Container, AppError> {
+    let system = state
+        .get_system(&system_id)
+        .ok_or_else(|| ContainerError::SystemNotFound(system_id.clone()))?;
+
+    if !is_restart_policy(&policy) {
+        return Err(ContainerError::InvalidConfiguration(format!(
+            "Invalid restart policy '{}': expected no, always, unless-stopped or on-failure",
+            policy
+        ))
+        .into());
+    }
+    let command =
+        CommandBuilder::update_restart_policy(runtime, &container_id, &policy, max_retries)
+            .ok_or_else(|| {
+                ContainerError::UnsupportedOperation(format!(
+                    "{:?} doesn't support restart policies",
+                    runtime
+                ))
+            })?;
+
+    let executor = get_executor_for_system(&system);
+    run_container_action(executor.as_ref(), command, &ExecContext::default()).await?;
+    tracing::info!(
+        "Set restart policy of container {} to {}",
+        container_id,
+        policy
+    );
+
+    Ok(fetch_container(&system, &container_id, runtime).await?)
+}
+
+/// Inspect one container into the shape `list_containers` returns
+async fn fetch_container(
+    system: &ContainerSystem,
+    container_id: &str,
+    runtime: ContainerRuntime,
+) -> Result<Container, ContainerError> {
+    let command = CommandBuilder::inspect_container(runtime, container_id);
+    let result = get_executor_for_system(system).execute(&command).await?;
+    if !result.success() {
+        return Err(ContainerError::CommandExecutionFailed {
+            command,
+            exit_code: result.exit_code,
+            stderr: result.stderr,
+        });
+    }
+
+    OutputParser::parse_full_containers_from_inspect(&result.stdout, runtime, &system.id.0)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| ContainerError::ContainerNotFound(container_id.to_string()))
+}
+
+/// Containers `perform_container_action_bulk` acts on at the same time when
+/// the runtime can't take them all in one command
+const BULK_ACTION_CONCURRENCY: usize = 4;
+
+/// Perform an action on several containers, reporting a result per container
+///
+/// Uses a single runtime invocation (`docker stop a b c`) where the runtime
+/// supports it and separate concurrent ones otherwise. `force` makes `Remove`
+/// a forced removal. Commands the danger classifier flags (like `rm -f`) are
+/// refused unless `confirmed` is set.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn perform_container_action_bulk(
+    state: State<'_, AppState>,
+    system_id: String,
+    container_ids: Vec<String>,
+    action: ContainerAction,
+    runtime: ContainerRuntime,
+    force: Option<bool>,
+    confirmed: bool,
+    context: Option<ExecContext>,
+) -> Result<HashMap<String, ContainerActionResult>, AppError> {
+    let system = state
+        .get_system(&system_id)
+        .ok_or_else(|| ContainerError::SystemNotFound(system_id.clone()))?;
+
+    let mut ids: Vec<&str> = Vec::with_capacity(container_ids.len());
+    for id in &container_ids {
+        if !ids.contains(&id.as_str()) {
+            ids.push(id);
+        }
+    }
+    if ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let bulk_command =
+        CommandBuilder::bulk_container_action(runtime, action, &ids, force.unwrap_or(false));
+    let commands: Vec<String> = match &bulk_command {
+        Some(command) => vec![command.clone()],
+        None => ids
+            .iter()
+            .map(|id| CommandBuilder::container_action(runtime, action, id))
+            .collect(),
+    };
+
+    if !confirmed {
+        let rules = {
+            let db = state.db.lock().map_err(|_| ContainerError::DatabaseError {
+                message: "Failed to acquire database lock".to_string(),
+            })?;
+            database::get_danger_rules(&db).map_err(|e| ContainerError::DatabaseError {
+                message: e.to_string(),
+            })?
+        };
+        let classifier = DangerClassifier::with_rules(&rules);
+        if let Some(danger) = commands
+            .iter()
+            .map(|command| classifier.classify(command))
+            .find(|danger| danger.requires_confirmation())
+        {
+            return Err(ContainerError::PermissionDenied(format!(
+                "{:?} on {} containers needs confirmation: {}",
+                action,
+                ids.len(),
+                danger.explanation
+            ))
+            .into());
+        }
+    }
+
+    let executor = get_executor_for_system(&system);
+    let context = context.unwrap_or_default();
+
+    let results: HashMap<String, ContainerActionResult> = match bulk_command {
+        Some(command) => {
+            let result = executor.execute_in_dir(&command, &context).await?;
+            let failures = if result.success() {
+                Vec::new()
+            } else {
+                OutputParser::parse_bulk_action_failures(&result.stdout, &result.stderr, &ids)
+            };
+            ids.iter()
+                .map(|id| {
+                    let outcome = match failures.iter().find(|(failed, _)| failed == id) {
+                        Some((_, stderr)) => Err(ContainerError::CommandExecutionFailed {
+                            command: command.clone(),
+                            exit_code: result.exit_code,
+                            stderr: stderr.clone(),
+                        }),
+                        None => Ok(()),
+                    };
+                    (id.to_string(), outcome.into())
+                })
+                .collect()
+        }
+        None => {
+            let executor = executor.as_ref();
+            let context = &context;
+            let ids: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+            stream::iter(ids.into_iter().zip(commands))
+                .map(|(id, command)| async move {
+                    let outcome = run_container_action(executor, command, context).await;
+                    (id, outcome.into())
+                })
+                .buffer_unordered(BULK_ACTION_CONCURRENCY)
+                .collect()
+                .await
+        }
+    };
+
+    tracing::info!(
+        "Performed {:?} action on {} containers (runtime: {:?}, {} failed)",
+        action,
+        ids.len(),
+        runtime,
+        results.values().filter(|r| !r.success).count()
+    );
+
+    Ok(results)
+}
+
+/// Run one container action command, failing on a non-zero exit
+async fn run_container_action(
+    executor: &dyn CommandExecutor,
+    command: String,
+    context: &ExecContext,
+) -> Result<(), ContainerError> {
+    let result = executor.execute_in_dir(&command, context).await?;
+    if !result.success() {
+        return Err(ContainerError::CommandExecutionFailed {
+            command,
+            exit_code: result.exit_code,
+            stderr: result.stderr,
+        });
+    }
+    Ok(())
+}
+
+/// Number of lines `get_container_logs` returns when neither `tail` nor a time range is given
+const DEFAULT_LOG_TAIL: u32 = 500;
+
+/// Reject `--since`/`--until` values that are neither a duration nor an RFC3339 timestamp
+fn validate_log_time(flag: &str, value: Option<&str>) -> Result<(), ContainerError> {
+    match value {
+        Some(value) if !is_valid_log_time(value) => {
+            Err(ContainerError::InvalidConfiguration(format!(
+                "Invalid {} value '{}': expected a duration like 10m or an RFC3339 timestamp",
+                flag, value
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
 /// Get container logs
+///
+/// `since`/`until` limit the time range and take a duration (`10m`) or an
+/// RFC3339 timestamp. Without `tail` or a range, the last 500 lines are returned.
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn get_container_logs(
     state: State<'_, AppState>,
     system_id: String,
     container_id: String,
     runtime: ContainerRuntime,
     tail: Option<u32>,
+    since: Option<String>,
+    until: Option<String>,
     timestamps: bool,
-) -> Result<String, ContainerError> {
+) -> Result<String, AppError> {
     let system = state
         .get_system(&system_id)
         .ok_or_else(|| ContainerError::SystemNotFound(system_id.clone()))?;
 
-    let command = CommandBuilder::container_logs(runtime, &container_id, tail, timestamps);
+    Ok(fetch_container_logs(
+        &system,
+        &container_id,
+        runtime,
+        tail,
+        since.as_deref(),
+        until.as_deref(),
+        timestamps,
+    )
+    .await?)
+}
+
+/// Run `logs` for a container on `system` and return its output, as
+/// `get_container_logs` does (the agent's log tool shares this path)
+pub(crate) async fn fetch_container_logs(
+    system: &ContainerSystem,
+    container_id: &str,
+    runtime: ContainerRuntime,
+    tail: Option<u32>,
+    since: Option<&str>,
+    until: Option<&str>,
+    timestamps: bool,
+) -> Result<String, ContainerError> {
+    validate_log_time("since", since)?;
+    validate_log_time("until", until)?;
+    let tail = match (tail, since, until) {
+        (None, None, None) => Some(DEFAULT_LOG_TAIL),
+        _ => tail,
+    };
+
+    let command =
+        CommandBuilder::container_logs(runtime, container_id, tail, since, until, timestamps);
 
     let result = match system.connection_type {
         ConnectionType::Local => {
             let executor = LocalExecutor::new();
             executor.execute(&command).await?
         }
-        ConnectionType::Remote => crate::ssh::execute_on_system(&system_id, &command).await?,
+        ConnectionType::Remote => crate::ssh::execute_on_system(&system.id.0, &command).await?,
     };
 
     // Logs can be in stdout or stderr depending on the container
@@ -264,6 +627,267 @@ pub async fn get_container_logs(
     })
 }
 
+/// Event carrying each line of a log stream started by `stream_container_logs`
+pub const CONTAINER_LOGS_EVENT: &str = "container:logs";
+
+/// Event emitted once a log stream ends, because it was stopped or the container went away
+pub const CONTAINER_LOGS_ENDED_EVENT: &str = "container:logs-ended";
+
+/// Follow a container's logs, emitting each line as a `container:logs` event
+///
+/// Returns a stream ID carried by every event; stop the stream with
+/// `stop_container_log_stream`, which kills the `logs -f` process itself.
+#[tauri::command]
+pub async fn stream_container_logs(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    executions: State<'_, RunningExecutions>,
+    system_id: String,
+    container_id: String,
+    runtime: ContainerRuntime,
+    options: Option<ContainerLogOptions>,
+) -> Result<String, AppError> {
+    let system = state
+        .get_system(&system_id)
+        .ok_or_else(|| ContainerError::SystemNotFound(system_id.clone()))?;
+
+    let options = options.unwrap_or_default();
+    validate_log_time("since", options.since.as_deref())?;
+    let command = CommandBuilder::container_logs_stream(runtime, &container_id, &options);
+
+    let stream_id = uuid::Uuid::new_v4().to_string();
+    let token = executions.start(&stream_id);
+    let executions = executions.inner().clone();
+    let id = stream_id.clone();
+
+    tokio::spawn(async move {
+        let (result, cancelled) = run_line_stream(&system, &command, token, |stream, line| {
+            let wanted = if stream == "stdout" {
+                options.stdout
+            } else {
+                options.stderr
+            };
+            if wanted {
+                let _ = app.emit(
+                    CONTAINER_LOGS_EVENT,
+                    serde_json::json!({
+                        "streamId": id,
+                        "systemId": system_id,
+                        "containerId": container_id,
+                        "stream": stream,
+                        "line": line,
+                    }),
+                );
+            }
+        })
+        .await;
+        executions.finish(&id);
+
+        let _ = app.emit(
+            CONTAINER_LOGS_ENDED_EVENT,
+            serde_json::json!({
+                "streamId": id,
+                "systemId": system_id,
+                "containerId": container_id,
+                "cancelled": cancelled,
+                "exitCode": result.as_ref().ok().map(|r| r.exit_code),
+                "error": result.err().filter(|_| !cancelled).map(|e| e.to_string()),
+            }),
+        );
+    });
+
+    Ok(stream_id)
+}
+
+/// Event carrying each parsed event of a stream started by `stream_docker_events`
+pub const RUNTIME_EVENT: &str = "runtime:event";
+
+/// Event emitted once a runtime event stream ends
+pub const RUNTIME_EVENTS_ENDED_EVENT: &str = "runtime:events-ended";
+
+/// Follow a runtime's lifecycle events, emitting each as a `runtime:event` event
+///
+/// Uses the system's primary runtime unless one is given. Returns a stream ID
+/// carried by every event; stop the stream with `stop_docker_events`.
+#[tauri::command]
+pub async fn stream_docker_events(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    executions: State<'_, RunningExecutions>,
+    system_id: String,
+    runtime: Option<ContainerRuntime>,
+) -> Result<String, AppError> {
+    let system = state
+        .get_system(&system_id)
+        .ok_or_else(|| ContainerError::SystemNotFound(system_id.clone()))?;
+
+    let runtime = runtime.unwrap_or(system.primary_runtime);
+    let command = CommandBuilder::events(runtime).ok_or_else(|| {
+        ContainerError::UnsupportedOperation(format!("{:?} does not report events", runtime))
+    })?;
+
+    let stream_id = uuid::Uuid::new_v4().to_string();
+    let token = executions.start(&stream_id);
+    let executions = executions.inner().clone();
+    let id = stream_id.clone();
+
+    tokio::spawn(async move {
+        let (result, cancelled) = run_line_stream(&system, &command, token, |stream, line| {
+            if stream != "stdout" {
+                return;
+            }
+            match OutputParser::parse_event(line) {
+                Some(event) => {
+                    let _ = app.emit(
+                        RUNTIME_EVENT,
+                        serde_json::json!({
+                            "streamId": id,
+                            "systemId": system_id,
+                            "event": event,
+                        }),
+                    );
+                }
+                None => tracing::debug!("Skipping unparseable runtime event: {}", line),
+            }
+        })
+        .await;
+        executions.finish(&id);
+
+        let _ = app.emit(
+            RUNTIME_EVENTS_ENDED_EVENT,
+            serde_json::json!({
+                "streamId": id,
+                "systemId": system_id,
+                "cancelled": cancelled,
+                "exitCode": result.as_ref().ok().map(|r| r.exit_code),
+                "error": result.err().filter(|_| !cancelled).map(|e| e.to_string()),
+            }),
+        );
+    });
+
+    Ok(stream_id)
+}
+
+/// Stop an event stream started by `stream_docker_events`, killing its `events` process
+#[tauri::command]
+pub fn stop_docker_events(
+    executions: State<'_, RunningExecutions>,
+    stream_id: String,
+) -> Result<(), AppError> {
+    if executions.cancel(&stream_id) {
+        tracing::info!("Stopped runtime event stream {}", stream_id);
+        Ok(())
+    } else {
+        Err(ContainerError::NotFound {
+            resource: "Event stream".to_string(),
+            id: stream_id,
+        }
+        .into())
+    }
+}
+
+/// First line printed by a wrapped stream, followed by the PID of the streaming process
+const STREAM_PID_MARKER: &str = "__STREAM_PID__:";
+
+/// Longest partial line held while waiting for its newline; longer output (e.g.
+/// `\r` progress bars in `logs -f`) is handed on as it is
+const MAX_PENDING_LINE: usize = 64 * 1024;
+
+/// Run a long-lived command until it exits or `token` is cancelled, handing each
+/// complete output line to `on_line` along with `"stdout"` or `"stderr"`
+///
+/// Under a POSIX shell the command reports its PID and `exec`s into the real
+/// command, so a cancelled stream kills the process itself: closing an SSH channel
+/// alone leaves it running on the host until it next writes. Output is not kept,
+/// so the returned result has empty `stdout` and `stderr`. Returns the execution
+/// result and whether the stream was cancelled.
+async fn run_line_stream(
+    system: &ContainerSystem,
+    command: &str,
+    token: CancellationToken,
+    mut on_line: impl FnMut(&'static str, &str) + Send,
+) -> (Result<CommandResult, ContainerError>, bool) {
+    // Remote systems are reached through a POSIX shell; only local Windows runs `cmd`
+    let killable = system.connection_type == ConnectionType::Remote || !cfg!(target_os = "windows");
+    let command = if killable {
+        format!("echo {}$$; exec {}", STREAM_PID_MARKER, command)
+    } else {
+        command.to_string()
+    };
+
+    let mut pid: Option<u32> = None;
+    // Output arrives in arbitrary chunks, so lines are reassembled per stream
+    let mut pending = [String::new(), String::new()];
+    let mut on_output = |chunk: &OutputChunk| {
+        let (index, stream, text) = match chunk {
+            OutputChunk::Stdout(text) => (0, "stdout", text),
+            OutputChunk::Stderr(text) => (1, "stderr", text),
+            OutputChunk::Exit(_) => return,
+        };
+        pending[index].push_str(text);
+        while let Some(end) = pending[index].find('\n') {
+            let line: String = pending[index].drain(..=end).collect();
+            let line = line.trim_end_matches(['\r', '\n']);
+            if index == 0 && killable && pid.is_none() {
+                if let Some(rest) = line.strip_prefix(STREAM_PID_MARKER) {
+                    pid = rest.trim().parse().ok();
+                    continue;
+                }
+            }
+            on_line(stream, line);
+        }
+        if pending[index].len() > MAX_PENDING_LINE {
+            on_line(stream, &pending[index]);
+            pending[index].clear();
+        }
+    };
+
+    let executor = get_executor_for_system(system);
+    let result = executor
+        .execute_cancellable_streaming(&command, &ExecContext::default(), token, &mut on_output)
+        .await;
+
+    for (line, stream) in [(&pending[0], "stdout"), (&pending[1], "stderr")] {
+        if !line.is_empty() {
+            on_line(stream, line);
+        }
+    }
+
+    // Locally the process dies with its stream; over SSH it has to be killed explicitly
+    let cancelled = matches!(result, Err(ContainerError::Cancelled(_)));
+    if cancelled && system.connection_type == ConnectionType::Remote {
+        if let Some(pid) = pid {
+            if let Err(e) = executor.execute(&format!("kill {} 2>/dev/null", pid)).await {
+                tracing::warn!(
+                    "Failed to kill stream (pid {}) on {}: {}",
+                    pid,
+                    system.id.0,
+                    e
+                );
+            }
+        }
+    }
+
+    (result, cancelled)
+}
+
+/// Stop a log stream started by `stream_container_logs`, killing its `logs -f` process
+#[tauri::command]
+pub fn stop_container_log_stream(
+    executions: State<'_, RunningExecutions>,
+    stream_id: String,
+) -> Result<(), AppError> {
+    if executions.cancel(&stream_id) {
+        tracing::info!("Stopped log stream {}", stream_id);
+        Ok(())
+    } else {
+        Err(ContainerError::NotFound {
+            resource: "Log stream".to_string(),
+            id: stream_id,
+        }.into())
+    }
+}
+
 /// Inspect a container to get detailed information
 #[tauri::command]
 pub async fn inspect_container(
@@ -271,19 +895,29 @@ pub async fn inspect_container(
     system_id: String,
     container_id: String,
     runtime: ContainerRuntime,
-) -> Result<ContainerDetails, ContainerError> {
+) -> Result<ContainerDetails, AppError> {
     let system = state
         .get_system(&system_id)
         .ok_or_else(|| ContainerError::SystemNotFound(system_id.clone()))?;
 
-    let command = CommandBuilder::inspect_container(runtime, &container_id);
+    Ok(fetch_container_details(&system, &container_id, runtime).await?)
+}
+
+/// Inspect a container on `system`, as `inspect_container` does (the agent's
+/// inspect tool shares this path)
+pub(crate) async fn fetch_container_details(
+    system: &ContainerSystem,
+    container_id: &str,
+    runtime: ContainerRuntime,
+) -> Result<ContainerDetails, ContainerError> {
+    let command = CommandBuilder::inspect_container(runtime, container_id);
 
     let result = match system.connection_type {
         ConnectionType::Local => {
             let executor = LocalExecutor::new();
             executor.execute(&command).await?
         }
-        ConnectionType::Remote => crate::ssh::execute_on_system(&system_id, &command).await?,
+        ConnectionType::Remote => crate::ssh::execute_on_system(&system.id.0, &command).await?,
     };
 
     if !result.success() {
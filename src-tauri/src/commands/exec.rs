@@ -0,0 +1,246 @@
+use dashmap::DashMap;
+use futures::stream::{self, StreamExt};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, State};
+use tokio_util::sync::CancellationToken;
+
+use crate::database::CommandHistoryEntry;
+use crate::executor::{get_executor_for_system, ExecContext, OutputChunk};
+use crate::models::error::{AppError, ContainerError};
+use crate::models::system::ConnectionState;
+use crate::state::AppState;
+
+/// Event carrying each chunk of a streamed command's output
+pub const EXEC_OUTPUT_EVENT: &str = "exec:output";
+
+/// Cancellation tokens of executions the frontend can still cancel, keyed by execution ID
+#[derive(Clone, Default)]
+pub struct RunningExecutions {
+    tokens: Arc<DashMap<String, CancellationToken>>,
+}
+
+impl RunningExecutions {
+    /// Register an execution and get the token that signals its cancellation
+    pub fn start(&self, execution_id: &str) -> CancellationToken {
+        let token = CancellationToken::new();
+        self.tokens.insert(execution_id.to_string(), token.clone());
+        token
+    }
+
+    /// Forget an execution once it has finished
+    pub fn finish(&self, execution_id: &str) {
+        self.tokens.remove(execution_id);
+    }
+
+    /// Cancel a running execution; returns false if it isn't running
+    pub fn cancel(&self, execution_id: &str) -> bool {
+        match self.tokens.remove(execution_id) {
+            Some((_, token)) => {
+                token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Run a command on a system, emitting its output as `exec:output` events while it runs
+///
+/// `execution_id` is chosen by the caller and echoed in every event so it can
+/// tell concurrent executions apart. Resolves with the exit code once the
+/// command finishes. `context` sets the working directory and extra
+/// environment, so relative paths in templates like `docker build .` resolve
+/// where the caller expects. The execution can be stopped with `cancel_execution`.
+#[tauri::command]
+pub async fn execute_streaming(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    executions: State<'_, RunningExecutions>,
+    system_id: String,
+    command: String,
+    execution_id: String,
+    context: Option<ExecContext>,
+) -> Result<i32, AppError> {
+    let system = state
+        .get_system(&system_id)
+        .ok_or_else(|| ContainerError::SystemNotFound(system_id.clone()))?;
+
+    let started = Instant::now();
+    let executor = get_executor_for_system(&system);
+    let mut output = executor
+        .execute_stream(&command, &context.unwrap_or_default())
+        .await?;
+
+    let token = executions.start(&execution_id);
+    let mut exit_code = -1;
+    loop {
+        let chunk = tokio::select! {
+            chunk = output.next() => chunk,
+            _ = token.cancelled() => {
+                state.record_command_history(&system_id, &command, None, started.elapsed());
+                return Err(ContainerError::Cancelled(command).into());
+            }
+        };
+        let Some(chunk) = chunk else {
+            break;
+        };
+        if let OutputChunk::Exit(code) = chunk {
+            exit_code = code;
+        }
+        let _ = app.emit(
+            EXEC_OUTPUT_EVENT,
+            serde_json::json!({
+                "executionId": execution_id,
+                "systemId": system_id,
+                "chunk": chunk,
+            }),
+        );
+    }
+    executions.finish(&execution_id);
+    state.record_command_history(&system_id, &command, Some(exit_code), started.elapsed());
+
+    Ok(exit_code)
+}
+
+/// Cancel a running execution started by `execute_streaming`, `pull_image` or
+/// `pull_ollama_model`
+#[tauri::command]
+pub fn cancel_execution(
+    executions: State<'_, RunningExecutions>,
+    execution_id: String,
+) -> Result<(), AppError> {
+    if executions.cancel(&execution_id) {
+        tracing::info!("Cancelled execution {}", execution_id);
+        Ok(())
+    } else {
+        Err(ContainerError::NotFound {
+            resource: "Execution".to_string(),
+            id: execution_id,
+        }.into())
+    }
+}
+
+/// Systems `execute_on_all_systems` runs on at the same time
+const FAN_OUT_CONCURRENCY: usize = 8;
+
+/// How long `execute_on_all_systems` waits for one system unless told otherwise
+const DEFAULT_FAN_OUT_TIMEOUT_SECS: u64 = 30;
+
+/// Output of `execute_on_all_systems` for one system
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HostCommandResult {
+    pub system_name: String,
+    /// None when the command couldn't be run or didn't finish in time
+    pub exit_code: Option<i32>,
+    pub stdout: String,
+    pub stderr: String,
+    pub execution_time_ms: u64,
+    pub error: Option<AppError>,
+}
+
+/// Run the same command on every system concurrently, returning results keyed by system ID
+///
+/// `only_connected` skips systems that aren't connected. Each system gets at most
+/// `timeout_secs` (default 30), so one slow host doesn't hold up the batch.
+#[tauri::command]
+pub async fn execute_on_all_systems(
+    state: State<'_, AppState>,
+    command: String,
+    only_connected: bool,
+    timeout_secs: Option<u64>,
+) -> Result<HashMap<String, HostCommandResult>, AppError> {
+    let timeout = Duration::from_secs(timeout_secs.unwrap_or(DEFAULT_FAN_OUT_TIMEOUT_SECS));
+    if timeout.is_zero() {
+        return Err(ContainerError::InvalidConfiguration(
+            "Per-system timeout must be greater than zero".to_string(),
+        )
+        .into());
+    }
+
+    let state = state.inner();
+    let command = command.as_str();
+    let systems: Vec<_> = state
+        .list_systems()
+        .into_iter()
+        .filter(|system| {
+            !only_connected || state.connection_state(&system.id.0) == ConnectionState::Connected
+        })
+        .collect();
+
+    Ok(stream::iter(systems)
+        .map(|system| async move {
+            let started = Instant::now();
+            let executor = get_executor_for_system(&system);
+            let result = tokio::time::timeout(timeout, executor.execute(command))
+                .await
+                .unwrap_or_else(|_| {
+                    Err(ContainerError::NetworkTimeout(format!(
+                        "Command on {} timed out after {}s",
+                        system.name,
+                        timeout.as_secs()
+                    )))
+                });
+            let exit_code = result.as_ref().ok().map(|r| r.exit_code);
+            state.record_command_history(&system.id.0, command, exit_code, started.elapsed());
+
+            let host_result = match result {
+                Ok(output) => HostCommandResult {
+                    system_name: system.name,
+                    exit_code,
+                    stdout: output.stdout,
+                    stderr: output.stderr,
+                    execution_time_ms: output.execution_time_ms,
+                    error: None,
+                },
+                Err(e) => HostCommandResult {
+                    system_name: system.name,
+                    exit_code: None,
+                    stdout: String::new(),
+                    stderr: String::new(),
+                    execution_time_ms: started.elapsed().as_millis() as u64,
+                    error: Some(e.into()),
+                },
+            };
+            (system.id.0, host_result)
+        })
+        .buffer_unordered(FAN_OUT_CONCURRENCY)
+        .collect()
+        .await)
+}
+
+/// Default number of entries returned by `get_command_history`
+const DEFAULT_HISTORY_LIMIT: usize = 100;
+
+/// Get recently run commands, newest first
+///
+/// Covers commands run through `execute_streaming` and template steps, across
+/// app restarts. Omit `system_id` for history across all systems; `search`
+/// filters to commands containing the given text.
+#[tauri::command]
+pub fn get_command_history(
+    state: State<'_, AppState>,
+    system_id: Option<String>,
+    limit: Option<usize>,
+    search: Option<String>,
+) -> Result<Vec<CommandHistoryEntry>, AppError> {
+    Ok(state.get_command_history(
+        system_id.as_deref(),
+        limit.unwrap_or(DEFAULT_HISTORY_LIMIT),
+        search.as_deref(),
+    )?)
+}
+
+/// Clear command history for a system, or for all systems when `system_id` is omitted
+#[tauri::command]
+pub fn clear_command_history(
+    state: State<'_, AppState>,
+    system_id: Option<String>,
+) -> Result<usize, AppError> {
+    let cleared = state.clear_command_history(system_id.as_deref())?;
+    tracing::info!("Cleared {} command history entries", cleared);
+    Ok(cleared)
+}
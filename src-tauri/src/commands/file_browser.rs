@@ -1,15 +1,23 @@
 use base64::Engine;
-use tauri::State;
+use russh_sftp::client::SftpSession;
+use tauri::{AppHandle, Emitter, State};
 
 use crate::executor::local::LocalExecutor;
 use crate::executor::CommandExecutor;
 use crate::models::container::ContainerRuntime;
-use crate::models::error::ContainerError;
+use crate::models::error::{AppError, ContainerError};
 use crate::models::file_browser::*;
 use crate::models::system::ConnectionType;
 use crate::runtime::{CommandBuilder, OutputParser};
+use crate::ssh::sftp;
 use crate::state::AppState;
 
+/// Event carrying byte progress of SFTP uploads and downloads
+pub const FILE_TRANSFER_PROGRESS_EVENT: &str = "file-transfer:progress";
+
+/// Event carrying byte progress of copies between two systems
+pub const SYSTEM_TRANSFER_PROGRESS_EVENT: &str = "transfer:progress";
+
 /// Validate that a path is safe to use in shell commands.
 fn validate_path(path: &str) -> Result<(), ContainerError> {
     if path.contains('\0') {
@@ -25,6 +33,33 @@ fn validate_path(path: &str) -> Result<(), ContainerError> {
     Ok(())
 }
 
+/// Validate a chmod mode: 3 or 4 octal digits, e.g. "755" or "2775".
+fn validate_mode(mode: &str) -> Result<(), ContainerError> {
+    if !(3..=4).contains(&mode.len()) || !mode.chars().all(|c| ('0'..='7').contains(&c)) {
+        return Err(ContainerError::InvalidConfiguration(format!(
+            "Invalid mode '{}': expected 3-4 octal digits like 755",
+            mode
+        )));
+    }
+    Ok(())
+}
+
+/// Validate a user or group name (or numeric ID) for chown.
+fn validate_account_name(kind: &str, name: &str) -> Result<(), ContainerError> {
+    let valid = !name.is_empty()
+        && !name.starts_with('-')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '$'));
+    if !valid {
+        return Err(ContainerError::InvalidConfiguration(format!(
+            "Invalid {} name '{}'",
+            kind, name
+        )));
+    }
+    Ok(())
+}
+
 /// Execute a command, routing to local or remote executor, optionally wrapping for a container.
 async fn execute_file_command(
     state: &AppState,
@@ -54,6 +89,53 @@ async fn execute_file_command(
     }
 }
 
+/// Open an SFTP session when the target is the filesystem of a remote host.
+///
+/// Returns `None` for local systems, paths inside containers (SFTP can't reach
+/// them), and servers without an SFTP subsystem; those go through shell commands.
+async fn sftp_session(
+    state: &AppState,
+    system_id: &str,
+    container_id: Option<&str>,
+) -> Option<SftpSession> {
+    if container_id.is_some() {
+        return None;
+    }
+    let system = state.get_system(system_id)?;
+    if system.connection_type != ConnectionType::Remote {
+        return None;
+    }
+
+    match sftp::open_session(system_id).await {
+        Ok(session) => Some(session),
+        Err(e) => {
+            tracing::debug!("SFTP unavailable for system {}, using exec: {}", system_id, e);
+            None
+        }
+    }
+}
+
+/// Build a progress callback that emits transfer events for one file
+fn transfer_progress(
+    app: AppHandle,
+    system_id: String,
+    path: String,
+    direction: &'static str,
+) -> impl FnMut(u64, u64) {
+    move |transferred, total| {
+        let _ = app.emit(
+            FILE_TRANSFER_PROGRESS_EVENT,
+            serde_json::json!({
+                "systemId": system_id,
+                "path": path,
+                "direction": direction,
+                "transferred": transferred,
+                "total": total,
+            }),
+        );
+    }
+}
+
 #[tauri::command]
 pub async fn list_directory(
     state: State<'_, AppState>,
@@ -61,7 +143,7 @@ pub async fn list_directory(
     path: String,
     container_id: Option<String>,
     runtime: Option<ContainerRuntime>,
-) -> Result<DirectoryListing, ContainerError> {
+) -> Result<DirectoryListing, AppError> {
     validate_path(&path)?;
 
     let command = CommandBuilder::list_directory(&path);
@@ -76,13 +158,13 @@ pub async fn list_directory(
 
     if !result.success() {
         if result.stderr.contains("Permission denied") {
-            return Err(ContainerError::PermissionDenied(path));
+            return Err(ContainerError::PermissionDenied(path).into());
         }
         return Err(ContainerError::CommandExecutionFailed {
             command,
             exit_code: result.exit_code,
             stderr: result.stderr,
-        });
+        }.into());
     }
 
     let entries = OutputParser::parse_directory_listing(&result.stdout, &path)?;
@@ -114,10 +196,21 @@ pub async fn read_file(
     path: String,
     container_id: Option<String>,
     runtime: Option<ContainerRuntime>,
-) -> Result<FileContent, ContainerError> {
+) -> Result<FileContent, AppError> {
     validate_path(&path)?;
 
     let max_size: u64 = 1_048_576; // 1 MB
+
+    if let Some(session) = sftp_session(state.inner(), &system_id, container_id.as_deref()).await {
+        let bytes = sftp::read_file(&session, &path, max_size).await?;
+        return Ok(FileContent {
+            size: bytes.len() as u64,
+            is_binary: bytes.contains(&0),
+            content: String::from_utf8_lossy(&bytes).into_owned(),
+            path,
+        });
+    }
+
     let command = CommandBuilder::read_file(&path, max_size);
     let result = execute_file_command(
         state.inner(),
@@ -130,20 +223,20 @@ pub async fn read_file(
 
     if !result.success() {
         if result.stderr.contains("Permission denied") {
-            return Err(ContainerError::PermissionDenied(path));
+            return Err(ContainerError::PermissionDenied(path).into());
         }
         return Err(ContainerError::CommandExecutionFailed {
             command,
             exit_code: result.exit_code,
             stderr: result.stderr,
-        });
+        }.into());
     }
 
     if result.stdout.starts_with("__FILE_TOO_LARGE__:") {
         let size_str = result.stdout.trim_start_matches("__FILE_TOO_LARGE__:").trim();
         return Err(ContainerError::InvalidOperation {
             message: format!("File is too large to edit in-app ({} bytes, max 1 MB)", size_str),
-        });
+        }.into());
     }
 
     let content = result.stdout;
@@ -158,6 +251,136 @@ pub async fn read_file(
     })
 }
 
+/// Upper bound on one `read_file_range` page
+const MAX_READ_RANGE_LENGTH: u64 = 4 * 1024 * 1024;
+
+/// Upper bound on the lines returned by `tail_file`
+const MAX_TAIL_LINES: usize = 10_000;
+
+/// Map a failed read to `PermissionDenied` or `CommandExecutionFailed`
+fn read_failure(path: String, command: String, exit_code: i32, stderr: String) -> ContainerError {
+    if stderr.contains("Permission denied") {
+        return ContainerError::PermissionDenied(path);
+    }
+    ContainerError::CommandExecutionFailed {
+        command,
+        exit_code,
+        stderr,
+    }
+}
+
+#[tauri::command]
+pub async fn get_file_size(
+    state: State<'_, AppState>,
+    system_id: String,
+    path: String,
+    container_id: Option<String>,
+    runtime: Option<ContainerRuntime>,
+) -> Result<u64, AppError> {
+    validate_path(&path)?;
+
+    if let Some(session) = sftp_session(state.inner(), &system_id, container_id.as_deref()).await {
+        return Ok(sftp::file_size(&session, &path).await?);
+    }
+
+    let command = CommandBuilder::file_size(&path);
+    let result = execute_file_command(
+        state.inner(),
+        &system_id,
+        container_id.as_deref(),
+        runtime,
+        &command,
+    )
+    .await?;
+
+    if !result.success() {
+        return Err(read_failure(path, command, result.exit_code, result.stderr).into());
+    }
+    Ok(OutputParser::parse_file_size(&result.stdout)?)
+}
+
+/// Read one page of a file without loading the rest of it. Offsets past the
+/// end are clamped; `bytes_read` says how much was actually returned.
+#[tauri::command]
+pub async fn read_file_range(
+    state: State<'_, AppState>,
+    system_id: String,
+    path: String,
+    offset_bytes: u64,
+    length_bytes: u64,
+    container_id: Option<String>,
+    runtime: Option<ContainerRuntime>,
+) -> Result<FileChunk, AppError> {
+    validate_path(&path)?;
+    let length = length_bytes.min(MAX_READ_RANGE_LENGTH);
+
+    let (file_size, offset, bytes) = if let Some(session) =
+        sftp_session(state.inner(), &system_id, container_id.as_deref()).await
+    {
+        sftp::read_range(&session, &path, offset_bytes, length).await?
+    } else {
+        let command = CommandBuilder::read_file_range(&path, offset_bytes, length);
+        let result = execute_file_command(
+            state.inner(),
+            &system_id,
+            container_id.as_deref(),
+            runtime,
+            &command,
+        )
+        .await?;
+
+        // The pipeline's status is base64's, so an unreadable file only shows up on stderr
+        if !result.success() || result.stderr.contains("Permission denied") {
+            return Err(read_failure(path, command, result.exit_code, result.stderr).into());
+        }
+        let (size, bytes) = OutputParser::parse_file_range(&result.stdout)?;
+        (size, offset_bytes.min(size), bytes)
+    };
+
+    Ok(FileChunk {
+        offset,
+        bytes_read: bytes.len() as u64,
+        file_size,
+        is_binary: bytes.contains(&0),
+        content: String::from_utf8_lossy(&bytes).into_owned(),
+        path,
+    })
+}
+
+#[tauri::command]
+pub async fn tail_file(
+    state: State<'_, AppState>,
+    system_id: String,
+    path: String,
+    lines: usize,
+    container_id: Option<String>,
+    runtime: Option<ContainerRuntime>,
+) -> Result<FileContent, AppError> {
+    validate_path(&path)?;
+
+    let command = CommandBuilder::tail_file(&path, lines.clamp(1, MAX_TAIL_LINES));
+    let result = execute_file_command(
+        state.inner(),
+        &system_id,
+        container_id.as_deref(),
+        runtime,
+        &command,
+    )
+    .await?;
+
+    if !result.success() {
+        return Err(read_failure(path, command, result.exit_code, result.stderr).into());
+    }
+
+    let content = result.stdout;
+    Ok(FileContent {
+        size: content.len() as u64,
+        is_binary: content.bytes().any(|b| b == 0),
+        content,
+        path,
+    })
+}
+
 #[tauri::command]
 pub async fn write_file(
     state: State<'_, AppState>,
@@ -166,9 +389,13 @@ pub async fn write_file(
     content: String,
     container_id: Option<String>,
     runtime: Option<ContainerRuntime>,
-) -> Result<(), ContainerError> {
+) -> Result<(), AppError> {
     validate_path(&path)?;
 
+    if let Some(session) = sftp_session(state.inner(), &system_id, container_id.as_deref()).await {
+        return Ok(sftp::write_file(&session, &path, content.as_bytes()).await?);
+    }
+
     let encoded = base64::engine::general_purpose::STANDARD.encode(&content);
     let command = CommandBuilder::write_file_from_base64(&path, &encoded);
     let result = execute_file_command(
@@ -182,13 +409,13 @@ pub async fn write_file(
 
     if !result.success() {
         if result.stderr.contains("Permission denied") {
-            return Err(ContainerError::PermissionDenied(path));
+            return Err(ContainerError::PermissionDenied(path).into());
         }
         return Err(ContainerError::CommandExecutionFailed {
             command: format!("write_file({})", path),
             exit_code: result.exit_code,
             stderr: result.stderr,
-        });
+        }.into());
     }
     Ok(())
 }
@@ -200,7 +427,7 @@ pub async fn create_directory(
     path: String,
     container_id: Option<String>,
     runtime: Option<ContainerRuntime>,
-) -> Result<(), ContainerError> {
+) -> Result<(), AppError> {
     validate_path(&path)?;
 
     let command = CommandBuilder::create_directory(&path);
@@ -218,7 +445,7 @@ pub async fn create_directory(
             command,
             exit_code: result.exit_code,
             stderr: result.stderr,
-        });
+        }.into());
     }
     Ok(())
 }
@@ -231,14 +458,14 @@ pub async fn delete_path(
     is_directory: bool,
     container_id: Option<String>,
     runtime: Option<ContainerRuntime>,
-) -> Result<(), ContainerError> {
+) -> Result<(), AppError> {
     validate_path(&path)?;
 
     // Safety: don't allow deleting root
     if path == "/" {
         return Err(ContainerError::InvalidOperation {
             message: "Cannot delete root directory".to_string(),
-        });
+        }.into());
     }
 
     let command = if is_directory {
@@ -257,13 +484,13 @@ pub async fn delete_path(
 
     if !result.success() {
         if result.stderr.contains("Permission denied") {
-            return Err(ContainerError::PermissionDenied(path));
+            return Err(ContainerError::PermissionDenied(path).into());
         }
         return Err(ContainerError::CommandExecutionFailed {
             command,
             exit_code: result.exit_code,
             stderr: result.stderr,
-        });
+        }.into());
     }
     Ok(())
 }
@@ -276,7 +503,7 @@ pub async fn rename_path(
     new_path: String,
     container_id: Option<String>,
     runtime: Option<ContainerRuntime>,
-) -> Result<(), ContainerError> {
+) -> Result<(), AppError> {
     validate_path(&old_path)?;
     validate_path(&new_path)?;
 
@@ -290,6 +517,117 @@ pub async fn rename_path(
     )
     .await?;
 
+    if !result.success() {
+        return Err(ContainerError::CommandExecutionFailed {
+            command,
+            exit_code: result.exit_code,
+            stderr: result.stderr,
+        }.into());
+    }
+    Ok(())
+}
+
+/// Run `du` on a path and parse its output.
+///
+/// du exits non-zero but still prints totals when some subdirectories are
+/// unreadable; that is reported as `partial` rather than an error.
+async fn run_directory_usage(
+    state: &AppState,
+    system_id: &str,
+    path: &str,
+    depth: Option<u32>,
+    container_id: Option<&str>,
+    runtime: Option<ContainerRuntime>,
+) -> Result<(Vec<DiskUsageEntry>, bool), ContainerError> {
+    validate_path(path)?;
+
+    let command = CommandBuilder::directory_usage(path, depth);
+    let result = execute_file_command(state, system_id, container_id, runtime, &command).await?;
+
+    let entries = OutputParser::parse_du(&result.stdout)?;
+    if entries.is_empty() {
+        if result.stderr.contains("Permission denied") {
+            return Err(ContainerError::PermissionDenied(path.to_string()));
+        }
+        return Err(ContainerError::CommandExecutionFailed {
+            command,
+            exit_code: result.exit_code,
+            stderr: result.stderr,
+        });
+    }
+
+    Ok((entries, !result.success()))
+}
+
+#[tauri::command]
+pub async fn get_directory_size(
+    state: State<'_, AppState>,
+    system_id: String,
+    path: String,
+    container_id: Option<String>,
+    runtime: Option<ContainerRuntime>,
+) -> Result<DirectorySize, AppError> {
+    let (entries, partial) = run_directory_usage(
+        state.inner(),
+        &system_id,
+        &path,
+        None,
+        container_id.as_deref(),
+        runtime,
+    )
+    .await?;
+
+    // `du -s` prints a single summary line for the path
+    let size = entries.last().map(|e| e.size).unwrap_or(0);
+    Ok(DirectorySize {
+        path,
+        size,
+        partial,
+    })
+}
+
+#[tauri::command]
+pub async fn get_directory_tree_sizes(
+    state: State<'_, AppState>,
+    system_id: String,
+    path: String,
+    depth: Option<u32>,
+    container_id: Option<String>,
+    runtime: Option<ContainerRuntime>,
+) -> Result<DirectoryTreeSizes, AppError> {
+    let (mut children, partial) = run_directory_usage(
+        state.inner(),
+        &system_id,
+        &path,
+        Some(depth.unwrap_or(1).max(1)),
+        container_id.as_deref(),
+        runtime,
+    )
+    .await?;
+
+    // du lists the requested path itself last, after everything below it
+    let total = children.pop().map(|e| e.size).unwrap_or(0);
+    children.sort_by_key(|e| std::cmp::Reverse(e.size));
+
+    Ok(DirectoryTreeSizes {
+        path,
+        total,
+        children,
+        partial,
+    })
+}
+
+/// Re-read a path's metadata after changing it
+async fn stat_entry(
+    state: &AppState,
+    system_id: &str,
+    path: &str,
+    container_id: Option<&str>,
+    runtime: Option<ContainerRuntime>,
+) -> Result<FileEntry, ContainerError> {
+    let command = CommandBuilder::stat_path(path);
+    let result = execute_file_command(state, system_id, container_id, runtime, &command).await?;
+
     if !result.success() {
         return Err(ContainerError::CommandExecutionFailed {
             command,
@@ -297,20 +635,292 @@ pub async fn rename_path(
             stderr: result.stderr,
         });
     }
+    OutputParser::parse_stat_entry(&result.stdout, path)
+}
+
+/// Run a chmod/chown command, mapping failures like the other file operations
+async fn run_attribute_change(
+    state: &AppState,
+    system_id: &str,
+    path: &str,
+    container_id: Option<&str>,
+    runtime: Option<ContainerRuntime>,
+    command: String,
+) -> Result<FileEntry, ContainerError> {
+    let result = execute_file_command(state, system_id, container_id, runtime, &command).await?;
+
+    if !result.success() {
+        if result.stderr.contains("Operation not permitted")
+            || result.stderr.contains("Permission denied")
+        {
+            return Err(ContainerError::PermissionDenied(path.to_string()));
+        }
+        return Err(ContainerError::CommandExecutionFailed {
+            command,
+            exit_code: result.exit_code,
+            stderr: result.stderr,
+        });
+    }
+
+    stat_entry(state, system_id, path, container_id, runtime).await
+}
+
+#[tauri::command]
+pub async fn change_permissions(
+    state: State<'_, AppState>,
+    system_id: String,
+    path: String,
+    mode: String,
+    recursive: Option<bool>,
+    container_id: Option<String>,
+    runtime: Option<ContainerRuntime>,
+) -> Result<FileEntry, AppError> {
+    validate_path(&path)?;
+    validate_mode(&mode)?;
+
+    let command = CommandBuilder::change_permissions(&path, &mode, recursive.unwrap_or(false));
+    Ok(run_attribute_change(
+        state.inner(),
+        &system_id,
+        &path,
+        container_id.as_deref(),
+        runtime,
+        command,
+    )
+    .await?)
+}
+
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn change_owner(
+    state: State<'_, AppState>,
+    system_id: String,
+    path: String,
+    owner: String,
+    group: Option<String>,
+    recursive: Option<bool>,
+    container_id: Option<String>,
+    runtime: Option<ContainerRuntime>,
+) -> Result<FileEntry, AppError> {
+    validate_path(&path)?;
+    validate_account_name("owner", &owner)?;
+    let group = group.filter(|g| !g.is_empty());
+    if let Some(group) = &group {
+        validate_account_name("group", group)?;
+    }
+
+    let command =
+        CommandBuilder::change_owner(&path, &owner, group.as_deref(), recursive.unwrap_or(false));
+    Ok(run_attribute_change(
+        state.inner(),
+        &system_id,
+        &path,
+        container_id.as_deref(),
+        runtime,
+        command,
+    )
+    .await?)
+}
+
+/// Run an archive command, reporting a missing `tar`/`zip`/`unzip` clearly
+async fn run_archive_command(
+    state: &AppState,
+    system_id: &str,
+    container_id: Option<&str>,
+    runtime: Option<ContainerRuntime>,
+    command: String,
+    target: &str,
+) -> Result<(), ContainerError> {
+    let result = execute_file_command(state, system_id, container_id, runtime, &command).await?;
+
+    if !result.success() {
+        if let Some(tool) = OutputParser::parse_missing_tool(&result.stderr) {
+            return Err(ContainerError::UnsupportedOperation(format!(
+                "'{}' is not installed on this host",
+                tool
+            )));
+        }
+        if result.stderr.contains("Permission denied") {
+            return Err(ContainerError::PermissionDenied(target.to_string()));
+        }
+        return Err(ContainerError::CommandExecutionFailed {
+            command,
+            exit_code: result.exit_code,
+            stderr: result.stderr,
+        });
+    }
     Ok(())
 }
 
+#[tauri::command]
+pub async fn create_archive(
+    state: State<'_, AppState>,
+    system_id: String,
+    paths: Vec<String>,
+    dest: String,
+    format: ArchiveFormat,
+    container_id: Option<String>,
+    runtime: Option<ContainerRuntime>,
+) -> Result<FileEntry, AppError> {
+    if paths.is_empty() {
+        return Err(ContainerError::InvalidConfiguration(
+            "No paths to archive".into(),
+        ).into());
+    }
+    for path in &paths {
+        validate_path(path)?;
+    }
+    validate_path(&dest)?;
+
+    let command = CommandBuilder::create_archive(&paths, &dest, format);
+    run_archive_command(
+        state.inner(),
+        &system_id,
+        container_id.as_deref(),
+        runtime,
+        command,
+        &dest,
+    )
+    .await?;
+
+    Ok(stat_entry(
+        state.inner(),
+        &system_id,
+        &dest,
+        container_id.as_deref(),
+        runtime,
+    )
+    .await?)
+}
+
+/// Extract an archive into `dest`, detecting the format from its extension.
+/// Returns the destination directory's entry.
+#[tauri::command]
+pub async fn extract_archive(
+    state: State<'_, AppState>,
+    system_id: String,
+    archive_path: String,
+    dest: String,
+    container_id: Option<String>,
+    runtime: Option<ContainerRuntime>,
+) -> Result<FileEntry, AppError> {
+    validate_path(&archive_path)?;
+    validate_path(&dest)?;
+    let format = ArchiveFormat::from_path(&archive_path).ok_or_else(|| {
+        ContainerError::UnsupportedOperation(format!(
+            "Unrecognized archive format: {} (expected .tar.gz, .tgz or .zip)",
+            archive_path
+        ))
+    })?;
+
+    let command = CommandBuilder::extract_archive(&archive_path, &dest, format);
+    run_archive_command(
+        state.inner(),
+        &system_id,
+        container_id.as_deref(),
+        runtime,
+        command,
+        &dest,
+    )
+    .await?;
+
+    Ok(stat_entry(
+        state.inner(),
+        &system_id,
+        &dest,
+        container_id.as_deref(),
+        runtime,
+    )
+    .await?)
+}
+
+/// Upper bound on `SearchOptions::max_results`, to keep responses manageable
+const MAX_SEARCH_RESULTS_LIMIT: usize = 10_000;
+
+#[tauri::command]
+pub async fn search_file_contents(
+    state: State<'_, AppState>,
+    system_id: String,
+    path: String,
+    query: String,
+    options: Option<SearchOptions>,
+    container_id: Option<String>,
+    runtime: Option<ContainerRuntime>,
+) -> Result<SearchResults, AppError> {
+    validate_path(&path)?;
+    if query.is_empty() {
+        return Err(ContainerError::InvalidConfiguration(
+            "Search query must not be empty".into(),
+        ).into());
+    }
+
+    let options = options.unwrap_or_default();
+    let max_results = options
+        .max_results
+        .unwrap_or(DEFAULT_MAX_SEARCH_RESULTS)
+        .clamp(1, MAX_SEARCH_RESULTS_LIMIT);
+
+    // Ask for one extra line to tell whether the results were cut off
+    let command = CommandBuilder::search_file_contents(
+        &path,
+        &query,
+        options.case_insensitive,
+        options.fixed_string,
+        max_results + 1,
+    );
+    let result = execute_file_command(
+        state.inner(),
+        &system_id,
+        container_id.as_deref(),
+        runtime,
+        &command,
+    )
+    .await?;
+
+    let mut hits = OutputParser::parse_grep_output(&result.stdout);
+
+    // The pipe through `head` hides grep's exit status, so look at stderr instead.
+    // Unreadable files are expected in a recursive search; anything else (missing
+    // path, invalid regex) is only an error if it left us with nothing to show.
+    if hits.is_empty() {
+        let real_errors: Vec<&str> = result
+            .stderr
+            .lines()
+            .filter(|l| !l.trim().is_empty() && !l.contains("Permission denied"))
+            .collect();
+        if !real_errors.is_empty() {
+            return Err(ContainerError::CommandExecutionFailed {
+                command,
+                exit_code: result.exit_code,
+                stderr: real_errors.join("\n"),
+            }.into());
+        }
+    }
+
+    let truncated = hits.len() > max_results;
+    hits.truncate(max_results);
+    Ok(SearchResults { hits, truncated })
+}
+
 #[tauri::command]
 pub async fn download_file(
+    app: AppHandle,
     state: State<'_, AppState>,
     system_id: String,
     remote_path: String,
     local_path: String,
     container_id: Option<String>,
     runtime: Option<ContainerRuntime>,
-) -> Result<(), ContainerError> {
+) -> Result<(), AppError> {
     validate_path(&remote_path)?;
 
+    // SFTP streams straight to disk; the exec fallback buffers the whole file
+    if let Some(session) = sftp_session(state.inner(), &system_id, container_id.as_deref()).await {
+        let progress = transfer_progress(app, system_id, remote_path.clone(), "download");
+        sftp::download(&session, &remote_path, &local_path, progress).await?;
+        return Ok(());
+    }
+
     let command = CommandBuilder::read_file_base64(&remote_path);
     let result = execute_file_command(
         state.inner(),
@@ -323,13 +933,13 @@ pub async fn download_file(
 
     if !result.success() {
         if result.stderr.contains("Permission denied") {
-            return Err(ContainerError::PermissionDenied(remote_path));
+            return Err(ContainerError::PermissionDenied(remote_path).into());
         }
         return Err(ContainerError::CommandExecutionFailed {
             command,
             exit_code: result.exit_code,
             stderr: result.stderr,
-        });
+        }.into());
     }
 
     let decoded = base64::engine::general_purpose::STANDARD
@@ -344,23 +954,30 @@ pub async fn download_file(
 
 #[tauri::command]
 pub async fn upload_file(
+    app: AppHandle,
     state: State<'_, AppState>,
     system_id: String,
     local_path: String,
     remote_path: String,
     container_id: Option<String>,
     runtime: Option<ContainerRuntime>,
-) -> Result<(), ContainerError> {
+) -> Result<(), AppError> {
     validate_path(&remote_path)?;
 
+    if let Some(session) = sftp_session(state.inner(), &system_id, container_id.as_deref()).await {
+        let progress = transfer_progress(app, system_id, remote_path.clone(), "upload");
+        sftp::upload(&session, &local_path, &remote_path, progress).await?;
+        return Ok(());
+    }
+
     let data = std::fs::read(&local_path)
         .map_err(|e| ContainerError::Internal(format!("Failed to read local file: {}", e)))?;
 
-    // Limit upload size (50 MB before base64 encoding)
+    // Limit upload size over exec (50 MB before base64 encoding)
     if data.len() > 50_000_000 {
         return Err(ContainerError::InvalidOperation {
             message: "File is too large to upload (max 50 MB)".to_string(),
-        });
+        }.into());
     }
 
     let encoded = base64::engine::general_purpose::STANDARD.encode(&data);
@@ -376,13 +993,82 @@ pub async fn upload_file(
 
     if !result.success() {
         if result.stderr.contains("Permission denied") {
-            return Err(ContainerError::PermissionDenied(remote_path));
+            return Err(ContainerError::PermissionDenied(remote_path).into());
         }
         return Err(ContainerError::CommandExecutionFailed {
             command: format!("upload_file({})", remote_path),
             exit_code: result.exit_code,
             stderr: result.stderr,
-        });
+        }.into());
     }
     Ok(())
 }
+
+/// Copy a file from one system to another, streaming it chunk by chunk over
+/// SFTP with progress events. A copy within one system runs `cp` there.
+/// Either way the destination is only replaced once the copy is complete.
+#[tauri::command]
+pub async fn transfer_between_systems(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    src_system_id: String,
+    src_path: String,
+    dst_system_id: String,
+    dst_path: String,
+) -> Result<(), AppError> {
+    validate_path(&src_path)?;
+    validate_path(&dst_path)?;
+
+    if src_system_id == dst_system_id {
+        let command =
+            CommandBuilder::copy_file(&src_path, &sftp::partial_path(&dst_path), &dst_path);
+        let result =
+            execute_file_command(state.inner(), &src_system_id, None, None, &command).await?;
+        if !result.success() {
+            if result.stderr.contains("Permission denied") {
+                return Err(ContainerError::PermissionDenied(dst_path).into());
+            }
+            return Err(ContainerError::CommandExecutionFailed {
+                command,
+                exit_code: result.exit_code,
+                stderr: result.stderr,
+            }
+            .into());
+        }
+        return Ok(());
+    }
+
+    let no_sftp = |system_id: &str| ContainerError::InvalidOperation {
+        message: format!(
+            "Copies between systems go over SFTP, which system {} doesn't offer \
+             (local systems use upload and download)",
+            system_id
+        ),
+    };
+    let src = sftp_session(state.inner(), &src_system_id, None)
+        .await
+        .ok_or_else(|| no_sftp(&src_system_id))?;
+    let dst = sftp_session(state.inner(), &dst_system_id, None)
+        .await
+        .ok_or_else(|| no_sftp(&dst_system_id))?;
+
+    let progress = {
+        let (src_system_id, src_path) = (src_system_id.clone(), src_path.clone());
+        let (dst_system_id, dst_path) = (dst_system_id.clone(), dst_path.clone());
+        move |transferred, total| {
+            let _ = app.emit(
+                SYSTEM_TRANSFER_PROGRESS_EVENT,
+                serde_json::json!({
+                    "srcSystemId": src_system_id,
+                    "srcPath": src_path,
+                    "dstSystemId": dst_system_id,
+                    "dstPath": dst_path,
+                    "transferred": transferred,
+                    "total": total,
+                }),
+            );
+        }
+    };
+    sftp::transfer(&src, &src_path, &dst, &dst_path, progress).await?;
+    Ok(())
+}
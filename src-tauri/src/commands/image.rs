@@ -1,10 +1,11 @@
-use tauri::State;
+use tauri::{AppHandle, Emitter, State};
 
+use super::exec::RunningExecutions;
 use crate::executor::local::LocalExecutor;
-use crate::executor::CommandExecutor;
+use crate::executor::{get_executor_for_system, CommandExecutor, ExecContext, OutputChunk};
 use crate::models::container::ContainerRuntime;
-use crate::models::error::ContainerError;
-use crate::models::image::ContainerImage;
+use crate::models::error::{AppError, ContainerError};
+use crate::models::image::{ContainerImage, ImageDetails};
 use crate::models::system::ConnectionType;
 use crate::runtime::{CommandBuilder, OutputParser};
 use crate::state::AppState;
@@ -14,7 +15,7 @@ use crate::state::AppState;
 pub async fn list_images(
     state: State<'_, AppState>,
     system_id: String,
-) -> Result<Vec<ContainerImage>, ContainerError> {
+) -> Result<Vec<ContainerImage>, AppError> {
     let system = state
         .get_system(&system_id)
         .ok_or_else(|| ContainerError::SystemNotFound(system_id.clone()))?;
@@ -47,38 +48,154 @@ pub async fn list_images(
     Ok(all_images)
 }
 
-/// Pull an image from a registry
+/// Inspect an image's configuration, layers and build history
 #[tauri::command]
-pub async fn pull_image(
+pub async fn inspect_image(
     state: State<'_, AppState>,
     system_id: String,
-    image: String,
+    image_id: String,
     runtime: ContainerRuntime,
-) -> Result<String, ContainerError> {
+) -> Result<ImageDetails, AppError> {
     let system = state
         .get_system(&system_id)
         .ok_or_else(|| ContainerError::SystemNotFound(system_id.clone()))?;
+    let executor = get_executor_for_system(&system);
 
-    let command = CommandBuilder::pull_image(runtime, &image);
-
-    let result = match system.connection_type {
-        ConnectionType::Local => {
-            let executor = LocalExecutor::new();
-            executor.execute(&command).await?
-        }
-        ConnectionType::Remote => crate::ssh::execute_on_system(&system_id, &command).await?,
-    };
-
+    let command = CommandBuilder::inspect_image(runtime, &image_id);
+    let result = executor.execute(&command).await?;
     if !result.success() {
         return Err(ContainerError::CommandExecutionFailed {
             command,
             exit_code: result.exit_code,
             stderr: result.stderr,
-        });
+        }.into());
     }
+    let mut details = OutputParser::parse_image_details(&result.stdout, runtime)?;
+
+    // The history command adds per-step sizes; without it, the inspect output's history stays
+    if let Some(command) = CommandBuilder::image_history(runtime, &image_id) {
+        match executor.execute(&command).await {
+            Ok(result) if result.success() => {
+                match OutputParser::parse_image_history(&result.stdout) {
+                    Ok(history) => details.history = history,
+                    Err(e) => {
+                        tracing::warn!("Failed to parse history of image {}: {}", image_id, e)
+                    }
+                }
+            }
+            Ok(result) => {
+                tracing::warn!("Image history failed for {}: {}", image_id, result.stderr)
+            }
+            Err(e) => tracing::warn!("Failed to execute image history for {}: {}", image_id, e),
+        }
+    }
+
+    Ok(details)
+}
+
+/// Event emitted when a pull started by `pull_image` succeeds, fails, or is cancelled
+pub const IMAGE_PULL_FINISHED_EVENT: &str = "image:pull-finished";
+
+/// Event emitted for each layer progress line of a pull started by `pull_image`
+pub const IMAGE_PULL_PROGRESS_EVENT: &str = "image:pull-progress";
+
+/// Pull an image from a registry
+///
+/// The pull runs in the background so it can be cancelled: this returns an
+/// execution ID for `cancel_execution`, and the outcome is reported through an
+/// `image:pull-finished` event carrying the same ID. Layer progress parsed from
+/// the runtime's output is streamed as `image:pull-progress` events meanwhile.
+#[tauri::command]
+pub async fn pull_image(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    executions: State<'_, RunningExecutions>,
+    system_id: String,
+    image: String,
+    runtime: ContainerRuntime,
+) -> Result<String, AppError> {
+    let system = state
+        .get_system(&system_id)
+        .ok_or_else(|| ContainerError::SystemNotFound(system_id.clone()))?;
+
+    let command = CommandBuilder::pull_image(runtime, &image);
+    let execution_id = uuid::Uuid::new_v4().to_string();
+    let token = executions.start(&execution_id);
+    let executions = executions.inner().clone();
+    let id = execution_id.clone();
+
+    tokio::spawn(async move {
+        // Output arrives in arbitrary chunks; progress is parsed per complete line.
+        // Docker reports on stdout and Podman on stderr, so each gets its own buffer.
+        let mut pending = [String::new(), String::new()];
+        let emit_progress = |line: &str| {
+            if let Some(progress) = OutputParser::parse_pull_progress(line) {
+                let _ = app.emit(
+                    IMAGE_PULL_PROGRESS_EVENT,
+                    serde_json::json!({
+                        "executionId": id,
+                        "systemId": system_id,
+                        "image": image,
+                        "layerId": progress.layer_id,
+                        "status": progress.status,
+                        "percent": progress.percent,
+                    }),
+                );
+            }
+        };
+        let mut on_output = |chunk: &OutputChunk| {
+            let (buffer, text) = match chunk {
+                OutputChunk::Stdout(text) => (&mut pending[0], text),
+                OutputChunk::Stderr(text) => (&mut pending[1], text),
+                OutputChunk::Exit(_) => {
+                    pending.iter().for_each(|rest| emit_progress(rest));
+                    return;
+                }
+            };
+            buffer.push_str(text);
+            while let Some(end) = buffer.find(['\n', '\r']) {
+                let line: String = buffer.drain(..=end).collect();
+                emit_progress(&line);
+            }
+        };
+
+        let executor = get_executor_for_system(&system);
+        let result = executor
+            .execute_cancellable_with_output(
+                &command,
+                &ExecContext::default(),
+                token,
+                &mut on_output,
+            )
+            .await;
+        executions.finish(&id);
+
+        let error = match result {
+            Ok(result) if result.success() => {
+                tracing::info!("Pulled image {} on system {}", image, system_id);
+                None
+            }
+            Ok(result) => Some(ContainerError::CommandExecutionFailed {
+                command,
+                exit_code: result.exit_code,
+                stderr: result.stderr,
+            }),
+            Err(e) => Some(e),
+        };
 
-    tracing::info!("Pulled image {} on system {}", image, system_id);
-    Ok(result.stdout)
+        let _ = app.emit(
+            IMAGE_PULL_FINISHED_EVENT,
+            serde_json::json!({
+                "executionId": id,
+                "systemId": system_id,
+                "image": image,
+                "cancelled": matches!(error, Some(ContainerError::Cancelled(_))),
+                "error": error.map(|e| e.to_string()),
+            }),
+        );
+    });
+
+    Ok(execution_id)
 }
 
 /// Remove an image
@@ -89,7 +206,7 @@ pub async fn remove_image(
     image_id: String,
     runtime: ContainerRuntime,
     force: bool,
-) -> Result<(), ContainerError> {
+) -> Result<(), AppError> {
     let system = state
         .get_system(&system_id)
         .ok_or_else(|| ContainerError::SystemNotFound(system_id.clone()))?;
@@ -109,7 +226,7 @@ pub async fn remove_image(
             command,
             exit_code: result.exit_code,
             stderr: result.stderr,
-        });
+        }.into());
     }
 
     tracing::info!("Removed image {} on system {}", image_id, system_id);
@@ -2,10 +2,13 @@ pub mod agent;
 pub mod ai;
 pub mod command_template;
 pub mod container;
+pub mod exec;
 pub mod file_browser;
 pub mod image;
 pub mod network;
+pub mod pod;
 pub mod port_forward;
+pub mod prune;
 pub mod system;
 pub mod terminal;
 pub mod volume;
@@ -14,10 +17,13 @@ pub use agent::*;
 pub use ai::*;
 pub use command_template::*;
 pub use container::*;
+pub use exec::*;
 pub use file_browser::*;
 pub use image::*;
 pub use network::*;
+pub use pod::*;
 pub use port_forward::*;
+pub use prune::*;
 pub use system::*;
 pub use terminal::*;
 pub use volume::*;
@@ -1,10 +1,12 @@
+use std::collections::HashMap;
+
 use tauri::State;
 
 use crate::executor::local::LocalExecutor;
 use crate::executor::CommandExecutor;
 use crate::models::container::ContainerRuntime;
-use crate::models::error::ContainerError;
-use crate::models::network::Network;
+use crate::models::error::{AppError, ContainerError};
+use crate::models::network::{Network, NetworkCreateOptions};
 use crate::models::system::ConnectionType;
 use crate::runtime::{CommandBuilder, OutputParser};
 use crate::state::AppState;
@@ -14,7 +16,7 @@ use crate::state::AppState;
 pub async fn list_networks(
     state: State<'_, AppState>,
     system_id: String,
-) -> Result<Vec<Network>, ContainerError> {
+) -> Result<Vec<Network>, AppError> {
     let system = state
         .get_system(&system_id)
         .ok_or_else(|| ContainerError::SystemNotFound(system_id.clone()))?;
@@ -49,6 +51,7 @@ pub async fn list_networks(
 
 /// Create a new network
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn create_network(
     state: State<'_, AppState>,
     system_id: String,
@@ -56,17 +59,28 @@ pub async fn create_network(
     runtime: ContainerRuntime,
     driver: Option<String>,
     subnet: Option<String>,
-) -> Result<(), ContainerError> {
+    gateway: Option<String>,
+    internal: Option<bool>,
+    labels: Option<HashMap<String, String>>,
+) -> Result<(), AppError> {
     let system = state
         .get_system(&system_id)
         .ok_or_else(|| ContainerError::SystemNotFound(system_id.clone()))?;
 
-    let command = CommandBuilder::create_network(
-        runtime,
-        &name,
-        driver.as_deref(),
-        subnet.as_deref(),
-    );
+    let options = NetworkCreateOptions {
+        driver,
+        subnet,
+        gateway,
+        internal: internal.unwrap_or(false),
+        labels: labels.unwrap_or_default(),
+    };
+    options.validate()?;
+    let command = CommandBuilder::create_network(runtime, &name, &options).ok_or_else(|| {
+        ContainerError::UnsupportedOperation(
+            "Apple Container networks don't support drivers, subnets, gateways, internal mode or labels"
+                .to_string(),
+        )
+    })?;
 
     let result = match system.connection_type {
         ConnectionType::Local => {
@@ -81,7 +95,7 @@ pub async fn create_network(
             command,
             exit_code: result.exit_code,
             stderr: result.stderr,
-        });
+        }.into());
     }
 
     tracing::info!("Created network {} on system {}", name, system_id);
@@ -95,7 +109,7 @@ pub async fn remove_network(
     system_id: String,
     name: String,
     runtime: ContainerRuntime,
-) -> Result<(), ContainerError> {
+) -> Result<(), AppError> {
     let system = state
         .get_system(&system_id)
         .ok_or_else(|| ContainerError::SystemNotFound(system_id.clone()))?;
@@ -115,7 +129,7 @@ pub async fn remove_network(
             command,
             exit_code: result.exit_code,
             stderr: result.stderr,
-        });
+        }.into());
     }
 
     tracing::info!("Removed network {} on system {}", name, system_id);
@@ -130,12 +144,18 @@ pub async fn connect_container_to_network(
     container_id: String,
     network_name: String,
     runtime: ContainerRuntime,
-) -> Result<(), ContainerError> {
+) -> Result<(), AppError> {
     let system = state
         .get_system(&system_id)
         .ok_or_else(|| ContainerError::SystemNotFound(system_id.clone()))?;
 
-    let command = CommandBuilder::connect_to_network(runtime, &network_name, &container_id);
+    let command = CommandBuilder::connect_to_network(runtime, &network_name, &container_id)
+        .ok_or_else(|| {
+            ContainerError::UnsupportedOperation(format!(
+                "{:?} cannot connect containers to networks",
+                runtime
+            ))
+        })?;
 
     let result = match system.connection_type {
         ConnectionType::Local => {
@@ -150,7 +170,7 @@ pub async fn connect_container_to_network(
             command,
             exit_code: result.exit_code,
             stderr: result.stderr,
-        });
+        }.into());
     }
 
     tracing::info!(
@@ -170,12 +190,18 @@ pub async fn disconnect_container_from_network(
     container_id: String,
     network_name: String,
     runtime: ContainerRuntime,
-) -> Result<(), ContainerError> {
+) -> Result<(), AppError> {
     let system = state
         .get_system(&system_id)
         .ok_or_else(|| ContainerError::SystemNotFound(system_id.clone()))?;
 
-    let command = CommandBuilder::disconnect_from_network(runtime, &network_name, &container_id);
+    let command = CommandBuilder::disconnect_from_network(runtime, &network_name, &container_id)
+        .ok_or_else(|| {
+            ContainerError::UnsupportedOperation(format!(
+                "{:?} cannot disconnect containers from networks",
+                runtime
+            ))
+        })?;
 
     let result = match system.connection_type {
         ConnectionType::Local => {
@@ -190,7 +216,7 @@ pub async fn disconnect_container_from_network(
             command,
             exit_code: result.exit_code,
             stderr: result.stderr,
-        });
+        }.into());
     }
 
     tracing::info!(
@@ -0,0 +1,75 @@
+use tauri::State;
+
+use crate::executor::get_executor_for_system;
+use crate::models::container::{ContainerAction, ContainerRuntime};
+use crate::models::error::{AppError, ContainerError};
+use crate::models::pod::Pod;
+use crate::models::system::ContainerSystem;
+use crate::runtime::{CommandBuilder, OutputParser};
+use crate::state::AppState;
+
+/// Look up a system and make sure it has Podman, the only runtime with pods
+fn podman_system(state: &AppState, system_id: &str) -> Result<ContainerSystem, ContainerError> {
+    let system = state
+        .get_system(system_id)
+        .ok_or_else(|| ContainerError::SystemNotFound(system_id.to_string()))?;
+
+    if !system
+        .available_runtimes
+        .contains(&ContainerRuntime::Podman)
+    {
+        return Err(ContainerError::UnsupportedOperation(
+            "Pods require the Podman runtime".to_string(),
+        ));
+    }
+
+    Ok(system)
+}
+
+/// List Podman pods on a system
+#[tauri::command]
+pub async fn list_pods(
+    state: State<'_, AppState>,
+    system_id: String,
+) -> Result<Vec<Pod>, AppError> {
+    let system = podman_system(&state, &system_id)?;
+
+    let command = CommandBuilder::list_pods();
+    let result = get_executor_for_system(&system).execute(&command).await?;
+
+    if !result.success() {
+        return Err(ContainerError::CommandExecutionFailed {
+            command,
+            exit_code: result.exit_code,
+            stderr: result.stderr,
+        }.into());
+    }
+
+    Ok(OutputParser::parse_pod_list(&result.stdout, &system_id)?)
+}
+
+/// Perform an action on a pod (start, stop, restart, pause, unpause, remove)
+#[tauri::command]
+pub async fn perform_pod_action(
+    state: State<'_, AppState>,
+    system_id: String,
+    pod_id: String,
+    action: ContainerAction,
+) -> Result<(), AppError> {
+    let system = podman_system(&state, &system_id)?;
+
+    let command = CommandBuilder::pod_action(action, &pod_id);
+    let result = get_executor_for_system(&system).execute(&command).await?;
+
+    if !result.success() {
+        return Err(ContainerError::CommandExecutionFailed {
+            command,
+            exit_code: result.exit_code,
+            stderr: result.stderr,
+        }.into());
+    }
+
+    tracing::info!("Performed {:?} action on pod {}", action, pod_id);
+
+    Ok(())
+}
@@ -1,7 +1,7 @@
 use std::sync::Arc;
 use tauri::State;
 
-use crate::models::error::ContainerError;
+use crate::models::error::{AppError, ContainerError};
 use crate::models::port_forward::{CreatePortForwardRequest, PortForward};
 use crate::models::system::ConnectionType;
 use crate::ssh::PortForwardManager;
@@ -12,7 +12,7 @@ pub async fn create_port_forward(
     app_state: State<'_, AppState>,
     forward_state: State<'_, Arc<PortForwardManager>>,
     request: CreatePortForwardRequest,
-) -> Result<PortForward, ContainerError> {
+) -> Result<PortForward, AppError> {
     // Check if system exists and is connected
     let system = app_state
         .get_system(&request.system_id)
@@ -26,7 +26,7 @@ pub async fn create_port_forward(
 
     let protocol = request.protocol.unwrap_or_else(|| "tcp".to_string());
 
-    forward_state
+    Ok(forward_state
         .start_forward(
             request.system_id,
             request.container_id,
@@ -37,15 +37,45 @@ pub async fn create_port_forward(
             protocol,
             is_local,
         )
-        .await
+        .await?)
+}
+
+/// Make a local service reachable from the remote host (`ssh -R`)
+///
+/// `remote_bind_port` 0 lets the server pick a port; the returned forward
+/// reports the one it chose.
+#[tauri::command]
+pub async fn create_remote_forward(
+    app_state: State<'_, AppState>,
+    forward_state: State<'_, Arc<PortForwardManager>>,
+    system_id: String,
+    remote_bind_port: u16,
+    local_host: Option<String>,
+    local_port: u16,
+) -> Result<PortForward, AppError> {
+    let system = app_state
+        .get_system(&system_id)
+        .ok_or_else(|| ContainerError::SystemNotFound(system_id.clone()))?;
+
+    if system.connection_type == ConnectionType::Local {
+        return Err(ContainerError::UnsupportedOperation(
+            "Remote forwards need an SSH connection".to_string(),
+        ).into());
+    }
+
+    let local_host = local_host.unwrap_or_else(|| "127.0.0.1".to_string());
+
+    Ok(forward_state
+        .start_remote_forward(system_id, remote_bind_port, local_host, local_port)
+        .await?)
 }
 
 #[tauri::command]
 pub fn stop_port_forward(
     forward_state: State<'_, Arc<PortForwardManager>>,
     forward_id: String,
-) -> Result<(), ContainerError> {
-    forward_state.stop_forward(&forward_id)
+) -> Result<(), AppError> {
+    Ok(forward_state.stop_forward(&forward_id)?)
 }
 
 #[tauri::command]
@@ -69,7 +99,7 @@ pub fn get_port_forward(
 pub async fn open_forwarded_port(
     forward_state: State<'_, Arc<PortForwardManager>>,
     forward_id: String,
-) -> Result<(), ContainerError> {
+) -> Result<(), AppError> {
     let forward = forward_state
         .get_forward(&forward_id)
         .ok_or_else(|| ContainerError::Internal(format!("Port forward {} not found", forward_id)))?;
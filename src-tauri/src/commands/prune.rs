@@ -0,0 +1,111 @@
+use tauri::State;
+
+use crate::executor::get_executor_for_system;
+use crate::models::container::ContainerRuntime;
+use crate::models::error::{AppError, ContainerError};
+use crate::models::prune::PruneResult;
+use crate::runtime::{CommandBuilder, OutputParser};
+use crate::state::AppState;
+
+/// Run a prune command on every runtime of a system that supports it and sum the results
+async fn prune_all_runtimes(
+    state: &AppState,
+    system_id: &str,
+    build: impl Fn(ContainerRuntime) -> Option<String>,
+) -> Result<PruneResult, ContainerError> {
+    let system = state
+        .get_system(system_id)
+        .ok_or_else(|| ContainerError::SystemNotFound(system_id.to_string()))?;
+
+    let commands: Vec<String> = system
+        .available_runtimes
+        .iter()
+        .filter_map(|runtime| build(*runtime))
+        .collect();
+    if commands.is_empty() {
+        return Err(ContainerError::UnsupportedOperation(
+            "None of this system's runtimes support pruning".to_string(),
+        ));
+    }
+
+    let executor = get_executor_for_system(&system);
+    let mut total = PruneResult::default();
+    for command in commands {
+        let result = executor.execute(&command).await?;
+        if !result.success() {
+            return Err(ContainerError::CommandExecutionFailed {
+                command,
+                exit_code: result.exit_code,
+                stderr: result.stderr,
+            });
+        }
+
+        let pruned = OutputParser::parse_prune_output(&result.stdout);
+        total.deleted.extend(pruned.deleted);
+        total.reclaimed_bytes += pruned.reclaimed_bytes;
+    }
+
+    tracing::info!(
+        "Pruned {} objects ({} bytes) on system {}",
+        total.deleted.len(),
+        total.reclaimed_bytes,
+        system_id
+    );
+    Ok(total)
+}
+
+/// Remove dangling images, or all unused images with `all`; `filters` are passed as `--filter`
+#[tauri::command]
+pub async fn prune_images(
+    state: State<'_, AppState>,
+    system_id: String,
+    all: bool,
+    filters: Vec<String>,
+) -> Result<PruneResult, AppError> {
+    Ok(prune_all_runtimes(&state, &system_id, |runtime| {
+        CommandBuilder::prune_images(runtime, all, &filters)
+    })
+    .await?)
+}
+
+/// Remove all stopped containers
+#[tauri::command]
+pub async fn prune_containers(
+    state: State<'_, AppState>,
+    system_id: String,
+) -> Result<PruneResult, AppError> {
+    Ok(prune_all_runtimes(&state, &system_id, CommandBuilder::prune_containers).await?)
+}
+
+/// Remove volumes not used by any container
+#[tauri::command]
+pub async fn prune_volumes(
+    state: State<'_, AppState>,
+    system_id: String,
+) -> Result<PruneResult, AppError> {
+    Ok(prune_all_runtimes(&state, &system_id, CommandBuilder::prune_volumes).await?)
+}
+
+/// Remove networks not used by any container
+#[tauri::command]
+pub async fn prune_networks(
+    state: State<'_, AppState>,
+    system_id: String,
+) -> Result<PruneResult, AppError> {
+    Ok(prune_all_runtimes(&state, &system_id, CommandBuilder::prune_networks).await?)
+}
+
+/// Remove stopped containers, unused networks, dangling images and build cache;
+/// `all` includes unused images and `volumes` unused volumes
+#[tauri::command]
+pub async fn prune_system(
+    state: State<'_, AppState>,
+    system_id: String,
+    all: bool,
+    volumes: bool,
+) -> Result<PruneResult, AppError> {
+    Ok(prune_all_runtimes(&state, &system_id, |runtime| {
+        CommandBuilder::prune_system(runtime, all, volumes)
+    })
+    .await?)
+}
@@ -1,21 +1,89 @@
 use std::collections::{HashMap, HashSet};
 
+use futures::stream::{self, StreamExt};
 use serde::{Deserialize, Serialize};
-use tauri::{AppHandle, State};
+use tauri::{AppHandle, Emitter, Manager, State};
 
 use crate::executor::local::LocalExecutor;
-use crate::executor::CommandExecutor;
+use crate::executor::{CommandExecutor, CommandResult};
 use crate::keyring_store::JumpHostCredentials;
 use crate::models::container::ContainerRuntime;
-use crate::models::error::ContainerError;
-use crate::models::system::{ConnectionState, ConnectionType, ContainerSystem, ExtendedSystemInfo, LiveSystemMetrics, SshConfig, SystemId};
+use crate::models::error::{AppError, ContainerError};
+use crate::models::system::{
+    normalize_tags, AlertConfig, ConnectionState, ConnectionType, ContainerSystem, ExportFormat,
+    ExportedSystem, ExtendedSystemInfo, LiveSystemMetrics, RuntimeDetection, SshAuthMethod,
+    SshConfig, SystemConnectionResult, SystemExport, SystemId,
+};
+use crate::monitoring::health::{HealthPoller, DEFAULT_HEALTH_INTERVAL_MS};
+use crate::monitoring::prometheus::{self, MetricsExporter};
 use crate::monitoring::MonitoringManager;
 use crate::runtime::{CommandBuilder, OutputParser};
 use crate::state::AppState;
 
+/// Event emitted when a background refresh updated the cached systems
+pub const SYSTEMS_REFRESHED_EVENT: &str = "systems:refreshed";
+
+/// List systems straight from the cache, refreshing their runtimes in the
+/// background if the cache is stale
+#[tauri::command]
+pub fn list_systems(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    tags: Option<Vec<String>>,
+) -> Vec<ContainerSystem> {
+    if state.begin_systems_refresh() {
+        tauri::async_runtime::spawn(refresh_systems(app));
+    }
+
+    let systems = state.list_systems();
+    match tags {
+        // Only systems carrying every requested tag
+        Some(tags) => systems
+            .into_iter()
+            .filter(|system| tags.iter().all(|tag| system.has_tag(tag)))
+            .collect(),
+        None => systems,
+    }
+}
+
+/// Re-detect runtimes on the systems we can reach without prompting (local
+/// ones and those already connected), then send the updated list
+async fn refresh_systems(app: AppHandle) {
+    let state = app.state::<AppState>();
+    let state = state.inner();
+
+    let reachable: Vec<String> = state
+        .list_systems()
+        .into_iter()
+        .filter(|system| {
+            system.connection_type == ConnectionType::Local
+                || state.connection_state(&system.id.0) == ConnectionState::Connected
+        })
+        .map(|system| system.id.0)
+        .collect();
+
+    stream::iter(reachable)
+        .for_each_concurrent(BATCH_CONNECTION_CONCURRENCY, |system_id| async move {
+            if let Err(e) = detect_system_runtimes(state, &system_id).await {
+                tracing::debug!("Failed to refresh runtimes of system {}: {}", system_id, e);
+            }
+        })
+        .await;
+
+    state.finish_systems_refresh();
+    if let Err(e) = app.emit(SYSTEMS_REFRESHED_EVENT, state.list_systems()) {
+        tracing::warn!("Failed to emit systems refresh event: {}", e);
+    }
+}
+
+/// List the systems carrying `tag` (case-insensitive)
 #[tauri::command]
-pub fn list_systems(state: State<'_, AppState>) -> Vec<ContainerSystem> {
-    state.list_systems()
+pub fn list_systems_by_tag(state: State<'_, AppState>, tag: String) -> Vec<ContainerSystem> {
+    state
+        .list_systems()
+        .into_iter()
+        .filter(|system| system.has_tag(&tag))
+        .collect()
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,13 +96,18 @@ pub struct NewSystemRequest {
     pub available_runtimes: Vec<ContainerRuntime>,
     pub ssh_config: Option<SshConfig>,
     pub auto_connect: bool,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Seconds; omit for the default, 0 for no limit
+    #[serde(default)]
+    pub command_timeout_secs: Option<u64>,
 }
 
 #[tauri::command]
-pub fn add_system(state: State<'_, AppState>, payload: NewSystemRequest) -> Result<ContainerSystem, ContainerError> {
+pub fn add_system(state: State<'_, AppState>, payload: NewSystemRequest) -> Result<ContainerSystem, AppError> {
     let available_runtimes = payload.available_runtimes.into_iter().collect::<HashSet<_>>();
 
-    state.add_system(ContainerSystem {
+    Ok(state.add_system(ContainerSystem {
         id: SystemId(String::new()),
         name: payload.name,
         hostname: payload.hostname,
@@ -43,7 +116,39 @@ pub fn add_system(state: State<'_, AppState>, payload: NewSystemRequest) -> Resu
         available_runtimes,
         ssh_config: payload.ssh_config,
         auto_connect: payload.auto_connect,
-    })
+        tags: normalize_tags(payload.tags),
+        command_timeout_secs: payload.command_timeout_secs,
+        last_connected_at: None,
+    })?)
+}
+
+/// Export every configured system, without credentials, as JSON or YAML
+#[tauri::command]
+pub fn export_systems(
+    state: State<'_, AppState>,
+    format: ExportFormat,
+) -> Result<String, AppError> {
+    Ok(SystemExport::new(&state.list_systems()).serialize(format)?)
+}
+
+/// Recreate the systems of an export document with fresh ids. Nothing is added
+/// unless the whole document is valid and every system is saved; credentials
+/// are asked for on first connect and proxy commands are not imported.
+#[tauri::command]
+pub fn import_systems(
+    state: State<'_, AppState>,
+    data: String,
+    format: ExportFormat,
+) -> Result<Vec<ContainerSystem>, AppError> {
+    let export = SystemExport::parse(&data, format)?;
+    let systems = export
+        .systems
+        .into_iter()
+        .map(ExportedSystem::into_system)
+        .collect();
+    let imported = state.add_systems(systems)?;
+    tracing::info!("Imported {} systems", imported.len());
+    Ok(imported)
 }
 
 #[tauri::command]
@@ -54,6 +159,67 @@ pub async fn connect_system(
     passphrase: Option<String>,
     private_key: Option<String>,
     jump_host_credentials: Option<HashMap<String, JumpHostCredentials>>,
+) -> Result<ConnectionState, AppError> {
+    Ok(connect_with_credentials(
+        &state,
+        system_id,
+        password,
+        passphrase,
+        private_key,
+        jump_host_credentials,
+    )
+    .await?)
+}
+
+/// Systems `connect_systems` / `disconnect_systems` work on at the same time
+const BATCH_CONNECTION_CONCURRENCY: usize = 4;
+
+/// Connect several systems concurrently with their stored credentials
+///
+/// Returns a result per system instead of failing the batch, so a host that
+/// needs a password or has a changed host key doesn't hold up the others.
+#[tauri::command]
+pub async fn connect_systems(
+    state: State<'_, AppState>,
+    system_ids: Vec<String>,
+) -> Result<HashMap<String, SystemConnectionResult>, AppError> {
+    let state = state.inner();
+    Ok(stream::iter(system_ids)
+        .map(|system_id| async move {
+            let result =
+                connect_with_credentials(state, system_id.clone(), None, None, None, None).await;
+            (system_id, result.into())
+        })
+        .buffer_unordered(BATCH_CONNECTION_CONCURRENCY)
+        .collect()
+        .await)
+}
+
+/// Disconnect several systems concurrently, reporting a result per system
+#[tauri::command]
+pub async fn disconnect_systems(
+    state: State<'_, AppState>,
+    system_ids: Vec<String>,
+) -> Result<HashMap<String, SystemConnectionResult>, AppError> {
+    let state = state.inner();
+    Ok(stream::iter(system_ids)
+        .map(|system_id| async move {
+            let result = disconnect_one(state, system_id.clone()).await;
+            (system_id, result.into())
+        })
+        .buffer_unordered(BATCH_CONNECTION_CONCURRENCY)
+        .collect()
+        .await)
+}
+
+/// Connect a system, falling back to cached or stored credentials for any not given
+async fn connect_with_credentials(
+    state: &AppState,
+    system_id: String,
+    password: Option<String>,
+    passphrase: Option<String>,
+    private_key: Option<String>,
+    jump_host_credentials: Option<HashMap<String, JumpHostCredentials>>,
 ) -> Result<ConnectionState, ContainerError> {
     let system = state
         .get_system(&system_id)
@@ -70,18 +236,28 @@ pub async fn connect_system(
             match executor.execute("echo ok").await {
                 Ok(result) if result.success() => {
                     state.set_connection_state(&system_id, ConnectionState::Connected);
+                    state.record_connected(&system_id);
                     Ok(ConnectionState::Connected)
                 }
                 Ok(result) => {
-                    state.set_connection_state(&system_id, ConnectionState::Error);
-                    Err(ContainerError::CommandExecutionFailed {
+                    let error = ContainerError::CommandExecutionFailed {
                         command: "echo ok".to_string(),
                         exit_code: result.exit_code,
                         stderr: result.stderr,
-                    })
+                    };
+                    state.set_connection_state_with_error(
+                        &system_id,
+                        ConnectionState::Error,
+                        Some(error.to_string()),
+                    );
+                    Err(error)
                 }
                 Err(e) => {
-                    state.set_connection_state(&system_id, ConnectionState::Error);
+                    state.set_connection_state_with_error(
+                        &system_id,
+                        ConnectionState::Error,
+                        Some(e.to_string()),
+                    );
                     Err(e)
                 }
             }
@@ -182,10 +358,15 @@ pub async fn connect_system(
             ).await {
                 Ok(()) => {
                     state.set_connection_state(&system_id, ConnectionState::Connected);
+                    state.record_connected(&system_id);
                     Ok(ConnectionState::Connected)
                 }
                 Err(e) => {
-                    state.set_connection_state(&system_id, ConnectionState::Error);
+                    state.set_connection_state_with_error(
+                        &system_id,
+                        ConnectionState::Error,
+                        Some(e.to_string()),
+                    );
                     Err(e)
                 }
             }
@@ -197,6 +378,13 @@ pub async fn connect_system(
 pub async fn disconnect_system(
     state: State<'_, AppState>,
     system_id: String,
+) -> Result<ConnectionState, AppError> {
+    Ok(disconnect_one(&state, system_id).await?)
+}
+
+async fn disconnect_one(
+    state: &AppState,
+    system_id: String,
 ) -> Result<ConnectionState, ContainerError> {
     let system = state
         .get_system(&system_id)
@@ -211,26 +399,73 @@ pub async fn disconnect_system(
     Ok(ConnectionState::Disconnected)
 }
 
+/// Event emitted after the SSH pool transparently rebuilt a dropped connection
+pub const SYSTEM_RECONNECTED_EVENT: &str = "system:reconnected";
+
+/// Forward pool reconnects to the frontend and mark those systems connected again
+pub fn watch_ssh_reconnects(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut reconnects = crate::ssh::subscribe_reconnects().await;
+        loop {
+            match reconnects.recv().await {
+                Ok(system_id) => {
+                    app.state::<AppState>()
+                        .set_connection_state(&system_id, ConnectionState::Connected);
+                    if let Err(e) = app.emit(
+                        SYSTEM_RECONNECTED_EVENT,
+                        serde_json::json!({ "systemId": system_id }),
+                    ) {
+                        tracing::warn!("Failed to emit reconnect event for system {}: {}", system_id, e);
+                    }
+                }
+                Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    });
+}
+
+/// Active/idle connection counts of the SSH connection pool
+#[tauri::command]
+pub async fn pool_stats() -> crate::ssh::PoolStats {
+    crate::ssh::pool_stats().await
+}
+
+/// Change how many SSH connections stay open before idle ones are evicted
+#[tauri::command]
+pub async fn set_pool_max_connections(max_connections: usize) {
+    crate::ssh::set_max_connections(max_connections).await
+}
+
 #[tauri::command]
 pub fn get_connection_state(state: State<'_, AppState>, system_id: String) -> ConnectionState {
     state.connection_state(&system_id)
 }
 
-/// Detect available container runtimes on a system
+/// Detect available container runtimes on a system, and whether the primary
+/// one (or the first found) runs rootless
 #[tauri::command]
 pub async fn detect_runtimes(
     state: State<'_, AppState>,
     system_id: String,
-) -> Result<Vec<ContainerRuntime>, ContainerError> {
+) -> Result<RuntimeDetection, AppError> {
+    Ok(detect_system_runtimes(&state, &system_id).await?)
+}
+
+async fn detect_system_runtimes(
+    state: &AppState,
+    system_id: &str,
+) -> Result<RuntimeDetection, ContainerError> {
     let system = state
-        .get_system(&system_id)
-        .ok_or_else(|| ContainerError::SystemNotFound(system_id.clone()))?;
+        .get_system(system_id)
+        .ok_or_else(|| ContainerError::SystemNotFound(system_id.to_string()))?;
 
     let mut available_runtimes = Vec::new();
     let runtimes_to_check = [
         ContainerRuntime::Docker,
         ContainerRuntime::Podman,
         ContainerRuntime::Apple,
+        ContainerRuntime::Nerdctl,
     ];
 
     for runtime in runtimes_to_check {
@@ -240,16 +475,7 @@ pub async fn detect_runtimes(
         }
 
         let command = CommandBuilder::detect_runtime(runtime);
-
-        let result = match system.connection_type {
-            ConnectionType::Local => {
-                let executor = LocalExecutor::new();
-                executor.execute(&command).await
-            }
-            ConnectionType::Remote => {
-                crate::ssh::execute_on_system(&system_id, &command).await
-            }
-        };
+        let result = run_detection_command(&system, &command).await;
 
         match result {
             Ok(res) if res.success() => {
@@ -272,12 +498,47 @@ pub async fn detect_runtimes(
         }
     }
 
+    let checked_runtime = if available_runtimes.contains(&system.primary_runtime) {
+        Some(system.primary_runtime)
+    } else {
+        available_runtimes.first().copied()
+    };
+    let mut rootless = false;
+    if let Some(command) = checked_runtime.and_then(CommandBuilder::rootless_check) {
+        match run_detection_command(&system, &command).await {
+            Ok(res) if res.success() => rootless = OutputParser::parse_rootless(&res.stdout),
+            Ok(res) => tracing::debug!(
+                "Rootless check failed on system {}: {}",
+                system_id,
+                res.stderr
+            ),
+            Err(e) => tracing::debug!("Rootless check failed on system {}: {}", system_id, e),
+        }
+    }
+
     // Update the system's available runtimes
     if !available_runtimes.is_empty() {
-        state.update_system_runtimes(&system_id, available_runtimes.iter().copied().collect());
+        state.update_system_runtimes(system_id, available_runtimes.iter().copied().collect());
     }
 
-    Ok(available_runtimes)
+    Ok(RuntimeDetection {
+        socket_path: checked_runtime
+            .and_then(|runtime| CommandBuilder::runtime_socket(runtime, rootless))
+            .map(str::to_string),
+        runtimes: available_runtimes,
+        rootless,
+    })
+}
+
+/// Run a runtime detection command on a system
+async fn run_detection_command(
+    system: &ContainerSystem,
+    command: &str,
+) -> Result<CommandResult, ContainerError> {
+    match system.connection_type {
+        ConnectionType::Local => LocalExecutor::new().execute(command).await,
+        ConnectionType::Remote => crate::ssh::execute_on_system(&system.id.0, command).await,
+    }
 }
 
 /// Update an existing system
@@ -292,14 +553,30 @@ pub struct UpdateSystemRequest {
     pub available_runtimes: Vec<ContainerRuntime>,
     pub ssh_config: Option<SshConfig>,
     pub auto_connect: bool,
+    /// Leave out to keep the system's current tags
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
+    /// Seconds, 0 for no limit; leave out to keep the current limit
+    #[serde(default)]
+    pub command_timeout_secs: Option<u64>,
 }
 
 #[tauri::command]
 pub fn update_system(
     state: State<'_, AppState>,
     payload: UpdateSystemRequest,
-) -> Result<ContainerSystem, ContainerError> {
+) -> Result<ContainerSystem, AppError> {
     let available_runtimes = payload.available_runtimes.into_iter().collect::<HashSet<_>>();
+    let existing = state
+        .get_system(&payload.id)
+        .ok_or_else(|| ContainerError::SystemNotFound(payload.id.clone()))?;
+    let tags = match payload.tags {
+        Some(tags) => normalize_tags(tags),
+        None => existing.tags,
+    };
+    let command_timeout_secs = payload
+        .command_timeout_secs
+        .or(existing.command_timeout_secs);
 
     let system = ContainerSystem {
         id: SystemId(payload.id.clone()),
@@ -310,11 +587,14 @@ pub fn update_system(
         available_runtimes,
         ssh_config: payload.ssh_config,
         auto_connect: payload.auto_connect,
+        tags,
+        command_timeout_secs,
+        last_connected_at: existing.last_connected_at,
     };
 
-    state
+    Ok(state
         .update_system(system)
-        .ok_or_else(|| ContainerError::SystemNotFound(payload.id))
+        .ok_or_else(|| ContainerError::SystemNotFound(payload.id))?)
 }
 
 /// Remove a system
@@ -322,7 +602,7 @@ pub fn update_system(
 pub fn remove_system(
     state: State<'_, AppState>,
     system_id: String,
-) -> Result<bool, ContainerError> {
+) -> Result<bool, AppError> {
     let removed = state.remove_system(&system_id);
 
     // Remove from cache and flush vault on desktop (non-fatal)
@@ -346,7 +626,7 @@ pub fn store_ssh_credentials(
     passphrase: Option<String>,
     private_key: Option<String>,
     jump_host_credentials: Option<HashMap<String, JumpHostCredentials>>,
-) -> Result<(), ContainerError> {
+) -> Result<(), AppError> {
     tracing::info!("Storing SSH credentials for system: {}", system_id);
 
     #[cfg(not(target_os = "android"))]
@@ -381,7 +661,7 @@ pub fn store_ssh_credentials(
 pub fn get_ssh_credentials(
     state: State<'_, AppState>,
     system_id: String,
-) -> Result<(Option<String>, Option<String>, Option<String>), ContainerError> {
+) -> Result<(Option<String>, Option<String>, Option<String>), AppError> {
     #[cfg(not(target_os = "android"))]
     {
         if let Some(kr) = state.get_cached_ssh_credentials(&system_id) {
@@ -402,29 +682,44 @@ pub fn get_ssh_credentials(
 }
 
 /// Get extended system information (user, OS, hardware) for a connected system
+/// Disk usage covers `mount_point` (default `/`, or a drive such as `D:` on Windows)
 #[tauri::command]
 pub async fn get_extended_system_info(
     state: State<'_, AppState>,
     system_id: String,
-) -> Result<ExtendedSystemInfo, ContainerError> {
+    mount_point: Option<String>,
+) -> Result<ExtendedSystemInfo, AppError> {
     let system = state
         .get_system(&system_id)
         .ok_or_else(|| ContainerError::SystemNotFound(system_id.clone()))?;
 
+    let mount_point = mount_point
+        .map(|m| m.trim().to_string())
+        .filter(|m| !m.is_empty())
+        .unwrap_or_else(|| "/".to_string());
+    let is_drive = mount_point.ends_with(':') || mount_point.ends_with(":\\");
+    if !mount_point.starts_with('/') && !is_drive {
+        return Err(ContainerError::InvalidConfiguration(format!(
+            "Mount point must be an absolute path or drive letter: {}",
+            mount_point
+        )).into());
+    }
+
     // Verify system is connected
     let conn_state = state.connection_state(&system_id);
     if conn_state != ConnectionState::Connected {
-        return Err(ContainerError::NotConnected(system_id));
+        return Err(ContainerError::NotConnected(system_id).into());
     }
 
     // Build the platform-appropriate command
     let command = match system.connection_type {
         ConnectionType::Local => {
-            CommandBuilder::get_extended_system_info_for_local(system.primary_runtime)
-        }
-        ConnectionType::Remote => {
-            CommandBuilder::get_extended_system_info_for_remote(system.primary_runtime)
+            CommandBuilder::get_extended_system_info_for_local(system.primary_runtime, &mount_point)
         }
+        ConnectionType::Remote => CommandBuilder::get_extended_system_info_for_remote(
+            system.primary_runtime,
+            &mount_point,
+        ),
     };
 
     // Execute command based on connection type
@@ -472,11 +767,12 @@ pub async fn get_extended_system_info(
             total_containers: None,
             total_images: None,
             runtime_version: None,
+            gpus: Vec::new(),
         });
     }
 
     // Parse the output
-    let info = OutputParser::parse_extended_system_info(&result.stdout);
+    let info = OutputParser::parse_extended_system_info(&result.stdout, &mount_point);
 
     tracing::info!(
         "Extended system info for {}: user={}, root={}, sudo={}, os={:?}, containers={:?}/{:?}",
@@ -505,7 +801,8 @@ pub async fn start_system_monitoring(
     monitoring: State<'_, MonitoringManager>,
     system_id: String,
     interval_ms: Option<u64>,
-) -> Result<bool, ContainerError> {
+    adaptive: Option<bool>,
+) -> Result<bool, AppError> {
     // Verify system exists and is connected
     let _system = state
         .get_system(&system_id)
@@ -513,13 +810,18 @@ pub async fn start_system_monitoring(
 
     let conn_state = state.connection_state(&system_id);
     if conn_state != ConnectionState::Connected {
-        return Err(ContainerError::NotConnected(system_id));
+        return Err(ContainerError::NotConnected(system_id).into());
     }
 
     // Default to 3 seconds if not specified
     let interval = interval_ms.unwrap_or(3000);
 
-    let started = monitoring.start_monitoring(app, system_id.clone(), interval);
+    let started = monitoring.start_monitoring(
+        app,
+        system_id.clone(),
+        interval,
+        adaptive.unwrap_or(false),
+    );
 
     tracing::info!(
         "Start monitoring request for system {}: started={}",
@@ -535,7 +837,7 @@ pub async fn start_system_monitoring(
 pub async fn stop_system_monitoring(
     monitoring: State<'_, MonitoringManager>,
     system_id: String,
-) -> Result<bool, ContainerError> {
+) -> Result<bool, AppError> {
     let stopped = monitoring.stop_monitoring(&system_id).await;
 
     tracing::info!(
@@ -556,6 +858,42 @@ pub fn is_system_monitoring(
     monitoring.is_monitoring(&system_id)
 }
 
+/// Pause live monitoring for a system (task and metrics history are kept)
+#[tauri::command]
+pub fn pause_system_monitoring(
+    monitoring: State<'_, MonitoringManager>,
+    system_id: String,
+) -> bool {
+    monitoring.pause_monitoring(&system_id)
+}
+
+/// Resume paused live monitoring for a system
+#[tauri::command]
+pub fn resume_system_monitoring(
+    monitoring: State<'_, MonitoringManager>,
+    system_id: String,
+) -> bool {
+    monitoring.resume_monitoring(&system_id)
+}
+
+/// Check if a system's monitoring is paused
+#[tauri::command]
+pub fn is_monitoring_paused(
+    monitoring: State<'_, MonitoringManager>,
+    system_id: String,
+) -> bool {
+    monitoring.is_paused(&system_id)
+}
+
+/// Get the polling interval in effect for a monitored system, in milliseconds
+#[tauri::command]
+pub fn get_monitoring_interval(
+    monitoring: State<'_, MonitoringManager>,
+    system_id: String,
+) -> Option<u64> {
+    monitoring.effective_interval(&system_id)
+}
+
 /// Get list of systems currently being monitored
 #[tauri::command]
 pub fn list_monitored_systems(
@@ -564,12 +902,117 @@ pub fn list_monitored_systems(
     monitoring.monitored_systems()
 }
 
+/// Get buffered metrics history for a system (oldest first) so charts can
+/// backfill immediately instead of waiting for new ticks
+#[tauri::command]
+pub fn get_metrics_history(
+    monitoring: State<'_, MonitoringManager>,
+    system_id: String,
+    max_points: Option<usize>,
+) -> Vec<LiveSystemMetrics> {
+    monitoring.metrics_history(&system_id, max_points)
+}
+
+/// Newest metrics sample of every monitored system in Prometheus text format
+#[tauri::command]
+pub fn export_metrics_prometheus(
+    state: State<'_, AppState>,
+    monitoring: State<'_, MonitoringManager>,
+) -> String {
+    prometheus::export(&state, &monitoring)
+}
+
+/// Get alert thresholds for a system
+#[tauri::command]
+pub fn get_alert_config(
+    state: State<'_, AppState>,
+    system_id: String,
+) -> Result<AlertConfig, AppError> {
+    let conn = state.db.lock().map_err(|e| ContainerError::Internal(e.to_string()))?;
+    Ok(crate::database::get_alert_config(&conn, &system_id)
+        .map_err(|e| ContainerError::Internal(format!("Failed to get alert config: {}", e)))?)
+}
+
+/// Set alert thresholds for a system
+/// Takes effect immediately for a running monitor; `system:alert` events are emitted on transitions
+#[tauri::command]
+pub fn set_alert_config(
+    state: State<'_, AppState>,
+    monitoring: State<'_, MonitoringManager>,
+    system_id: String,
+    config: AlertConfig,
+) -> Result<(), AppError> {
+    {
+        let conn = state.db.lock().map_err(|e| ContainerError::Internal(e.to_string()))?;
+        crate::database::upsert_alert_config(&conn, &system_id, &config)
+            .map_err(|e| ContainerError::Internal(format!("Failed to update alert config: {}", e)))?;
+    }
+
+    monitoring.set_alert_config(&system_id, config);
+    Ok(())
+}
+
+/// Start per-container monitoring for a system
+/// Emits `container:metrics` events at the specified interval
+#[tauri::command]
+pub async fn start_container_monitoring(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    monitoring: State<'_, MonitoringManager>,
+    system_id: String,
+    container_ids: Option<Vec<String>>,
+    interval_ms: Option<u64>,
+) -> Result<bool, AppError> {
+    let _system = state
+        .get_system(&system_id)
+        .ok_or_else(|| ContainerError::SystemNotFound(system_id.clone()))?;
+
+    if state.connection_state(&system_id) != ConnectionState::Connected {
+        return Err(ContainerError::NotConnected(system_id).into());
+    }
+
+    // Default to 3 seconds if not specified
+    let interval = interval_ms.unwrap_or(3000);
+
+    let started = monitoring.start_container_monitoring(
+        app,
+        system_id.clone(),
+        container_ids.unwrap_or_default(),
+        interval,
+    );
+
+    tracing::info!(
+        "Start container monitoring request for system {}: started={}",
+        system_id,
+        started
+    );
+
+    Ok(started)
+}
+
+/// Stop per-container monitoring for a system
+#[tauri::command]
+pub async fn stop_container_monitoring(
+    monitoring: State<'_, MonitoringManager>,
+    system_id: String,
+) -> Result<bool, AppError> {
+    let stopped = monitoring.stop_container_monitoring(&system_id).await;
+
+    tracing::info!(
+        "Stop container monitoring request for system {}: stopped={}",
+        system_id,
+        stopped
+    );
+
+    Ok(stopped)
+}
+
 /// Get current live metrics for a system (one-shot, not streaming)
 #[tauri::command]
 pub async fn get_live_metrics(
     state: State<'_, AppState>,
     system_id: String,
-) -> Result<LiveSystemMetrics, ContainerError> {
+) -> Result<LiveSystemMetrics, AppError> {
     let system = state
         .get_system(&system_id)
         .ok_or_else(|| ContainerError::SystemNotFound(system_id.clone()))?;
@@ -577,7 +1020,7 @@ pub async fn get_live_metrics(
     // Verify system is connected
     let conn_state = state.connection_state(&system_id);
     if conn_state != ConnectionState::Connected {
-        return Err(ContainerError::NotConnected(system_id));
+        return Err(ContainerError::NotConnected(system_id).into());
     }
 
     // Build the platform-appropriate command
@@ -606,16 +1049,18 @@ pub async fn get_live_metrics(
             command: command.to_string(),
             exit_code: result.exit_code,
             stderr: result.stderr,
-        });
+        }.into());
     }
 
-    Ok(OutputParser::parse_live_metrics(&result.stdout, &system_id))
+    // One-shot reads have no previous sample, so throughput rates are None
+    let (metrics, _) = OutputParser::parse_live_metrics(&result.stdout, &system_id, None);
+    Ok(metrics)
 }
 
 /// Import SSH private key from a file and return its content as PEM string
 /// Used for mobile file picker where we can't rely on file paths
 #[tauri::command]
-pub async fn import_ssh_key_from_file(file_path: String) -> Result<String, ContainerError> {
+pub async fn import_ssh_key_from_file(file_path: String) -> Result<String, AppError> {
     tracing::info!("Importing SSH key from file: {}", file_path);
 
     // Expand ~ to home directory
@@ -641,13 +1086,108 @@ pub async fn import_ssh_key_from_file(file_path: String) -> Result<String, Conta
     if !content.contains("-----BEGIN") || !content.contains("PRIVATE KEY-----") {
         return Err(ContainerError::InvalidConfiguration(
             "Invalid SSH key format. Expected PEM format (-----BEGIN ... PRIVATE KEY-----)".to_string(),
-        ));
+        ).into());
     }
 
     tracing::info!("Successfully imported SSH key from {}", file_path);
     Ok(content)
 }
 
+/// Import an SSH private key from pasted PEM content and store it for a system
+/// Returns the key's `SHA256:...` fingerprint. The key is stored like
+/// `store_ssh_credentials` does (keyring on desktop, encrypted in the database on
+/// Android), so it is used the same way as `private_key_content` on mobile.
+#[tauri::command]
+pub fn import_ssh_key_from_content(
+    state: State<'_, AppState>,
+    system_id: String,
+    pem: String,
+    passphrase: Option<String>,
+) -> Result<String, AppError> {
+    if state.get_system(&system_id).is_none() {
+        return Err(ContainerError::SystemNotFound(system_id).into());
+    }
+
+    let fingerprint = crate::ssh::inspect_private_key(&pem, passphrase.as_deref())?.fingerprint;
+    // Keys pasted from a password manager often lose their trailing newline
+    let pem = format!("{}\n", pem.trim());
+    let passphrase = passphrase.filter(|p| !p.is_empty());
+    store_ssh_credentials(state, system_id.clone(), None, passphrase, Some(pem), None)?;
+
+    tracing::info!("Imported SSH key {} for system {}", fingerprint, system_id);
+    Ok(fingerprint)
+}
+
+/// Type and fingerprint of the key a system authenticates with
+///
+/// Checks the stored key first, then the system's key content and key file, in
+/// the order connecting uses them. Only public details are returned.
+#[tauri::command]
+pub fn get_ssh_key_fingerprint(
+    state: State<'_, AppState>,
+    system_id: String,
+) -> Result<crate::ssh::SshKeyInfo, AppError> {
+    let system = state
+        .get_system(&system_id)
+        .ok_or_else(|| ContainerError::SystemNotFound(system_id.clone()))?;
+    let ssh_config = system.ssh_config.ok_or_else(|| {
+        ContainerError::InvalidConfiguration("System has no SSH configuration".to_string())
+    })?;
+    if ssh_config.auth_method != SshAuthMethod::PublicKey {
+        return Err(ContainerError::InvalidConfiguration(
+            "System uses password authentication".to_string(),
+        )
+        .into());
+    }
+
+    let (_, passphrase, stored_key) = get_ssh_credentials(state, system_id)?;
+    let passphrase = passphrase.as_deref();
+    if let Some(pem) = stored_key.or(ssh_config.private_key_content) {
+        Ok(crate::ssh::inspect_private_key(&pem, passphrase)?)
+    } else if let Some(path) = ssh_config.private_key_path {
+        Ok(crate::ssh::inspect_identity_file(&path, passphrase)?)
+    } else {
+        Err(ContainerError::InvalidConfiguration(
+            "No SSH key is configured for this system".to_string(),
+        )
+        .into())
+    }
+}
+
+/// Keys in ~/.ssh that can be used as identities, with their types and fingerprints
+#[tauri::command]
+pub fn list_available_identity_keys() -> Vec<crate::ssh::SshKeyInfo> {
+    crate::ssh::list_identity_keys()
+}
+
+// ========================================================================
+// Connection Health Commands
+// ========================================================================
+
+/// Start validating connected remote systems in the background
+/// Emits `system:connection-changed` when a system is found disconnected or errored
+#[tauri::command]
+pub fn start_health_polling(
+    app: AppHandle,
+    health: State<'_, HealthPoller>,
+    interval_ms: Option<u64>,
+) -> Result<(), AppError> {
+    let interval = interval_ms.unwrap_or(DEFAULT_HEALTH_INTERVAL_MS);
+    if interval == 0 {
+        return Err(ContainerError::InvalidConfiguration(
+            "Health polling interval must be greater than zero".to_string(),
+        ).into());
+    }
+    health.start(app, interval);
+    Ok(())
+}
+
+/// Stop background connection health polling
+#[tauri::command]
+pub async fn stop_health_polling(health: State<'_, HealthPoller>) -> Result<bool, AppError> {
+    Ok(health.stop().await)
+}
+
 // ========================================================================
 // SSH Config Commands (for importing hosts from ~/.ssh/config)
 // ========================================================================
@@ -665,16 +1205,16 @@ pub fn has_ssh_config(config_paths: Option<Vec<String>>) -> bool {
 
 /// List all SSH hosts from config files (excludes wildcard patterns)
 #[tauri::command]
-pub fn list_ssh_config_hosts(config_paths: Option<Vec<String>>) -> Result<Vec<crate::ssh::SshHostEntry>, ContainerError> {
+pub fn list_ssh_config_hosts(config_paths: Option<Vec<String>>) -> Result<Vec<crate::ssh::SshHostEntry>, AppError> {
     let paths = config_paths.unwrap_or_default();
-    crate::ssh::list_hosts_multi(&paths)
+    Ok(crate::ssh::list_hosts_multi(&paths)?)
 }
 
 /// Get resolved SSH configuration for a specific host from config files
 #[tauri::command]
-pub fn get_ssh_host_config(host: String, config_paths: Option<Vec<String>>) -> Result<crate::ssh::SshHostEntry, ContainerError> {
+pub fn get_ssh_host_config(host: String, config_paths: Option<Vec<String>>) -> Result<crate::ssh::SshHostEntry, AppError> {
     let paths = config_paths.unwrap_or_default();
-    crate::ssh::resolve_host_multi(&host, &paths)
+    Ok(crate::ssh::resolve_host_multi(&host, &paths)?)
 }
 
 // ========================================================================
@@ -683,25 +1223,63 @@ pub fn get_ssh_host_config(host: String, config_paths: Option<Vec<String>>) -> R
 
 /// Get app settings (SSH config path, etc.)
 #[tauri::command]
-pub fn get_app_settings(state: State<'_, AppState>) -> Result<crate::database::AppSettings, ContainerError> {
+pub fn get_app_settings(state: State<'_, AppState>) -> Result<crate::database::AppSettings, AppError> {
     let conn = state.db.lock().map_err(|e| ContainerError::Internal(e.to_string()))?;
-    crate::database::get_app_settings(&conn)
-        .map_err(|e| ContainerError::Internal(format!("Failed to get app settings: {}", e)))
+    Ok(crate::database::get_app_settings(&conn)
+        .map_err(|e| ContainerError::Internal(format!("Failed to get app settings: {}", e)))?)
 }
 
 /// Remove a host key from ~/.ssh/known_hosts (used when user trusts a changed key)
 #[tauri::command]
-pub fn remove_known_host(hostname: String, port: u16) -> Result<usize, ContainerError> {
+pub fn remove_known_host(hostname: String, port: u16) -> Result<usize, AppError> {
     tracing::info!("Removing known host key for {}:{}", hostname, port);
-    crate::ssh::known_hosts::remove_host_key(&hostname, port)
+    Ok(crate::ssh::known_hosts::remove_host_key(&hostname, port)?)
 }
 
-/// Update app settings
+/// Trust a host key that a connection stopped at, adding it to ~/.ssh/known_hosts.
+/// Only the key the server actually presented for `hostname` with this fingerprint
+/// can be trusted; reconnect afterwards.
 #[tauri::command]
-pub fn update_app_settings(state: State<'_, AppState>, settings: crate::database::AppSettings) -> Result<(), ContainerError> {
+pub fn trust_host_key(hostname: String, fingerprint: String) -> Result<(), AppError> {
+    tracing::info!("Trusting host key {} for {}", fingerprint, hostname);
+    Ok(crate::ssh::known_hosts::trust_host_key(
+        &hostname,
+        &fingerprint,
+    )?)
+}
+
+/// Update app settings, starting or stopping the metrics exporter to match
+#[tauri::command]
+pub async fn update_app_settings(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    exporter: State<'_, MetricsExporter>,
+    settings: crate::database::AppSettings,
+) -> Result<(), AppError> {
+    // Bind first so a port that is already taken isn't saved
+    exporter.apply(app, settings.metrics_exporter_port).await?;
+
     let conn = state.db.lock().map_err(|e| ContainerError::Internal(e.to_string()))?;
-    crate::database::upsert_app_settings(&conn, &settings)
-        .map_err(|e| ContainerError::Internal(format!("Failed to update app settings: {}", e)))
+    Ok(crate::database::upsert_app_settings(&conn, &settings)
+        .map_err(|e| ContainerError::Internal(format!("Failed to update app settings: {}", e)))?)
+}
+
+// ========================================================================
+// Database Backup Commands
+// ========================================================================
+
+/// Back up the database to a file; safe to run while the app is in use
+#[tauri::command]
+pub fn backup_database(state: State<'_, AppState>, dest_path: String) -> Result<(), AppError> {
+    tracing::info!("Backing up database to {}", dest_path);
+    Ok(state.backup_database(std::path::Path::new(&dest_path))?)
+}
+
+/// Restore the database from a backup file, replacing all systems, templates and settings
+#[tauri::command]
+pub fn restore_database(state: State<'_, AppState>, src_path: String) -> Result<(), AppError> {
+    tracing::info!("Restoring database from {}", src_path);
+    Ok(state.restore_database(std::path::Path::new(&src_path))?)
 }
 
 /// Get the changelog content (embedded at compile time from CHANGELOG.md)
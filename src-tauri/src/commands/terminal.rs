@@ -1,19 +1,28 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::fs::File;
 #[cfg(not(target_os = "android"))]
-use std::io::{Read, Write};
+use std::io::Read;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::{mpsc, Mutex, RwLock};
 
+use once_cell::sync::Lazy;
 #[cfg(not(target_os = "android"))]
 use portable_pty::{native_pty_system, CommandBuilder, MasterPty, PtySize};
+use regex::Regex;
 use russh::ChannelMsg;
 use serde::{Deserialize, Serialize};
-use tauri::{AppHandle, Emitter, State};
+use tauri::{AppHandle, Emitter, Manager, State};
 use uuid::Uuid;
 
+use crate::agent::session::{AgentSessionManager, TerminalContext};
+use crate::executor::local::LocalExecutor;
+use crate::executor::CommandExecutor;
 use crate::models::container::ContainerRuntime;
-use crate::models::error::ContainerError;
-use crate::models::system::ConnectionType;
+use crate::models::error::{AppError, ContainerError};
+use crate::models::system::{ConnectionType, ContainerSystem};
 use crate::ssh;
 use crate::state::AppState;
 
@@ -26,6 +35,23 @@ pub struct TerminalSession {
     pub shell: String,
 }
 
+/// A single command from a remote shell history file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShellHistoryEntry {
+    pub command: String,
+    /// Unix timestamp (seconds) when the history format records one
+    pub timestamp: Option<i64>,
+}
+
+/// The container and shell a terminal session was opened into
+#[derive(Debug, Clone)]
+pub struct ContainerShell {
+    pub container_id: String,
+    pub runtime: ContainerRuntime,
+    pub shell: String,
+}
+
 /// Represents a terminal session handle - either local PTY or SSH channel
 #[allow(dead_code)]
 pub enum SessionHandle {
@@ -47,12 +73,196 @@ pub enum TerminalInput {
     Close,
 }
 
+/// Shells looked for by `detect_shells`, most preferred first
+const PREFERRED_SHELLS: &[&str] = &["bash", "zsh", "fish", "ash", "sh"];
+
+/// Lines of output kept per session for scrollback export
+const SCROLLBACK_MAX_LINES: usize = 10_000;
+
+/// Longest unterminated line kept before it is broken, so output without
+/// newlines can't grow the scrollback without bound
+const SCROLLBACK_MAX_LINE_BYTES: usize = 16 * 1024;
+
+/// CSI sequences (colors, cursor movement), OSC sequences (titles) and other escapes
+static ANSI_ESCAPE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"\x1b(?:\[[0-?]*[ -/]*[@-~]|\][^\x07\x1b]*(?:\x07|\x1b\\)|[()][0-9A-Za-z]|[@-Z\\-_])",
+    )
+    .unwrap()
+});
+
+/// Remove ANSI escape sequences, leaving plain text
+pub(crate) fn strip_ansi(text: &str) -> String {
+    ANSI_ESCAPE.replace_all(text, "").into_owned()
+}
+
+/// Recent output of a terminal session, as raw lines including escapes
+#[derive(Default)]
+struct Scrollback {
+    lines: VecDeque<String>,
+    /// Output after the last newline
+    partial: String,
+}
+
+impl Scrollback {
+    fn push(&mut self, data: &str) {
+        for (i, piece) in data.split('\n').enumerate() {
+            if i > 0 {
+                self.end_line();
+            }
+            self.partial.push_str(piece);
+            if self.partial.len() > SCROLLBACK_MAX_LINE_BYTES {
+                self.end_line();
+            }
+        }
+    }
+
+    fn end_line(&mut self) {
+        let mut line = std::mem::take(&mut self.partial);
+        if line.ends_with('\r') {
+            line.pop();
+        }
+        self.lines.push_back(line);
+        while self.lines.len() > SCROLLBACK_MAX_LINES {
+            self.lines.pop_front();
+        }
+    }
+
+    /// The last `max_lines` lines (all when None), joined with newlines
+    fn text(&self, max_lines: Option<usize>, strip: bool) -> String {
+        let partial = (!self.partial.is_empty()).then_some(self.partial.as_str());
+        let all: Vec<&str> = self
+            .lines
+            .iter()
+            .map(String::as_str)
+            .chain(partial)
+            .collect();
+        let start = all.len().saturating_sub(max_lines.unwrap_or(all.len()));
+        let text = all[start..].join("\n");
+        if strip {
+            strip_ansi(&text)
+        } else {
+            text
+        }
+    }
+}
+
+/// Size a terminal opens at when the frontend doesn't know its own yet
+const DEFAULT_COLS: u16 = 80;
+const DEFAULT_ROWS: u16 = 24;
+
+/// Resizes arriving closer together than this are coalesced into the last one
+const RESIZE_DEBOUNCE: Duration = Duration::from_millis(50);
+
+/// Requested terminal size, falling back to the default for missing or zero
+/// dimensions (a hidden xterm measures 0x0)
+fn terminal_size(cols: Option<u16>, rows: Option<u16>) -> (u16, u16) {
+    (
+        cols.filter(|&c| c > 0).unwrap_or(DEFAULT_COLS),
+        rows.filter(|&r| r > 0).unwrap_or(DEFAULT_ROWS),
+    )
+}
+
+/// Bytes of a recording buffered in memory before they are written to disk
+const RECORDING_BUFFER_SIZE: usize = 64 * 1024;
+
+/// Flush a recording at least this often while the session produces output
+const RECORDING_FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// An asciicast v2 recording of a terminal session, written to disk as it goes
+pub struct TerminalRecording {
+    path: PathBuf,
+    /// None once the recording has been stopped
+    writer: Option<BufWriter<File>>,
+    started: Instant,
+    last_flush: Instant,
+    record_input: bool,
+}
+
+impl TerminalRecording {
+    /// Create the file and write the asciicast header
+    fn create(path: PathBuf, cols: u16, rows: u16, record_input: bool) -> std::io::Result<Self> {
+        let mut writer = BufWriter::with_capacity(RECORDING_BUFFER_SIZE, File::create(&path)?);
+        let header = serde_json::json!({
+            "version": 2,
+            "width": cols,
+            "height": rows,
+            "timestamp": chrono::Utc::now().timestamp(),
+            "env": { "TERM": "xterm-256color" },
+        });
+        writeln!(writer, "{}", header)?;
+        writer.flush()?;
+
+        let now = Instant::now();
+        Ok(Self {
+            path,
+            writer: Some(writer),
+            started: now,
+            last_flush: now,
+            record_input,
+        })
+    }
+
+    fn is_active(&self) -> bool {
+        self.writer.is_some()
+    }
+
+    /// Append an event: `o` output, `i` input or `r` resize
+    fn write_event(&mut self, kind: &str, data: &str) -> std::io::Result<()> {
+        let Some(writer) = self.writer.as_mut() else {
+            return Ok(());
+        };
+        // Microsecond precision keeps lines short without losing replay timing
+        let time = (self.started.elapsed().as_secs_f64() * 1e6).round() / 1e6;
+        writeln!(writer, "{}", serde_json::json!([time, kind, data]))?;
+
+        if self.last_flush.elapsed() >= RECORDING_FLUSH_INTERVAL {
+            writer.flush()?;
+            self.last_flush = Instant::now();
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self.writer.as_mut() {
+            Some(writer) => writer.flush(),
+            None => Ok(()),
+        }
+    }
+
+    /// Flush and close the file; the path stays available for export
+    fn finish(&mut self) -> std::io::Result<()> {
+        match self.writer.take() {
+            Some(mut writer) => writer.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
 /// Manages all active terminal sessions
 #[derive(Clone)]
 pub struct TerminalSessions {
     sessions: Arc<Mutex<HashMap<String, SessionHandle>>>,
     /// Output listeners for agent commands - keyed by terminal session ID
     output_listeners: Arc<RwLock<HashMap<String, mpsc::Sender<String>>>>,
+    /// Recordings keyed by terminal session ID, kept after stopping for export.
+    /// A std mutex because the local PTY reader records from a plain thread.
+    recordings: Arc<std::sync::Mutex<HashMap<String, TerminalRecording>>>,
+    /// Scrollback per session, kept after the shell exits until the session is closed
+    scrollback: Arc<std::sync::Mutex<HashMap<String, Scrollback>>>,
+    /// System each open session runs on, so the agent can reach its containers
+    system_ids: Arc<std::sync::Mutex<HashMap<String, String>>>,
+    /// Shell each open session was started with
+    session_shells: Arc<std::sync::Mutex<HashMap<String, String>>>,
+    /// Container each container session execs into, until its shell exits
+    containers: Arc<std::sync::Mutex<HashMap<String, ContainerShell>>>,
+    /// Shells found on each system, or in a container as `system/container`,
+    /// kept for the rest of the app session
+    shells: Arc<std::sync::Mutex<HashMap<String, Vec<String>>>>,
+    /// When each session last had input, output or a resize, for the idle reaper
+    last_activity: Arc<std::sync::Mutex<HashMap<String, Instant>>>,
+    /// Latest size requested per session while a debounced resize is waiting
+    pending_resizes: Arc<std::sync::Mutex<HashMap<String, (u16, u16)>>>,
 }
 
 impl Default for TerminalSessions {
@@ -60,6 +270,14 @@ impl Default for TerminalSessions {
         Self {
             sessions: Arc::new(Mutex::new(HashMap::new())),
             output_listeners: Arc::new(RwLock::new(HashMap::new())),
+            recordings: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            scrollback: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            system_ids: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            session_shells: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            containers: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            shells: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            last_activity: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            pending_resizes: Arc::new(std::sync::Mutex::new(HashMap::new())),
         }
     }
 }
@@ -94,6 +312,193 @@ impl TerminalSessions {
             let _ = tx.try_send(output.to_string());
         }
     }
+
+    /// Append an event to the session's recording, if one is running.
+    /// A write failure stops the recording rather than failing the terminal.
+    fn record(&self, session_id: &str, kind: &str, data: &str) {
+        let mut recordings = self.recordings.lock().unwrap();
+        let Some(recording) = recordings.get_mut(session_id) else {
+            return;
+        };
+        if kind == "i" && !recording.record_input {
+            return;
+        }
+        if let Err(e) = recording.write_event(kind, data) {
+            tracing::warn!("Stopping recording of terminal {}: {}", session_id, e);
+            let _ = recording.finish();
+        }
+    }
+
+    /// Keep terminal output for scrollback and any running recording
+    pub fn capture_output(&self, session_id: &str, data: &str) {
+        self.touch(session_id);
+        self.scrollback
+            .lock()
+            .unwrap()
+            .entry(session_id.to_string())
+            .or_default()
+            .push(data);
+        self.record_output(session_id, data);
+    }
+
+    /// Mark a session as in use now
+    fn touch(&self, session_id: &str) {
+        self.last_activity
+            .lock()
+            .unwrap()
+            .insert(session_id.to_string(), Instant::now());
+    }
+
+    /// How long a session has gone without input, output or a resize
+    fn idle_time(&self, session_id: &str) -> Option<Duration> {
+        self.last_activity
+            .lock()
+            .unwrap()
+            .get(session_id)
+            .map(Instant::elapsed)
+    }
+
+    /// Sessions that have been idle for longer than `timeout`
+    fn idle_sessions(&self, timeout: Duration) -> Vec<String> {
+        idle_beyond(&self.last_activity.lock().unwrap(), Instant::now(), timeout)
+    }
+
+    /// Remember the latest requested size. Returns true when no resize was
+    /// waiting, so the caller should schedule applying it.
+    fn queue_resize(&self, session_id: &str, cols: u16, rows: u16) -> bool {
+        self.pending_resizes
+            .lock()
+            .unwrap()
+            .insert(session_id.to_string(), (cols, rows))
+            .is_none()
+    }
+
+    /// Take the size waiting to be applied to a session
+    fn take_resize(&self, session_id: &str) -> Option<(u16, u16)> {
+        self.pending_resizes.lock().unwrap().remove(session_id)
+    }
+
+    /// Resize a session's local PTY, or send a window-change over its SSH channel
+    async fn apply_resize(&self, session_id: &str, cols: u16, rows: u16) -> Result<(), ContainerError> {
+        match self.sessions.lock().await.get_mut(session_id) {
+            #[cfg(not(target_os = "android"))]
+            Some(SessionHandle::Local { master, .. }) => {
+                master
+                    .resize(PtySize {
+                        rows,
+                        cols,
+                        pixel_width: 0,
+                        pixel_height: 0,
+                    })
+                    .map_err(|e| ContainerError::Internal(e.to_string()))?;
+            }
+            Some(SessionHandle::Ssh { input_tx }) => {
+                input_tx
+                    .send(TerminalInput::Resize { cols, rows })
+                    .await
+                    .map_err(|e| ContainerError::Internal(e.to_string()))?;
+            }
+            None => return Ok(()),
+        }
+        self.touch(session_id);
+        self.record_resize(session_id, cols, rows);
+        Ok(())
+    }
+
+    /// Close a session and drop everything kept for it
+    async fn close(&self, session_id: &str) {
+        if let Some(handle) = self.sessions.lock().await.remove(session_id) {
+            match handle {
+                SessionHandle::Ssh { input_tx } => {
+                    let _ = input_tx.send(TerminalInput::Close).await;
+                }
+                #[cfg(not(target_os = "android"))]
+                SessionHandle::Local { .. } => {
+                    // Local sessions are cleaned up when dropped
+                }
+            }
+        }
+        self.finish_recording(session_id);
+        self.scrollback.lock().unwrap().remove(session_id);
+        self.system_ids.lock().unwrap().remove(session_id);
+        self.session_shells.lock().unwrap().remove(session_id);
+        self.last_activity.lock().unwrap().remove(session_id);
+        self.pending_resizes.lock().unwrap().remove(session_id);
+    }
+
+    /// ID of the system an open session runs on
+    pub fn system_id(&self, session_id: &str) -> Option<String> {
+        self.system_ids.lock().unwrap().get(session_id).cloned()
+    }
+
+    /// Shell an open session was started with
+    pub fn shell(&self, session_id: &str) -> Option<String> {
+        self.session_shells.lock().unwrap().get(session_id).cloned()
+    }
+
+    /// Container an open session runs a shell in
+    pub fn container(&self, session_id: &str) -> Option<ContainerShell> {
+        self.containers.lock().unwrap().get(session_id).cloned()
+    }
+
+    /// Shells detected earlier on a system, or inside one of its containers
+    fn cached_shells(&self, system_id: &str, container_id: Option<&str>) -> Option<Vec<String>> {
+        self.shells
+            .lock()
+            .unwrap()
+            .get(&shell_cache_key(system_id, container_id))
+            .cloned()
+    }
+
+    fn cache_shells(&self, system_id: &str, container_id: Option<&str>, shells: Vec<String>) {
+        self.shells
+            .lock()
+            .unwrap()
+            .insert(shell_cache_key(system_id, container_id), shells);
+    }
+
+    /// Recent output of a session, or None if it has produced none
+    pub fn scrollback_text(
+        &self,
+        session_id: &str,
+        max_lines: Option<usize>,
+        strip: bool,
+    ) -> Option<String> {
+        self.scrollback
+            .lock()
+            .unwrap()
+            .get(session_id)
+            .map(|s| s.text(max_lines, strip))
+    }
+
+    /// Record terminal output
+    pub fn record_output(&self, session_id: &str, data: &str) {
+        self.record(session_id, "o", data);
+    }
+
+    /// Record user input (only when the recording opted in)
+    pub fn record_input(&self, session_id: &str, data: &str) {
+        self.record(session_id, "i", data);
+    }
+
+    /// Record a terminal resize
+    pub fn record_resize(&self, session_id: &str, cols: u16, rows: u16) {
+        self.record(session_id, "r", &format!("{}x{}", cols, rows));
+    }
+
+    /// Stop a session's recording, returning its file path if one was running
+    fn finish_recording(&self, session_id: &str) -> Option<PathBuf> {
+        let mut recordings = self.recordings.lock().unwrap();
+        let recording = recordings.get_mut(session_id).filter(|r| r.is_active())?;
+        if let Err(e) = recording.finish() {
+            tracing::warn!(
+                "Failed to flush recording of terminal {}: {}",
+                session_id,
+                e
+            );
+        }
+        Some(recording.path.clone())
+    }
 }
 
 /// Start a new terminal session
@@ -104,19 +509,87 @@ pub async fn start_terminal_session(
     sessions: State<'_, TerminalSessions>,
     system_id: String,
     container_id: Option<String>,
-    shell: String,
+    shell: Option<String>,
     cols: Option<u16>,
     rows: Option<u16>,
-) -> Result<TerminalSession, ContainerError> {
-    let cols = cols.unwrap_or(80);
-    let rows = rows.unwrap_or(24);
-    let session_id = Uuid::new_v4().to_string();
-
+) -> Result<TerminalSession, AppError> {
     // Get system to determine connection type
     let system = state
         .get_system(&system_id)
         .ok_or_else(|| ContainerError::SystemNotFound(system_id.clone()))?;
 
+    // Images without the requested shell (Alpine has no bash) would fail the exec
+    let shell = match &container_id {
+        Some(cid) => container_shell(&sessions, &system, cid, shell).await,
+        None => {
+            shell.unwrap_or_else(|| crate::runtime::CommandBuilder::default_shell().to_string())
+        }
+    };
+
+    Ok(open_session(app, &sessions, &system, container_id, shell, cols, rows).await?)
+}
+
+/// Start a terminal inside a container with its preferred shell, pointing the
+/// terminal's agent at the container until the shell exits
+#[tauri::command]
+pub async fn start_container_terminal(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    sessions: State<'_, TerminalSessions>,
+    system_id: String,
+    container_id: String,
+    cols: Option<u16>,
+    rows: Option<u16>,
+) -> Result<TerminalSession, AppError> {
+    let system = state
+        .get_system(&system_id)
+        .ok_or_else(|| ContainerError::SystemNotFound(system_id.clone()))?;
+
+    let shell = match detect_available_shells(&sessions, &system, Some(&container_id)).await {
+        Ok(shells) => shells.into_iter().next(),
+        // The probe runs under sh, so an image without sh fails to exec it
+        Err(ContainerError::CommandExecutionFailed {
+            exit_code: 126 | 127,
+            ..
+        }) => None,
+        Err(e) => return Err(e.into()),
+    }
+    .ok_or_else(|| {
+        ContainerError::UnsupportedOperation(format!(
+            "Container {} has no shell to open a terminal with (looked for {})",
+            container_id,
+            PREFERRED_SHELLS.join(", ")
+        ))
+    })?;
+
+    let session = open_session(
+        app.clone(),
+        &sessions,
+        &system,
+        Some(container_id),
+        shell,
+        cols,
+        rows,
+    )
+    .await?;
+    enter_agent_container(&app, &sessions, &session.id).await;
+    Ok(session)
+}
+
+/// Open a PTY on the system, running `shell` inside the container if one is given
+async fn open_session(
+    app: AppHandle,
+    sessions: &TerminalSessions,
+    system: &ContainerSystem,
+    container_id: Option<String>,
+    shell: String,
+    cols: Option<u16>,
+    rows: Option<u16>,
+) -> Result<TerminalSession, ContainerError> {
+    let (cols, rows) = terminal_size(cols, rows);
+    let session_id = Uuid::new_v4().to_string();
+    let system_id = system.id.0.clone();
+
     let command = build_terminal_command(&container_id, &shell, &system.primary_runtime);
 
     match system.connection_type {
@@ -125,7 +598,7 @@ pub async fn start_terminal_session(
             {
                 start_local_session(
                     app,
-                    sessions.inner().clone(),
+                    sessions.clone(),
                     session_id.clone(),
                     command,
                     cols,
@@ -143,7 +616,7 @@ pub async fn start_terminal_session(
         ConnectionType::Remote => {
             start_ssh_session(
                 app,
-                sessions.inner().clone(),
+                sessions.clone(),
                 session_id.clone(),
                 &system_id,
                 command,
@@ -154,6 +627,28 @@ pub async fn start_terminal_session(
         }
     }
 
+    sessions
+        .system_ids
+        .lock()
+        .unwrap()
+        .insert(session_id.clone(), system_id.clone());
+    sessions
+        .session_shells
+        .lock()
+        .unwrap()
+        .insert(session_id.clone(), shell.clone());
+    sessions.touch(&session_id);
+    if let Some(cid) = &container_id {
+        sessions.containers.lock().unwrap().insert(
+            session_id.clone(),
+            ContainerShell {
+                container_id: cid.clone(),
+                runtime: system.primary_runtime,
+                shell: shell.clone(),
+            },
+        );
+    }
+
     Ok(TerminalSession {
         id: session_id,
         system_id,
@@ -162,20 +657,186 @@ pub async fn start_terminal_session(
     })
 }
 
+/// Switch the agent attached to a container terminal, if any, to the container
+async fn enter_agent_container(app: &AppHandle, sessions: &TerminalSessions, session_id: &str) {
+    let Some(container) = sessions.container(session_id) else {
+        return;
+    };
+    let Some(context) = agent_context(app, session_id).await else {
+        return;
+    };
+    context.write().await.enter_container(
+        container.container_id,
+        runtime_binary(container.runtime).to_string(),
+        shell_name(&container.shell).to_string(),
+    );
+}
+
+/// Restore the host context of the agent attached to a container terminal
+/// whose shell has exited
+async fn exit_agent_container(app: &AppHandle, sessions: &TerminalSessions, session_id: &str) {
+    if sessions
+        .containers
+        .lock()
+        .unwrap()
+        .remove(session_id)
+        .is_none()
+    {
+        return;
+    }
+    if let Some(context) = agent_context(app, session_id).await {
+        context.write().await.exit_container();
+    }
+}
+
+/// Context of the agent session attached to a terminal
+async fn agent_context(app: &AppHandle, session_id: &str) -> Option<Arc<RwLock<TerminalContext>>> {
+    let agents = app.try_state::<AgentSessionManager>()?;
+    let agent = agents.get_session_by_terminal(session_id).await?;
+    agents.get_context(&agent.id).await
+}
+
+/// Shell to exec in a container: the requested one when it is installed,
+/// otherwise the preferred installed shell. If probing fails the requested
+/// shell is tried, or `/bin/sh` when none was requested.
+async fn container_shell(
+    sessions: &TerminalSessions,
+    system: &ContainerSystem,
+    container_id: &str,
+    requested: Option<String>,
+) -> String {
+    let available = match detect_available_shells(sessions, system, Some(container_id)).await {
+        Ok(shells) => shells,
+        Err(e) => {
+            tracing::debug!(
+                "Shell detection in container {} failed: {}",
+                container_id,
+                e
+            );
+            Vec::new()
+        }
+    };
+    pick_shell(&available, requested.as_deref())
+}
+
+/// Pick `requested` if it is among the available shells (matching by name, as
+/// the path may differ), else the first available shell
+fn pick_shell(available: &[String], requested: Option<&str>) -> String {
+    let installed = |name: &str| {
+        available
+            .iter()
+            .find(|path| shell_name(path) == shell_name(name))
+            .cloned()
+    };
+    requested
+        .and_then(installed)
+        .or_else(|| available.first().cloned())
+        .or_else(|| requested.map(str::to_string))
+        .unwrap_or_else(|| crate::runtime::CommandBuilder::default_shell().to_string())
+}
+
+/// Executable name of a shell path, e.g. `bash` for `/usr/bin/bash`
+pub(crate) fn shell_name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+fn shell_cache_key(system_id: &str, container_id: Option<&str>) -> String {
+    match container_id {
+        Some(cid) => format!("{}/{}", system_id, cid),
+        None => system_id.to_string(),
+    }
+}
+
+/// Installed shells on a system, or inside one of its containers, most
+/// preferred first. Results are cached for the rest of the app session.
+async fn detect_available_shells(
+    sessions: &TerminalSessions,
+    system: &ContainerSystem,
+    container_id: Option<&str>,
+) -> Result<Vec<String>, ContainerError> {
+    if let Some(shells) = sessions.cached_shells(&system.id.0, container_id) {
+        return Ok(shells);
+    }
+
+    let command = match container_id {
+        Some(cid) => {
+            crate::runtime::CommandBuilder::detect_container_shells(system.primary_runtime, cid)
+        }
+        None => crate::runtime::CommandBuilder::detect_shells().to_string(),
+    };
+    let result = match system.connection_type {
+        ConnectionType::Local => LocalExecutor::new().execute(&command).await?,
+        ConnectionType::Remote => ssh::execute_on_system(&system.id.0, &command).await?,
+    };
+    if !result.success() {
+        return Err(ContainerError::CommandExecutionFailed {
+            command,
+            exit_code: result.exit_code,
+            stderr: result.stderr,
+        });
+    }
+
+    let shells = parse_detected_shells(&result.stdout);
+    sessions.cache_shells(&system.id.0, container_id, shells.clone());
+    Ok(shells)
+}
+
+/// Order `command -v` output by `PREFERRED_SHELLS`, keeping one path per shell
+fn parse_detected_shells(output: &str) -> Vec<String> {
+    let mut found: Vec<(usize, &str)> = output
+        .lines()
+        .map(str::trim)
+        // Anything but a path is an alias or builtin, not an executable
+        .filter(|line| line.starts_with('/'))
+        .filter_map(|path| {
+            let rank = PREFERRED_SHELLS
+                .iter()
+                .position(|name| *name == shell_name(path))?;
+            Some((rank, path))
+        })
+        .collect();
+    found.sort_by_key(|(rank, _)| *rank);
+    found.dedup_by_key(|(rank, _)| *rank);
+    found
+        .into_iter()
+        .map(|(_, path)| path.to_string())
+        .collect()
+}
+
+/// Detect the shells installed on a system, or inside `container_id` when
+/// given, most preferred first (bash, zsh, fish, ash, sh)
+#[tauri::command]
+pub async fn detect_shells(
+    state: State<'_, AppState>,
+    sessions: State<'_, TerminalSessions>,
+    system_id: String,
+    container_id: Option<String>,
+) -> Result<Vec<String>, AppError> {
+    let system = state
+        .get_system(&system_id)
+        .ok_or_else(|| ContainerError::SystemNotFound(system_id.clone()))?;
+    Ok(detect_available_shells(&sessions, &system, container_id.as_deref()).await?)
+}
+
 /// Build the command to run in the terminal
 fn build_terminal_command(
     container_id: &Option<String>,
     shell: &str,
     runtime: &ContainerRuntime,
 ) -> Option<String> {
-    container_id.as_ref().map(|cid| {
-        let runtime_cmd = match runtime {
-            ContainerRuntime::Docker => "docker",
-            ContainerRuntime::Podman => "podman",
-            ContainerRuntime::Apple => "container",
-        };
-        format!("{} exec -it {} {}", runtime_cmd, cid, shell)
-    })
+    container_id
+        .as_ref()
+        .map(|cid| format!("{} exec -it {} {}", runtime_binary(*runtime), cid, shell))
+}
+
+/// CLI binary of a container runtime
+pub(crate) fn runtime_binary(runtime: ContainerRuntime) -> &'static str {
+    match runtime {
+        ContainerRuntime::Docker => "docker",
+        ContainerRuntime::Podman => "podman",
+        ContainerRuntime::Apple => "container",
+        ContainerRuntime::Nerdctl => "nerdctl",
+    }
 }
 
 /// Start a local PTY session (desktop only - not available on Android)
@@ -270,6 +931,7 @@ async fn start_local_session(
                             "data": &data
                         }),
                     );
+                    sessions_for_notify.capture_output(&sid, &data);
                     // Notify any registered output listeners
                     let sid_clone = sid.clone();
                     let sessions_clone = sessions_for_notify.clone();
@@ -280,6 +942,10 @@ async fn start_local_session(
                 Err(_) => break,
             }
         }
+        sessions_for_notify.finish_recording(&sid);
+        rt.spawn(async move {
+            exit_agent_container(&app, &sessions_for_notify, &sid).await;
+        });
     });
 
     Ok(())
@@ -301,6 +967,8 @@ async fn start_ssh_session(
     // Reuse existing SSH connection from pool and open a PTY channel
     let pool = ssh::get_pool();
     let pool_guard = pool.read().await;
+    // Held by the I/O task so the pool never evicts the connection under this terminal
+    let lease = pool_guard.lease(system_id);
 
     // Get the client and open a PTY channel
     let client = pool_guard
@@ -336,7 +1004,9 @@ async fn start_ssh_session(
                             }
                         }
                         TerminalInput::Resize { cols, rows } => {
-                            let _ = channel.window_change(cols as u32, rows as u32, 0, 0).await;
+                            if let Err(e) = channel.window_change(cols as u32, rows as u32, 0, 0).await {
+                                tracing::warn!("Failed to resize terminal {}: {}", sid, e);
+                            }
                         }
                         TerminalInput::Close => {
                             let _ = channel.close().await;
@@ -359,6 +1029,8 @@ async fn start_ssh_session(
                                 }),
                             );
 
+                            sessions_clone.capture_output(&sid, &output);
+
                             // Notify any registered output listeners (for AI agent)
                             sessions_clone.notify_output(&sid, &output).await;
                         }
@@ -370,6 +1042,9 @@ async fn start_ssh_session(
         }
         // Clean up session when done
         sessions_clone.sessions.lock().await.remove(&sid);
+        sessions_clone.finish_recording(&sid);
+        exit_agent_container(&app, &sessions_clone, &sid).await;
+        drop(lease);
     });
 
     Ok(())
@@ -381,7 +1056,9 @@ pub async fn send_terminal_input(
     sessions: State<'_, TerminalSessions>,
     session_id: String,
     data: String,
-) -> Result<(), ContainerError> {
+) -> Result<(), AppError> {
+    sessions.record_input(&session_id, &data);
+    sessions.touch(&session_id);
     let mut sessions_guard = sessions.sessions.lock().await;
 
     match sessions_guard.get_mut(&session_id) {
@@ -400,39 +1077,39 @@ pub async fn send_terminal_input(
                 .await
                 .map_err(|e| ContainerError::Internal(e.to_string()))?;
         }
-        None => return Err(ContainerError::Internal("Session not found".to_string())),
+        None => return Err(ContainerError::Internal("Session not found".to_string()).into()),
     }
 
     Ok(())
 }
 
-/// Resize a terminal session
+/// Resize a terminal session. A burst of resizes (dragging a window edge) is
+/// debounced so only the last size reaches the PTY or SSH channel.
 #[tauri::command]
 pub async fn resize_terminal(
     sessions: State<'_, TerminalSessions>,
     session_id: String,
     cols: u16,
     rows: u16,
-) -> Result<(), ContainerError> {
-    let mut sessions_guard = sessions.sessions.lock().await;
+) -> Result<(), AppError> {
+    if cols == 0 || rows == 0 {
+        return Ok(());
+    }
+    if !sessions.queue_resize(&session_id, cols, rows) {
+        // A resize is already scheduled and will pick up this size
+        return Ok(());
+    }
 
-    match sessions_guard.get_mut(&session_id) {
-        #[cfg(not(target_os = "android"))]
-        Some(SessionHandle::Local { master, .. }) => {
-            master
-                .resize(PtySize {
-                    rows,
-                    cols,
-                    pixel_width: 0,
-                    pixel_height: 0,
-                })
-                .map_err(|e| ContainerError::Internal(e.to_string()))?;
-        }
-        Some(SessionHandle::Ssh { input_tx }) => {
-            let _ = input_tx.send(TerminalInput::Resize { cols, rows }).await;
+    let sessions = sessions.inner().clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(RESIZE_DEBOUNCE).await;
+        let Some((cols, rows)) = sessions.take_resize(&session_id) else {
+            return;
+        };
+        if let Err(e) = sessions.apply_resize(&session_id, cols, rows).await {
+            tracing::warn!("Failed to resize terminal {}: {}", session_id, e);
         }
-        None => {}
-    }
+    });
 
     Ok(())
 }
@@ -442,21 +1119,141 @@ pub async fn resize_terminal(
 pub async fn close_terminal_session(
     sessions: State<'_, TerminalSessions>,
     session_id: String,
-) -> Result<(), ContainerError> {
-    let mut sessions_guard = sessions.sessions.lock().await;
+) -> Result<(), AppError> {
+    sessions.close(&session_id).await;
+    Ok(())
+}
 
-    if let Some(handle) = sessions_guard.remove(&session_id) {
-        match handle {
-            SessionHandle::Ssh { input_tx } => {
-                let _ = input_tx.send(TerminalInput::Close).await;
-            }
-            #[cfg(not(target_os = "android"))]
-            SessionHandle::Local { .. } => {
-                // Local sessions are cleaned up when dropped
-            }
-        }
+/// Get the last `max_lines` lines of a session's output (all kept lines when omitted),
+/// with ANSI escapes removed when `strip_ansi` is set
+#[tauri::command]
+pub async fn get_terminal_scrollback(
+    sessions: State<'_, TerminalSessions>,
+    session_id: String,
+    max_lines: Option<usize>,
+    strip_ansi: Option<bool>,
+) -> Result<String, AppError> {
+    Ok(sessions
+        .scrollback_text(&session_id, max_lines, strip_ansi.unwrap_or(false))
+        .unwrap_or_default())
+}
+
+/// Write a session's whole scrollback buffer to `path`
+#[tauri::command]
+pub async fn export_terminal_scrollback(
+    sessions: State<'_, TerminalSessions>,
+    session_id: String,
+    path: String,
+    strip_ansi: Option<bool>,
+) -> Result<(), AppError> {
+    let Some(mut text) = sessions.scrollback_text(&session_id, None, strip_ansi.unwrap_or(false))
+    else {
+        return Err(ContainerError::NotFound {
+            resource: "Terminal output".to_string(),
+            id: session_id,
+        }.into());
+    };
+    text.push('\n');
+
+    Ok(std::fs::write(&path, text)
+        .map_err(|e| ContainerError::Internal(format!("Failed to export scrollback: {}", e)))?)
+}
+
+/// Start recording a terminal session to an asciicast v2 file in the app data
+/// directory. `cols`/`rows` describe the terminal at the start of the recording.
+/// Returns the path of the recording file.
+#[tauri::command]
+pub async fn start_terminal_recording(
+    app: AppHandle,
+    sessions: State<'_, TerminalSessions>,
+    session_id: String,
+    cols: Option<u16>,
+    rows: Option<u16>,
+    record_input: Option<bool>,
+) -> Result<String, AppError> {
+    if !sessions.sessions.lock().await.contains_key(&session_id) {
+        return Err(ContainerError::Internal("Session not found".to_string()).into());
+    }
+    if sessions
+        .recordings
+        .lock()
+        .unwrap()
+        .get(&session_id)
+        .is_some_and(|r| r.is_active())
+    {
+        return Err(ContainerError::InvalidOperation {
+            message: "This session is already being recorded".to_string(),
+        }.into());
     }
 
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| ContainerError::Internal(e.to_string()))?
+        .join("recordings");
+    std::fs::create_dir_all(&dir).map_err(|e| {
+        ContainerError::Internal(format!("Failed to create recordings directory: {}", e))
+    })?;
+    let path = dir.join(format!(
+        "{}-{}.cast",
+        session_id,
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ));
+
+    let (cols, rows) = terminal_size(cols, rows);
+    let recording = TerminalRecording::create(
+        path.clone(),
+        cols,
+        rows,
+        record_input.unwrap_or(false),
+    )
+    .map_err(|e| ContainerError::Internal(format!("Failed to start recording: {}", e)))?;
+    sessions
+        .recordings
+        .lock()
+        .unwrap()
+        .insert(session_id, recording);
+
+    Ok(path.to_string_lossy().into_owned())
+}
+
+/// Stop recording a terminal session, returning the recording's file path
+#[tauri::command]
+pub async fn stop_terminal_recording(
+    sessions: State<'_, TerminalSessions>,
+    session_id: String,
+) -> Result<String, AppError> {
+    Ok(sessions
+        .finish_recording(&session_id)
+        .map(|path| path.to_string_lossy().into_owned())
+        .ok_or_else(|| ContainerError::InvalidOperation {
+            message: "This session is not being recorded".to_string(),
+        })?)
+}
+
+/// Copy a session's recording (running or stopped) to `path`
+#[tauri::command]
+pub async fn export_terminal_recording(
+    sessions: State<'_, TerminalSessions>,
+    session_id: String,
+    path: String,
+) -> Result<(), AppError> {
+    let source = {
+        let mut recordings = sessions.recordings.lock().unwrap();
+        let Some(recording) = recordings.get_mut(&session_id) else {
+            return Err(ContainerError::NotFound {
+                resource: "Recording".to_string(),
+                id: session_id,
+            }.into());
+        };
+        recording
+            .flush()
+            .map_err(|e| ContainerError::Internal(format!("Failed to flush recording: {}", e)))?;
+        recording.path.clone()
+    };
+
+    std::fs::copy(&source, &path)
+        .map_err(|e| ContainerError::Internal(format!("Failed to export recording: {}", e)))?;
     Ok(())
 }
 
@@ -467,11 +1264,12 @@ pub async fn execute_in_terminal(
     sessions: State<'_, TerminalSessions>,
     session_id: String,
     command: String,
-) -> Result<(), ContainerError> {
+) -> Result<(), AppError> {
     let mut sessions_guard = sessions.sessions.lock().await;
 
     // Append newline to execute the command
     let command_with_newline = format!("{}\n", command);
+    sessions.touch(&session_id);
 
     match sessions_guard.get_mut(&session_id) {
         #[cfg(not(target_os = "android"))]
@@ -489,7 +1287,7 @@ pub async fn execute_in_terminal(
                 .await
                 .map_err(|e| ContainerError::Internal(e.to_string()))?;
         }
-        None => return Err(ContainerError::Internal(format!("Session not found: {}", session_id))),
+        None => return Err(ContainerError::Internal(format!("Session not found: {}", session_id)).into()),
     }
 
     Ok(())
@@ -499,58 +1297,609 @@ pub async fn execute_in_terminal(
 #[tauri::command]
 pub async fn list_terminal_sessions(
     sessions: State<'_, TerminalSessions>,
-) -> Result<Vec<String>, ContainerError> {
+) -> Result<Vec<String>, AppError> {
     let sessions_guard = sessions.sessions.lock().await;
     Ok(sessions_guard.keys().cloned().collect())
 }
 
-/// Fetch shell history from a remote system
-/// Tries .bash_history and .zsh_history, returns the first found
-/// Optionally filters using grep -i on the remote system
+/// Event emitted when the backend closes a session on its own
+pub const TERMINAL_CLOSED_EVENT: &str = "terminal:closed";
+
+/// Minutes a terminal may sit idle before it is closed, unless app settings say otherwise
+pub const DEFAULT_TERMINAL_IDLE_TIMEOUT_MINS: u32 = 30;
+
+/// How often the reaper looks for idle sessions
+const IDLE_REAP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// An open terminal session and how long it has been idle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TerminalSessionStats {
+    pub session_id: String,
+    pub system_id: Option<String>,
+    pub container_id: Option<String>,
+    pub idle_secs: u64,
+    /// An agent session runs through this terminal, so the reaper leaves it open
+    pub agent_attached: bool,
+}
+
+/// Sessions whose last activity is more than `timeout` before `now`
+fn idle_beyond(
+    last_activity: &HashMap<String, Instant>,
+    now: Instant,
+    timeout: Duration,
+) -> Vec<String> {
+    last_activity
+        .iter()
+        .filter(|(_, last)| now.saturating_duration_since(**last) > timeout)
+        .map(|(id, _)| id.clone())
+        .collect()
+}
+
+/// Whether an agent session is attached to the terminal
+async fn agent_attached(app: &AppHandle, session_id: &str) -> bool {
+    match app.try_state::<AgentSessionManager>() {
+        Some(agents) => agents.get_session_by_terminal(session_id).await.is_some(),
+        None => false,
+    }
+}
+
+/// Idle timeout from app settings, or None when idle sessions are kept open
+fn idle_timeout(app: &AppHandle) -> Option<Duration> {
+    let state = app.state::<AppState>();
+    let mins = {
+        let conn = state.db.lock().unwrap();
+        crate::database::get_app_settings(&conn)
+            .ok()
+            .and_then(|s| s.terminal_idle_timeout_mins)
+    }
+    .unwrap_or(DEFAULT_TERMINAL_IDLE_TIMEOUT_MINS);
+    (mins > 0).then(|| Duration::from_secs(u64::from(mins) * 60))
+}
+
+/// Close sessions left idle past the configured timeout, so terminals the
+/// frontend lost track of (crash, reload) don't keep their processes alive.
+/// Sessions an agent is attached to are kept.
+pub fn spawn_idle_reaper(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(IDLE_REAP_INTERVAL);
+        loop {
+            interval.tick().await;
+            let Some(timeout) = idle_timeout(&app) else {
+                continue;
+            };
+
+            let sessions = app.state::<TerminalSessions>();
+            for session_id in sessions.idle_sessions(timeout) {
+                if agent_attached(&app, &session_id).await {
+                    continue;
+                }
+                tracing::info!(
+                    "Closing terminal {} after {} minutes idle",
+                    session_id,
+                    timeout.as_secs() / 60
+                );
+                sessions.close(&session_id).await;
+                if let Err(e) = app.emit(
+                    TERMINAL_CLOSED_EVENT,
+                    serde_json::json!({ "sessionId": session_id, "reason": "idle" }),
+                ) {
+                    tracing::warn!("Failed to emit close of terminal {}: {}", session_id, e);
+                }
+            }
+        }
+    });
+}
+
+/// Open terminal sessions with their idle time, longest idle first
+#[tauri::command]
+pub async fn terminal_session_stats(
+    app: AppHandle,
+    sessions: State<'_, TerminalSessions>,
+) -> Result<Vec<TerminalSessionStats>, AppError> {
+    let ids: Vec<String> = sessions.sessions.lock().await.keys().cloned().collect();
+
+    let mut stats = Vec::with_capacity(ids.len());
+    for session_id in ids {
+        stats.push(TerminalSessionStats {
+            system_id: sessions.system_id(&session_id),
+            container_id: sessions.container(&session_id).map(|c| c.container_id),
+            idle_secs: sessions
+                .idle_time(&session_id)
+                .map_or(0, |idle| idle.as_secs()),
+            agent_attached: agent_attached(&app, &session_id).await,
+            session_id,
+        });
+    }
+    stats.sort_by_key(|s| std::cmp::Reverse(s.idle_secs));
+    Ok(stats)
+}
+
+/// Number of trailing history-file lines read from the remote host
+const HISTORY_TAIL_LINES: usize = 50_000;
+
+/// Marker line emitted before the history file contents, naming its format
+const HISTORY_MARKER: &str = "__HISTORY__:";
+
+/// On-disk history formats understood by the history fetcher
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HistoryFormat {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl HistoryFormat {
+    fn from_marker(name: &str) -> Self {
+        match name.trim() {
+            "zsh" => Self::Zsh,
+            "fish" => Self::Fish,
+            _ => Self::Bash,
+        }
+    }
+}
+
+/// Build the remote script that picks the history file for the user's login
+/// shell (falling back to the other shells' files) and prints it behind a
+/// format marker. Runs under `sh` so it works when the login shell is fish.
+fn history_fetch_command() -> String {
+    let script = format!(
+        r#"FISH="${{XDG_DATA_HOME:-$HOME/.local/share}}/fish/fish_history"
+case "$(basename "${{SHELL:-sh}}")" in
+  zsh) set -- "zsh:$HOME/.zsh_history" "bash:$HOME/.bash_history" "fish:$FISH" ;;
+  fish) set -- "fish:$FISH" "zsh:$HOME/.zsh_history" "bash:$HOME/.bash_history" ;;
+  *) set -- "bash:$HOME/.bash_history" "zsh:$HOME/.zsh_history" "fish:$FISH" ;;
+esac
+for entry in "$@"; do
+  f="${{entry#*:}}"
+  if [ -s "$f" ]; then echo "{marker}${{entry%%:*}}"; tail -n {lines} "$f"; exit 0; fi
+done"#,
+        marker = HISTORY_MARKER,
+        lines = HISTORY_TAIL_LINES,
+    );
+    format!("sh -c '{}'", script)
+}
+
+/// Parse bash history. Timestamps come from `#<epoch>` comment lines, which
+/// bash writes when `HISTTIMEFORMAT` is set.
+fn parse_bash_history(content: &str) -> Vec<ShellHistoryEntry> {
+    let mut entries = Vec::new();
+    let mut timestamp = None;
+    for line in content.lines() {
+        if let Some(ts) = line.strip_prefix('#').and_then(|t| t.parse::<i64>().ok()) {
+            timestamp = Some(ts);
+        } else if !line.trim().is_empty() {
+            entries.push(ShellHistoryEntry {
+                command: line.to_string(),
+                timestamp: timestamp.take(),
+            });
+        }
+    }
+    entries
+}
+
+/// Parse zsh history, both plain and `EXTENDED_HISTORY` (`: <start>:<elapsed>;<cmd>`).
+/// Multi-line commands are stored with each embedded newline escaped by a
+/// trailing backslash.
+fn parse_zsh_history(content: &str) -> Vec<ShellHistoryEntry> {
+    let mut entries = Vec::new();
+    let mut lines = content.lines();
+    while let Some(line) = lines.next() {
+        let (timestamp, first) = match line
+            .strip_prefix(": ")
+            .and_then(|rest| rest.split_once(';'))
+            .and_then(|(meta, cmd)| Some((meta.split_once(':')?.0.parse::<i64>().ok()?, cmd)))
+        {
+            Some((ts, cmd)) => (Some(ts), cmd),
+            None => (None, line),
+        };
+
+        let mut command = first.to_string();
+        while command.ends_with('\\') {
+            command.pop();
+            command.push('\n');
+            match lines.next() {
+                Some(next) => command.push_str(next),
+                None => break,
+            }
+        }
+
+        let command = command.trim_end().to_string();
+        if !command.trim().is_empty() {
+            entries.push(ShellHistoryEntry { command, timestamp });
+        }
+    }
+    entries
+}
+
+/// Undo fish's escaping of backslashes and newlines in history commands
+fn unescape_fish(command: &str) -> String {
+    let mut out = String::with_capacity(command.len());
+    let mut chars = command.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('\\') => out.push('\\'),
+            Some(other) => {
+                out.push('\\');
+                out.push(other);
+            }
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+/// Parse fish's YAML-like history (`- cmd: ...` followed by `  when: <epoch>`)
+fn parse_fish_history(content: &str) -> Vec<ShellHistoryEntry> {
+    let mut entries: Vec<ShellHistoryEntry> = Vec::new();
+    let mut in_entry = false;
+    for line in content.lines() {
+        if let Some(cmd) = line.strip_prefix("- cmd: ") {
+            entries.push(ShellHistoryEntry {
+                command: unescape_fish(cmd),
+                timestamp: None,
+            });
+            in_entry = true;
+        } else if let Some(when) = line.strip_prefix("  when: ") {
+            if let Some(entry) = entries.last_mut().filter(|_| in_entry) {
+                entry.timestamp = when.trim().parse().ok();
+            }
+        } else if !line.starts_with(' ') {
+            in_entry = false;
+        }
+    }
+    entries.retain(|e| !e.command.trim().is_empty());
+    entries
+}
+
+/// Parse the output of [`history_fetch_command`] into history entries,
+/// newest first. Consecutive duplicates are collapsed after filtering.
+fn parse_shell_history(output: &str, filter: Option<&str>, limit: usize) -> Vec<ShellHistoryEntry> {
+    let Some((marker, content)) = output
+        .split_once('\n')
+        .and_then(|(first, rest)| Some((first.strip_prefix(HISTORY_MARKER)?, rest)))
+    else {
+        return Vec::new();
+    };
+
+    let entries = match HistoryFormat::from_marker(marker) {
+        HistoryFormat::Bash => parse_bash_history(content),
+        HistoryFormat::Zsh => parse_zsh_history(content),
+        HistoryFormat::Fish => parse_fish_history(content),
+    };
+
+    let filter = filter
+        .map(|f| f.trim().to_lowercase())
+        .filter(|f| !f.is_empty());
+    let mut result: Vec<ShellHistoryEntry> = Vec::new();
+    for entry in entries.into_iter().rev() {
+        if let Some(filter) = &filter {
+            if !entry.command.to_lowercase().contains(filter) {
+                continue;
+            }
+        }
+        if result
+            .last()
+            .is_some_and(|prev| prev.command == entry.command)
+        {
+            continue;
+        }
+        if result.len() >= limit {
+            break;
+        }
+        result.push(entry);
+    }
+    result
+}
+
+/// Fetch shell history from a remote system.
+/// Detects the login shell and parses its bash, zsh or fish history file,
+/// returning at most `max_entries` commands newest first. `filter` is a
+/// case-insensitive substring match on the command.
 #[tauri::command]
 pub async fn fetch_shell_history(
     system_id: String,
     max_entries: Option<u32>,
     filter: Option<String>,
-) -> Result<Vec<String>, ContainerError> {
-    let limit = max_entries.unwrap_or(500);
+) -> Result<Vec<ShellHistoryEntry>, AppError> {
+    let limit = max_entries.unwrap_or(500) as usize;
 
-    // Build grep filter if provided (escape single quotes for shell safety)
-    let grep_filter = filter
-        .as_ref()
-        .filter(|f| !f.trim().is_empty())
-        .map(|f| format!(" | grep -i '{}'", f.replace('\'', "'\\''")))
-        .unwrap_or_default();
-
-    // Try common history files in order
-    let history_commands = vec![
-        // bash history - simple format, one command per line
-        // When filtering: cat | grep | tail (filter first, then limit)
-        format!(
-            "[ -f ~/.bash_history ] && cat ~/.bash_history{} | tail -{}",
-            grep_filter, limit
-        ),
-        // zsh history - may have timestamp format `: 1234567890:0;command`
-        // Strip timestamps first with sed, then filter, then limit
-        format!(
-            "[ -f ~/.zsh_history ] && cat ~/.zsh_history | sed 's/^: [0-9]*:[0-9]*;//'{} | tail -{}",
-            grep_filter, limit
-        ),
-    ];
-
-    for cmd in history_commands {
-        if let Ok(result) = crate::ssh::execute_on_system(&system_id, &cmd).await {
-            if result.success() && !result.stdout.trim().is_empty() {
-                return Ok(result
-                    .stdout
-                    .lines()
-                    .filter(|s| !s.trim().is_empty())
-                    .map(|s| s.to_string())
-                    .collect());
-            }
+    let result = crate::ssh::execute_on_system(&system_id, &history_fetch_command()).await?;
+    if !result.success() {
+        // No history file found - return empty (not an error)
+        return Ok(vec![]);
+    }
+
+    Ok(parse_shell_history(
+        &result.stdout,
+        filter.as_deref(),
+        limit,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_detected_shells_orders_by_preference() {
+        let output = "/bin/sh\n/usr/bin/zsh\n/bin/bash\n/usr/bin/bash\nalias fish='fish -l'\n";
+        assert_eq!(
+            parse_detected_shells(output),
+            vec!["/bin/bash", "/usr/bin/zsh", "/bin/sh"]
+        );
+        assert!(parse_detected_shells("").is_empty());
+    }
+
+    #[test]
+    fn test_pick_shell_falls_back_to_installed() {
+        let alpine = vec!["/bin/ash".to_string(), "/bin/sh".to_string()];
+        assert_eq!(pick_shell(&alpine, Some("/bin/bash")), "/bin/ash");
+        assert_eq!(pick_shell(&alpine, Some("sh")), "/bin/sh");
+        assert_eq!(pick_shell(&alpine, None), "/bin/ash");
+        assert_eq!(pick_shell(&[], Some("/bin/bash")), "/bin/bash");
+        assert_eq!(pick_shell(&[], None), "/bin/sh");
+    }
+
+    #[test]
+    fn test_build_terminal_command() {
+        assert_eq!(
+            build_terminal_command(
+                &Some("c1".to_string()),
+                "/bin/ash",
+                &ContainerRuntime::Apple
+            )
+            .as_deref(),
+            Some("container exec -it c1 /bin/ash")
+        );
+        assert_eq!(
+            build_terminal_command(&None, "/bin/sh", &ContainerRuntime::Docker),
+            None
+        );
+    }
+
+    #[test]
+    fn test_strip_ansi() {
+        assert_eq!(
+            strip_ansi("\x1b[1;32muser@host\x1b[0m:\x1b[34m~\x1b[0m$ ls"),
+            "user@host:~$ ls"
+        );
+        assert_eq!(strip_ansi("\x1b]0;title\x07\x1b(Bdone\x1b[?2004h"), "done");
+    }
+
+    #[test]
+    fn test_scrollback_joins_chunks_and_caps_lines() {
+        let sessions = TerminalSessions::default();
+        sessions.capture_output("s1", "\x1b[31mfirst\x1b[0m li");
+        sessions.capture_output("s1", "ne\r\nsecond\r\nthi");
+
+        assert_eq!(
+            sessions.scrollback_text("s1", None, true).unwrap(),
+            "first line\nsecond\nthi"
+        );
+        assert_eq!(
+            sessions.scrollback_text("s1", Some(1), false).unwrap(),
+            "thi"
+        );
+        assert!(sessions
+            .scrollback_text("s1", None, false)
+            .unwrap()
+            .starts_with("\x1b[31mfirst"));
+        assert!(sessions.scrollback_text("other", None, false).is_none());
+
+        let mut scrollback = Scrollback::default();
+        for i in 0..SCROLLBACK_MAX_LINES + 5 {
+            scrollback.push(&format!("{}\n", i));
+        }
+        assert_eq!(scrollback.lines.len(), SCROLLBACK_MAX_LINES);
+        assert_eq!(scrollback.lines.front().map(String::as_str), Some("5"));
+
+        // Output without newlines is broken into bounded lines
+        scrollback.push(&"x".repeat(SCROLLBACK_MAX_LINE_BYTES + 1));
+        assert!(scrollback.partial.is_empty());
+    }
+
+    #[test]
+    fn test_recording_writes_asciicast_v2() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.cast");
+        let sessions = TerminalSessions::default();
+        let recording = TerminalRecording::create(path.clone(), 120, 40, false).unwrap();
+        sessions
+            .recordings
+            .lock()
+            .unwrap()
+            .insert("s1".to_string(), recording);
+
+        sessions.record_output("s1", "$ ls\r\n");
+        sessions.record_input("s1", "secret\r");
+        sessions.record_resize("s1", 100, 30);
+        sessions.record_output("other", "not recorded");
+        assert_eq!(sessions.finish_recording("s1"), Some(path.clone()));
+        // Events after stopping are dropped
+        sessions.record_output("s1", "late");
+        assert_eq!(sessions.finish_recording("s1"), None);
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<serde_json::Value> = contents
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0]["version"], 2);
+        assert_eq!(lines[0]["width"], 120);
+        assert_eq!(lines[0]["height"], 40);
+        assert_eq!(lines[1][1], "o");
+        assert_eq!(lines[1][2], "$ ls\r\n");
+        assert!(lines[1][0].as_f64().unwrap() >= 0.0);
+        assert_eq!(lines[2][1], "r");
+        assert_eq!(lines[2][2], "100x30");
+    }
+
+    #[test]
+    fn test_recording_includes_input_when_enabled() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.cast");
+        let sessions = TerminalSessions::default();
+        let recording = TerminalRecording::create(path.clone(), 80, 24, true).unwrap();
+        sessions
+            .recordings
+            .lock()
+            .unwrap()
+            .insert("s1".to_string(), recording);
+
+        sessions.record_input("s1", "ls\r");
+        sessions.finish_recording("s1");
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(contents.lines().nth(1).unwrap().contains(r#""i","ls\r""#));
+    }
+
+    fn entry(command: &str, timestamp: Option<i64>) -> ShellHistoryEntry {
+        ShellHistoryEntry {
+            command: command.to_string(),
+            timestamp,
         }
     }
 
-    // No history found - return empty (not an error)
-    Ok(vec![])
+    #[test]
+    fn test_parse_zsh_extended_history() {
+        let output = r#"__HISTORY__:zsh
+: 1700000000:0;git status
+: 1700000005:2;docker compose up -d
+: 1700000009:0;for f in *.log; do\
+  gzip $f\
+done
+: 1700000012:0;docker compose up -d
+: 1700000013:0;docker compose up -d
+ls -la
+"#;
+
+        let entries = parse_shell_history(output, None, 500);
+        assert_eq!(
+            entries,
+            vec![
+                entry("ls -la", None),
+                entry("docker compose up -d", Some(1700000013)),
+                entry("for f in *.log; do\n  gzip $f\ndone", Some(1700000009)),
+                entry("docker compose up -d", Some(1700000005)),
+                entry("git status", Some(1700000000)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_fish_history() {
+        let output = r#"__HISTORY__:fish
+- cmd: cd ~/projects
+  when: 1699990000
+- cmd: vim src/main.rs
+  when: 1699990042
+  paths:
+    - src/main.rs
+- cmd: echo "a\\b"\necho done
+  when: 1699990100
+- cmd: vim src/main.rs
+  when: 1699990200
+  paths:
+    - src/main.rs
+"#;
+
+        let entries = parse_shell_history(output, None, 500);
+        assert_eq!(
+            entries,
+            vec![
+                entry("vim src/main.rs", Some(1699990200)),
+                entry("echo \"a\\b\"\necho done", Some(1699990100)),
+                entry("vim src/main.rs", Some(1699990042)),
+                entry("cd ~/projects", Some(1699990000)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_bash_history_with_timestamps() {
+        let output = "__HISTORY__:bash\nuptime\n#1700000100\nls\n#1700000200\nls\n\n";
+        assert_eq!(
+            parse_shell_history(output, None, 500),
+            vec![entry("ls", Some(1700000200)), entry("uptime", None)]
+        );
+    }
+
+    #[test]
+    fn test_shell_history_filter_and_limit() {
+        let output = "__HISTORY__:bash\ndocker ps\nls\nDocker images\nls\ndocker logs web\n";
+        assert_eq!(
+            parse_shell_history(output, Some("DOCKER"), 2),
+            vec![entry("docker logs web", None), entry("Docker images", None)]
+        );
+        // Collapsing happens after filtering, so duplicates separated only by
+        // non-matching commands are merged
+        let output = "__HISTORY__:bash\ngit pull\nls\ngit pull\n";
+        assert_eq!(
+            parse_shell_history(output, Some("git"), 10),
+            vec![entry("git pull", None)]
+        );
+        assert!(parse_shell_history("", None, 10).is_empty());
+    }
+
+    #[test]
+    fn test_idle_beyond_timeout() {
+        let start = Instant::now();
+        let timeout = Duration::from_secs(30 * 60);
+        let now = start + Duration::from_secs(31 * 60);
+        let mut activity = HashMap::new();
+        activity.insert("stale".to_string(), start);
+        activity.insert("exactly".to_string(), now - timeout);
+        activity.insert("fresh".to_string(), now - Duration::from_secs(60));
+
+        assert_eq!(idle_beyond(&activity, now, timeout), vec!["stale".to_string()]);
+        assert!(idle_beyond(&HashMap::new(), now, timeout).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_close_forgets_session_activity() {
+        let sessions = TerminalSessions::default();
+        sessions.touch("term-1");
+        sessions.capture_output("term-1", "hello\n");
+        assert!(sessions.idle_time("term-1").is_some());
+
+        sessions.close("term-1").await;
+        assert!(sessions.idle_time("term-1").is_none());
+        assert!(sessions.scrollback_text("term-1", None, false).is_none());
+        assert!(sessions.idle_sessions(Duration::ZERO).is_empty());
+    }
+
+    #[test]
+    fn test_terminal_size_defaults() {
+        assert_eq!(terminal_size(Some(132), Some(43)), (132, 43));
+        assert_eq!(terminal_size(None, None), (DEFAULT_COLS, DEFAULT_ROWS));
+        assert_eq!(terminal_size(Some(0), Some(0)), (DEFAULT_COLS, DEFAULT_ROWS));
+    }
+
+    #[test]
+    fn test_resize_burst_keeps_last_size() {
+        let sessions = TerminalSessions::default();
+        assert!(sessions.queue_resize("term-1", 100, 30));
+        assert!(!sessions.queue_resize("term-1", 110, 32));
+        assert!(!sessions.queue_resize("term-1", 120, 40));
+        assert!(sessions.queue_resize("term-2", 80, 24));
+
+        assert_eq!(sessions.take_resize("term-1"), Some((120, 40)));
+        assert_eq!(sessions.take_resize("term-1"), None);
+        // The next resize after the burst is applied schedules a new one
+        assert!(sessions.queue_resize("term-1", 90, 25));
+    }
+
+    #[tokio::test]
+    async fn test_resize_of_unknown_session_is_ignored() {
+        let sessions = TerminalSessions::default();
+        assert!(sessions.apply_resize("missing", 100, 30).await.is_ok());
+        assert!(sessions.idle_time("missing").is_none());
+    }
 }
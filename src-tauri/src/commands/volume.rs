@@ -1,11 +1,13 @@
+use std::collections::HashMap;
+
 use tauri::State;
 
 use crate::executor::local::LocalExecutor;
 use crate::executor::CommandExecutor;
 use crate::models::container::ContainerRuntime;
-use crate::models::error::ContainerError;
+use crate::models::error::{AppError, ContainerError};
 use crate::models::system::ConnectionType;
-use crate::models::volume::Volume;
+use crate::models::volume::{Volume, VolumeCreateOptions};
 use crate::runtime::{CommandBuilder, OutputParser};
 use crate::state::AppState;
 
@@ -14,7 +16,7 @@ use crate::state::AppState;
 pub async fn list_volumes(
     state: State<'_, AppState>,
     system_id: String,
-) -> Result<Vec<Volume>, ContainerError> {
+) -> Result<Vec<Volume>, AppError> {
     let system = state
         .get_system(&system_id)
         .ok_or_else(|| ContainerError::SystemNotFound(system_id.clone()))?;
@@ -54,12 +56,25 @@ pub async fn create_volume(
     system_id: String,
     name: String,
     runtime: ContainerRuntime,
-) -> Result<(), ContainerError> {
+    driver: Option<String>,
+    driver_opts: Option<HashMap<String, String>>,
+    labels: Option<HashMap<String, String>>,
+) -> Result<(), AppError> {
     let system = state
         .get_system(&system_id)
         .ok_or_else(|| ContainerError::SystemNotFound(system_id.clone()))?;
 
-    let command = CommandBuilder::create_volume(runtime, &name);
+    let options = VolumeCreateOptions {
+        driver,
+        driver_opts: driver_opts.unwrap_or_default(),
+        labels: labels.unwrap_or_default(),
+    };
+    options.validate()?;
+    let command = CommandBuilder::create_volume(runtime, &name, &options).ok_or_else(|| {
+        ContainerError::UnsupportedOperation(
+            "Apple Container volumes don't support drivers, driver options or labels".to_string(),
+        )
+    })?;
 
     let result = match system.connection_type {
         ConnectionType::Local => {
@@ -74,7 +89,7 @@ pub async fn create_volume(
             command,
             exit_code: result.exit_code,
             stderr: result.stderr,
-        });
+        }.into());
     }
 
     tracing::info!("Created volume {} on system {}", name, system_id);
@@ -89,7 +104,7 @@ pub async fn remove_volume(
     name: String,
     runtime: ContainerRuntime,
     force: bool,
-) -> Result<(), ContainerError> {
+) -> Result<(), AppError> {
     let system = state
         .get_system(&system_id)
         .ok_or_else(|| ContainerError::SystemNotFound(system_id.clone()))?;
@@ -109,7 +124,7 @@ pub async fn remove_volume(
             command,
             exit_code: result.exit_code,
             stderr: result.stderr,
-        });
+        }.into());
     }
 
     tracing::info!("Removed volume {} on system {}", name, system_id);
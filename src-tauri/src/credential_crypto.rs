@@ -0,0 +1,197 @@
+//! Encryption for credentials kept in the database.
+//!
+//! SSH secrets that live in SQLite (on Android, where there is no desktop
+//! keyring vault) are sealed with AES-256-GCM under an app-level key that is
+//! kept in the OS keychain, never in the database. Each value gets a fresh
+//! random nonce and is bound to its row, so a copied database is useless
+//! without the keychain entry and tampered or swapped values fail to decrypt.
+
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::Engine;
+use rand::RngCore;
+
+/// Keychain entry holding the key on platforms without the desktop vault
+pub const KEYCHAIN_KEY_NAME: &str = "containerus.credential-key";
+
+/// Marks values written by this module, as opposed to the old XOR obfuscation
+const ENCRYPTED_PREFIX: &str = "v2:";
+
+const KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+
+/// AES-256-GCM cipher for database credential columns
+#[derive(Clone)]
+pub struct CredentialCipher {
+    key: [u8; KEY_LEN],
+}
+
+impl std::fmt::Debug for CredentialCipher {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("CredentialCipher").field("key", &"[REDACTED]").finish()
+    }
+}
+
+impl CredentialCipher {
+    /// Generate a new random key, base64-encoded for storing in the keychain
+    pub fn generate_key() -> String {
+        let mut key = [0u8; KEY_LEN];
+        rand::rngs::OsRng.fill_bytes(&mut key);
+        base64::engine::general_purpose::STANDARD.encode(key)
+    }
+
+    /// Build a cipher from a base64 key as stored in the keychain
+    pub fn from_key(encoded: &str) -> Result<Self, String> {
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(encoded.trim())
+            .map_err(|e| format!("invalid credential key: {}", e))?;
+        let key: [u8; KEY_LEN] = bytes
+            .try_into()
+            .map_err(|_| format!("credential key must be {} bytes", KEY_LEN))?;
+        Ok(Self { key })
+    }
+
+    /// The key, base64-encoded for storing in the keychain
+    pub fn key(&self) -> String {
+        base64::engine::general_purpose::STANDARD.encode(self.key)
+    }
+
+    fn aead(&self) -> Aes256Gcm {
+        Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&self.key))
+    }
+
+    /// Encrypt `plaintext` for the row identified by `row_id`
+    pub fn encrypt(&self, plaintext: &str, row_id: &str) -> String {
+        let mut nonce = [0u8; NONCE_LEN];
+        rand::rngs::OsRng.fill_bytes(&mut nonce);
+
+        let ciphertext = self
+            .aead()
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: plaintext.as_bytes(),
+                    aad: row_id.as_bytes(),
+                },
+            )
+            .expect("AES-GCM encryption cannot fail for in-memory buffers");
+
+        let mut sealed = nonce.to_vec();
+        sealed.extend_from_slice(&ciphertext);
+        format!(
+            "{}{}",
+            ENCRYPTED_PREFIX,
+            base64::engine::general_purpose::STANDARD.encode(sealed)
+        )
+    }
+
+    /// Decrypt a value written by `encrypt` for the same row. Fails if the
+    /// value was modified, moved to another row or sealed with another key.
+    pub fn decrypt(&self, stored: &str, row_id: &str) -> Result<String, String> {
+        let encoded = stored
+            .strip_prefix(ENCRYPTED_PREFIX)
+            .ok_or_else(|| "credential is not encrypted".to_string())?;
+        let sealed = base64::engine::general_purpose::STANDARD
+            .decode(encoded)
+            .map_err(|e| format!("credential is not valid base64: {}", e))?;
+        if sealed.len() < NONCE_LEN {
+            return Err("credential is truncated".to_string());
+        }
+
+        let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+        let plaintext = self
+            .aead()
+            .decrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: row_id.as_bytes(),
+                },
+            )
+            .map_err(|_| "credential failed authentication".to_string())?;
+
+        String::from_utf8(plaintext).map_err(|_| "credential is not valid UTF-8".to_string())
+    }
+}
+
+/// Whether a stored value was written by `CredentialCipher::encrypt`
+pub fn is_encrypted(stored: &str) -> bool {
+    stored.starts_with(ENCRYPTED_PREFIX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cipher() -> CredentialCipher {
+        CredentialCipher::from_key(&CredentialCipher::generate_key()).unwrap()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let cipher = cipher();
+        for plaintext in ["hunter2", "", "unicode: \u{00e9}\u{00e8}", "-----BEGIN KEY-----\nabc"] {
+            let sealed = cipher.encrypt(plaintext, "sys-1");
+            assert!(is_encrypted(&sealed));
+            assert_eq!(cipher.decrypt(&sealed, "sys-1").unwrap(), plaintext);
+        }
+    }
+
+    #[test]
+    fn test_each_value_gets_its_own_nonce() {
+        let cipher = cipher();
+        assert_ne!(cipher.encrypt("same", "sys-1"), cipher.encrypt("same", "sys-1"));
+    }
+
+    #[test]
+    fn test_tampered_value_is_rejected() {
+        let cipher = cipher();
+        let sealed = cipher.encrypt("password", "sys-1");
+
+        let mut bytes = base64::engine::general_purpose::STANDARD
+            .decode(&sealed[ENCRYPTED_PREFIX.len()..])
+            .unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0x01;
+        let tampered = format!(
+            "{}{}",
+            ENCRYPTED_PREFIX,
+            base64::engine::general_purpose::STANDARD.encode(&bytes)
+        );
+
+        assert!(cipher.decrypt(&tampered, "sys-1").is_err());
+    }
+
+    #[test]
+    fn test_value_moved_to_another_row_is_rejected() {
+        let cipher = cipher();
+        let sealed = cipher.encrypt("password", "sys-1");
+        assert!(cipher.decrypt(&sealed, "sys-2").is_err());
+    }
+
+    #[test]
+    fn test_other_key_cannot_decrypt() {
+        let sealed = cipher().encrypt("password", "sys-1");
+        assert!(cipher().decrypt(&sealed, "sys-1").is_err());
+    }
+
+    #[test]
+    fn test_truncated_and_legacy_values_are_rejected() {
+        let cipher = cipher();
+        assert!(cipher.decrypt("v2:AAAA", "sys-1").is_err());
+        assert!(cipher.decrypt("bGVnYWN5", "sys-1").is_err());
+        assert!(!is_encrypted("bGVnYWN5"));
+    }
+
+    #[test]
+    fn test_key_round_trip_and_validation() {
+        let cipher = cipher();
+        let restored = CredentialCipher::from_key(&cipher.key()).unwrap();
+        let sealed = cipher.encrypt("password", "sys-1");
+        assert_eq!(restored.decrypt(&sealed, "sys-1").unwrap(), "password");
+
+        assert!(CredentialCipher::from_key("c2hvcnQ=").is_err());
+        assert!(CredentialCipher::from_key("not base64!").is_err());
+        assert!(!format!("{:?}", cipher).contains(&cipher.key()));
+    }
+}
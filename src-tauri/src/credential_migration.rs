@@ -89,6 +89,12 @@ fn collect_ssh_credentials_from_db(
         rows.len()
     );
 
+    // Values encrypted by `credential_crypto` need the key kept in the vault
+    let cipher = vault
+        .credential_key
+        .as_deref()
+        .and_then(|key| crate::credential_crypto::CredentialCipher::from_key(key).ok());
+
     let mut changed = false;
 
     for (system_id, password_enc, passphrase_enc, private_key_enc) in rows {
@@ -100,21 +106,21 @@ fn collect_ssh_credentials_from_db(
         let mut system_changed = false;
 
         if let Some(ref enc) = password_enc {
-            if let Some(plain) = crate::database::deobfuscate_credential(enc) {
+            if let Some(plain) = crate::database::decode_credential(enc, cipher.as_ref(), &system_id) {
                 creds.password = Some(plain);
                 system_changed = true;
             }
         }
 
         if let Some(ref enc) = passphrase_enc {
-            if let Some(plain) = crate::database::deobfuscate_credential(enc) {
+            if let Some(plain) = crate::database::decode_credential(enc, cipher.as_ref(), &system_id) {
                 creds.passphrase = Some(plain);
                 system_changed = true;
             }
         }
 
         if let Some(ref enc) = private_key_enc {
-            if let Some(plain) = crate::database::deobfuscate_credential(enc) {
+            if let Some(plain) = crate::database::decode_credential(enc, cipher.as_ref(), &system_id) {
                 creds.private_key = Some(plain);
                 system_changed = true;
             }
@@ -1,21 +1,116 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
 use base64::Engine;
-use rusqlite::{Connection, Result as SqliteResult};
+use rusqlite::backup::Progress;
+use rusqlite::{Connection, DatabaseName, OpenFlags, Result as SqliteResult};
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
+use crate::agent::safety::{DangerLevel, DangerRule};
+use crate::agent::session::{ConversationMessage, ConversationTurn};
 use crate::ai::AiSettings;
+use crate::credential_crypto::{self, CredentialCipher};
 use crate::models::command_template::{
     category_to_str, get_built_in_templates, str_to_category, CommandTemplate,
+    TemplateImportResult,
 };
 use crate::models::container::ContainerRuntime;
-use crate::models::system::{ConnectionType, ContainerSystem, SystemId};
-
-/// Initialize the database and create tables if they don't exist
+use crate::models::system::{AlertConfig, ConnectionType, ContainerSystem, SystemId};
+
+/// A numbered schema migration; must be idempotent so it can run against
+/// databases created before versioning was introduced
+type Migration = fn(&Connection) -> SqliteResult<()>;
+
+/// Schema migrations in order; migration `i` brings the schema to version `i + 1`
+const MIGRATIONS: &[Migration] = &[
+    migrate_base_schema,
+    migrate_ai_settings_columns,
+    migrate_ssh_private_key,
+    migrate_app_settings_columns,
+    migrate_template_usage,
+    migrate_template_steps,
+    migrate_command_history,
+    migrate_system_tags,
+    migrate_command_timeout,
+    migrate_summary_min_input_length,
+    migrate_last_connected,
+    migrate_metrics_exporter_port,
+    migrate_ai_request_timeout,
+    migrate_agent_auto_approve_level,
+    migrate_terminal_idle_timeout,
+    migrate_agent_conversation_key,
+];
+
+/// Schema version this build writes and understands
+pub const SCHEMA_VERSION: u32 = MIGRATIONS.len() as u32;
+
+/// Initialize the database and bring its schema up to date
 pub fn init_database(path: &Path) -> SqliteResult<Connection> {
     let conn = Connection::open(path)?;
 
+    run_migrations(&conn)?;
+
+    // Seed built-in templates if table is empty
+    seed_built_in_templates(&conn)?;
+
+    Ok(conn)
+}
+
+/// Read the schema version recorded in the database (`PRAGMA user_version`)
+pub fn schema_version(conn: &Connection) -> SqliteResult<u32> {
+    conn.query_row("PRAGMA user_version", [], |row| row.get(0))
+}
+
+/// Apply every migration newer than the recorded schema version, each in its
+/// own transaction. Fails if the database was written by a newer build.
+fn run_migrations(conn: &Connection) -> SqliteResult<()> {
+    let current = schema_version(conn)?;
+    if current > SCHEMA_VERSION {
+        return Err(rusqlite::Error::SqliteFailure(
+            rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_ERROR),
+            Some(format!(
+                "Database schema version {} is newer than supported version {}",
+                current, SCHEMA_VERSION
+            )),
+        ));
+    }
+
+    for (index, migration) in MIGRATIONS.iter().enumerate().skip(current as usize) {
+        let version = index as u32 + 1;
+        let tx = conn.unchecked_transaction()?;
+        migration(&tx)?;
+        tx.pragma_update(None, "user_version", version)?;
+        tx.commit()?;
+        tracing::info!("Applied database migration {}", version);
+    }
+
+    Ok(())
+}
+
+/// Add a column unless the table already has it
+fn add_column_if_missing(
+    conn: &Connection,
+    table: &str,
+    column: &str,
+    definition: &str,
+) -> SqliteResult<()> {
+    let exists: bool = conn.query_row(
+        &format!("SELECT COUNT(*) > 0 FROM pragma_table_info('{}') WHERE name = ?1", table),
+        [column],
+        |row| row.get(0),
+    )?;
+    if !exists {
+        conn.execute(
+            &format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition),
+            [],
+        )?;
+    }
+    Ok(())
+}
+
+/// Migration 1: the tables that existed before schema versioning
+fn migrate_base_schema(conn: &Connection) -> SqliteResult<()> {
     conn.execute(
         "CREATE TABLE IF NOT EXISTS systems (
             id TEXT PRIMARY KEY,
@@ -68,39 +163,12 @@ pub fn init_database(path: &Path) -> SqliteResult<Connection> {
             endpoint_url TEXT NOT NULL DEFAULT 'http://localhost:11434',
             temperature REAL NOT NULL DEFAULT 0.3,
             max_tokens INTEGER NOT NULL DEFAULT 256,
-            memory_enabled INTEGER NOT NULL DEFAULT 1,
-            summary_model TEXT,
-            summary_max_tokens INTEGER NOT NULL DEFAULT 100,
             created_at TEXT NOT NULL,
             updated_at TEXT NOT NULL
         )",
         [],
     )?;
 
-    // Migration: Add memory columns if they don't exist (for existing databases)
-    let _ = conn.execute(
-        "ALTER TABLE ai_settings ADD COLUMN memory_enabled INTEGER NOT NULL DEFAULT 1",
-        [],
-    );
-    let _ = conn.execute(
-        "ALTER TABLE ai_settings ADD COLUMN summary_model TEXT",
-        [],
-    );
-    let _ = conn.execute(
-        "ALTER TABLE ai_settings ADD COLUMN summary_max_tokens INTEGER NOT NULL DEFAULT 100",
-        [],
-    );
-    let _ = conn.execute(
-        "ALTER TABLE ai_settings ADD COLUMN api_version TEXT",
-        [],
-    );
-
-    // Migration: Add private_key_enc column for SSH key content storage (mobile support)
-    let _ = conn.execute(
-        "ALTER TABLE ssh_credentials ADD COLUMN private_key_enc TEXT",
-        [],
-    );
-
     // App settings table (singleton pattern)
     conn.execute(
         "CREATE TABLE IF NOT EXISTS app_settings (
@@ -112,36 +180,233 @@ pub fn init_database(path: &Path) -> SqliteResult<Connection> {
         [],
     )?;
 
-    // Migration: Add ssh_config_paths column (JSON array) for multiple SSH config paths
-    let _ = conn.execute(
-        "ALTER TABLE app_settings ADD COLUMN ssh_config_paths TEXT",
+    // Per-system monitoring alert thresholds
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS alert_configs (
+            system_id TEXT PRIMARY KEY,
+            cpu_pct REAL,
+            memory_pct REAL,
+            disk_pct INTEGER,
+            swap_pct REAL,
+            updated_at TEXT NOT NULL,
+            FOREIGN KEY (system_id) REFERENCES systems(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    // Persisted agent conversations, keyed by agent and terminal session
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS agent_sessions (
+            id TEXT PRIMARY KEY,
+            terminal_session_id TEXT NOT NULL,
+            created_at INTEGER NOT NULL,
+            last_activity INTEGER NOT NULL
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_agent_sessions_terminal
+         ON agent_sessions(terminal_session_id)",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS agent_messages (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            session_id TEXT NOT NULL,
+            kind TEXT NOT NULL,
+            payload TEXT NOT NULL,
+            timestamp INTEGER NOT NULL,
+            FOREIGN KEY (session_id) REFERENCES agent_sessions(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    // User-defined danger rules for the agent's command classifier
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS danger_rules (
+            id TEXT PRIMARY KEY,
+            pattern TEXT NOT NULL,
+            is_regex INTEGER NOT NULL DEFAULT 0,
+            level TEXT NOT NULL,
+            warning TEXT NOT NULL,
+            created_at TEXT NOT NULL
+        )",
         [],
-    );
+    )?;
+
+    Ok(())
+}
+
+/// Migration 2: AI memory/summary settings, API version and retry count
+fn migrate_ai_settings_columns(conn: &Connection) -> SqliteResult<()> {
+    add_column_if_missing(conn, "ai_settings", "memory_enabled", "INTEGER NOT NULL DEFAULT 1")?;
+    add_column_if_missing(conn, "ai_settings", "summary_model", "TEXT")?;
+    add_column_if_missing(
+        conn,
+        "ai_settings",
+        "summary_max_tokens",
+        "INTEGER NOT NULL DEFAULT 100",
+    )?;
+    add_column_if_missing(conn, "ai_settings", "api_version", "TEXT")?;
+    add_column_if_missing(conn, "ai_settings", "max_retries", "INTEGER NOT NULL DEFAULT 2")
+}
+
+/// Migration 3: SSH key content storage (mobile support)
+fn migrate_ssh_private_key(conn: &Connection) -> SqliteResult<()> {
+    add_column_if_missing(conn, "ssh_credentials", "private_key_enc", "TEXT")
+}
+
+/// Migration 4: multiple SSH config paths, vault consolidation flag and
+/// "What's New" changelog tracking
+fn migrate_app_settings_columns(conn: &Connection) -> SqliteResult<()> {
+    add_column_if_missing(conn, "app_settings", "ssh_config_paths", "TEXT")?;
 
-    // Migration: Migrate single ssh_config_path to ssh_config_paths array
-    let _ = conn.execute(
+    // Migrate single ssh_config_path to ssh_config_paths array
+    conn.execute(
         "UPDATE app_settings SET ssh_config_paths = json_array(ssh_config_path)
          WHERE ssh_config_path IS NOT NULL AND ssh_config_path != ''
          AND (ssh_config_paths IS NULL OR ssh_config_paths = '')",
         [],
-    );
+    )?;
+
+    add_column_if_missing(
+        conn,
+        "app_settings",
+        "vault_migration_done",
+        "INTEGER NOT NULL DEFAULT 0",
+    )?;
+    add_column_if_missing(conn, "app_settings", "last_seen_version", "TEXT")
+}
+
+/// Migration 5: track how often and how recently each command template is used
+fn migrate_template_usage(conn: &Connection) -> SqliteResult<()> {
+    add_column_if_missing(
+        conn,
+        "command_templates",
+        "use_count",
+        "INTEGER NOT NULL DEFAULT 0",
+    )?;
+    add_column_if_missing(conn, "command_templates", "last_used_at", "TEXT")
+}
+
+/// Migration 6: ordered steps of composite command templates (JSON, NULL for plain templates)
+fn migrate_template_steps(conn: &Connection) -> SqliteResult<()> {
+    add_column_if_missing(conn, "command_templates", "steps", "TEXT")
+}
 
-    // Migration: Add vault_migration_done flag for single-vault keyring consolidation
-    let _ = conn.execute(
-        "ALTER TABLE app_settings ADD COLUMN vault_migration_done INTEGER NOT NULL DEFAULT 0",
+/// Migration 7: durable history of commands run outside agent sessions
+fn migrate_command_history(conn: &Connection) -> SqliteResult<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS command_history (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            system_id TEXT NOT NULL,
+            command TEXT NOT NULL,
+            exit_code INTEGER,
+            duration_ms INTEGER NOT NULL,
+            executed_at TEXT NOT NULL,
+            FOREIGN KEY (system_id) REFERENCES systems(id) ON DELETE CASCADE
+        )",
         [],
-    );
+    )?;
 
-    // Migration: Add last_seen_version for "What's New" changelog tracking
-    let _ = conn.execute(
-        "ALTER TABLE app_settings ADD COLUMN last_seen_version TEXT",
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_command_history_system
+         ON command_history(system_id, id)",
         [],
-    );
+    )?;
 
-    // Seed built-in templates if table is empty
-    seed_built_in_templates(&conn)?;
+    Ok(())
+}
 
-    Ok(conn)
+/// Migration 8: free-form system tags (JSON array)
+fn migrate_system_tags(conn: &Connection) -> SqliteResult<()> {
+    add_column_if_missing(conn, "systems", "tags", "TEXT NOT NULL DEFAULT '[]'")
+}
+
+/// Migration 9: per-system command time limit (NULL uses the default)
+fn migrate_command_timeout(conn: &Connection) -> SqliteResult<()> {
+    add_column_if_missing(conn, "systems", "command_timeout_secs", "INTEGER")
+}
+
+/// Migration 10: shortest AI input that gets summarized
+fn migrate_summary_min_input_length(conn: &Connection) -> SqliteResult<()> {
+    add_column_if_missing(
+        conn,
+        "ai_settings",
+        "summary_min_input_length",
+        "INTEGER NOT NULL DEFAULT 100",
+    )
+}
+
+/// Migration 11: when each system last connected (unix seconds, NULL if never)
+fn migrate_last_connected(conn: &Connection) -> SqliteResult<()> {
+    add_column_if_missing(conn, "systems", "last_connected_at", "INTEGER")
+}
+
+/// Migration 12: local port serving Prometheus metrics (NULL = exporter off)
+fn migrate_metrics_exporter_port(conn: &Connection) -> SqliteResult<()> {
+    add_column_if_missing(conn, "app_settings", "metrics_exporter_port", "INTEGER")
+}
+
+/// Migration 13: seconds an AI provider may stay silent before a request fails
+fn migrate_ai_request_timeout(conn: &Connection) -> SqliteResult<()> {
+    add_column_if_missing(
+        conn,
+        "ai_settings",
+        "request_timeout_secs",
+        "INTEGER NOT NULL DEFAULT 120",
+    )
+}
+
+/// Migration 14: highest danger level the agent runs without confirmation.
+/// The preferences table used to be created on first use, so create it here.
+fn migrate_agent_auto_approve_level(conn: &Connection) -> SqliteResult<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS agent_preferences (
+            id INTEGER PRIMARY KEY CHECK (id = 1),
+            auto_execute_safe_commands INTEGER NOT NULL DEFAULT 1,
+            show_thinking_process INTEGER NOT NULL DEFAULT 0,
+            confirm_all_commands INTEGER NOT NULL DEFAULT 0,
+            max_auto_execute_steps INTEGER NOT NULL DEFAULT 5,
+            confirmation_timeout_secs INTEGER NOT NULL DEFAULT 300,
+            preferred_shell TEXT,
+            dangerous_command_patterns TEXT NOT NULL DEFAULT '[]'
+        )",
+        [],
+    )?;
+    add_column_if_missing(
+        conn,
+        "agent_preferences",
+        "auto_approve_level",
+        "TEXT NOT NULL DEFAULT 'moderate'",
+    )
+}
+
+/// Migration 15: minutes a terminal may sit idle before it is closed (NULL = default)
+fn migrate_terminal_idle_timeout(conn: &Connection) -> SqliteResult<()> {
+    add_column_if_missing(conn, "app_settings", "terminal_idle_timeout_mins", "INTEGER")
+}
+
+/// Migration 16: agent history is kept per conversation (system, container and
+/// shell) instead of per terminal. Rows stored under terminal ids could never
+/// be restored, so they are dropped.
+fn migrate_agent_conversation_key(conn: &Connection) -> SqliteResult<()> {
+    add_column_if_missing(conn, "agent_sessions", "conversation_key", "TEXT")?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_agent_sessions_conversation
+         ON agent_sessions(conversation_key)",
+        [],
+    )?;
+    conn.execute(
+        "DELETE FROM agent_messages WHERE session_id IN (
+             SELECT id FROM agent_sessions WHERE conversation_key IS NULL
+         )",
+        [],
+    )?;
+    conn.execute("DELETE FROM agent_sessions WHERE conversation_key IS NULL", [])?;
+    Ok(())
 }
 
 /// Insert a new system into the database
@@ -151,10 +416,11 @@ pub fn insert_system(conn: &Connection, system: &ContainerSystem) -> SqliteResul
         .ssh_config
         .as_ref()
         .map(|c| serde_json::to_string(c).unwrap_or_default());
+    let tags_json = serde_json::to_string(&system.tags).unwrap_or_default();
 
     conn.execute(
-        "INSERT INTO systems (id, name, hostname, connection_type, primary_runtime, available_runtimes, ssh_config, auto_connect)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        "INSERT INTO systems (id, name, hostname, connection_type, primary_runtime, available_runtimes, ssh_config, auto_connect, tags, command_timeout_secs, last_connected_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
         (
             &system.id.0,
             &system.name,
@@ -164,6 +430,9 @@ pub fn insert_system(conn: &Connection, system: &ContainerSystem) -> SqliteResul
             &runtimes_json,
             &ssh_config_json,
             system.auto_connect as i32,
+            &tags_json,
+            system.command_timeout_secs.map(|secs| secs as i64),
+            system.last_connected_at,
         ),
     )?;
 
@@ -173,7 +442,7 @@ pub fn insert_system(conn: &Connection, system: &ContainerSystem) -> SqliteResul
 /// Get all systems from the database
 pub fn get_all_systems(conn: &Connection) -> SqliteResult<Vec<ContainerSystem>> {
     let mut stmt = conn.prepare(
-        "SELECT id, name, hostname, connection_type, primary_runtime, available_runtimes, ssh_config, auto_connect FROM systems",
+        "SELECT id, name, hostname, connection_type, primary_runtime, available_runtimes, ssh_config, auto_connect, tags, command_timeout_secs, last_connected_at FROM systems",
     )?;
 
     let systems = stmt
@@ -186,6 +455,9 @@ pub fn get_all_systems(conn: &Connection) -> SqliteResult<Vec<ContainerSystem>>
             let runtimes_json: String = row.get(5)?;
             let ssh_config_json: Option<String> = row.get(6)?;
             let auto_connect: i32 = row.get(7)?;
+            let tags_json: String = row.get(8)?;
+            let command_timeout_secs: Option<i64> = row.get(9)?;
+            let last_connected_at: Option<i64> = row.get(10)?;
 
             Ok(ContainerSystem {
                 id: SystemId(id),
@@ -196,6 +468,9 @@ pub fn get_all_systems(conn: &Connection) -> SqliteResult<Vec<ContainerSystem>>
                 available_runtimes: serde_json::from_str(&runtimes_json).unwrap_or_default(),
                 ssh_config: ssh_config_json.and_then(|j| serde_json::from_str(&j).ok()),
                 auto_connect: auto_connect != 0,
+                tags: serde_json::from_str(&tags_json).unwrap_or_default(),
+                command_timeout_secs: command_timeout_secs.map(|secs| secs.max(0) as u64),
+                last_connected_at,
             })
         })?
         .collect::<SqliteResult<Vec<_>>>()?;
@@ -219,6 +494,20 @@ pub fn update_system_runtimes(
     Ok(())
 }
 
+/// Record that a system connected at `at` (unix seconds)
+pub fn update_system_last_connected(
+    conn: &Connection,
+    system_id: &str,
+    at: i64,
+) -> SqliteResult<()> {
+    conn.execute(
+        "UPDATE systems SET last_connected_at = ?1 WHERE id = ?2",
+        (at, system_id),
+    )?;
+
+    Ok(())
+}
+
 /// Update an existing system in the database
 pub fn update_system(conn: &Connection, system: &ContainerSystem) -> SqliteResult<bool> {
     let runtimes_json = serde_json::to_string(&system.available_runtimes).unwrap_or_default();
@@ -226,9 +515,10 @@ pub fn update_system(conn: &Connection, system: &ContainerSystem) -> SqliteResul
         .ssh_config
         .as_ref()
         .map(|c| serde_json::to_string(c).unwrap_or_default());
+    let tags_json = serde_json::to_string(&system.tags).unwrap_or_default();
 
     let rows_affected = conn.execute(
-        "UPDATE systems SET name = ?1, hostname = ?2, connection_type = ?3, primary_runtime = ?4, available_runtimes = ?5, ssh_config = ?6, auto_connect = ?7 WHERE id = ?8",
+        "UPDATE systems SET name = ?1, hostname = ?2, connection_type = ?3, primary_runtime = ?4, available_runtimes = ?5, ssh_config = ?6, auto_connect = ?7, tags = ?8, command_timeout_secs = ?9 WHERE id = ?10",
         (
             &system.name,
             &system.hostname,
@@ -237,6 +527,8 @@ pub fn update_system(conn: &Connection, system: &ContainerSystem) -> SqliteResul
             &runtimes_json,
             &ssh_config_json,
             system.auto_connect as i32,
+            &tags_json,
+            system.command_timeout_secs.map(|secs| secs as i64),
             &system.id.0,
         ),
     )?;
@@ -271,6 +563,7 @@ fn runtime_to_str(rt: ContainerRuntime) -> &'static str {
         ContainerRuntime::Docker => "docker",
         ContainerRuntime::Podman => "podman",
         ContainerRuntime::Apple => "apple",
+        ContainerRuntime::Nerdctl => "nerdctl",
     }
 }
 
@@ -278,6 +571,7 @@ fn str_to_runtime(s: &str) -> ContainerRuntime {
     match s {
         "podman" => ContainerRuntime::Podman,
         "apple" => ContainerRuntime::Apple,
+        "nerdctl" => ContainerRuntime::Nerdctl,
         _ => ContainerRuntime::Docker,
     }
 }
@@ -349,15 +643,24 @@ fn seed_built_in_templates(conn: &Connection) -> SqliteResult<()> {
     Ok(())
 }
 
+/// Serialize a composite template's steps; plain templates store NULL
+fn steps_to_json(template: &CommandTemplate) -> Option<String> {
+    template
+        .steps
+        .as_ref()
+        .and_then(|steps| serde_json::to_string(steps).ok())
+}
+
 /// Insert a new command template into the database
 pub fn insert_command_template(conn: &Connection, template: &CommandTemplate) -> SqliteResult<()> {
     let tags_json = serde_json::to_string(&template.tags).unwrap_or_default();
     let variables_json = serde_json::to_string(&template.variables).unwrap_or_default();
     let compatibility_json = serde_json::to_string(&template.compatibility).unwrap_or_default();
+    let steps_json = steps_to_json(template);
 
     conn.execute(
-        "INSERT INTO command_templates (id, name, description, command, category, tags, variables, compatibility, is_favorite, is_built_in, created_at, updated_at)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+        "INSERT INTO command_templates (id, name, description, command, category, tags, variables, compatibility, is_favorite, is_built_in, created_at, updated_at, use_count, last_used_at, steps)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
         (
             &template.id,
             &template.name,
@@ -371,6 +674,9 @@ pub fn insert_command_template(conn: &Connection, template: &CommandTemplate) ->
             template.is_built_in as i32,
             &template.created_at,
             &template.updated_at,
+            template.use_count,
+            &template.last_used_at,
+            &steps_json,
         ),
     )?;
 
@@ -380,7 +686,7 @@ pub fn insert_command_template(conn: &Connection, template: &CommandTemplate) ->
 /// Get all command templates from the database
 pub fn get_all_command_templates(conn: &Connection) -> SqliteResult<Vec<CommandTemplate>> {
     let mut stmt = conn.prepare(
-        "SELECT id, name, description, command, category, tags, variables, compatibility, is_favorite, is_built_in, created_at, updated_at
+        "SELECT id, name, description, command, category, tags, variables, compatibility, is_favorite, is_built_in, created_at, updated_at, use_count, last_used_at, steps
          FROM command_templates
          ORDER BY is_favorite DESC, name ASC",
     )?;
@@ -399,12 +705,16 @@ pub fn get_all_command_templates(conn: &Connection) -> SqliteResult<Vec<CommandT
             let is_built_in: i32 = row.get(9)?;
             let created_at: String = row.get(10)?;
             let updated_at: String = row.get(11)?;
+            let use_count: u32 = row.get(12)?;
+            let last_used_at: Option<String> = row.get(13)?;
+            let steps_json: Option<String> = row.get(14)?;
 
             Ok(CommandTemplate {
                 id,
                 name,
                 description,
                 command,
+                steps: steps_json.and_then(|json| serde_json::from_str(&json).ok()),
                 category: str_to_category(&category_str),
                 tags: serde_json::from_str(&tags_json).unwrap_or_default(),
                 variables: serde_json::from_str(&variables_json).unwrap_or_default(),
@@ -413,6 +723,8 @@ pub fn get_all_command_templates(conn: &Connection) -> SqliteResult<Vec<CommandT
                 is_built_in: is_built_in != 0,
                 created_at,
                 updated_at,
+                use_count,
+                last_used_at,
             })
         })?
         .collect::<SqliteResult<Vec<_>>>()?;
@@ -423,7 +735,7 @@ pub fn get_all_command_templates(conn: &Connection) -> SqliteResult<Vec<CommandT
 /// Get a single command template by ID
 pub fn get_command_template(conn: &Connection, id: &str) -> SqliteResult<Option<CommandTemplate>> {
     let mut stmt = conn.prepare(
-        "SELECT id, name, description, command, category, tags, variables, compatibility, is_favorite, is_built_in, created_at, updated_at
+        "SELECT id, name, description, command, category, tags, variables, compatibility, is_favorite, is_built_in, created_at, updated_at, use_count, last_used_at, steps
          FROM command_templates
          WHERE id = ?1",
     )?;
@@ -443,12 +755,16 @@ pub fn get_command_template(conn: &Connection, id: &str) -> SqliteResult<Option<
         let is_built_in: i32 = row.get(9)?;
         let created_at: String = row.get(10)?;
         let updated_at: String = row.get(11)?;
+        let use_count: u32 = row.get(12)?;
+        let last_used_at: Option<String> = row.get(13)?;
+        let steps_json: Option<String> = row.get(14)?;
 
         Ok(Some(CommandTemplate {
             id,
             name,
             description,
             command,
+            steps: steps_json.and_then(|json| serde_json::from_str(&json).ok()),
             category: str_to_category(&category_str),
             tags: serde_json::from_str(&tags_json).unwrap_or_default(),
             variables: serde_json::from_str(&variables_json).unwrap_or_default(),
@@ -457,6 +773,8 @@ pub fn get_command_template(conn: &Connection, id: &str) -> SqliteResult<Option<
             is_built_in: is_built_in != 0,
             created_at,
             updated_at,
+            use_count,
+            last_used_at,
         }))
     } else {
         Ok(None)
@@ -468,10 +786,11 @@ pub fn update_command_template(conn: &Connection, template: &CommandTemplate) ->
     let tags_json = serde_json::to_string(&template.tags).unwrap_or_default();
     let variables_json = serde_json::to_string(&template.variables).unwrap_or_default();
     let compatibility_json = serde_json::to_string(&template.compatibility).unwrap_or_default();
+    let steps_json = steps_to_json(template);
 
     let rows_affected = conn.execute(
         "UPDATE command_templates
-         SET name = ?1, description = ?2, command = ?3, category = ?4, tags = ?5, variables = ?6, compatibility = ?7, is_favorite = ?8, updated_at = ?9
+         SET name = ?1, description = ?2, command = ?3, category = ?4, tags = ?5, variables = ?6, compatibility = ?7, is_favorite = ?8, updated_at = ?9, steps = ?11
          WHERE id = ?10",
         (
             &template.name,
@@ -484,6 +803,7 @@ pub fn update_command_template(conn: &Connection, template: &CommandTemplate) ->
             template.is_favorite as i32,
             &template.updated_at,
             &template.id,
+            &steps_json,
         ),
     )?;
 
@@ -499,6 +819,72 @@ pub fn delete_command_template(conn: &Connection, id: &str) -> SqliteResult<bool
     Ok(rows_affected > 0)
 }
 
+/// Import templates in a single transaction, matching existing templates by name.
+/// New templates get fresh IDs; built-in templates are never overwritten.
+pub fn import_command_templates(
+    conn: &Connection,
+    templates: Vec<CommandTemplate>,
+    overwrite: bool,
+) -> SqliteResult<TemplateImportResult> {
+    let tx = conn.unchecked_transaction()?;
+    let mut by_name: HashMap<String, CommandTemplate> = get_all_command_templates(&tx)?
+        .into_iter()
+        .map(|t| (t.name.clone(), t))
+        .collect();
+    let now = chrono::Utc::now().to_rfc3339();
+    let mut result = TemplateImportResult::default();
+
+    for template in templates {
+        let imported = match by_name.get(&template.name) {
+            Some(existing) if existing.is_built_in || !overwrite => {
+                result.skipped.push(template.name);
+                continue;
+            }
+            Some(existing) => {
+                let updated = CommandTemplate {
+                    id: existing.id.clone(),
+                    is_built_in: false,
+                    created_at: existing.created_at.clone(),
+                    updated_at: now.clone(),
+                    use_count: existing.use_count,
+                    last_used_at: existing.last_used_at.clone(),
+                    ..template
+                };
+                update_command_template(&tx, &updated)?;
+                result.overwritten += 1;
+                updated
+            }
+            None => {
+                let inserted = CommandTemplate {
+                    id: Uuid::new_v4().to_string(),
+                    is_built_in: false,
+                    created_at: now.clone(),
+                    updated_at: now.clone(),
+                    use_count: 0,
+                    last_used_at: None,
+                    ..template
+                };
+                insert_command_template(&tx, &inserted)?;
+                result.imported += 1;
+                inserted
+            }
+        };
+        by_name.insert(imported.name.clone(), imported);
+    }
+
+    tx.commit()?;
+    Ok(result)
+}
+
+/// Record that a command template was executed
+pub fn record_template_use(conn: &Connection, id: &str) -> SqliteResult<bool> {
+    let rows_affected = conn.execute(
+        "UPDATE command_templates SET use_count = use_count + 1, last_used_at = ?1 WHERE id = ?2",
+        (chrono::Utc::now().to_rfc3339(), id),
+    )?;
+    Ok(rows_affected > 0)
+}
+
 /// Toggle the favorite status of a command template
 pub fn toggle_command_favorite(conn: &Connection, id: &str) -> SqliteResult<bool> {
     let rows_affected = conn.execute(
@@ -516,7 +902,8 @@ pub fn toggle_command_favorite(conn: &Connection, id: &str) -> SqliteResult<bool
 pub fn get_ai_settings(conn: &Connection) -> SqliteResult<AiSettings> {
     let mut stmt = conn.prepare(
         "SELECT provider, api_key, model_name, endpoint_url, temperature, max_tokens,
-                memory_enabled, summary_model, summary_max_tokens, api_version
+                memory_enabled, summary_model, summary_max_tokens, api_version, max_retries,
+                summary_min_input_length, request_timeout_secs
          FROM ai_settings WHERE id = 1",
     )?;
 
@@ -533,6 +920,9 @@ pub fn get_ai_settings(conn: &Connection) -> SqliteResult<AiSettings> {
         let summary_model: Option<String> = row.get(7).unwrap_or(None);
         let summary_max_tokens: i32 = row.get(8).unwrap_or(100);
         let api_version: Option<String> = row.get(9).unwrap_or(None);
+        let max_retries: u32 = row.get(10).unwrap_or(2);
+        let summary_min_input_length: u32 = row.get(11).unwrap_or(100);
+        let request_timeout_secs: u64 = row.get(12).unwrap_or(120);
 
         Ok(AiSettings {
             provider: AiSettings::str_to_provider(&provider),
@@ -544,7 +934,10 @@ pub fn get_ai_settings(conn: &Connection) -> SqliteResult<AiSettings> {
             memory_enabled: memory_enabled != 0,
             summary_model,
             summary_max_tokens,
+            summary_min_input_length,
             api_version,
+            max_retries,
+            request_timeout_secs,
         })
     } else {
         // Return default settings
@@ -558,8 +951,9 @@ pub fn upsert_ai_settings(conn: &Connection, settings: &AiSettings) -> SqliteRes
 
     conn.execute(
         "INSERT INTO ai_settings (id, provider, api_key, model_name, endpoint_url, temperature, max_tokens,
-            memory_enabled, summary_model, summary_max_tokens, api_version, created_at, updated_at)
-         VALUES (1, ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?11)
+            memory_enabled, summary_model, summary_max_tokens, api_version, max_retries,
+            summary_min_input_length, request_timeout_secs, created_at, updated_at)
+         VALUES (1, ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?14)
          ON CONFLICT(id) DO UPDATE SET
              provider = excluded.provider,
              api_key = excluded.api_key,
@@ -571,6 +965,9 @@ pub fn upsert_ai_settings(conn: &Connection, settings: &AiSettings) -> SqliteRes
              summary_model = excluded.summary_model,
              summary_max_tokens = excluded.summary_max_tokens,
              api_version = excluded.api_version,
+             max_retries = excluded.max_retries,
+             summary_min_input_length = excluded.summary_min_input_length,
+             request_timeout_secs = excluded.request_timeout_secs,
              updated_at = excluded.updated_at",
         (
             settings.provider_to_str(),
@@ -583,6 +980,9 @@ pub fn upsert_ai_settings(conn: &Connection, settings: &AiSettings) -> SqliteRes
             &settings.summary_model,
             settings.summary_max_tokens,
             &settings.api_version,
+            settings.max_retries,
+            settings.summary_min_input_length,
+            settings.request_timeout_secs,
             &now,
         ),
     )?;
@@ -598,26 +998,11 @@ use crate::models::agent::AgentPreferences;
 
 /// Get agent preferences from the database (returns default if not set)
 pub fn get_agent_preferences(conn: &Connection) -> Result<AgentPreferences, String> {
-    // First ensure the table exists
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS agent_preferences (
-            id INTEGER PRIMARY KEY CHECK (id = 1),
-            auto_execute_safe_commands INTEGER NOT NULL DEFAULT 1,
-            show_thinking_process INTEGER NOT NULL DEFAULT 0,
-            confirm_all_commands INTEGER NOT NULL DEFAULT 0,
-            max_auto_execute_steps INTEGER NOT NULL DEFAULT 5,
-            confirmation_timeout_secs INTEGER NOT NULL DEFAULT 300,
-            preferred_shell TEXT,
-            dangerous_command_patterns TEXT NOT NULL DEFAULT '[]'
-        )",
-        [],
-    )
-    .map_err(|e| e.to_string())?;
-
     let mut stmt = conn
         .prepare(
             "SELECT auto_execute_safe_commands, show_thinking_process, confirm_all_commands,
-                max_auto_execute_steps, confirmation_timeout_secs, preferred_shell, dangerous_command_patterns
+                max_auto_execute_steps, confirmation_timeout_secs, preferred_shell, dangerous_command_patterns,
+                auto_approve_level
              FROM agent_preferences WHERE id = 1",
         )
         .map_err(|e| e.to_string())?;
@@ -632,6 +1017,7 @@ pub fn get_agent_preferences(conn: &Connection) -> Result<AgentPreferences, Stri
         let timeout: i32 = row.get(4).map_err(|e| e.to_string())?;
         let shell: Option<String> = row.get(5).map_err(|e| e.to_string())?;
         let patterns_json: String = row.get(6).map_err(|e| e.to_string())?;
+        let auto_approve_level: String = row.get(7).map_err(|e| e.to_string())?;
 
         Ok(AgentPreferences {
             auto_execute_safe_commands: auto_execute != 0,
@@ -641,6 +1027,9 @@ pub fn get_agent_preferences(conn: &Connection) -> Result<AgentPreferences, Stri
             confirmation_timeout_secs: timeout,
             preferred_shell: shell,
             dangerous_command_patterns: serde_json::from_str(&patterns_json).unwrap_or_default(),
+            auto_approve_level: auto_approve_level
+                .parse()
+                .unwrap_or(AgentPreferences::default().auto_approve_level),
         })
     } else {
         // Return default settings
@@ -653,28 +1042,12 @@ pub fn update_agent_preferences(
     conn: &Connection,
     preferences: &AgentPreferences,
 ) -> Result<(), String> {
-    // First ensure the table exists
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS agent_preferences (
-            id INTEGER PRIMARY KEY CHECK (id = 1),
-            auto_execute_safe_commands INTEGER NOT NULL DEFAULT 1,
-            show_thinking_process INTEGER NOT NULL DEFAULT 0,
-            confirm_all_commands INTEGER NOT NULL DEFAULT 0,
-            max_auto_execute_steps INTEGER NOT NULL DEFAULT 5,
-            confirmation_timeout_secs INTEGER NOT NULL DEFAULT 300,
-            preferred_shell TEXT,
-            dangerous_command_patterns TEXT NOT NULL DEFAULT '[]'
-        )",
-        [],
-    )
-    .map_err(|e| e.to_string())?;
-
     let patterns_json =
         serde_json::to_string(&preferences.dangerous_command_patterns).unwrap_or_default();
 
     conn.execute(
-        "INSERT INTO agent_preferences (id, auto_execute_safe_commands, show_thinking_process, confirm_all_commands, max_auto_execute_steps, confirmation_timeout_secs, preferred_shell, dangerous_command_patterns)
-         VALUES (1, ?1, ?2, ?3, ?4, ?5, ?6, ?7)
+        "INSERT INTO agent_preferences (id, auto_execute_safe_commands, show_thinking_process, confirm_all_commands, max_auto_execute_steps, confirmation_timeout_secs, preferred_shell, dangerous_command_patterns, auto_approve_level)
+         VALUES (1, ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
          ON CONFLICT(id) DO UPDATE SET
              auto_execute_safe_commands = excluded.auto_execute_safe_commands,
              show_thinking_process = excluded.show_thinking_process,
@@ -682,7 +1055,8 @@ pub fn update_agent_preferences(
              max_auto_execute_steps = excluded.max_auto_execute_steps,
              confirmation_timeout_secs = excluded.confirmation_timeout_secs,
              preferred_shell = excluded.preferred_shell,
-             dangerous_command_patterns = excluded.dangerous_command_patterns",
+             dangerous_command_patterns = excluded.dangerous_command_patterns,
+             auto_approve_level = excluded.auto_approve_level",
         (
             preferences.auto_execute_safe_commands as i32,
             preferences.show_thinking_process as i32,
@@ -691,6 +1065,7 @@ pub fn update_agent_preferences(
             preferences.confirmation_timeout_secs,
             &preferences.preferred_shell,
             &patterns_json,
+            preferences.auto_approve_level.to_string(),
         ),
     )
     .map_err(|e| e.to_string())?;
@@ -702,11 +1077,12 @@ pub fn update_agent_preferences(
 // SSH Credentials Database Functions
 // ============================================================================
 
-/// Simple obfuscation key - in production, derive from device-specific data
+/// Key of the XOR obfuscation older versions stored credentials with
 const OBFUSCATION_KEY: &[u8] = b"containerus_ssh_credential_key_v1";
 
-/// Obfuscate a credential string (simple XOR + base64)
-/// Note: This is NOT cryptographically secure, just basic obfuscation
+/// Obfuscate a credential string the way older versions did (XOR + base64).
+/// Only kept to test reading and re-encrypting such rows.
+#[cfg(test)]
 fn obfuscate(plaintext: &str) -> String {
     let bytes: Vec<u8> = plaintext
         .bytes()
@@ -716,8 +1092,8 @@ fn obfuscate(plaintext: &str) -> String {
     base64::engine::general_purpose::STANDARD.encode(&bytes)
 }
 
-/// Deobfuscate a credential string (pub(crate) for migration use)
-pub(crate) fn deobfuscate_credential(encoded: &str) -> Option<String> {
+/// Deobfuscate a credential string written by older versions
+fn deobfuscate_credential(encoded: &str) -> Option<String> {
     let bytes = base64::engine::general_purpose::STANDARD.decode(encoded).ok()?;
     let plaintext: Vec<u8> = bytes
         .iter()
@@ -727,6 +1103,27 @@ pub(crate) fn deobfuscate_credential(encoded: &str) -> Option<String> {
     String::from_utf8(plaintext).ok()
 }
 
+/// Read a stored credential column: AES-GCM values need the keychain key,
+/// values from older versions are only obfuscated (pub(crate) for migration use)
+pub(crate) fn decode_credential(
+    stored: &str,
+    cipher: Option<&CredentialCipher>,
+    system_id: &str,
+) -> Option<String> {
+    if !credential_crypto::is_encrypted(stored) {
+        return deobfuscate_credential(stored);
+    }
+
+    let Some(cipher) = cipher else {
+        tracing::warn!("No credential key available to decrypt credentials for {}", system_id);
+        return None;
+    };
+    cipher
+        .decrypt(stored, system_id)
+        .map_err(|e| tracing::warn!("Failed to decrypt credentials for {}: {}", system_id, e))
+        .ok()
+}
+
 /// SSH credentials for a system
 #[derive(Debug, Clone, Default)]
 pub struct SshCredentials {
@@ -736,17 +1133,18 @@ pub struct SshCredentials {
     pub private_key: Option<String>,
 }
 
-/// Store SSH credentials for a system (upsert)
+/// Store SSH credentials for a system (upsert), encrypted with the keychain key
 pub fn store_ssh_credentials(
     conn: &Connection,
+    cipher: &CredentialCipher,
     system_id: &str,
     password: Option<&str>,
     passphrase: Option<&str>,
     private_key: Option<&str>,
 ) -> SqliteResult<()> {
-    let password_enc = password.map(obfuscate);
-    let passphrase_enc = passphrase.map(obfuscate);
-    let private_key_enc = private_key.map(obfuscate);
+    let password_enc = password.map(|p| cipher.encrypt(p, system_id));
+    let passphrase_enc = passphrase.map(|p| cipher.encrypt(p, system_id));
+    let private_key_enc = private_key.map(|k| cipher.encrypt(k, system_id));
 
     conn.execute(
         "INSERT INTO ssh_credentials (system_id, password_enc, passphrase_enc, private_key_enc)
@@ -761,8 +1159,12 @@ pub fn store_ssh_credentials(
     Ok(())
 }
 
-/// Get SSH credentials for a system
-pub fn get_ssh_credentials(conn: &Connection, system_id: &str) -> SqliteResult<SshCredentials> {
+/// Get SSH credentials for a system; encrypted columns read as `None` without the key
+pub fn get_ssh_credentials(
+    conn: &Connection,
+    cipher: Option<&CredentialCipher>,
+    system_id: &str,
+) -> SqliteResult<SshCredentials> {
     let mut stmt = conn.prepare(
         "SELECT password_enc, passphrase_enc, private_key_enc FROM ssh_credentials WHERE system_id = ?1",
     )?;
@@ -775,9 +1177,9 @@ pub fn get_ssh_credentials(conn: &Connection, system_id: &str) -> SqliteResult<S
         let private_key_enc: Option<String> = row.get(2)?;
 
         Ok(SshCredentials {
-            password: password_enc.and_then(|e| deobfuscate_credential(&e)),
-            passphrase: passphrase_enc.and_then(|e| deobfuscate_credential(&e)),
-            private_key: private_key_enc.and_then(|e| deobfuscate_credential(&e)),
+            password: password_enc.and_then(|e| decode_credential(&e, cipher, system_id)),
+            passphrase: passphrase_enc.and_then(|e| decode_credential(&e, cipher, system_id)),
+            private_key: private_key_enc.and_then(|e| decode_credential(&e, cipher, system_id)),
         })
     } else {
         Ok(SshCredentials::default())
@@ -790,65 +1192,494 @@ pub fn delete_ssh_credentials(conn: &Connection, system_id: &str) -> SqliteResul
     Ok(())
 }
 
-#[cfg(test)]
-mod db_tests {
-    use super::*;
-    use std::collections::HashSet;
+/// Re-encrypt credentials still stored with the old obfuscation. Idempotent,
+/// so it runs on every startup once the keychain key is available.
+/// Returns the number of systems whose credentials were re-encrypted.
+pub fn encrypt_legacy_ssh_credentials(
+    conn: &Connection,
+    cipher: &CredentialCipher,
+) -> SqliteResult<usize> {
+    let rows: Vec<(String, [Option<String>; 3])> = {
+        let mut stmt = conn.prepare(
+            "SELECT system_id, password_enc, passphrase_enc, private_key_enc FROM ssh_credentials",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get(0)?, [row.get(1)?, row.get(2)?, row.get(3)?]))
+        })?;
+        rows.collect::<SqliteResult<_>>()?
+    };
+
+    let mut migrated = 0;
+    for (system_id, columns) in rows {
+        let is_legacy = |value: &Option<String>| {
+            value.as_deref().is_some_and(|v| !credential_crypto::is_encrypted(v))
+        };
+        if !columns.iter().any(is_legacy) {
+            continue;
+        }
 
-    fn setup_db() -> Connection {
-        let dir = tempfile::tempdir().unwrap();
-        let db_path = dir.path().join("test.db");
-        let conn = init_database(&db_path).unwrap();
-        // Leak the dir so it lives for the lifetime of the test
-        std::mem::forget(dir);
-        conn
+        let [password, passphrase, private_key] = columns.map(|value| match value {
+            Some(v) if !credential_crypto::is_encrypted(&v) => {
+                deobfuscate_credential(&v).map(|plain| cipher.encrypt(&plain, &system_id))
+            }
+            other => other,
+        });
+        conn.execute(
+            "UPDATE ssh_credentials SET password_enc = ?2, passphrase_enc = ?3, private_key_enc = ?4
+             WHERE system_id = ?1",
+            (&system_id, &password, &passphrase, &private_key),
+        )?;
+        migrated += 1;
     }
 
-    #[test]
-    fn test_init_database_creates_tables() {
-        let conn = setup_db();
-        // Verify tables exist by querying them
-        let count: i64 = conn.query_row("SELECT COUNT(*) FROM systems", [], |row| row.get(0)).unwrap();
-        assert_eq!(count, 0);
+    Ok(migrated)
+}
 
-        let count: i64 = conn.query_row("SELECT COUNT(*) FROM command_templates", [], |row| row.get(0)).unwrap();
-        // Built-in templates should be seeded
-        assert!(count > 0);
-    }
+// ============================================================================
+// Alert Config Database Functions
+// ============================================================================
 
-    #[test]
-    fn test_system_crud() {
-        let conn = setup_db();
+/// Get alert thresholds for a system (returns default, i.e. all disabled, if not set)
+pub fn get_alert_config(conn: &Connection, system_id: &str) -> SqliteResult<AlertConfig> {
+    let mut stmt = conn.prepare(
+        "SELECT cpu_pct, memory_pct, disk_pct, swap_pct FROM alert_configs WHERE system_id = ?1",
+    )?;
 
-        let system = ContainerSystem {
-            id: SystemId("test-sys-1".to_string()),
-            name: "Test Server".to_string(),
-            hostname: "192.168.1.100".to_string(),
-            connection_type: ConnectionType::Remote,
-            primary_runtime: ContainerRuntime::Docker,
-            available_runtimes: HashSet::from([ContainerRuntime::Docker]),
-            ssh_config: None,
-            auto_connect: false,
-        };
+    let mut rows = stmt.query([system_id])?;
 
-        // Insert
-        insert_system(&conn, &system).unwrap();
+    if let Some(row) = rows.next()? {
+        let cpu_pct: Option<f64> = row.get(0)?;
+        let memory_pct: Option<f64> = row.get(1)?;
+        let disk_pct: Option<i64> = row.get(2)?;
+        let swap_pct: Option<f64> = row.get(3)?;
+
+        Ok(AlertConfig {
+            cpu_pct: cpu_pct.map(|v| v as f32),
+            memory_pct: memory_pct.map(|v| v as f32),
+            disk_pct: disk_pct.map(|v| v.clamp(0, 100) as u8),
+            swap_pct: swap_pct.map(|v| v as f32),
+        })
+    } else {
+        Ok(AlertConfig::default())
+    }
+}
 
-        // Read
-        let systems = get_all_systems(&conn).unwrap();
-        assert_eq!(systems.len(), 1);
-        assert_eq!(systems[0].name, "Test Server");
-        assert_eq!(systems[0].hostname, "192.168.1.100");
-        assert_eq!(systems[0].connection_type, ConnectionType::Remote);
+/// Insert or update alert thresholds for a system (upsert)
+pub fn upsert_alert_config(conn: &Connection, system_id: &str, config: &AlertConfig) -> SqliteResult<()> {
+    let now = chrono::Utc::now().to_rfc3339();
 
-        // Update
-        let mut updated = system.clone();
-        updated.name = "Updated Server".to_string();
+    conn.execute(
+        "INSERT INTO alert_configs (system_id, cpu_pct, memory_pct, disk_pct, swap_pct, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+         ON CONFLICT(system_id) DO UPDATE SET
+             cpu_pct = excluded.cpu_pct,
+             memory_pct = excluded.memory_pct,
+             disk_pct = excluded.disk_pct,
+             swap_pct = excluded.swap_pct,
+             updated_at = excluded.updated_at",
+        (
+            system_id,
+            config.cpu_pct.map(|v| v as f64),
+            config.memory_pct.map(|v| v as f64),
+            config.disk_pct.map(|v| v as i64),
+            config.swap_pct.map(|v| v as f64),
+            &now,
+        ),
+    )?;
+
+    Ok(())
+}
+
+// ============================================================================
+// Agent History Database Functions
+// ============================================================================
+
+/// Maximum stored rows of each kind (messages, turns) per agent session
+const MAX_AGENT_ROWS_PER_SESSION: i64 = 100;
+
+/// Agent sessions kept in the database; the least recently active are dropped
+const MAX_AGENT_SESSIONS: i64 = 50;
+
+const AGENT_ROW_MESSAGE: &str = "message";
+const AGENT_ROW_TURN: &str = "turn";
+
+/// Conversation history restored for a conversation, oldest first
+#[derive(Debug, Clone, Default)]
+pub struct AgentHistory {
+    pub messages: Vec<ConversationMessage>,
+    pub turns: Vec<ConversationTurn>,
+}
+
+/// Insert or refresh an agent session row, then drop the least recently
+/// active sessions beyond `MAX_AGENT_SESSIONS`
+pub fn upsert_agent_session(
+    conn: &Connection,
+    session_id: &str,
+    terminal_session_id: &str,
+    conversation_key: &str,
+    created_at: i64,
+) -> SqliteResult<()> {
+    let now = chrono::Utc::now().timestamp_millis();
+
+    conn.execute(
+        "INSERT INTO agent_sessions (id, terminal_session_id, conversation_key, created_at, last_activity)
+         VALUES (?1, ?2, ?3, ?4, ?5)
+         ON CONFLICT(id) DO UPDATE SET last_activity = excluded.last_activity",
+        (session_id, terminal_session_id, conversation_key, created_at, now),
+    )?;
+
+    prune_agent_sessions(conn)
+}
+
+/// Delete sessions beyond `MAX_AGENT_SESSIONS` along with their rows, and any
+/// rows left without a session. Foreign keys aren't enforced, so the cascade
+/// on `agent_messages` never fires and children are deleted explicitly.
+fn prune_agent_sessions(conn: &Connection) -> SqliteResult<()> {
+    conn.execute(
+        "DELETE FROM agent_sessions WHERE id NOT IN (
+             SELECT id FROM agent_sessions ORDER BY last_activity DESC LIMIT ?1
+         )",
+        [MAX_AGENT_SESSIONS],
+    )?;
+    conn.execute(
+        "DELETE FROM agent_messages
+         WHERE session_id NOT IN (SELECT id FROM agent_sessions)",
+        [],
+    )?;
+    Ok(())
+}
+
+/// Append a conversation message to an agent session
+pub fn append_agent_message(
+    conn: &Connection,
+    session_id: &str,
+    message: &ConversationMessage,
+) -> SqliteResult<()> {
+    let payload = serde_json::to_string(message).unwrap_or_default();
+    insert_agent_row(conn, session_id, AGENT_ROW_MESSAGE, &payload, message.timestamp)
+}
+
+/// Append a conversation turn to an agent session
+pub fn append_agent_turn(
+    conn: &Connection,
+    session_id: &str,
+    turn: &ConversationTurn,
+) -> SqliteResult<()> {
+    let payload = serde_json::to_string(turn).unwrap_or_default();
+    insert_agent_row(conn, session_id, AGENT_ROW_TURN, &payload, turn.timestamp)
+}
+
+/// Insert a history row and drop the oldest rows of that kind beyond the cap
+fn insert_agent_row(
+    conn: &Connection,
+    session_id: &str,
+    kind: &str,
+    payload: &str,
+    timestamp: i64,
+) -> SqliteResult<()> {
+    conn.execute(
+        "INSERT INTO agent_messages (session_id, kind, payload, timestamp)
+         VALUES (?1, ?2, ?3, ?4)",
+        (session_id, kind, payload, timestamp),
+    )?;
+
+    conn.execute(
+        "DELETE FROM agent_messages
+         WHERE session_id = ?1 AND kind = ?2 AND id NOT IN (
+             SELECT id FROM agent_messages
+             WHERE session_id = ?1 AND kind = ?2
+             ORDER BY id DESC LIMIT ?3
+         )",
+        (session_id, kind, MAX_AGENT_ROWS_PER_SESSION),
+    )?;
+
+    conn.execute(
+        "UPDATE agent_sessions SET last_activity = ?2 WHERE id = ?1",
+        (session_id, chrono::Utc::now().timestamp_millis()),
+    )?;
+
+    Ok(())
+}
+
+/// Load the most recent history stored for a conversation across its agent sessions
+pub fn get_agent_history(conn: &Connection, conversation_key: &str) -> SqliteResult<AgentHistory> {
+    let mut stmt = conn.prepare(
+        "SELECT m.kind, m.payload FROM agent_messages m
+         JOIN agent_sessions s ON s.id = m.session_id
+         WHERE s.conversation_key = ?1
+         ORDER BY m.id DESC",
+    )?;
+
+    let rows = stmt.query_map([conversation_key], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+    })?;
+
+    let mut history = AgentHistory::default();
+    for row in rows {
+        let (kind, payload) = row?;
+        match kind.as_str() {
+            AGENT_ROW_MESSAGE if (history.messages.len() as i64) < MAX_AGENT_ROWS_PER_SESSION => {
+                if let Ok(message) = serde_json::from_str(&payload) {
+                    history.messages.push(message);
+                }
+            }
+            AGENT_ROW_TURN if (history.turns.len() as i64) < MAX_AGENT_ROWS_PER_SESSION => {
+                if let Ok(turn) = serde_json::from_str(&payload) {
+                    history.turns.push(turn);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    history.messages.reverse();
+    history.turns.reverse();
+    Ok(history)
+}
+
+/// Delete all stored history of the conversation an agent session belongs to,
+/// returning the number of rows removed
+pub fn delete_agent_history(conn: &Connection, session_id: &str) -> SqliteResult<usize> {
+    let conversation_sessions = "SELECT id FROM agent_sessions WHERE conversation_key = (
+             SELECT conversation_key FROM agent_sessions WHERE id = ?1
+         )";
+    let deleted = conn.execute(
+        &format!(
+            "DELETE FROM agent_messages WHERE session_id = ?1 OR session_id IN ({})",
+            conversation_sessions
+        ),
+        [session_id],
+    )?;
+    // Keep this session's row so the rest of its conversation is still saved
+    conn.execute(
+        &format!(
+            "DELETE FROM agent_sessions WHERE id != ?1 AND id IN ({})",
+            conversation_sessions
+        ),
+        [session_id],
+    )?;
+    Ok(deleted)
+}
+
+// ============================================================================
+// Danger Rule Database Functions
+// ============================================================================
+
+/// Get all user-defined danger rules, oldest first
+pub fn get_danger_rules(conn: &Connection) -> SqliteResult<Vec<DangerRule>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, pattern, is_regex, level, warning FROM danger_rules ORDER BY created_at, id",
+    )?;
+
+    let rules = stmt
+        .query_map([], |row| {
+            let level: String = row.get(3)?;
+            Ok(DangerRule {
+                id: row.get(0)?,
+                pattern: row.get(1)?,
+                is_regex: row.get::<_, i32>(2)? != 0,
+                level: level.parse().unwrap_or(DangerLevel::Dangerous),
+                warning: row.get(4)?,
+            })
+        })?
+        .collect::<SqliteResult<Vec<_>>>()?;
+
+    Ok(rules)
+}
+
+/// Insert a danger rule
+pub fn insert_danger_rule(conn: &Connection, rule: &DangerRule) -> SqliteResult<()> {
+    let now = chrono::Utc::now().to_rfc3339();
+
+    conn.execute(
+        "INSERT INTO danger_rules (id, pattern, is_regex, level, warning, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        (
+            &rule.id,
+            &rule.pattern,
+            rule.is_regex as i32,
+            rule.level.to_string(),
+            &rule.warning,
+            &now,
+        ),
+    )?;
+
+    Ok(())
+}
+
+/// Delete a danger rule
+pub fn delete_danger_rule(conn: &Connection, id: &str) -> SqliteResult<bool> {
+    let rows = conn.execute("DELETE FROM danger_rules WHERE id = ?1", [id])?;
+    Ok(rows > 0)
+}
+
+#[cfg(test)]
+mod db_tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn setup_db() -> Connection {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let conn = init_database(&db_path).unwrap();
+        // Leak the dir so it lives for the lifetime of the test
+        std::mem::forget(dir);
+        conn
+    }
+
+    fn test_cipher() -> CredentialCipher {
+        CredentialCipher::from_key(&CredentialCipher::generate_key()).unwrap()
+    }
+
+    fn insert_credential_system(conn: &Connection, id: &str) {
+        let system = ContainerSystem {
+            id: SystemId(id.to_string()),
+            name: id.to_string(),
+            hostname: "host".to_string(),
+            connection_type: ConnectionType::Remote,
+            primary_runtime: ContainerRuntime::Docker,
+            available_runtimes: HashSet::new(),
+            ssh_config: None,
+            auto_connect: false,
+            tags: Vec::new(),
+            command_timeout_secs: None,
+            last_connected_at: None,
+        };
+        insert_system(conn, &system).unwrap();
+    }
+
+    #[test]
+    fn test_init_database_creates_tables() {
+        let conn = setup_db();
+        // Verify tables exist by querying them
+        let count: i64 = conn.query_row("SELECT COUNT(*) FROM systems", [], |row| row.get(0)).unwrap();
+        assert_eq!(count, 0);
+
+        let count: i64 = conn.query_row("SELECT COUNT(*) FROM command_templates", [], |row| row.get(0)).unwrap();
+        // Built-in templates should be seeded
+        assert!(count > 0);
+    }
+
+    #[test]
+    fn test_init_database_records_schema_version() {
+        let conn = setup_db();
+        assert_eq!(schema_version(&conn).unwrap(), SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn test_migrations_upgrade_unversioned_database() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("legacy.db");
+        {
+            // A pre-versioning database: some ad-hoc columns applied, others not
+            let conn = Connection::open(&db_path).unwrap();
+            conn.execute(
+                "CREATE TABLE ai_settings (
+                    id INTEGER PRIMARY KEY CHECK (id = 1),
+                    provider TEXT NOT NULL DEFAULT 'ollama',
+                    api_key TEXT,
+                    model_name TEXT NOT NULL DEFAULT 'llama3.2',
+                    endpoint_url TEXT NOT NULL DEFAULT 'http://localhost:11434',
+                    temperature REAL NOT NULL DEFAULT 0.3,
+                    max_tokens INTEGER NOT NULL DEFAULT 256,
+                    memory_enabled INTEGER NOT NULL DEFAULT 1,
+                    created_at TEXT NOT NULL,
+                    updated_at TEXT NOT NULL
+                )",
+                [],
+            )
+            .unwrap();
+            conn.execute(
+                "CREATE TABLE app_settings (
+                    id INTEGER PRIMARY KEY CHECK (id = 1),
+                    ssh_config_path TEXT,
+                    created_at TEXT NOT NULL,
+                    updated_at TEXT NOT NULL
+                )",
+                [],
+            )
+            .unwrap();
+            conn.execute(
+                "INSERT INTO app_settings (id, ssh_config_path, created_at, updated_at)
+                 VALUES (1, '/home/me/.ssh/config', 'now', 'now')",
+                [],
+            )
+            .unwrap();
+        }
+
+        let conn = init_database(&db_path).unwrap();
+        assert_eq!(schema_version(&conn).unwrap(), SCHEMA_VERSION);
+
+        let settings = get_ai_settings(&conn).unwrap();
+        assert!(settings.memory_enabled);
+
+        let paths: String = conn
+            .query_row("SELECT ssh_config_paths FROM app_settings WHERE id = 1", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(paths, r#"["/home/me/.ssh/config"]"#);
+
+        // Re-opening an up-to-date database is a no-op
+        drop(conn);
+        let conn = init_database(&db_path).unwrap();
+        assert_eq!(schema_version(&conn).unwrap(), SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn test_init_database_rejects_newer_schema() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("future.db");
+        {
+            let conn = Connection::open(&db_path).unwrap();
+            conn.pragma_update(None, "user_version", SCHEMA_VERSION + 1).unwrap();
+        }
+
+        let err = init_database(&db_path).unwrap_err();
+        assert!(err.to_string().contains("newer than supported"));
+    }
+
+    #[test]
+    fn test_system_crud() {
+        let conn = setup_db();
+
+        let system = ContainerSystem {
+            id: SystemId("test-sys-1".to_string()),
+            name: "Test Server".to_string(),
+            hostname: "192.168.1.100".to_string(),
+            connection_type: ConnectionType::Remote,
+            primary_runtime: ContainerRuntime::Docker,
+            available_runtimes: HashSet::from([ContainerRuntime::Docker]),
+            ssh_config: None,
+            auto_connect: false,
+            tags: Vec::new(),
+            command_timeout_secs: None,
+            last_connected_at: None,
+        };
+
+        // Insert
+        insert_system(&conn, &system).unwrap();
+
+        // Read
+        let systems = get_all_systems(&conn).unwrap();
+        assert_eq!(systems.len(), 1);
+        assert_eq!(systems[0].name, "Test Server");
+        assert_eq!(systems[0].hostname, "192.168.1.100");
+        assert_eq!(systems[0].connection_type, ConnectionType::Remote);
+
+        // Update
+        let mut updated = system.clone();
+        updated.name = "Updated Server".to_string();
+        updated.tags = vec!["prod".to_string(), "eu-west".to_string()];
+        updated.command_timeout_secs = Some(300);
         let result = update_system(&conn, &updated).unwrap();
         assert!(result);
 
         let systems = get_all_systems(&conn).unwrap();
         assert_eq!(systems[0].name, "Updated Server");
+        assert_eq!(
+            systems[0].tags,
+            vec!["prod".to_string(), "eu-west".to_string()]
+        );
+        assert_eq!(systems[0].command_timeout_secs, Some(300));
 
         // Delete
         let deleted = delete_system(&conn, "test-sys-1").unwrap();
@@ -882,6 +1713,9 @@ mod db_tests {
                 ..Default::default()
             }),
             auto_connect: true,
+            tags: Vec::new(),
+            command_timeout_secs: None,
+            last_connected_at: None,
         };
 
         insert_system(&conn, &system).unwrap();
@@ -907,6 +1741,9 @@ mod db_tests {
             available_runtimes: HashSet::from([ContainerRuntime::Docker]),
             ssh_config: None,
             auto_connect: false,
+            tags: Vec::new(),
+            command_timeout_secs: None,
+            last_connected_at: None,
         };
 
         insert_system(&conn, &system).unwrap();
@@ -917,6 +1754,40 @@ mod db_tests {
         let systems = get_all_systems(&conn).unwrap();
         assert!(systems[0].available_runtimes.contains(&ContainerRuntime::Docker));
         assert!(systems[0].available_runtimes.contains(&ContainerRuntime::Podman));
+        assert_eq!(systems[0].last_connected_at, None);
+    }
+
+    #[test]
+    fn test_update_system_last_connected() {
+        let conn = setup_db();
+
+        let mut system = ContainerSystem {
+            id: SystemId("lc-sys".to_string()),
+            name: "Server".to_string(),
+            hostname: "localhost".to_string(),
+            connection_type: ConnectionType::Local,
+            primary_runtime: ContainerRuntime::Docker,
+            available_runtimes: HashSet::from([ContainerRuntime::Docker]),
+            ssh_config: None,
+            auto_connect: false,
+            tags: Vec::new(),
+            command_timeout_secs: None,
+            last_connected_at: None,
+        };
+        insert_system(&conn, &system).unwrap();
+
+        update_system_last_connected(&conn, "lc-sys", 1_700_000_000).unwrap();
+        assert_eq!(
+            get_all_systems(&conn).unwrap()[0].last_connected_at,
+            Some(1_700_000_000)
+        );
+
+        // Editing the system doesn't clear the hint
+        system.name = "Renamed".to_string();
+        update_system(&conn, &system).unwrap();
+        let systems = get_all_systems(&conn).unwrap();
+        assert_eq!(systems[0].name, "Renamed");
+        assert_eq!(systems[0].last_connected_at, Some(1_700_000_000));
     }
 
     #[test]
@@ -928,6 +1799,7 @@ mod db_tests {
             name: "My Command".to_string(),
             description: "A custom command".to_string(),
             command: "docker ps -a".to_string(),
+            steps: None,
             category: crate::models::command_template::CommandCategory::ContainerManagement,
             tags: vec!["docker".to_string()],
             variables: vec![],
@@ -939,6 +1811,8 @@ mod db_tests {
             is_built_in: false,
             created_at: "2024-01-01T00:00:00Z".to_string(),
             updated_at: "2024-01-01T00:00:00Z".to_string(),
+            use_count: 0,
+            last_used_at: None,
         };
 
         insert_command_template(&conn, &template).unwrap();
@@ -967,6 +1841,35 @@ mod db_tests {
         assert!(retrieved.is_none());
     }
 
+    #[test]
+    fn test_composite_template_steps_persist() {
+        use crate::models::command_template::TemplateStep;
+
+        let conn = setup_db();
+        let mut template = get_all_command_templates(&conn)
+            .unwrap()
+            .into_iter()
+            .find(|t| t.is_built_in)
+            .unwrap();
+        assert!(template.steps.is_none());
+
+        template.id = "composite-1".to_string();
+        template.is_built_in = false;
+        template.steps = Some(vec![TemplateStep {
+            description: "Stop".to_string(),
+            command: "docker stop web".to_string(),
+        }]);
+        insert_command_template(&conn, &template).unwrap();
+
+        let stored = get_command_template(&conn, "composite-1").unwrap().unwrap();
+        assert_eq!(stored.steps, template.steps);
+
+        template.steps = None;
+        update_command_template(&conn, &template).unwrap();
+        let stored = get_command_template(&conn, "composite-1").unwrap().unwrap();
+        assert!(stored.steps.is_none());
+    }
+
     #[test]
     fn test_builtin_templates_not_deletable() {
         let conn = setup_db();
@@ -999,6 +1902,85 @@ mod db_tests {
         assert_eq!(after2.is_favorite, initial_fav);
     }
 
+    #[test]
+    fn test_record_template_use() {
+        let conn = setup_db();
+        let builtin = get_all_command_templates(&conn)
+            .unwrap()
+            .into_iter()
+            .find(|t| t.is_built_in)
+            .unwrap();
+        assert_eq!(builtin.use_count, 0);
+        assert!(builtin.last_used_at.is_none());
+
+        assert!(record_template_use(&conn, &builtin.id).unwrap());
+        assert!(record_template_use(&conn, &builtin.id).unwrap());
+        assert!(!record_template_use(&conn, "missing").unwrap());
+
+        let used = get_command_template(&conn, &builtin.id).unwrap().unwrap();
+        assert_eq!(used.use_count, 2);
+        assert!(used.last_used_at.is_some());
+        assert_eq!(used.updated_at, builtin.updated_at);
+
+        // Re-seeding built-ins on startup keeps the counter
+        seed_built_in_templates(&conn).unwrap();
+        let reseeded = get_command_template(&conn, &builtin.id).unwrap().unwrap();
+        assert_eq!(reseeded.use_count, 2);
+    }
+
+    #[test]
+    fn test_import_command_templates() {
+        let conn = setup_db();
+        let builtin = get_all_command_templates(&conn)
+            .unwrap()
+            .into_iter()
+            .find(|t| t.is_built_in)
+            .unwrap();
+
+        let custom = |name: &str, command: &str| CommandTemplate {
+            id: "exported-id".to_string(),
+            name: name.to_string(),
+            command: command.to_string(),
+            is_built_in: false,
+            ..builtin.clone()
+        };
+
+        let result = import_command_templates(
+            &conn,
+            vec![custom("Deploy", "echo v1"), custom(&builtin.name, "rm -rf /")],
+            false,
+        )
+        .unwrap();
+        assert_eq!(result.imported, 1);
+        assert_eq!(result.skipped, vec![builtin.name.clone()]);
+
+        let all = get_all_command_templates(&conn).unwrap();
+        let deploy = all.iter().find(|t| t.name == "Deploy").unwrap();
+        assert_ne!(deploy.id, "exported-id");
+        assert!(!deploy.is_built_in);
+
+        // Without overwrite, same-named templates are skipped
+        let result =
+            import_command_templates(&conn, vec![custom("Deploy", "echo v2")], false).unwrap();
+        assert_eq!(result.skipped, vec!["Deploy".to_string()]);
+
+        // With overwrite, the existing template keeps its ID but takes the new content
+        let result = import_command_templates(
+            &conn,
+            vec![custom("Deploy", "echo v2"), custom(&builtin.name, "rm -rf /")],
+            true,
+        )
+        .unwrap();
+        assert_eq!(result.overwritten, 1);
+        assert_eq!(result.skipped, vec![builtin.name.clone()]);
+        let updated = get_command_template(&conn, &deploy.id).unwrap().unwrap();
+        assert_eq!(updated.command, "echo v2");
+
+        // Built-in templates are never overwritten
+        let untouched = get_command_template(&conn, &builtin.id).unwrap().unwrap();
+        assert_eq!(untouched.command, builtin.command);
+    }
+
     #[test]
     fn test_ai_settings_default() {
         let conn = setup_db();
@@ -1022,7 +2004,10 @@ mod db_tests {
             memory_enabled: true,
             summary_model: Some("gpt-4o-mini".to_string()),
             summary_max_tokens: 200,
+            summary_min_input_length: 300,
             api_version: None,
+            max_retries: 4,
+            request_timeout_secs: 900,
         };
 
         upsert_ai_settings(&conn, &settings).unwrap();
@@ -1034,6 +2019,9 @@ mod db_tests {
         assert_eq!(retrieved.max_tokens, 1024);
         assert!(retrieved.memory_enabled);
         assert_eq!(retrieved.summary_model.as_deref(), Some("gpt-4o-mini"));
+        assert_eq!(retrieved.max_retries, 4);
+        assert_eq!(retrieved.summary_min_input_length, 300);
+        assert_eq!(retrieved.request_timeout_secs, 900);
     }
 
     #[test]
@@ -1073,14 +2061,18 @@ mod db_tests {
             available_runtimes: HashSet::new(),
             ssh_config: None,
             auto_connect: false,
+            tags: Vec::new(),
+            command_timeout_secs: None,
+            last_connected_at: None,
         };
         insert_system(&conn, &system).unwrap();
 
         // Store credentials
-        store_ssh_credentials(&conn, "cred-sys", Some("mypassword"), None, None).unwrap();
+        let cipher = test_cipher();
+        store_ssh_credentials(&conn, &cipher, "cred-sys", Some("mypassword"), None, None).unwrap();
 
         // Retrieve
-        let creds = get_ssh_credentials(&conn, "cred-sys").unwrap();
+        let creds = get_ssh_credentials(&conn, Some(&cipher), "cred-sys").unwrap();
         assert_eq!(creds.password.as_deref(), Some("mypassword"));
         assert!(creds.passphrase.is_none());
         assert!(creds.private_key.is_none());
@@ -1099,12 +2091,16 @@ mod db_tests {
             available_runtimes: HashSet::new(),
             ssh_config: None,
             auto_connect: false,
+            tags: Vec::new(),
+            command_timeout_secs: None,
+            last_connected_at: None,
         };
         insert_system(&conn, &system).unwrap();
 
-        store_ssh_credentials(&conn, "key-sys", None, Some("my-passphrase"), Some("PEM-KEY-DATA")).unwrap();
+        let cipher = test_cipher();
+        store_ssh_credentials(&conn, &cipher, "key-sys", None, Some("my-passphrase"), Some("PEM-KEY-DATA")).unwrap();
 
-        let creds = get_ssh_credentials(&conn, "key-sys").unwrap();
+        let creds = get_ssh_credentials(&conn, Some(&cipher), "key-sys").unwrap();
         assert!(creds.password.is_none());
         assert_eq!(creds.passphrase.as_deref(), Some("my-passphrase"));
         assert_eq!(creds.private_key.as_deref(), Some("PEM-KEY-DATA"));
@@ -1113,7 +2109,7 @@ mod db_tests {
     #[test]
     fn test_ssh_credentials_nonexistent_returns_default() {
         let conn = setup_db();
-        let creds = get_ssh_credentials(&conn, "nonexistent").unwrap();
+        let creds = get_ssh_credentials(&conn, Some(&test_cipher()), "nonexistent").unwrap();
         assert!(creds.password.is_none());
         assert!(creds.passphrase.is_none());
         assert!(creds.private_key.is_none());
@@ -1132,16 +2128,59 @@ mod db_tests {
             available_runtimes: HashSet::new(),
             ssh_config: None,
             auto_connect: false,
+            tags: Vec::new(),
+            command_timeout_secs: None,
+            last_connected_at: None,
         };
         insert_system(&conn, &system).unwrap();
 
-        store_ssh_credentials(&conn, "del-sys", Some("pass"), None, None).unwrap();
+        let cipher = test_cipher();
+        store_ssh_credentials(&conn, &cipher, "del-sys", Some("pass"), None, None).unwrap();
         delete_ssh_credentials(&conn, "del-sys").unwrap();
 
-        let creds = get_ssh_credentials(&conn, "del-sys").unwrap();
+        let creds = get_ssh_credentials(&conn, Some(&cipher), "del-sys").unwrap();
         assert!(creds.password.is_none());
     }
 
+    #[test]
+    fn test_ssh_credentials_are_encrypted_at_rest() {
+        let conn = setup_db();
+        insert_credential_system(&conn, "enc-sys");
+        let cipher = test_cipher();
+        store_ssh_credentials(&conn, &cipher, "enc-sys", Some("mypassword"), None, None).unwrap();
+
+        let stored: String = conn
+            .query_row("SELECT password_enc FROM ssh_credentials WHERE system_id = 'enc-sys'", [], |row| row.get(0))
+            .unwrap();
+        assert!(credential_crypto::is_encrypted(&stored));
+        assert!(!stored.contains("mypassword"));
+
+        // Without the keychain key, or with another one, nothing can be read
+        assert!(get_ssh_credentials(&conn, None, "enc-sys").unwrap().password.is_none());
+        assert!(get_ssh_credentials(&conn, Some(&test_cipher()), "enc-sys").unwrap().password.is_none());
+    }
+
+    #[test]
+    fn test_encrypt_legacy_ssh_credentials() {
+        let conn = setup_db();
+        insert_credential_system(&conn, "old-sys");
+        conn.execute(
+            "INSERT INTO ssh_credentials (system_id, password_enc, passphrase_enc) VALUES ('old-sys', ?1, NULL)",
+            [obfuscate("legacy-pass")],
+        )
+        .unwrap();
+
+        let cipher = test_cipher();
+        assert_eq!(encrypt_legacy_ssh_credentials(&conn, &cipher).unwrap(), 1);
+        // Already migrated rows are left alone
+        assert_eq!(encrypt_legacy_ssh_credentials(&conn, &cipher).unwrap(), 0);
+
+        assert!(get_ssh_credentials(&conn, None, "old-sys").unwrap().password.is_none());
+        let creds = get_ssh_credentials(&conn, Some(&cipher), "old-sys").unwrap();
+        assert_eq!(creds.password.as_deref(), Some("legacy-pass"));
+        assert!(creds.passphrase.is_none());
+    }
+
     #[test]
     fn test_obfuscation_roundtrip() {
         let test_cases = vec![
@@ -1182,12 +2221,16 @@ mod db_tests {
         let settings = AppSettings {
             ssh_config_paths: vec!["/home/user/.ssh/config".to_string()],
             last_seen_version: None,
+            metrics_exporter_port: Some(9464),
+            terminal_idle_timeout_mins: Some(45),
         };
         upsert_app_settings(&conn, &settings).unwrap();
 
         let retrieved = get_app_settings(&conn).unwrap();
         assert_eq!(retrieved.ssh_config_paths.len(), 1);
         assert_eq!(retrieved.ssh_config_paths[0], "/home/user/.ssh/config");
+        assert_eq!(retrieved.metrics_exporter_port, Some(9464));
+        assert_eq!(retrieved.terminal_idle_timeout_mins, Some(45));
     }
 
     #[test]
@@ -1198,6 +2241,7 @@ mod db_tests {
         assert!(!prefs.show_thinking_process);
         assert!(!prefs.confirm_all_commands);
         assert_eq!(prefs.max_auto_execute_steps, 5);
+        assert_eq!(prefs.auto_approve_level, DangerLevel::Moderate);
     }
 
     #[test]
@@ -1212,6 +2256,7 @@ mod db_tests {
             confirmation_timeout_secs: 60,
             preferred_shell: Some("/bin/zsh".to_string()),
             dangerous_command_patterns: vec!["rm -rf".to_string()],
+            auto_approve_level: DangerLevel::Safe,
         };
         update_agent_preferences(&conn, &prefs).unwrap();
 
@@ -1221,6 +2266,318 @@ mod db_tests {
         assert_eq!(retrieved.max_auto_execute_steps, 10);
         assert_eq!(retrieved.preferred_shell.as_deref(), Some("/bin/zsh"));
         assert_eq!(retrieved.dangerous_command_patterns.len(), 1);
+        assert_eq!(retrieved.auto_approve_level, DangerLevel::Safe);
+    }
+
+    #[test]
+    fn test_alert_config_defaults_when_unset() {
+        let conn = setup_db();
+        let config = get_alert_config(&conn, "sys-1").unwrap();
+        assert_eq!(config, AlertConfig::default());
+    }
+
+    #[test]
+    fn test_alert_config_upsert() {
+        let conn = setup_db();
+
+        // First insert a system (FK constraint)
+        let system = ContainerSystem {
+            id: SystemId("sys-1".to_string()),
+            name: "Alert System".to_string(),
+            hostname: "host".to_string(),
+            connection_type: ConnectionType::Remote,
+            primary_runtime: ContainerRuntime::Docker,
+            available_runtimes: HashSet::new(),
+            ssh_config: None,
+            auto_connect: false,
+            tags: Vec::new(),
+            command_timeout_secs: None,
+            last_connected_at: None,
+        };
+        insert_system(&conn, &system).unwrap();
+
+        let config = AlertConfig {
+            cpu_pct: Some(90.0),
+            memory_pct: None,
+            disk_pct: Some(85),
+            swap_pct: Some(50.0),
+        };
+        upsert_alert_config(&conn, "sys-1", &config).unwrap();
+        assert_eq!(get_alert_config(&conn, "sys-1").unwrap(), config);
+
+        let updated = AlertConfig {
+            cpu_pct: None,
+            memory_pct: Some(75.0),
+            ..config
+        };
+        upsert_alert_config(&conn, "sys-1", &updated).unwrap();
+        assert_eq!(get_alert_config(&conn, "sys-1").unwrap(), updated);
+    }
+
+    // ========================================================================
+    // Agent history tests
+    // ========================================================================
+
+    fn make_turn(input: &str, timestamp: i64) -> ConversationTurn {
+        ConversationTurn {
+            user_input: input.to_string(),
+            tool_calls: vec![],
+            ai_response: Some(format!("re: {}", input)),
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn test_agent_history_empty_for_unknown_conversation() {
+        let conn = setup_db();
+        let history = get_agent_history(&conn, "sys-x/bash").unwrap();
+        assert!(history.messages.is_empty());
+        assert!(history.turns.is_empty());
+    }
+
+    #[test]
+    fn test_agent_history_round_trip_across_sessions() {
+        let conn = setup_db();
+        // Each terminal gets a fresh id; the conversation key ties them together
+        upsert_agent_session(&conn, "agent-1", "term-1", "sys-1/bash", 1).unwrap();
+        upsert_agent_session(&conn, "agent-2", "term-2", "sys-1/bash", 2).unwrap();
+        upsert_agent_session(&conn, "agent-3", "term-3", "sys-1/web/sh", 3).unwrap();
+
+        append_agent_turn(&conn, "agent-1", &make_turn("first", 10)).unwrap();
+        append_agent_turn(&conn, "agent-2", &make_turn("second", 20)).unwrap();
+        append_agent_turn(&conn, "agent-3", &make_turn("other", 30)).unwrap();
+        let message = ConversationMessage {
+            id: "m1".to_string(),
+            role: crate::agent::session::MessageRole::User,
+            content: "first".to_string(),
+            timestamp: 10,
+            tool_calls: None,
+        };
+        append_agent_message(&conn, "agent-1", &message).unwrap();
+
+        let history = get_agent_history(&conn, "sys-1/bash").unwrap();
+        let inputs: Vec<&str> = history.turns.iter().map(|t| t.user_input.as_str()).collect();
+        assert_eq!(inputs, vec!["first", "second"]);
+        assert_eq!(history.messages.len(), 1);
+        assert_eq!(history.messages[0].content, "first");
+    }
+
+    #[test]
+    fn test_agent_history_capped_per_session() {
+        let conn = setup_db();
+        upsert_agent_session(&conn, "agent-1", "term-1", "sys-1/bash", 1).unwrap();
+        for i in 0..(MAX_AGENT_ROWS_PER_SESSION + 5) {
+            append_agent_turn(&conn, "agent-1", &make_turn(&format!("turn {}", i), i)).unwrap();
+        }
+
+        let stored: i64 = conn
+            .query_row("SELECT COUNT(*) FROM agent_messages", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(stored, MAX_AGENT_ROWS_PER_SESSION);
+
+        let history = get_agent_history(&conn, "sys-1/bash").unwrap();
+        assert_eq!(history.turns.first().unwrap().user_input, "turn 5");
+    }
+
+    #[test]
+    fn test_agent_sessions_capped_with_their_rows() {
+        let conn = setup_db();
+        for i in 0..(MAX_AGENT_SESSIONS + 3) {
+            let session_id = format!("agent-{}", i);
+            conn.execute(
+                "INSERT INTO agent_sessions (id, terminal_session_id, conversation_key, created_at, last_activity)
+                 VALUES (?1, 'term', ?2, 0, 0)",
+                (&session_id, format!("sys-{}/bash", i)),
+            )
+            .unwrap();
+            append_agent_turn(&conn, &session_id, &make_turn("hi", i)).unwrap();
+            // Backdate so the new session below is the most recently active
+            conn.execute(
+                "UPDATE agent_sessions SET last_activity = ?2 WHERE id = ?1",
+                (&session_id, i),
+            )
+            .unwrap();
+        }
+        // The newest session triggers pruning of the least recently active
+        upsert_agent_session(&conn, "agent-new", "term", "sys-new/bash", 0).unwrap();
+
+        let sessions: i64 = conn
+            .query_row("SELECT COUNT(*) FROM agent_sessions", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(sessions, MAX_AGENT_SESSIONS);
+        let orphans: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM agent_messages
+                 WHERE session_id NOT IN (SELECT id FROM agent_sessions)",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(orphans, 0);
+        assert!(get_agent_history(&conn, "sys-new/bash").is_ok());
+    }
+
+    #[test]
+    fn test_danger_rules_crud() {
+        let conn = setup_db();
+        assert!(get_danger_rules(&conn).unwrap().is_empty());
+
+        let rule = DangerRule {
+            id: "rule-1".to_string(),
+            pattern: r"wipe-\w+".to_string(),
+            is_regex: true,
+            level: DangerLevel::Critical,
+            warning: "Wipes an environment".to_string(),
+        };
+        insert_danger_rule(&conn, &rule).unwrap();
+        assert_eq!(get_danger_rules(&conn).unwrap(), vec![rule]);
+
+        assert!(delete_danger_rule(&conn, "rule-1").unwrap());
+        assert!(!delete_danger_rule(&conn, "rule-1").unwrap());
+        assert!(get_danger_rules(&conn).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_delete_agent_history() {
+        let conn = setup_db();
+        upsert_agent_session(&conn, "agent-1", "term-1", "sys-1/bash", 1).unwrap();
+        upsert_agent_session(&conn, "agent-2", "term-2", "sys-1/bash", 2).unwrap();
+        upsert_agent_session(&conn, "agent-3", "term-3", "sys-2/bash", 3).unwrap();
+        append_agent_turn(&conn, "agent-1", &make_turn("a", 1)).unwrap();
+        append_agent_turn(&conn, "agent-2", &make_turn("b", 2)).unwrap();
+        append_agent_turn(&conn, "agent-3", &make_turn("c", 3)).unwrap();
+
+        // Clearing from the current session removes the whole conversation
+        assert_eq!(delete_agent_history(&conn, "agent-2").unwrap(), 2);
+        assert!(get_agent_history(&conn, "sys-1/bash").unwrap().turns.is_empty());
+        assert_eq!(get_agent_history(&conn, "sys-2/bash").unwrap().turns.len(), 1);
+
+        // The current session keeps saving under the conversation
+        append_agent_turn(&conn, "agent-2", &make_turn("d", 4)).unwrap();
+        assert_eq!(get_agent_history(&conn, "sys-1/bash").unwrap().turns.len(), 1);
+    }
+
+    fn make_system(id: &str) -> ContainerSystem {
+        ContainerSystem {
+            id: SystemId(id.to_string()),
+            name: id.to_string(),
+            hostname: "localhost".to_string(),
+            connection_type: ConnectionType::Local,
+            primary_runtime: ContainerRuntime::Docker,
+            available_runtimes: HashSet::from([ContainerRuntime::Docker]),
+            ssh_config: None,
+            auto_connect: false,
+            tags: Vec::new(),
+            command_timeout_secs: None,
+            last_connected_at: None,
+        }
+    }
+
+    #[test]
+    fn test_backup_and_restore_roundtrip() {
+        let mut conn = setup_db();
+        insert_system(&conn, &make_system("before-backup")).unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let backup_path = dir.path().join("backup.db");
+        backup_database(&conn, &backup_path).unwrap();
+        assert_eq!(validate_backup(&backup_path).unwrap(), SCHEMA_VERSION);
+
+        insert_system(&conn, &make_system("after-backup")).unwrap();
+        assert_eq!(get_all_systems(&conn).unwrap().len(), 2);
+
+        restore_database(&mut conn, &backup_path).unwrap();
+        let systems = get_all_systems(&conn).unwrap();
+        assert_eq!(systems.len(), 1);
+        assert_eq!(systems[0].id.0, "before-backup");
+        assert_eq!(schema_version(&conn).unwrap(), SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn test_restore_rejects_invalid_files() {
+        let mut conn = setup_db();
+        insert_system(&conn, &make_system("keep-me")).unwrap();
+        let dir = tempfile::tempdir().unwrap();
+
+        let text_file = dir.path().join("notes.txt");
+        std::fs::write(&text_file, "definitely not sqlite, just some text padding it out").unwrap();
+        assert!(restore_database(&mut conn, &text_file).is_err());
+
+        let foreign_db = dir.path().join("other.db");
+        Connection::open(&foreign_db)
+            .unwrap()
+            .execute("CREATE TABLE notes (body TEXT)", [])
+            .unwrap();
+        let err = restore_database(&mut conn, &foreign_db).unwrap_err();
+        assert!(err.contains("missing table"));
+
+        let newer_db = dir.path().join("newer.db");
+        backup_database(&conn, &newer_db).unwrap();
+        Connection::open(&newer_db)
+            .unwrap()
+            .pragma_update(None, "user_version", SCHEMA_VERSION + 1)
+            .unwrap();
+        let err = restore_database(&mut conn, &newer_db).unwrap_err();
+        assert!(err.contains("newer than supported"));
+
+        assert!(validate_backup(&dir.path().join("missing.db")).is_err());
+
+        // The live database is untouched by rejected restores
+        assert_eq!(get_all_systems(&conn).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_command_history_prunes_per_system() {
+        let conn = setup_db();
+        insert_system(&conn, &make_system("sys-a")).unwrap();
+        insert_system(&conn, &make_system("sys-b")).unwrap();
+        for i in 0..5 {
+            record_command_history(&conn, "sys-a", &format!("echo {}", i), Some(0), 10, 3).unwrap();
+        }
+        record_command_history(&conn, "sys-b", "docker ps", Some(1), 25, 3).unwrap();
+
+        let history = get_command_history(&conn, Some("sys-a"), 10, None).unwrap();
+        let commands: Vec<_> = history.iter().map(|e| e.command.as_str()).collect();
+        assert_eq!(commands, vec!["echo 4", "echo 3", "echo 2"]);
+
+        // Pruning one system leaves the others alone
+        let all = get_command_history(&conn, None, 10, None).unwrap();
+        assert_eq!(all.len(), 4);
+        assert_eq!(all[0].system_id, "sys-b");
+        assert_eq!(all[0].exit_code, Some(1));
+        assert_eq!(all[0].duration_ms, 25);
+
+        assert_eq!(get_command_history(&conn, None, 2, None).unwrap().len(), 2);
+
+        // Deleting a system drops its history
+        delete_system(&conn, "sys-a").unwrap();
+        assert!(get_command_history(&conn, Some("sys-a"), 10, None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_command_history_search_and_clear() {
+        let conn = setup_db();
+        insert_system(&conn, &make_system("sys-a")).unwrap();
+        insert_system(&conn, &make_system("sys-b")).unwrap();
+        record_command_history(&conn, "sys-a", "docker logs web_1", Some(0), 5, 100).unwrap();
+        record_command_history(&conn, "sys-a", "docker logs web-2", None, 5, 100).unwrap();
+        record_command_history(&conn, "sys-b", "DOCKER PS", Some(0), 5, 100).unwrap();
+
+        let found = get_command_history(&conn, None, 10, Some("docker")).unwrap();
+        assert_eq!(found.len(), 3);
+
+        // LIKE wildcards in the search text match literally
+        let found = get_command_history(&conn, None, 10, Some("web_")).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].command, "docker logs web_1");
+
+        let found = get_command_history(&conn, Some("sys-a"), 10, Some("ps")).unwrap();
+        assert!(found.is_empty());
+
+        assert_eq!(clear_command_history(&conn, Some("sys-a")).unwrap(), 2);
+        assert_eq!(get_command_history(&conn, None, 10, None).unwrap().len(), 1);
+        assert_eq!(clear_command_history(&conn, None).unwrap(), 1);
+        assert!(get_command_history(&conn, None, 10, None).unwrap().is_empty());
     }
 }
 
@@ -1238,12 +2595,19 @@ pub struct AppSettings {
     /// Last app version the user has seen the "What's New" dialog for
     #[serde(default)]
     pub last_seen_version: Option<String>,
+    /// Port serving Prometheus metrics on 127.0.0.1 (None = exporter off)
+    #[serde(default)]
+    pub metrics_exporter_port: Option<u16>,
+    /// Minutes a terminal may sit idle before it is closed (None = default, 0 = never)
+    #[serde(default)]
+    pub terminal_idle_timeout_mins: Option<u32>,
 }
 
 /// Get app settings from the database (returns default if not set)
 pub fn get_app_settings(conn: &Connection) -> SqliteResult<AppSettings> {
     let mut stmt = conn.prepare(
-        "SELECT ssh_config_paths, last_seen_version FROM app_settings WHERE id = 1",
+        "SELECT ssh_config_paths, last_seen_version, metrics_exporter_port, terminal_idle_timeout_mins
+         FROM app_settings WHERE id = 1",
     )?;
 
     let mut rows = stmt.query([])?;
@@ -1251,10 +2615,17 @@ pub fn get_app_settings(conn: &Connection) -> SqliteResult<AppSettings> {
     if let Some(row) = rows.next()? {
         let paths_json: Option<String> = row.get(0)?;
         let last_seen_version: Option<String> = row.get(1)?;
+        let metrics_exporter_port: Option<u16> = row.get(2)?;
+        let terminal_idle_timeout_mins: Option<u32> = row.get(3)?;
         let ssh_config_paths: Vec<String> = paths_json
             .and_then(|j| serde_json::from_str(&j).ok())
             .unwrap_or_default();
-        Ok(AppSettings { ssh_config_paths, last_seen_version })
+        Ok(AppSettings {
+            ssh_config_paths,
+            last_seen_version,
+            metrics_exporter_port,
+            terminal_idle_timeout_mins,
+        })
     } else {
         Ok(AppSettings::default())
     }
@@ -1267,18 +2638,174 @@ pub fn upsert_app_settings(conn: &Connection, settings: &AppSettings) -> SqliteR
         .unwrap_or_else(|_| "[]".to_string());
 
     conn.execute(
-        "INSERT INTO app_settings (id, ssh_config_paths, last_seen_version, created_at, updated_at)
-         VALUES (1, ?1, ?2, ?3, ?3)
+        "INSERT INTO app_settings (id, ssh_config_paths, last_seen_version, metrics_exporter_port, terminal_idle_timeout_mins, created_at, updated_at)
+         VALUES (1, ?1, ?2, ?3, ?4, ?5, ?5)
          ON CONFLICT(id) DO UPDATE SET
              ssh_config_paths = excluded.ssh_config_paths,
              last_seen_version = excluded.last_seen_version,
+             metrics_exporter_port = excluded.metrics_exporter_port,
+             terminal_idle_timeout_mins = excluded.terminal_idle_timeout_mins,
              updated_at = excluded.updated_at",
         (
             &paths_json,
             &settings.last_seen_version,
+            settings.metrics_exporter_port,
+            settings.terminal_idle_timeout_mins,
             &now,
         ),
     )?;
 
     Ok(())
 }
+
+// ============================================================================
+// Command History Database Functions
+// ============================================================================
+
+/// Number of history rows kept per system; older rows are pruned on insert
+pub const MAX_HISTORY_PER_SYSTEM: usize = 1000;
+
+/// A command that was run on a system
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandHistoryEntry {
+    pub id: i64,
+    pub system_id: String,
+    pub command: String,
+    /// `None` when the command was cancelled or failed to start
+    pub exit_code: Option<i32>,
+    pub duration_ms: u64,
+    pub executed_at: String,
+}
+
+/// Append a command to a system's history, pruning the oldest rows beyond `max_per_system`
+pub fn record_command_history(
+    conn: &Connection,
+    system_id: &str,
+    command: &str,
+    exit_code: Option<i32>,
+    duration_ms: u64,
+    max_per_system: usize,
+) -> SqliteResult<()> {
+    let now = chrono::Utc::now().to_rfc3339();
+
+    conn.execute(
+        "INSERT INTO command_history (system_id, command, exit_code, duration_ms, executed_at)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        (system_id, command, exit_code, duration_ms as i64, &now),
+    )?;
+
+    conn.execute(
+        "DELETE FROM command_history WHERE system_id = ?1 AND id NOT IN (
+             SELECT id FROM command_history WHERE system_id = ?1 ORDER BY id DESC LIMIT ?2
+         )",
+        (system_id, max_per_system as i64),
+    )?;
+
+    Ok(())
+}
+
+/// Get the most recent history entries, newest first, optionally for one system
+/// and/or only commands containing `search` (case-insensitive)
+pub fn get_command_history(
+    conn: &Connection,
+    system_id: Option<&str>,
+    limit: usize,
+    search: Option<&str>,
+) -> SqliteResult<Vec<CommandHistoryEntry>> {
+    let pattern = search.filter(|s| !s.is_empty()).map(|s| {
+        let escaped = s.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+        format!("%{}%", escaped)
+    });
+
+    let mut stmt = conn.prepare(
+        "SELECT id, system_id, command, exit_code, duration_ms, executed_at
+         FROM command_history
+         WHERE (?1 IS NULL OR system_id = ?1)
+           AND (?2 IS NULL OR command LIKE ?2 ESCAPE '\\')
+         ORDER BY id DESC
+         LIMIT ?3",
+    )?;
+
+    let entries = stmt
+        .query_map((system_id, pattern, limit as i64), |row| {
+            Ok(CommandHistoryEntry {
+                id: row.get(0)?,
+                system_id: row.get(1)?,
+                command: row.get(2)?,
+                exit_code: row.get(3)?,
+                duration_ms: row.get::<_, i64>(4)? as u64,
+                executed_at: row.get(5)?,
+            })
+        })?
+        .collect::<SqliteResult<Vec<_>>>()?;
+
+    Ok(entries)
+}
+
+/// Delete history for one system, or for all systems; returns the number of rows removed
+pub fn clear_command_history(conn: &Connection, system_id: Option<&str>) -> SqliteResult<usize> {
+    conn.execute(
+        "DELETE FROM command_history WHERE ?1 IS NULL OR system_id = ?1",
+        [system_id],
+    )
+}
+
+// ============================================================================
+// Backup & Restore Database Functions
+// ============================================================================
+
+/// Tables a file must have to be accepted as a Containerus database
+const REQUIRED_TABLES: &[&str] = &["systems", "command_templates", "ai_settings", "app_settings"];
+
+/// Copy the live database to `dest` using SQLite's online backup API, which is
+/// safe while the app is running. The schema version travels in the copy's
+/// header (`user_version`).
+pub fn backup_database(conn: &Connection, dest: &Path) -> SqliteResult<()> {
+    conn.backup(DatabaseName::Main, dest, None)
+}
+
+/// Check that `path` is a readable Containerus database this build can load,
+/// returning its schema version
+pub fn validate_backup(path: &Path) -> Result<u32, String> {
+    let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(|e| format!("Cannot open backup: {}", e))?;
+
+    let version =
+        schema_version(&conn).map_err(|e| format!("Not a valid database file: {}", e))?;
+    if version > SCHEMA_VERSION {
+        return Err(format!(
+            "Backup schema version {} is newer than supported version {}",
+            version, SCHEMA_VERSION
+        ));
+    }
+
+    for table in REQUIRED_TABLES {
+        let exists: bool = conn
+            .query_row(
+                "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type = 'table' AND name = ?1",
+                [table],
+                |row| row.get(0),
+            )
+            .map_err(|e| format!("Not a valid database file: {}", e))?;
+        if !exists {
+            return Err(format!("Not a Containerus database: missing table '{}'", table));
+        }
+    }
+
+    Ok(version)
+}
+
+/// Replace the live database contents with a validated backup, then migrate it
+/// to the current schema
+pub fn restore_database(conn: &mut Connection, src: &Path) -> Result<(), String> {
+    validate_backup(src)?;
+
+    conn.restore(DatabaseName::Main, src, None::<fn(Progress)>)
+        .map_err(|e| format!("Failed to restore backup: {}", e))?;
+    run_migrations(conn).map_err(|e| format!("Failed to migrate restored database: {}", e))?;
+    seed_built_in_templates(conn)
+        .map_err(|e| format!("Failed to seed restored database: {}", e))?;
+
+    Ok(())
+}
@@ -1,8 +1,11 @@
 use async_trait::async_trait;
 use std::process::Stdio;
 use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
 use tokio::process::Command;
+use tokio::sync::mpsc;
 use tokio::time::timeout;
+use tokio_stream::wrappers::UnboundedReceiverStream;
 
 #[cfg(target_os = "windows")]
 use std::os::windows::process::CommandExt;
@@ -11,16 +14,27 @@ use std::os::windows::process::CommandExt;
 #[cfg(target_os = "windows")]
 const CREATE_NO_WINDOW: u32 = 0x08000000;
 
-use super::{CommandExecutor, CommandResult};
+use super::{CommandExecutor, CommandResult, ExecContext, OutputChunk, OutputStream};
 use crate::models::error::ContainerError;
-use crate::models::system::{ConnectionType, ContainerSystem};
+use crate::models::system::{ConnectionType, ContainerSystem, DEFAULT_COMMAND_TIMEOUT_SECS};
 
 /// Executor for local command execution using std::process
-pub struct LocalExecutor;
+pub struct LocalExecutor {
+    /// Limit applied by `execute`; None lets commands run indefinitely
+    command_timeout: Option<Duration>,
+}
 
 impl LocalExecutor {
     pub fn new() -> Self {
-        Self
+        Self {
+            command_timeout: Some(Duration::from_secs(DEFAULT_COMMAND_TIMEOUT_SECS)),
+        }
+    }
+
+    /// Replace the limit applied by `execute` (None disables it)
+    pub fn with_timeout(mut self, command_timeout: Option<Duration>) -> Self {
+        self.command_timeout = command_timeout;
+        self
     }
 
     /// Get the shell and shell argument for the current platform
@@ -68,8 +82,9 @@ impl LocalExecutor {
         }
     }
 
-    async fn execute_internal(&self, command: &str) -> Result<CommandResult, ContainerError> {
-        let start = Instant::now();
+    /// Build a shell invocation of `command` with piped stdout/stderr
+    fn shell_command(command: &str, context: &ExecContext) -> Result<Command, ContainerError> {
+        context.validate()?;
         let (shell, shell_arg) = Self::get_shell_command();
 
         let mut cmd = Command::new(shell);
@@ -79,9 +94,27 @@ impl LocalExecutor {
             .stdout(Stdio::piped())
             .stderr(Stdio::piped());
 
+        if let Some(cwd) = &context.cwd {
+            cmd.current_dir(cwd);
+        }
+        cmd.envs(&context.env);
+
         #[cfg(target_os = "windows")]
         cmd.creation_flags(CREATE_NO_WINDOW);
 
+        Ok(cmd)
+    }
+
+    async fn execute_internal(
+        &self,
+        command: &str,
+        context: &ExecContext,
+    ) -> Result<CommandResult, ContainerError> {
+        let start = Instant::now();
+        let mut cmd = Self::shell_command(command, context)?;
+        // A timed-out command is dropped mid-wait; make sure the process goes with it
+        cmd.kill_on_drop(true);
+
         let output = cmd.output().await.map_err(|e| {
             ContainerError::CommandExecutionFailed {
                 command: command.to_string(),
@@ -100,6 +133,49 @@ impl LocalExecutor {
         })
     }
 
+    /// Spawn `command` and forward its output line by line until it exits
+    ///
+    /// The child is killed if the receiving side of the stream goes away.
+    fn spawn_streaming(command: &str, context: &ExecContext) -> Result<OutputStream, ContainerError> {
+        let mut child = Self::shell_command(command, context)?
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| ContainerError::CommandExecutionFailed {
+                command: command.to_string(),
+                exit_code: -1,
+                stderr: e.to_string(),
+            })?;
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        let readers = [
+            child
+                .stdout
+                .take()
+                .map(|out| tokio::spawn(forward_lines(out, tx.clone(), OutputChunk::Stdout))),
+            child
+                .stderr
+                .take()
+                .map(|err| tokio::spawn(forward_lines(err, tx.clone(), OutputChunk::Stderr))),
+        ];
+
+        tokio::spawn(async move {
+            let exit_code = tokio::select! {
+                status = child.wait() => status.ok().and_then(|s| s.code()).unwrap_or(-1),
+                _ = tx.closed() => {
+                    let _ = child.kill().await;
+                    return;
+                }
+            };
+            // Drain whatever the readers still hold before reporting the exit
+            for reader in readers.into_iter().flatten() {
+                let _ = reader.await;
+            }
+            let _ = tx.send(OutputChunk::Exit(exit_code));
+        });
+
+        Ok(Box::pin(UnboundedReceiverStream::new(rx)))
+    }
+
     /// Execute a PowerShell command (Windows only, but callable on any platform)
     pub async fn execute_powershell(&self, command: &str) -> Result<CommandResult, ContainerError> {
         let start = Instant::now();
@@ -159,6 +235,29 @@ impl LocalExecutor {
     }
 }
 
+/// Send each line read from `reader` (newline included) until EOF or the stream closes
+async fn forward_lines<R>(
+    reader: R,
+    tx: mpsc::UnboundedSender<OutputChunk>,
+    wrap: fn(String) -> OutputChunk,
+) where
+    R: AsyncRead + Unpin,
+{
+    let mut reader = BufReader::new(reader);
+    let mut line = Vec::new();
+    loop {
+        line.clear();
+        match reader.read_until(b'\n', &mut line).await {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {
+                if tx.send(wrap(String::from_utf8_lossy(&line).to_string())).is_err() {
+                    break;
+                }
+            }
+        }
+    }
+}
+
 impl Default for LocalExecutor {
     fn default() -> Self {
         Self::new()
@@ -168,7 +267,10 @@ impl Default for LocalExecutor {
 #[async_trait]
 impl CommandExecutor for LocalExecutor {
     async fn execute(&self, command: &str) -> Result<CommandResult, ContainerError> {
-        self.execute_internal(command).await
+        match self.command_timeout {
+            Some(limit) => self.execute_with_timeout(command, limit).await,
+            None => self.execute_internal(command, &ExecContext::default()).await,
+        }
     }
 
     async fn execute_with_timeout(
@@ -176,7 +278,8 @@ impl CommandExecutor for LocalExecutor {
         command: &str,
         timeout_duration: Duration,
     ) -> Result<CommandResult, ContainerError> {
-        match timeout(timeout_duration, self.execute_internal(command)).await {
+        let context = ExecContext::default();
+        match timeout(timeout_duration, self.execute_internal(command, &context)).await {
             Ok(result) => result,
             Err(_) => Err(ContainerError::NetworkTimeout(format!(
                 "Command timed out after {}ms: {}",
@@ -186,6 +289,22 @@ impl CommandExecutor for LocalExecutor {
         }
     }
 
+    async fn execute_in_dir(
+        &self,
+        command: &str,
+        context: &ExecContext,
+    ) -> Result<CommandResult, ContainerError> {
+        self.execute_internal(command, context).await
+    }
+
+    async fn execute_stream(
+        &self,
+        command: &str,
+        context: &ExecContext,
+    ) -> Result<OutputStream, ContainerError> {
+        Self::spawn_streaming(command, context)
+    }
+
     fn can_execute(&self, system: &ContainerSystem) -> bool {
         system.connection_type == ConnectionType::Local
     }
@@ -214,4 +333,143 @@ mod tests {
         let result = executor.execute("exit 1").await.unwrap();
         assert!(!result.success());
     }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_local_executor_cuts_off_at_deadline() {
+        let executor = LocalExecutor::new().with_timeout(Some(Duration::from_millis(200)));
+        let started = Instant::now();
+        let err = executor.execute("sleep 5").await.unwrap_err();
+        assert!(matches!(err, ContainerError::NetworkTimeout(_)));
+        assert!(started.elapsed() < Duration::from_secs(2));
+
+        // Opting out lets the command finish
+        let executor = LocalExecutor::new().with_timeout(None);
+        assert!(executor.execute("sleep 0.3").await.unwrap().success());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_local_executor_cancellable() {
+        use tokio_util::sync::CancellationToken;
+
+        let executor = LocalExecutor::new();
+        let result = executor
+            .execute_cancellable("echo done", &ExecContext::default(), CancellationToken::new())
+            .await
+            .unwrap();
+        assert!(result.success());
+        assert_eq!(result.stdout.trim(), "done");
+
+        let token = CancellationToken::new();
+        let canceller = token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            canceller.cancel();
+        });
+
+        let start = Instant::now();
+        let err = executor
+            .execute_cancellable("sleep 30", &ExecContext::default(), token)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ContainerError::Cancelled(_)));
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_local_executor_cancellable_reports_output() {
+        use tokio_util::sync::CancellationToken;
+
+        let executor = LocalExecutor::new();
+        let mut seen = String::new();
+        let result = executor
+            .execute_cancellable_with_output(
+                "echo out; echo err >&2",
+                &ExecContext::default(),
+                CancellationToken::new(),
+                &mut |chunk| match chunk {
+                    OutputChunk::Stdout(text) | OutputChunk::Stderr(text) => seen.push_str(text),
+                    OutputChunk::Exit(code) => seen.push_str(&format!("exit {}", code)),
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(result.stdout.trim(), "out");
+        assert_eq!(result.stderr.trim(), "err");
+        assert!(seen.contains("out\n") && seen.contains("err\n"));
+        assert!(seen.ends_with("exit 0"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_local_executor_streaming_keeps_no_output() {
+        use tokio_util::sync::CancellationToken;
+
+        let executor = LocalExecutor::new();
+        let mut seen = String::new();
+        let result = executor
+            .execute_cancellable_streaming(
+                "echo out; exit 3",
+                &ExecContext::default(),
+                CancellationToken::new(),
+                &mut |chunk| {
+                    if let OutputChunk::Stdout(text) = chunk {
+                        seen.push_str(text);
+                    }
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(seen.trim(), "out");
+        assert!(result.stdout.is_empty());
+        assert_eq!(result.exit_code, 3);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_local_executor_in_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let context = ExecContext {
+            cwd: Some(dir.path().to_string_lossy().to_string()),
+            env: [("GREETING".to_string(), "hi there".to_string())].into(),
+        };
+
+        let executor = LocalExecutor::new();
+        let result = executor
+            .execute_in_dir("pwd && echo \"$GREETING\"", &context)
+            .await
+            .unwrap();
+
+        let canonical = dir.path().canonicalize().unwrap();
+        assert!(result.stdout.contains(canonical.to_str().unwrap()));
+        assert!(result.stdout.contains("hi there"));
+    }
+
+    #[tokio::test]
+    async fn test_local_executor_stream() {
+        use futures::StreamExt;
+
+        let executor = LocalExecutor::new();
+        let chunks: Vec<OutputChunk> = executor
+            .execute_stream("echo one && echo two && exit 3", &ExecContext::default())
+            .await
+            .unwrap()
+            .collect()
+            .await;
+
+        let stdout: String = chunks
+            .iter()
+            .filter_map(|chunk| match chunk {
+                OutputChunk::Stdout(text) => Some(text.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert!(stdout.contains("one"));
+        assert!(stdout.contains("two"));
+        assert_eq!(chunks.last(), Some(&OutputChunk::Exit(3)));
+    }
 }
@@ -2,11 +2,16 @@ pub mod local;
 pub mod remote;
 
 use async_trait::async_trait;
+use futures::stream::BoxStream;
+use futures::StreamExt;
 use serde::{Deserialize, Serialize};
-use std::time::Duration;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
 
 use crate::models::error::ContainerError;
 use crate::models::system::{ConnectionType, ContainerSystem};
+use crate::runtime::CommandBuilder;
 
 /// Result of executing a command
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -33,6 +38,75 @@ impl CommandResult {
     }
 }
 
+/// Working directory and extra environment a command should run with
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecContext {
+    #[serde(default)]
+    pub cwd: Option<String>,
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+}
+
+impl ExecContext {
+    /// Reject environment variable names a POSIX shell can't `export`
+    pub fn validate(&self) -> Result<(), ContainerError> {
+        for name in self.env.keys() {
+            let mut chars = name.chars();
+            let valid = chars
+                .next()
+                .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+                && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+            if !valid {
+                return Err(ContainerError::InvalidConfiguration(format!(
+                    "Invalid environment variable name: {}",
+                    name
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Prefix `command` with `cd` and `export` so a plain shell honors the context
+    ///
+    /// The command is not run if the directory can't be entered.
+    pub fn wrap_command(&self, command: &str) -> String {
+        let mut prefix = String::new();
+        if let Some(cwd) = &self.cwd {
+            prefix.push_str(&format!("cd {} || exit 1; ", CommandBuilder::shell_escape(cwd)));
+        }
+
+        let mut vars: Vec<_> = self.env.iter().collect();
+        vars.sort();
+        for (name, value) in vars {
+            prefix.push_str(&format!(
+                "export {}={}; ",
+                name,
+                CommandBuilder::shell_escape(value)
+            ));
+        }
+
+        format!("{}{}", prefix, command)
+    }
+}
+
+/// A piece of a running command's output, delivered as soon as it is read
+///
+/// Output chunks are not guaranteed to be whole lines. `Exit` is always the
+/// last item of a stream that ran to completion.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "stream", content = "data", rename_all = "camelCase")]
+pub enum OutputChunk {
+    Stdout(String),
+    Stderr(String),
+    Exit(i32),
+}
+
+/// Live output of a command started with [`CommandExecutor::execute_stream`]
+///
+/// Dropping the stream stops the command.
+pub type OutputStream = BoxStream<'static, OutputChunk>;
+
 /// Trait for command execution (local or remote)
 #[async_trait]
 pub trait CommandExecutor: Send + Sync {
@@ -46,6 +120,103 @@ pub trait CommandExecutor: Send + Sync {
         timeout: Duration,
     ) -> Result<CommandResult, ContainerError>;
 
+    /// Execute a command in the given working directory with extra environment variables
+    async fn execute_in_dir(
+        &self,
+        command: &str,
+        context: &ExecContext,
+    ) -> Result<CommandResult, ContainerError>;
+
+    /// Start a command and stream its output as it is produced
+    async fn execute_stream(
+        &self,
+        command: &str,
+        context: &ExecContext,
+    ) -> Result<OutputStream, ContainerError>;
+
+    /// Execute a command until it finishes or `token` is cancelled
+    ///
+    /// Cancelling drops the output stream, which kills the local child process
+    /// or closes the SSH channel, and returns [`ContainerError::Cancelled`].
+    async fn execute_cancellable(
+        &self,
+        command: &str,
+        context: &ExecContext,
+        token: CancellationToken,
+    ) -> Result<CommandResult, ContainerError> {
+        self.execute_cancellable_with_output(command, context, token, &mut |_| {})
+            .await
+    }
+
+    /// Like [`CommandExecutor::execute_cancellable`], but also hands each output chunk
+    /// to `on_output` as it arrives (e.g. to report progress)
+    async fn execute_cancellable_with_output(
+        &self,
+        command: &str,
+        context: &ExecContext,
+        token: CancellationToken,
+        // The `for<'c>` is spelled out so async_trait doesn't bind it to the method's lifetimes
+        on_output: &mut (dyn for<'c> FnMut(&'c OutputChunk) + Send),
+    ) -> Result<CommandResult, ContainerError> {
+        let mut stdout = String::new();
+        let mut stderr = String::new();
+        let mut result = self
+            .execute_cancellable_streaming(command, context, token, &mut |chunk| {
+                match chunk {
+                    OutputChunk::Stdout(text) => stdout.push_str(text),
+                    OutputChunk::Stderr(text) => stderr.push_str(text),
+                    OutputChunk::Exit(_) => {}
+                }
+                on_output(chunk);
+            })
+            .await?;
+
+        result.stdout = stdout;
+        result.stderr = stderr;
+        Ok(result)
+    }
+
+    /// Like [`CommandExecutor::execute_cancellable_with_output`], but output is only
+    /// handed to `on_output` and never collected: the returned result has empty
+    /// `stdout` and `stderr`
+    ///
+    /// Use this for commands that may run indefinitely (`logs -f`, `events`), whose
+    /// output would otherwise grow in memory for as long as they run.
+    async fn execute_cancellable_streaming(
+        &self,
+        command: &str,
+        context: &ExecContext,
+        token: CancellationToken,
+        on_output: &mut (dyn for<'c> FnMut(&'c OutputChunk) + Send),
+    ) -> Result<CommandResult, ContainerError> {
+        let start = Instant::now();
+        let mut output = self.execute_stream(command, context).await?;
+
+        let mut exit_code = -1;
+        loop {
+            let chunk = tokio::select! {
+                chunk = output.next() => chunk,
+                _ = token.cancelled() => return Err(ContainerError::Cancelled(command.to_string())),
+            };
+            match chunk {
+                Some(chunk) => {
+                    if let OutputChunk::Exit(code) = chunk {
+                        exit_code = code;
+                    }
+                    on_output(&chunk);
+                }
+                None => break,
+            }
+        }
+
+        Ok(CommandResult {
+            stdout: String::new(),
+            stderr: String::new(),
+            exit_code,
+            execution_time_ms: start.elapsed().as_millis() as u64,
+        })
+    }
+
     /// Check if this executor can handle the given system
     fn can_execute(&self, system: &ContainerSystem) -> bool;
 
@@ -56,7 +227,9 @@ pub trait CommandExecutor: Send + Sync {
 /// Factory function to get the appropriate executor for a system
 pub fn get_executor_for_system(system: &ContainerSystem) -> Box<dyn CommandExecutor> {
     match system.connection_type {
-        ConnectionType::Local => Box::new(local::LocalExecutor::new()),
+        ConnectionType::Local => {
+            Box::new(local::LocalExecutor::new().with_timeout(system.command_timeout()))
+        }
         ConnectionType::Remote => Box::new(remote::RemoteExecutor::new(system.id.0.clone())),
     }
 }
@@ -86,4 +259,49 @@ mod tests {
         };
         assert!(!result.success());
     }
+
+    #[test]
+    fn test_output_chunk_serialization() {
+        let chunk = OutputChunk::Stdout("hello\n".to_string());
+        let json = serde_json::to_value(&chunk).unwrap();
+        assert_eq!(json, serde_json::json!({ "stream": "stdout", "data": "hello\n" }));
+
+        let exit = serde_json::to_value(OutputChunk::Exit(2)).unwrap();
+        assert_eq!(exit, serde_json::json!({ "stream": "exit", "data": 2 }));
+    }
+
+    #[test]
+    fn test_exec_context_wrap_command() {
+        assert_eq!(ExecContext::default().wrap_command("ls"), "ls");
+
+        let context = ExecContext {
+            cwd: Some("/srv/my app".to_string()),
+            env: HashMap::from([
+                ("TAG".to_string(), "it's".to_string()),
+                ("DOCKER_HOST".to_string(), "unix:///run/docker.sock".to_string()),
+            ]),
+        };
+        assert_eq!(
+            context.wrap_command("docker build ."),
+            "cd '/srv/my app' || exit 1; export DOCKER_HOST='unix:///run/docker.sock'; \
+             export TAG='it'\\''s'; docker build ."
+        );
+    }
+
+    #[test]
+    fn test_exec_context_rejects_invalid_env_names() {
+        let valid = ExecContext {
+            cwd: None,
+            env: HashMap::from([("_BUILD_2".to_string(), "1".to_string())]),
+        };
+        assert!(valid.validate().is_ok());
+
+        for name in ["", "2FAST", "A-B", "X; rm -rf /"] {
+            let context = ExecContext {
+                cwd: None,
+                env: HashMap::from([(name.to_string(), "1".to_string())]),
+            };
+            assert!(context.validate().is_err(), "{:?} should be rejected", name);
+        }
+    }
 }
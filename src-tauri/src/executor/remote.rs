@@ -1,7 +1,7 @@
 use async_trait::async_trait;
 use std::time::Duration;
 
-use super::{CommandExecutor, CommandResult};
+use super::{CommandExecutor, CommandResult, ExecContext, OutputStream};
 use crate::models::error::ContainerError;
 use crate::models::system::{ConnectionType, ContainerSystem};
 
@@ -33,15 +33,26 @@ impl CommandExecutor for RemoteExecutor {
         command: &str,
         timeout_duration: Duration,
     ) -> Result<CommandResult, ContainerError> {
-        // SSH has its own timeout mechanisms, but we can wrap it
-        match tokio::time::timeout(timeout_duration, self.execute(command)).await {
-            Ok(result) => result,
-            Err(_) => Err(ContainerError::NetworkTimeout(format!(
-                "SSH command timed out after {}ms: {}",
-                timeout_duration.as_millis(),
-                command
-            ))),
-        }
+        crate::ssh::execute_on_system_with_timeout(&self.system_id, command, Some(timeout_duration))
+            .await
+    }
+
+    async fn execute_in_dir(
+        &self,
+        command: &str,
+        context: &ExecContext,
+    ) -> Result<CommandResult, ContainerError> {
+        context.validate()?;
+        self.execute(&context.wrap_command(command)).await
+    }
+
+    async fn execute_stream(
+        &self,
+        command: &str,
+        context: &ExecContext,
+    ) -> Result<OutputStream, ContainerError> {
+        context.validate()?;
+        crate::ssh::execute_stream_on_system(&self.system_id, &context.wrap_command(command)).await
     }
 
     fn can_execute(&self, system: &ContainerSystem) -> bool {
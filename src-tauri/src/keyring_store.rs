@@ -71,6 +71,9 @@ pub struct CredentialVault {
     pub ssh_credentials: HashMap<String, SshCredentials>,
     #[serde(default)]
     pub ai_api_keys: HashMap<String, String>,
+    /// Base64 key encrypting credentials that are kept in the database
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credential_key: Option<String>,
 }
 
 impl std::fmt::Debug for CredentialVault {
@@ -79,6 +82,7 @@ impl std::fmt::Debug for CredentialVault {
             .field("version", &self.version)
             .field("ssh_credentials", &format!("{} systems", self.ssh_credentials.len()))
             .field("ai_api_keys", &format!("{} keys", self.ai_api_keys.len()))
+            .field("credential_key", &self.credential_key.as_ref().map(|_| "[REDACTED]"))
             .finish()
     }
 }
@@ -1,6 +1,7 @@
 pub mod agent;
 pub mod ai;
 pub mod commands;
+pub mod credential_crypto;
 pub mod credential_migration;
 pub mod database;
 pub mod executor;
@@ -42,6 +43,7 @@ pub fn run() {
 
             // Initialize AppState with database
             let app_state = state::AppState::new(db_path);
+            app_state.set_app_handle(app.handle().clone());
             app.manage(app_state);
 
             // Migrate credentials from DB to vault (desktop only).
@@ -55,13 +57,15 @@ pub fn run() {
                     credential_migration::migrate_credentials_to_keychain(&conn)
                 };
 
-                let vault = match migrated_vault {
-                    Some(v) => v,
-                    None => keyring_store::load_vault().unwrap_or_else(|e| {
-                        tracing::warn!("Failed to load vault from keyring: {e}");
-                        Default::default()
-                    }),
+                let loaded = match migrated_vault {
+                    Some(v) => Ok(v),
+                    None => keyring_store::load_vault(),
                 };
+                let vault_readable = loaded.is_ok();
+                let vault = loaded.unwrap_or_else(|e| {
+                    tracing::warn!("Failed to load vault from keyring: {e}");
+                    Default::default()
+                });
 
                 for (id, creds) in &vault.ssh_credentials {
                     state.cache_ssh_credentials(id, creds.clone());
@@ -74,10 +78,84 @@ pub fn run() {
                     vault.ssh_credentials.len(),
                     vault.ai_api_keys.len()
                 );
+
+                // Key for credentials kept in the database. A new one is only
+                // created when the vault could be read, so an existing key is
+                // never replaced because of a transient keyring failure.
+                match vault.credential_key.as_deref() {
+                    Some(key) => match credential_crypto::CredentialCipher::from_key(key) {
+                        Ok(cipher) => state.set_credential_cipher(cipher),
+                        Err(e) => tracing::error!("Credential key in vault is invalid: {e}"),
+                    },
+                    None if vault_readable => {
+                        let key = credential_crypto::CredentialCipher::generate_key();
+                        let updated = keyring_store::CredentialVault {
+                            credential_key: Some(key.clone()),
+                            ..vault.clone()
+                        };
+                        match keyring_store::save_vault(&updated) {
+                            Ok(()) => match credential_crypto::CredentialCipher::from_key(&key) {
+                                Ok(cipher) => state.set_credential_cipher(cipher),
+                                Err(e) => tracing::error!("Generated credential key is invalid: {e}"),
+                            },
+                            Err(e) => tracing::error!("Failed to save credential key: {e}"),
+                        }
+                    }
+                    None => {}
+                }
+            }
+
+            // Android has no vault; the key lives in the keychain plugin's store
+            #[cfg(target_os = "android")]
+            {
+                use tauri_plugin_keychain::{KeychainExt, KeychainRequest};
+
+                let state = app.state::<AppState>();
+                let keychain = app.get_item();
+                let request = |password: Option<String>| KeychainRequest {
+                    key: Some(credential_crypto::KEYCHAIN_KEY_NAME.to_string()),
+                    password,
+                };
+
+                let key = match keychain.get_item(request(None)) {
+                    // A missing entry comes back as an empty password
+                    Ok(response) => match response.password.filter(|k| !k.is_empty()) {
+                        Some(key) => Ok(key),
+                        None => {
+                            let key = credential_crypto::CredentialCipher::generate_key();
+                            keychain
+                                .save_item(request(Some(key.clone())))
+                                .map(|_| key)
+                                .map_err(|e| e.to_string())
+                        }
+                    },
+                    Err(e) => Err(e.to_string()),
+                };
+                match key.and_then(|k| credential_crypto::CredentialCipher::from_key(&k)) {
+                    Ok(cipher) => state.set_credential_cipher(cipher),
+                    Err(e) => tracing::error!("Failed to load credential key from keychain: {e}"),
+                }
+            }
+
+            // Re-encrypt credentials older versions stored with obfuscation only
+            {
+                let state = app.state::<AppState>();
+                if let Some(cipher) = state.credential_cipher() {
+                    let conn = state.db.lock().unwrap();
+                    match database::encrypt_legacy_ssh_credentials(&conn, cipher) {
+                        Ok(0) => {}
+                        Ok(n) => tracing::info!("Encrypted stored credentials for {} system(s)", n),
+                        Err(e) => tracing::warn!("Failed to encrypt stored credentials: {}", e),
+                    }
+                }
             }
 
             // Initialize terminal sessions
             app.manage(commands::terminal::TerminalSessions::default());
+            commands::terminal::spawn_idle_reaper(app.handle().clone());
+
+            // Track executions the frontend can cancel
+            app.manage(commands::exec::RunningExecutions::default());
 
             // Initialize agent session manager
             app.manage(agent::AgentSessionManager::new());
@@ -88,6 +166,31 @@ pub fn run() {
             // Initialize monitoring manager
             app.manage(monitoring::MonitoringManager::new());
 
+            // Initialize connection health poller (started on request)
+            app.manage(monitoring::health::HealthPoller::new());
+
+            // Serve Prometheus metrics if the exporter was left on
+            app.manage(monitoring::prometheus::MetricsExporter::new());
+            let exporter_port = {
+                let state = app.state::<AppState>();
+                let conn = state.db.lock().unwrap();
+                database::get_app_settings(&conn)
+                    .ok()
+                    .and_then(|s| s.metrics_exporter_port)
+            };
+            if let Some(port) = exporter_port {
+                let handle = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    let exporter = handle.state::<monitoring::prometheus::MetricsExporter>();
+                    if let Err(e) = exporter.apply(handle.clone(), Some(port)).await {
+                        tracing::warn!("Failed to start metrics exporter: {}", e);
+                    }
+                });
+            }
+
+            // Surface transparent SSH reconnects to the frontend
+            commands::system::watch_ssh_reconnects(app.handle().clone());
+
             Ok(())
         })
         .plugin(tauri_plugin_opener::init())
@@ -99,15 +202,25 @@ pub fn run() {
         .invoke_handler(tauri::generate_handler![
             // System commands
             commands::add_system,
+            commands::export_systems,
+            commands::import_systems,
             commands::update_system,
             commands::remove_system,
             commands::list_systems,
+            commands::list_systems_by_tag,
             commands::connect_system,
             commands::disconnect_system,
+            commands::connect_systems,
+            commands::disconnect_systems,
             commands::get_connection_state,
+            commands::pool_stats,
+            commands::set_pool_max_connections,
             commands::store_ssh_credentials,
             commands::get_ssh_credentials,
             commands::import_ssh_key_from_file,
+            commands::import_ssh_key_from_content,
+            commands::get_ssh_key_fingerprint,
+            commands::list_available_identity_keys,
             commands::get_extended_system_info,
             commands::has_ssh_config,
             commands::list_ssh_config_hosts,
@@ -115,16 +228,28 @@ pub fn run() {
             commands::get_app_settings,
             commands::update_app_settings,
             commands::get_changelog,
+            commands::backup_database,
+            commands::restore_database,
             commands::remove_known_host,
+            commands::trust_host_key,
             // Container commands
             commands::list_containers,
             commands::perform_container_action,
+            commands::perform_container_action_bulk,
+            commands::create_container,
+            commands::rename_container,
+            commands::update_container_restart_policy,
             commands::get_container_logs,
+            commands::stream_container_logs,
+            commands::stop_container_log_stream,
+            commands::stream_docker_events,
+            commands::stop_docker_events,
             commands::inspect_container,
             // Image commands
             commands::list_images,
             commands::pull_image,
             commands::remove_image,
+            commands::inspect_image,
             // Volume commands
             commands::list_volumes,
             commands::create_volume,
@@ -135,18 +260,42 @@ pub fn run() {
             commands::remove_network,
             commands::connect_container_to_network,
             commands::disconnect_container_from_network,
+            // Pod commands (Podman)
+            commands::list_pods,
+            commands::perform_pod_action,
+            // Prune commands
+            commands::prune_images,
+            commands::prune_containers,
+            commands::prune_volumes,
+            commands::prune_networks,
+            commands::prune_system,
             // Runtime detection
             commands::detect_runtimes,
+            // Streaming execution
+            commands::execute_streaming,
+            commands::cancel_execution,
+            commands::execute_on_all_systems,
+            commands::get_command_history,
+            commands::clear_command_history,
             // Terminal commands
             commands::start_terminal_session,
+            commands::start_container_terminal,
             commands::send_terminal_input,
             commands::resize_terminal,
             commands::close_terminal_session,
+            commands::start_terminal_recording,
+            commands::stop_terminal_recording,
+            commands::export_terminal_recording,
+            commands::get_terminal_scrollback,
+            commands::export_terminal_scrollback,
             commands::execute_in_terminal,
             commands::list_terminal_sessions,
+            commands::terminal_session_stats,
             commands::fetch_shell_history,
+            commands::detect_shells,
             // Port forwarding commands
             commands::create_port_forward,
+            commands::create_remote_forward,
             commands::stop_port_forward,
             commands::list_port_forwards,
             commands::get_port_forward,
@@ -160,6 +309,12 @@ pub fn run() {
             commands::delete_command_template,
             commands::toggle_command_favorite,
             commands::duplicate_command_template,
+            commands::record_template_use,
+            commands::validate_template_variables,
+            commands::export_command_templates,
+            commands::import_command_templates,
+            commands::plan_template_steps,
+            commands::run_template_steps,
             // AI assistant commands
             commands::get_ai_settings_cmd,
             commands::update_ai_settings_cmd,
@@ -168,6 +323,7 @@ pub fn run() {
             commands::test_ai_connection,
             commands::test_ai_connection_with_settings,
             commands::get_shell_suggestion,
+            commands::get_shell_suggestion_stream,
             commands::pull_ollama_model,
             commands::delete_ollama_model,
             // Agent commands
@@ -178,11 +334,16 @@ pub fn run() {
             commands::respond_to_confirmation,
             commands::cancel_agent_query,
             commands::close_agent_session,
+            commands::clear_agent_history,
+            commands::export_agent_transcript,
             commands::update_agent_context,
             commands::append_agent_output,
             commands::get_agent_context_summary,
             commands::get_agent_preferences,
             commands::update_agent_preferences,
+            commands::get_danger_rules,
+            commands::add_danger_rule,
+            commands::remove_danger_rule,
             // File browser commands
             commands::list_directory,
             commands::read_file,
@@ -190,14 +351,38 @@ pub fn run() {
             commands::create_directory,
             commands::delete_path,
             commands::rename_path,
+            commands::get_directory_size,
+            commands::get_directory_tree_sizes,
+            commands::search_file_contents,
+            commands::change_permissions,
+            commands::change_owner,
+            commands::create_archive,
+            commands::extract_archive,
+            commands::get_file_size,
+            commands::read_file_range,
+            commands::tail_file,
             commands::download_file,
             commands::upload_file,
+            commands::transfer_between_systems,
             // Monitoring commands
             commands::start_system_monitoring,
             commands::stop_system_monitoring,
             commands::is_system_monitoring,
+            commands::pause_system_monitoring,
+            commands::resume_system_monitoring,
+            commands::is_monitoring_paused,
+            commands::get_monitoring_interval,
             commands::list_monitored_systems,
             commands::get_live_metrics,
+            commands::get_metrics_history,
+            commands::export_metrics_prometheus,
+            commands::get_alert_config,
+            commands::set_alert_config,
+            commands::start_container_monitoring,
+            commands::stop_container_monitoring,
+            // Connection health commands
+            commands::start_health_polling,
+            commands::stop_health_polling,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
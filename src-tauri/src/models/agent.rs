@@ -4,6 +4,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::agent::safety::DangerLevel;
+
 /// User preferences for agent behavior
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -22,6 +24,14 @@ pub struct AgentPreferences {
     pub preferred_shell: Option<String>,
     /// Additional regex patterns to flag as dangerous
     pub dangerous_command_patterns: Vec<String>,
+    /// Highest danger level the agent runs without asking; dangerous and
+    /// critical commands always ask
+    #[serde(default = "default_auto_approve_level")]
+    pub auto_approve_level: DangerLevel,
+}
+
+fn default_auto_approve_level() -> DangerLevel {
+    DangerLevel::Moderate
 }
 
 impl Default for AgentPreferences {
@@ -34,6 +44,7 @@ impl Default for AgentPreferences {
             confirmation_timeout_secs: 300,
             preferred_shell: None,
             dangerous_command_patterns: vec![],
+            auto_approve_level: default_auto_approve_level(),
         }
     }
 }
@@ -131,6 +142,7 @@ mod tests {
         assert_eq!(prefs.confirmation_timeout_secs, 300);
         assert!(prefs.preferred_shell.is_none());
         assert!(prefs.dangerous_command_patterns.is_empty());
+        assert_eq!(prefs.auto_approve_level, DangerLevel::Moderate);
     }
 
     #[test]
@@ -143,15 +155,27 @@ mod tests {
             confirmation_timeout_secs: 60,
             preferred_shell: Some("/bin/zsh".to_string()),
             dangerous_command_patterns: vec!["rm -rf".to_string()],
+            auto_approve_level: DangerLevel::Safe,
         };
 
         let json = serde_json::to_string(&prefs).unwrap();
+        assert!(json.contains("\"autoApproveLevel\":\"safe\""));
         let deserialized: AgentPreferences = serde_json::from_str(&json).unwrap();
         assert!(!deserialized.auto_execute_safe_commands);
         assert!(deserialized.show_thinking_process);
         assert_eq!(deserialized.max_auto_execute_steps, 10);
         assert_eq!(deserialized.preferred_shell.as_deref(), Some("/bin/zsh"));
         assert_eq!(deserialized.dangerous_command_patterns.len(), 1);
+        assert_eq!(deserialized.auto_approve_level, DangerLevel::Safe);
+    }
+
+    #[test]
+    fn test_agent_preferences_without_auto_approve_level() {
+        let mut json = serde_json::to_value(AgentPreferences::default()).unwrap();
+        json.as_object_mut().unwrap().remove("autoApproveLevel");
+
+        let prefs: AgentPreferences = serde_json::from_value(json).unwrap();
+        assert_eq!(prefs.auto_approve_level, DangerLevel::Moderate);
     }
 
     #[test]
@@ -1,7 +1,12 @@
+use std::collections::HashMap;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::models::container::ContainerRuntime;
+use crate::models::error::ContainerError;
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
@@ -16,6 +21,50 @@ pub enum CommandCategory {
     Custom,
 }
 
+/// Expected type of a template variable's value, checked before substitution
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "kind", content = "options", rename_all = "camelCase")]
+pub enum VarType {
+    String,
+    Integer,
+    /// TCP/UDP port number (1-65535)
+    Port,
+    Path,
+    /// One of a fixed set of values
+    Enum(Vec<String>),
+}
+
+impl VarType {
+    /// Check a non-empty value against this type, returning the reason it was rejected
+    pub fn check(&self, value: &str) -> Result<(), String> {
+        match self {
+            VarType::String => Ok(()),
+            VarType::Integer => value
+                .parse::<i64>()
+                .map(|_| ())
+                .map_err(|_| "Must be a whole number".to_string()),
+            VarType::Port => match value.parse::<u16>() {
+                Ok(port) if port > 0 => Ok(()),
+                _ => Err("Must be a port number between 1 and 65535".to_string()),
+            },
+            VarType::Path => {
+                if value.chars().any(char::is_control) {
+                    Err("Path must not contain control characters".to_string())
+                } else {
+                    Ok(())
+                }
+            }
+            VarType::Enum(options) => {
+                if options.iter().any(|o| o == value) {
+                    Ok(())
+                } else {
+                    Err(format!("Must be one of: {}", options.join(", ")))
+                }
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TemplateVariable {
@@ -24,6 +73,25 @@ pub struct TemplateVariable {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub default_value: Option<String>,
     pub required: bool,
+    /// Untyped variables accept any string
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub var_type: Option<VarType>,
+}
+
+/// A variable value that failed validation, reported per variable for inline display
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct VariableValidationError {
+    pub name: String,
+    pub message: String,
+}
+
+/// One command of a composite template
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateStep {
+    pub description: String,
+    pub command: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -50,6 +118,9 @@ pub struct CommandTemplate {
     pub name: String,
     pub description: String,
     pub command: String,
+    /// Ordered sub-commands of a composite template; when present, `command` is ignored
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub steps: Option<Vec<TemplateStep>>,
     pub category: CommandCategory,
     pub tags: Vec<String>,
     pub variables: Vec<TemplateVariable>,
@@ -58,6 +129,11 @@ pub struct CommandTemplate {
     pub is_built_in: bool,
     pub created_at: String,
     pub updated_at: String,
+    /// Number of times the template has been executed
+    #[serde(default)]
+    pub use_count: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_used_at: Option<String>,
 }
 
 impl CommandTemplate {
@@ -76,6 +152,7 @@ impl CommandTemplate {
             name,
             description,
             command,
+            steps: None,
             category,
             tags,
             variables,
@@ -84,6 +161,8 @@ impl CommandTemplate {
             is_built_in: false,
             created_at: now.clone(),
             updated_at: now,
+            use_count: 0,
+            last_used_at: None,
         }
     }
 
@@ -120,6 +199,7 @@ impl CommandTemplate {
             name: name.to_string(),
             description: description.to_string(),
             command: command.to_string(),
+            steps: None,
             category,
             tags: tags.into_iter().map(String::from).collect(),
             variables,
@@ -128,6 +208,8 @@ impl CommandTemplate {
             is_built_in: true,
             created_at: now.clone(),
             updated_at: now,
+            use_count: 0,
+            last_used_at: None,
         }
     }
 
@@ -146,6 +228,7 @@ impl CommandTemplate {
             name: name.to_string(),
             description: description.to_string(),
             command: command.to_string(),
+            steps: None,
             category,
             tags: tags.into_iter().map(String::from).collect(),
             variables,
@@ -157,8 +240,68 @@ impl CommandTemplate {
             is_built_in: true,
             created_at: now.clone(),
             updated_at: now,
+            use_count: 0,
+            last_used_at: None,
+        }
+    }
+
+    /// Steps this template runs, in order. A plain template is a single step running `command`.
+    pub fn execution_steps(&self) -> Vec<TemplateStep> {
+        match &self.steps {
+            Some(steps) if !steps.is_empty() => steps.clone(),
+            _ => vec![TemplateStep {
+                description: self.name.clone(),
+                command: self.command.clone(),
+            }],
         }
     }
+
+    /// Validate variable values against this template's variable definitions.
+    /// An empty result means the values are safe to substitute into the command.
+    pub fn validate_variables(
+        &self,
+        values: &HashMap<String, String>,
+    ) -> Vec<VariableValidationError> {
+        self.variables
+            .iter()
+            .filter_map(|var| {
+                let value = values.get(&var.name).map(String::as_str).unwrap_or("");
+                let result = if value.trim().is_empty() {
+                    if var.required {
+                        Err("Required".to_string())
+                    } else {
+                        Ok(())
+                    }
+                } else {
+                    var.var_type.as_ref().map_or(Ok(()), |t| t.check(value))
+                };
+                result.err().map(|message| VariableValidationError {
+                    name: var.name.clone(),
+                    message,
+                })
+            })
+            .collect()
+    }
+}
+
+static VARIABLE_PLACEHOLDER: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\$\{([A-Z_][A-Z0-9_]*)\}").unwrap());
+
+/// Replace `${NAME}` placeholders with their values, leaving unknown placeholders in place
+pub fn substitute_variables(command: &str, values: &HashMap<String, String>) -> String {
+    VARIABLE_PLACEHOLDER
+        .replace_all(command, |caps: &regex::Captures| {
+            values
+                .get(&caps[1])
+                .cloned()
+                .unwrap_or_else(|| caps[0].to_string())
+        })
+        .into_owned()
+}
+
+/// Check whether a command still contains `${NAME}` placeholders
+pub fn has_unresolved_variables(command: &str) -> bool {
+    VARIABLE_PLACEHOLDER.is_match(command)
 }
 
 // Common variable definitions
@@ -168,6 +311,7 @@ fn var_container_name() -> TemplateVariable {
         description: "Container name or ID".to_string(),
         default_value: None,
         required: true,
+        var_type: None,
     }
 }
 
@@ -177,6 +321,7 @@ fn var_image_name() -> TemplateVariable {
         description: "Image name with optional tag".to_string(),
         default_value: None,
         required: true,
+        var_type: None,
     }
 }
 
@@ -186,6 +331,7 @@ fn var_network_name() -> TemplateVariable {
         description: "Network name".to_string(),
         default_value: None,
         required: true,
+        var_type: None,
     }
 }
 
@@ -195,6 +341,17 @@ fn var_volume_name() -> TemplateVariable {
         description: "Volume name".to_string(),
         default_value: None,
         required: true,
+        var_type: None,
+    }
+}
+
+fn var_namespace() -> TemplateVariable {
+    TemplateVariable {
+        name: "NAMESPACE".to_string(),
+        description: "containerd namespace (k8s.io for Kubernetes, moby for Docker)".to_string(),
+        default_value: Some("default".to_string()),
+        required: true,
+        var_type: None,
     }
 }
 
@@ -204,6 +361,7 @@ fn var_pod_name() -> TemplateVariable {
         description: "Pod name".to_string(),
         default_value: None,
         required: true,
+        var_type: None,
     }
 }
 
@@ -213,6 +371,7 @@ fn var_shell() -> TemplateVariable {
         description: "Shell to execute".to_string(),
         default_value: Some("/bin/sh".to_string()),
         required: true,
+        var_type: None,
     }
 }
 
@@ -222,6 +381,7 @@ fn var_command() -> TemplateVariable {
         description: "Command to execute".to_string(),
         default_value: None,
         required: true,
+        var_type: None,
     }
 }
 
@@ -231,6 +391,7 @@ fn var_host_port() -> TemplateVariable {
         description: "Port on host machine".to_string(),
         default_value: Some("8080".to_string()),
         required: true,
+        var_type: Some(VarType::Port),
     }
 }
 
@@ -240,6 +401,7 @@ fn var_container_port() -> TemplateVariable {
         description: "Port inside container".to_string(),
         default_value: Some("80".to_string()),
         required: true,
+        var_type: Some(VarType::Port),
     }
 }
 
@@ -249,6 +411,7 @@ fn var_host_path() -> TemplateVariable {
         description: "Path on host filesystem".to_string(),
         default_value: Some("./".to_string()),
         required: true,
+        var_type: Some(VarType::Path),
     }
 }
 
@@ -258,6 +421,7 @@ fn var_container_path() -> TemplateVariable {
         description: "Path inside container".to_string(),
         default_value: Some("/app".to_string()),
         required: true,
+        var_type: Some(VarType::Path),
     }
 }
 
@@ -267,6 +431,7 @@ fn var_new_name() -> TemplateVariable {
         description: "New container name".to_string(),
         default_value: None,
         required: true,
+        var_type: None,
     }
 }
 
@@ -276,6 +441,7 @@ fn var_lines() -> TemplateVariable {
         description: "Number of log lines".to_string(),
         default_value: Some("100".to_string()),
         required: true,
+        var_type: Some(VarType::Integer),
     }
 }
 
@@ -285,6 +451,7 @@ fn var_source_image() -> TemplateVariable {
         description: "Source image to tag".to_string(),
         default_value: None,
         required: true,
+        var_type: None,
     }
 }
 
@@ -294,6 +461,7 @@ fn var_target_image() -> TemplateVariable {
         description: "Target image name".to_string(),
         default_value: None,
         required: true,
+        var_type: None,
     }
 }
 
@@ -303,6 +471,7 @@ fn var_build_path() -> TemplateVariable {
         description: "Build context path".to_string(),
         default_value: Some(".".to_string()),
         required: true,
+        var_type: Some(VarType::Path),
     }
 }
 
@@ -312,6 +481,7 @@ fn var_filename() -> TemplateVariable {
         description: "Output filename".to_string(),
         default_value: None,
         required: true,
+        var_type: None,
     }
 }
 
@@ -321,6 +491,7 @@ fn var_search_term() -> TemplateVariable {
         description: "Search query".to_string(),
         default_value: None,
         required: true,
+        var_type: None,
     }
 }
 
@@ -330,6 +501,7 @@ fn var_yaml_file() -> TemplateVariable {
         description: "Path to YAML file".to_string(),
         default_value: None,
         required: true,
+        var_type: Some(VarType::Path),
     }
 }
 
@@ -339,6 +511,12 @@ fn var_runtime() -> TemplateVariable {
         description: "Container runtime (docker/podman)".to_string(),
         default_value: Some("docker".to_string()),
         required: true,
+        var_type: Some(VarType::Enum(
+            ["docker", "podman", "nerdctl", "container"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        )),
     }
 }
 
@@ -348,6 +526,8 @@ pub struct CreateCommandTemplateRequest {
     pub name: String,
     pub description: String,
     pub command: String,
+    #[serde(default)]
+    pub steps: Option<Vec<TemplateStep>>,
     pub category: CommandCategory,
     pub tags: Vec<String>,
     pub variables: Vec<TemplateVariable>,
@@ -363,6 +543,8 @@ pub struct UpdateCommandTemplateRequest {
     pub name: Option<String>,
     pub description: Option<String>,
     pub command: Option<String>,
+    /// An empty list turns a composite template back into a plain one
+    pub steps: Option<Vec<TemplateStep>>,
     pub category: Option<CommandCategory>,
     pub tags: Option<Vec<String>>,
     pub variables: Option<Vec<TemplateVariable>>,
@@ -370,6 +552,94 @@ pub struct UpdateCommandTemplateRequest {
     pub is_favorite: Option<bool>,
 }
 
+/// Ordering for the template list
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum TemplateSortBy {
+    /// Favorites first, then alphabetical
+    #[default]
+    Name,
+    MostUsed,
+    Recent,
+}
+
+impl TemplateSortBy {
+    pub fn sort(self, templates: &mut [CommandTemplate]) {
+        match self {
+            TemplateSortBy::Name => templates.sort_by(|a, b| {
+                b.is_favorite.cmp(&a.is_favorite).then_with(|| a.name.cmp(&b.name))
+            }),
+            TemplateSortBy::MostUsed => templates.sort_by(|a, b| {
+                b.use_count.cmp(&a.use_count).then_with(|| a.name.cmp(&b.name))
+            }),
+            // RFC 3339 UTC timestamps sort lexically; never-used templates go last
+            TemplateSortBy::Recent => templates.sort_by(|a, b| {
+                b.last_used_at
+                    .cmp(&a.last_used_at)
+                    .then_with(|| a.name.cmp(&b.name))
+            }),
+        }
+    }
+}
+
+/// Version of the JSON document written by template export
+pub const TEMPLATE_EXPORT_VERSION: u32 = 1;
+
+/// Versioned JSON document for moving custom templates between machines
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateExport {
+    pub version: u32,
+    pub exported_at: String,
+    pub templates: Vec<CommandTemplate>,
+}
+
+impl TemplateExport {
+    pub fn new(templates: Vec<CommandTemplate>) -> Self {
+        Self {
+            version: TEMPLATE_EXPORT_VERSION,
+            exported_at: chrono::Utc::now().to_rfc3339(),
+            templates,
+        }
+    }
+
+    /// Parse and validate an export document produced by this or an older version
+    pub fn from_json(json: &str) -> Result<Self, ContainerError> {
+        let export: Self = serde_json::from_str(json)
+            .map_err(|e| ContainerError::ParseError(format!("Invalid template export: {}", e)))?;
+
+        if export.version == 0 || export.version > TEMPLATE_EXPORT_VERSION {
+            return Err(ContainerError::InvalidConfiguration(format!(
+                "Unsupported template export version {} (expected {} or lower)",
+                export.version, TEMPLATE_EXPORT_VERSION
+            )));
+        }
+
+        if let Some(t) = export
+            .templates
+            .iter()
+            .find(|t| t.name.trim().is_empty() || t.command.trim().is_empty())
+        {
+            return Err(ContainerError::InvalidConfiguration(format!(
+                "Template '{}' must have a name and a command",
+                t.name
+            )));
+        }
+
+        Ok(export)
+    }
+}
+
+/// Outcome of importing a template export document
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateImportResult {
+    pub imported: usize,
+    pub overwritten: usize,
+    /// Names of templates left untouched because one with the same name already exists
+    pub skipped: Vec<String>,
+}
+
 /// Get the built-in command templates
 pub fn get_built_in_templates() -> Vec<CommandTemplate> {
     let mut templates = Vec::new();
@@ -1492,6 +1762,80 @@ pub fn get_built_in_templates() -> Vec<CommandTemplate> {
         vec![ContainerRuntime::Podman],
     ));
 
+    // =====================================================================
+    // NERDCTL COMMANDS (containerd namespaces)
+    // =====================================================================
+
+    templates.push(CommandTemplate::new_built_in_for_runtime(
+        "List Namespaces (nerdctl)",
+        "List containerd namespaces",
+        "nerdctl namespace ls",
+        CommandCategory::System,
+        vec!["nerdctl", "containerd", "namespace", "list"],
+        vec![],
+        vec![ContainerRuntime::Nerdctl],
+    ));
+
+    templates.push(CommandTemplate::new_built_in_for_runtime(
+        "List Containers in Namespace (nerdctl)",
+        "List all containers in a containerd namespace",
+        "nerdctl --namespace ${NAMESPACE} ps -a",
+        CommandCategory::ContainerManagement,
+        vec!["nerdctl", "containerd", "namespace", "list", "ps"],
+        vec![var_namespace()],
+        vec![ContainerRuntime::Nerdctl],
+    ));
+
+    templates.push(CommandTemplate::new_built_in_for_runtime(
+        "List Images in Namespace (nerdctl)",
+        "List images in a containerd namespace",
+        "nerdctl --namespace ${NAMESPACE} images",
+        CommandCategory::Images,
+        vec!["nerdctl", "containerd", "namespace", "images"],
+        vec![var_namespace()],
+        vec![ContainerRuntime::Nerdctl],
+    ));
+
+    templates.push(CommandTemplate::new_built_in_for_runtime(
+        "Pull Image into Namespace (nerdctl)",
+        "Pull an image into a containerd namespace",
+        "nerdctl --namespace ${NAMESPACE} pull ${IMAGE_NAME}",
+        CommandCategory::Images,
+        vec!["nerdctl", "containerd", "namespace", "pull"],
+        vec![var_namespace(), var_image_name()],
+        vec![ContainerRuntime::Nerdctl],
+    ));
+
+    templates.push(CommandTemplate::new_built_in_for_runtime(
+        "View Logs in Namespace (nerdctl)",
+        "Show the last 100 log lines of a container in a containerd namespace",
+        "nerdctl --namespace ${NAMESPACE} logs --tail 100 ${CONTAINER_NAME}",
+        CommandCategory::Debugging,
+        vec!["nerdctl", "containerd", "namespace", "logs"],
+        vec![var_namespace(), var_container_name()],
+        vec![ContainerRuntime::Nerdctl],
+    ));
+
+    templates.push(CommandTemplate::new_built_in_for_runtime(
+        "Shell into Container in Namespace (nerdctl)",
+        "Open a shell in a container in a containerd namespace",
+        "nerdctl --namespace ${NAMESPACE} exec -it ${CONTAINER_NAME} /bin/sh",
+        CommandCategory::Debugging,
+        vec!["nerdctl", "containerd", "namespace", "exec", "shell"],
+        vec![var_namespace(), var_container_name()],
+        vec![ContainerRuntime::Nerdctl],
+    ));
+
+    templates.push(CommandTemplate::new_built_in_for_runtime(
+        "Remove Namespace (nerdctl)",
+        "Remove an empty containerd namespace",
+        "nerdctl namespace remove ${NAMESPACE}",
+        CommandCategory::System,
+        vec!["nerdctl", "containerd", "namespace", "remove"],
+        vec![var_namespace()],
+        vec![ContainerRuntime::Nerdctl],
+    ));
+
     templates
 }
 
@@ -1612,6 +1956,7 @@ mod tests {
             description: "Port number".to_string(),
             default_value: Some("8080".to_string()),
             required: true,
+            var_type: Some(VarType::Port),
         };
 
         let json = serde_json::to_string(&var).unwrap();
@@ -1630,12 +1975,203 @@ mod tests {
             description: "Container name".to_string(),
             default_value: None,
             required: true,
+            var_type: None,
         };
 
         let json = serde_json::to_string(&var).unwrap();
         assert!(!json.contains("defaultValue"));
     }
 
+    #[test]
+    fn test_execution_steps() {
+        let mut tpl = CommandTemplate::new(
+            "Redeploy".to_string(),
+            String::new(),
+            "ignored".to_string(),
+            CommandCategory::Custom,
+            vec![],
+            vec![],
+            CommandCompatibility::default(),
+        );
+        let steps = tpl.execution_steps();
+        assert_eq!(steps.len(), 1);
+        assert_eq!(steps[0].command, "ignored");
+
+        tpl.steps = Some(vec![
+            TemplateStep {
+                description: "Stop".to_string(),
+                command: "docker stop ${CONTAINER_NAME}".to_string(),
+            },
+            TemplateStep {
+                description: "Remove".to_string(),
+                command: "docker rm ${CONTAINER_NAME}".to_string(),
+            },
+        ]);
+        let steps = tpl.execution_steps();
+        assert_eq!(steps.len(), 2);
+        assert_eq!(steps[1].description, "Remove");
+
+        let json = serde_json::to_string(&tpl).unwrap();
+        assert!(json.contains("\"steps\":[{\"description\":\"Stop\""));
+    }
+
+    #[test]
+    fn test_substitute_variables() {
+        let values = HashMap::from([("CONTAINER_NAME".to_string(), "web".to_string())]);
+        assert_eq!(
+            substitute_variables("docker stop ${CONTAINER_NAME} && docker rm ${CONTAINER_NAME}", &values),
+            "docker stop web && docker rm web"
+        );
+
+        let partial = substitute_variables("docker run -p ${HOST_PORT}:80 ${CONTAINER_NAME}", &values);
+        assert_eq!(partial, "docker run -p ${HOST_PORT}:80 web");
+        assert!(has_unresolved_variables(&partial));
+        assert!(!has_unresolved_variables("echo $HOME ${lowercase}"));
+    }
+
+    #[test]
+    fn test_template_sort_by() {
+        let make = |name: &str, use_count: u32, last_used_at: Option<&str>| CommandTemplate {
+            use_count,
+            last_used_at: last_used_at.map(String::from),
+            ..CommandTemplate::new(
+                name.to_string(),
+                String::new(),
+                "true".to_string(),
+                CommandCategory::Custom,
+                vec![],
+                vec![],
+                CommandCompatibility::default(),
+            )
+        };
+        let mut templates = vec![
+            make("alpha", 1, Some("2026-01-02T00:00:00+00:00")),
+            make("beta", 5, Some("2026-01-01T00:00:00+00:00")),
+            make("gamma", 0, None),
+            make("delta", 5, Some("2026-01-03T00:00:00+00:00")),
+        ];
+        let names = |t: &[CommandTemplate]| t.iter().map(|t| t.name.clone()).collect::<Vec<_>>();
+
+        TemplateSortBy::MostUsed.sort(&mut templates);
+        assert_eq!(names(&templates), ["beta", "delta", "alpha", "gamma"]);
+
+        TemplateSortBy::Recent.sort(&mut templates);
+        assert_eq!(names(&templates), ["delta", "alpha", "beta", "gamma"]);
+
+        templates[3].is_favorite = true;
+        TemplateSortBy::Name.sort(&mut templates);
+        assert_eq!(names(&templates), ["gamma", "alpha", "beta", "delta"]);
+
+        let parsed: TemplateSortBy = serde_json::from_str("\"mostUsed\"").unwrap();
+        assert_eq!(parsed, TemplateSortBy::MostUsed);
+    }
+
+    #[test]
+    fn test_template_export_round_trip() {
+        let mut tpl = CommandTemplate::new(
+            "Deploy".to_string(),
+            "Deploy the app".to_string(),
+            "echo deploy".to_string(),
+            CommandCategory::Custom,
+            vec![],
+            vec![],
+            CommandCompatibility::default(),
+        );
+        tpl.is_favorite = true;
+
+        let json = serde_json::to_string(&TemplateExport::new(vec![tpl])).unwrap();
+        assert!(json.contains("\"version\":1"));
+
+        let parsed = TemplateExport::from_json(&json).unwrap();
+        assert_eq!(parsed.templates.len(), 1);
+        assert_eq!(parsed.templates[0].name, "Deploy");
+        assert!(parsed.templates[0].is_favorite);
+    }
+
+    #[test]
+    fn test_template_export_rejects_invalid_documents() {
+        assert!(matches!(
+            TemplateExport::from_json("not json"),
+            Err(ContainerError::ParseError(_))
+        ));
+        assert!(matches!(
+            TemplateExport::from_json(r#"{"version":99,"exportedAt":"","templates":[]}"#),
+            Err(ContainerError::InvalidConfiguration(_))
+        ));
+
+        let mut export = TemplateExport::new(vec![get_built_in_templates().remove(0)]);
+        export.templates[0].command = "  ".to_string();
+        let json = serde_json::to_string(&export).unwrap();
+        assert!(matches!(
+            TemplateExport::from_json(&json),
+            Err(ContainerError::InvalidConfiguration(_))
+        ));
+    }
+
+    #[test]
+    fn test_var_type_serialization() {
+        let json = serde_json::to_string(&VarType::Port).unwrap();
+        assert_eq!(json, r#"{"kind":"port"}"#);
+
+        let json = serde_json::to_string(&VarType::Enum(vec!["a".to_string()])).unwrap();
+        assert_eq!(json, r#"{"kind":"enum","options":["a"]}"#);
+
+        // Variables stored before var_type existed still deserialize
+        let var: TemplateVariable = serde_json::from_str(
+            r#"{"name":"NAME","description":"","required":true}"#,
+        )
+        .unwrap();
+        assert!(var.var_type.is_none());
+    }
+
+    #[test]
+    fn test_var_type_check() {
+        assert!(VarType::Integer.check("42").is_ok());
+        assert!(VarType::Integer.check("forty").is_err());
+        assert!(VarType::Port.check("1").is_ok());
+        assert!(VarType::Port.check("65535").is_ok());
+        assert!(VarType::Port.check("0").is_err());
+        assert!(VarType::Port.check("65536").is_err());
+        assert!(VarType::Port.check("http").is_err());
+        assert!(VarType::Path.check("/var/lib/data").is_ok());
+        assert!(VarType::Path.check("/tmp/a b").is_ok());
+        assert!(VarType::Path.check("/tmp\n").is_err());
+        let runtime = VarType::Enum(vec!["docker".to_string(), "podman".to_string()]);
+        assert!(runtime.check("podman").is_ok());
+        assert_eq!(
+            runtime.check("lxc").unwrap_err(),
+            "Must be one of: docker, podman"
+        );
+    }
+
+    #[test]
+    fn test_validate_variables_reports_each_invalid_variable() {
+        let tpl = get_built_in_templates()
+            .into_iter()
+            .find(|t| t.name == "Run with Port Mapping")
+            .unwrap();
+
+        let mut values = HashMap::from([
+            ("RUNTIME".to_string(), "docker".to_string()),
+            ("HOST_PORT".to_string(), "80a".to_string()),
+            ("CONTAINER_PORT".to_string(), "70000".to_string()),
+            ("IMAGE_NAME".to_string(), "nginx".to_string()),
+        ]);
+        let errors = tpl.validate_variables(&values);
+        let mut names: Vec<_> = errors.iter().map(|e| e.name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, vec!["CONTAINER_NAME", "CONTAINER_PORT", "HOST_PORT"]);
+        assert_eq!(
+            errors.iter().find(|e| e.name == "CONTAINER_NAME").unwrap().message,
+            "Required"
+        );
+
+        values.insert("HOST_PORT".to_string(), "8080".to_string());
+        values.insert("CONTAINER_PORT".to_string(), "80".to_string());
+        values.insert("CONTAINER_NAME".to_string(), "web".to_string());
+        assert!(tpl.validate_variables(&values).is_empty());
+    }
+
     #[test]
     fn test_command_template_new() {
         let tpl = CommandTemplate::new(
@@ -1765,6 +2301,7 @@ mod tests {
                 description: "Name".to_string(),
                 default_value: None,
                 required: true,
+                var_type: None,
             }],
         );
 
@@ -1859,6 +2396,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_built_in_templates_include_nerdctl_namespaces() {
+        let templates = get_built_in_templates();
+        let nerdctl: Vec<_> = templates.iter()
+            .filter(|t| t.compatibility.runtimes.contains(&ContainerRuntime::Nerdctl))
+            .collect();
+
+        assert!(!nerdctl.is_empty(), "Should have nerdctl templates");
+        for tpl in &nerdctl {
+            assert_eq!(tpl.compatibility.runtimes, vec![ContainerRuntime::Nerdctl]);
+            assert!(tpl.command.starts_with("nerdctl "));
+        }
+        assert!(nerdctl.iter().any(|t| t.command.contains("--namespace ${NAMESPACE}")));
+    }
+
     #[test]
     fn test_built_in_templates_include_podman_only() {
         let templates = get_built_in_templates();
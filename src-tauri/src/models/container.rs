@@ -1,6 +1,9 @@
+use std::collections::BTreeMap;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::models::error::{AppError, ContainerError};
 use crate::models::system::SystemId;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
@@ -24,6 +27,7 @@ pub enum ContainerRuntime {
     Docker,
     Podman,
     Apple,
+    Nerdctl,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -37,6 +41,30 @@ pub enum ContainerAction {
     Remove,
 }
 
+/// Outcome for one container of `perform_container_action_bulk`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContainerActionResult {
+    pub success: bool,
+    /// Why the action failed, in the same shape single-container commands reject with
+    pub error: Option<AppError>,
+}
+
+impl From<Result<(), ContainerError>> for ContainerActionResult {
+    fn from(result: Result<(), ContainerError>) -> Self {
+        match result {
+            Ok(()) => Self {
+                success: true,
+                error: None,
+            },
+            Err(error) => Self {
+                success: false,
+                error: Some(error.into()),
+            },
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PortMapping {
@@ -58,6 +86,10 @@ pub struct Container {
     pub system_id: SystemId,
     pub created_at: DateTime<Utc>,
     pub ports: Vec<PortMapping>,
+    /// How long the container has been up, or since it exited, as `docker ps` shows it
+    /// (e.g. "Up 3 days", "Exited (1) 5 minutes ago"); None when it never started
+    #[serde(default)]
+    pub uptime: Option<String>,
 
     // Full details (always populated from docker inspect)
     pub environment_variables: std::collections::HashMap<String, String>,
@@ -198,6 +230,9 @@ pub struct HostConfigExtras {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ContainerDetails {
+    /// Creation time, when the runtime reports one
+    #[serde(default)]
+    pub created_at: Option<DateTime<Utc>>,
     pub environment_variables: std::collections::HashMap<String, String>,
     pub volumes: Vec<VolumeMount>,
     pub network_settings: NetworkSettings,
@@ -210,6 +245,316 @@ pub struct ContainerDetails {
     pub host_config: HostConfigExtras,
 }
 
+/// Options for following a container's logs with `stream_container_logs`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ContainerLogOptions {
+    /// Number of existing lines to show before following
+    pub tail: Option<u32>,
+    /// Only show logs since a timestamp (e.g. `2024-01-01T00:00:00Z`) or duration (e.g. `10m`)
+    pub since: Option<String>,
+    pub timestamps: bool,
+    /// Forward lines the container wrote to stdout
+    pub stdout: bool,
+    /// Forward lines the container wrote to stderr
+    pub stderr: bool,
+}
+
+impl Default for ContainerLogOptions {
+    fn default() -> Self {
+        Self {
+            tail: None,
+            since: None,
+            timestamps: false,
+            stdout: true,
+            stderr: true,
+        }
+    }
+}
+
+/// Filters `list_containers` hands to the runtime (`docker ps --filter ...`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ContainerListFilter {
+    /// Include stopped containers (`-a`); ignored when `status` is set
+    pub all: bool,
+    pub status: Option<ContainerStatus>,
+    /// `key` to require a label, or `key=value` to require its value
+    pub label: Option<String>,
+    /// Part of the container name
+    pub name: Option<String>,
+    /// Image the container was created from, with or without a tag
+    pub ancestor: Option<String>,
+}
+
+impl Default for ContainerListFilter {
+    fn default() -> Self {
+        Self {
+            all: true,
+            status: None,
+            label: None,
+            name: None,
+            ancestor: None,
+        }
+    }
+}
+
+impl ContainerListFilter {
+    /// Apply the filter in Rust, for runtimes whose list command can't
+    pub fn matches(&self, container: &Container) -> bool {
+        let status_ok = match self.status {
+            Some(status) => container.status == status,
+            None => self.all || container.is_running(),
+        };
+        let label_ok = self
+            .label
+            .as_deref()
+            .is_none_or(|label| match label.split_once('=') {
+                Some((key, value)) => container.labels.get(key).is_some_and(|v| v == value),
+                None => container.labels.contains_key(label),
+            });
+        let name_ok = self
+            .name
+            .as_deref()
+            .is_none_or(|name| container.name.contains(name));
+        let ancestor_ok = self.ancestor.as_deref().is_none_or(|ancestor| {
+            container.image == ancestor
+                || (!ancestor.contains(':')
+                    && container.image.split_once(':').map(|(repo, _)| repo) == Some(ancestor))
+        });
+        status_ok && label_ok && name_ok && ancestor_ok
+    }
+}
+
+/// A port `create_container` publishes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PortSpec {
+    /// Host address to bind, all interfaces when None
+    #[serde(default)]
+    pub host_ip: Option<String>,
+    /// Host port, picked by the runtime when None
+    #[serde(default)]
+    pub host_port: Option<u16>,
+    pub container_port: u16,
+    /// tcp (default), udp or sctp
+    #[serde(default)]
+    pub protocol: Option<String>,
+}
+
+/// A bind mount (absolute source path) or named volume `create_container` mounts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeSpec {
+    pub source: String,
+    pub destination: String,
+    #[serde(default)]
+    pub read_only: bool,
+}
+
+/// Everything `create_container` needs to build a `run`/`create` command
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContainerCreateSpec {
+    pub image: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub ports: Vec<PortSpec>,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    #[serde(default)]
+    pub volumes: Vec<VolumeSpec>,
+    /// no, always, unless-stopped, on-failure or on-failure:<max retries>
+    #[serde(default)]
+    pub restart_policy: Option<String>,
+    #[serde(default)]
+    pub network: Option<String>,
+    /// Command and arguments replacing the image's default command
+    #[serde(default)]
+    pub command: Vec<String>,
+    /// Start the container right away (`run -d`); otherwise it is only created
+    #[serde(default = "default_detach")]
+    pub detach: bool,
+}
+
+fn default_detach() -> bool {
+    true
+}
+
+impl ContainerCreateSpec {
+    /// Reject specs the runtime would misread once they are on a command line
+    pub fn validate(&self, runtime: ContainerRuntime) -> Result<(), ContainerError> {
+        let invalid = |message: String| Err(ContainerError::InvalidConfiguration(message));
+
+        // A leading '-' would be read as an option
+        if self.image.trim().is_empty()
+            || self.image.starts_with('-')
+            || self.image.chars().any(char::is_whitespace)
+        {
+            return invalid(format!("Invalid image '{}'", self.image));
+        }
+        if let Some(name) = &self.name {
+            // Names must start with a letter or digit, which also rules out a leading '-'
+            validate_container_name(name)?;
+        }
+
+        for port in &self.ports {
+            if port.container_port == 0 || port.host_port == Some(0) {
+                return invalid("Ports must be between 1 and 65535".to_string());
+            }
+            if let Some(ip) = &port.host_ip {
+                if ip.parse::<std::net::IpAddr>().is_err() {
+                    return invalid(format!("Invalid host address '{}'", ip));
+                }
+            }
+            if let Some(protocol) = &port.protocol {
+                if !matches!(protocol.as_str(), "tcp" | "udp" | "sctp") {
+                    return invalid(format!(
+                        "Invalid protocol '{}': expected tcp, udp or sctp",
+                        protocol
+                    ));
+                }
+            }
+        }
+
+        for volume in &self.volumes {
+            // `-v source:destination[:ro]` can't carry a colon in either path
+            let unusable = |path: &str| path.is_empty() || path.contains([':', '\n', '\0']);
+            if unusable(&volume.source) || unusable(&volume.destination) {
+                return invalid(format!(
+                    "Invalid volume '{}:{}': paths must be non-empty without ':' or newlines",
+                    volume.source, volume.destination
+                ));
+            }
+            if !volume.destination.starts_with('/') {
+                return invalid(format!(
+                    "Invalid volume destination '{}': must be an absolute path",
+                    volume.destination
+                ));
+            }
+        }
+
+        for key in self.env.keys() {
+            if key.is_empty() || key.contains('=') || key.chars().any(char::is_whitespace) {
+                return invalid(format!("Invalid environment variable name '{}'", key));
+            }
+        }
+
+        if let Some(policy) = &self.restart_policy {
+            let valid = match policy.split_once(':') {
+                Some(("on-failure", retries)) => retries.parse::<u32>().is_ok(),
+                Some(_) => false,
+                None => is_restart_policy(policy),
+            };
+            if !valid {
+                return invalid(format!("Invalid restart policy '{}'", policy));
+            }
+            if runtime == ContainerRuntime::Apple && policy != "no" {
+                return Err(ContainerError::UnsupportedOperation(
+                    "Apple Container doesn't support restart policies".to_string(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Check a container name against the runtimes' `[a-zA-Z0-9][a-zA-Z0-9_.-]+`
+pub fn validate_container_name(name: &str) -> Result<(), ContainerError> {
+    let valid = name.len() >= 2
+        && name.starts_with(|c: char| c.is_ascii_alphanumeric())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'));
+    if valid {
+        Ok(())
+    } else {
+        Err(ContainerError::InvalidConfiguration(format!(
+            "Invalid container name '{}': use at least two letters, digits, '_', '.' or '-', starting with a letter or digit",
+            name
+        )))
+    }
+}
+
+/// Check a restart policy name: no, always, unless-stopped or on-failure
+pub fn is_restart_policy(policy: &str) -> bool {
+    matches!(policy, "no" | "always" | "unless-stopped" | "on-failure")
+}
+
+/// A lifecycle event reported by `<runtime> events`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContainerEvent {
+    /// Kind of object the event is about: container, image, network, volume, ...
+    #[serde(rename = "type")]
+    pub event_type: String,
+    /// What happened to it, e.g. `start`, `die` or `pull`
+    pub action: String,
+    pub actor_id: String,
+    pub actor_name: Option<String>,
+    /// Unix timestamp in seconds
+    pub time: i64,
+}
+
+/// Check a `--since`/`--until` value for container logs: a duration such as
+/// `10m` or `1h30m`, or an RFC3339 timestamp
+pub fn is_valid_log_time(value: &str) -> bool {
+    if DateTime::parse_from_rfc3339(value).is_ok() {
+        return true;
+    }
+
+    let mut rest = value;
+    while !rest.is_empty() {
+        let number_len = rest
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(rest.len());
+        if rest[..number_len].parse::<f64>().is_err() {
+            return false;
+        }
+        rest = &rest[number_len..];
+        // "ms" has to be tried before "m"
+        let Some(unit) = ["ns", "us", "µs", "ms", "s", "m", "h"]
+            .iter()
+            .find(|unit| rest.starts_with(**unit))
+        else {
+            return false;
+        };
+        rest = &rest[unit.len()..];
+    }
+    !value.is_empty()
+}
+
+/// Point-in-time resource usage for a single container (from `docker stats`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContainerMetrics {
+    pub container_id: String,
+    pub name: String,
+    pub system_id: String,
+    /// Unix timestamp in milliseconds
+    pub timestamp: i64,
+    /// CPU usage percentage (can exceed 100 on multi-core hosts)
+    pub cpu_percent: f32,
+    /// Memory usage percentage of the limit (0-100)
+    pub memory_percent: f32,
+    /// Memory currently used in bytes
+    pub memory_usage_bytes: Option<u64>,
+    /// Memory limit in bytes
+    pub memory_limit_bytes: Option<u64>,
+    /// Total bytes received over the container's network interfaces
+    pub net_rx_bytes: Option<u64>,
+    /// Total bytes sent over the container's network interfaces
+    pub net_tx_bytes: Option<u64>,
+    /// Total bytes read from block devices
+    pub block_read_bytes: Option<u64>,
+    /// Total bytes written to block devices
+    pub block_write_bytes: Option<u64>,
+    /// Number of processes running in the container
+    pub pids: Option<u32>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -226,6 +571,7 @@ mod tests {
             system_id: SystemId("sys-1".to_string()),
             created_at: Utc::now(),
             ports: vec![],
+            uptime: None,
             environment_variables: std::collections::HashMap::new(),
             volumes: vec![],
             network_settings: NetworkSettings {
@@ -288,6 +634,138 @@ mod tests {
         assert_eq!(actions, vec![ContainerAction::Unpause, ContainerAction::Stop]);
     }
 
+    #[test]
+    fn test_list_filter_matches() {
+        let mut container = make_container(ContainerStatus::Exited);
+        container
+            .labels
+            .insert("com.docker.compose.project".to_string(), "shop".to_string());
+
+        assert!(ContainerListFilter::default().matches(&container));
+        let running_only = ContainerListFilter {
+            all: false,
+            ..ContainerListFilter::default()
+        };
+        assert!(!running_only.matches(&container));
+        assert!(ContainerListFilter {
+            status: Some(ContainerStatus::Exited),
+            ..running_only
+        }
+        .matches(&container));
+
+        let filter =
+            |label: Option<&str>, name: Option<&str>, ancestor: Option<&str>| ContainerListFilter {
+                label: label.map(str::to_string),
+                name: name.map(str::to_string),
+                ancestor: ancestor.map(str::to_string),
+                ..ContainerListFilter::default()
+            };
+        assert!(filter(Some("com.docker.compose.project"), None, None).matches(&container));
+        assert!(filter(Some("com.docker.compose.project=shop"), None, None).matches(&container));
+        assert!(!filter(Some("com.docker.compose.project=blog"), None, None).matches(&container));
+        assert!(filter(None, Some("web"), Some("nginx")).matches(&container));
+        assert!(filter(None, None, Some("nginx:latest")).matches(&container));
+        assert!(!filter(None, None, Some("nginx:1.25")).matches(&container));
+        assert!(!filter(None, Some("db"), None).matches(&container));
+    }
+
+    fn create_spec() -> ContainerCreateSpec {
+        serde_json::from_value(serde_json::json!({ "image": "nginx:latest" })).unwrap()
+    }
+
+    #[test]
+    fn test_create_spec_validation() {
+        let spec = create_spec();
+        assert!(spec.detach);
+        assert!(spec.validate(ContainerRuntime::Docker).is_ok());
+
+        let invalid = [
+            ContainerCreateSpec {
+                image: "nginx latest".to_string(),
+                ..create_spec()
+            },
+            ContainerCreateSpec {
+                image: "--privileged".to_string(),
+                ..create_spec()
+            },
+            ContainerCreateSpec {
+                name: Some("-web".to_string()),
+                ..create_spec()
+            },
+            ContainerCreateSpec {
+                ports: vec![PortSpec {
+                    host_ip: Some("localhost".to_string()),
+                    host_port: Some(8080),
+                    container_port: 80,
+                    protocol: None,
+                }],
+                ..create_spec()
+            },
+            ContainerCreateSpec {
+                volumes: vec![VolumeSpec {
+                    source: "/srv/data".to_string(),
+                    destination: "data".to_string(),
+                    read_only: false,
+                }],
+                ..create_spec()
+            },
+            ContainerCreateSpec {
+                volumes: vec![VolumeSpec {
+                    source: "C:/data".to_string(),
+                    destination: "/data".to_string(),
+                    read_only: false,
+                }],
+                ..create_spec()
+            },
+            ContainerCreateSpec {
+                env: BTreeMap::from([("A B".to_string(), "1".to_string())]),
+                ..create_spec()
+            },
+            ContainerCreateSpec {
+                restart_policy: Some("on-failure:often".to_string()),
+                ..create_spec()
+            },
+        ];
+        for spec in invalid {
+            assert!(
+                matches!(
+                    spec.validate(ContainerRuntime::Docker),
+                    Err(ContainerError::InvalidConfiguration(_))
+                ),
+                "{:?} should be rejected",
+                spec
+            );
+        }
+
+        let restarting = ContainerCreateSpec {
+            restart_policy: Some("on-failure:3".to_string()),
+            ..create_spec()
+        };
+        assert!(restarting.validate(ContainerRuntime::Podman).is_ok());
+        assert!(matches!(
+            restarting.validate(ContainerRuntime::Apple),
+            Err(ContainerError::UnsupportedOperation(_))
+        ));
+    }
+
+    #[test]
+    fn test_validate_container_name() {
+        for name in ["web", "my_app.v2-1", "0x"] {
+            assert!(
+                validate_container_name(name).is_ok(),
+                "{} should be valid",
+                name
+            );
+        }
+        for name in ["", "w", "-web", ".web", "my app", "web;rm", "wéb"] {
+            assert!(
+                validate_container_name(name).is_err(),
+                "{} should be invalid",
+                name
+            );
+        }
+    }
+
     #[test]
     fn test_available_actions_created() {
         let actions = make_container(ContainerStatus::Created).available_actions();
@@ -378,11 +856,46 @@ mod tests {
         assert!(json.contains("hostIp")); // camelCase
         assert!(json.contains("8080"));
     }
+
+    #[test]
+    fn test_log_options_defaults_to_both_streams() {
+        let options: ContainerLogOptions = serde_json::from_str(r#"{"tail": 50}"#).unwrap();
+        assert_eq!(options.tail, Some(50));
+        assert!(options.stdout && options.stderr);
+        assert!(!options.timestamps);
+    }
+
+    #[test]
+    fn test_is_valid_log_time() {
+        for valid in [
+            "10m",
+            "1h30m",
+            "1.5h",
+            "500ms",
+            "2024-01-01T00:00:00Z",
+            "2024-06-01T12:30:00+02:00",
+        ] {
+            assert!(is_valid_log_time(valid), "{}", valid);
+        }
+        for invalid in [
+            "",
+            "10",
+            "m",
+            "10 m",
+            "10d",
+            "yesterday",
+            "2024-01-01",
+            "1m; rm -rf /",
+        ] {
+            assert!(!is_valid_log_time(invalid), "{}", invalid);
+        }
+    }
 }
 
 impl From<&Container> for ContainerDetails {
     fn from(c: &Container) -> Self {
         Self {
+            created_at: Some(c.created_at),
             environment_variables: c.environment_variables.clone(),
             volumes: c.volumes.clone(),
             network_settings: c.network_settings.clone(),
@@ -60,6 +60,26 @@ pub enum ContainerError {
 
     #[error("SSH host key verification failed for {hostname}: {reason}")]
     HostKeyVerificationFailed { hostname: String, reason: String },
+
+    #[error("Unknown SSH host key for {hostname}: {key_type} SHA256:{fingerprint}")]
+    HostKeyUnknown {
+        hostname: String,
+        port: u16,
+        key_type: String,
+        fingerprint: String,
+    },
+
+    #[error("SSH host key for {hostname} has changed! Expected SHA256:{expected}, received {key_type} SHA256:{actual}")]
+    HostKeyChanged {
+        hostname: String,
+        port: u16,
+        key_type: String,
+        expected: String,
+        actual: String,
+    },
+
+    #[error("Command cancelled: {0}")]
+    Cancelled(String),
 }
 
 impl ContainerError {
@@ -96,12 +116,200 @@ impl ContainerError {
             ContainerError::NotFound { .. } => "The requested resource may have been deleted",
             ContainerError::InvalidOperation { .. } => "This operation is not allowed",
             ContainerError::HostKeyVerificationFailed { .. } => {
+                "The server's host key could not be verified. Check ~/.ssh/known_hosts for this host."
+            }
+            ContainerError::HostKeyUnknown { .. } => {
+                "Compare the fingerprint with the server's own (ssh-keygen -lf /etc/ssh/ssh_host_*_key.pub) before trusting it"
+            }
+            ContainerError::HostKeyChanged { .. } => {
                 "The server's host key has changed. This could indicate a man-in-the-middle attack. If the server was reinstalled, remove the old key from ~/.ssh/known_hosts."
             }
+            ContainerError::Cancelled(_) => "Run the command again if this was unintended",
+        }
+    }
+}
+
+/// Broad error category the frontend uses to pick a recovery action
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ErrorKind {
+    Connection,
+    Auth,
+    HostKey,
+    /// First connection to a host; the user decides whether to trust its key
+    HostKeyUnknown,
+    /// The host presented a different key than known_hosts records (possible MITM)
+    HostKeyChanged,
+    Timeout,
+    NotFound,
+    Parse,
+    RuntimeNotAvailable,
+    CommandFailed,
+    InvalidInput,
+    PermissionDenied,
+    Unsupported,
+    Cancelled,
+    Internal,
+}
+
+/// Error returned by every Tauri command
+///
+/// Serializes as `{ kind, message, detail }`. `message` is the `Display` text of
+/// the underlying error; `detail` carries the raw cause where there is one
+/// (stderr of a failed command, the reason a connection or host key check failed).
+/// Unknown and changed host keys also carry `hostKey` so the user can check the
+/// fingerprint before trusting it. Rejected form input carries `fieldErrors` so
+/// the UI can mark each offending field.
+#[derive(Debug, Error, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[error("{message}")]
+#[serde(rename_all = "camelCase")]
+pub struct AppError {
+    pub kind: ErrorKind,
+    pub message: String,
+    pub detail: Option<String>,
+    #[serde(default)]
+    pub host_key: Option<Box<HostKeyDetails>>,
+    #[serde(default)]
+    pub field_errors: Option<Vec<FieldError>>,
+}
+
+/// A submitted field that failed validation
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldError {
+    /// Field name as the request names it, e.g. `model_name`
+    pub field: String,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+}
+
+/// Host key a server presented that isn't trusted yet
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct HostKeyDetails {
+    pub hostname: String,
+    pub port: u16,
+    pub key_type: String,
+    /// SHA256 fingerprint of the presented key, without the `SHA256:` prefix
+    pub fingerprint: String,
+    /// Fingerprint known_hosts has for the host, when the key changed
+    pub expected_fingerprint: Option<String>,
+}
+
+impl AppError {
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            detail: None,
+            host_key: None,
+            field_errors: None,
+        }
+    }
+
+    /// Invalid input, listing every field that was rejected
+    pub fn invalid_fields(message: impl Into<String>, field_errors: Vec<FieldError>) -> Self {
+        Self {
+            field_errors: Some(field_errors),
+            ..Self::new(ErrorKind::InvalidInput, message)
         }
     }
 }
 
+impl From<ContainerError> for AppError {
+    fn from(err: ContainerError) -> Self {
+        let message = err.to_string();
+        let mut host_key = None;
+        let (kind, detail) = match err {
+            ContainerError::SystemNotFound(_)
+            | ContainerError::ContainerNotFound(_)
+            | ContainerError::NotFound { .. } => (ErrorKind::NotFound, None),
+            ContainerError::NotConnected(_) => (ErrorKind::Connection, None),
+            ContainerError::ConnectionFailed(_, reason) => (ErrorKind::Connection, Some(reason)),
+            ContainerError::SshAuthenticationFailed(_) | ContainerError::CredentialError(_) => {
+                (ErrorKind::Auth, None)
+            }
+            ContainerError::HostKeyVerificationFailed { reason, .. } => {
+                (ErrorKind::HostKey, Some(reason))
+            }
+            ContainerError::HostKeyUnknown {
+                hostname,
+                port,
+                key_type,
+                fingerprint,
+            } => {
+                host_key = Some(Box::new(HostKeyDetails {
+                    hostname,
+                    port,
+                    key_type,
+                    fingerprint,
+                    expected_fingerprint: None,
+                }));
+                (ErrorKind::HostKeyUnknown, None)
+            }
+            ContainerError::HostKeyChanged {
+                hostname,
+                port,
+                key_type,
+                expected,
+                actual,
+            } => {
+                host_key = Some(Box::new(HostKeyDetails {
+                    hostname,
+                    port,
+                    key_type,
+                    fingerprint: actual,
+                    expected_fingerprint: Some(expected),
+                }));
+                (ErrorKind::HostKeyChanged, None)
+            }
+            ContainerError::CommandExecutionFailed { stderr, .. } => {
+                (ErrorKind::CommandFailed, Some(stderr))
+            }
+            ContainerError::UnsupportedRuntime(_) => (ErrorKind::RuntimeNotAvailable, None),
+            ContainerError::NetworkTimeout(_) => (ErrorKind::Timeout, None),
+            ContainerError::InvalidConfiguration(_) | ContainerError::InvalidOperation { .. } => {
+                (ErrorKind::InvalidInput, None)
+            }
+            ContainerError::ParseError(_) => (ErrorKind::Parse, None),
+            ContainerError::PermissionDenied(_) => (ErrorKind::PermissionDenied, None),
+            ContainerError::UnsupportedOperation(_) => (ErrorKind::Unsupported, None),
+            ContainerError::Cancelled(_) => (ErrorKind::Cancelled, None),
+            ContainerError::Internal(_) | ContainerError::DatabaseError { .. } => {
+                (ErrorKind::Internal, None)
+            }
+        };
+        Self {
+            kind,
+            message,
+            detail,
+            host_key,
+            field_errors: None,
+        }
+    }
+}
+
+/// Untyped errors (the AI and agent layers report plain strings)
+impl From<String> for AppError {
+    fn from(message: String) -> Self {
+        Self::new(ErrorKind::Internal, message)
+    }
+}
+
+impl From<&str> for AppError {
+    fn from(message: &str) -> Self {
+        Self::new(ErrorKind::Internal, message)
+    }
+}
+
 // For backwards compatibility
 pub type ContainerusError = ContainerError;
 
@@ -177,10 +385,34 @@ mod tests {
             ContainerError::UnsupportedOperation("x".to_string()),
             ContainerError::CredentialError("x".to_string()),
             ContainerError::Internal("x".to_string()),
-            ContainerError::DatabaseError { message: "x".to_string() },
-            ContainerError::NotFound { resource: "x".to_string(), id: "y".to_string() },
-            ContainerError::InvalidOperation { message: "x".to_string() },
-            ContainerError::HostKeyVerificationFailed { hostname: "x".to_string(), reason: "y".to_string() },
+            ContainerError::DatabaseError {
+                message: "x".to_string(),
+            },
+            ContainerError::NotFound {
+                resource: "x".to_string(),
+                id: "y".to_string(),
+            },
+            ContainerError::InvalidOperation {
+                message: "x".to_string(),
+            },
+            ContainerError::HostKeyVerificationFailed {
+                hostname: "x".to_string(),
+                reason: "y".to_string(),
+            },
+            ContainerError::HostKeyUnknown {
+                hostname: "x".to_string(),
+                port: 22,
+                key_type: "y".to_string(),
+                fingerprint: "z".to_string(),
+            },
+            ContainerError::HostKeyChanged {
+                hostname: "x".to_string(),
+                port: 22,
+                key_type: "y".to_string(),
+                expected: "a".to_string(),
+                actual: "b".to_string(),
+            },
+            ContainerError::Cancelled("x".to_string()),
         ];
 
         for err in errors {
@@ -206,4 +438,95 @@ mod tests {
         let cloned = err.clone();
         assert_eq!(err.to_string(), cloned.to_string());
     }
+
+    #[test]
+    fn test_app_error_from_container_error() {
+        let err = ContainerError::SshAuthenticationFailed("bad key".to_string());
+        let app: AppError = err.clone().into();
+        assert_eq!(app.kind, ErrorKind::Auth);
+        assert_eq!(app.to_string(), err.to_string());
+        assert_eq!(app.detail, None);
+
+        let app = AppError::from(ContainerError::CommandExecutionFailed {
+            command: "docker ps".to_string(),
+            exit_code: 1,
+            stderr: "permission denied".to_string(),
+        });
+        assert_eq!(app.kind, ErrorKind::CommandFailed);
+        assert_eq!(app.detail.as_deref(), Some("permission denied"));
+
+        let app = AppError::from(ContainerError::UnsupportedRuntime("podman".to_string()));
+        assert_eq!(app.kind, ErrorKind::RuntimeNotAvailable);
+
+        let app = AppError::from("model not loaded".to_string());
+        assert_eq!(app.kind, ErrorKind::Internal);
+        assert_eq!(app.message, "model not loaded");
+    }
+
+    #[test]
+    fn test_app_error_serialization() {
+        let app = AppError::from(ContainerError::HostKeyVerificationFailed {
+            hostname: "example.com".to_string(),
+            reason: "Expected: A Received: B".to_string(),
+        });
+        let json = serde_json::to_value(&app).unwrap();
+        assert_eq!(json["kind"], "hostKey");
+        assert_eq!(
+            json["message"],
+            "SSH host key verification failed for example.com: Expected: A Received: B"
+        );
+        assert_eq!(json["detail"], "Expected: A Received: B");
+
+        let json = serde_json::to_value(AppError::from(ContainerError::NotConnected("s".into())))
+            .unwrap();
+        assert_eq!(json["kind"], "connection");
+        assert!(json["detail"].is_null());
+        assert!(json["hostKey"].is_null());
+        assert!(json["fieldErrors"].is_null());
+    }
+
+    #[test]
+    fn test_app_error_carries_field_errors() {
+        let app = AppError::invalid_fields(
+            "Invalid settings",
+            vec![FieldError::new("model_name", "Model name is required")],
+        );
+        let json = serde_json::to_value(&app).unwrap();
+        assert_eq!(json["kind"], "invalidInput");
+        assert_eq!(json["fieldErrors"][0]["field"], "model_name");
+        assert_eq!(json["fieldErrors"][0]["message"], "Model name is required");
+    }
+
+    #[test]
+    fn test_app_error_carries_host_key() {
+        let json = serde_json::to_value(AppError::from(ContainerError::HostKeyUnknown {
+            hostname: "example.com".to_string(),
+            port: 2222,
+            key_type: "ssh-ed25519".to_string(),
+            fingerprint: "abc".to_string(),
+        }))
+        .unwrap();
+        assert_eq!(json["kind"], "hostKeyUnknown");
+        assert_eq!(json["hostKey"]["hostname"], "example.com");
+        assert_eq!(json["hostKey"]["port"], 2222);
+        assert_eq!(json["hostKey"]["keyType"], "ssh-ed25519");
+        assert_eq!(json["hostKey"]["fingerprint"], "abc");
+        assert!(json["hostKey"]["expectedFingerprint"].is_null());
+
+        let json = serde_json::to_value(AppError::from(ContainerError::HostKeyChanged {
+            hostname: "example.com".to_string(),
+            port: 22,
+            key_type: "ssh-ed25519".to_string(),
+            expected: "old".to_string(),
+            actual: "new".to_string(),
+        }))
+        .unwrap();
+        assert_eq!(json["kind"], "hostKeyChanged");
+        assert_eq!(json["hostKey"]["fingerprint"], "new");
+        assert_eq!(json["hostKey"]["expectedFingerprint"], "old");
+        assert_eq!(
+            json["message"],
+            "SSH host key for example.com has changed! Expected SHA256:old, received ssh-ed25519 SHA256:new"
+        );
+    }
 }
@@ -41,6 +41,114 @@ pub struct FileContent {
     pub is_binary: bool,
 }
 
+/// A byte range of a file, for paging through files too large to open whole
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileChunk {
+    pub path: String,
+    /// Where the read started, clamped to the file size
+    pub offset: u64,
+    /// Bytes actually read; less than requested at the end of the file
+    pub bytes_read: u64,
+    pub file_size: u64,
+    /// The bytes decoded as UTF-8; a character split at either edge is replaced
+    pub content: String,
+    pub is_binary: bool,
+}
+
+/// Disk usage of one path as reported by `du`, in bytes
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DiskUsageEntry {
+    pub path: String,
+    pub size: u64,
+}
+
+/// Total size of a directory tree
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DirectorySize {
+    pub path: String,
+    pub size: u64,
+    /// Some subdirectories couldn't be read, so `size` undercounts
+    pub partial: bool,
+}
+
+/// Size of a directory broken down by the entries below it, largest first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DirectoryTreeSizes {
+    pub path: String,
+    pub total: u64,
+    pub children: Vec<DiskUsageEntry>,
+    /// Some subdirectories couldn't be read, so sizes undercount
+    pub partial: bool,
+}
+
+/// Default cap on the number of hits returned by a content search
+pub const DEFAULT_MAX_SEARCH_RESULTS: usize = 500;
+
+/// How `search_file_contents` matches the query
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SearchOptions {
+    pub case_insensitive: bool,
+    /// Match the query literally instead of as an extended regular expression
+    pub fixed_string: bool,
+    /// Stop after this many hits (defaults to `DEFAULT_MAX_SEARCH_RESULTS`)
+    pub max_results: Option<usize>,
+}
+
+/// A line matching a content search
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchHit {
+    pub file: String,
+    pub line_number: u64,
+    pub line: String,
+}
+
+/// Hits of a content search, in the order grep found them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchResults {
+    pub hits: Vec<SearchHit>,
+    /// More hits exist beyond the `max_results` returned
+    pub truncated: bool,
+}
+
+/// Archive formats the file browser can create and extract
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    #[serde(rename = "tar.gz")]
+    TarGz,
+    #[serde(rename = "zip")]
+    Zip,
+}
+
+impl ArchiveFormat {
+    /// Detect the format from an archive's file name
+    pub fn from_path(path: &str) -> Option<Self> {
+        let lower = path.to_lowercase();
+        if lower.ends_with(".tar.gz") || lower.ends_with(".tgz") {
+            Some(Self::TarGz)
+        } else if lower.ends_with(".zip") {
+            Some(Self::Zip)
+        } else {
+            None
+        }
+    }
+
+    /// Tools that must be installed on the host to create or extract this format
+    pub fn required_tools(&self, extract: bool) -> &'static [&'static str] {
+        match (self, extract) {
+            (Self::TarGz, _) => &["tar", "gzip"],
+            (Self::Zip, false) => &["zip"],
+            (Self::Zip, true) => &["unzip"],
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -60,6 +168,25 @@ mod tests {
         assert_eq!(ft, FileType::File);
     }
 
+    #[test]
+    fn test_archive_format() {
+        assert_eq!(
+            serde_json::to_string(&ArchiveFormat::TarGz).unwrap(),
+            "\"tar.gz\""
+        );
+        let zip: ArchiveFormat = serde_json::from_str("\"zip\"").unwrap();
+        assert_eq!(zip, ArchiveFormat::Zip);
+
+        for (path, expected) in [
+            ("/tmp/app.tar.gz", Some(ArchiveFormat::TarGz)),
+            ("/tmp/app.TGZ", Some(ArchiveFormat::TarGz)),
+            ("/tmp/app.zip", Some(ArchiveFormat::Zip)),
+            ("/tmp/app.tar.bz2", None),
+        ] {
+            assert_eq!(ArchiveFormat::from_path(path), expected, "{}", path);
+        }
+    }
+
     #[test]
     fn test_file_entry_serialization() {
         let entry = FileEntry {
@@ -1,5 +1,6 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 use crate::models::container::ContainerRuntime;
 use crate::models::system::SystemId;
@@ -20,6 +21,56 @@ pub struct ContainerImage {
     pub os: Option<String>,
 }
 
+/// One progress update parsed from `docker pull` / `podman pull` output
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PullProgress {
+    /// Layer (blob) the update refers to; `None` for image-wide messages
+    pub layer_id: Option<String>,
+    /// Docker-style status, e.g. "Downloading", "Extracting", "Pull complete"
+    pub status: String,
+    /// Progress of the current status, when the output includes sizes
+    pub percent: Option<f32>,
+}
+
+/// Full configuration of a single image, from `<runtime> image inspect`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageDetails {
+    pub id: String,
+    pub repo_tags: Vec<String>,
+    pub architecture: Option<String>,
+    pub os: Option<String>,
+    pub created: Option<DateTime<Utc>>,
+    pub size: Option<i64>,
+    /// `KEY=value` entries, in the order the image defines them
+    pub env: Vec<String>,
+    pub cmd: Vec<String>,
+    pub entrypoint: Vec<String>,
+    /// Ports as declared by the image, e.g. `80/tcp`
+    pub exposed_ports: Vec<String>,
+    pub working_dir: Option<String>,
+    pub user: Option<String>,
+    pub labels: HashMap<String, String>,
+    /// Root filesystem layer digests, base layer first
+    pub layers: Vec<String>,
+    /// Build steps that produced the image, newest first
+    pub history: Vec<ImageHistoryEntry>,
+}
+
+/// One build step of an image, from `<runtime> history`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageHistoryEntry {
+    /// Layer ID; `None` for steps built elsewhere (`<missing>`)
+    pub id: Option<String>,
+    pub created: Option<DateTime<Utc>>,
+    pub created_by: String,
+    /// Size the step added in bytes; `None` when the runtime doesn't report it
+    pub size: Option<i64>,
+    pub comment: Option<String>,
+}
+
 impl ContainerImage {
     pub fn full_name(&self) -> String {
         if self.tag.is_empty() || self.tag == "<none>" {
@@ -4,7 +4,9 @@ pub mod container;
 pub mod error;
 pub mod image;
 pub mod network;
+pub mod pod;
 pub mod port_forward;
+pub mod prune;
 pub mod system;
 pub mod file_browser;
 pub mod volume;
@@ -15,6 +17,8 @@ pub use container::*;
 pub use error::*;
 pub use image::*;
 pub use network::*;
+pub use pod::*;
 pub use port_forward::*;
+pub use prune::*;
 pub use system::*;
 pub use volume::*;
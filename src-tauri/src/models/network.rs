@@ -1,8 +1,12 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use std::net::IpAddr;
+
 use crate::models::container::ContainerRuntime;
+use crate::models::error::ContainerError;
 use crate::models::system::SystemId;
+use crate::models::volume::{validate_driver_name, validate_option_keys};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -19,6 +23,90 @@ pub struct Network {
     pub system_id: SystemId,
 }
 
+/// Optional settings for `create_network`; the default creates a plain bridge network
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct NetworkCreateOptions {
+    pub driver: Option<String>,
+    /// CIDR range, e.g. `10.10.0.0/24`
+    pub subnet: Option<String>,
+    /// Gateway address inside `subnet`
+    pub gateway: Option<String>,
+    /// Block traffic to and from outside the network
+    pub internal: bool,
+    pub labels: std::collections::HashMap<String, String>,
+}
+
+impl NetworkCreateOptions {
+    /// Whether anything beyond a name was asked for
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn validate(&self) -> Result<(), ContainerError> {
+        let invalid = |message: String| Err(ContainerError::InvalidConfiguration(message));
+
+        if let Some(driver) = &self.driver {
+            validate_driver_name(driver)?;
+        }
+        let subnet = match &self.subnet {
+            Some(subnet) => match parse_cidr(subnet) {
+                Some(cidr) => Some(cidr),
+                None => {
+                    return invalid(format!(
+                        "Invalid subnet '{}': expected CIDR like 10.10.0.0/24",
+                        subnet
+                    ))
+                }
+            },
+            None => None,
+        };
+        if let Some(gateway) = &self.gateway {
+            let Ok(address) = gateway.parse::<IpAddr>() else {
+                return invalid(format!(
+                    "Invalid gateway '{}': expected an IP address",
+                    gateway
+                ));
+            };
+            match subnet {
+                Some((network, prefix)) if !cidr_contains(network, prefix, address) => {
+                    return invalid(format!(
+                        "Gateway {} is outside subnet {}",
+                        gateway,
+                        self.subnet.as_deref().unwrap_or_default()
+                    ));
+                }
+                Some(_) => {}
+                None => return invalid("A gateway needs a subnet".to_string()),
+            }
+        }
+        validate_option_keys("label", self.labels.keys())
+    }
+}
+
+/// Split `address/prefix`, checking the prefix fits the address family
+fn parse_cidr(cidr: &str) -> Option<(IpAddr, u8)> {
+    let (address, prefix) = cidr.split_once('/')?;
+    let address: IpAddr = address.parse().ok()?;
+    let prefix: u8 = prefix.parse().ok()?;
+    let max = if address.is_ipv4() { 32 } else { 128 };
+    (prefix <= max).then_some((address, prefix))
+}
+
+fn cidr_contains(network: IpAddr, prefix: u8, address: IpAddr) -> bool {
+    match (network, address) {
+        (IpAddr::V4(network), IpAddr::V4(address)) => {
+            let mask = u32::MAX.checked_shl(32 - prefix as u32).unwrap_or(0);
+            u32::from(network) & mask == u32::from(address) & mask
+        }
+        (IpAddr::V6(network), IpAddr::V6(address)) => {
+            let mask = u128::MAX.checked_shl(128 - prefix as u32).unwrap_or(0);
+            u128::from(network) & mask == u128::from(address) & mask
+        }
+        _ => false,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -132,4 +220,43 @@ mod tests {
             assert_eq!(deserialized.scope, *scope);
         }
     }
+
+    #[test]
+    fn test_create_options_validation() {
+        let options = |subnet: Option<&str>, gateway: Option<&str>| NetworkCreateOptions {
+            subnet: subnet.map(str::to_string),
+            gateway: gateway.map(str::to_string),
+            ..NetworkCreateOptions::default()
+        };
+
+        assert!(NetworkCreateOptions::default().validate().is_ok());
+        assert!(options(Some("10.10.0.0/24"), Some("10.10.0.1"))
+            .validate()
+            .is_ok());
+        assert!(options(Some("fd00:1::/64"), Some("fd00:1::1"))
+            .validate()
+            .is_ok());
+
+        for invalid in [
+            options(Some("10.10.0.0"), None),
+            options(Some("10.10.0.0/33"), None),
+            options(Some("10.10.0.0/24"), Some("10.20.0.1")),
+            options(Some("10.10.0.0/24"), Some("fd00::1")),
+            options(None, Some("10.10.0.1")),
+            NetworkCreateOptions {
+                driver: Some("bridge; rm -rf /".to_string()),
+                ..NetworkCreateOptions::default()
+            },
+            NetworkCreateOptions {
+                labels: HashMap::from([("a=b".to_string(), "c".to_string())]),
+                ..NetworkCreateOptions::default()
+            },
+        ] {
+            assert!(
+                invalid.validate().is_err(),
+                "{:?} should be rejected",
+                invalid
+            );
+        }
+    }
 }
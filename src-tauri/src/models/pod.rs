@@ -0,0 +1,81 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::models::system::SystemId;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PodStatus {
+    Created,
+    Running,
+    /// Some, but not all, of the pod's containers are running
+    Degraded,
+    Paused,
+    Stopped,
+    Exited,
+    Dead,
+    Unknown,
+}
+
+impl PodStatus {
+    /// Map the `Status` reported by `podman pod ps`
+    pub fn from_podman(status: &str) -> Self {
+        match status.to_lowercase().as_str() {
+            "created" => Self::Created,
+            "running" => Self::Running,
+            "degraded" => Self::Degraded,
+            "paused" => Self::Paused,
+            "stopped" => Self::Stopped,
+            "exited" => Self::Exited,
+            "dead" => Self::Dead,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// A Podman pod: a group of containers sharing namespaces
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Pod {
+    pub id: String,
+    pub name: String,
+    pub status: PodStatus,
+    /// Number of containers in the pod, including the infra container
+    pub container_count: u32,
+    pub created_at: Option<DateTime<Utc>>,
+    /// ID of the infra container holding the shared namespaces, if the pod has one
+    pub infra_id: Option<String>,
+    pub system_id: SystemId,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pod_status_from_podman() {
+        assert_eq!(PodStatus::from_podman("Running"), PodStatus::Running);
+        assert_eq!(PodStatus::from_podman("Degraded"), PodStatus::Degraded);
+        assert_eq!(PodStatus::from_podman("Exited"), PodStatus::Exited);
+        assert_eq!(PodStatus::from_podman("something-new"), PodStatus::Unknown);
+    }
+
+    #[test]
+    fn test_pod_camel_case_serialization() {
+        let pod = Pod {
+            id: "8e7f0c9d".to_string(),
+            name: "webapp".to_string(),
+            status: PodStatus::Running,
+            container_count: 3,
+            created_at: None,
+            infra_id: Some("a1b2c3".to_string()),
+            system_id: SystemId("sys-123".to_string()),
+        };
+        let json = serde_json::to_value(&pod).unwrap();
+
+        assert_eq!(json["status"], "running");
+        assert_eq!(json["containerCount"], 3);
+        assert_eq!(json["infraId"], "a1b2c3");
+        assert!(json.get("container_count").is_none());
+    }
+}
@@ -10,6 +10,17 @@ pub enum PortForwardStatus {
     Error,
 }
 
+/// Which side listens for connections
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ForwardDirection {
+    /// Listen locally and tunnel to the remote host (`ssh -L`)
+    #[default]
+    Local,
+    /// Listen on the remote host and tunnel back to a local target (`ssh -R`)
+    Remote,
+}
+
 /// Represents an active port forward/tunnel
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -22,12 +33,18 @@ pub struct PortForward {
     pub container_id: String,
     /// Container port (for UI tracking/matching)
     pub container_port: u16,
-    /// Local port to listen on
+    /// Local port to listen on (remote forwards: local port to connect to)
     pub local_port: u16,
-    /// Remote host to forward to (container IP or 0.0.0.0)
+    /// Local host the tunnel ends at (listen address, or target for remote forwards)
+    #[serde(default = "default_local_host")]
+    pub local_host: String,
+    /// Remote host to forward to (container IP or 0.0.0.0; remote forwards: bind address)
     pub remote_host: String,
-    /// Remote port to forward to (host port for SSH tunnel)
+    /// Remote port to forward to (host port for SSH tunnel; remote forwards: listening port)
     pub remote_port: u16,
+    /// Whether the local or the remote side listens
+    #[serde(default)]
+    pub direction: ForwardDirection,
     /// Protocol (tcp/udp)
     pub protocol: String,
     /// Current status
@@ -53,13 +70,43 @@ impl PortForward {
             container_id,
             container_port,
             local_port,
+            local_host: default_local_host(),
             remote_host,
             remote_port,
+            direction: ForwardDirection::Local,
             protocol,
             status: PortForwardStatus::Active,
             created_at: chrono::Utc::now().to_rfc3339(),
         }
     }
+
+    /// Create a remote (reverse) forward entry: `remote_host:remote_port` on the
+    /// server tunnels back to `local_host:local_port`
+    pub fn remote(
+        system_id: String,
+        remote_host: String,
+        remote_port: u16,
+        local_host: String,
+        local_port: u16,
+    ) -> Self {
+        Self {
+            local_host,
+            direction: ForwardDirection::Remote,
+            ..Self::new(
+                system_id,
+                String::new(),
+                0,
+                local_port,
+                remote_host,
+                remote_port,
+                "tcp".to_string(),
+            )
+        }
+    }
+}
+
+fn default_local_host() -> String {
+    "127.0.0.1".to_string()
 }
 
 /// Request to create a new port forward
@@ -128,6 +175,19 @@ mod tests {
         assert_eq!(status, PortForwardStatus::Active);
     }
 
+    #[test]
+    fn test_remote_port_forward() {
+        let pf = PortForward::remote("s".into(), "localhost".into(), 9000, "127.0.0.1".into(), 3000);
+        assert_eq!(pf.direction, ForwardDirection::Remote);
+        assert_eq!(pf.remote_port, 9000);
+        assert_eq!(pf.local_port, 3000);
+        assert!(pf.container_id.is_empty());
+
+        let json = serde_json::to_value(&pf).unwrap();
+        assert_eq!(json["direction"], "remote");
+        assert_eq!(json["localHost"], "127.0.0.1");
+    }
+
     #[test]
     fn test_port_forward_serialization() {
         let pf = PortForward::new("s".into(), "c".into(), 80, 8080, "localhost".into(), 80, "tcp".into());
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+/// Outcome of a prune command, summed over every runtime it ran on
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PruneResult {
+    /// IDs or names of the removed objects, as the runtime printed them
+    pub deleted: Vec<String>,
+    /// Space freed in bytes; 0 when the runtime doesn't report it
+    pub reclaimed_bytes: i64,
+}
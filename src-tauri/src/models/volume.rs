@@ -2,6 +2,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 use crate::models::container::ContainerRuntime;
+use crate::models::error::ContainerError;
 use crate::models::system::SystemId;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -17,6 +18,64 @@ pub struct Volume {
     pub system_id: SystemId,
 }
 
+/// Optional settings for `create_volume`; the default creates a plain local volume
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct VolumeCreateOptions {
+    pub driver: Option<String>,
+    /// Driver-specific options (`--opt key=value`)
+    pub driver_opts: std::collections::HashMap<String, String>,
+    pub labels: std::collections::HashMap<String, String>,
+}
+
+impl VolumeCreateOptions {
+    /// Whether anything beyond a name was asked for
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn validate(&self) -> Result<(), ContainerError> {
+        if let Some(driver) = &self.driver {
+            validate_driver_name(driver)?;
+        }
+        validate_option_keys("driver option", self.driver_opts.keys())?;
+        validate_option_keys("label", self.labels.keys())
+    }
+}
+
+/// Driver names are passed unquoted, so only allow what plugin references use
+pub(crate) fn validate_driver_name(driver: &str) -> Result<(), ContainerError> {
+    let valid = !driver.is_empty()
+        && driver
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-' | '/' | ':'));
+    if valid {
+        Ok(())
+    } else {
+        Err(ContainerError::InvalidConfiguration(format!(
+            "Invalid driver '{}'",
+            driver
+        )))
+    }
+}
+
+/// Keys of `key=value` flags can't be empty or contain '='
+pub(crate) fn validate_option_keys<'a>(
+    kind: &str,
+    keys: impl IntoIterator<Item = &'a String>,
+) -> Result<(), ContainerError> {
+    match keys
+        .into_iter()
+        .find(|key| key.is_empty() || key.contains('='))
+    {
+        Some(key) => Err(ContainerError::InvalidConfiguration(format!(
+            "Invalid {} name '{}'",
+            kind, key
+        ))),
+        None => Ok(()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
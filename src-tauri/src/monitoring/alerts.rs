@@ -0,0 +1,146 @@
+use std::collections::HashSet;
+
+use crate::models::system::{AlertConfig, AlertState, LiveSystemMetrics, SystemAlert};
+
+/// Event name for threshold alerts
+pub const ALERT_EVENT: &str = "system:alert";
+
+/// How far (in percentage points) a metric must drop below its threshold
+/// before the alert is considered resolved. Prevents flapping around the limit.
+const RECOVERY_MARGIN: f32 = 2.0;
+
+/// Tracks which alerts are currently active for one system so events are only
+/// emitted on transitions (into the alert state and back out of it).
+#[derive(Debug, Default)]
+pub struct AlertTracker {
+    active: HashSet<&'static str>,
+}
+
+impl AlertTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Compare a sample against the configured thresholds and return the
+    /// alerts that changed state since the previous sample.
+    pub fn evaluate(&mut self, config: &AlertConfig, metrics: &LiveSystemMetrics) -> Vec<SystemAlert> {
+        let checks: [(&'static str, Option<f32>, Option<f32>); 4] = [
+            ("cpuPct", config.cpu_pct, Some(metrics.cpu_usage_percent)),
+            ("memoryPct", config.memory_pct, Some(metrics.memory_usage_percent)),
+            ("diskPct", config.disk_pct.map(f32::from), metrics.disk_usage_percent.map(f32::from)),
+            ("swapPct", config.swap_pct, metrics.swap_usage_percent),
+        ];
+
+        let mut alerts = Vec::new();
+
+        for (field, threshold, value) in checks {
+            let Some(threshold) = threshold else {
+                // Threshold removed: forget any active alert without emitting
+                self.active.remove(field);
+                continue;
+            };
+            let Some(value) = value else {
+                continue;
+            };
+
+            let was_active = self.active.contains(field);
+            let state = if !was_active && value > threshold {
+                self.active.insert(field);
+                AlertState::Triggered
+            } else if was_active && value < threshold - RECOVERY_MARGIN {
+                self.active.remove(field);
+                AlertState::Resolved
+            } else {
+                continue;
+            };
+
+            alerts.push(SystemAlert {
+                system_id: metrics.system_id.clone(),
+                field: field.to_string(),
+                value,
+                threshold,
+                state,
+                timestamp: metrics.timestamp,
+            });
+        }
+
+        alerts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metrics(cpu: f32, disk: Option<u8>) -> LiveSystemMetrics {
+        LiveSystemMetrics {
+            system_id: "sys-1".to_string(),
+            timestamp: 0,
+            cpu_usage_percent: cpu,
+            memory_usage_percent: 10.0,
+            memory_used: None,
+            memory_total: None,
+            load_average: None,
+            swap_usage_percent: None,
+            disk_read_bytes_per_sec: None,
+            disk_write_bytes_per_sec: None,
+            disk_io_bytes_per_sec: None,
+            net_rx_bytes_per_sec: None,
+            net_tx_bytes_per_sec: None,
+            disk_usage_percent: disk,
+        }
+    }
+
+    #[test]
+    fn test_alert_emitted_only_on_transition() {
+        let config = AlertConfig {
+            cpu_pct: Some(80.0),
+            ..AlertConfig::default()
+        };
+        let mut tracker = AlertTracker::new();
+
+        assert!(tracker.evaluate(&config, &metrics(50.0, None)).is_empty());
+
+        let alerts = tracker.evaluate(&config, &metrics(90.0, None));
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].field, "cpuPct");
+        assert_eq!(alerts[0].state, AlertState::Triggered);
+
+        // Still above threshold: no repeat event
+        assert!(tracker.evaluate(&config, &metrics(95.0, None)).is_empty());
+
+        // Within the recovery margin: still active
+        assert!(tracker.evaluate(&config, &metrics(79.0, None)).is_empty());
+
+        let alerts = tracker.evaluate(&config, &metrics(60.0, None));
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].state, AlertState::Resolved);
+
+        assert!(tracker.evaluate(&config, &metrics(60.0, None)).is_empty());
+    }
+
+    #[test]
+    fn test_disk_alert_uses_disk_usage() {
+        let config = AlertConfig {
+            disk_pct: Some(85),
+            ..AlertConfig::default()
+        };
+        let mut tracker = AlertTracker::new();
+
+        // Missing disk reading never triggers
+        assert!(tracker.evaluate(&config, &metrics(0.0, None)).is_empty());
+
+        let alerts = tracker.evaluate(&config, &metrics(0.0, Some(92)));
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].field, "diskPct");
+        assert!((alerts[0].threshold - 85.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_no_alerts_without_thresholds() {
+        let mut tracker = AlertTracker::new();
+        assert!(tracker
+            .evaluate(&AlertConfig::default(), &metrics(100.0, Some(100)))
+            .is_empty());
+    }
+}
@@ -0,0 +1,170 @@
+use std::sync::Mutex;
+
+use futures::stream::{self, StreamExt};
+use tauri::{AppHandle, Manager};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use crate::models::error::ContainerError;
+use crate::models::system::{ConnectionState, ConnectionType};
+use crate::state::AppState;
+
+/// Default time between health checks
+pub const DEFAULT_HEALTH_INTERVAL_MS: u64 = 30_000;
+
+/// A check that takes longer than this marks the connection as errored
+const HEALTH_CHECK_TIMEOUT: tokio::time::Duration = tokio::time::Duration::from_secs(10);
+
+/// How many systems are checked at the same time
+const HEALTH_CHECK_CONCURRENCY: usize = 8;
+
+/// Periodically validates the SSH connection of every connected remote system,
+/// so a rebooted or unreachable host stops showing as connected.
+#[derive(Default)]
+pub struct HealthPoller {
+    poller: Mutex<Option<PollerHandle>>,
+}
+
+struct PollerHandle {
+    task: JoinHandle<()>,
+    stop_tx: mpsc::Sender<()>,
+}
+
+impl HealthPoller {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start polling at the given interval, replacing a poller that is already running.
+    pub fn start(&self, app: AppHandle, interval_ms: u64) {
+        let (stop_tx, mut stop_rx) = mpsc::channel::<()>(1);
+
+        let task = tokio::spawn(async move {
+            let mut interval =
+                tokio::time::interval(tokio::time::Duration::from_millis(interval_ms));
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            tracing::info!(
+                "Started connection health polling (interval: {}ms)",
+                interval_ms
+            );
+
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => Self::check_all(&app).await,
+                    _ = stop_rx.recv() => break,
+                }
+            }
+
+            tracing::info!("Connection health polling stopped");
+        });
+
+        let previous = self
+            .poller
+            .lock()
+            .unwrap()
+            .replace(PollerHandle { task, stop_tx });
+        if let Some(handle) = previous {
+            handle.task.abort();
+        }
+    }
+
+    /// Stop polling. Returns false if no poller was running.
+    pub async fn stop(&self) -> bool {
+        let Some(handle) = self.poller.lock().unwrap().take() else {
+            return false;
+        };
+        let _ = handle.stop_tx.send(()).await;
+        let _ = tokio::time::timeout(tokio::time::Duration::from_secs(2), handle.task).await;
+        true
+    }
+
+    /// Validate every connected remote system and publish the ones whose state changed
+    async fn check_all(app: &AppHandle) {
+        let state = app.state::<AppState>();
+        let system_ids: Vec<String> = state
+            .list_systems()
+            .into_iter()
+            .filter(|system| system.connection_type == ConnectionType::Remote)
+            .map(|system| system.id.0)
+            .filter(|id| state.connection_state(id) == ConnectionState::Connected)
+            .collect();
+
+        stream::iter(system_ids)
+            .for_each_concurrent(HEALTH_CHECK_CONCURRENCY, |system_id| async move {
+                let result = tokio::time::timeout(
+                    HEALTH_CHECK_TIMEOUT,
+                    crate::ssh::validate_connection(&system_id),
+                )
+                .await
+                .unwrap_or_else(|_| {
+                    Err(ContainerError::NetworkTimeout(format!(
+                        "Health check for {} timed out",
+                        system_id
+                    )))
+                });
+
+                let Some((new_state, error)) = Self::health_transition(result) else {
+                    return;
+                };
+
+                let state = app.state::<AppState>();
+                // Skip systems the user disconnected while the check was running
+                if state.connection_state(&system_id) != ConnectionState::Connected {
+                    return;
+                }
+                tracing::warn!(
+                    "Health check marked system {} as {:?}",
+                    system_id,
+                    new_state
+                );
+                state.set_connection_state_with_error(&system_id, new_state, error);
+            })
+            .await;
+    }
+
+    /// Map a health check result to the state a connected system should move to,
+    /// or None when the connection is still healthy.
+    fn health_transition(
+        result: Result<bool, ContainerError>,
+    ) -> Option<(ConnectionState, Option<String>)> {
+        match result {
+            Ok(true) => None,
+            Ok(false) => Some((ConnectionState::Disconnected, None)),
+            // The pool no longer holds a connection for it
+            Err(ContainerError::SystemNotFound(_)) => Some((ConnectionState::Disconnected, None)),
+            Err(e) => Some((ConnectionState::Error, Some(e.to_string()))),
+        }
+    }
+}
+
+impl Drop for HealthPoller {
+    fn drop(&mut self) {
+        if let Some(handle) = self.poller.lock().unwrap().take() {
+            handle.task.abort();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn health_transition_maps_check_results() {
+        assert_eq!(HealthPoller::health_transition(Ok(true)), None);
+        assert_eq!(
+            HealthPoller::health_transition(Ok(false)),
+            Some((ConnectionState::Disconnected, None))
+        );
+        assert_eq!(
+            HealthPoller::health_transition(Err(ContainerError::SystemNotFound("sys-1".into()))),
+            Some((ConnectionState::Disconnected, None))
+        );
+
+        let (state, error) =
+            HealthPoller::health_transition(Err(ContainerError::NetworkTimeout("slow".into())))
+                .unwrap();
+        assert_eq!(state, ConnectionState::Error);
+        assert!(error.unwrap().contains("slow"));
+    }
+}
@@ -1,4 +1,10 @@
+pub mod alerts;
+pub mod health;
+pub mod prometheus;
+
 use dashmap::DashMap;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use tauri::{AppHandle, Emitter, Manager};
 use tokio::sync::mpsc;
@@ -6,17 +12,41 @@ use tokio::task::JoinHandle;
 
 use crate::executor::local::LocalExecutor;
 use crate::executor::CommandExecutor;
-use crate::models::system::{ConnectionState, ConnectionType, LiveSystemMetrics};
+use crate::models::container::ContainerMetrics;
+use crate::models::system::{
+    AlertConfig, ConnectionState, ConnectionType, LiveSystemMetrics, MetricsCounters,
+};
 use crate::runtime::{CommandBuilder, OutputParser};
 use crate::state::AppState;
 
+use alerts::{AlertTracker, ALERT_EVENT};
+
 /// Event name for live metrics updates
 pub const METRICS_EVENT: &str = "system:metrics";
 
+/// Event name for per-container metrics updates
+pub const CONTAINER_METRICS_EVENT: &str = "container:metrics";
+
+/// Maximum number of samples kept per system for history/sparklines
+pub const MAX_HISTORY_SAMPLES: usize = 300;
+
+/// Time limit for one metrics fetch; longer than the default command limit so
+/// an overloaded host slows the monitor down instead of failing every tick
+const MONITOR_COMMAND_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(120);
+
+/// In adaptive mode the effective interval never exceeds this multiple of the configured one
+const ADAPTIVE_MAX_BACKOFF_FACTOR: u64 = 8;
+
 /// Manages background monitoring tasks for connected systems
 pub struct MonitoringManager {
     /// Active monitoring tasks, keyed by system_id
     active_monitors: DashMap<String, MonitorHandle>,
+    /// Active per-container monitoring tasks, keyed by system_id
+    container_monitors: DashMap<String, MonitorHandle>,
+    /// Rolling metrics history, keyed by system_id (bounded to MAX_HISTORY_SAMPLES)
+    history: Arc<DashMap<String, VecDeque<LiveSystemMetrics>>>,
+    /// Cached alert thresholds, keyed by system_id (persisted in the database)
+    alert_configs: Arc<DashMap<String, AlertConfig>>,
 }
 
 struct MonitorHandle {
@@ -24,6 +54,10 @@ struct MonitorHandle {
     task: JoinHandle<()>,
     /// Channel to signal stop
     stop_tx: mpsc::Sender<()>,
+    /// Interval currently in effect (differs from the configured one while backing off)
+    effective_interval_ms: Arc<AtomicU64>,
+    /// When set, ticks are skipped but the task and its history stay alive
+    paused: Arc<AtomicBool>,
 }
 
 impl Default for MonitoringManager {
@@ -36,15 +70,23 @@ impl MonitoringManager {
     pub fn new() -> Self {
         Self {
             active_monitors: DashMap::new(),
+            container_monitors: DashMap::new(),
+            history: Arc::new(DashMap::new()),
+            alert_configs: Arc::new(DashMap::new()),
         }
     }
 
-    /// Start monitoring a system at the specified interval
+    /// Start monitoring a system at the specified interval.
+    ///
+    /// In adaptive mode, a fetch that takes longer than the current interval doubles
+    /// it (up to a cap) so slow hosts aren't hit with overlapping requests; fast
+    /// fetches halve it back toward the configured interval.
     pub fn start_monitoring(
         &self,
         app: AppHandle,
         system_id: String,
         interval_ms: u64,
+        adaptive: bool,
     ) -> bool {
         // Don't start if already monitoring
         if self.active_monitors.contains_key(&system_id) {
@@ -55,11 +97,40 @@ impl MonitoringManager {
         let (stop_tx, mut stop_rx) = mpsc::channel::<()>(1);
         let system_id_clone = system_id.clone();
         let app_clone = app.clone();
+        let history = Arc::clone(&self.history);
+        let alert_configs = Arc::clone(&self.alert_configs);
+        let effective_interval_ms = Arc::new(AtomicU64::new(interval_ms));
+        let effective_interval_clone = Arc::clone(&effective_interval_ms);
+        let paused = Arc::new(AtomicBool::new(false));
+        let paused_clone = Arc::clone(&paused);
 
         let task = tokio::spawn(async move {
             let mut interval = tokio::time::interval(tokio::time::Duration::from_millis(interval_ms));
+            // Never fire catch-up ticks back to back after a slow fetch
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
 
-            tracing::info!("Started monitoring for system {} (interval: {}ms)", system_id_clone, interval_ms);
+            tracing::info!(
+                "Started monitoring for system {} (interval: {}ms, adaptive: {})",
+                system_id_clone,
+                interval_ms,
+                adaptive
+            );
+
+            // Raw counters from the previous tick, used to compute throughput rates
+            let mut previous: Option<MetricsCounters> = None;
+
+            // Load persisted alert thresholds unless they were already set this session
+            if !alert_configs.contains_key(&system_id_clone) {
+                let state = app_clone.state::<AppState>();
+                let config = state
+                    .db
+                    .lock()
+                    .ok()
+                    .and_then(|conn| crate::database::get_alert_config(&conn, &system_id_clone).ok())
+                    .unwrap_or_default();
+                alert_configs.insert(system_id_clone.clone(), config);
+            }
+            let mut alert_tracker = AlertTracker::new();
 
             loop {
                 tokio::select! {
@@ -70,16 +141,60 @@ impl MonitoringManager {
                         // Check if system is still connected
                         if state.connection_state(&system_id_clone) != ConnectionState::Connected {
                             tracing::debug!("System {} disconnected, stopping monitor", system_id_clone);
+                            history.remove(&system_id_clone);
                             break;
                         }
 
+                        if paused_clone.load(Ordering::Relaxed) {
+                            // Rates after resuming must not span the paused gap
+                            previous = None;
+                            continue;
+                        }
+
                         // Fetch metrics
-                        match Self::fetch_metrics_internal(&app_clone, &system_id_clone).await {
-                            Ok(metrics) => {
+                        let fetch_started = tokio::time::Instant::now();
+                        let fetch_result = Self::fetch_metrics_internal(&app_clone, &system_id_clone, previous.as_ref()).await;
+
+                        if adaptive {
+                            let elapsed_ms = fetch_started.elapsed().as_millis() as u64;
+                            let current_interval_ms = effective_interval_clone.load(Ordering::Relaxed);
+                            let next_ms = Self::next_adaptive_interval(interval_ms, current_interval_ms, elapsed_ms);
+                            if next_ms != current_interval_ms {
+                                tracing::debug!(
+                                    "Adjusting monitor interval for {}: {}ms -> {}ms (fetch took {}ms)",
+                                    system_id_clone, current_interval_ms, next_ms, elapsed_ms
+                                );
+                                effective_interval_clone.store(next_ms, Ordering::Relaxed);
+                                let period = tokio::time::Duration::from_millis(next_ms);
+                                interval = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+                                interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+                            }
+                        }
+
+                        match fetch_result {
+                            Ok((metrics, counters)) => {
+                                previous = Some(counters);
+                                Self::push_history(&history, &system_id_clone, metrics.clone());
+
                                 // Emit event to frontend
                                 if let Err(e) = app_clone.emit(METRICS_EVENT, &metrics) {
                                     tracing::warn!("Failed to emit metrics event for {}: {}", system_id_clone, e);
                                 }
+
+                                // Emit threshold alerts on state transitions only
+                                let config = alert_configs
+                                    .get(&system_id_clone)
+                                    .map(|c| c.clone())
+                                    .unwrap_or_default();
+                                for alert in alert_tracker.evaluate(&config, &metrics) {
+                                    tracing::debug!(
+                                        "Alert {:?} for {}: {}={} (threshold {})",
+                                        alert.state, system_id_clone, alert.field, alert.value, alert.threshold
+                                    );
+                                    if let Err(e) = app_clone.emit(ALERT_EVENT, &alert) {
+                                        tracing::warn!("Failed to emit alert event for {}: {}", system_id_clone, e);
+                                    }
+                                }
                             }
                             Err(e) => {
                                 tracing::debug!("Failed to fetch metrics for {}: {}", system_id_clone, e);
@@ -98,7 +213,7 @@ impl MonitoringManager {
 
         self.active_monitors.insert(
             system_id.clone(),
-            MonitorHandle { task, stop_tx },
+            MonitorHandle { task, stop_tx, effective_interval_ms, paused },
         );
 
         true
@@ -114,6 +229,7 @@ impl MonitoringManager {
                 tokio::time::Duration::from_secs(2),
                 handle.task,
             ).await;
+            self.history.remove(system_id);
             tracing::info!("Stopped monitoring for system {}", system_id);
             true
         } else {
@@ -121,6 +237,104 @@ impl MonitoringManager {
         }
     }
 
+    /// Compute the next adaptive interval: double (up to a cap) when the fetch
+    /// overran the current interval, halve back toward the configured interval
+    /// once fetches take less than half of it.
+    fn next_adaptive_interval(configured_ms: u64, current_ms: u64, elapsed_ms: u64) -> u64 {
+        let max_ms = configured_ms.saturating_mul(ADAPTIVE_MAX_BACKOFF_FACTOR);
+        if elapsed_ms > current_ms {
+            current_ms.saturating_mul(2).min(max_ms)
+        } else if current_ms > configured_ms && elapsed_ms < current_ms / 2 {
+            (current_ms / 2).max(configured_ms)
+        } else {
+            current_ms
+        }
+    }
+
+    /// Update the cached alert thresholds used by a running (or future) monitor.
+    /// Persisting the config is the caller's responsibility.
+    pub fn set_alert_config(&self, system_id: &str, config: AlertConfig) {
+        self.alert_configs.insert(system_id.to_string(), config);
+    }
+
+    /// Get the most recent metrics samples for a system, oldest first.
+    /// `max_points` limits the result to the newest N samples.
+    pub fn metrics_history(&self, system_id: &str, max_points: Option<usize>) -> Vec<LiveSystemMetrics> {
+        let Some(samples) = self.history.get(system_id) else {
+            return Vec::new();
+        };
+        let take = max_points.unwrap_or(samples.len()).min(samples.len());
+        samples.iter().skip(samples.len() - take).cloned().collect()
+    }
+
+    /// Newest sample of every system with history, ordered by system id
+    pub fn latest_metrics(&self) -> Vec<LiveSystemMetrics> {
+        let mut latest: Vec<LiveSystemMetrics> = self
+            .history
+            .iter()
+            .filter_map(|samples| samples.back().cloned())
+            .collect();
+        latest.sort_by(|a, b| a.system_id.cmp(&b.system_id));
+        latest
+    }
+
+    /// Append a sample to a system's history, dropping the oldest beyond the cap
+    fn push_history(
+        history: &DashMap<String, VecDeque<LiveSystemMetrics>>,
+        system_id: &str,
+        metrics: LiveSystemMetrics,
+    ) {
+        let mut samples = history.entry(system_id.to_string()).or_default();
+        while samples.len() >= MAX_HISTORY_SAMPLES {
+            samples.pop_front();
+        }
+        samples.push_back(metrics);
+    }
+
+    /// Pause monitoring a system without tearing down the task or its history
+    pub fn pause_monitoring(&self, system_id: &str) -> bool {
+        self.set_paused(system_id, true)
+    }
+
+    /// Resume a paused monitor
+    pub fn resume_monitoring(&self, system_id: &str) -> bool {
+        self.set_paused(system_id, false)
+    }
+
+    /// Flip the pause flag; returns false if the system isn't monitored or already in that state
+    fn set_paused(&self, system_id: &str, paused: bool) -> bool {
+        match self.active_monitors.get(system_id) {
+            Some(handle) => {
+                let changed = handle.paused.swap(paused, Ordering::Relaxed) != paused;
+                if changed {
+                    tracing::info!(
+                        "{} monitoring for system {}",
+                        if paused { "Paused" } else { "Resumed" },
+                        system_id
+                    );
+                }
+                changed
+            }
+            None => false,
+        }
+    }
+
+    /// Check if a system's monitor exists but is paused
+    pub fn is_paused(&self, system_id: &str) -> bool {
+        self.active_monitors
+            .get(system_id)
+            .map(|h| h.paused.load(Ordering::Relaxed))
+            .unwrap_or(false)
+    }
+
+    /// Get the polling interval currently in effect for a monitored system,
+    /// which is longer than the configured one while adaptive polling backs off
+    pub fn effective_interval(&self, system_id: &str) -> Option<u64> {
+        self.active_monitors
+            .get(system_id)
+            .map(|h| h.effective_interval_ms.load(Ordering::Relaxed))
+    }
+
     /// Check if a system is being monitored
     pub fn is_monitoring(&self, system_id: &str) -> bool {
         self.active_monitors.contains_key(system_id)
@@ -144,13 +358,117 @@ impl MonitoringManager {
         for system_id in system_ids {
             self.stop_monitoring(&system_id).await;
         }
+
+        let container_system_ids: Vec<String> = self.container_monitors
+            .iter()
+            .map(|r| r.key().clone())
+            .collect();
+
+        for system_id in container_system_ids {
+            self.stop_container_monitoring(&system_id).await;
+        }
+    }
+
+    /// Start per-container monitoring for a system at the specified interval.
+    /// An empty `container_ids` list monitors all running containers.
+    pub fn start_container_monitoring(
+        &self,
+        app: AppHandle,
+        system_id: String,
+        container_ids: Vec<String>,
+        interval_ms: u64,
+    ) -> bool {
+        // Don't start if already monitoring
+        if self.container_monitors.contains_key(&system_id) {
+            tracing::debug!("Already monitoring containers on system {}", system_id);
+            return false;
+        }
+
+        let (stop_tx, mut stop_rx) = mpsc::channel::<()>(1);
+        let system_id_clone = system_id.clone();
+        let app_clone = app.clone();
+
+        let task = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_millis(interval_ms));
+
+            tracing::info!(
+                "Started container monitoring for system {} ({} containers, interval: {}ms)",
+                system_id_clone,
+                container_ids.len(),
+                interval_ms
+            );
+
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {
+                        let state = app_clone.state::<AppState>();
+
+                        // Check if system is still connected
+                        if state.connection_state(&system_id_clone) != ConnectionState::Connected {
+                            tracing::debug!("System {} disconnected, stopping container monitor", system_id_clone);
+                            break;
+                        }
+
+                        match Self::fetch_container_metrics_internal(&app_clone, &system_id_clone, &container_ids).await {
+                            Ok(metrics) => {
+                                if let Err(e) = app_clone.emit(CONTAINER_METRICS_EVENT, &metrics) {
+                                    tracing::warn!("Failed to emit container metrics event for {}: {}", system_id_clone, e);
+                                }
+                            }
+                            Err(e) => {
+                                tracing::debug!("Failed to fetch container metrics for {}: {}", system_id_clone, e);
+                            }
+                        }
+                    }
+                    _ = stop_rx.recv() => {
+                        tracing::info!("Received container monitor stop signal for system {}", system_id_clone);
+                        break;
+                    }
+                }
+            }
+
+            tracing::info!("Container monitoring stopped for system {}", system_id_clone);
+        });
+
+        self.container_monitors.insert(
+            system_id,
+            MonitorHandle {
+                task,
+                stop_tx,
+                effective_interval_ms: Arc::new(AtomicU64::new(interval_ms)),
+                paused: Arc::new(AtomicBool::new(false)),
+            },
+        );
+
+        true
+    }
+
+    /// Stop per-container monitoring for a system
+    pub async fn stop_container_monitoring(&self, system_id: &str) -> bool {
+        if let Some((_, handle)) = self.container_monitors.remove(system_id) {
+            let _ = handle.stop_tx.send(()).await;
+            let _ = tokio::time::timeout(
+                tokio::time::Duration::from_secs(2),
+                handle.task,
+            ).await;
+            tracing::info!("Stopped container monitoring for system {}", system_id);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Check if containers on a system are being monitored
+    pub fn is_container_monitoring(&self, system_id: &str) -> bool {
+        self.container_monitors.contains_key(system_id)
     }
 
     /// Fetch metrics for a system (internal version for the monitoring loop)
     async fn fetch_metrics_internal(
         app: &AppHandle,
         system_id: &str,
-    ) -> Result<LiveSystemMetrics, String> {
+        previous: Option<&MetricsCounters>,
+    ) -> Result<(LiveSystemMetrics, MetricsCounters), String> {
         let state = app.state::<AppState>();
 
         let system = state
@@ -166,7 +484,7 @@ impl MonitoringManager {
         // Execute command based on connection type
         let result = match system.connection_type {
             ConnectionType::Local => {
-                let executor = LocalExecutor::new();
+                let executor = LocalExecutor::new().with_timeout(Some(MONITOR_COMMAND_TIMEOUT));
                 if cfg!(windows) {
                     executor.execute_powershell(command).await
                 } else {
@@ -174,13 +492,59 @@ impl MonitoringManager {
                 }
             }
             ConnectionType::Remote => {
-                crate::ssh::execute_on_system(system_id, command).await
+                crate::ssh::execute_on_system_with_timeout(
+                    system_id,
+                    command,
+                    Some(MONITOR_COMMAND_TIMEOUT),
+                )
+                .await
+            }
+        };
+
+        match result {
+            Ok(res) if res.success() => {
+                Ok(OutputParser::parse_live_metrics(&res.stdout, system_id, previous))
+            }
+            Ok(res) => Err(format!("Command failed: {}", res.stderr)),
+            Err(e) => Err(format!("Execution error: {}", e)),
+        }
+    }
+
+    /// Fetch per-container stats for a system using its primary runtime
+    async fn fetch_container_metrics_internal(
+        app: &AppHandle,
+        system_id: &str,
+        container_ids: &[String],
+    ) -> Result<Vec<ContainerMetrics>, String> {
+        let state = app.state::<AppState>();
+
+        let system = state
+            .get_system(system_id)
+            .ok_or_else(|| format!("System {} not found", system_id))?;
+
+        let ids: Vec<&str> = container_ids.iter().map(|id| id.as_str()).collect();
+        let command = CommandBuilder::container_stats(system.primary_runtime, &ids);
+
+        let result = match system.connection_type {
+            ConnectionType::Local => {
+                LocalExecutor::new()
+                    .with_timeout(Some(MONITOR_COMMAND_TIMEOUT))
+                    .execute(&command)
+                    .await
+            }
+            ConnectionType::Remote => {
+                crate::ssh::execute_on_system_with_timeout(
+                    system_id,
+                    &command,
+                    Some(MONITOR_COMMAND_TIMEOUT),
+                )
+                .await
             }
         };
 
         match result {
             Ok(res) if res.success() => {
-                Ok(OutputParser::parse_live_metrics(&res.stdout, system_id))
+                OutputParser::parse_container_stats(&res.stdout, system_id).map_err(|e| e.to_string())
             }
             Ok(res) => Err(format!("Command failed: {}", res.stderr)),
             Err(e) => Err(format!("Execution error: {}", e)),
@@ -194,5 +558,84 @@ impl Drop for MonitoringManager {
         for entry in self.active_monitors.iter() {
             entry.value().task.abort();
         }
+        for entry in self.container_monitors.iter() {
+            entry.value().task.abort();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(system_id: &str, timestamp: i64) -> LiveSystemMetrics {
+        LiveSystemMetrics {
+            system_id: system_id.to_string(),
+            timestamp,
+            cpu_usage_percent: 0.0,
+            memory_usage_percent: 0.0,
+            memory_used: None,
+            memory_total: None,
+            load_average: None,
+            swap_usage_percent: None,
+            disk_read_bytes_per_sec: None,
+            disk_write_bytes_per_sec: None,
+            disk_io_bytes_per_sec: None,
+            net_rx_bytes_per_sec: None,
+            net_tx_bytes_per_sec: None,
+            disk_usage_percent: None,
+        }
+    }
+
+    #[test]
+    fn test_history_is_capped() {
+        let manager = MonitoringManager::new();
+        for ts in 0..(MAX_HISTORY_SAMPLES as i64 + 50) {
+            MonitoringManager::push_history(&manager.history, "sys-1", sample("sys-1", ts));
+        }
+
+        let history = manager.metrics_history("sys-1", None);
+        assert_eq!(history.len(), MAX_HISTORY_SAMPLES);
+        assert_eq!(history.first().unwrap().timestamp, 50);
+        assert_eq!(history.last().unwrap().timestamp, MAX_HISTORY_SAMPLES as i64 + 49);
+    }
+
+    #[test]
+    fn test_adaptive_interval_backs_off_and_recovers() {
+        // Slow fetch doubles the interval
+        assert_eq!(MonitoringManager::next_adaptive_interval(1000, 1000, 1500), 2000);
+        // Capped at the max backoff factor
+        assert_eq!(MonitoringManager::next_adaptive_interval(1000, 8000, 9000), 8000);
+        // Fast fetch halves back toward the configured interval
+        assert_eq!(MonitoringManager::next_adaptive_interval(1000, 4000, 100), 2000);
+        assert_eq!(MonitoringManager::next_adaptive_interval(1000, 1500, 100), 1000);
+        // Never drops below the configured interval
+        assert_eq!(MonitoringManager::next_adaptive_interval(1000, 1000, 100), 1000);
+        // Moderately slow fetches hold steady
+        assert_eq!(MonitoringManager::next_adaptive_interval(1000, 4000, 3000), 4000);
+    }
+
+    #[test]
+    fn test_pause_unknown_system_is_noop() {
+        let manager = MonitoringManager::new();
+        assert!(!manager.pause_monitoring("missing"));
+        assert!(!manager.resume_monitoring("missing"));
+        assert!(!manager.is_paused("missing"));
+        assert_eq!(manager.effective_interval("missing"), None);
+    }
+
+    #[test]
+    fn test_history_max_points_returns_newest() {
+        let manager = MonitoringManager::new();
+        for ts in 0..10 {
+            MonitoringManager::push_history(&manager.history, "sys-1", sample("sys-1", ts));
+        }
+
+        let history = manager.metrics_history("sys-1", Some(3));
+        let timestamps: Vec<i64> = history.iter().map(|m| m.timestamp).collect();
+        assert_eq!(timestamps, vec![7, 8, 9]);
+
+        assert_eq!(manager.metrics_history("sys-1", Some(100)).len(), 10);
+        assert!(manager.metrics_history("unknown", None).is_empty());
     }
 }
@@ -0,0 +1,330 @@
+//! Prometheus text exposition of the newest monitoring sample of each system,
+//! returned by `export_metrics_prometheus` and optionally served on a local
+//! `/metrics` endpoint for scraping.
+
+use std::fmt::Write;
+use std::sync::Mutex;
+
+use tauri::{AppHandle, Manager};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+use super::MonitoringManager;
+use crate::models::error::ContainerError;
+use crate::models::system::LiveSystemMetrics;
+use crate::state::AppState;
+
+/// Content type of the text exposition format
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Scrapers that don't send their request within this time are dropped
+const REQUEST_TIMEOUT: tokio::time::Duration = tokio::time::Duration::from_secs(5);
+
+/// A gauge exported once per system: metric name, help text and its value in a sample
+type Gauge = (
+    &'static str,
+    &'static str,
+    fn(&LiveSystemMetrics) -> Option<String>,
+);
+
+const GAUGES: &[Gauge] = &[
+    (
+        "containerus_cpu_usage_percent",
+        "CPU usage in percent",
+        |m| Some(m.cpu_usage_percent.to_string()),
+    ),
+    (
+        "containerus_memory_usage_percent",
+        "Memory usage in percent",
+        |m| Some(m.memory_usage_percent.to_string()),
+    ),
+    (
+        "containerus_swap_usage_percent",
+        "Swap usage in percent",
+        |m| m.swap_usage_percent.map(|v| v.to_string()),
+    ),
+    (
+        "containerus_disk_usage_percent",
+        "Root filesystem usage in percent",
+        |m| m.disk_usage_percent.map(|v| v.to_string()),
+    ),
+    ("containerus_load1", "1 minute load average", |m| {
+        m.load_average.map(|l| l[0].to_string())
+    }),
+    ("containerus_load5", "5 minute load average", |m| {
+        m.load_average.map(|l| l[1].to_string())
+    }),
+    ("containerus_load15", "15 minute load average", |m| {
+        m.load_average.map(|l| l[2].to_string())
+    }),
+    (
+        "containerus_disk_read_bytes_per_second",
+        "Disk read throughput",
+        |m| m.disk_read_bytes_per_sec.map(|v| v.to_string()),
+    ),
+    (
+        "containerus_disk_write_bytes_per_second",
+        "Disk write throughput",
+        |m| m.disk_write_bytes_per_sec.map(|v| v.to_string()),
+    ),
+    (
+        "containerus_disk_io_bytes_per_second",
+        "Combined disk read and write throughput",
+        |m| m.disk_io_bytes_per_sec.map(|v| v.to_string()),
+    ),
+    (
+        "containerus_network_receive_bytes_per_second",
+        "Network receive throughput on external interfaces",
+        |m| m.net_rx_bytes_per_sec.map(|v| v.to_string()),
+    ),
+    (
+        "containerus_network_transmit_bytes_per_second",
+        "Network transmit throughput on external interfaces",
+        |m| m.net_tx_bytes_per_sec.map(|v| v.to_string()),
+    ),
+];
+
+/// Render the newest sample of every monitored system, labelled with the
+/// system's id and name. Samples carry the time they were taken, so a paused
+/// monitor doesn't look like fresh data.
+pub fn export(state: &AppState, monitoring: &MonitoringManager) -> String {
+    let latest = monitoring.latest_metrics();
+    let samples: Vec<(String, &LiveSystemMetrics)> = latest
+        .iter()
+        .map(|m| {
+            let name = state.get_system(&m.system_id).map(|s| s.name);
+            (name.unwrap_or_default(), m)
+        })
+        .collect();
+    render(&samples)
+}
+
+/// Format `(system name, sample)` pairs as Prometheus text exposition
+fn render(samples: &[(String, &LiveSystemMetrics)]) -> String {
+    let mut out = String::new();
+    for (metric, help, value) in GAUGES {
+        let values: Vec<_> = samples
+            .iter()
+            .filter_map(|(name, m)| Some((name, m, value(m)?)))
+            .collect();
+        if values.is_empty() {
+            continue;
+        }
+
+        let _ = writeln!(out, "# HELP {} {}", metric, help);
+        let _ = writeln!(out, "# TYPE {} gauge", metric);
+        for (name, m, value) in values {
+            let _ = writeln!(
+                out,
+                "{}{{system=\"{}\",name=\"{}\"}} {} {}",
+                metric,
+                escape_label(&m.system_id),
+                escape_label(name),
+                value,
+                m.timestamp
+            );
+        }
+    }
+    out
+}
+
+/// Escape a label value: backslash, double quote and newline
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Serves `/metrics` on the loopback interface while the exporter setting is on
+#[derive(Default)]
+pub struct MetricsExporter {
+    server: Mutex<Option<ServerHandle>>,
+}
+
+struct ServerHandle {
+    port: u16,
+    task: JoinHandle<()>,
+}
+
+impl MetricsExporter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Serve on 127.0.0.1:`port`, or stop serving when `port` is None.
+    /// A server already listening on the same port is left running.
+    pub async fn apply(&self, app: AppHandle, port: Option<u16>) -> Result<(), ContainerError> {
+        {
+            let mut server = self.server.lock().unwrap();
+            if server.as_ref().map(|s| s.port) == port {
+                return Ok(());
+            }
+            if let Some(handle) = server.take() {
+                handle.task.abort();
+                tracing::info!("Stopped metrics exporter on port {}", handle.port);
+            }
+        }
+
+        let Some(port) = port else {
+            return Ok(());
+        };
+        if port == 0 {
+            return Err(ContainerError::InvalidConfiguration(
+                "The metrics exporter needs a fixed port".to_string(),
+            ));
+        }
+
+        let listener = TcpListener::bind(("127.0.0.1", port)).await.map_err(|e| {
+            ContainerError::InvalidConfiguration(format!(
+                "Could not serve metrics on 127.0.0.1:{}: {}",
+                port, e
+            ))
+        })?;
+        tracing::info!(
+            "Serving Prometheus metrics on http://127.0.0.1:{}/metrics",
+            port
+        );
+
+        let task = tokio::spawn(Self::serve(app, listener));
+        let previous = self
+            .server
+            .lock()
+            .unwrap()
+            .replace(ServerHandle { port, task });
+        if let Some(handle) = previous {
+            handle.task.abort();
+        }
+        Ok(())
+    }
+
+    async fn serve(app: AppHandle, listener: TcpListener) {
+        loop {
+            let stream = match listener.accept().await {
+                Ok((stream, _)) => stream,
+                Err(e) => {
+                    tracing::warn!("Metrics exporter failed to accept a connection: {}", e);
+                    continue;
+                }
+            };
+            let app = app.clone();
+            tokio::spawn(async move {
+                if let Err(e) = Self::respond(&app, stream).await {
+                    tracing::debug!("Metrics request failed: {}", e);
+                }
+            });
+        }
+    }
+
+    /// Answer a single request and close the connection
+    async fn respond(app: &AppHandle, mut stream: TcpStream) -> std::io::Result<()> {
+        let mut buf = [0u8; 1024];
+        let n = tokio::time::timeout(REQUEST_TIMEOUT, stream.read(&mut buf))
+            .await
+            .map_err(|_| std::io::Error::from(std::io::ErrorKind::TimedOut))??;
+        let request = String::from_utf8_lossy(&buf[..n]);
+
+        let (status, body) = match route(&request) {
+            Ok(()) => (
+                "200 OK",
+                export(&app.state::<AppState>(), &app.state::<MonitoringManager>()),
+            ),
+            Err(status) => (status, format!("{}\n", status)),
+        };
+        let response = format!(
+            "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            CONTENT_TYPE,
+            body.len(),
+            body
+        );
+        stream.write_all(response.as_bytes()).await?;
+        stream.shutdown().await
+    }
+}
+
+/// Check a request is `GET /metrics`; otherwise the status line to answer with
+fn route(request: &str) -> Result<(), &'static str> {
+    let mut parts = request
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Err("400 Bad Request");
+    };
+    let path = target.split('?').next().unwrap_or_default();
+    match (method, path) {
+        ("GET", "/metrics") => Ok(()),
+        ("GET", _) => Err("404 Not Found"),
+        _ => Err("405 Method Not Allowed"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(system_id: &str) -> LiveSystemMetrics {
+        LiveSystemMetrics {
+            system_id: system_id.to_string(),
+            timestamp: 1_717_012_895_000,
+            cpu_usage_percent: 42.1,
+            memory_usage_percent: 63.5,
+            memory_used: Some("10.2G".to_string()),
+            memory_total: Some("16G".to_string()),
+            load_average: Some([0.5, 0.75, 1.0]),
+            swap_usage_percent: None,
+            disk_read_bytes_per_sec: Some(4096),
+            disk_write_bytes_per_sec: None,
+            disk_io_bytes_per_sec: Some(4096),
+            net_rx_bytes_per_sec: None,
+            net_tx_bytes_per_sec: None,
+            disk_usage_percent: Some(71),
+        }
+    }
+
+    #[test]
+    fn test_render_exposition() {
+        let web = sample("sys-1");
+        let mut db = sample("sys-2");
+        db.cpu_usage_percent = 3.0;
+        db.load_average = None;
+
+        let text = render(&[("web \"prod\"".to_string(), &web), (String::new(), &db)]);
+
+        assert!(text.contains("# HELP containerus_cpu_usage_percent CPU usage in percent\n"));
+        assert!(text.contains("# TYPE containerus_cpu_usage_percent gauge\n"));
+        assert!(text.contains(
+            "containerus_cpu_usage_percent{system=\"sys-1\",name=\"web \\\"prod\\\"\"} 42.1 1717012895000\n"
+        ));
+        assert!(text.contains(
+            "containerus_cpu_usage_percent{system=\"sys-2\",name=\"\"} 3 1717012895000\n"
+        ));
+        assert!(text.contains("containerus_load5{system=\"sys-1\",name=\"web \\\"prod\\\"\"} 0.75"));
+        assert!(!text.contains("containerus_load5{system=\"sys-2\""));
+        // Metrics no system reports are left out entirely
+        assert!(!text.contains("containerus_swap_usage_percent"));
+        assert_eq!(
+            text.matches("# TYPE containerus_disk_usage_percent")
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_route() {
+        assert_eq!(
+            route("GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n"),
+            Ok(())
+        );
+        assert_eq!(route("GET /metrics?name[]=x HTTP/1.1\r\n"), Ok(()));
+        assert_eq!(route("GET / HTTP/1.1\r\n"), Err("404 Not Found"));
+        assert_eq!(
+            route("POST /metrics HTTP/1.1\r\n"),
+            Err("405 Method Not Allowed")
+        );
+        assert_eq!(route(""), Err("400 Bad Request"));
+    }
+}
@@ -1,6 +1,14 @@
-use crate::models::container::{ContainerAction, ContainerRuntime};
+use std::collections::HashMap;
 
-/// Builder for container runtime commands (Docker, Podman, Apple Container)
+use crate::models::container::{
+    ContainerAction, ContainerCreateSpec, ContainerListFilter, ContainerLogOptions,
+    ContainerRuntime, ContainerStatus, PortSpec,
+};
+use crate::models::file_browser::ArchiveFormat;
+use crate::models::network::NetworkCreateOptions;
+use crate::models::volume::VolumeCreateOptions;
+
+/// Builder for container runtime commands (Docker, Podman, Apple Container, nerdctl)
 pub struct CommandBuilder;
 
 impl CommandBuilder {
@@ -12,21 +20,78 @@ impl CommandBuilder {
     /// Note: Port data is fetched separately via batch_inspect_containers() because
     /// Docker's {{.Ports}} template has known issues returning empty strings.
     pub fn list_containers(runtime: ContainerRuntime) -> String {
-        match runtime {
-            ContainerRuntime::Docker => "docker ps -a --no-trunc --format json".to_string(),
-            ContainerRuntime::Podman => "podman ps -a --no-trunc --format json".to_string(),
-            ContainerRuntime::Apple => "container list --all --format json".to_string(),
+        Self::list_containers_filtered(runtime, &ContainerListFilter::default())
+    }
+
+    /// Build container list command that lets the runtime apply `filter`
+    ///
+    /// Apple's `container list` has no `--filter`, so only `all` is passed on
+    /// and the rest has to be applied with `ContainerListFilter::matches`.
+    pub fn list_containers_filtered(
+        runtime: ContainerRuntime,
+        filter: &ContainerListFilter,
+    ) -> String {
+        let binary = match runtime {
+            ContainerRuntime::Docker => "docker",
+            ContainerRuntime::Podman => "podman",
+            ContainerRuntime::Nerdctl => "nerdctl",
+            ContainerRuntime::Apple => {
+                return if filter.all || filter.status.is_some() {
+                    "container list --all --format json".to_string()
+                } else {
+                    "container list --format json".to_string()
+                };
+            }
+        };
+
+        let mut command = format!("{} ps", binary);
+        if filter.all {
+            command.push_str(" -a");
+        }
+        command.push_str(" --no-trunc --format json");
+
+        let filters = [
+            (
+                "status",
+                filter
+                    .status
+                    .map(Self::status_filter_value)
+                    .map(str::to_string),
+            ),
+            ("label", filter.label.clone()),
+            ("name", filter.name.clone()),
+            ("ancestor", filter.ancestor.clone()),
+        ];
+        for (key, value) in filters {
+            if let Some(value) = value {
+                let filter = format!("{}={}", key, value);
+                command.push_str(&format!(" --filter {}", Self::shell_escape(&filter)));
+            }
+        }
+        command
+    }
+
+    /// Value `--filter status=` takes for a container status
+    fn status_filter_value(status: ContainerStatus) -> &'static str {
+        match status {
+            ContainerStatus::Running => "running",
+            ContainerStatus::Exited => "exited",
+            ContainerStatus::Paused => "paused",
+            ContainerStatus::Restarting => "restarting",
+            ContainerStatus::Removing => "removing",
+            ContainerStatus::Dead => "dead",
+            ContainerStatus::Created => "created",
         }
     }
 
     /// Build container list fallback command (table format for older versions)
     pub fn list_containers_fallback(runtime: ContainerRuntime) -> Option<String> {
         match runtime {
-            ContainerRuntime::Docker | ContainerRuntime::Podman => {
-                let binary = if runtime == ContainerRuntime::Docker {
-                    "docker"
-                } else {
-                    "podman"
+            ContainerRuntime::Docker | ContainerRuntime::Podman | ContainerRuntime::Nerdctl => {
+                let binary = match runtime {
+                    ContainerRuntime::Podman => "podman",
+                    ContainerRuntime::Nerdctl => "nerdctl",
+                    _ => "docker",
                 };
                 Some(format!(
                     "{} ps -a --no-trunc --format 'table {{{{.ID}}}}\\t{{{{.Names}}}}\\t{{{{.Image}}}}\\t{{{{.Status}}}}\\t{{{{.CreatedAt}}}}\\t{{{{.Ports}}}}'",
@@ -39,11 +104,13 @@ impl CommandBuilder {
 
     /// Build container inspect command
     pub fn inspect_container(runtime: ContainerRuntime, container_id: &str) -> String {
-        match runtime {
-            ContainerRuntime::Docker => format!("docker inspect {}", container_id),
-            ContainerRuntime::Podman => format!("podman inspect {}", container_id),
-            ContainerRuntime::Apple => format!("container inspect {}", container_id),
-        }
+        let binary = match runtime {
+            ContainerRuntime::Docker => "docker",
+            ContainerRuntime::Podman => "podman",
+            ContainerRuntime::Nerdctl => "nerdctl",
+            ContainerRuntime::Apple => "container",
+        };
+        format!("{} inspect {}", binary, Self::shell_escape(container_id))
     }
 
     /// Build batch inspect command for multiple containers
@@ -52,6 +119,7 @@ impl CommandBuilder {
         match runtime {
             ContainerRuntime::Docker => format!("docker inspect {}", ids),
             ContainerRuntime::Podman => format!("podman inspect {}", ids),
+            ContainerRuntime::Nerdctl => format!("nerdctl inspect {}", ids),
             ContainerRuntime::Apple => format!("container inspect {}", ids),
         }
     }
@@ -103,6 +171,26 @@ impl CommandBuilder {
                 format!("podman rm {}", container_id)
             }
 
+            // nerdctl (Docker-compatible CLI for containerd)
+            (ContainerRuntime::Nerdctl, ContainerAction::Start) => {
+                format!("nerdctl start {}", container_id)
+            }
+            (ContainerRuntime::Nerdctl, ContainerAction::Stop) => {
+                format!("nerdctl stop {}", container_id)
+            }
+            (ContainerRuntime::Nerdctl, ContainerAction::Restart) => {
+                format!("nerdctl restart {}", container_id)
+            }
+            (ContainerRuntime::Nerdctl, ContainerAction::Pause) => {
+                format!("nerdctl pause {}", container_id)
+            }
+            (ContainerRuntime::Nerdctl, ContainerAction::Unpause) => {
+                format!("nerdctl unpause {}", container_id)
+            }
+            (ContainerRuntime::Nerdctl, ContainerAction::Remove) => {
+                format!("nerdctl rm {}", container_id)
+            }
+
             // Apple Container (slightly different commands)
             (ContainerRuntime::Apple, ContainerAction::Start) => {
                 format!("container start {}", container_id)
@@ -130,46 +218,249 @@ impl CommandBuilder {
         }
     }
 
+    /// Build one command applying `action` to all `container_ids`, or None when
+    /// the runtime can't do it in a single invocation (Apple's chained restart)
+    ///
+    /// `force` only applies to `Remove`, turning it into a forced removal.
+    pub fn bulk_container_action(
+        runtime: ContainerRuntime,
+        action: ContainerAction,
+        container_ids: &[&str],
+        force: bool,
+    ) -> Option<String> {
+        let ids = container_ids.join(" ");
+        match (runtime, action) {
+            (ContainerRuntime::Apple, ContainerAction::Restart) => None,
+            (_, ContainerAction::Remove) if force => {
+                Some(Self::force_remove_container(runtime, &ids))
+            }
+            _ => Some(Self::container_action(runtime, action, &ids)),
+        }
+    }
+
+    /// Build the `run -d` (or `create` when not detached) command for a spec
+    /// already checked with `ContainerCreateSpec::validate`; prints the new ID
+    pub fn create_container(runtime: ContainerRuntime, spec: &ContainerCreateSpec) -> String {
+        let binary = match runtime {
+            ContainerRuntime::Docker => "docker",
+            ContainerRuntime::Podman => "podman",
+            ContainerRuntime::Nerdctl => "nerdctl",
+            ContainerRuntime::Apple => "container",
+        };
+        let mut command = if spec.detach {
+            format!("{} run -d", binary)
+        } else {
+            format!("{} create", binary)
+        };
+
+        if let Some(name) = &spec.name {
+            command.push_str(&format!(" --name {}", Self::shell_escape(name)));
+        }
+        for port in &spec.ports {
+            command.push_str(&format!(
+                " -p {}",
+                Self::shell_escape(&Self::publish_arg(port))
+            ));
+        }
+        for (key, value) in &spec.env {
+            command.push_str(&format!(
+                " -e {}",
+                Self::shell_escape(&format!("{}={}", key, value))
+            ));
+        }
+        for volume in &spec.volumes {
+            let mut mount = format!("{}:{}", volume.source, volume.destination);
+            if volume.read_only {
+                mount.push_str(":ro");
+            }
+            command.push_str(&format!(" -v {}", Self::shell_escape(&mount)));
+        }
+        // Apple Container has no restart policies; validation rejects them
+        if let Some(policy) = spec
+            .restart_policy
+            .as_ref()
+            .filter(|_| runtime != ContainerRuntime::Apple)
+        {
+            command.push_str(&format!(" --restart {}", Self::shell_escape(policy)));
+        }
+        if let Some(network) = &spec.network {
+            command.push_str(&format!(" --network {}", Self::shell_escape(network)));
+        }
+
+        command.push_str(&format!(" {}", Self::shell_escape(&spec.image)));
+        for arg in &spec.command {
+            command.push_str(&format!(" {}", Self::shell_escape(arg)));
+        }
+        command
+    }
+
+    /// `-p` value: `[host_ip:][host_port:]container_port[/protocol]`
+    fn publish_arg(port: &PortSpec) -> String {
+        let mut arg = String::new();
+        if let Some(ip) = &port.host_ip {
+            // IPv6 addresses are bracketed so their colons aren't read as separators
+            if ip.contains(':') {
+                arg.push_str(&format!("[{}]:", ip));
+            } else {
+                arg.push_str(&format!("{}:", ip));
+            }
+        }
+        match port.host_port {
+            Some(host_port) => arg.push_str(&format!("{}:", host_port)),
+            // `ip::80` lets the runtime pick the host port on that address
+            None if port.host_ip.is_some() => arg.push(':'),
+            None => {}
+        }
+        arg.push_str(&port.container_port.to_string());
+        if let Some(protocol) = &port.protocol {
+            arg.push_str(&format!("/{}", protocol));
+        }
+        arg
+    }
+
+    /// Build container rename command; None for Apple Container, which can't rename
+    pub fn rename_container(
+        runtime: ContainerRuntime,
+        container_id: &str,
+        new_name: &str,
+    ) -> Option<String> {
+        let binary = match runtime {
+            ContainerRuntime::Docker => "docker",
+            ContainerRuntime::Podman => "podman",
+            ContainerRuntime::Nerdctl => "nerdctl",
+            ContainerRuntime::Apple => return None,
+        };
+        Some(format!(
+            "{} rename {} {}",
+            binary,
+            Self::shell_escape(container_id),
+            Self::shell_escape(new_name)
+        ))
+    }
+
+    /// Build restart policy update command; `max_retries` only applies to
+    /// `on-failure`. None for Apple Container, which has no restart policies.
+    pub fn update_restart_policy(
+        runtime: ContainerRuntime,
+        container_id: &str,
+        policy: &str,
+        max_retries: Option<u32>,
+    ) -> Option<String> {
+        let binary = match runtime {
+            ContainerRuntime::Docker => "docker",
+            ContainerRuntime::Podman => "podman",
+            ContainerRuntime::Nerdctl => "nerdctl",
+            ContainerRuntime::Apple => return None,
+        };
+        let policy = match max_retries {
+            Some(retries) if policy == "on-failure" => format!("{}:{}", policy, retries),
+            _ => policy.to_string(),
+        };
+        Some(format!(
+            "{} update --restart {} {}",
+            binary,
+            Self::shell_escape(&policy),
+            Self::shell_escape(container_id)
+        ))
+    }
+
     /// Build force remove command
     pub fn force_remove_container(runtime: ContainerRuntime, container_id: &str) -> String {
         match runtime {
             ContainerRuntime::Docker => format!("docker rm -f {}", container_id),
             ContainerRuntime::Podman => format!("podman rm -f {}", container_id),
+            ContainerRuntime::Nerdctl => format!("nerdctl rm -f {}", container_id),
             ContainerRuntime::Apple => format!("container remove --force {}", container_id),
         }
     }
 
     /// Build container logs command
+    /// `since`/`until` take a duration (`10m`) or an RFC3339 timestamp
     pub fn container_logs(
         runtime: ContainerRuntime,
         container_id: &str,
         tail: Option<u32>,
+        since: Option<&str>,
+        until: Option<&str>,
         timestamps: bool,
     ) -> String {
-        let tail_arg = tail.map(|n| format!("--tail {}", n)).unwrap_or_default();
-        let ts_arg = if timestamps { "--timestamps" } else { "" };
-
-        match runtime {
-            ContainerRuntime::Docker => {
-                format!("docker logs {} {} {}", tail_arg, ts_arg, container_id).trim().to_string()
+        let binary = match runtime {
+            ContainerRuntime::Docker => "docker",
+            ContainerRuntime::Podman => "podman",
+            ContainerRuntime::Nerdctl => "nerdctl",
+            ContainerRuntime::Apple => "container",
+        };
+        let mut command = format!("{} logs", binary);
+        if let Some(tail) = tail {
+            command.push_str(&format!(" --tail {}", tail));
+        }
+        // Apple Container has simpler log options
+        if runtime != ContainerRuntime::Apple {
+            if let Some(since) = since {
+                command.push_str(&format!(" --since {}", Self::shell_escape(since)));
             }
-            ContainerRuntime::Podman => {
-                format!("podman logs {} {} {}", tail_arg, ts_arg, container_id).trim().to_string()
+            if let Some(until) = until {
+                command.push_str(&format!(" --until {}", Self::shell_escape(until)));
             }
-            ContainerRuntime::Apple => {
-                // Apple Container has simpler log options
-                format!("container logs {} {}", tail_arg, container_id).trim().to_string()
+            if timestamps {
+                command.push_str(" --timestamps");
             }
         }
+        format!("{} {}", command, Self::shell_escape(container_id))
+    }
+
+    /// Build one-shot container stats command (JSON format).
+    /// An empty `container_ids` slice reports all running containers.
+    pub fn container_stats(runtime: ContainerRuntime, container_ids: &[&str]) -> String {
+        let binary = match runtime {
+            ContainerRuntime::Docker => "docker",
+            ContainerRuntime::Podman => "podman",
+            ContainerRuntime::Nerdctl => "nerdctl",
+            ContainerRuntime::Apple => "container",
+        };
+        let ids: Vec<String> = container_ids.iter().map(|id| Self::shell_escape(id)).collect();
+        format!("{} stats --no-stream --format json {}", binary, ids.join(" "))
+            .trim()
+            .to_string()
     }
 
     /// Build streaming logs command (follow mode)
-    pub fn container_logs_stream(runtime: ContainerRuntime, container_id: &str) -> String {
-        match runtime {
-            ContainerRuntime::Docker => format!("docker logs -f {}", container_id),
-            ContainerRuntime::Podman => format!("podman logs -f {}", container_id),
-            ContainerRuntime::Apple => format!("container logs -f {}", container_id),
+    /// Apple Container only supports `tail` of the options
+    pub fn container_logs_stream(
+        runtime: ContainerRuntime,
+        container_id: &str,
+        options: &ContainerLogOptions,
+    ) -> String {
+        let binary = match runtime {
+            ContainerRuntime::Docker => "docker",
+            ContainerRuntime::Podman => "podman",
+            ContainerRuntime::Nerdctl => "nerdctl",
+            ContainerRuntime::Apple => "container",
+        };
+        let mut command = format!("{} logs -f", binary);
+        if let Some(tail) = options.tail {
+            command.push_str(&format!(" --tail {}", tail));
+        }
+        if runtime != ContainerRuntime::Apple {
+            if let Some(since) = &options.since {
+                command.push_str(&format!(" --since {}", Self::shell_escape(since)));
+            }
+            if options.timestamps {
+                command.push_str(" --timestamps");
+            }
         }
+        format!("{} {}", command, Self::shell_escape(container_id))
+    }
+
+    /// Build command that follows runtime events as one JSON object per line
+    /// Returns None for nerdctl, whose events are raw containerd topics, and Apple Container
+    pub fn events(runtime: ContainerRuntime) -> Option<String> {
+        let binary = match runtime {
+            ContainerRuntime::Docker => "docker",
+            ContainerRuntime::Podman => "podman",
+            ContainerRuntime::Nerdctl | ContainerRuntime::Apple => return None,
+        };
+        Some(format!("{} events --format json", binary))
     }
 
     // ========================================================================
@@ -181,6 +472,7 @@ impl CommandBuilder {
         match runtime {
             ContainerRuntime::Docker => "docker images --format json".to_string(),
             ContainerRuntime::Podman => "podman images --format json".to_string(),
+            ContainerRuntime::Nerdctl => "nerdctl images --format json".to_string(),
             ContainerRuntime::Apple => "container image list --format json".to_string(),
         }
     }
@@ -190,6 +482,7 @@ impl CommandBuilder {
         match runtime {
             ContainerRuntime::Docker => format!("docker pull {}", image),
             ContainerRuntime::Podman => format!("podman pull {}", image),
+            ContainerRuntime::Nerdctl => format!("nerdctl pull {}", image),
             ContainerRuntime::Apple => format!("container image pull {}", image),
         }
     }
@@ -200,6 +493,7 @@ impl CommandBuilder {
         match runtime {
             ContainerRuntime::Docker => format!("docker rmi {}{}", force_flag, image_id),
             ContainerRuntime::Podman => format!("podman rmi {}{}", force_flag, image_id),
+            ContainerRuntime::Nerdctl => format!("nerdctl rmi {}{}", force_flag, image_id),
             ContainerRuntime::Apple => {
                 let force_opt = if force { "--force " } else { "" };
                 format!("container image remove {}{}", force_opt, image_id)
@@ -212,15 +506,32 @@ impl CommandBuilder {
         match runtime {
             ContainerRuntime::Docker => format!("docker image inspect {}", image_id),
             ContainerRuntime::Podman => format!("podman image inspect {}", image_id),
+            ContainerRuntime::Nerdctl => format!("nerdctl image inspect {}", image_id),
             ContainerRuntime::Apple => format!("container image inspect {}", image_id),
         }
     }
 
+    /// Build image history command (layer-by-layer breakdown, sizes in bytes)
+    /// Returns None for Apple Container, which has no history subcommand
+    pub fn image_history(runtime: ContainerRuntime, image_id: &str) -> Option<String> {
+        let binary = match runtime {
+            ContainerRuntime::Docker => "docker",
+            ContainerRuntime::Podman => "podman",
+            ContainerRuntime::Nerdctl => "nerdctl",
+            ContainerRuntime::Apple => return None,
+        };
+        Some(format!(
+            "{} history --no-trunc --human=false --format json {}",
+            binary, image_id
+        ))
+    }
+
     /// Build image tag command
     pub fn tag_image(runtime: ContainerRuntime, source: &str, target: &str) -> String {
         match runtime {
             ContainerRuntime::Docker => format!("docker tag {} {}", source, target),
             ContainerRuntime::Podman => format!("podman tag {} {}", source, target),
+            ContainerRuntime::Nerdctl => format!("nerdctl tag {} {}", source, target),
             ContainerRuntime::Apple => format!("container image tag {} {}", source, target),
         }
     }
@@ -234,17 +545,51 @@ impl CommandBuilder {
         match runtime {
             ContainerRuntime::Docker => "docker volume ls --format json".to_string(),
             ContainerRuntime::Podman => "podman volume ls --format json".to_string(),
+            ContainerRuntime::Nerdctl => "nerdctl volume ls --format json".to_string(),
             ContainerRuntime::Apple => "container volume list --format json".to_string(),
         }
     }
 
-    /// Build volume create command
-    pub fn create_volume(runtime: ContainerRuntime, name: &str) -> String {
-        match runtime {
-            ContainerRuntime::Docker => format!("docker volume create {}", name),
-            ContainerRuntime::Podman => format!("podman volume create {}", name),
-            ContainerRuntime::Apple => format!("container volume create {}", name),
+    /// Build volume create command from options checked with `validate`
+    ///
+    /// None when Apple Container is given options, which its CLI doesn't take.
+    pub fn create_volume(
+        runtime: ContainerRuntime,
+        name: &str,
+        options: &VolumeCreateOptions,
+    ) -> Option<String> {
+        let binary = match runtime {
+            ContainerRuntime::Docker => "docker",
+            ContainerRuntime::Podman => "podman",
+            ContainerRuntime::Nerdctl => "nerdctl",
+            ContainerRuntime::Apple if options.is_empty() => "container",
+            ContainerRuntime::Apple => return None,
+        };
+
+        let mut command = format!("{} volume create", binary);
+        if let Some(driver) = &options.driver {
+            command.push_str(&format!(" --driver {}", driver));
         }
+        command.push_str(&Self::key_value_flags("--opt", &options.driver_opts));
+        command.push_str(&Self::key_value_flags("--label", &options.labels));
+        command.push_str(&format!(" {}", name));
+        Some(command)
+    }
+
+    /// ` <flag> 'key=value'` for each entry, sorted by key
+    fn key_value_flags(flag: &str, values: &HashMap<String, String>) -> String {
+        let mut entries: Vec<_> = values.iter().collect();
+        entries.sort();
+        entries
+            .into_iter()
+            .map(|(key, value)| {
+                format!(
+                    " {} {}",
+                    flag,
+                    Self::shell_escape(&format!("{}={}", key, value))
+                )
+            })
+            .collect()
     }
 
     /// Build volume remove command
@@ -253,6 +598,7 @@ impl CommandBuilder {
         match runtime {
             ContainerRuntime::Docker => format!("docker volume rm {}{}", force_flag, name),
             ContainerRuntime::Podman => format!("podman volume rm {}{}", force_flag, name),
+            ContainerRuntime::Nerdctl => format!("nerdctl volume rm {}{}", force_flag, name),
             ContainerRuntime::Apple => {
                 let force_opt = if force { "--force " } else { "" };
                 format!("container volume remove {}{}", force_opt, name)
@@ -265,6 +611,7 @@ impl CommandBuilder {
         match runtime {
             ContainerRuntime::Docker => format!("docker volume inspect {}", name),
             ContainerRuntime::Podman => format!("podman volume inspect {}", name),
+            ContainerRuntime::Nerdctl => format!("nerdctl volume inspect {}", name),
             ContainerRuntime::Apple => format!("container volume inspect {}", name),
         }
     }
@@ -278,39 +625,43 @@ impl CommandBuilder {
         match runtime {
             ContainerRuntime::Docker => "docker network ls --format json".to_string(),
             ContainerRuntime::Podman => "podman network ls --format json".to_string(),
+            ContainerRuntime::Nerdctl => "nerdctl network ls --format json".to_string(),
             ContainerRuntime::Apple => "container network list --format json".to_string(),
         }
     }
 
-    /// Build network create command
+    /// Build network create command from options checked with `validate`
+    ///
+    /// None when Apple Container is given options, which its CLI doesn't take.
     pub fn create_network(
         runtime: ContainerRuntime,
         name: &str,
-        driver: Option<&str>,
-        subnet: Option<&str>,
-    ) -> String {
-        let driver_arg = driver
-            .map(|d| format!("--driver {}", d))
-            .unwrap_or_default();
-        let subnet_arg = subnet
-            .map(|s| format!("--subnet {}", s))
-            .unwrap_or_default();
+        options: &NetworkCreateOptions,
+    ) -> Option<String> {
+        let binary = match runtime {
+            ContainerRuntime::Docker => "docker",
+            ContainerRuntime::Podman => "podman",
+            ContainerRuntime::Nerdctl => "nerdctl",
+            ContainerRuntime::Apple if options.is_empty() => "container",
+            ContainerRuntime::Apple => return None,
+        };
 
-        match runtime {
-            ContainerRuntime::Docker => {
-                format!("docker network create {} {} {}", driver_arg, subnet_arg, name)
-                    .split_whitespace()
-                    .collect::<Vec<_>>()
-                    .join(" ")
-            }
-            ContainerRuntime::Podman => {
-                format!("podman network create {} {} {}", driver_arg, subnet_arg, name)
-                    .split_whitespace()
-                    .collect::<Vec<_>>()
-                    .join(" ")
-            }
-            ContainerRuntime::Apple => format!("container network create {}", name),
+        let mut command = format!("{} network create", binary);
+        if let Some(driver) = &options.driver {
+            command.push_str(&format!(" --driver {}", driver));
+        }
+        if let Some(subnet) = &options.subnet {
+            command.push_str(&format!(" --subnet {}", subnet));
+        }
+        if let Some(gateway) = &options.gateway {
+            command.push_str(&format!(" --gateway {}", gateway));
+        }
+        if options.internal {
+            command.push_str(" --internal");
         }
+        command.push_str(&Self::key_value_flags("--label", &options.labels));
+        command.push_str(&format!(" {}", name));
+        Some(command)
     }
 
     /// Build network remove command
@@ -318,6 +669,7 @@ impl CommandBuilder {
         match runtime {
             ContainerRuntime::Docker => format!("docker network rm {}", name),
             ContainerRuntime::Podman => format!("podman network rm {}", name),
+            ContainerRuntime::Nerdctl => format!("nerdctl network rm {}", name),
             ContainerRuntime::Apple => format!("container network remove {}", name),
         }
     }
@@ -327,48 +679,135 @@ impl CommandBuilder {
         match runtime {
             ContainerRuntime::Docker => format!("docker network inspect {}", name),
             ContainerRuntime::Podman => format!("podman network inspect {}", name),
+            ContainerRuntime::Nerdctl => format!("nerdctl network inspect {}", name),
             ContainerRuntime::Apple => format!("container network inspect {}", name),
         }
     }
 
     /// Build network connect command
+    /// Returns None for nerdctl, which has no `network connect`
     pub fn connect_to_network(
         runtime: ContainerRuntime,
         network: &str,
         container_id: &str,
-    ) -> String {
+    ) -> Option<String> {
         match runtime {
             ContainerRuntime::Docker => {
-                format!("docker network connect {} {}", network, container_id)
+                Some(format!("docker network connect {} {}", network, container_id))
             }
             ContainerRuntime::Podman => {
-                format!("podman network connect {} {}", network, container_id)
+                Some(format!("podman network connect {} {}", network, container_id))
             }
+            ContainerRuntime::Nerdctl => None,
             ContainerRuntime::Apple => {
-                format!("container network connect {} {}", network, container_id)
+                Some(format!("container network connect {} {}", network, container_id))
             }
         }
     }
 
     /// Build network disconnect command
+    /// Returns None for nerdctl, which has no `network disconnect`
     pub fn disconnect_from_network(
         runtime: ContainerRuntime,
         network: &str,
         container_id: &str,
-    ) -> String {
+    ) -> Option<String> {
         match runtime {
             ContainerRuntime::Docker => {
-                format!("docker network disconnect {} {}", network, container_id)
+                Some(format!("docker network disconnect {} {}", network, container_id))
             }
             ContainerRuntime::Podman => {
-                format!("podman network disconnect {} {}", network, container_id)
+                Some(format!("podman network disconnect {} {}", network, container_id))
             }
+            ContainerRuntime::Nerdctl => None,
             ContainerRuntime::Apple => {
-                format!("container network disconnect {} {}", network, container_id)
+                Some(format!("container network disconnect {} {}", network, container_id))
             }
         }
     }
 
+    // ========================================================================
+    // Pod Commands (Podman only)
+    // ========================================================================
+
+    /// Build pod list command
+    pub fn list_pods() -> String {
+        "podman pod ps --format json".to_string()
+    }
+
+    /// Build pod action command (start, stop, restart, pause, unpause, remove)
+    pub fn pod_action(action: ContainerAction, pod_id: &str) -> String {
+        let verb = match action {
+            ContainerAction::Start => "start",
+            ContainerAction::Stop => "stop",
+            ContainerAction::Restart => "restart",
+            ContainerAction::Pause => "pause",
+            ContainerAction::Unpause => "unpause",
+            ContainerAction::Remove => "rm",
+        };
+        format!("podman pod {} {}", verb, Self::shell_escape(pod_id))
+    }
+
+    // ========================================================================
+    // Prune Commands
+    // ========================================================================
+
+    /// Runtime binary for prune commands; Apple Container has no prune subcommands
+    fn prune_binary(runtime: ContainerRuntime) -> Option<&'static str> {
+        match runtime {
+            ContainerRuntime::Docker => Some("docker"),
+            ContainerRuntime::Podman => Some("podman"),
+            ContainerRuntime::Nerdctl => Some("nerdctl"),
+            ContainerRuntime::Apple => None,
+        }
+    }
+
+    /// Build image prune command; `all` also removes unused tagged images
+    pub fn prune_images(
+        runtime: ContainerRuntime,
+        all: bool,
+        filters: &[String],
+    ) -> Option<String> {
+        let mut command = format!("{} image prune -f", Self::prune_binary(runtime)?);
+        if all {
+            command.push_str(" -a");
+        }
+        for filter in filters {
+            command.push_str(&format!(" --filter {}", Self::shell_escape(filter)));
+        }
+        Some(command)
+    }
+
+    /// Build stopped-container prune command
+    pub fn prune_containers(runtime: ContainerRuntime) -> Option<String> {
+        Some(format!(
+            "{} container prune -f",
+            Self::prune_binary(runtime)?
+        ))
+    }
+
+    /// Build unused volume prune command
+    pub fn prune_volumes(runtime: ContainerRuntime) -> Option<String> {
+        Some(format!("{} volume prune -f", Self::prune_binary(runtime)?))
+    }
+
+    /// Build unused network prune command
+    pub fn prune_networks(runtime: ContainerRuntime) -> Option<String> {
+        Some(format!("{} network prune -f", Self::prune_binary(runtime)?))
+    }
+
+    /// Build system prune command (stopped containers, unused networks, dangling images, build cache)
+    pub fn prune_system(runtime: ContainerRuntime, all: bool, volumes: bool) -> Option<String> {
+        let mut command = format!("{} system prune -f", Self::prune_binary(runtime)?);
+        if all {
+            command.push_str(" -a");
+        }
+        if volumes {
+            command.push_str(" --volumes");
+        }
+        Some(command)
+    }
+
     // ========================================================================
     // System Commands
     // ========================================================================
@@ -378,6 +817,7 @@ impl CommandBuilder {
         match runtime {
             ContainerRuntime::Docker => "docker --version".to_string(),
             ContainerRuntime::Podman => "podman --version".to_string(),
+            ContainerRuntime::Nerdctl => "nerdctl --version".to_string(),
             ContainerRuntime::Apple => "container --version".to_string(),
         }
     }
@@ -387,22 +827,55 @@ impl CommandBuilder {
         match runtime {
             ContainerRuntime::Docker => "docker info --format json".to_string(),
             ContainerRuntime::Podman => "podman info --format json".to_string(),
+            ContainerRuntime::Nerdctl => "nerdctl info --format json".to_string(),
             ContainerRuntime::Apple => "container system status".to_string(),
         }
     }
 
     /// Build disk usage command
-    pub fn disk_usage(runtime: ContainerRuntime) -> String {
+    /// Returns None for runtimes without a `system df` equivalent
+    pub fn disk_usage(runtime: ContainerRuntime) -> Option<String> {
         match runtime {
-            ContainerRuntime::Docker => "docker system df --format json".to_string(),
-            ContainerRuntime::Podman => "podman system df --format json".to_string(),
-            ContainerRuntime::Apple => "container system status".to_string(), // No direct equivalent
+            ContainerRuntime::Docker => Some("docker system df --format json".to_string()),
+            ContainerRuntime::Podman => Some("podman system df --format json".to_string()),
+            ContainerRuntime::Nerdctl | ContainerRuntime::Apple => None,
         }
     }
 
     /// Build runtime detection command (checks if runtime is available)
     pub fn detect_runtime(runtime: ContainerRuntime) -> String {
-        Self::runtime_version(runtime)
+        match runtime {
+            // `nerdctl version` also reports the containerd server, so it fails
+            // when nerdctl is installed but containerd isn't reachable
+            ContainerRuntime::Nerdctl => "nerdctl version".to_string(),
+            _ => Self::runtime_version(runtime),
+        }
+    }
+
+    /// Build command that reports whether a runtime runs rootless
+    /// Podman prints `true`/`false`; Docker lists `name=rootless` among its security options
+    pub fn rootless_check(runtime: ContainerRuntime) -> Option<String> {
+        match runtime {
+            ContainerRuntime::Docker => {
+                Some("docker info --format '{{json .SecurityOptions}}'".to_string())
+            }
+            ContainerRuntime::Podman => {
+                Some("podman info --format '{{.Host.Security.Rootless}}'".to_string())
+            }
+            ContainerRuntime::Nerdctl | ContainerRuntime::Apple => None,
+        }
+    }
+
+    /// API socket a runtime listens on, which moves under `$XDG_RUNTIME_DIR` when rootless
+    /// Returns None for runtimes without a Docker-compatible socket
+    pub fn runtime_socket(runtime: ContainerRuntime, rootless: bool) -> Option<&'static str> {
+        match (runtime, rootless) {
+            (ContainerRuntime::Docker, false) => Some("/var/run/docker.sock"),
+            (ContainerRuntime::Docker, true) => Some("$XDG_RUNTIME_DIR/docker.sock"),
+            (ContainerRuntime::Podman, false) => Some("/run/podman/podman.sock"),
+            (ContainerRuntime::Podman, true) => Some("$XDG_RUNTIME_DIR/podman/podman.sock"),
+            (ContainerRuntime::Nerdctl | ContainerRuntime::Apple, _) => None,
+        }
     }
 
     // ========================================================================
@@ -414,6 +887,7 @@ impl CommandBuilder {
         match runtime {
             ContainerRuntime::Docker => format!("docker exec -it {} {}", container_id, shell),
             ContainerRuntime::Podman => format!("podman exec -it {} {}", container_id, shell),
+            ContainerRuntime::Nerdctl => format!("nerdctl exec -it {} {}", container_id, shell),
             ContainerRuntime::Apple => format!("container exec -it {} {}", container_id, shell),
         }
     }
@@ -438,6 +912,9 @@ impl CommandBuilder {
             ContainerRuntime::Podman => {
                 format!("podman exec {} sh -c \"{}\"", container_id, escaped)
             }
+            ContainerRuntime::Nerdctl => {
+                format!("nerdctl exec {} sh -c \"{}\"", container_id, escaped)
+            }
             ContainerRuntime::Apple => {
                 format!("container exec {} sh -c \"{}\"", container_id, escaped)
             }
@@ -449,16 +926,28 @@ impl CommandBuilder {
         "/bin/sh"
     }
 
+    /// Print the path of each of bash, zsh, fish, ash and sh that is installed
+    pub fn detect_shells() -> &'static str {
+        "for s in bash zsh fish ash sh; do command -v $s; done; true"
+    }
+
+    /// Probe for installed shells inside a running container
+    pub fn detect_container_shells(runtime: ContainerRuntime, container_id: &str) -> String {
+        Self::exec_command(runtime, container_id, Self::detect_shells())
+    }
+
     // ========================================================================
     // Extended System Info Commands
     // ========================================================================
 
     /// Combined command to get all extended system info in one call (Unix/Linux/macOS)
     /// Returns structured output with delimiters for parsing
-    pub fn get_extended_system_info_unix(runtime: ContainerRuntime) -> String {
+    /// Disk usage is reported for the filesystem containing `mount_point`
+    pub fn get_extended_system_info_unix(runtime: ContainerRuntime, mount_point: &str) -> String {
         let runtime_bin = match runtime {
             ContainerRuntime::Docker => "docker",
             ContainerRuntime::Podman => "podman",
+            ContainerRuntime::Nerdctl => "nerdctl",
             ContainerRuntime::Apple => "container",
         };
 
@@ -471,72 +960,100 @@ echo "===HOSTNAME===" && hostname && \
 echo "===DISTRO===" && (cat /etc/os-release 2>/dev/null || sw_vers 2>/dev/null || echo unknown) && \
 echo "===CPUCOUNT===" && (nproc 2>/dev/null || sysctl -n hw.ncpu 2>/dev/null || echo 0) && \
 echo "===MEMORY===" && (free -h 2>/dev/null | grep -i mem || (sysctl -n hw.memsize 2>/dev/null | awk '{{printf "%.0fG\n", $1/1024/1024/1024}}')) && \
-echo "===DISK===" && df -h / 2>/dev/null | tail -1 && \
+echo "===DISK===" && (df -h {1} 2>/dev/null || true) && \
+echo "===GPU===" && ((command -v nvidia-smi >/dev/null 2>&1 && nvidia-smi --query-gpu=name,memory.total,driver_version --format=csv,noheader 2>/dev/null) || (command -v system_profiler >/dev/null 2>&1 && system_profiler SPDisplaysDataType 2>/dev/null) || true) && \
 echo "===UPTIME===" && (uptime -p 2>/dev/null || uptime | sed 's/.*up /up /' | sed 's/,.*load.*//' 2>/dev/null || echo unknown) && \
 echo "===CONTAINERS===" && ({0} ps -q 2>/dev/null | wc -l | tr -d ' ') && \
 echo "===TOTALCONTAINERS===" && ({0} ps -aq 2>/dev/null | wc -l | tr -d ' ') && \
 echo "===IMAGES===" && ({0} images -q 2>/dev/null | wc -l | tr -d ' ') && \
 echo "===RUNTIMEVERSION===" && ({0} --version 2>/dev/null | head -1) && \
 echo "===END===""#,
-            runtime_bin
+            runtime_bin,
+            Self::shell_escape(mount_point)
         )
     }
 
     /// Combined command for Windows systems using PowerShell
-    pub fn get_extended_system_info_windows(runtime: ContainerRuntime) -> String {
+    /// `mount_point` selects the drive (e.g. "D:"); anything else reports C:
+    pub fn get_extended_system_info_windows(
+        runtime: ContainerRuntime,
+        mount_point: &str,
+    ) -> String {
         let runtime_bin = match runtime {
             ContainerRuntime::Docker => "docker",
             ContainerRuntime::Podman => "podman",
+            ContainerRuntime::Nerdctl => "nerdctl",
             ContainerRuntime::Apple => "container", // Won't work on Windows anyway
         };
 
+        let drive = match mount_point.as_bytes() {
+            [letter, b':'] | [letter, b':', b'\\'] if letter.is_ascii_alphabetic() => {
+                format!("{}:", (*letter as char).to_ascii_uppercase())
+            }
+            _ => "C:".to_string(),
+        };
+
         // PowerShell commands that work on Windows - all on one line, no backticks
         format!(
-            r#"$isAdmin = ([Security.Principal.WindowsPrincipal][Security.Principal.WindowsIdentity]::GetCurrent()).IsInRole([Security.Principal.WindowsBuiltInRole]::Administrator); $os = Get-CimInstance Win32_OperatingSystem; $cs = Get-CimInstance Win32_ComputerSystem; $disk = Get-CimInstance Win32_LogicalDisk -Filter "DeviceID='C:'"; $uptime = (Get-Date) - $os.LastBootUpTime; Write-Output "===USERNAME==="; Write-Output $env:USERNAME; Write-Output "===USERID==="; if ($isAdmin) {{ Write-Output "0" }} else {{ Write-Output "1000" }}; Write-Output "===SUDO==="; Write-Output "no"; Write-Output "===OSTYPE==="; Write-Output "Windows"; Write-Output "===HOSTNAME==="; Write-Output $env:COMPUTERNAME; Write-Output "===DISTRO==="; Write-Output $os.Caption; Write-Output "===CPUCOUNT==="; Write-Output $env:NUMBER_OF_PROCESSORS; Write-Output "===MEMORY==="; Write-Output "$([math]::Round($cs.TotalPhysicalMemory / 1GB))G"; Write-Output "===DISK==="; Write-Output "$([math]::Round($disk.Size/1GB))G $([math]::Round(($disk.Size - $disk.FreeSpace) / $disk.Size * 100))%"; Write-Output "===UPTIME==="; Write-Output "$($uptime.Days) days, $($uptime.Hours) hours"; Write-Output "===CONTAINERS==="; Write-Output (({0} ps -q 2>$null | Measure-Object -Line).Lines); Write-Output "===TOTALCONTAINERS==="; Write-Output (({0} ps -aq 2>$null | Measure-Object -Line).Lines); Write-Output "===IMAGES==="; Write-Output (({0} images -q 2>$null | Measure-Object -Line).Lines); Write-Output "===RUNTIMEVERSION==="; {0} --version 2>$null | Select-Object -First 1; Write-Output "===END===""#,
-            runtime_bin
+            r#"$isAdmin = ([Security.Principal.WindowsPrincipal][Security.Principal.WindowsIdentity]::GetCurrent()).IsInRole([Security.Principal.WindowsBuiltInRole]::Administrator); $os = Get-CimInstance Win32_OperatingSystem; $cs = Get-CimInstance Win32_ComputerSystem; $disk = Get-CimInstance Win32_LogicalDisk -Filter "DeviceID='{1}'"; $uptime = (Get-Date) - $os.LastBootUpTime; Write-Output "===USERNAME==="; Write-Output $env:USERNAME; Write-Output "===USERID==="; if ($isAdmin) {{ Write-Output "0" }} else {{ Write-Output "1000" }}; Write-Output "===SUDO==="; Write-Output "no"; Write-Output "===OSTYPE==="; Write-Output "Windows"; Write-Output "===HOSTNAME==="; Write-Output $env:COMPUTERNAME; Write-Output "===DISTRO==="; Write-Output $os.Caption; Write-Output "===CPUCOUNT==="; Write-Output $env:NUMBER_OF_PROCESSORS; Write-Output "===MEMORY==="; Write-Output "$([math]::Round($cs.TotalPhysicalMemory / 1GB))G"; Write-Output "===DISK==="; Write-Output "$([math]::Round($disk.Size/1GB))G $([math]::Round(($disk.Size - $disk.FreeSpace) / $disk.Size * 100))%"; Write-Output "===UPTIME==="; Write-Output "$($uptime.Days) days, $($uptime.Hours) hours"; Write-Output "===CONTAINERS==="; Write-Output (({0} ps -q 2>$null | Measure-Object -Line).Lines); Write-Output "===TOTALCONTAINERS==="; Write-Output (({0} ps -aq 2>$null | Measure-Object -Line).Lines); Write-Output "===IMAGES==="; Write-Output (({0} images -q 2>$null | Measure-Object -Line).Lines); Write-Output "===RUNTIMEVERSION==="; {0} --version 2>$null | Select-Object -First 1; Write-Output "===END===""#,
+            runtime_bin, drive
         )
     }
 
     /// Get the appropriate system info command based on platform
     /// For remote (SSH) systems, always use Unix commands
     /// For local systems, detect the current OS
-    pub fn get_extended_system_info_for_local(runtime: ContainerRuntime) -> String {
+    pub fn get_extended_system_info_for_local(
+        runtime: ContainerRuntime,
+        mount_point: &str,
+    ) -> String {
         if cfg!(windows) {
-            Self::get_extended_system_info_windows(runtime)
+            Self::get_extended_system_info_windows(runtime, mount_point)
         } else {
-            Self::get_extended_system_info_unix(runtime)
+            Self::get_extended_system_info_unix(runtime, mount_point)
         }
     }
 
     /// Get Unix system info command (for SSH/remote systems which are typically Unix)
-    pub fn get_extended_system_info_for_remote(runtime: ContainerRuntime) -> String {
-        Self::get_extended_system_info_unix(runtime)
+    pub fn get_extended_system_info_for_remote(
+        runtime: ContainerRuntime,
+        mount_point: &str,
+    ) -> String {
+        Self::get_extended_system_info_unix(runtime, mount_point)
     }
 
     // ========================================================================
     // Live Metrics Commands
     // ========================================================================
 
-    /// Lightweight command to get live CPU/Memory/Load metrics (Unix/Linux/macOS)
+    /// Lightweight command to get live CPU/Memory/Load/Disk/Network metrics (Unix/Linux/macOS)
     /// Uses /proc filesystem on Linux for minimal overhead
     pub fn get_live_metrics_unix() -> &'static str {
         r#"echo "===CPU===" && cat /proc/stat 2>/dev/null | head -1 && \
 echo "===MEM===" && cat /proc/meminfo 2>/dev/null | grep -E '^(MemTotal|MemAvailable|MemFree|Buffers|Cached|SwapTotal|SwapFree):' && \
 echo "===LOAD===" && cat /proc/loadavg 2>/dev/null && \
+echo "===DISKIO===" && (cat /proc/diskstats 2>/dev/null || true) && \
+echo "===NET===" && (cat /proc/net/dev 2>/dev/null || true) && \
+echo "===DISK===" && (df -P / 2>/dev/null || true) && \
 echo "===END===""#
     }
 
-    /// Lightweight command for macOS (uses vm_stat and sysctl)
+    /// Lightweight command for macOS (uses vm_stat, sysctl, iostat and netstat)
     pub fn get_live_metrics_macos() -> &'static str {
         r#"echo "===CPU===" && top -l 1 -n 0 2>/dev/null | grep "CPU usage" && \
 echo "===MEM===" && vm_stat 2>/dev/null && sysctl -n hw.memsize 2>/dev/null && \
 echo "===LOAD===" && sysctl -n vm.loadavg 2>/dev/null && \
+echo "===DISKIO===" && (iostat -Id 2>/dev/null || true) && \
+echo "===NET===" && (netstat -ib 2>/dev/null || true) && \
+echo "===DISK===" && (df -P / 2>/dev/null || true) && \
 echo "===END===""#
     }
 
     /// Lightweight command for Windows using PowerShell
+    /// Emits `Key=Value` lines from projected CIM queries, formatted with the invariant
+    /// culture so the output does not depend on the host's locale
     pub fn get_live_metrics_windows() -> &'static str {
-        r#"$cpu = (Get-CimInstance Win32_Processor | Measure-Object -Property LoadPercentage -Average).Average; $os = Get-CimInstance Win32_OperatingSystem; $cores = (Get-CimInstance Win32_Processor | Measure-Object -Property NumberOfLogicalProcessors -Sum).Sum; Write-Output "===CPU==="; Write-Output $cpu; Write-Output "===MEM==="; Write-Output "$($os.TotalVisibleMemorySize) $($os.FreePhysicalMemory)"; Write-Output "===SWAP==="; $pf = Get-CimInstance Win32_PageFileUsage -ErrorAction SilentlyContinue; if ($pf) { Write-Output "$($pf.AllocatedBaseSize) $($pf.CurrentUsage)" } else { Write-Output "0 0" }; Write-Output "===LOAD==="; $load = [math]::Round(($cpu / 100) * $cores, 2); Write-Output "$load $load $load"; Write-Output "===END===""#
+        r#"$ErrorActionPreference = 'SilentlyContinue'; $inv = [Globalization.CultureInfo]::InvariantCulture; $cpu = @(Get-CimInstance -ClassName Win32_Processor -Property LoadPercentage,NumberOfLogicalProcessors); $os = Get-CimInstance -ClassName Win32_OperatingSystem -Property TotalVisibleMemorySize,FreePhysicalMemory; $pf = @(Get-CimInstance -ClassName Win32_PageFileUsage -Property AllocatedBaseSize,CurrentUsage); $load = [double]($cpu | Measure-Object -Property LoadPercentage -Average).Average; $cores = [uint64]($cpu | Measure-Object -Property NumberOfLogicalProcessors -Sum).Sum; Write-Output "===CPU==="; Write-Output ('LoadPercentage=' + $load.ToString($inv)); Write-Output ('LogicalProcessors=' + $cores.ToString($inv)); Write-Output "===MEM==="; Write-Output ('TotalVisibleMemorySize=' + ([uint64]$os.TotalVisibleMemorySize).ToString($inv)); Write-Output ('FreePhysicalMemory=' + ([uint64]$os.FreePhysicalMemory).ToString($inv)); Write-Output "===SWAP==="; Write-Output ('AllocatedBaseSize=' + ([uint64]($pf | Measure-Object -Property AllocatedBaseSize -Sum).Sum).ToString($inv)); Write-Output ('CurrentUsage=' + ([uint64]($pf | Measure-Object -Property CurrentUsage -Sum).Sum).ToString($inv)); Write-Output "===END===""#
     }
 
     /// Get the appropriate live metrics command based on platform
@@ -589,6 +1106,36 @@ echo "===END===""#
         )
     }
 
+    /// Print a file's size in bytes (GNU stat, then BSD stat, then `wc -c`,
+    /// whose error names the file clearly when it doesn't exist).
+    pub fn file_size(path: &str) -> String {
+        let escaped = Self::shell_escape(path);
+        format!(
+            "stat -c%s {0} 2>/dev/null || stat -f%z {0} 2>/dev/null || wc -c < {0}",
+            escaped
+        )
+    }
+
+    /// Read up to `length` bytes starting at `offset`, base64-encoded after a
+    /// `__FILE_SIZE__:<bytes>` line so the exact byte count survives transport.
+    pub fn read_file_range(path: &str, offset: u64, length: u64) -> String {
+        let escaped = Self::shell_escape(path);
+        format!(
+            "SIZE=$({0}) || exit 1; \
+             echo \"__FILE_SIZE__:$SIZE\"; \
+             tail -c +{2} {1} | head -c {3} | base64",
+            Self::file_size(path),
+            escaped,
+            offset + 1,
+            length
+        )
+    }
+
+    /// Print the last `lines` lines of a file.
+    pub fn tail_file(path: &str, lines: usize) -> String {
+        format!("tail -n {} {}", lines, Self::shell_escape(path))
+    }
+
     /// Write content to a file using base64 transport (safe for special chars).
     pub fn write_file_from_base64(path: &str, base64_content: &str) -> String {
         let escaped = Self::shell_escape(path);
@@ -608,6 +1155,18 @@ echo "===END===""#
         format!("rm {}", Self::shell_escape(path))
     }
 
+    /// Copy a file, keeping its mode and timestamps. The copy goes to
+    /// `partial_path` first and is moved into place only once complete.
+    pub fn copy_file(src_path: &str, partial_path: &str, dst_path: &str) -> String {
+        let partial = Self::shell_escape(partial_path);
+        format!(
+            "cp -p {src} {partial} && mv -f {partial} {dst} || {{ rm -f {partial}; exit 1; }}",
+            src = Self::shell_escape(src_path),
+            partial = partial,
+            dst = Self::shell_escape(dst_path)
+        )
+    }
+
     /// Delete a directory recursively.
     pub fn delete_directory(path: &str) -> String {
         format!("rm -rf {}", Self::shell_escape(path))
@@ -632,6 +1191,148 @@ echo "===END===""#
         let escaped = Self::shell_escape(path);
         format!("printf '%s' '{}' | base64 -d > {}", base64_data, escaped)
     }
+
+    /// Disk usage of a path, summarized (`depth` = None) or down to `depth` levels.
+    /// Uses GNU `du -b` (bytes) where supported, else `du -k` (macOS/BSD/BusyBox);
+    /// a `__DU_UNIT__:<bytes>` first line tells the parser which one ran.
+    pub fn directory_usage(path: &str, depth: Option<u32>) -> String {
+        let escaped = Self::shell_escape(path);
+        let scope = match depth {
+            Some(depth) => format!("-d {}", depth),
+            None => "-s".to_string(),
+        };
+        format!(
+            "if du -sb /dev/null >/dev/null 2>&1; then \
+               echo '__DU_UNIT__:1'; du -b {1} {0}; \
+             else \
+               echo '__DU_UNIT__:1024'; du -k {1} {0}; \
+             fi",
+            escaped, scope
+        )
+    }
+
+    /// Show a single path's metadata in `ls -la` format (without listing directory contents).
+    pub fn stat_path(path: &str) -> String {
+        let escaped = Self::shell_escape(path);
+        format!(
+            "ls -lad --time-style=long-iso {} 2>/dev/null || ls -lad {}",
+            escaped, escaped
+        )
+    }
+
+    /// Change permissions to an octal mode like `755`.
+    pub fn change_permissions(path: &str, mode: &str, recursive: bool) -> String {
+        let flag = if recursive { "-R " } else { "" };
+        format!("chmod {}{} {}", flag, mode, Self::shell_escape(path))
+    }
+
+    /// Change the owner and, optionally, the group.
+    pub fn change_owner(path: &str, owner: &str, group: Option<&str>, recursive: bool) -> String {
+        let flag = if recursive { "-R " } else { "" };
+        let spec = match group {
+            Some(group) => format!("{}:{}", owner, group),
+            None => owner.to_string(),
+        };
+        format!(
+            "chown {}{} {}",
+            flag,
+            Self::shell_escape(&spec),
+            Self::shell_escape(path)
+        )
+    }
+
+    /// Fail with a `__MISSING_TOOL__:<name>` line on stderr unless every tool is on the PATH.
+    fn require_tools(tools: &[&str]) -> String {
+        format!(
+            "for t in {}; do command -v \"$t\" >/dev/null 2>&1 || {{ echo \"__MISSING_TOOL__:$t\" >&2; exit 127; }}; done",
+            tools.join(" ")
+        )
+    }
+
+    /// Split a path into its parent directory and final component.
+    fn split_parent(path: &str) -> (&str, &str) {
+        let trimmed = path.trim_end_matches('/');
+        match trimmed.rfind('/') {
+            Some(0) => ("/", &trimmed[1..]),
+            Some(i) => (&trimmed[..i], &trimmed[i + 1..]),
+            None => (".", trimmed),
+        }
+    }
+
+    /// Pack `paths` into the archive at `dest`. Entries are stored relative to their
+    /// parent directories, so `/srv/app` is archived as `app/...`.
+    pub fn create_archive(paths: &[String], dest: &str, format: ArchiveFormat) -> String {
+        let check = Self::require_tools(format.required_tools(false));
+        let dest = Self::shell_escape(dest);
+        let pack = match format {
+            ArchiveFormat::TarGz => {
+                let members: Vec<String> = paths
+                    .iter()
+                    .map(|p| {
+                        let (parent, name) = Self::split_parent(p);
+                        format!(
+                            "-C {} {}",
+                            Self::shell_escape(parent),
+                            Self::shell_escape(name)
+                        )
+                    })
+                    .collect();
+                format!("tar -czf {} {}", dest, members.join(" "))
+            }
+            ArchiveFormat::Zip => {
+                // zip has no -C; add each path from its own directory, appending to the archive
+                let steps: Vec<String> = paths
+                    .iter()
+                    .map(|p| {
+                        let (parent, name) = Self::split_parent(p);
+                        format!(
+                            "(cd {} && zip -rqy {} {})",
+                            Self::shell_escape(parent),
+                            dest,
+                            Self::shell_escape(name)
+                        )
+                    })
+                    .collect();
+                format!("rm -f {} && {}", dest, steps.join(" && "))
+            }
+        };
+        format!("{}; {}", check, pack)
+    }
+
+    /// Unpack an archive into `dest`, creating it if needed.
+    pub fn extract_archive(archive: &str, dest: &str, format: ArchiveFormat) -> String {
+        let check = Self::require_tools(format.required_tools(true));
+        let archive = Self::shell_escape(archive);
+        let dest = Self::shell_escape(dest);
+        let unpack = match format {
+            ArchiveFormat::TarGz => format!("tar -xzf {} -C {}", archive, dest),
+            ArchiveFormat::Zip => format!("unzip -oq {} -d {}", archive, dest),
+        };
+        format!("{}; mkdir -p {} && {}", check, dest, unpack)
+    }
+
+    /// Recursively search file contents under a path, printing `file:line:content`.
+    /// Binary files are skipped and output stops after `limit` lines.
+    pub fn search_file_contents(
+        path: &str,
+        query: &str,
+        case_insensitive: bool,
+        fixed_string: bool,
+        limit: usize,
+    ) -> String {
+        let mut flags = String::from("-rnHI");
+        if case_insensitive {
+            flags.push('i');
+        }
+        flags.push_str(if fixed_string { " -F" } else { " -E" });
+        format!(
+            "grep {} -e {} -- {} | head -n {}",
+            flags,
+            Self::shell_escape(query),
+            Self::shell_escape(path),
+            limit
+        )
+    }
 }
 
 #[cfg(test)]
@@ -654,6 +1355,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_list_containers_filtered() {
+        let filter = ContainerListFilter {
+            all: false,
+            status: Some(ContainerStatus::Exited),
+            label: Some("com.docker.compose.project=shop".to_string()),
+            name: None,
+            ancestor: Some("nginx".to_string()),
+        };
+        assert_eq!(
+            CommandBuilder::list_containers_filtered(ContainerRuntime::Docker, &filter),
+            "docker ps --no-trunc --format json --filter 'status=exited' --filter 'label=com.docker.compose.project=shop' --filter 'ancestor=nginx'"
+        );
+        assert_eq!(
+            CommandBuilder::list_containers_filtered(ContainerRuntime::Apple, &filter),
+            "container list --all --format json"
+        );
+        assert_eq!(
+            CommandBuilder::list_containers_filtered(
+                ContainerRuntime::Apple,
+                &ContainerListFilter {
+                    all: false,
+                    ..ContainerListFilter::default()
+                }
+            ),
+            "container list --format json"
+        );
+    }
+
     #[test]
     fn test_container_action() {
         assert_eq!(
@@ -730,11 +1460,11 @@ mod tests {
     fn test_inspect_container() {
         assert_eq!(
             CommandBuilder::inspect_container(ContainerRuntime::Docker, "abc"),
-            "docker inspect abc"
+            "docker inspect 'abc'"
         );
         assert_eq!(
             CommandBuilder::inspect_container(ContainerRuntime::Apple, "abc"),
-            "container inspect abc"
+            "container inspect 'abc'"
         );
     }
 
@@ -744,6 +1474,149 @@ mod tests {
         assert_eq!(cmd, "docker inspect c1 c2 c3");
     }
 
+    #[test]
+    fn test_bulk_container_action() {
+        assert_eq!(
+            CommandBuilder::bulk_container_action(
+                ContainerRuntime::Docker,
+                ContainerAction::Stop,
+                &["c1", "c2"],
+                true
+            )
+            .as_deref(),
+            Some("docker stop c1 c2")
+        );
+        assert_eq!(
+            CommandBuilder::bulk_container_action(
+                ContainerRuntime::Podman,
+                ContainerAction::Remove,
+                &["c1", "c2"],
+                true
+            )
+            .as_deref(),
+            Some("podman rm -f c1 c2")
+        );
+        assert_eq!(
+            CommandBuilder::bulk_container_action(
+                ContainerRuntime::Apple,
+                ContainerAction::Unpause,
+                &["c1", "c2"],
+                false
+            )
+            .as_deref(),
+            Some("container resume c1 c2")
+        );
+        assert_eq!(
+            CommandBuilder::bulk_container_action(
+                ContainerRuntime::Apple,
+                ContainerAction::Restart,
+                &["c1", "c2"],
+                false
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_create_container() {
+        let spec = ContainerCreateSpec {
+            image: "nginx:latest".to_string(),
+            name: Some("web".to_string()),
+            ports: vec![
+                PortSpec {
+                    host_ip: Some("127.0.0.1".to_string()),
+                    host_port: Some(8080),
+                    container_port: 80,
+                    protocol: None,
+                },
+                PortSpec {
+                    host_ip: Some("::1".to_string()),
+                    host_port: None,
+                    container_port: 53,
+                    protocol: Some("udp".to_string()),
+                },
+            ],
+            env: std::collections::BTreeMap::from([(
+                "GREETING".to_string(),
+                "it's $HOME; rm -rf /".to_string(),
+            )]),
+            volumes: vec![crate::models::container::VolumeSpec {
+                source: "/srv/my site".to_string(),
+                destination: "/usr/share/nginx/html".to_string(),
+                read_only: true,
+            }],
+            restart_policy: Some("unless-stopped".to_string()),
+            network: Some("frontend".to_string()),
+            command: vec![
+                "nginx".to_string(),
+                "-g".to_string(),
+                "daemon off;".to_string(),
+            ],
+            detach: true,
+        };
+        assert_eq!(
+            CommandBuilder::create_container(ContainerRuntime::Docker, &spec),
+            "docker run -d --name 'web' -p '127.0.0.1:8080:80' -p '[::1]::53/udp' \
+             -e 'GREETING=it'\\''s $HOME; rm -rf /' -v '/srv/my site:/usr/share/nginx/html:ro' \
+             --restart 'unless-stopped' --network 'frontend' 'nginx:latest' 'nginx' '-g' 'daemon off;'"
+        );
+
+        let spec = ContainerCreateSpec {
+            ports: vec![PortSpec {
+                host_ip: None,
+                host_port: None,
+                container_port: 80,
+                protocol: None,
+            }],
+            env: Default::default(),
+            volumes: Vec::new(),
+            command: Vec::new(),
+            detach: false,
+            ..spec
+        };
+        assert_eq!(
+            CommandBuilder::create_container(ContainerRuntime::Apple, &spec),
+            "container create --name 'web' -p '80' --network 'frontend' 'nginx:latest'"
+        );
+    }
+
+    #[test]
+    fn test_rename_and_update_restart_policy() {
+        assert_eq!(
+            CommandBuilder::rename_container(ContainerRuntime::Docker, "c1", "web").as_deref(),
+            Some("docker rename 'c1' 'web'")
+        );
+        assert_eq!(
+            CommandBuilder::rename_container(ContainerRuntime::Apple, "c1", "web"),
+            None
+        );
+        assert_eq!(
+            CommandBuilder::update_restart_policy(
+                ContainerRuntime::Podman,
+                "c1",
+                "on-failure",
+                Some(5)
+            )
+            .as_deref(),
+            Some("podman update --restart 'on-failure:5' 'c1'")
+        );
+        // Retries only mean something for on-failure
+        assert_eq!(
+            CommandBuilder::update_restart_policy(
+                ContainerRuntime::Nerdctl,
+                "c1",
+                "always",
+                Some(5)
+            )
+            .as_deref(),
+            Some("nerdctl update --restart 'always' 'c1'")
+        );
+        assert_eq!(
+            CommandBuilder::update_restart_policy(ContainerRuntime::Apple, "c1", "always", None),
+            None
+        );
+    }
+
     #[test]
     fn test_force_remove_container() {
         assert_eq!(
@@ -758,7 +1631,14 @@ mod tests {
 
     #[test]
     fn test_container_logs_with_options() {
-        let cmd = CommandBuilder::container_logs(ContainerRuntime::Docker, "c1", Some(100), true);
+        let cmd = CommandBuilder::container_logs(
+            ContainerRuntime::Docker,
+            "c1",
+            Some(100),
+            None,
+            None,
+            true,
+        );
         assert!(cmd.contains("--tail 100"));
         assert!(cmd.contains("--timestamps"));
         assert!(cmd.contains("c1"));
@@ -766,7 +1646,8 @@ mod tests {
 
     #[test]
     fn test_container_logs_no_options() {
-        let cmd = CommandBuilder::container_logs(ContainerRuntime::Docker, "c1", None, false);
+        let cmd =
+            CommandBuilder::container_logs(ContainerRuntime::Docker, "c1", None, None, None, false);
         assert!(!cmd.contains("--tail"));
         assert!(!cmd.contains("--timestamps"));
         assert!(cmd.contains("docker logs"));
@@ -775,22 +1656,102 @@ mod tests {
 
     #[test]
     fn test_container_logs_apple_no_timestamps() {
-        let cmd = CommandBuilder::container_logs(ContainerRuntime::Apple, "c1", Some(50), true);
+        let cmd = CommandBuilder::container_logs(
+            ContainerRuntime::Apple,
+            "c1",
+            Some(50),
+            Some("10m"),
+            None,
+            true,
+        );
         assert!(cmd.contains("container logs"));
         assert!(cmd.contains("--tail 50"));
-        // Apple doesn't support --timestamps
+        // Apple doesn't support --timestamps or time filters
         assert!(!cmd.contains("--timestamps"));
+        assert!(!cmd.contains("--since"));
+    }
+
+    #[test]
+    fn test_container_logs_escapes_container() {
+        let cmd = CommandBuilder::container_logs(
+            ContainerRuntime::Docker,
+            "web; curl x|sh",
+            None,
+            None,
+            None,
+            false,
+        );
+        assert_eq!(cmd, "docker logs 'web; curl x|sh'");
+    }
+
+    #[test]
+    fn test_container_logs_time_range() {
+        let cmd = CommandBuilder::container_logs(
+            ContainerRuntime::Podman,
+            "c1",
+            None,
+            Some("2024-01-01T00:00:00Z"),
+            Some("10m"),
+            false,
+        );
+        assert_eq!(
+            cmd,
+            "podman logs --since '2024-01-01T00:00:00Z' --until '10m' 'c1'"
+        );
+    }
+
+    #[test]
+    fn test_container_stats() {
+        assert_eq!(
+            CommandBuilder::container_stats(ContainerRuntime::Docker, &["abc", "def"]),
+            "docker stats --no-stream --format json 'abc' 'def'"
+        );
+        assert_eq!(
+            CommandBuilder::container_stats(ContainerRuntime::Podman, &[]),
+            "podman stats --no-stream --format json"
+        );
+        assert_eq!(
+            CommandBuilder::container_stats(ContainerRuntime::Apple, &["abc"]),
+            "container stats --no-stream --format json 'abc'"
+        );
+        assert_eq!(
+            CommandBuilder::container_stats(ContainerRuntime::Docker, &["a; rm -rf /"]),
+            "docker stats --no-stream --format json 'a; rm -rf /'"
+        );
     }
 
     #[test]
     fn test_container_logs_stream() {
         assert_eq!(
-            CommandBuilder::container_logs_stream(ContainerRuntime::Docker, "c1"),
-            "docker logs -f c1"
+            CommandBuilder::container_logs_stream(
+                ContainerRuntime::Docker,
+                "c1",
+                &ContainerLogOptions::default()
+            ),
+            "docker logs -f 'c1'"
         );
         assert_eq!(
-            CommandBuilder::container_logs_stream(ContainerRuntime::Apple, "c1"),
-            "container logs -f c1"
+            CommandBuilder::container_logs_stream(
+                ContainerRuntime::Apple,
+                "c1",
+                &ContainerLogOptions::default()
+            ),
+            "container logs -f 'c1'"
+        );
+
+        let options = ContainerLogOptions {
+            tail: Some(100),
+            since: Some("10m".to_string()),
+            timestamps: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            CommandBuilder::container_logs_stream(ContainerRuntime::Podman, "c1", &options),
+            "podman logs -f --tail 100 --since '10m' --timestamps 'c1'"
+        );
+        assert_eq!(
+            CommandBuilder::container_logs_stream(ContainerRuntime::Apple, "c1", &options),
+            "container logs -f --tail 100 'c1'"
         );
     }
 
@@ -842,6 +1803,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_events() {
+        assert_eq!(
+            CommandBuilder::events(ContainerRuntime::Docker).as_deref(),
+            Some("docker events --format json")
+        );
+        assert_eq!(
+            CommandBuilder::events(ContainerRuntime::Podman).as_deref(),
+            Some("podman events --format json")
+        );
+        assert_eq!(CommandBuilder::events(ContainerRuntime::Apple), None);
+    }
+
+    #[test]
+    fn test_image_history() {
+        assert_eq!(
+            CommandBuilder::image_history(ContainerRuntime::Podman, "img1").as_deref(),
+            Some("podman history --no-trunc --human=false --format json img1")
+        );
+        assert_eq!(
+            CommandBuilder::image_history(ContainerRuntime::Apple, "img1"),
+            None
+        );
+    }
+
     #[test]
     fn test_tag_image() {
         assert_eq!(
@@ -865,8 +1851,42 @@ mod tests {
     #[test]
     fn test_create_volume() {
         assert_eq!(
-            CommandBuilder::create_volume(ContainerRuntime::Docker, "myvol"),
-            "docker volume create myvol"
+            CommandBuilder::create_volume(
+                ContainerRuntime::Docker,
+                "myvol",
+                &VolumeCreateOptions::default()
+            )
+            .as_deref(),
+            Some("docker volume create myvol")
+        );
+    }
+
+    #[test]
+    fn test_create_volume_with_options() {
+        let options = VolumeCreateOptions {
+            driver: Some("local".to_string()),
+            driver_opts: HashMap::from([
+                ("type".to_string(), "nfs".to_string()),
+                ("o".to_string(), "addr=10.0.0.5,rw".to_string()),
+            ]),
+            labels: HashMap::from([("team".to_string(), "data's".to_string())]),
+        };
+        assert_eq!(
+            CommandBuilder::create_volume(ContainerRuntime::Podman, "share", &options).as_deref(),
+            Some("podman volume create --driver local --opt 'o=addr=10.0.0.5,rw' --opt 'type=nfs' --label 'team=data'\\''s' share")
+        );
+        assert_eq!(
+            CommandBuilder::create_volume(ContainerRuntime::Apple, "share", &options),
+            None
+        );
+        assert_eq!(
+            CommandBuilder::create_volume(
+                ContainerRuntime::Apple,
+                "share",
+                &VolumeCreateOptions::default()
+            )
+            .as_deref(),
+            Some("container volume create share")
         );
     }
 
@@ -898,35 +1918,117 @@ mod tests {
         );
     }
 
+    fn network_options(driver: Option<&str>, subnet: Option<&str>) -> NetworkCreateOptions {
+        NetworkCreateOptions {
+            driver: driver.map(str::to_string),
+            subnet: subnet.map(str::to_string),
+            ..NetworkCreateOptions::default()
+        }
+    }
+
     #[test]
     fn test_create_network_with_options() {
         let cmd = CommandBuilder::create_network(
             ContainerRuntime::Docker,
             "mynet",
-            Some("bridge"),
-            Some("10.0.0.0/24"),
-        );
+            &network_options(Some("bridge"), Some("10.0.0.0/24")),
+        )
+        .unwrap();
         assert!(cmd.contains("docker network create"));
         assert!(cmd.contains("--driver bridge"));
         assert!(cmd.contains("--subnet 10.0.0.0/24"));
         assert!(cmd.contains("mynet"));
+
+        let options = NetworkCreateOptions {
+            gateway: Some("10.0.0.1".to_string()),
+            internal: true,
+            labels: HashMap::from([("env".to_string(), "test".to_string())]),
+            ..network_options(None, Some("10.0.0.0/24"))
+        };
+        assert_eq!(
+            CommandBuilder::create_network(ContainerRuntime::Podman, "mynet", &options).as_deref(),
+            Some("podman network create --subnet 10.0.0.0/24 --gateway 10.0.0.1 --internal --label 'env=test' mynet")
+        );
     }
 
     #[test]
     fn test_create_network_no_options() {
-        let cmd = CommandBuilder::create_network(ContainerRuntime::Docker, "mynet", None, None);
-        assert_eq!(cmd, "docker network create mynet");
+        let cmd = CommandBuilder::create_network(
+            ContainerRuntime::Docker,
+            "mynet",
+            &NetworkCreateOptions::default(),
+        );
+        assert_eq!(cmd.as_deref(), Some("docker network create mynet"));
     }
 
     #[test]
-    fn test_create_network_apple_ignores_driver_subnet() {
+    fn test_create_network_apple_rejects_options() {
         let cmd = CommandBuilder::create_network(
             ContainerRuntime::Apple,
             "mynet",
-            Some("bridge"),
-            Some("10.0.0.0/24"),
+            &network_options(Some("bridge"), Some("10.0.0.0/24")),
+        );
+        assert_eq!(cmd, None);
+        assert_eq!(
+            CommandBuilder::create_network(
+                ContainerRuntime::Apple,
+                "mynet",
+                &NetworkCreateOptions::default()
+            )
+            .as_deref(),
+            Some("container network create mynet")
+        );
+    }
+
+    #[test]
+    fn test_pod_commands() {
+        assert_eq!(CommandBuilder::list_pods(), "podman pod ps --format json");
+        assert_eq!(
+            CommandBuilder::pod_action(ContainerAction::Start, "webapp"),
+            "podman pod start 'webapp'"
+        );
+        assert_eq!(
+            CommandBuilder::pod_action(ContainerAction::Remove, "8e7f0c9d"),
+            "podman pod rm '8e7f0c9d'"
+        );
+    }
+
+    #[test]
+    fn test_prune_commands() {
+        let filters = vec!["until=24h".to_string(), "label=env=dev".to_string()];
+        let cases = [
+            (
+                CommandBuilder::prune_images(ContainerRuntime::Docker, true, &filters),
+                "docker image prune -f -a --filter 'until=24h' --filter 'label=env=dev'",
+            ),
+            (
+                CommandBuilder::prune_images(ContainerRuntime::Podman, false, &[]),
+                "podman image prune -f",
+            ),
+            (
+                CommandBuilder::prune_containers(ContainerRuntime::Nerdctl),
+                "nerdctl container prune -f",
+            ),
+            (
+                CommandBuilder::prune_volumes(ContainerRuntime::Docker),
+                "docker volume prune -f",
+            ),
+            (
+                CommandBuilder::prune_networks(ContainerRuntime::Podman),
+                "podman network prune -f",
+            ),
+            (
+                CommandBuilder::prune_system(ContainerRuntime::Docker, true, true),
+                "docker system prune -f -a --volumes",
+            ),
+        ];
+        for (command, expected) in cases {
+            assert_eq!(command.as_deref(), Some(expected));
+        }
+        assert_eq!(
+            CommandBuilder::prune_system(ContainerRuntime::Apple, false, false),
+            None
         );
-        assert_eq!(cmd, "container network create mynet");
     }
 
     #[test]
@@ -952,16 +2054,25 @@ mod tests {
     #[test]
     fn test_connect_to_network() {
         assert_eq!(
-            CommandBuilder::connect_to_network(ContainerRuntime::Docker, "mynet", "c1"),
-            "docker network connect mynet c1"
+            CommandBuilder::connect_to_network(ContainerRuntime::Docker, "mynet", "c1").as_deref(),
+            Some("docker network connect mynet c1")
+        );
+        assert_eq!(
+            CommandBuilder::connect_to_network(ContainerRuntime::Nerdctl, "mynet", "c1"),
+            None
         );
     }
 
     #[test]
     fn test_disconnect_from_network() {
         assert_eq!(
-            CommandBuilder::disconnect_from_network(ContainerRuntime::Podman, "mynet", "c1"),
-            "podman network disconnect mynet c1"
+            CommandBuilder::disconnect_from_network(ContainerRuntime::Podman, "mynet", "c1")
+                .as_deref(),
+            Some("podman network disconnect mynet c1")
+        );
+        assert_eq!(
+            CommandBuilder::disconnect_from_network(ContainerRuntime::Nerdctl, "mynet", "c1"),
+            None
         );
     }
 
@@ -987,9 +2098,10 @@ mod tests {
     #[test]
     fn test_disk_usage() {
         assert_eq!(
-            CommandBuilder::disk_usage(ContainerRuntime::Docker),
-            "docker system df --format json"
+            CommandBuilder::disk_usage(ContainerRuntime::Docker).as_deref(),
+            Some("docker system df --format json")
         );
+        assert_eq!(CommandBuilder::disk_usage(ContainerRuntime::Nerdctl), None);
     }
 
     #[test]
@@ -1000,6 +2112,71 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_rootless_check_and_socket() {
+        assert_eq!(
+            CommandBuilder::rootless_check(ContainerRuntime::Podman).as_deref(),
+            Some("podman info --format '{{.Host.Security.Rootless}}'")
+        );
+        assert_eq!(
+            CommandBuilder::rootless_check(ContainerRuntime::Apple),
+            None
+        );
+
+        assert_eq!(
+            CommandBuilder::runtime_socket(ContainerRuntime::Podman, false),
+            Some("/run/podman/podman.sock")
+        );
+        assert_eq!(
+            CommandBuilder::runtime_socket(ContainerRuntime::Podman, true),
+            Some("$XDG_RUNTIME_DIR/podman/podman.sock")
+        );
+        assert_eq!(
+            CommandBuilder::runtime_socket(ContainerRuntime::Nerdctl, false),
+            None
+        );
+    }
+
+    #[test]
+    fn test_nerdctl_commands_follow_docker_syntax() {
+        assert_eq!(
+            CommandBuilder::list_containers(ContainerRuntime::Nerdctl),
+            "nerdctl ps -a --no-trunc --format json"
+        );
+        assert_eq!(
+            CommandBuilder::container_action(ContainerRuntime::Nerdctl, ContainerAction::Unpause, "c1"),
+            "nerdctl unpause c1"
+        );
+        assert_eq!(
+            CommandBuilder::container_logs(
+                ContainerRuntime::Nerdctl,
+                "c1",
+                Some(50),
+                None,
+                None,
+                true
+            ),
+            "nerdctl logs --tail 50 --timestamps 'c1'"
+        );
+        assert_eq!(
+            CommandBuilder::remove_image(ContainerRuntime::Nerdctl, "nginx", true),
+            "nerdctl rmi -f nginx"
+        );
+        assert_eq!(
+            CommandBuilder::create_network(
+                ContainerRuntime::Nerdctl,
+                "mynet",
+                &network_options(Some("bridge"), None)
+            )
+            .as_deref(),
+            Some("nerdctl network create --driver bridge mynet")
+        );
+        assert_eq!(CommandBuilder::detect_runtime(ContainerRuntime::Nerdctl), "nerdctl version");
+        assert!(CommandBuilder::list_containers_fallback(ContainerRuntime::Nerdctl)
+            .unwrap()
+            .starts_with("nerdctl ps -a"));
+    }
+
     #[test]
     fn test_exec_terminal() {
         assert_eq!(
@@ -1025,6 +2202,14 @@ mod tests {
         assert_eq!(CommandBuilder::default_shell(), "/bin/sh");
     }
 
+    #[test]
+    fn test_detect_container_shells() {
+        assert_eq!(
+            CommandBuilder::detect_container_shells(ContainerRuntime::Podman, "c1"),
+            "podman exec c1 sh -c \"for s in bash zsh fish ash sh; do command -v \\$s; done; true\""
+        );
+    }
+
     #[test]
     fn test_shell_escape() {
         assert_eq!(CommandBuilder::shell_escape("/tmp/test"), "'/tmp/test'");
@@ -1046,6 +2231,21 @@ mod tests {
         assert!(cmd.contains("__FILE_TOO_LARGE__"));
     }
 
+    #[test]
+    fn test_read_file_range() {
+        let cmd = CommandBuilder::read_file_range("/var/log/syslog", 0, 4096);
+        assert!(cmd.contains("__FILE_SIZE__:$SIZE"));
+        assert!(cmd.ends_with("tail -c +1 '/var/log/syslog' | head -c 4096 | base64"));
+
+        let cmd = CommandBuilder::read_file_range("/var/log/syslog", 1000, 10);
+        assert!(cmd.contains("tail -c +1001 '/var/log/syslog' | head -c 10"));
+
+        assert_eq!(
+            CommandBuilder::tail_file("/var/log/my app.log", 200),
+            "tail -n 200 '/var/log/my app.log'"
+        );
+    }
+
     #[test]
     fn test_write_file_from_base64() {
         let cmd = CommandBuilder::write_file_from_base64("/tmp/file.txt", "SGVsbG8=");
@@ -1069,6 +2269,15 @@ mod tests {
         assert_eq!(CommandBuilder::delete_directory("/tmp/dir"), "rm -rf '/tmp/dir'");
     }
 
+    #[test]
+    fn test_copy_file() {
+        assert_eq!(
+            CommandBuilder::copy_file("/a.txt", "/.b.txt.partial", "/b.txt"),
+            "cp -p '/a.txt' '/.b.txt.partial' && mv -f '/.b.txt.partial' '/b.txt' \
+             || { rm -f '/.b.txt.partial'; exit 1; }"
+        );
+    }
+
     #[test]
     fn test_rename_path() {
         assert_eq!(
@@ -1085,19 +2294,113 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_directory_usage() {
+        let summary = CommandBuilder::directory_usage("/var/log", None);
+        assert!(summary.contains("du -b -s '/var/log'"));
+        assert!(summary.contains("du -k -s '/var/log'"));
+        assert!(summary.contains("__DU_UNIT__:1'"));
+        assert!(summary.contains("__DU_UNIT__:1024"));
+
+        let tree = CommandBuilder::directory_usage("/var/log", Some(1));
+        assert!(tree.contains("du -b -d 1 '/var/log'"));
+        assert!(tree.contains("du -k -d 1 '/var/log'"));
+    }
+
+    #[test]
+    fn test_change_permissions_and_owner() {
+        assert_eq!(
+            CommandBuilder::change_permissions("/srv/my app", "755", false),
+            "chmod 755 '/srv/my app'"
+        );
+        assert_eq!(
+            CommandBuilder::change_permissions("/srv", "2775", true),
+            "chmod -R 2775 '/srv'"
+        );
+        assert_eq!(
+            CommandBuilder::change_owner("/srv", "www-data", Some("staff"), true),
+            "chown -R 'www-data:staff' '/srv'"
+        );
+        assert_eq!(
+            CommandBuilder::change_owner("/srv/file", "1000", None, false),
+            "chown '1000' '/srv/file'"
+        );
+        assert!(CommandBuilder::stat_path("/srv").contains("ls -lad --time-style=long-iso '/srv'"));
+    }
+
+    #[test]
+    fn test_create_archive() {
+        let paths = vec!["/srv/app".to_string(), "/etc/nginx/".to_string()];
+
+        let tar = CommandBuilder::create_archive(&paths, "/tmp/out.tar.gz", ArchiveFormat::TarGz);
+        assert!(tar.starts_with("for t in tar gzip; do command -v"));
+        assert!(tar.ends_with("tar -czf '/tmp/out.tar.gz' -C '/srv' 'app' -C '/etc' 'nginx'"));
+
+        let zip = CommandBuilder::create_archive(&paths[..1], "/tmp/out.zip", ArchiveFormat::Zip);
+        assert!(zip.contains("for t in zip;"));
+        assert!(
+            zip.ends_with("rm -f '/tmp/out.zip' && (cd '/srv' && zip -rqy '/tmp/out.zip' 'app')")
+        );
+
+        let root = CommandBuilder::create_archive(
+            &["/data".to_string()],
+            "/tmp/d.tar.gz",
+            ArchiveFormat::TarGz,
+        );
+        assert!(root.ends_with("-C '/' 'data'"));
+    }
+
+    #[test]
+    fn test_extract_archive() {
+        let tar = CommandBuilder::extract_archive("/tmp/a.tgz", "/srv/a", ArchiveFormat::TarGz);
+        assert!(tar.ends_with("mkdir -p '/srv/a' && tar -xzf '/tmp/a.tgz' -C '/srv/a'"));
+
+        let zip = CommandBuilder::extract_archive("/tmp/a.zip", "/srv/a", ArchiveFormat::Zip);
+        assert!(zip.contains("for t in unzip;"));
+        assert!(zip.ends_with("unzip -oq '/tmp/a.zip' -d '/srv/a'"));
+    }
+
+    #[test]
+    fn test_search_file_contents() {
+        assert_eq!(
+            CommandBuilder::search_file_contents("/srv", "it's", false, true, 101),
+            "grep -rnHI -F -e 'it'\\''s' -- '/srv' | head -n 101"
+        );
+        assert_eq!(
+            CommandBuilder::search_file_contents("/srv", "-v|err", true, false, 10),
+            "grep -rnHIi -E -e '-v|err' -- '/srv' | head -n 10"
+        );
+    }
+
     #[test]
     fn test_extended_system_info_unix_contains_markers() {
-        let cmd = CommandBuilder::get_extended_system_info_unix(ContainerRuntime::Docker);
+        let cmd = CommandBuilder::get_extended_system_info_unix(ContainerRuntime::Docker, "/");
         assert!(cmd.contains("===USERNAME==="));
         assert!(cmd.contains("===HOSTNAME==="));
         assert!(cmd.contains("===CONTAINERS==="));
+        assert!(cmd.contains("===GPU==="));
+        assert!(cmd.contains("nvidia-smi --query-gpu=name,memory.total,driver_version"));
+        assert!(cmd.contains("system_profiler SPDisplaysDataType"));
         assert!(cmd.contains("===END==="));
         assert!(cmd.contains("docker"));
     }
 
+    #[test]
+    fn test_extended_system_info_disk_mount_point() {
+        let cmd =
+            CommandBuilder::get_extended_system_info_unix(ContainerRuntime::Docker, "/srv/my data");
+        assert!(cmd.contains("df -h '/srv/my data'"));
+        assert!(!cmd.contains("tail -1"));
+
+        let cmd = CommandBuilder::get_extended_system_info_windows(ContainerRuntime::Docker, "d:");
+        assert!(cmd.contains("DeviceID='D:'"));
+        let cmd = CommandBuilder::get_extended_system_info_windows(ContainerRuntime::Docker, "/");
+        assert!(cmd.contains("DeviceID='C:'"));
+    }
+
     #[test]
     fn test_extended_system_info_windows_contains_markers() {
-        let cmd = CommandBuilder::get_extended_system_info_windows(ContainerRuntime::Docker);
+        let cmd = CommandBuilder::get_extended_system_info_windows(ContainerRuntime::Docker, "/");
         assert!(cmd.contains("===USERNAME==="));
         assert!(cmd.contains("===END==="));
         assert!(cmd.contains("docker"));
@@ -1109,6 +2412,11 @@ mod tests {
         assert!(cmd.contains("===CPU==="));
         assert!(cmd.contains("===MEM==="));
         assert!(cmd.contains("===LOAD==="));
+        assert!(cmd.contains("===DISKIO==="));
+        assert!(cmd.contains("/proc/diskstats"));
+        assert!(cmd.contains("===NET==="));
+        assert!(cmd.contains("/proc/net/dev"));
+        assert!(cmd.contains("===DISK==="));
         assert!(cmd.contains("===END==="));
     }
 
@@ -1117,6 +2425,9 @@ mod tests {
         let cmd = CommandBuilder::get_live_metrics_macos();
         assert!(cmd.contains("===CPU==="));
         assert!(cmd.contains("vm_stat"));
+        assert!(cmd.contains("===DISKIO==="));
+        assert!(cmd.contains("iostat"));
+        assert!(cmd.contains("netstat -ib"));
     }
 
     #[test]
@@ -1124,11 +2435,16 @@ mod tests {
         let cmd = CommandBuilder::get_live_metrics_windows();
         assert!(cmd.contains("===CPU==="));
         assert!(cmd.contains("===MEM==="));
+        assert!(cmd.contains("===SWAP==="));
+        assert!(cmd.contains("Win32_Processor -Property LoadPercentage,NumberOfLogicalProcessors"));
+        assert!(cmd.contains("Win32_PageFileUsage -Property AllocatedBaseSize,CurrentUsage"));
+        assert!(cmd.contains("InvariantCulture"));
     }
 
     #[test]
     fn test_extended_system_info_for_remote_always_unix() {
-        let cmd = CommandBuilder::get_extended_system_info_for_remote(ContainerRuntime::Podman);
+        let cmd =
+            CommandBuilder::get_extended_system_info_for_remote(ContainerRuntime::Podman, "/");
         // Remote always uses Unix commands
         assert!(cmd.contains("whoami"));
         assert!(cmd.contains("podman"));
@@ -5,9 +5,13 @@ use std::collections::HashMap;
 
 use crate::models::container::*;
 use crate::models::error::ContainerError;
-use crate::models::image::ContainerImage;
+use crate::models::image::{ContainerImage, ImageDetails, ImageHistoryEntry, PullProgress};
 use crate::models::network::Network;
-use crate::models::system::{ExtendedSystemInfo, LiveSystemMetrics, OsType, SystemId};
+use crate::models::pod::{Pod, PodStatus};
+use crate::models::prune::PruneResult;
+use crate::models::system::{
+    ExtendedSystemInfo, GpuInfo, LiveSystemMetrics, MetricsCounters, OsType, SystemId,
+};
 use crate::models::volume::Volume;
 
 /// Parser for container runtime command output
@@ -25,7 +29,7 @@ impl OutputParser {
         system_id: &str,
     ) -> Result<Vec<Container>, ContainerError> {
         match runtime {
-            ContainerRuntime::Docker | ContainerRuntime::Podman => {
+            ContainerRuntime::Docker | ContainerRuntime::Podman | ContainerRuntime::Nerdctl => {
                 Self::parse_docker_container_list(output, runtime, system_id)
             }
             ContainerRuntime::Apple => Self::parse_apple_container_list(output, system_id),
@@ -107,11 +111,7 @@ impl OutputParser {
                 .unwrap_or_default(),
         );
 
-        let created_str = json["CreatedAt"]
-            .as_str()
-            .or_else(|| json["Created"].as_str())
-            .unwrap_or_default();
-        let created_at = Self::parse_docker_date(created_str).unwrap_or_else(Utc::now);
+        let created_at = Self::parse_created(json).unwrap_or_else(Utc::now);
 
         // Handle Ports field - can be string, null, or missing
         let ports_str = json["Ports"].as_str().unwrap_or_default();
@@ -126,6 +126,7 @@ impl OutputParser {
             system_id: SystemId(system_id.to_string()),
             created_at,
             ports,
+            uptime: None,
             // Default values for details - will be populated from inspect
             environment_variables: HashMap::new(),
             volumes: Vec::new(),
@@ -176,8 +177,10 @@ impl OutputParser {
                 status,
                 runtime: ContainerRuntime::Apple,
                 system_id: SystemId(system_id.to_string()),
-                created_at: Utc::now(), // Apple doesn't provide creation time in list
+                // Older Apple Container releases don't report a creation time
+                created_at: Self::parse_apple_created(&item).unwrap_or_else(Utc::now),
                 ports,
+                uptime: None,
                 // Default values for details - will be populated from inspect
                 environment_variables: HashMap::new(),
                 volumes: Vec::new(),
@@ -198,6 +201,15 @@ impl OutputParser {
         Ok(containers)
     }
 
+    /// Creation time of an Apple container: recent releases report `createdAt`
+    /// (or `created`) next to `status`, some inside `configuration`
+    fn parse_apple_created(item: &Value) -> Option<DateTime<Utc>> {
+        [item, &item["configuration"]]
+            .iter()
+            .flat_map(|obj| [&obj["createdAt"], &obj["created"]])
+            .find_map(Self::parse_date_value)
+    }
+
     /// Parse container status string to enum
     pub fn parse_status(status: &str) -> ContainerStatus {
         let lower = status.to_lowercase();
@@ -299,14 +311,12 @@ impl OutputParser {
         let containers: Vec<Value> = match serde_json::from_str(output) {
             Ok(c) => c,
             Err(e) => {
-                eprintln!("[DEBUG PARSER] Failed to parse JSON: {}", e);
+                tracing::debug!("Failed to parse inspect JSON for port bindings: {}", e);
                 return result;
             }
         };
 
-        eprintln!("[DEBUG PARSER] Parsed {} containers from inspect JSON", containers.len());
-
-        for (idx, container) in containers.iter().enumerate() {
+        for container in &containers {
             // Get container ID
             let id = container["Id"]
                 .as_str()
@@ -314,104 +324,115 @@ impl OutputParser {
                 .unwrap_or_default()
                 .to_string();
 
-            let short_id = if id.len() >= 12 { &id[..12] } else { &id };
-            eprintln!("[DEBUG PARSER] Container {}: id={}", idx, short_id);
-
             if id.is_empty() {
                 continue;
             }
 
-            let mut ports = Vec::new();
-
-            // Check network mode - host networking stores ports differently
-            let network_mode = container["HostConfig"]["NetworkMode"]
-                .as_str()
-                .unwrap_or("default");
-            eprintln!("[DEBUG PARSER]   -> NetworkMode: {}", network_mode);
-
-            if network_mode == "host" {
-                // HOST NETWORKING: Get ports from Config.ExposedPorts
-                // Format: { "7880/tcp": {}, "7881/udp": {} }
-                let exposed_ports = &container["Config"]["ExposedPorts"];
-                eprintln!("[DEBUG PARSER]   -> Config.ExposedPorts: {}", exposed_ports);
-
-                if let Some(exposed_obj) = exposed_ports.as_object() {
-                    for port_key in exposed_obj.keys() {
-                        // port_key = "7880/tcp"
-                        let parts: Vec<&str> = port_key.split('/').collect();
-                        if parts.len() == 2 {
-                            if let Ok(port) = parts[0].parse::<u16>() {
-                                let protocol = parts[1].to_string();
-                                // In host mode, host_port == container_port (bound directly)
-                                ports.push(PortMapping {
-                                    host_ip: "0.0.0.0".to_string(),
-                                    host_port: port,
-                                    container_port: port,
-                                    protocol,
-                                });
-                                eprintln!("[DEBUG PARSER]     -> Host mode port: {}/{}", port, parts[1]);
-                            }
-                        }
-                    }
-                }
-            } else {
-                // BRIDGE/OTHER: Get ports from NetworkSettings.Ports
-                // Format: { "80/tcp": [{"HostIp": "0.0.0.0", "HostPort": "8080"}] }
-                let ports_value = &container["NetworkSettings"]["Ports"];
-                eprintln!("[DEBUG PARSER]   -> NetworkSettings.Ports: {}", ports_value);
-
-                if let Some(ports_obj) = ports_value.as_object() {
-                    for (port_key, bindings) in ports_obj {
-                        let parts: Vec<&str> = port_key.split('/').collect();
-                        if parts.len() != 2 {
-                            continue;
-                        }
-
-                        let container_port: u16 = match parts[0].parse() {
-                            Ok(p) => p,
-                            Err(_) => continue,
-                        };
-                        let protocol = parts[1].to_string();
-
-                        // bindings is an array of { "HostIp": "0.0.0.0", "HostPort": "8080" }
-                        // Can be null if port is exposed but not published
-                        if let Some(binding_arr) = bindings.as_array() {
-                            for binding in binding_arr {
-                                let host_ip = binding["HostIp"]
-                                    .as_str()
-                                    .unwrap_or("0.0.0.0")
-                                    .to_string();
-
-                                let host_port: u16 = binding["HostPort"]
-                                    .as_str()
-                                    .and_then(|s| s.parse().ok())
-                                    .unwrap_or(0);
-
-                                if host_port > 0 {
-                                    ports.push(PortMapping {
-                                        host_ip,
-                                        host_port,
-                                        container_port,
-                                        protocol: protocol.clone(),
-                                    });
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-
-            eprintln!("[DEBUG PARSER]   -> Total ports: {}", ports.len());
-
+            let ports = Self::parse_ports_from_inspect_container(container);
             if !ports.is_empty() {
                 result.insert(id, ports);
             }
         }
 
-        eprintln!("[DEBUG PARSER] Final: {} containers with ports", result.len());
+        tracing::debug!(
+            "Parsed port bindings for {} of {} inspected containers",
+            result.len(),
+            containers.len()
+        );
         result
     }
 
+    /// Describe how long a container has been up, or since it stopped, the way
+    /// `docker ps` does; None for states without a meaningful start or finish time
+    fn container_uptime(
+        status: ContainerStatus,
+        state: &ContainerState,
+        now: DateTime<Utc>,
+    ) -> Option<String> {
+        let since = |at: &Option<String>| {
+            let at = Self::parse_docker_date(at.as_deref()?)?;
+            Some(Self::human_duration(now.signed_duration_since(at)))
+        };
+
+        match status {
+            ContainerStatus::Running => Some(format!("Up {}", since(&state.started_at)?)),
+            ContainerStatus::Paused => Some(format!("Up {} (Paused)", since(&state.started_at)?)),
+            ContainerStatus::Restarting => Some(format!(
+                "Restarting ({}) {} ago",
+                state.exit_code,
+                since(&state.finished_at)?
+            )),
+            ContainerStatus::Exited => Some(format!(
+                "Exited ({}) {} ago",
+                state.exit_code,
+                since(&state.finished_at)?
+            )),
+            ContainerStatus::Removing | ContainerStatus::Dead | ContainerStatus::Created => None,
+        }
+    }
+
+    /// Round a duration the way Docker's `HumanDuration` does ("About an hour", "3 days")
+    fn human_duration(duration: chrono::Duration) -> String {
+        let seconds = duration.num_seconds();
+        let minutes = duration.num_minutes();
+        // Docker rounds hours to the nearest one rather than truncating
+        let hours = (duration.num_seconds() as f64 / 3600.0).round() as i64;
+
+        if seconds < 1 {
+            "Less than a second".to_string()
+        } else if seconds == 1 {
+            "1 second".to_string()
+        } else if seconds < 60 {
+            format!("{} seconds", seconds)
+        } else if minutes == 1 {
+            "About a minute".to_string()
+        } else if minutes < 60 {
+            format!("{} minutes", minutes)
+        } else if hours == 1 {
+            "About an hour".to_string()
+        } else if hours < 48 {
+            format!("{} hours", hours)
+        } else if hours < 24 * 7 * 2 {
+            format!("{} days", hours / 24)
+        } else if hours < 24 * 30 * 2 {
+            format!("{} weeks", hours / 24 / 7)
+        } else if hours < 24 * 365 * 2 {
+            format!("{} months", hours / 24 / 30)
+        } else {
+            format!("{} years", hours / 24 / 365)
+        }
+    }
+
+    /// Creation time from `CreatedAt` or `Created`, whichever parses first.
+    /// Podman's list output puts a relative "2 hours ago" in `CreatedAt` and
+    /// the epoch in `Created`, so an unparseable first field isn't final
+    fn parse_created(json: &Value) -> Option<DateTime<Utc>> {
+        ["CreatedAt", "Created"]
+            .iter()
+            .find_map(|key| Self::parse_date_value(&json[*key]))
+    }
+
+    /// Parse a date that is either a string or a Unix epoch number. Podman
+    /// emits epochs in seconds, and in nanoseconds in some versions
+    fn parse_date_value(value: &Value) -> Option<DateTime<Utc>> {
+        match value {
+            Value::String(s) => Self::parse_docker_date(s),
+            Value::Number(n) => {
+                let epoch = n.as_i64().or_else(|| n.as_f64().map(|f| f as i64))?;
+                // Seconds stay below 1e11 until the year 5138; anything larger is a finer unit
+                match epoch.unsigned_abs() {
+                    0..100_000_000_000 => DateTime::from_timestamp(epoch, 0),
+                    100_000_000_000..100_000_000_000_000 => DateTime::from_timestamp_millis(epoch),
+                    100_000_000_000_000..100_000_000_000_000_000 => {
+                        DateTime::from_timestamp_micros(epoch)
+                    }
+                    _ => Some(DateTime::from_timestamp_nanos(epoch)),
+                }
+            }
+            _ => None,
+        }
+    }
+
     /// Parse Docker date string
     fn parse_docker_date(date_str: &str) -> Option<DateTime<Utc>> {
         // First try RFC 3339 parsing (handles Z suffix correctly)
@@ -498,8 +519,7 @@ impl OutputParser {
         };
 
         // Parse created date
-        let created_str = container["Created"].as_str().unwrap_or_default();
-        let created_at = Self::parse_docker_date(created_str).unwrap_or_else(Utc::now);
+        let created_at = Self::parse_created(container).unwrap_or_else(Utc::now);
 
         // Parse ports
         let ports = Self::parse_ports_from_inspect_container(container);
@@ -716,6 +736,8 @@ impl OutputParser {
             ulimits,
         };
 
+        let uptime = Self::container_uptime(status, &state, Utc::now());
+
         Ok(Container {
             id: ContainerId(id),
             name,
@@ -725,6 +747,7 @@ impl OutputParser {
             system_id: SystemId(system_id.to_string()),
             created_at,
             ports,
+            uptime,
             environment_variables,
             volumes,
             network_settings,
@@ -808,7 +831,10 @@ impl OutputParser {
 
         // Deduplicate IPv4/IPv6 bindings for the same port
         // Docker returns both 0.0.0.0 and :: for each mapping; keep IPv4 only
-        ports.sort_by(|a, b| a.container_port.cmp(&b.container_port));
+        ports.sort_by_key(|p| {
+            let ipv6 = p.host_ip.contains(':');
+            (p.container_port, p.protocol.clone(), p.host_port, ipv6)
+        });
         ports.dedup_by(|a, b| {
             a.container_port == b.container_port
                 && a.host_port == b.host_port
@@ -831,7 +857,7 @@ impl OutputParser {
             .ok_or_else(|| ContainerError::ParseError("Empty inspect result".to_string()))?;
 
         match runtime {
-            ContainerRuntime::Docker | ContainerRuntime::Podman => {
+            ContainerRuntime::Docker | ContainerRuntime::Podman | ContainerRuntime::Nerdctl => {
                 Self::parse_docker_container_details(container)
             }
             ContainerRuntime::Apple => Self::parse_apple_container_details(container),
@@ -1048,11 +1074,12 @@ impl OutputParser {
         };
 
         Ok(ContainerDetails {
+            created_at: Self::parse_created(container),
             environment_variables: env_vars,
             volumes,
             network_settings: NetworkSettings {
                 networks,
-                port_bindings: Vec::new(),
+                port_bindings: Self::parse_ports_from_inspect_container(container),
             },
             resource_limits,
             labels,
@@ -1122,6 +1149,7 @@ impl OutputParser {
         };
 
         Ok(ContainerDetails {
+            created_at: Self::parse_apple_created(container),
             environment_variables: env_vars,
             volumes,
             network_settings: NetworkSettings {
@@ -1138,6 +1166,123 @@ impl OutputParser {
         })
     }
 
+    // ========================================================================
+    // Container Stats Parsing
+    // ========================================================================
+
+    /// Parse `stats --no-stream --format json` output into per-container metrics.
+    /// Handles both formats:
+    /// - Docker: one JSON object per line with PascalCase keys ("CPUPerc", "MemUsage")
+    /// - Podman: JSON array with snake_case keys ("cpu_percent", "mem_usage")
+    pub fn parse_container_stats(
+        output: &str,
+        system_id: &str,
+    ) -> Result<Vec<ContainerMetrics>, ContainerError> {
+        let trimmed = output.trim();
+
+        if trimmed.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let entries: Vec<Value> = if trimmed.starts_with('[') {
+            serde_json::from_str(trimmed)
+                .map_err(|e| ContainerError::ParseError(format!("Failed to parse JSON array: {}", e)))?
+        } else {
+            let mut entries = Vec::new();
+            for line in trimmed.lines() {
+                let line = line.trim();
+                if line.is_empty() || !line.starts_with('{') {
+                    continue;
+                }
+                entries.push(
+                    serde_json::from_str(line)
+                        .map_err(|e| ContainerError::ParseError(format!("Failed to parse JSON: {}", e)))?,
+                );
+            }
+            entries
+        };
+
+        let timestamp = chrono::Utc::now().timestamp_millis();
+        Ok(entries
+            .iter()
+            .map(|json| Self::parse_container_stats_entry(json, system_id, timestamp))
+            .collect())
+    }
+
+    /// Parse a single stats entry, accepting Docker and Podman key names
+    fn parse_container_stats_entry(json: &Value, system_id: &str, timestamp: i64) -> ContainerMetrics {
+        let field = |keys: &[&str]| -> Option<String> {
+            keys.iter().find_map(|k| match json.get(*k) {
+                Some(Value::String(s)) => Some(s.clone()),
+                Some(Value::Number(n)) => Some(n.to_string()),
+                _ => None,
+            })
+        };
+
+        let percent = |keys: &[&str]| -> f32 {
+            field(keys)
+                .and_then(|v| v.trim().trim_end_matches('%').trim().parse::<f32>().ok())
+                .unwrap_or(0.0)
+        };
+
+        // "9.5MiB / 7.7GiB" style pairs
+        let pair = |keys: &[&str]| -> (Option<u64>, Option<u64>) {
+            match field(keys) {
+                Some(v) => match v.split_once('/') {
+                    Some((a, b)) => (Self::parse_stats_size(a), Self::parse_stats_size(b)),
+                    None => (Self::parse_stats_size(&v), None),
+                },
+                None => (None, None),
+            }
+        };
+
+        let (memory_usage_bytes, memory_limit_bytes) = pair(&["MemUsage", "mem_usage"]);
+        let (net_rx_bytes, net_tx_bytes) = pair(&["NetIO", "net_io"]);
+        let (block_read_bytes, block_write_bytes) = pair(&["BlockIO", "block_io"]);
+
+        ContainerMetrics {
+            container_id: field(&["ID", "id", "Container"]).unwrap_or_default(),
+            name: field(&["Name", "name"]).unwrap_or_default(),
+            system_id: system_id.to_string(),
+            timestamp,
+            cpu_percent: percent(&["CPUPerc", "cpu_percent", "CPU"]),
+            memory_percent: percent(&["MemPerc", "mem_percent"]),
+            memory_usage_bytes,
+            memory_limit_bytes,
+            net_rx_bytes,
+            net_tx_bytes,
+            block_read_bytes,
+            block_write_bytes,
+            pids: field(&["PIDs", "pids"]).and_then(|v| v.trim().parse().ok()),
+        }
+    }
+
+    /// Parse a size from stats output. Docker mixes binary units for memory
+    /// ("1.5GiB") with decimal units for I/O ("1.2kB", "3MB").
+    fn parse_stats_size(s: &str) -> Option<u64> {
+        let s = s.trim();
+        let split = s
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(s.len());
+        let (num, unit) = s.split_at(split);
+        let num: f64 = num.parse().ok()?;
+
+        let multiplier: f64 = match unit.trim().to_lowercase().as_str() {
+            "" | "b" => 1.0,
+            "kb" => 1e3,
+            "mb" => 1e6,
+            "gb" => 1e9,
+            "tb" => 1e12,
+            "kib" => 1024.0,
+            "mib" => 1024.0 * 1024.0,
+            "gib" => 1024.0 * 1024.0 * 1024.0,
+            "tib" => 1024.0 * 1024.0 * 1024.0 * 1024.0,
+            _ => return None,
+        };
+
+        Some((num * multiplier).round() as u64)
+    }
+
     // ========================================================================
     // Image Parsing
     // ========================================================================
@@ -1149,7 +1294,7 @@ impl OutputParser {
         system_id: &str,
     ) -> Result<Vec<ContainerImage>, ContainerError> {
         match runtime {
-            ContainerRuntime::Docker | ContainerRuntime::Podman => {
+            ContainerRuntime::Docker | ContainerRuntime::Podman | ContainerRuntime::Nerdctl => {
                 Self::parse_docker_image_list(output, runtime, system_id)
             }
             ContainerRuntime::Apple => Self::parse_apple_image_list(output, system_id),
@@ -1221,11 +1366,7 @@ impl OutputParser {
             .or_else(|| json["Size"].as_i64())
             .unwrap_or(0);
 
-        let created_str = json["CreatedAt"]
-            .as_str()
-            .or_else(|| json["Created"].as_str())
-            .unwrap_or_default();
-        let created = Self::parse_docker_date(created_str);
+        let created = Self::parse_created(json);
 
         Ok(ContainerImage {
             id,
@@ -1281,21 +1422,354 @@ impl OutputParser {
         Ok(images)
     }
 
-    /// Parse size string like "1.5GB" to bytes
+    /// Parse image inspection output into the image's configuration and layers
+    pub fn parse_image_details(
+        output: &str,
+        runtime: ContainerRuntime,
+    ) -> Result<ImageDetails, ContainerError> {
+        let json: Vec<Value> = serde_json::from_str(output).map_err(|e| {
+            ContainerError::ParseError(format!("Failed to parse inspect JSON: {}", e))
+        })?;
+
+        let image = json
+            .first()
+            .ok_or_else(|| ContainerError::ParseError("Empty inspect result".to_string()))?;
+
+        match runtime {
+            ContainerRuntime::Docker | ContainerRuntime::Podman | ContainerRuntime::Nerdctl => {
+                Ok(Self::parse_docker_image_details(image))
+            }
+            ContainerRuntime::Apple => Ok(Self::parse_apple_image_details(image)),
+        }
+    }
+
+    fn parse_docker_image_details(image: &Value) -> ImageDetails {
+        let config = &image["Config"];
+        let history = image["History"]
+            .as_array()
+            .map(|steps| Self::parse_oci_history(steps))
+            .unwrap_or_default();
+
+        ImageDetails {
+            id: image["Id"].as_str().unwrap_or_default().to_string(),
+            repo_tags: Self::json_strings(&image["RepoTags"]),
+            architecture: image["Architecture"].as_str().map(String::from),
+            os: image["Os"].as_str().map(String::from),
+            created: Self::parse_date_value(&image["Created"]),
+            size: image["Size"].as_i64(),
+            layers: Self::json_strings(&image["RootFS"]["Layers"]),
+            history,
+            ..Self::parse_image_config(config)
+        }
+    }
+
+    /// Apple Container reports an OCI index with one config per platform variant
+    fn parse_apple_image_details(image: &Value) -> ImageDetails {
+        let variants = image["variants"]
+            .as_array()
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        // Attestation manifests show up as variants with an "unknown" platform
+        let variant = variants
+            .iter()
+            .find(|v| {
+                v["platform"]["os"]
+                    .as_str()
+                    .is_some_and(|os| os != "unknown")
+            })
+            .or_else(|| variants.first())
+            .unwrap_or(&Value::Null);
+        let config = &variant["config"];
+        let platform = &variant["platform"];
+        let history = config["history"]
+            .as_array()
+            .map(|steps| Self::parse_oci_history(steps))
+            .unwrap_or_default();
+
+        ImageDetails {
+            id: image["index"]["digest"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string(),
+            repo_tags: image["name"]
+                .as_str()
+                .map(String::from)
+                .into_iter()
+                .collect(),
+            architecture: config["architecture"]
+                .as_str()
+                .or_else(|| platform["architecture"].as_str())
+                .map(String::from),
+            os: config["os"]
+                .as_str()
+                .or_else(|| platform["os"].as_str())
+                .map(String::from),
+            created: config["created"].as_str().and_then(Self::parse_docker_date),
+            size: variant["size"].as_i64(),
+            layers: Self::json_strings(&config["rootfs"]["diff_ids"]),
+            history,
+            ..Self::parse_image_config(&config["config"])
+        }
+    }
+
+    /// Runtime settings of an image config, which use the same keys in every format
+    fn parse_image_config(config: &Value) -> ImageDetails {
+        let mut exposed_ports: Vec<String> = config["ExposedPorts"]
+            .as_object()
+            .map(|ports| ports.keys().cloned().collect())
+            .unwrap_or_default();
+        exposed_ports.sort();
+
+        let labels = config["Labels"]
+            .as_object()
+            .map(|labels| {
+                labels
+                    .iter()
+                    .map(|(k, v)| (k.clone(), v.as_str().unwrap_or_default().to_string()))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let non_empty = |key: &str| {
+            config[key]
+                .as_str()
+                .filter(|s| !s.is_empty())
+                .map(String::from)
+        };
+
+        ImageDetails {
+            env: Self::json_strings(&config["Env"]),
+            cmd: Self::json_strings(&config["Cmd"]),
+            entrypoint: Self::json_strings(&config["Entrypoint"]),
+            exposed_ports,
+            working_dir: non_empty("WorkingDir"),
+            user: non_empty("User"),
+            labels,
+            ..Default::default()
+        }
+    }
+
+    /// Build steps from an OCI image config's `history`, which is oldest first and has no sizes
+    fn parse_oci_history(steps: &[Value]) -> Vec<ImageHistoryEntry> {
+        steps
+            .iter()
+            .rev()
+            .map(|step| ImageHistoryEntry {
+                id: None,
+                created: step["created"].as_str().and_then(Self::parse_docker_date),
+                created_by: step["created_by"].as_str().unwrap_or_default().to_string(),
+                size: None,
+                comment: step["comment"]
+                    .as_str()
+                    .filter(|s| !s.is_empty())
+                    .map(String::from),
+            })
+            .collect()
+    }
+
+    /// Collect a JSON array of strings, treating null or a missing key as empty
+    fn json_strings(value: &Value) -> Vec<String> {
+        value
+            .as_array()
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(|v| v.as_str().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Parse `<runtime> history --format json` output, newest step first
+    /// Handles both formats:
+    /// - Docker/nerdctl: one JSON object per line with capitalized keys
+    /// - Podman: JSON array with lowercase keys
+    pub fn parse_image_history(output: &str) -> Result<Vec<ImageHistoryEntry>, ContainerError> {
+        let trimmed = output.trim();
+        if trimmed.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let steps: Vec<Value> = if trimmed.starts_with('[') {
+            serde_json::from_str(trimmed).map_err(|e| {
+                ContainerError::ParseError(format!("Failed to parse JSON array: {}", e))
+            })?
+        } else {
+            trimmed
+                .lines()
+                .map(str::trim)
+                .filter(|line| line.starts_with('{'))
+                .map(|line| {
+                    serde_json::from_str(line).map_err(|e| {
+                        ContainerError::ParseError(format!("Failed to parse JSON: {}", e))
+                    })
+                })
+                .collect::<Result<_, _>>()?
+        };
+
+        Ok(steps
+            .iter()
+            .map(|step| {
+                let field = |keys: &[&str]| keys.iter().find_map(|key| step[*key].as_str());
+                let size = match &step["Size"] {
+                    Value::Null => &step["size"],
+                    size => size,
+                };
+
+                ImageHistoryEntry {
+                    id: field(&["ID", "id"])
+                        .filter(|id| !id.is_empty() && *id != "<missing>")
+                        .map(String::from),
+                    created: field(&["CreatedAt", "created"]).and_then(Self::parse_docker_date),
+                    created_by: field(&["CreatedBy", "createdBy", "created_by"])
+                        .unwrap_or_default()
+                        .to_string(),
+                    size: size.as_i64().or_else(|| {
+                        let s = size.as_str()?;
+                        s.parse().ok().or_else(|| Self::parse_size_string(s))
+                    }),
+                    comment: field(&["Comment", "comment"])
+                        .filter(|s| !s.is_empty())
+                        .map(String::from),
+                }
+            })
+            .collect())
+    }
+
+    /// Parse one line of `docker pull` or `podman pull` output into a progress update.
+    /// Lines that don't describe a layer are returned as image-wide status messages.
+    ///
+    /// Docker: `a2abf6c4d29d: Downloading [=====>     ]  10.5MB/31.4MB`, `a2abf6c4d29d: Pull complete`
+    /// Podman: `Copying blob a2abf6c4d29d [==>----] 8.1MiB / 26.6MiB`, `Copying blob a2abf6c4d29d done`
+    pub fn parse_pull_progress(line: &str) -> Option<PullProgress> {
+        let line = line.trim();
+        if line.is_empty() {
+            return None;
+        }
+
+        let is_layer_id =
+            |id: &str| (12..=64).contains(&id.len()) && id.chars().all(|c| c.is_ascii_hexdigit());
+        // "10.5MB/31.4MB" or "8.1MiB / 26.6MiB" -> percent
+        let percent = |sizes: &str| {
+            let (current, total) = sizes.split_once('/')?;
+            let current = Self::parse_stats_size(current)? as f64;
+            let total = Self::parse_stats_size(total)? as f64;
+            (total > 0.0).then(|| ((current / total) * 100.0).clamp(0.0, 100.0) as f32)
+        };
+
+        if let Some(rest) = line.strip_prefix("Copying blob ") {
+            let rest = rest.trim_start_matches("sha256:");
+            let (id, detail) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+            // Podman 5 appends the transfer rate after a '|'
+            let detail = detail.split('|').next().unwrap_or_default().trim();
+            let layer_id = Some(id.trim_end_matches("...").chars().take(12).collect());
+
+            let (status, pct) = if detail == "done" {
+                ("Pull complete", Some(100.0))
+            } else if detail.starts_with("skipped") {
+                ("Already exists", Some(100.0))
+            } else if let Some((_, sizes)) = detail.rsplit_once(']') {
+                ("Downloading", percent(sizes))
+            } else {
+                ("Pulling fs layer", None)
+            };
+            return Some(PullProgress {
+                layer_id,
+                status: status.to_string(),
+                percent: pct,
+            });
+        }
+
+        if let Some((id, rest)) = line.split_once(": ").filter(|(id, _)| is_layer_id(id)) {
+            let (status, pct) = match rest.split_once('[') {
+                Some((status, bar)) => {
+                    let sizes = bar.rsplit_once(']').map(|(_, s)| s).unwrap_or_default();
+                    (status.trim(), percent(sizes.trim()))
+                }
+                None => {
+                    let status = rest.trim();
+                    let done = matches!(
+                        status,
+                        "Pull complete" | "Already exists" | "Download complete"
+                    );
+                    (status, done.then_some(100.0))
+                }
+            };
+            return Some(PullProgress {
+                layer_id: Some(id.to_string()),
+                status: status.to_string(),
+                percent: pct,
+            });
+        }
+
+        Some(PullProgress {
+            layer_id: None,
+            status: line.to_string(),
+            percent: None,
+        })
+    }
+
+    /// Parse one line of `docker events --format json` or `podman events --format json`.
+    /// Returns None for lines that aren't a complete event.
+    ///
+    /// Docker: `{"Type":"container","Action":"start","Actor":{"ID":"..","Attributes":{"name":"web"}},"time":1700000000}`
+    /// Podman: `{"ID":"..","Name":"web","Status":"start","Time":"2024-01-15T10:30:00Z","Type":"container"}`
+    pub fn parse_event(line: &str) -> Option<ContainerEvent> {
+        let value: Value = serde_json::from_str(line.trim()).ok()?;
+        let text = |value: Option<&Value>| {
+            value
+                .and_then(|v| v.as_str())
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+        };
+        let actor = value.get("Actor");
+
+        let event_type = text(value.get("Type"))?;
+        let action = text(value.get("Action")).or_else(|| text(value.get("Status")))?;
+        let actor_id = text(actor.and_then(|a| a.get("ID")))
+            .or_else(|| text(value.get("ID")))
+            .unwrap_or_default();
+        let actor_name = text(
+            actor
+                .and_then(|a| a.get("Attributes"))
+                .and_then(|a| a.get("name")),
+        )
+        .or_else(|| text(value.get("Name")));
+        // Docker sends epoch seconds; Podman 4 an RFC3339 string and Podman 5 epoch seconds
+        let time = ["time", "Time"]
+            .iter()
+            .find_map(|key| match value.get(*key)? {
+                Value::Number(n) => n.as_i64(),
+                Value::String(s) => Self::parse_docker_date(s).map(|d| d.timestamp()),
+                _ => None,
+            })
+            .unwrap_or(0);
+
+        Some(ContainerEvent {
+            event_type,
+            action,
+            actor_id,
+            actor_name,
+            time,
+        })
+    }
+
+    /// Parse size string like "1.5GB", "512MiB", "3.2 MB" or "10k" to bytes.
+    /// Units are case-insensitive and all 1024-based: the runtimes round their
+    /// output to a few digits anyway, and existing callers expect KB = 1024
     fn parse_size_string(s: &str) -> Option<i64> {
         let s = s.trim().to_uppercase();
-        let re = Regex::new(r"^([\d.]+)\s*(B|KB|MB|GB|TB)?$").ok()?;
+        let re = Regex::new(r"^([\d.]+)\s*(?:([KMGT])(?:IB|B)?|B)?$").ok()?;
         let caps = re.captures(&s)?;
 
         let num: f64 = caps.get(1)?.as_str().parse().ok()?;
-        let unit = caps.get(2).map(|m| m.as_str()).unwrap_or("B");
-
-        let multiplier: i64 = match unit {
-            "B" => 1,
-            "KB" => 1024,
-            "MB" => 1024 * 1024,
-            "GB" => 1024 * 1024 * 1024,
-            "TB" => 1024_i64 * 1024 * 1024 * 1024,
+        let prefix = caps.get(2).map(|m| m.as_str()).unwrap_or("");
+
+        let multiplier: i64 = match prefix {
+            "K" => 1024,
+            "M" => 1024 * 1024,
+            "G" => 1024 * 1024 * 1024,
+            "T" => 1024_i64 * 1024 * 1024 * 1024,
             _ => 1,
         };
 
@@ -1496,6 +1970,126 @@ impl OutputParser {
         })
     }
 
+    // ========================================================================
+    // Pod Parsing
+    // ========================================================================
+
+    /// Parse `podman pod ps --format json` output (a JSON array; `null` when there are no pods)
+    pub fn parse_pod_list(output: &str, system_id: &str) -> Result<Vec<Pod>, ContainerError> {
+        let trimmed = output.trim();
+        if trimmed.is_empty() || trimmed == "null" {
+            return Ok(Vec::new());
+        }
+
+        let json_array: Vec<Value> = serde_json::from_str(trimmed)
+            .map_err(|e| ContainerError::ParseError(format!("Failed to parse pod list: {}", e)))?;
+
+        Ok(json_array
+            .iter()
+            .map(|json| {
+                let infra_id = json["InfraId"]
+                    .as_str()
+                    .filter(|id| !id.is_empty())
+                    .map(String::from);
+
+                Pod {
+                    id: json["Id"].as_str().unwrap_or_default().to_string(),
+                    name: json["Name"].as_str().unwrap_or_default().to_string(),
+                    status: PodStatus::from_podman(json["Status"].as_str().unwrap_or_default()),
+                    container_count: json["Containers"]
+                        .as_array()
+                        .map(|c| c.len() as u32)
+                        .unwrap_or(0),
+                    created_at: Self::parse_date_value(&json["Created"]),
+                    infra_id,
+                    system_id: SystemId(system_id.to_string()),
+                }
+            })
+            .collect())
+    }
+
+    // ========================================================================
+    // Prune Parsing
+    // ========================================================================
+
+    /// Parse the output of any `<runtime> ... prune -f` command
+    ///
+    /// Docker groups IDs under `Deleted Images:` style headers, prefixes image lines with
+    /// `untagged:`/`deleted:` and ends with `Total reclaimed space: 187.7MB`. Podman prints
+    /// bare IDs (with colon-less headers for `system prune`) and only sometimes a total.
+    pub fn parse_prune_output(output: &str) -> PruneResult {
+        let mut result = PruneResult::default();
+
+        for line in output.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with("Deleted ") || line.starts_with("untagged:") {
+                continue;
+            }
+            if let Some(space) = line.strip_prefix("Total reclaimed space:") {
+                result.reclaimed_bytes += Self::parse_size_string(space).unwrap_or(0);
+                continue;
+            }
+            let id = line.strip_prefix("deleted:").map(str::trim).unwrap_or(line);
+            result.deleted.push(id.to_string());
+        }
+
+        result
+    }
+
+    // ========================================================================
+    // Bulk Action Parsing
+    // ========================================================================
+
+    /// Whether `line` names `id` as a whole container name or ID, so `web`
+    /// doesn't match an error about `web-2`
+    fn mentions_container(line: &str, id: &str) -> bool {
+        line.split(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-')))
+            .any(|token| token.trim_end_matches('.') == id)
+    }
+
+    /// Find which containers a failed bulk action (`docker stop a b c`) failed on
+    ///
+    /// The runtime echoes each container it handled on stdout and reports the
+    /// others on stderr, one line each. Returns the failed containers with their
+    /// error; when stderr names none of them, those missing from stdout get the
+    /// whole stderr.
+    pub fn parse_bulk_action_failures<'a>(
+        stdout: &str,
+        stderr: &str,
+        container_ids: &[&'a str],
+    ) -> Vec<(&'a str, String)> {
+        let stderr_line = |id: &str| {
+            stderr
+                .lines()
+                .map(str::trim)
+                .find(|line| Self::mentions_container(line, id))
+                .map(str::to_string)
+        };
+        let failed: Vec<(&str, String)> = container_ids
+            .iter()
+            .filter_map(|id| stderr_line(id).map(|line| (*id, line)))
+            .collect();
+        if !failed.is_empty() {
+            return failed;
+        }
+
+        let echoed: Vec<&str> = stdout.lines().map(str::trim).collect();
+        let stderr = stderr.trim();
+        let missing: Vec<(&str, String)> = container_ids
+            .iter()
+            .filter(|id| !echoed.contains(id))
+            .map(|id| (*id, stderr.to_string()))
+            .collect();
+        if missing.is_empty() {
+            // Everything was echoed yet the command failed; blame all of them
+            container_ids
+                .iter()
+                .map(|id| (*id, stderr.to_string()))
+                .collect()
+        } else {
+            missing
+        }
+    }
+
     // ========================================================================
     // Runtime Detection
     // ========================================================================
@@ -1513,16 +2107,27 @@ impl OutputParser {
             ContainerRuntime::Apple => {
                 output_lower.contains("container") || output_lower.contains("version")
             }
+            // `nerdctl version` prints client/server sections rather than its own name
+            ContainerRuntime::Nerdctl => {
+                output_lower.contains("nerdctl") || output_lower.contains("containerd")
+            }
         }
     }
 
+    /// Parse the output of `CommandBuilder::rootless_check`
+    pub fn parse_rootless(output: &str) -> bool {
+        let output = output.trim();
+        output.eq_ignore_ascii_case("true") || output.contains("name=rootless")
+    }
+
     // ========================================================================
     // Extended System Info Parsing
     // ========================================================================
 
     /// Parse the combined extended system info output
     /// The output contains sections delimited by ===SECTION_NAME===
-    pub fn parse_extended_system_info(output: &str) -> ExtendedSystemInfo {
+    /// Disk usage is reported for `mount_point` when df lists it.
+    pub fn parse_extended_system_info(output: &str, mount_point: &str) -> ExtendedSystemInfo {
         let mut username = String::new();
         let mut is_root = false;
         let mut can_sudo = false;
@@ -1537,6 +2142,7 @@ impl OutputParser {
         let mut total_containers: Option<u32> = None;
         let mut total_images: Option<u32> = None;
         let mut runtime_version: Option<String> = None;
+        let mut gpus: Vec<GpuInfo> = Vec::new();
 
         // Split output by section markers
         let sections: Vec<&str> = output.split("===").collect();
@@ -1590,9 +2196,13 @@ impl OutputParser {
                     // Parse disk usage from df -h output or Windows format
                     if i + 1 < sections.len() {
                         let disk_section = sections[i + 1].trim();
-                        disk_usage_percent = Self::parse_disk_usage(disk_section);
+                        disk_usage_percent = Self::parse_disk_usage(disk_section, mount_point);
                     }
                 }
+                "GPU" if i + 1 < sections.len() => {
+                    // nvidia-smi CSV or macOS system_profiler; empty when no GPU/driver
+                    gpus = Self::parse_gpu_info(sections[i + 1].trim());
+                }
                 "UPTIME" => {
                     if !content.is_empty() && content != "unknown" {
                         uptime = Some(Self::format_uptime(content));
@@ -1631,15 +2241,73 @@ impl OutputParser {
             total_containers,
             total_images,
             runtime_version,
+            gpus,
         }
     }
 
-    /// Format uptime string for display
-    fn format_uptime(raw: &str) -> String {
-        let lower = raw.to_lowercase();
-        // Remove "up " prefix if present
-        let cleaned = lower.trim_start_matches("up ").trim();
-        cleaned.to_string()
+    /// Parse GPUs from nvidia-smi CSV or macOS `system_profiler SPDisplaysDataType` output
+    fn parse_gpu_info(section: &str) -> Vec<GpuInfo> {
+        let mut gpus = Vec::new();
+
+        // macOS: each GPU starts with "Chipset Model:"; VRAM is absent on Apple Silicon
+        if section.contains("Chipset Model:") {
+            for line in section.lines().map(str::trim) {
+                if let Some(name) = line.strip_prefix("Chipset Model:") {
+                    gpus.push(GpuInfo {
+                        name: name.trim().to_string(),
+                        memory_total: None,
+                        driver: None,
+                    });
+                } else if line.starts_with("VRAM") {
+                    if let (Some(gpu), Some((_, vram))) = (gpus.last_mut(), line.split_once(':')) {
+                        gpu.memory_total = Some(vram.trim().to_string());
+                    }
+                }
+            }
+            return gpus;
+        }
+
+        // nvidia-smi: "NVIDIA GeForce RTX 3090, 24576 MiB, 535.129.03"
+        for line in section.lines() {
+            let mut fields = line.rsplitn(3, ',').map(str::trim);
+            let (Some(driver), Some(memory), Some(name)) =
+                (fields.next(), fields.next(), fields.next())
+            else {
+                continue;
+            };
+            // Skip error text (e.g. "NVIDIA-SMI has failed ...") that happens to contain commas
+            if name.is_empty() || !memory.starts_with(|c: char| c.is_ascii_digit() || c == '[') {
+                continue;
+            }
+
+            let memory_total = memory
+                .strip_suffix("MiB")
+                .and_then(|mib| mib.trim().parse::<u64>().ok())
+                .map(|mib| {
+                    if mib % 1024 == 0 {
+                        format!("{}G", mib / 1024)
+                    } else {
+                        format!("{:.1}G", mib as f64 / 1024.0)
+                    }
+                });
+            let driver = Some(driver.to_string()).filter(|d| !d.is_empty() && !d.starts_with('['));
+
+            gpus.push(GpuInfo {
+                name: name.to_string(),
+                memory_total,
+                driver,
+            });
+        }
+
+        gpus
+    }
+
+    /// Format uptime string for display
+    fn format_uptime(raw: &str) -> String {
+        let lower = raw.to_lowercase();
+        // Remove "up " prefix if present
+        let cleaned = lower.trim_start_matches("up ").trim();
+        cleaned.to_string()
     }
 
     /// Parse OS type from uname -s output
@@ -1724,23 +2392,49 @@ impl OutputParser {
         None
     }
 
-    /// Parse disk usage percentage from df -h output line
-    fn parse_disk_usage(line: &str) -> Option<u8> {
+    /// Parse disk usage percentage from df output, preferring the row mounted at `mount_point`
+    fn parse_disk_usage(output: &str, mount_point: &str) -> Option<u8> {
         // df -h output format: "Filesystem      Size  Used Avail Use% Mounted on"
         // Example: "/dev/sda1       100G   45G   55G  45% /"
-        let parts: Vec<&str> = line.split_whitespace().collect();
-
-        // Find the percentage field (usually 5th column, but can vary)
-        for part in parts {
-            if part.ends_with('%') {
-                let num_str = part.trim_end_matches('%');
-                if let Ok(pct) = num_str.parse::<u8>() {
-                    return Some(pct);
-                }
+        // Long device names make df wrap the rest of the row onto the next line:
+        //   "/dev/mapper/vg-very-long-volume-name"
+        //   "                100G   45G   55G  45% /data"
+        let mut rows: Vec<String> = Vec::new();
+        let mut pending: Option<&str> = None;
+        for line in output.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with("Filesystem") {
+                continue;
+            }
+            if let Some(device) = pending.take() {
+                rows.push(format!("{} {}", device, line));
+            } else if line.split_whitespace().count() == 1 && !line.ends_with('%') {
+                pending = Some(line);
+            } else {
+                rows.push(line.to_string());
             }
         }
 
-        None
+        // (percentage, mount point) for each row; Windows output has no mount column
+        let usages: Vec<(u8, String)> = rows
+            .iter()
+            .filter_map(|row| {
+                let parts: Vec<&str> = row.split_whitespace().collect();
+                // Find the percentage field (usually 5th column, but can vary)
+                let idx = parts.iter().position(|part| {
+                    part.strip_suffix('%')
+                        .is_some_and(|num| num.parse::<u8>().is_ok())
+                })?;
+                let pct = parts[idx].trim_end_matches('%').parse::<u8>().ok()?;
+                Some((pct, parts[idx + 1..].join(" ")))
+            })
+            .collect();
+
+        usages
+            .iter()
+            .find(|(_, mount)| mount == mount_point)
+            .or_else(|| usages.first())
+            .map(|(pct, _)| *pct)
     }
 
     // ========================================================================
@@ -1749,13 +2443,21 @@ impl OutputParser {
 
     /// Parse live metrics output from Unix/Linux systems
     /// The output contains sections delimited by ===SECTION_NAME===
-    pub fn parse_live_metrics(output: &str, system_id: &str) -> LiveSystemMetrics {
+    ///
+    /// Throughput rates are computed against `previous` (the counters returned by the
+    /// last call); without a previous sample they are reported as `None`.
+    pub fn parse_live_metrics(
+        output: &str,
+        system_id: &str,
+        previous: Option<&MetricsCounters>,
+    ) -> (LiveSystemMetrics, MetricsCounters) {
         let mut cpu_usage_percent: f32 = 0.0;
         let mut memory_usage_percent: f32 = 0.0;
         let mut memory_used: Option<String> = None;
         let mut memory_total: Option<String> = None;
         let mut load_average: Option<[f32; 3]> = None;
         let mut swap_usage_percent: Option<f32> = None;
+        let mut disk_usage_percent: Option<u8> = None;
 
         // Track CPU values for calculation
         let mut cpu_user: u64 = 0;
@@ -1783,6 +2485,20 @@ impl OutputParser {
         let mut macos_pages_wired: u64 = 0;
         let mut macos_total_bytes: u64 = 0;
 
+        // Windows CIM values (Win32_Processor / Win32_PageFileUsage)
+        let mut windows_logical_processors: Option<f32> = None;
+        let mut windows_swap_allocated_mb: Option<f64> = None;
+        let mut windows_swap_used_mb: Option<f64> = None;
+
+        // Cumulative disk counters in bytes
+        let mut disk_read_bytes: Option<u64> = None;
+        let mut disk_write_bytes: Option<u64> = None;
+        let mut disk_total_bytes: Option<u64> = None;
+
+        // Cumulative network counters in bytes
+        let mut net_rx_bytes: Option<u64> = None;
+        let mut net_tx_bytes: Option<u64> = None;
+
         // Split output by section markers
         let sections: Vec<&str> = output.split("===").collect();
 
@@ -1822,9 +2538,13 @@ impl OutputParser {
                                     }
                                 }
                             }
-                            // Windows: Just a number (CPU percentage)
-                            if let Ok(pct) = line.parse::<f32>() {
-                                cpu_usage_percent = pct;
+                            // Windows: LoadPercentage=12.5 / LogicalProcessors=8
+                            match Self::parse_windows_metric(line) {
+                                Some(("LoadPercentage", pct)) => cpu_usage_percent = pct as f32,
+                                Some(("LogicalProcessors", count)) => {
+                                    windows_logical_processors = Some(count as f32);
+                                }
+                                _ => {}
                             }
                         }
                     }
@@ -1850,13 +2570,11 @@ impl OutputParser {
                             } else if line.starts_with("SwapFree:") {
                                 swap_free_kb = Self::parse_meminfo_value(line);
                             }
-                            // Windows format: "TotalKB FreeKB"
-                            let parts: Vec<&str> = line.split_whitespace().collect();
-                            if parts.len() == 2 {
-                                if let (Ok(total), Ok(free)) = (parts[0].parse::<u64>(), parts[1].parse::<u64>()) {
-                                    mem_total_kb = total;
-                                    mem_available_kb = free;
-                                }
+                            // Windows: TotalVisibleMemorySize=16777216 / FreePhysicalMemory=8388608 (KB)
+                            match Self::parse_windows_metric(line) {
+                                Some(("TotalVisibleMemorySize", kb)) => mem_total_kb = kb as u64,
+                                Some(("FreePhysicalMemory", kb)) => mem_available_kb = kb as u64,
+                                _ => {}
                             }
                             // macOS vm_stat format: "Pages free:                 12345."
                             if line.starts_with("Pages free:") {
@@ -1914,21 +2632,31 @@ impl OutputParser {
                     }
                 }
                 "SWAP" => {
-                    // Windows swap format
+                    // Windows page file: AllocatedBaseSize=4096 / CurrentUsage=512 (MB)
                     if i + 1 < sections.len() {
-                        let swap_section = sections[i + 1].trim();
-                        for line in swap_section.lines() {
-                            let parts: Vec<&str> = line.split_whitespace().collect();
-                            if parts.len() == 2 {
-                                if let (Ok(total), Ok(used)) = (parts[0].parse::<u64>(), parts[1].parse::<u64>()) {
-                                    if total > 0 {
-                                        swap_usage_percent = Some((used as f32 / total as f32) * 100.0);
-                                    }
+                        for line in sections[i + 1].trim().lines() {
+                            match Self::parse_windows_metric(line) {
+                                Some(("AllocatedBaseSize", mb)) => {
+                                    windows_swap_allocated_mb = Some(mb);
                                 }
+                                Some(("CurrentUsage", mb)) => windows_swap_used_mb = Some(mb),
+                                _ => {}
                             }
                         }
                     }
                 }
+                "DISKIO" if i + 1 < sections.len() => {
+                    let disk_section = sections[i + 1].trim();
+                    (disk_read_bytes, disk_write_bytes, disk_total_bytes) =
+                        Self::parse_disk_io_counters(disk_section);
+                }
+                "NET" if i + 1 < sections.len() => {
+                    let net_section = sections[i + 1].trim();
+                    (net_rx_bytes, net_tx_bytes) = Self::parse_network_counters(net_section);
+                }
+                "DISK" if i + 1 < sections.len() => {
+                    disk_usage_percent = Self::parse_disk_usage(sections[i + 1].trim(), "/");
+                }
                 _ => {}
             }
         }
@@ -1965,22 +2693,253 @@ impl OutputParser {
             memory_total = Some(Self::format_bytes(mem_total_kb * 1024));
         }
 
+        // Windows has no load average; approximate it from CPU load across logical processors
+        if let Some(cores) = windows_logical_processors.filter(|c| *c > 0.0) {
+            let load = (cpu_usage_percent / 100.0) * cores;
+            load_average = Some([load, load, load]);
+        }
+
+        // Windows page file usage
+        if let (Some(allocated), Some(used)) = (windows_swap_allocated_mb, windows_swap_used_mb) {
+            swap_usage_percent = Some(if allocated > 0.0 {
+                ((used / allocated) * 100.0) as f32
+            } else {
+                0.0
+            });
+        }
+
         // Calculate swap usage
         if swap_total_kb > 0 && swap_usage_percent.is_none() {
             let swap_used_kb = swap_total_kb.saturating_sub(swap_free_kb);
             swap_usage_percent = Some((swap_used_kb as f32 / swap_total_kb as f32) * 100.0);
         }
 
-        LiveSystemMetrics {
-            system_id: system_id.to_string(),
+        let counters = MetricsCounters {
             timestamp: chrono::Utc::now().timestamp_millis(),
+            disk_read_bytes,
+            disk_write_bytes,
+            disk_total_bytes,
+            net_rx_bytes,
+            net_tx_bytes,
+        };
+
+        let elapsed_ms = previous.map(|p| counters.timestamp - p.timestamp).unwrap_or(0);
+        let disk_read_bytes_per_sec = Self::rate_per_sec(
+            counters.disk_read_bytes,
+            previous.and_then(|p| p.disk_read_bytes),
+            elapsed_ms,
+        );
+        let disk_write_bytes_per_sec = Self::rate_per_sec(
+            counters.disk_write_bytes,
+            previous.and_then(|p| p.disk_write_bytes),
+            elapsed_ms,
+        );
+        let disk_io_bytes_per_sec = Self::rate_per_sec(
+            counters.disk_total_bytes,
+            previous.and_then(|p| p.disk_total_bytes),
+            elapsed_ms,
+        );
+        let net_rx_bytes_per_sec = Self::rate_per_sec(
+            counters.net_rx_bytes,
+            previous.and_then(|p| p.net_rx_bytes),
+            elapsed_ms,
+        );
+        let net_tx_bytes_per_sec = Self::rate_per_sec(
+            counters.net_tx_bytes,
+            previous.and_then(|p| p.net_tx_bytes),
+            elapsed_ms,
+        );
+
+        let metrics = LiveSystemMetrics {
+            system_id: system_id.to_string(),
+            timestamp: counters.timestamp,
             cpu_usage_percent,
             memory_usage_percent,
             memory_used,
             memory_total,
             load_average,
             swap_usage_percent,
+            disk_read_bytes_per_sec,
+            disk_write_bytes_per_sec,
+            disk_io_bytes_per_sec,
+            net_rx_bytes_per_sec,
+            net_tx_bytes_per_sec,
+            disk_usage_percent,
+        };
+
+        (metrics, counters)
+    }
+
+    /// Parse a Windows `Key=Value` metric line. Values may use a comma as the decimal
+    /// separator (e.g. "12,5") or contain grouping separators (e.g. "1.234,5").
+    fn parse_windows_metric(line: &str) -> Option<(&str, f64)> {
+        let (key, value) = line.trim().split_once('=')?;
+        let value: String = value
+            .trim()
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != '\'' && *c != '\u{a0}' && *c != '\u{202f}')
+            .collect();
+        // Whichever separator appears last is the decimal separator and the other groups
+        // digits; a separator that repeats on its own (e.g. "8.388.608") is grouping only
+        let count = |sep: char| value.matches(sep).count();
+        let normalized = match (value.rfind(','), value.rfind('.')) {
+            (Some(comma), Some(dot)) if comma > dot => value.replace('.', "").replace(',', "."),
+            (Some(_), Some(_)) => value.replace(',', ""),
+            (Some(_), None) if count(',') > 1 => value.replace(',', ""),
+            (Some(_), None) => value.replace(',', "."),
+            (None, Some(_)) if count('.') > 1 => value.replace('.', ""),
+            _ => value,
+        };
+        Some((key.trim(), normalized.parse().ok()?))
+    }
+
+    /// Compute a per-second rate from two cumulative counter readings.
+    /// Returns None when either reading is missing, no time has passed,
+    /// or the counter went backwards (e.g., after a reboot).
+    fn rate_per_sec(current: Option<u64>, previous: Option<u64>, elapsed_ms: i64) -> Option<u64> {
+        let (current, previous) = (current?, previous?);
+        if elapsed_ms <= 0 || current < previous {
+            return None;
+        }
+        Some((current - previous) * 1000 / elapsed_ms as u64)
+    }
+
+    /// Parse cumulative disk (read, write, combined) bytes from the DISKIO section.
+    ///
+    /// Linux: /proc/diskstats, summing whole disks only (partitions and virtual
+    /// devices would double count). macOS: `iostat -Id` only reports a combined
+    /// transfer total, so reads and writes stay None there.
+    fn parse_disk_io_counters(section: &str) -> (Option<u64>, Option<u64>, Option<u64>) {
+        const SECTOR_SIZE: u64 = 512;
+
+        let mut read_sectors: Option<u64> = None;
+        let mut write_sectors: Option<u64> = None;
+
+        // Linux /proc/diskstats format:
+        // major minor name reads merged sectors_read ms_reading writes merged sectors_written ...
+        for line in section.lines() {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            if parts.len() < 10 || parts[0].parse::<u64>().is_err() {
+                continue;
+            }
+            if !Self::is_whole_disk(parts[2]) {
+                continue;
+            }
+            if let (Ok(read), Ok(written)) = (parts[5].parse::<u64>(), parts[9].parse::<u64>()) {
+                read_sectors = Some(read_sectors.unwrap_or(0) + read);
+                write_sectors = Some(write_sectors.unwrap_or(0) + written);
+            }
+        }
+
+        if let (Some(read), Some(written)) = (read_sectors, write_sectors) {
+            return (
+                Some(read * SECTOR_SIZE),
+                Some(written * SECTOR_SIZE),
+                Some((read + written) * SECTOR_SIZE),
+            );
+        }
+
+        // macOS iostat -Id format:
+        //               disk0               disk4
+        //     KB/t  xfrs   MB     KB/t  xfrs   MB
+        //    25.58 3489287 87167.37    12.52   1003 12.27
+        let lines: Vec<&str> = section.lines().collect();
+        if let Some(header_idx) = lines.iter().position(|l| l.contains("KB/t")) {
+            if let Some(data) = lines.get(header_idx + 1) {
+                let values: Vec<f64> = data
+                    .split_whitespace()
+                    .filter_map(|v| v.parse::<f64>().ok())
+                    .collect();
+                if !values.is_empty() && values.len().is_multiple_of(3) {
+                    let total_mb: f64 = values.iter().skip(2).step_by(3).sum();
+                    return (None, None, Some((total_mb * 1024.0 * 1024.0) as u64));
+                }
+            }
+        }
+
+        (None, None, None)
+    }
+
+    /// Parse cumulative network RX/TX bytes from the NET section, summed across
+    /// external interfaces.
+    ///
+    /// Linux: /proc/net/dev. macOS: `netstat -ib`, using only the `<Link#N>` rows
+    /// since every interface is listed once per address.
+    fn parse_network_counters(section: &str) -> (Option<u64>, Option<u64>) {
+        let mut rx_total: Option<u64> = None;
+        let mut tx_total: Option<u64> = None;
+
+        for line in section.lines() {
+            let line = line.trim();
+
+            let counters = if line.contains("<Link#") {
+                // macOS: Name Mtu Network Address Ipkts Ierrs Ibytes Opkts Oerrs Obytes Coll
+                // Address can be empty, so index from the end of the row
+                let parts: Vec<&str> = line.split_whitespace().collect();
+                if parts.len() < 7 {
+                    continue;
+                }
+                let name = parts[0].trim_end_matches('*');
+                let rx = parts[parts.len() - 5].parse::<u64>();
+                let tx = parts[parts.len() - 2].parse::<u64>();
+                match (rx, tx) {
+                    (Ok(rx), Ok(tx)) => Some((name, rx, tx)),
+                    _ => None,
+                }
+            } else if let Some((name, rest)) = line.split_once(':') {
+                // Linux: "eth0: rx_bytes rx_packets ... (8 fields) tx_bytes tx_packets ..."
+                let fields: Vec<&str> = rest.split_whitespace().collect();
+                if fields.len() < 9 || name.contains(' ') {
+                    continue;
+                }
+                match (fields[0].parse::<u64>(), fields[8].parse::<u64>()) {
+                    (Ok(rx), Ok(tx)) => Some((name.trim(), rx, tx)),
+                    _ => None,
+                }
+            } else {
+                None
+            };
+
+            if let Some((name, rx, tx)) = counters {
+                if Self::is_internal_interface(name) {
+                    continue;
+                }
+                rx_total = Some(rx_total.unwrap_or(0) + rx);
+                tx_total = Some(tx_total.unwrap_or(0) + tx);
+            }
         }
+
+        (rx_total, tx_total)
+    }
+
+    /// Whether a network interface only carries host-internal traffic
+    /// (loopback, the default Docker bridge, and container veth peers)
+    fn is_internal_interface(name: &str) -> bool {
+        name.starts_with("lo") || name == "docker0" || name.starts_with("veth")
+    }
+
+    /// Whether a /proc/diskstats device name is a whole physical disk
+    /// (not a partition, loop device, ramdisk, or device-mapper target)
+    fn is_whole_disk(name: &str) -> bool {
+        const VIRTUAL_PREFIXES: [&str; 7] = ["loop", "ram", "zram", "dm-", "md", "sr", "fd"];
+        if VIRTUAL_PREFIXES.iter().any(|p| name.starts_with(p)) {
+            return false;
+        }
+
+        // nvme0n1p1 / mmcblk0p1 style partitions
+        if name.starts_with("nvme") || name.starts_with("mmcblk") {
+            return match name.rfind('p') {
+                Some(pos) => {
+                    let suffix = &name[pos + 1..];
+                    let preceded_by_digit = name[..pos].ends_with(|c: char| c.is_ascii_digit());
+                    !(preceded_by_digit && !suffix.is_empty() && suffix.chars().all(|c| c.is_ascii_digit()))
+                }
+                None => true,
+            };
+        }
+
+        // sda1 / vdb2 / xvda1 style partitions
+        !name.ends_with(|c: char| c.is_ascii_digit())
     }
 
     /// Parse a meminfo line value (e.g., "MemTotal:       16384000 kB")
@@ -2125,6 +3084,119 @@ impl OutputParser {
         Ok(entries)
     }
 
+    /// Parse the size printed by `CommandBuilder::file_size`.
+    pub fn parse_file_size(output: &str) -> Result<u64, ContainerError> {
+        output.trim().parse().map_err(|_| {
+            ContainerError::ParseError(format!("Invalid file size: {}", output.trim()))
+        })
+    }
+
+    /// Parse `CommandBuilder::read_file_range` output into the file size and the bytes read.
+    pub fn parse_file_range(output: &str) -> Result<(u64, Vec<u8>), ContainerError> {
+        use base64::Engine;
+
+        let mut lines = output.lines();
+        let size = lines
+            .by_ref()
+            .find_map(|l| l.trim().strip_prefix("__FILE_SIZE__:"))
+            .ok_or_else(|| {
+                ContainerError::ParseError("Missing file size in range output".into())
+            })?;
+        let size = Self::parse_file_size(size)?;
+
+        let encoded: String = lines.flat_map(|l| l.trim().chars()).collect();
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(encoded)
+            .map_err(|e| {
+                ContainerError::ParseError(format!("Invalid base64 in range output: {}", e))
+            })?;
+        Ok((size, bytes))
+    }
+
+    /// Parse `du` output produced by `CommandBuilder::directory_usage` into sizes in bytes.
+    /// The first line names the unit (`-b` bytes or `-k` kilobytes); entries are
+    /// `SIZE<TAB>PATH` in du's order, so the requested path comes last.
+    pub fn parse_du(
+        output: &str,
+    ) -> Result<Vec<crate::models::file_browser::DiskUsageEntry>, ContainerError> {
+        use crate::models::file_browser::DiskUsageEntry;
+
+        let mut lines = output.lines().skip_while(|l| l.trim().is_empty());
+        let unit: u64 = lines
+            .next()
+            .and_then(|l| l.trim().strip_prefix("__DU_UNIT__:"))
+            .and_then(|u| u.parse().ok())
+            .ok_or_else(|| ContainerError::ParseError("Missing du unit marker".to_string()))?;
+
+        let mut entries = Vec::new();
+        for line in lines {
+            let Some((size, path)) = line.split_once('\t') else {
+                continue;
+            };
+            let Ok(size) = size.trim().parse::<u64>() else {
+                continue;
+            };
+            entries.push(DiskUsageEntry {
+                path: path.to_string(),
+                size: size * unit,
+            });
+        }
+
+        Ok(entries)
+    }
+
+    /// Name of the tool reported missing by a `CommandBuilder` tool check, if any.
+    pub fn parse_missing_tool(stderr: &str) -> Option<String> {
+        stderr
+            .lines()
+            .find_map(|l| l.trim().strip_prefix("__MISSING_TOOL__:"))
+            .map(|t| t.to_string())
+    }
+
+    /// Parse `ls -lad <path>` output into the entry for that path.
+    pub fn parse_stat_entry(
+        output: &str,
+        path: &str,
+    ) -> Result<crate::models::file_browser::FileEntry, ContainerError> {
+        // ls prints the path as given; parse it as a listing of "/" and then fix up the names
+        let mut entry = Self::parse_directory_listing(output, "/")?
+            .into_iter()
+            .next()
+            .ok_or_else(|| ContainerError::ParseError(format!("No metadata for {}", path)))?;
+
+        let name = path
+            .trim_end_matches('/')
+            .rsplit('/')
+            .next()
+            .filter(|n| !n.is_empty())
+            .unwrap_or("/")
+            .to_string();
+        entry.is_hidden = name.starts_with('.');
+        entry.name = name;
+        entry.path = path.to_string();
+        Ok(entry)
+    }
+
+    /// Parse `grep -rnH` output (`file:line:content`) into search hits.
+    /// Splits at the first `:<digits>:` so colons in the matched content survive,
+    /// as do colons in file names unless followed by digits and another colon.
+    pub fn parse_grep_output(output: &str) -> Vec<crate::models::file_browser::SearchHit> {
+        use crate::models::file_browser::SearchHit;
+
+        let re = Regex::new(r"^(.+?):(\d+):(.*)$").unwrap();
+        output
+            .lines()
+            .filter_map(|line| {
+                let caps = re.captures(line.trim_end_matches('\r'))?;
+                Some(SearchHit {
+                    file: caps[1].to_string(),
+                    line_number: caps[2].parse().ok()?,
+                    line: caps[3].to_string(),
+                })
+            })
+            .collect()
+    }
+
     /// Format bytes to human-readable string (e.g., "8.5G")
     fn format_bytes(bytes: u64) -> String {
         const KB: u64 = 1024;
@@ -2218,6 +3290,115 @@ lrwxr-xr-x@  1 root  wheel    11 Jul 20 09:19:46 2025 etc -> private/etc"#;
         assert_eq!(etc.symlink_target.as_deref(), Some("private/etc"));
     }
 
+    #[test]
+    fn test_parse_du_bytes() {
+        // GNU du -b -d 1, with the requested path last
+        let output = "__DU_UNIT__:1\n\
+                      1048576\t/var/log/journal\n\
+                      2048\t/var/log/apt\n\
+                      1050624\t/var/log\n";
+
+        let entries = OutputParser::parse_du(output).unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].path, "/var/log/journal");
+        assert_eq!(entries[0].size, 1_048_576);
+        assert_eq!(entries[2].path, "/var/log");
+        assert_eq!(entries[2].size, 1_050_624);
+    }
+
+    #[test]
+    fn test_parse_du_kilobytes() {
+        // BSD du -k -s on macOS; paths may contain spaces
+        let output = "__DU_UNIT__:1024\n12\t/Users/me/My Documents\n";
+
+        let entries = OutputParser::parse_du(output).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].path, "/Users/me/My Documents");
+        assert_eq!(entries[0].size, 12 * 1024);
+    }
+
+    #[test]
+    fn test_parse_du_skips_noise_and_requires_marker() {
+        let output = "\n__DU_UNIT__:1\ngarbage line\n4096\t/srv\n";
+        let entries = OutputParser::parse_du(output).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].size, 4096);
+
+        assert!(OutputParser::parse_du("4096\t/srv\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_file_range() {
+        // base64 output wrapped over two lines
+        let output = "__FILE_SIZE__:123456\naMOp\nbGxvCg==\n";
+        let (size, bytes) = OutputParser::parse_file_range(output).unwrap();
+        assert_eq!(size, 123456);
+        assert_eq!(bytes, "héllo\n".as_bytes());
+
+        // Offset past EOF: nothing read
+        let (size, bytes) = OutputParser::parse_file_range("__FILE_SIZE__:10\n").unwrap();
+        assert_eq!(size, 10);
+        assert!(bytes.is_empty());
+
+        assert!(OutputParser::parse_file_range("aGVsbG8=\n").is_err());
+        assert_eq!(OutputParser::parse_file_size(" 42\n").unwrap(), 42);
+        assert!(OutputParser::parse_file_size("stat: cannot stat").is_err());
+    }
+
+    #[test]
+    fn test_parse_missing_tool() {
+        assert_eq!(
+            OutputParser::parse_missing_tool("warning\n__MISSING_TOOL__:unzip\n"),
+            Some("unzip".to_string())
+        );
+        assert_eq!(
+            OutputParser::parse_missing_tool("tar: Error exit delayed"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_stat_entry() {
+        let output = "drwxr-x--- 2 www-data staff 4096 2024-01-15 10:30 /srv/app\n";
+        let entry = OutputParser::parse_stat_entry(output, "/srv/app").unwrap();
+        assert_eq!(entry.name, "app");
+        assert_eq!(entry.path, "/srv/app");
+        assert_eq!(entry.permissions, "rwxr-x---");
+        assert_eq!(entry.owner, "www-data");
+        assert_eq!(entry.group, "staff");
+
+        let output = "-rw------- 1 root root 12 2024-01-15 10:30 /root/.env\n";
+        let entry = OutputParser::parse_stat_entry(output, "/root/.env").unwrap();
+        assert_eq!(entry.name, ".env");
+        assert!(entry.is_hidden);
+
+        assert!(OutputParser::parse_stat_entry("", "/missing").is_err());
+    }
+
+    #[test]
+    fn test_parse_grep_output() {
+        let output = "/etc/nginx/nginx.conf:12:    listen 80;\n\
+                      /srv/app/config.yml:3:url: http://localhost:8080\n\
+                      /srv/backup:latest/app.log:7:ERROR: timeout: 30s\r\n\
+                      Binary file /srv/blob matches\n";
+
+        let hits = OutputParser::parse_grep_output(output);
+        assert_eq!(hits.len(), 3);
+
+        assert_eq!(hits[0].file, "/etc/nginx/nginx.conf");
+        assert_eq!(hits[0].line_number, 12);
+        assert_eq!(hits[0].line, "    listen 80;");
+
+        // Colons in the content are kept
+        assert_eq!(hits[1].file, "/srv/app/config.yml");
+        assert_eq!(hits[1].line, "url: http://localhost:8080");
+
+        // Colons in the file name are kept when not followed by digits and a colon
+        assert_eq!(hits[2].file, "/srv/backup:latest/app.log");
+        assert_eq!(hits[2].line_number, 7);
+        assert_eq!(hits[2].line, "ERROR: timeout: 30s");
+    }
+
     #[test]
     fn test_parse_directory_listing_gnu() {
         // GNU ls --time-style=long-iso format
@@ -2274,6 +3455,179 @@ lrwxrwxrwx  1 user group     6 2024-01-15 10:30 link -> target"#;
         assert!(ports.is_empty());
     }
 
+    #[test]
+    fn test_parse_date_value_epochs() {
+        let expected = DateTime::parse_from_rfc3339("2024-05-29T20:01:35Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(
+            OutputParser::parse_date_value(&serde_json::json!(1717012895)),
+            Some(expected)
+        );
+        assert_eq!(
+            OutputParser::parse_date_value(&serde_json::json!(1717012895123456789i64)),
+            Some(expected + chrono::Duration::nanoseconds(123456789))
+        );
+        assert_eq!(
+            OutputParser::parse_date_value(&serde_json::json!("2024-05-29T20:01:35Z")),
+            Some(expected)
+        );
+        assert_eq!(OutputParser::parse_date_value(&Value::Null), None);
+
+        // `podman ps --format json`: relative CreatedAt, epoch Created
+        let podman = serde_json::json!({
+            "Id": "abc123",
+            "Names": ["web"],
+            "Image": "nginx",
+            "State": "running",
+            "CreatedAt": "2 hours ago",
+            "Created": 1717012895
+        });
+        let container =
+            OutputParser::parse_container_from_json(&podman, ContainerRuntime::Podman, "sys-1")
+                .unwrap();
+        assert_eq!(container.created_at, expected);
+    }
+
+    #[test]
+    fn test_parse_apple_container_created() {
+        // `container ls --all --format json`, trimmed
+        let output = r#"[{
+            "status": "running",
+            "createdAt": "2025-06-12T09:14:03Z",
+            "configuration": {
+                "id": "web",
+                "hostname": "web",
+                "image": { "reference": "docker.io/library/nginx:latest" },
+                "labels": { "app": "web" },
+                "mounts": [],
+                "publishedPorts": [],
+                "initProcess": {
+                    "executable": "/docker-entrypoint.sh",
+                    "arguments": ["nginx", "-g", "daemon off;"],
+                    "workingDirectory": "/"
+                }
+            },
+            "networks": [{ "network": "default", "address": "192.168.64.3/24" }]
+        }, {
+            "status": "stopped",
+            "configuration": { "id": "old", "image": { "reference": "alpine:latest" } }
+        }]"#;
+        let expected = DateTime::parse_from_rfc3339("2025-06-12T09:14:03Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let containers =
+            OutputParser::parse_container_list(output, ContainerRuntime::Apple, "sys-1").unwrap();
+        assert_eq!(containers[0].created_at, expected);
+        // Without a creation time the container still parses, dated now
+        assert!(containers[1].created_at > expected);
+
+        let details =
+            OutputParser::parse_container_details(output, ContainerRuntime::Apple).unwrap();
+        assert_eq!(details.created_at, Some(expected));
+        assert_eq!(details.labels["app"], "web");
+    }
+
+    #[test]
+    fn test_container_uptime() {
+        let now = DateTime::parse_from_rfc3339("2024-06-10T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let state = ContainerState {
+            exit_code: 137,
+            started_at: Some("2024-06-07T11:00:00.123456789Z".to_string()),
+            // Podman reports offsets rather than UTC
+            finished_at: Some("2024-06-10T13:55:00+02:00".to_string()),
+            ..Default::default()
+        };
+
+        let uptime = |status| OutputParser::container_uptime(status, &state, now);
+        assert_eq!(
+            uptime(ContainerStatus::Running).as_deref(),
+            Some("Up 3 days")
+        );
+        assert_eq!(
+            uptime(ContainerStatus::Paused).as_deref(),
+            Some("Up 3 days (Paused)")
+        );
+        assert_eq!(
+            uptime(ContainerStatus::Exited).as_deref(),
+            Some("Exited (137) 5 minutes ago")
+        );
+        assert_eq!(
+            uptime(ContainerStatus::Restarting).as_deref(),
+            Some("Restarting (137) 5 minutes ago")
+        );
+        assert_eq!(uptime(ContainerStatus::Created), None);
+
+        // Never started: inspect reports the zero time, which the parser drops
+        let created = ContainerState::default();
+        assert_eq!(
+            OutputParser::container_uptime(ContainerStatus::Running, &created, now),
+            None
+        );
+    }
+
+    #[test]
+    fn test_human_duration() {
+        let human = |secs| OutputParser::human_duration(chrono::Duration::seconds(secs));
+        assert_eq!(human(0), "Less than a second");
+        assert_eq!(human(1), "1 second");
+        assert_eq!(human(45), "45 seconds");
+        assert_eq!(human(90), "About a minute");
+        assert_eq!(human(59 * 60), "59 minutes");
+        assert_eq!(human(80 * 60), "About an hour");
+        assert_eq!(human(47 * 3600), "47 hours");
+        assert_eq!(human(10 * 86400), "10 days");
+        assert_eq!(human(30 * 86400), "4 weeks");
+        assert_eq!(human(200 * 86400), "6 months");
+        assert_eq!(human(3 * 365 * 86400), "3 years");
+    }
+
+    #[test]
+    fn test_parse_port_bindings_from_inspect_dual_stack() {
+        // Trimmed from `docker inspect` of `docker run -p 8080:80 -p 5353:53/udp`
+        let output = r#"[{
+            "Id": "3f4e8a9b2c1d5e6f7a8b9c0d1e2f3a4b5c6d7e8f9a0b1c2d3e4f5a6b7c8d9e0f",
+            "HostConfig": { "NetworkMode": "bridge" },
+            "NetworkSettings": {
+                "Ports": {
+                    "80/tcp": [
+                        { "HostIp": "::", "HostPort": "8080" },
+                        { "HostIp": "0.0.0.0", "HostPort": "8080" }
+                    ],
+                    "53/udp": [
+                        { "HostIp": "0.0.0.0", "HostPort": "5353" },
+                        { "HostIp": "::", "HostPort": "5353" }
+                    ],
+                    "443/tcp": null
+                }
+            }
+        }]"#;
+
+        let result = OutputParser::parse_port_bindings_from_inspect(output);
+        let ports = &result["3f4e8a9b2c1d5e6f7a8b9c0d1e2f3a4b5c6d7e8f9a0b1c2d3e4f5a6b7c8d9e0f"];
+        assert_eq!(ports.len(), 2);
+        assert_eq!(
+            (
+                ports[0].container_port,
+                ports[0].host_port,
+                ports[0].protocol.as_str()
+            ),
+            (53, 5353, "udp")
+        );
+        assert_eq!(
+            (
+                ports[1].container_port,
+                ports[1].host_port,
+                ports[1].protocol.as_str()
+            ),
+            (80, 8080, "tcp")
+        );
+        assert!(ports.iter().all(|p| p.host_ip == "0.0.0.0"));
+    }
+
     #[test]
     fn test_parse_size_string_variants() {
         assert_eq!(OutputParser::parse_size_string("500B"), Some(500));
@@ -2288,6 +3642,20 @@ lrwxrwxrwx  1 user group     6 2024-01-15 10:30 link -> target"#;
         assert_eq!(OutputParser::parse_size_string("100Mb"), Some(104857600));
     }
 
+    #[test]
+    fn test_parse_size_string_binary_and_spaced_units() {
+        assert_eq!(OutputParser::parse_size_string("1.5GiB"), Some(1610612736));
+        assert_eq!(OutputParser::parse_size_string("512MiB"), Some(536870912));
+        assert_eq!(OutputParser::parse_size_string("3.2 MB"), Some(3355443));
+        assert_eq!(OutputParser::parse_size_string("2TiB"), Some(2199023255552));
+        assert_eq!(OutputParser::parse_size_string("64kB"), Some(65536));
+        assert_eq!(OutputParser::parse_size_string("4KiB"), Some(4096));
+        assert_eq!(OutputParser::parse_size_string("10k"), Some(10240));
+        assert_eq!(OutputParser::parse_size_string("2m"), Some(2097152));
+        assert_eq!(OutputParser::parse_size_string("1g"), Some(1073741824));
+        assert_eq!(OutputParser::parse_size_string("5iB"), None);
+    }
+
     #[test]
     fn test_parse_runtime_available_docker() {
         assert!(OutputParser::parse_runtime_available(
@@ -2312,6 +3680,51 @@ lrwxrwxrwx  1 user group     6 2024-01-15 10:30 link -> target"#;
         ));
     }
 
+    #[test]
+    fn test_parse_runtime_available_nerdctl() {
+        let output = "Client:\n Version:\tv1.7.6\n OS/Arch:\tlinux/amd64\n Git commit:\t845e989f69d25b420b6f3ebd7fb7c8b2b6b4fbe8\n buildctl:\n  Version:\tv0.13.1\n\nServer:\n containerd:\n  Version:\tv1.7.16\n  GitCommit:\t83031836b2cf55637d7abf847b17134c51b38e53\n runc:\n  Version:\t1.1.12\n";
+        assert!(OutputParser::parse_runtime_available(output, ContainerRuntime::Nerdctl));
+        assert!(!OutputParser::parse_runtime_available(
+            "command not found",
+            ContainerRuntime::Nerdctl
+        ));
+    }
+
+    #[test]
+    fn test_parse_rootless() {
+        assert!(OutputParser::parse_rootless("true\n"));
+        assert!(!OutputParser::parse_rootless("false\n"));
+        assert!(OutputParser::parse_rootless(
+            r#"["name=seccomp,profile=builtin","name=rootless","name=cgroupns"]"#
+        ));
+        assert!(!OutputParser::parse_rootless(
+            r#"["name=apparmor","name=seccomp,profile=builtin","name=cgroupns"]"#
+        ));
+    }
+
+    #[test]
+    fn test_parse_nerdctl_container_list() {
+        // `nerdctl ps -a --no-trunc --format json` emits Docker-style JSON lines,
+        // with Labels as an object rather than a string
+        let output = r#"{"Command":"\"/docker-entrypoint.sh nginx -g 'daemon off;'\"","CreatedAt":"2024-05-20 10:12:33 +0000 UTC","ID":"3f2a1c9e8b7d6a5f4e3d2c1b0a9f8e7d6c5b4a3f2e1d0c9b8a7f6e5d4c3b2a1f","Image":"docker.io/library/nginx:alpine","Platform":"linux/amd64","Names":"web","Ports":"0.0.0.0:8080->80/tcp","Status":"Up","Runtime":"io.containerd.runc.v2","Size":"","Labels":{"nerdctl/name":"web"}}
+{"Command":"\"redis-server\"","CreatedAt":"2024-05-19 08:01:02 +0000 UTC","ID":"9a8b7c6d5e4f3a2b1c0d9e8f7a6b5c4d3e2f1a0b9c8d7e6f5a4b3c2d1e0f9a8b","Image":"docker.io/library/redis:7","Platform":"linux/amd64","Names":"cache","Ports":"","Status":"Exited (0) 2 hours ago","Runtime":"io.containerd.runc.v2","Size":"","Labels":{}}"#;
+
+        let containers =
+            OutputParser::parse_container_list(output, ContainerRuntime::Nerdctl, "sys-1").unwrap();
+        assert_eq!(containers.len(), 2);
+
+        let web = &containers[0];
+        assert_eq!(web.name, "web");
+        assert_eq!(web.image, "docker.io/library/nginx:alpine");
+        assert_eq!(web.runtime, ContainerRuntime::Nerdctl);
+        assert_eq!(web.status, ContainerStatus::Running);
+        assert_eq!(web.ports.len(), 1);
+        assert_eq!(web.ports[0].host_port, 8080);
+
+        assert_eq!(containers[1].name, "cache");
+        assert_eq!(containers[1].status, ContainerStatus::Exited);
+    }
+
     #[test]
     fn test_format_human_bytes() {
         assert_eq!(OutputParser::format_bytes(500), "500B");
@@ -2342,4 +3755,850 @@ lrwxrwxrwx  1 user group     6 2024-01-15 10:30 link -> target"#;
         let visible = entries.iter().find(|e| e.name == "visible").unwrap();
         assert!(!visible.is_hidden);
     }
+
+    #[test]
+    fn test_parse_live_metrics_disk_io_first_sample_has_no_rate() {
+        let output = "===DISKIO===\n   8       0 sda 100 0 2000 0 50 0 4000 0 0 0 0\n===END===";
+        let (metrics, counters) = OutputParser::parse_live_metrics(output, "sys-1", None);
+        assert!(metrics.disk_read_bytes_per_sec.is_none());
+        assert!(metrics.disk_write_bytes_per_sec.is_none());
+        assert_eq!(counters.disk_read_bytes, Some(2000 * 512));
+        assert_eq!(counters.disk_write_bytes, Some(4000 * 512));
+        assert_eq!(counters.disk_total_bytes, Some(6000 * 512));
+    }
+
+    #[test]
+    fn test_parse_live_metrics_disk_io_rate_from_previous() {
+        let output = "===DISKIO===\n   8       0 sda 100 0 4000 0 50 0 8000 0 0 0 0\n===END===";
+        let previous = MetricsCounters {
+            timestamp: chrono::Utc::now().timestamp_millis() - 2000,
+            disk_read_bytes: Some(2000 * 512),
+            disk_write_bytes: Some(4000 * 512),
+            disk_total_bytes: Some(6000 * 512),
+            ..Default::default()
+        };
+        let (metrics, counters) =
+            OutputParser::parse_live_metrics(output, "sys-1", Some(&previous));
+        // Rates follow from the sample's own timestamp, however long parsing took
+        let elapsed_ms = (counters.timestamp - previous.timestamp) as u64;
+        let rate = |bytes: u64| Some(bytes * 1000 / elapsed_ms);
+        assert_eq!(metrics.disk_read_bytes_per_sec, rate(2000 * 512));
+        assert_eq!(metrics.disk_write_bytes_per_sec, rate(4000 * 512));
+        assert_eq!(metrics.disk_io_bytes_per_sec, rate(6000 * 512));
+    }
+
+    #[test]
+    fn test_parse_disk_io_counters_skips_partitions_and_virtual_devices() {
+        let section = "\
+   8       0 sda 10 0 100 0 10 0 200 0 0 0 0
+   8       1 sda1 10 0 90 0 10 0 180 0 0 0 0
+ 259       0 nvme0n1 10 0 50 0 10 0 60 0 0 0 0
+ 259       1 nvme0n1p1 10 0 40 0 10 0 50 0 0 0 0
+   7       0 loop0 10 0 999 0 10 0 999 0 0 0 0
+ 253       0 dm-0 10 0 999 0 10 0 999 0 0 0 0";
+        let (read, write, total) = OutputParser::parse_disk_io_counters(section);
+        assert_eq!(read, Some(150 * 512));
+        assert_eq!(write, Some(260 * 512));
+        assert_eq!(total, Some(410 * 512));
+    }
+
+    #[test]
+    fn test_parse_disk_io_counters_macos_iostat() {
+        let section = "\
+              disk0               disk4
+    KB/t  xfrs   MB     KB/t  xfrs   MB
+   25.58 3489287 100.00    12.52   1003 50.00";
+        let (read, write, total) = OutputParser::parse_disk_io_counters(section);
+        assert_eq!(total, Some(150 * 1024 * 1024));
+        // iostat doesn't split reads from writes, so neither is guessed
+        assert!(read.is_none());
+        assert!(write.is_none());
+    }
+
+    #[test]
+    fn test_parse_disk_io_counters_unreadable() {
+        assert_eq!(OutputParser::parse_disk_io_counters(""), (None, None, None));
+        let (metrics, counters) =
+            OutputParser::parse_live_metrics("===DISKIO===\n===END===", "sys-1", None);
+        assert!(metrics.disk_read_bytes_per_sec.is_none());
+        assert!(counters.disk_read_bytes.is_none());
+    }
+
+    #[test]
+    fn test_parse_network_counters_linux_skips_internal_interfaces() {
+        let section = "\
+Inter-|   Receive                                                |  Transmit
+ face |bytes    packets errs drop fifo frame compressed multicast|bytes    packets errs drop fifo colls carrier compressed
+    lo: 9999999    100    0    0    0     0          0         0  9999999    100    0    0    0     0       0          0
+  eth0: 1000000   2000    0    0    0     0          0         0   500000   1000    0    0    0     0       0          0
+  eth1:  200000    300    0    0    0     0          0         0   100000    200    0    0    0     0       0          0
+docker0:  777777    10    0    0    0     0          0         0   777777     10    0    0    0     0       0          0
+vethab12:  55555    10    0    0    0     0          0         0    55555     10    0    0    0     0       0          0";
+        let (rx, tx) = OutputParser::parse_network_counters(section);
+        assert_eq!(rx, Some(1_200_000));
+        assert_eq!(tx, Some(600_000));
+    }
+
+    #[test]
+    fn test_parse_network_counters_macos_netstat() {
+        let section = "\
+Name       Mtu   Network       Address            Ipkts Ierrs     Ibytes    Opkts Oerrs     Obytes  Coll
+lo0        16384 <Link#1>                        12345     0    9999999    12345     0    9999999     0
+lo0        16384 127           localhost         12345     -    9999999    12345     -    9999999     -
+en0        1500  <Link#4>    a4:83:e7:00:00:01  54321     0    3000000    43210     0    1500000     0
+en0        1500  192.168.1     192.168.1.10     54321     -    3000000    43210     -    1500000     -";
+        let (rx, tx) = OutputParser::parse_network_counters(section);
+        assert_eq!(rx, Some(3_000_000));
+        assert_eq!(tx, Some(1_500_000));
+    }
+
+    #[test]
+    fn test_parse_live_metrics_network_rate_from_previous() {
+        let output = "===NET===\n  eth0: 3000000 0 0 0 0 0 0 0 1500000 0 0 0 0 0 0 0\n===END===";
+        let (first, counters) = OutputParser::parse_live_metrics(output, "sys-1", None);
+        assert!(first.net_rx_bytes_per_sec.is_none());
+        assert_eq!(counters.net_rx_bytes, Some(3_000_000));
+
+        let previous = MetricsCounters {
+            timestamp: counters.timestamp - 1000,
+            net_rx_bytes: Some(2_000_000),
+            net_tx_bytes: Some(1_000_000),
+            ..Default::default()
+        };
+        let (metrics, _) = OutputParser::parse_live_metrics(output, "sys-1", Some(&previous));
+        let rx = metrics.net_rx_bytes_per_sec.unwrap();
+        let tx = metrics.net_tx_bytes_per_sec.unwrap();
+        assert!(rx <= 1_000_000 && rx > 900_000, "rx rate {}", rx);
+        assert!(tx <= 500_000 && tx > 450_000, "tx rate {}", tx);
+    }
+
+    #[test]
+    fn test_parse_container_stats_docker_ndjson() {
+        let output = r#"{"BlockIO":"4.1MB / 0B","CPUPerc":"1.25%","Container":"abc123","ID":"abc123","MemPerc":"0.12%","MemUsage":"9.5MiB / 7.7GiB","Name":"web","NetIO":"1.2kB / 648B","PIDs":"3"}
+{"BlockIO":"0B / 0B","CPUPerc":"0.00%","Container":"def456","ID":"def456","MemPerc":"0.00%","MemUsage":"0B / 0B","Name":"db","NetIO":"0B / 0B","PIDs":"0"}"#;
+
+        let stats = OutputParser::parse_container_stats(output, "sys-1").unwrap();
+        assert_eq!(stats.len(), 2);
+
+        let web = &stats[0];
+        assert_eq!(web.container_id, "abc123");
+        assert_eq!(web.name, "web");
+        assert_eq!(web.system_id, "sys-1");
+        assert!((web.cpu_percent - 1.25).abs() < f32::EPSILON);
+        assert_eq!(web.memory_usage_bytes, Some(9_961_472));
+        assert_eq!(web.net_rx_bytes, Some(1200));
+        assert_eq!(web.net_tx_bytes, Some(648));
+        assert_eq!(web.block_read_bytes, Some(4_100_000));
+        assert_eq!(web.block_write_bytes, Some(0));
+        assert_eq!(web.pids, Some(3));
+    }
+
+    #[test]
+    fn test_parse_container_stats_podman_array() {
+        let output = r#"[{"id":"abc123","name":"web","cpu_percent":"12.50%","mem_usage":"100MB / 2GB","mem_percent":"5.00%","net_io":"1kB / 2kB","block_io":"3MB / 4MB","pids":"7"}]"#;
+
+        let stats = OutputParser::parse_container_stats(output, "sys-1").unwrap();
+        assert_eq!(stats.len(), 1);
+        assert!((stats[0].cpu_percent - 12.5).abs() < f32::EPSILON);
+        assert!((stats[0].memory_percent - 5.0).abs() < f32::EPSILON);
+        assert_eq!(stats[0].memory_limit_bytes, Some(2_000_000_000));
+        assert_eq!(stats[0].net_tx_bytes, Some(2000));
+        assert_eq!(stats[0].block_write_bytes, Some(4_000_000));
+        assert_eq!(stats[0].pids, Some(7));
+    }
+
+    #[test]
+    fn test_parse_container_stats_captured_docker_output() {
+        // `docker stats --no-stream --format '{{json .}}'` (Docker 26) with one container
+        // mid-restart, which Docker reports with "--" placeholders
+        let output = r#"{"BlockIO":"30.2MB / 4.1kB","CPUPerc":"0.31%","Container":"5b1d3e0c2f4a","ID":"5b1d3e0c2f4a","MemPerc":"1.46%","MemUsage":"114.5MiB / 7.653GiB","Name":"postgres","NetIO":"12.6kB / 8.94kB","PIDs":"7"}
+{"BlockIO":"--","CPUPerc":"--","Container":"9e07c1f8d2b6","ID":"9e07c1f8d2b6","MemPerc":"--","MemUsage":"-- / --","Name":"flaky-worker","NetIO":"--","PIDs":"--"}
+"#;
+
+        let stats = OutputParser::parse_container_stats(output, "sys-1").unwrap();
+        assert_eq!(stats.len(), 2);
+
+        let postgres = &stats[0];
+        assert_eq!(postgres.name, "postgres");
+        assert!((postgres.memory_percent - 1.46).abs() < 0.001);
+        assert_eq!(postgres.memory_usage_bytes, Some(120_061_952));
+        assert_eq!(postgres.memory_limit_bytes, Some(8_217_346_179));
+        assert_eq!(postgres.net_rx_bytes, Some(12_600));
+        assert_eq!(postgres.net_tx_bytes, Some(8_940));
+        assert_eq!(postgres.block_read_bytes, Some(30_200_000));
+        assert_eq!(postgres.block_write_bytes, Some(4_100));
+        assert_eq!(postgres.pids, Some(7));
+
+        let restarting = &stats[1];
+        assert_eq!(restarting.container_id, "9e07c1f8d2b6");
+        assert_eq!(restarting.cpu_percent, 0.0);
+        assert_eq!(restarting.memory_usage_bytes, None);
+        assert_eq!(restarting.memory_limit_bytes, None);
+        assert_eq!(restarting.net_rx_bytes, None);
+        assert_eq!(restarting.pids, None);
+    }
+
+    #[test]
+    fn test_parse_container_stats_captured_podman_output() {
+        // `podman stats --no-stream --format json` (Podman 4.9) pretty-prints an array
+        // and includes extra CPU timing fields
+        let output = r#"[
+ {
+  "id": "e6d9b8a5c9a8",
+  "name": "web",
+  "cpu_time": "41.587ms",
+  "cpu_percent": "0.04%",
+  "avg_cpu": "0.04%",
+  "mem_usage": "3.219MB / 8.148GB",
+  "mem_percent": "0.04%",
+  "net_io": "796B / 1.012kB",
+  "block_io": "0B / 0B",
+  "pids": "2"
+ },
+ {
+  "id": "0c3f4b2a1d77",
+  "name": "cache",
+  "cpu_time": "1.204s",
+  "cpu_percent": "2.17%",
+  "avg_cpu": "1.02%",
+  "mem_usage": "12.58MB / 536.9MB",
+  "mem_percent": "2.34%",
+  "net_io": "1.5MB / 220.3kB",
+  "block_io": "4.096kB / 0B",
+  "pids": "5"
+ }
+]
+"#;
+
+        let stats = OutputParser::parse_container_stats(output, "sys-2").unwrap();
+        assert_eq!(stats.len(), 2);
+
+        let web = &stats[0];
+        assert_eq!(web.container_id, "e6d9b8a5c9a8");
+        assert!((web.cpu_percent - 0.04).abs() < 0.001);
+        assert_eq!(web.memory_usage_bytes, Some(3_219_000));
+        assert_eq!(web.memory_limit_bytes, Some(8_148_000_000));
+        assert_eq!(web.net_rx_bytes, Some(796));
+        assert_eq!(web.net_tx_bytes, Some(1_012));
+
+        let cache = &stats[1];
+        assert_eq!(cache.name, "cache");
+        assert_eq!(cache.system_id, "sys-2");
+        assert!((cache.cpu_percent - 2.17).abs() < 0.001);
+        assert_eq!(cache.memory_limit_bytes, Some(536_900_000));
+        assert_eq!(cache.net_rx_bytes, Some(1_500_000));
+        assert_eq!(cache.block_read_bytes, Some(4_096));
+        assert_eq!(cache.pids, Some(5));
+    }
+
+    #[test]
+    fn test_parse_stats_size_units() {
+        assert_eq!(OutputParser::parse_stats_size("1.5GiB"), Some(1_610_612_736));
+        assert_eq!(OutputParser::parse_stats_size("3.2MB"), Some(3_200_000));
+        assert_eq!(OutputParser::parse_stats_size(" 512 KiB "), Some(524_288));
+        assert_eq!(OutputParser::parse_stats_size("0B"), Some(0));
+        assert_eq!(OutputParser::parse_stats_size("--"), None);
+        assert_eq!(OutputParser::parse_stats_size("12 parsecs"), None);
+    }
+
+    #[test]
+    fn test_parse_container_stats_empty() {
+        assert!(OutputParser::parse_container_stats("", "sys-1").unwrap().is_empty());
+        assert!(OutputParser::parse_container_stats("  \n", "sys-1").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_live_metrics_windows() {
+        let output = "===CPU===\r\nLoadPercentage=25\r\nLogicalProcessors=8\r\n\
+===MEM===\r\nTotalVisibleMemorySize=16777216\r\nFreePhysicalMemory=4194304\r\n\
+===SWAP===\r\nAllocatedBaseSize=4096\r\nCurrentUsage=1024\r\n===END===";
+        let (metrics, _) = OutputParser::parse_live_metrics(output, "sys-1", None);
+
+        assert_eq!(metrics.cpu_usage_percent, 25.0);
+        assert_eq!(metrics.memory_usage_percent, 75.0);
+        assert_eq!(metrics.memory_total.as_deref(), Some("16.0G"));
+        assert_eq!(metrics.swap_usage_percent, Some(25.0));
+        assert_eq!(metrics.load_average, Some([2.0, 2.0, 2.0]));
+    }
+
+    #[test]
+    fn test_parse_live_metrics_windows_comma_decimal_locale() {
+        let output = "===CPU===\nLoadPercentage=12,5\nLogicalProcessors=4\n\
+===MEM===\nTotalVisibleMemorySize=8.388.608\nFreePhysicalMemory=2 097 152\n\
+===SWAP===\nAllocatedBaseSize=0\nCurrentUsage=0\n===END===";
+        let (metrics, _) = OutputParser::parse_live_metrics(output, "sys-1", None);
+
+        assert_eq!(metrics.cpu_usage_percent, 12.5);
+        assert_eq!(metrics.memory_usage_percent, 75.0);
+        assert_eq!(metrics.swap_usage_percent, Some(0.0));
+        assert_eq!(metrics.load_average, Some([0.5, 0.5, 0.5]));
+    }
+
+    #[test]
+    fn test_parse_windows_metric_separators() {
+        let cases = [
+            ("LoadPercentage=12.5", Some(("LoadPercentage", 12.5))),
+            (" Load = 1,234.5 ", Some(("Load", 1234.5))),
+            ("Load=1.234,5", Some(("Load", 1234.5))),
+            ("Load=1,234,567", Some(("Load", 1234567.0))),
+            ("Load=1\u{a0}234", Some(("Load", 1234.0))),
+            ("Load=", None),
+            ("cpu  1 2 3", None),
+        ];
+        for (line, expected) in cases {
+            assert_eq!(OutputParser::parse_windows_metric(line), expected, "{line}");
+        }
+    }
+
+    #[test]
+    fn test_parse_live_metrics_disk_usage() {
+        let output = "===DISK===\n/dev/sda1  102400000  46080000  56320000  45% /\n===END===";
+        let (metrics, _) = OutputParser::parse_live_metrics(output, "sys-1", None);
+        assert_eq!(metrics.disk_usage_percent, Some(45));
+
+        let (metrics, _) = OutputParser::parse_live_metrics("===DISK===\n===END===", "sys-1", None);
+        assert!(metrics.disk_usage_percent.is_none());
+    }
+
+    #[test]
+    fn test_parse_disk_usage_joins_wrapped_lines() {
+        let output = "Filesystem      Size  Used Avail Use% Mounted on
+/dev/mapper/ubuntu--vg-ubuntu--lv
+                 98G   61G   33G  65% /
+/dev/mapper/data--vg-storage--volume--01
+                1.8T  1.2T  550G  69% /srv/data
+tmpfs           3.9G     0  3.9G   0% /dev/shm";
+
+        assert_eq!(OutputParser::parse_disk_usage(output, "/"), Some(65));
+        assert_eq!(
+            OutputParser::parse_disk_usage(output, "/srv/data"),
+            Some(69)
+        );
+        assert_eq!(OutputParser::parse_disk_usage(output, "/dev/shm"), Some(0));
+    }
+
+    #[test]
+    fn test_parse_disk_usage_prefers_requested_mount() {
+        // The first percentage is not the root filesystem
+        let output = "Filesystem 1024-blocks Used Available Capacity Mounted on
+/dev/sdb1 1024000 972800 51200 95% /boot
+/dev/sda1 102400000 46080000 56320000 45% /";
+        assert_eq!(OutputParser::parse_disk_usage(output, "/"), Some(45));
+
+        // Mount points containing spaces
+        let output = "/dev/disk3s1 500G 100G 400G 20% /Volumes/My Disk";
+        assert_eq!(
+            OutputParser::parse_disk_usage(output, "/Volumes/My Disk"),
+            Some(20)
+        );
+
+        // Unknown mount (e.g. df run on a subdirectory) and Windows output fall back
+        // to the only row
+        assert_eq!(OutputParser::parse_disk_usage(output, "/data"), Some(20));
+        assert_eq!(OutputParser::parse_disk_usage("476G 58%", "/"), Some(58));
+        assert_eq!(OutputParser::parse_disk_usage("", "/"), None);
+    }
+
+    #[test]
+    fn test_parse_extended_system_info_disk_for_mount_point() {
+        let output = "===USERNAME===\nadmin\n===DISK===\nFilesystem Size Used Avail Use% Mounted on
+/dev/mapper/data--vg-storage--volume--01
+                1.8T  1.2T  550G  69% /srv/data
+===UPTIME===\nup 2 days\n===END===";
+        let info = OutputParser::parse_extended_system_info(output, "/srv/data");
+        assert_eq!(info.username, "admin");
+        assert_eq!(info.disk_usage_percent, Some(69));
+    }
+
+    #[test]
+    fn test_parse_pod_list_podman_json() {
+        // Output of `podman pod ps --format json` (podman 4.9)
+        let output = r#"[
+     {
+          "Cgroup": "user.slice",
+          "Containers": [
+               {
+                    "Id": "3c5a0c1bd2b6e2d1d2c1f1a6f0e1c7c5b7d9f0e2a4b6c8d0e2f4a6b8c0d2e4f6",
+                    "Names": "8e7f0c9d1a2b-infra",
+                    "Status": "running"
+               },
+               {
+                    "Id": "9b1d7e3f5a7c9e1b3d5f7a9c1e3b5d7f9a1c3e5b7d9f1a3c5e7b9d1f3a5c7e9b",
+                    "Names": "webapp-nginx",
+                    "Status": "running"
+               },
+               {
+                    "Id": "1f3e5d7c9b1a3f5e7d9c1b3a5f7e9d1c3b5a7f9e1d3c5b7a9f1e3d5c7b9a1f3e",
+                    "Names": "webapp-worker",
+                    "Status": "exited"
+               }
+          ],
+          "Created": "2024-03-11T09:15:42.123456789+01:00",
+          "Id": "8e7f0c9d1a2b3c4d5e6f7a8b9c0d1e2f3a4b5c6d7e8f9a0b1c2d3e4f5a6b7c8d",
+          "InfraId": "3c5a0c1bd2b6e2d1d2c1f1a6f0e1c7c5b7d9f0e2a4b6c8d0e2f4a6b8c0d2e4f6",
+          "Name": "webapp",
+          "Namespace": "",
+          "Networks": [
+               "podman"
+          ],
+          "Status": "Degraded",
+          "Labels": {}
+     },
+     {
+          "Cgroup": "user.slice",
+          "Containers": [],
+          "Created": "2024-03-12T18:02:07.5+00:00",
+          "Id": "c0ffee0123456789abcdef0123456789abcdef0123456789abcdef0123456789",
+          "InfraId": "",
+          "Name": "empty-pod",
+          "Namespace": "",
+          "Networks": [],
+          "Status": "Created",
+          "Labels": {
+               "app": "demo"
+          }
+     }
+]"#;
+
+        let pods = OutputParser::parse_pod_list(output, "sys-1").unwrap();
+        assert_eq!(pods.len(), 2);
+
+        assert_eq!(pods[0].name, "webapp");
+        assert!(pods[0].id.starts_with("8e7f0c9d1a2b"));
+        assert_eq!(pods[0].status, PodStatus::Degraded);
+        assert_eq!(pods[0].container_count, 3);
+        assert!(pods[0].infra_id.as_deref().unwrap().starts_with("3c5a0c1b"));
+        assert_eq!(
+            pods[0].created_at.unwrap().to_rfc3339(),
+            "2024-03-11T08:15:42.123456789+00:00"
+        );
+        assert_eq!(pods[0].system_id.0, "sys-1");
+
+        assert_eq!(pods[1].status, PodStatus::Created);
+        assert_eq!(pods[1].container_count, 0);
+        assert!(pods[1].infra_id.is_none());
+    }
+
+    #[test]
+    fn test_parse_pod_list_empty() {
+        for output in ["", "null\n", "[]"] {
+            let pods = OutputParser::parse_pod_list(output, "sys-1").unwrap();
+            assert!(pods.is_empty(), "{output:?}");
+        }
+        assert!(OutputParser::parse_pod_list("Error: no such pod", "sys-1").is_err());
+    }
+
+    #[test]
+    fn test_parse_extended_system_info_nvidia_gpus() {
+        let output = "===USERNAME===\nml\n===GPU===\n\
+NVIDIA GeForce RTX 3090, 24576 MiB, 535.129.03\n\
+NVIDIA A100-SXM4-80GB, 81920 MiB, 535.129.03\n\
+Tesla T4, 15360 MiB, [N/A]\n\
+===UPTIME===\nup 3 days\n===END===";
+        let info = OutputParser::parse_extended_system_info(output, "/");
+
+        assert_eq!(
+            info.gpus,
+            vec![
+                GpuInfo {
+                    name: "NVIDIA GeForce RTX 3090".to_string(),
+                    memory_total: Some("24G".to_string()),
+                    driver: Some("535.129.03".to_string()),
+                },
+                GpuInfo {
+                    name: "NVIDIA A100-SXM4-80GB".to_string(),
+                    memory_total: Some("80G".to_string()),
+                    driver: Some("535.129.03".to_string()),
+                },
+                GpuInfo {
+                    name: "Tesla T4".to_string(),
+                    memory_total: Some("15G".to_string()),
+                    driver: None,
+                },
+            ]
+        );
+        assert_eq!(info.uptime.as_deref(), Some("3 days"));
+    }
+
+    #[test]
+    fn test_parse_extended_system_info_without_gpu() {
+        let outputs = [
+            "===USERNAME===\nme\n===END===",
+            "===GPU===\n===UPTIME===\nup 1 day",
+            // Driver not loaded: nvidia-smi prints an error instead of CSV
+            "===GPU===\nNVIDIA-SMI has failed because it couldn't communicate with the NVIDIA \
+driver. Make sure that the latest NVIDIA driver is installed and running.\n===END===",
+        ];
+        for output in outputs {
+            let info = OutputParser::parse_extended_system_info(output, "/");
+            assert!(info.gpus.is_empty(), "{output}");
+        }
+    }
+
+    #[test]
+    fn test_parse_gpu_info_macos_system_profiler() {
+        let output = "Graphics/Displays:
+
+    Intel UHD Graphics 630:
+
+      Chipset Model: Intel UHD Graphics 630
+      Type: GPU
+      Bus: Built-In
+      VRAM (Dynamic, Max): 1536 MB
+      Vendor: Intel
+
+    AMD Radeon Pro 5500M:
+
+      Chipset Model: AMD Radeon Pro 5500M
+      Type: GPU
+      Bus: PCIe
+      PCIe Lane Width: x16
+      VRAM (Total): 4 GB
+      Vendor: AMD (0x1002)
+      Metal Support: Metal 3
+
+    Apple M2 Pro:
+
+      Chipset Model: Apple M2 Pro
+      Type: GPU
+      Total Number of Cores: 19
+      Displays:
+        Color LCD:
+          Display Type: Built-in Liquid Retina XDR Display";
+
+        let gpus = OutputParser::parse_gpu_info(output);
+        let summary: Vec<(&str, Option<&str>)> = gpus
+            .iter()
+            .map(|g| (g.name.as_str(), g.memory_total.as_deref()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Intel UHD Graphics 630", Some("1536 MB")),
+                ("AMD Radeon Pro 5500M", Some("4 GB")),
+                ("Apple M2 Pro", None),
+            ]
+        );
+        assert!(gpus.iter().all(|g| g.driver.is_none()));
+    }
+
+    /// (layer, status, whole percent)
+    type PullUpdate = (Option<String>, String, Option<u32>);
+
+    fn pull_updates(output: &str) -> Vec<PullUpdate> {
+        output
+            .lines()
+            .filter_map(OutputParser::parse_pull_progress)
+            .map(|p| (p.layer_id, p.status, p.percent.map(|v| v.round() as u32)))
+            .collect()
+    }
+
+    fn update(layer: Option<&str>, status: &str, pct: Option<u32>) -> PullUpdate {
+        (layer.map(String::from), status.to_string(), pct)
+    }
+
+    #[test]
+    fn test_parse_pull_progress_docker() {
+        // `docker pull nginx:1.25` captured without a TTY, plus TTY progress lines
+        let output = "1.25: Pulling from library/nginx
+a2abf6c4d29d: Pulling fs layer
+a9edb18cadd1: Waiting
+a2abf6c4d29d: Downloading [=====>                                             ]  3.146MB/31.37MB
+a2abf6c4d29d: Verifying Checksum
+a2abf6c4d29d: Download complete
+a2abf6c4d29d: Extracting [==================================================>]  31.37MB/31.37MB
+a2abf6c4d29d: Pull complete
+589b7251471a: Already exists
+Status: Downloaded newer image for nginx:1.25
+";
+        let layer = Some("a2abf6c4d29d");
+        assert_eq!(
+            pull_updates(output),
+            vec![
+                update(None, "1.25: Pulling from library/nginx", None),
+                update(layer, "Pulling fs layer", None),
+                update(Some("a9edb18cadd1"), "Waiting", None),
+                update(layer, "Downloading", Some(10)),
+                update(layer, "Verifying Checksum", None),
+                update(layer, "Download complete", Some(100)),
+                update(layer, "Extracting", Some(100)),
+                update(layer, "Pull complete", Some(100)),
+                update(Some("589b7251471a"), "Already exists", Some(100)),
+                update(None, "Status: Downloaded newer image for nginx:1.25", None),
+            ]
+        );
+        assert_eq!(OutputParser::parse_pull_progress("  \r\n"), None);
+    }
+
+    #[test]
+    fn test_parse_pull_progress_podman() {
+        // `podman pull docker.io/library/alpine` (podman 4 without a TTY, podman 5 with one)
+        let output = "Trying to pull docker.io/library/alpine:latest...
+Copying blob sha256:4abcf20661432fb2d719aaf90656f55c287f8ca915dc1c92ec14ff61e67fbaf8
+Copying blob 4abcf2066143 [=========>----------------------------] 850.0KiB / 3.3MiB
+Copying blob 4abcf2066143 [======================>---------------] 2.0MiB / 3.3MiB | 1.1 MiB/s
+Copying blob 4abcf2066143 done   |
+Copying blob 9c704ecd0c69 skipped: already exists
+Copying config 05455a0888 done   |
+Writing manifest to image destination
+";
+        let layer = Some("4abcf2066143");
+        assert_eq!(
+            pull_updates(output),
+            vec![
+                update(
+                    None,
+                    "Trying to pull docker.io/library/alpine:latest...",
+                    None
+                ),
+                update(layer, "Pulling fs layer", None),
+                update(layer, "Downloading", Some(25)),
+                update(layer, "Downloading", Some(61)),
+                update(layer, "Pull complete", Some(100)),
+                update(Some("9c704ecd0c69"), "Already exists", Some(100)),
+                update(None, "Copying config 05455a0888 done   |", None),
+                update(None, "Writing manifest to image destination", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_event() {
+        let docker = r#"{"status":"start","id":"3f4e8a","from":"nginx","Type":"container","Action":"start","Actor":{"ID":"3f4e8a","Attributes":{"image":"nginx","name":"web"}},"scope":"local","time":1700000000,"timeNano":1700000000123456789}"#;
+        assert_eq!(
+            OutputParser::parse_event(docker),
+            Some(ContainerEvent {
+                event_type: "container".to_string(),
+                action: "start".to_string(),
+                actor_id: "3f4e8a".to_string(),
+                actor_name: Some("web".to_string()),
+                time: 1700000000,
+            })
+        );
+
+        let podman = r#"{"ID":"9b1c2d","Image":"docker.io/library/redis:latest","Name":"cache","Status":"died","Time":"2023-11-14T22:13:20Z","Type":"container","Attributes":{"image":"docker.io/library/redis:latest"}}"#;
+        assert_eq!(
+            OutputParser::parse_event(podman),
+            Some(ContainerEvent {
+                event_type: "container".to_string(),
+                action: "died".to_string(),
+                actor_id: "9b1c2d".to_string(),
+                actor_name: Some("cache".to_string()),
+                time: 1700000000,
+            })
+        );
+
+        let image_pull = r#"{"Type":"image","Action":"pull","Actor":{"ID":"nginx:latest","Attributes":{}},"time":1700000000}"#;
+        let event = OutputParser::parse_event(image_pull).unwrap();
+        assert_eq!(event.actor_id, "nginx:latest");
+        assert_eq!(event.actor_name, None);
+
+        assert_eq!(OutputParser::parse_event(""), None);
+        assert_eq!(OutputParser::parse_event("not json"), None);
+        assert_eq!(OutputParser::parse_event(r#"{"Type":"container"}"#), None);
+    }
+
+    #[test]
+    fn test_parse_image_details_docker() {
+        let output = r#"[{
+            "Id": "sha256:a6bd71f48f68",
+            "RepoTags": ["nginx:latest"],
+            "Created": "2024-05-29T20:01:35.913546Z",
+            "Architecture": "amd64",
+            "Os": "linux",
+            "Size": 187694648,
+            "Config": {
+                "ExposedPorts": {"80/tcp": {}, "443/tcp": {}},
+                "Env": ["PATH=/usr/local/sbin:/usr/local/bin", "NGINX_VERSION=1.27.0"],
+                "Cmd": ["nginx", "-g", "daemon off;"],
+                "Entrypoint": ["/docker-entrypoint.sh"],
+                "WorkingDir": "",
+                "Labels": {"maintainer": "NGINX Docker Maintainers"}
+            },
+            "RootFS": {"Type": "layers", "Layers": ["sha256:5d4427064ecc", "sha256:7c0ab7e3bb83"]}
+        }]"#;
+
+        let details = OutputParser::parse_image_details(output, ContainerRuntime::Docker).unwrap();
+        assert_eq!(details.id, "sha256:a6bd71f48f68");
+        assert_eq!(details.repo_tags, vec!["nginx:latest"]);
+        assert_eq!(details.architecture.as_deref(), Some("amd64"));
+        assert_eq!(details.size, Some(187694648));
+        assert!(details.created.is_some());
+        assert_eq!(details.env[1], "NGINX_VERSION=1.27.0");
+        assert_eq!(details.cmd, vec!["nginx", "-g", "daemon off;"]);
+        assert_eq!(details.entrypoint, vec!["/docker-entrypoint.sh"]);
+        assert_eq!(details.exposed_ports, vec!["443/tcp", "80/tcp"]);
+        assert_eq!(details.working_dir, None);
+        assert_eq!(details.labels["maintainer"], "NGINX Docker Maintainers");
+        assert_eq!(details.layers.len(), 2);
+        assert!(details.history.is_empty());
+    }
+
+    #[test]
+    fn test_parse_image_details_apple() {
+        let output = r#"[{
+            "name": "docker.io/library/alpine:latest",
+            "index": {"digest": "sha256:beefdbd8a1da", "size": 9218},
+            "variants": [
+                {"platform": {"os": "unknown", "architecture": "unknown"}, "size": 840},
+                {
+                    "platform": {"os": "linux", "architecture": "arm64", "variant": "v8"},
+                    "size": 4090000,
+                    "config": {
+                        "architecture": "arm64",
+                        "os": "linux",
+                        "created": "2024-06-20T17:19:49Z",
+                        "config": {"Env": ["PATH=/usr/bin"], "Cmd": ["/bin/sh"], "Labels": null},
+                        "rootfs": {"type": "layers", "diff_ids": ["sha256:94e5f06ff8e3"]},
+                        "history": [
+                            {"created": "2024-06-20T17:19:49Z", "created_by": "ADD file:33ebe in / "},
+                            {"created": "2024-06-20T17:19:49Z", "created_by": "CMD [\"/bin/sh\"]", "empty_layer": true}
+                        ]
+                    }
+                }
+            ]
+        }]"#;
+
+        let details = OutputParser::parse_image_details(output, ContainerRuntime::Apple).unwrap();
+        assert_eq!(details.id, "sha256:beefdbd8a1da");
+        assert_eq!(details.repo_tags, vec!["docker.io/library/alpine:latest"]);
+        assert_eq!(details.architecture.as_deref(), Some("arm64"));
+        assert_eq!(details.size, Some(4090000));
+        assert_eq!(details.cmd, vec!["/bin/sh"]);
+        assert!(details.labels.is_empty());
+        assert_eq!(details.layers, vec!["sha256:94e5f06ff8e3"]);
+        assert_eq!(details.history.len(), 2);
+        assert_eq!(details.history[0].created_by, "CMD [\"/bin/sh\"]");
+        assert_eq!(details.history[0].size, None);
+    }
+
+    #[test]
+    fn test_parse_image_history() {
+        // `docker history --no-trunc --human=false --format json nginx`
+        let docker = r#"{"Comment":"buildkit.dockerfile.v0","CreatedAt":"2024-05-29T20:01:35Z","CreatedBy":"CMD [\"nginx\" \"-g\" \"daemon off;\"]","CreatedSince":"2 weeks ago","ID":"sha256:a6bd71f48f68","Size":"0"}
+{"Comment":"","CreatedAt":"2024-05-14T01:28:20Z","CreatedBy":"/bin/sh -c #(nop) ADD file:5d6b639 in / ","CreatedSince":"4 weeks ago","ID":"<missing>","Size":"74780380"}
+"#;
+        // `podman history --no-trunc --human=false --format json nginx`
+        let podman = r#"[
+            {"id": "a6bd71f48f68", "created": "2024-05-29T20:01:35.000000000Z", "CreatedBy": "CMD [\"nginx\" \"-g\" \"daemon off;\"]", "size": 0, "comment": "buildkit.dockerfile.v0"},
+            {"id": "<missing>", "created": "2024-05-14T01:28:20.000000000Z", "CreatedBy": "/bin/sh -c #(nop) ADD file:5d6b639 in / ", "size": 74780380, "comment": ""}
+        ]"#;
+
+        for output in [docker, podman] {
+            let history = OutputParser::parse_image_history(output).unwrap();
+            assert_eq!(history.len(), 2);
+            assert!(history[0].id.as_deref().unwrap().ends_with("a6bd71f48f68"));
+            assert_eq!(history[0].size, Some(0));
+            assert_eq!(
+                history[0].comment.as_deref(),
+                Some("buildkit.dockerfile.v0")
+            );
+            assert!(history[0].created_by.starts_with("CMD"));
+            assert_eq!(history[1].id, None);
+            assert_eq!(history[1].size, Some(74780380));
+            assert_eq!(history[1].comment, None);
+            assert!(history[1].created.is_some());
+        }
+
+        // Human-readable sizes still parse
+        let human = r#"{"CreatedBy":"RUN apt-get update","ID":"<missing>","Size":"77.8MB"}"#;
+        let history = OutputParser::parse_image_history(human).unwrap();
+        assert_eq!(history[0].size, Some(81579212));
+        assert!(OutputParser::parse_image_history("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_bulk_action_failures() {
+        // `docker stop web db ghost`
+        let failed = OutputParser::parse_bulk_action_failures(
+            "web\ndb\n",
+            "Error response from daemon: No such container: ghost\n",
+            &["web", "db", "ghost"],
+        );
+        assert_eq!(
+            failed,
+            vec![(
+                "ghost",
+                "Error response from daemon: No such container: ghost".to_string()
+            )]
+        );
+
+        // Stderr that doesn't name the container falls back to what wasn't echoed
+        let failed = OutputParser::parse_bulk_action_failures(
+            "web\n",
+            "permission denied\n",
+            &["web", "db"],
+        );
+        assert_eq!(failed, vec![("db", "permission denied".to_string())]);
+
+        let failed =
+            OutputParser::parse_bulk_action_failures("", "daemon not running", &["web", "db"]);
+        assert_eq!(failed.len(), 2);
+
+        // A name that is a prefix of another isn't blamed for the other's error
+        let failed = OutputParser::parse_bulk_action_failures(
+            "web
+",
+            "Error response from daemon: No such container: web-2.\n",
+            &["web", "web-2"],
+        );
+        assert_eq!(
+            failed,
+            vec![(
+                "web-2",
+                "Error response from daemon: No such container: web-2.".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_parse_prune_output() {
+        // `docker image prune -f -a`
+        let docker_images = "Deleted Images:
+untagged: nginx:latest
+untagged: nginx@sha256:0f04e4f646a3f14bf31d8bc8d885b6c951fdcf42589d06845f64d18aec6a3c4d
+deleted: sha256:a6bd71f48f6839d9faae1f29d3babef831e76bc213107682c5cc80f0cbb30866
+deleted: sha256:5d4427064ecc46e3c2add169e9b5eafc7ed2be7861081ec925938ab628ac0e25
+
+Total reclaimed space: 187.7MB
+";
+        // `docker system prune -f`
+        let docker_system = "Deleted Containers:
+4a7f7eebae0f2a4b2c8e1d5b6c9f0e3a7d8b1c2e3f4a5b6c7d8e9f0a1b2c3d4e
+
+Deleted Networks:
+old_default
+
+Total reclaimed space: 0B
+";
+        // `podman system prune -f`
+        let podman_system = "Deleted Containers
+c6e1b2a0f9d8
+Deleted Images
+4abcf2066143
+Total reclaimed space: 3.3MB
+";
+        // `podman volume prune -f` reports names only
+        let podman_volumes = "pgdata\ncache\n";
+
+        let cases = [
+            (docker_images, 2, 196817715),
+            (docker_system, 2, 0),
+            (podman_system, 2, 3460300),
+            (podman_volumes, 2, 0),
+            ("", 0, 0),
+        ];
+        for (output, deleted, reclaimed_bytes) in cases {
+            let result = OutputParser::parse_prune_output(output);
+            assert_eq!(result.deleted.len(), deleted, "{}", output);
+            assert_eq!(result.reclaimed_bytes, reclaimed_bytes, "{}", output);
+        }
+
+        let images = OutputParser::parse_prune_output(docker_images);
+        assert!(images.deleted[0].starts_with("sha256:a6bd71f48f68"));
+        assert_eq!(
+            OutputParser::parse_prune_output(docker_system).deleted[1],
+            "old_default"
+        );
+    }
 }
@@ -3,11 +3,13 @@ use russh::client::{self, Config, Handle};
 use russh::keys::key;
 use russh::ChannelMsg;
 use russh_keys::{decode_secret_key, load_secret_key};
+use russh_sftp::client::SftpSession;
 use std::pin::Pin;
 use std::sync::{Arc, Mutex};
 use std::task::{Context, Poll};
 use std::time::{Duration, Instant};
 use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::sync::mpsc;
 
 use std::collections::HashMap;
 
@@ -16,6 +18,25 @@ use crate::keyring_store::JumpHostCredentials;
 use crate::models::error::ContainerError;
 use crate::models::system::{ContainerSystem, JumpHost, SshAuthMethod, SshConfig};
 
+/// Unanswered keepalives after which russh closes the connection
+const KEEPALIVE_MAX_MISSED: usize = 3;
+
+/// Build a client config that sends SSH keepalives every `interval_secs` (0 disables them)
+///
+/// russh closes the session once `KEEPALIVE_MAX_MISSED` keepalives go unanswered,
+/// which the connection pool detects via `SshClient::is_closed`.
+fn keepalive_config(interval_secs: u64) -> Config {
+    if interval_secs == 0 {
+        return Config::default();
+    }
+
+    Config {
+        keepalive_interval: Some(Duration::from_secs(interval_secs)),
+        keepalive_max: KEEPALIVE_MAX_MISSED,
+        ..Config::default()
+    }
+}
+
 /// Format a host:port pair, bracketing IPv6 addresses to avoid ambiguity.
 fn host_port(hostname: &str, port: u16) -> String {
     if hostname.contains(':') {
@@ -25,9 +46,35 @@ fn host_port(hostname: &str, port: u16) -> String {
     }
 }
 
+/// Prefix an error from a ProxyJump hop with its position in the chain, so a
+/// failure in `a,b,c` says whether `a`, `b` or `c` rejected the connection.
+/// Host key errors keep their shape so the trust prompt still recognizes them.
+fn name_hop(err: ContainerError, index: usize, hops: usize) -> ContainerError {
+    let hop = format!("Hop {} of {}", index + 1, hops);
+    match err {
+        ContainerError::SshAuthenticationFailed(msg) => {
+            ContainerError::SshAuthenticationFailed(format!("{}: {}", hop, msg))
+        }
+        ContainerError::CredentialError(msg) => {
+            ContainerError::CredentialError(format!("{}: {}", hop, msg))
+        }
+        ContainerError::InvalidConfiguration(msg) => {
+            ContainerError::InvalidConfiguration(format!("{}: {}", hop, msg))
+        }
+        ContainerError::NetworkTimeout(msg) => {
+            ContainerError::NetworkTimeout(format!("{}: {}", hop, msg))
+        }
+        ContainerError::ConnectionFailed(host, reason) => {
+            ContainerError::ConnectionFailed(host, format!("{}: {}", hop, reason))
+        }
+        other => other,
+    }
+}
+
 /// Reason a host key was rejected during verification.
 pub enum HostKeyRejection {
-    Mismatch { expected: String, actual: String },
+    Unknown { key_type: String, fingerprint: String },
+    Mismatch { key_type: String, expected: String, actual: String },
     Revoked,
 }
 
@@ -41,13 +88,19 @@ impl HostKeyWatcher {
     pub fn check(&self, hostname: &str, port: u16) -> Option<ContainerError> {
         let reason = self.0.lock().unwrap().take()?;
         Some(match reason {
-            HostKeyRejection::Mismatch { expected, actual } => {
-                ContainerError::HostKeyVerificationFailed {
+            HostKeyRejection::Unknown { key_type, fingerprint } => ContainerError::HostKeyUnknown {
+                hostname: hostname.to_string(),
+                port,
+                key_type,
+                fingerprint,
+            },
+            HostKeyRejection::Mismatch { key_type, expected, actual } => {
+                ContainerError::HostKeyChanged {
                     hostname: hostname.to_string(),
-                    reason: format!(
-                        "Host key has changed!\nPort: {}\nExpected: {}\nReceived: {}",
-                        port, expected, actual
-                    ),
+                    port,
+                    key_type,
+                    expected,
+                    actual,
                 }
             }
             HostKeyRejection::Revoked => ContainerError::HostKeyVerificationFailed {
@@ -58,18 +111,44 @@ impl HostKeyWatcher {
     }
 }
 
+/// Channel the server opens for each connection to a remote (reverse) forward
+pub type ForwardedChannel = russh::Channel<client::Msg>;
+
+/// Remote forwards on a connection, keyed by the port the server listens on.
+/// Shared between the handler (which receives `forwarded-tcpip` channels) and
+/// the `SshClient` (which registers forwards).
+type ForwardRoutes = Arc<Mutex<HashMap<u32, mpsc::UnboundedSender<ForwardedChannel>>>>;
+
 /// SSH connection handler with host key verification.
 pub struct SshHandler {
     hostname: String,
     port: u16,
     rejection: Arc<Mutex<Option<HostKeyRejection>>>,
+    forward_routes: ForwardRoutes,
+    hash_known_hosts: bool,
+    confirm_new_host_keys: bool,
 }
 
 impl SshHandler {
-    pub fn new(hostname: String, port: u16) -> (Self, HostKeyWatcher) {
+    /// Handler for `hostname:port`, taking the known_hosts policy from `ssh_config`
+    pub fn new(hostname: String, port: u16, ssh_config: &SshConfig) -> (Self, HostKeyWatcher) {
         let rejection = Arc::new(Mutex::new(None));
         let watcher = HostKeyWatcher(rejection.clone());
-        (Self { hostname, port, rejection }, watcher)
+        let forward_routes = Arc::new(Mutex::new(HashMap::new()));
+        let handler = Self {
+            hostname,
+            port,
+            rejection,
+            forward_routes,
+            hash_known_hosts: ssh_config.hash_known_hosts,
+            confirm_new_host_keys: ssh_config.confirm_new_host_keys,
+        };
+        (handler, watcher)
+    }
+
+    /// Routing table for remote forwards, kept by the client after connecting
+    fn forward_routes(&self) -> ForwardRoutes {
+        self.forward_routes.clone()
     }
 }
 
@@ -88,22 +167,34 @@ impl client::Handler for SshHandler {
                 tracing::debug!("Host key verified for {}:{}", self.hostname, self.port);
                 Ok(true)
             }
+            Ok(HostKeyCheckResult::Unknown { key_type, fingerprint }) if self.confirm_new_host_keys => {
+                tracing::info!(
+                    "Unknown host key for {}:{} ({} {}), waiting for the user to trust it",
+                    self.hostname, self.port, key_type, fingerprint
+                );
+                known_hosts::hold_host_key(&self.hostname, self.port, server_public_key, self.hash_known_hosts);
+                *self.rejection.lock().unwrap() = Some(HostKeyRejection::Unknown { key_type, fingerprint });
+                Ok(false)
+            }
             Ok(HostKeyCheckResult::Unknown { key_type, fingerprint }) => {
                 tracing::info!(
                     "Unknown host key for {}:{} ({} {}), auto-accepting",
                     self.hostname, self.port, key_type, fingerprint
                 );
-                if let Err(e) = known_hosts::add_host_key(&self.hostname, self.port, server_public_key) {
+                if let Err(e) = known_hosts::add_host_key(&self.hostname, self.port, server_public_key, self.hash_known_hosts) {
                     tracing::warn!("Failed to save host key to known_hosts: {}", e);
                 }
                 Ok(true)
             }
-            Ok(HostKeyCheckResult::Mismatch { expected_fingerprint, actual_fingerprint }) => {
+            Ok(HostKeyCheckResult::Mismatch { key_type, expected_fingerprint, actual_fingerprint }) => {
                 tracing::error!(
                     "HOST KEY MISMATCH for {}:{} — possible MITM attack! Expected {}, got {}",
                     self.hostname, self.port, expected_fingerprint, actual_fingerprint
                 );
+                // Held so the user can replace the old key after checking the new one
+                known_hosts::hold_host_key(&self.hostname, self.port, server_public_key, self.hash_known_hosts);
                 *self.rejection.lock().unwrap() = Some(HostKeyRejection::Mismatch {
+                    key_type,
                     expected: expected_fingerprint,
                     actual: actual_fingerprint,
                 });
@@ -126,6 +217,33 @@ impl client::Handler for SshHandler {
             }
         }
     }
+
+    async fn server_channel_open_forwarded_tcpip(
+        &mut self,
+        channel: ForwardedChannel,
+        connected_address: &str,
+        connected_port: u32,
+        originator_address: &str,
+        originator_port: u32,
+        _session: &mut client::Session,
+    ) -> Result<(), Self::Error> {
+        let route = self.forward_routes.lock().unwrap().get(&connected_port).cloned();
+        match route {
+            Some(tx) if tx.send(channel).is_ok() => {
+                tracing::debug!(
+                    "Forwarded connection from {}:{} on {}:{}",
+                    originator_address, originator_port, connected_address, connected_port
+                );
+            }
+            _ => {
+                tracing::warn!(
+                    "Dropping forwarded connection on {}:{}: no remote forward registered",
+                    connected_address, connected_port
+                );
+            }
+        }
+        Ok(())
+    }
 }
 
 /// A stream wrapping a child process stdin/stdout for ProxyCommand
@@ -178,6 +296,8 @@ pub struct SshClient {
     _jump_sessions: Vec<Handle<SshHandler>>,
     /// ProxyCommand child process (kept alive for the duration of the connection)
     _proxy_child: Option<tokio::process::Child>,
+    /// Remote forwards registered on this connection
+    forward_routes: ForwardRoutes,
     system_id: String,
     created_at: Instant,
     last_used: Instant,
@@ -201,7 +321,7 @@ impl SshClient {
                 "SSH configuration required for remote system".to_string(),
             ))?;
 
-        let config = Config::default();
+        let config = keepalive_config(ssh_config.keepalive_interval_secs);
 
         let addr = host_port(&system.hostname, ssh_config.port);
         let timeout_duration = Duration::from_secs(ssh_config.connection_timeout);
@@ -209,7 +329,8 @@ impl SshClient {
         tracing::info!("Connecting to SSH server at {}", addr);
 
         // Apply timeout using tokio
-        let (handler, watcher) = SshHandler::new(system.hostname.clone(), ssh_config.port);
+        let (handler, watcher) = SshHandler::new(system.hostname.clone(), ssh_config.port, ssh_config);
+        let forward_routes = handler.forward_routes();
         let connect_future = client::connect(Arc::new(config), &addr, handler);
         let mut session = tokio::time::timeout(timeout_duration, connect_future)
             .await
@@ -234,6 +355,7 @@ impl SshClient {
             session,
             _jump_sessions: Vec::new(),
             _proxy_child: None,
+            forward_routes,
             system_id: system.id.0.clone(),
             created_at: Instant::now(),
             last_used: Instant::now(),
@@ -241,8 +363,9 @@ impl SshClient {
     }
 
     /// Connect to a system via ProxyJump (SSH-over-SSH tunneling)
-    /// Each jump host in the chain is connected sequentially, with the final
-    /// connection tunneled through the chain.
+    /// Jump hosts are connected in order, each through a `direct-tcpip` channel
+    /// on the one before, and the target is reached through the last. Chains can
+    /// be any length; errors name the hop that failed.
     pub async fn connect_via_jump(
         system: &ContainerSystem,
         jump_hosts: &[JumpHost],
@@ -263,88 +386,72 @@ impl SshClient {
         }
 
         let timeout_duration = Duration::from_secs(ssh_config.connection_timeout);
-        let mut jump_sessions: Vec<Handle<SshHandler>> = Vec::new();
-
-        // Step 1: Connect to the first jump host via TCP
-        let first_jump = &jump_hosts[0];
-        let first_addr = host_port(&first_jump.hostname, first_jump.port);
-        tracing::info!("ProxyJump: connecting to first jump host at {}", first_addr);
-
-        let config = Config::default();
-        let (handler, watcher) = SshHandler::new(first_jump.hostname.clone(), first_jump.port);
-        let mut current_session = tokio::time::timeout(
-            timeout_duration,
-            client::connect(Arc::new(config), &first_addr, handler),
-        )
-        .await
-        .map_err(|_| ContainerError::NetworkTimeout(format!(
-            "Connection to jump host {} timed out", first_jump.hostname
-        )))?
-        .map_err(|e| {
-            watcher.check(&first_jump.hostname, first_jump.port)
-                .unwrap_or_else(|| ContainerError::ConnectionFailed(
-                    first_jump.hostname.clone(),
-                    e.to_string(),
-                ))
-        })?;
-
-        // Authenticate on the first jump host
-        let first_cred_key = host_port(&first_jump.hostname, first_jump.port);
-        Self::authenticate_jump_host(&mut current_session, first_jump, jump_host_creds.get(&first_cred_key)).await?;
-
-        // Step 2: Chain through remaining jump hosts (if any)
-        for (i, jump) in jump_hosts.iter().enumerate().skip(1) {
-            tracing::info!("ProxyJump: opening tunnel through jump host {} to {}:{}", i, jump.hostname, jump.port);
-
-            // Open direct-tcpip channel to the next hop
-            let channel = current_session
-                .channel_open_direct_tcpip(
-                    jump.hostname.clone(),
-                    jump.port as u32,
-                    "127.0.0.1",
-                    0u32,
-                )
-                .await
-                .map_err(|e| ContainerError::ConnectionFailed(
-                    jump.hostname.clone(),
-                    format!("Failed to open tunnel: {}", e),
-                ))?;
-
-            let stream = channel.into_stream();
-
-            // Keep the previous session alive
-            jump_sessions.push(current_session);
-
-            // Connect SSH over the tunnel
-            let config = Config::default();
-            let (handler, watcher) = SshHandler::new(jump.hostname.clone(), jump.port);
-            current_session = tokio::time::timeout(
-                timeout_duration,
-                client::connect_stream(Arc::new(config), stream, handler),
-            )
-            .await
-            .map_err(|_| ContainerError::NetworkTimeout(format!(
-                "Connection to jump host {} timed out", jump.hostname
-            )))?
-            .map_err(|e| {
-                watcher.check(&jump.hostname, jump.port)
-                    .unwrap_or_else(|| ContainerError::ConnectionFailed(
-                        jump.hostname.clone(),
-                        e.to_string(),
-                    ))
-            })?;
+        let hops = jump_hosts.len();
+        // One session per hop, in chain order; each carries the tunnel to the next
+        let mut jump_sessions: Vec<Handle<SshHandler>> = Vec::with_capacity(hops);
+
+        for (i, jump) in jump_hosts.iter().enumerate() {
+            let config = Arc::new(keepalive_config(ssh_config.keepalive_interval_secs));
+            let (handler, watcher) = SshHandler::new(jump.hostname.clone(), jump.port, ssh_config);
+            let connected = match jump_sessions.last() {
+                // The first hop is reached directly over TCP
+                None => {
+                    let addr = host_port(&jump.hostname, jump.port);
+                    tracing::info!("ProxyJump: connecting to jump host 1/{} at {}", hops, addr);
+                    tokio::time::timeout(timeout_duration, client::connect(config, &addr, handler)).await
+                }
+                // Every later hop through a direct-tcpip channel on the previous one
+                Some(previous) => {
+                    tracing::info!(
+                        "ProxyJump: opening tunnel to jump host {}/{} at {}:{}",
+                        i + 1, hops, jump.hostname, jump.port
+                    );
+                    let channel = previous
+                        .channel_open_direct_tcpip(
+                            jump.hostname.clone(),
+                            jump.port as u32,
+                            "127.0.0.1",
+                            0u32,
+                        )
+                        .await
+                        .map_err(|e| name_hop(ContainerError::ConnectionFailed(
+                            jump.hostname.clone(),
+                            format!("Failed to open tunnel: {}", e),
+                        ), i, hops))?;
+                    tokio::time::timeout(
+                        timeout_duration,
+                        client::connect_stream(config, channel.into_stream(), handler),
+                    )
+                    .await
+                }
+            };
+            let mut session = connected
+                .map_err(|_| ContainerError::NetworkTimeout(format!(
+                    "Connection to jump host {} timed out", jump.hostname
+                )))
+                .and_then(|result| result.map_err(|e| {
+                    watcher.check(&jump.hostname, jump.port)
+                        .unwrap_or_else(|| ContainerError::ConnectionFailed(
+                            jump.hostname.clone(),
+                            e.to_string(),
+                        ))
+                }))
+                .map_err(|e| name_hop(e, i, hops))?;
 
-            // Authenticate on this jump host
             let cred_key = host_port(&jump.hostname, jump.port);
-            Self::authenticate_jump_host(&mut current_session, jump, jump_host_creds.get(&cred_key)).await?;
+            Self::authenticate_jump_host(&mut session, jump, jump_host_creds.get(&cred_key))
+                .await
+                .map_err(|e| name_hop(e, i, hops))?;
+            jump_sessions.push(session);
         }
 
-        // Step 3: Open tunnel from last jump host to the target
+        // Open a tunnel from the last jump host to the target
         let target_host = &system.hostname;
         let target_port = ssh_config.port;
         tracing::info!("ProxyJump: opening final tunnel to {}:{}", target_host, target_port);
 
-        let channel = current_session
+        let last_hop = jump_sessions.last().expect("jump_hosts is not empty");
+        let channel = last_hop
             .channel_open_direct_tcpip(
                 target_host.clone(),
                 target_port as u32,
@@ -358,11 +465,11 @@ impl SshClient {
             ))?;
 
         let stream = channel.into_stream();
-        jump_sessions.push(current_session);
 
-        // Step 4: Connect SSH to the target over the tunnel
-        let config = Config::default();
-        let (handler, watcher) = SshHandler::new(system.hostname.clone(), ssh_config.port);
+        // Connect SSH to the target over the tunnel
+        let config = keepalive_config(ssh_config.keepalive_interval_secs);
+        let (handler, watcher) = SshHandler::new(system.hostname.clone(), ssh_config.port, ssh_config);
+        let forward_routes = handler.forward_routes();
         let mut target_session = tokio::time::timeout(
             timeout_duration,
             client::connect_stream(Arc::new(config), stream, handler),
@@ -388,6 +495,7 @@ impl SshClient {
             session: target_session,
             _jump_sessions: jump_sessions,
             _proxy_child: None,
+            forward_routes,
             system_id: system.id.0.clone(),
             created_at: Instant::now(),
             last_used: Instant::now(),
@@ -453,8 +561,9 @@ impl SshClient {
         };
 
         // Connect SSH over the proxy stream
-        let config = Config::default();
-        let (handler, watcher) = SshHandler::new(system.hostname.clone(), ssh_config.port);
+        let config = keepalive_config(ssh_config.keepalive_interval_secs);
+        let (handler, watcher) = SshHandler::new(system.hostname.clone(), ssh_config.port, ssh_config);
+        let forward_routes = handler.forward_routes();
         let mut session = tokio::time::timeout(
             timeout_duration,
             client::connect_stream(Arc::new(config), stream, handler),
@@ -480,6 +589,7 @@ impl SshClient {
             session,
             _jump_sessions: Vec::new(),
             _proxy_child: Some(child),
+            forward_routes,
             system_id: system.id.0.clone(),
             created_at: Instant::now(),
             last_used: Instant::now(),
@@ -700,22 +810,12 @@ impl SshClient {
     /// Execute a command on the remote system
     pub async fn execute(&mut self, command: &str) -> Result<CommandResult, ContainerError> {
         let start = Instant::now();
-        self.last_used = Instant::now();
-
-        let mut channel = self
-            .session
-            .channel_open_session()
-            .await
-            .map_err(|e| ContainerError::Internal(format!("Failed to open SSH channel: {}", e)))?;
-
-        channel
-            .exec(true, command)
-            .await
-            .map_err(|e| ContainerError::Internal(format!("Failed to execute command: {}", e)))?;
+        let mut channel = self.open_exec_channel(command).await?;
 
         let mut stdout = Vec::new();
         let mut stderr = Vec::new();
         let mut exit_code = 0;
+        let mut exit_seen = false;
 
         loop {
             match channel.wait().await {
@@ -727,12 +827,21 @@ impl SshClient {
                 }
                 Some(ChannelMsg::ExitStatus { exit_status }) => {
                     exit_code = exit_status as i32;
+                    exit_seen = true;
                 }
                 Some(ChannelMsg::Eof) | None => break,
                 _ => {}
             }
         }
 
+        // The channel ended because the whole session went away, not the command
+        if !exit_seen && self.session.is_closed() {
+            return Err(ContainerError::ConnectionFailed(
+                self.system_id.clone(),
+                "SSH session closed before the command finished".to_string(),
+            ));
+        }
+
         let execution_time_ms = start.elapsed().as_millis() as u64;
 
         Ok(CommandResult {
@@ -743,6 +852,30 @@ impl SshClient {
         })
     }
 
+    /// Start `command` on a fresh channel and hand the channel back to the caller
+    ///
+    /// The channel is independent of the client, so callers can read it without
+    /// holding the client lock for the lifetime of the command.
+    pub async fn open_exec_channel(
+        &mut self,
+        command: &str,
+    ) -> Result<russh::Channel<client::Msg>, ContainerError> {
+        self.last_used = Instant::now();
+
+        let channel = self
+            .session
+            .channel_open_session()
+            .await
+            .map_err(|e| ContainerError::Internal(format!("Failed to open SSH channel: {}", e)))?;
+
+        channel
+            .exec(true, command)
+            .await
+            .map_err(|e| ContainerError::Internal(format!("Failed to execute command: {}", e)))?;
+
+        Ok(channel)
+    }
+
     /// Get the system ID this client is connected to
     pub fn system_id(&self) -> &str {
         &self.system_id
@@ -758,6 +891,11 @@ impl SshClient {
         self.last_used
     }
 
+    /// Check whether the underlying SSH session has shut down
+    pub fn is_closed(&self) -> bool {
+        self.session.is_closed()
+    }
+
     /// Check if the connection is still valid by sending a ping
     pub async fn is_alive(&mut self) -> bool {
         match self.execute("echo ok").await {
@@ -766,6 +904,78 @@ impl SshClient {
         }
     }
 
+    /// Ask the server to listen on `bind_address:port` and forward connections back
+    ///
+    /// Port 0 lets the server pick one. Returns the port the server listens on
+    /// and a receiver yielding one channel per incoming connection.
+    pub async fn request_remote_forward(
+        &mut self,
+        bind_address: &str,
+        port: u32,
+    ) -> Result<(u32, mpsc::UnboundedReceiver<ForwardedChannel>), ContainerError> {
+        self.last_used = Instant::now();
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        // Register up front when the port is known so no early connection is lost
+        if port != 0 {
+            self.forward_routes.lock().unwrap().insert(port, tx.clone());
+        }
+
+        let bound_port = match self.session.tcpip_forward(bind_address, port).await {
+            Ok(0) => port,
+            Ok(allocated) => allocated,
+            Err(e) => {
+                self.forward_routes.lock().unwrap().remove(&port);
+                return Err(ContainerError::Internal(format!(
+                    "Server refused remote forward on {}:{}: {}",
+                    bind_address, port, e
+                )));
+            }
+        };
+        self.forward_routes.lock().unwrap().insert(bound_port, tx);
+
+        Ok((bound_port, rx))
+    }
+
+    /// Stop the server listening for a remote forward
+    pub async fn cancel_remote_forward(
+        &mut self,
+        bind_address: &str,
+        port: u32,
+    ) -> Result<(), ContainerError> {
+        self.forward_routes.lock().unwrap().remove(&port);
+        self.session
+            .cancel_tcpip_forward(bind_address, port)
+            .await
+            .map_err(|e| ContainerError::Internal(format!(
+                "Failed to cancel remote forward on {}:{}: {}",
+                bind_address, port, e
+            )))
+    }
+
+    /// Start the SFTP subsystem on a new channel of this connection
+    ///
+    /// Fails if the server has no SFTP subsystem configured; callers fall back
+    /// to shell commands in that case.
+    pub async fn open_sftp(&mut self) -> Result<SftpSession, ContainerError> {
+        self.last_used = Instant::now();
+
+        let channel = self
+            .session
+            .channel_open_session()
+            .await
+            .map_err(|e| ContainerError::Internal(format!("Failed to open SSH channel: {}", e)))?;
+
+        channel
+            .request_subsystem(true, "sftp")
+            .await
+            .map_err(|e| ContainerError::Internal(format!("Failed to request SFTP subsystem: {}", e)))?;
+
+        SftpSession::new(channel.into_stream())
+            .await
+            .map_err(|e| ContainerError::Internal(format!("Failed to start SFTP session: {}", e)))
+    }
+
     /// Open an interactive PTY channel for terminal sessions
     /// This creates a new channel on the existing SSH connection (subterminal)
     /// Returns the raw channel for the caller to manage
@@ -806,3 +1016,35 @@ impl SshClient {
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_name_hop_prefixes_position_in_chain() {
+        let err = name_hop(
+            ContainerError::SshAuthenticationFailed(
+                "Password authentication failed on jump host 10.0.0.2".to_string(),
+            ),
+            1,
+            2,
+        );
+        assert_eq!(
+            err.to_string(),
+            ContainerError::SshAuthenticationFailed(
+                "Hop 2 of 2: Password authentication failed on jump host 10.0.0.2".to_string()
+            )
+            .to_string()
+        );
+
+        let host_key = ContainerError::HostKeyVerificationFailed {
+            hostname: "10.0.0.1".to_string(),
+            reason: "mismatch".to_string(),
+        };
+        assert!(matches!(
+            name_hop(host_key, 0, 2),
+            ContainerError::HostKeyVerificationFailed { reason, .. } if reason == "mismatch"
+        ));
+    }
+}
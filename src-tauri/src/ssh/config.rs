@@ -65,7 +65,7 @@ pub fn has_ssh_config(config_path: Option<&str>) -> bool {
 }
 
 /// Expand ~ to home directory in a path
-fn expand_home(path: &str) -> String {
+pub(crate) fn expand_home(path: &str) -> String {
     if path.starts_with("~/") || path == "~" {
         if let Some(home) = dirs::home_dir() {
             return path.replacen("~", &home.to_string_lossy(), 1);
@@ -75,7 +75,7 @@ fn expand_home(path: &str) -> String {
 }
 
 /// Contract home directory to ~ in a path for display
-fn contract_home(path: &str) -> String {
+pub(crate) fn contract_home(path: &str) -> String {
     if let Some(home) = dirs::home_dir() {
         let home_str = home.to_string_lossy();
         if path.starts_with(home_str.as_ref()) {
@@ -102,6 +102,42 @@ fn expand_glob(pattern: &str) -> Vec<PathBuf> {
     }
 }
 
+/// Split the argument of an Include directive into its patterns, honoring double quotes
+fn split_include_patterns(value: &str) -> Vec<String> {
+    let mut patterns = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+
+    for c in value.chars() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            c if c.is_whitespace() && !in_quotes => {
+                if !current.is_empty() {
+                    patterns.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        patterns.push(current);
+    }
+
+    patterns
+}
+
+/// Make an Include pattern absolute: `~` and absolute paths are kept, relative
+/// ones resolve against the including file's directory (or `~/.ssh` for inline content)
+fn resolve_include_pattern(pattern: &str, base_dir: Option<&Path>) -> String {
+    if pattern.starts_with('/') || pattern.starts_with('~') {
+        return pattern.to_string();
+    }
+    match base_dir {
+        Some(base) => base.join(pattern).to_string_lossy().to_string(),
+        None => format!("~/.ssh/{}", pattern),
+    }
+}
+
 /// Parse an SSH config file, following Include directives recursively.
 /// `visited` tracks files already parsed to prevent circular includes.
 fn parse_config_file(
@@ -152,14 +188,10 @@ fn parse_config_content_with_includes(
 
         match key.as_str() {
             "include" => {
-                // Resolve the include path
-                let include_paths = if value.starts_with('/') || value.starts_with('~') {
-                    expand_glob(&value)
-                } else if let Some(base) = base_dir {
-                    expand_glob(&base.join(&value).to_string_lossy())
-                } else {
-                    expand_glob(&value)
-                };
+                // One Include line may list several (optionally quoted) patterns
+                let include_paths = split_include_patterns(&value)
+                    .into_iter()
+                    .flat_map(|pattern| expand_glob(&resolve_include_pattern(&pattern, base_dir)));
 
                 for include_path in include_paths {
                     let included = parse_config_file(&include_path, visited);
@@ -359,10 +391,19 @@ pub fn resolve_host(host_name: &str, config_path: Option<&str>) -> Result<SshHos
 /// - Bare host aliases (e.g., "jump-admin") resolved against parsed SSH config
 /// - Explicit user@host:port format
 /// - Chained jumps separated by commas (e.g., "bastion1,bastion2")
+/// - `none`, which disables jumping
+///
+/// The result is in connection order, as with OpenSSH: for `a,b,c` the client
+/// connects to `a` directly, tunnels through `a` to `b`, through `b` to `c`, and
+/// through `c` to the target. A hop's own ProxyJump setting is not followed.
 pub fn resolve_jump_hosts(
     proxy_jump: &str,
     config_path: Option<&str>,
 ) -> Result<Vec<JumpHost>, ContainerError> {
+    if proxy_jump.trim().eq_ignore_ascii_case("none") {
+        return Ok(Vec::new());
+    }
+
     let hosts = read_and_parse(config_path)?;
     let mut result = Vec::new();
 
@@ -739,6 +780,104 @@ Host bastion
         assert_eq!(chain[1].username, "user");
     }
 
+    #[test]
+    fn test_resolve_jump_hosts_keeps_chain_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("config");
+        fs::write(
+            &config,
+            r#"
+Host outer
+    HostName bastion.example.com
+    User edge
+
+Host inner
+    HostName 10.0.0.2
+    User ops
+    Port 2222
+    IdentityFile ~/.ssh/inner_key
+"#,
+        )
+        .unwrap();
+        let config = config.to_str();
+
+        let chain = resolve_jump_hosts("outer, inner", config).unwrap();
+        let hops: Vec<(&str, u16, &str)> = chain
+            .iter()
+            .map(|j| (j.hostname.as_str(), j.port, j.username.as_str()))
+            .collect();
+        assert_eq!(
+            hops,
+            vec![("bastion.example.com", 22, "edge"), ("10.0.0.2", 2222, "ops")]
+        );
+        assert_eq!(chain[1].identity_file.as_deref(), Some("~/.ssh/inner_key"));
+
+        // Reversing the list reverses the route
+        let reversed = resolve_jump_hosts("inner,outer", config).unwrap();
+        assert_eq!(reversed[0].hostname, "10.0.0.2");
+        assert_eq!(reversed[1].hostname, "bastion.example.com");
+
+        assert!(resolve_jump_hosts("none", config).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_split_include_patterns() {
+        assert_eq!(split_include_patterns("config.d/*"), vec!["config.d/*"]);
+        assert_eq!(
+            split_include_patterns("work/* \"~/My Hosts/extra\"  personal"),
+            vec!["work/*", "~/My Hosts/extra", "personal"]
+        );
+        assert!(split_include_patterns("").is_empty());
+    }
+
+    #[test]
+    fn test_include_glob_and_relative_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let conf_d = dir.path().join("config.d");
+        fs::create_dir(&conf_d).unwrap();
+        fs::write(conf_d.join("a.conf"), "Host alpha\n    Hostname 10.0.0.1\n").unwrap();
+        fs::write(
+            conf_d.join("b.conf"),
+            "Host beta\n    Hostname 10.0.0.2\nInclude nested\n",
+        )
+        .unwrap();
+        // Relative to the including file (config.d/), not the top-level config
+        fs::write(conf_d.join("nested"), "Host gamma\n    Port 2222\n").unwrap();
+        let extra = dir.path().join("extra");
+        fs::write(&extra, "Host delta\n").unwrap();
+
+        let main = dir.path().join("config");
+        fs::write(
+            &main,
+            format!(
+                "Include config.d/*.conf \"{}\"\n\nHost main\n    User root\n",
+                extra.display()
+            ),
+        )
+        .unwrap();
+
+        let hosts = parse_config_file(&main, &mut HashSet::new());
+        for host in ["alpha", "beta", "gamma", "delta", "main"] {
+            assert!(hosts.contains_key(host), "missing {}", host);
+        }
+        assert_eq!(hosts["alpha"].hostname.as_deref(), Some("10.0.0.1"));
+        assert_eq!(hosts["gamma"].port, Some(2222));
+        assert_eq!(hosts["main"].user.as_deref(), Some("root"));
+    }
+
+    #[test]
+    fn test_include_cycle_is_ignored() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = dir.path().join("first");
+        let second = dir.path().join("second");
+        fs::write(&first, "Include second\nHost one\n").unwrap();
+        fs::write(&second, "Include first\nHost two\n").unwrap();
+
+        let hosts = parse_config_file(&first, &mut HashSet::new());
+        assert_eq!(hosts.len(), 2);
+        assert!(hosts.contains_key("one") && hosts.contains_key("two"));
+    }
+
     #[test]
     fn test_identities_only_default_inheritance() {
         let content = r#"
@@ -2,13 +2,19 @@
 ///
 /// Implements an "AcceptNew" policy:
 /// - Known host with matching key → accept
-/// - Unknown host → auto-accept and append to known_hosts
-/// - Known host with different key → reject (MITM warning)
+/// - Unknown host → auto-accept and append to known_hosts, or, for systems that
+///   confirm new host keys, reject and hold the key until the user trusts it
+/// - Known host with different key → reject (MITM warning) and hold the new key
+///   in case the user confirms the server was reinstalled
 /// - Revoked key → reject
 
+use std::collections::HashMap;
 use std::fs;
 use std::io::Write;
 use std::path::PathBuf;
+use std::sync::Mutex;
+
+use once_cell::sync::Lazy;
 
 use hmac::{Hmac, Mac};
 use sha1::Sha1;
@@ -30,6 +36,7 @@ pub enum HostKeyCheckResult {
     },
     /// Key does NOT match the stored entry (possible MITM)
     Mismatch {
+        key_type: String,
         expected_fingerprint: String,
         actual_fingerprint: String,
     },
@@ -115,6 +122,7 @@ fn check_host_key_against_content(
     // If we found host entries but none matched, it's a mismatch
     if let Some(expected_fp) = first_mismatch_fingerprint {
         return Ok(HostKeyCheckResult::Mismatch {
+            key_type: server_key.name().to_string(),
             expected_fingerprint: expected_fp,
             actual_fingerprint: server_key.fingerprint(),
         });
@@ -128,10 +136,12 @@ fn check_host_key_against_content(
 }
 
 /// Append a new host key entry to ~/.ssh/known_hosts.
+/// With `hash` set, the host is stored as `|1|salt|hash` like `HashKnownHosts yes`.
 pub fn add_host_key(
     hostname: &str,
     port: u16,
     server_key: &RusshPublicKey,
+    hash: bool,
 ) -> Result<(), ContainerError> {
     let known_hosts_path = known_hosts_path()?;
 
@@ -158,7 +168,12 @@ pub fn add_host_key(
 
     let algo = server_key.name();
     let key_base64 = server_key.public_key_base64();
-    let line = format!("{} {} {}\n", host_label, algo, key_base64);
+    let host_field = if hash {
+        hash_host(&host_label, &rand::random::<[u8; 20]>())
+    } else {
+        host_label.clone()
+    };
+    let line = format!("{} {} {}\n", host_field, algo, key_base64);
 
     let mut file = fs::OpenOptions::new()
         .create(true)
@@ -187,6 +202,46 @@ pub fn add_host_key(
     Ok(())
 }
 
+/// Rejected host keys waiting for the user's decision, keyed by hostname and fingerprint
+struct PendingHostKey {
+    port: u16,
+    key: RusshPublicKey,
+    hash: bool,
+}
+
+static PENDING_HOST_KEYS: Lazy<Mutex<HashMap<(String, String), PendingHostKey>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Hold an unknown or changed host key that was rejected so `trust_host_key` can
+/// save exactly that key. A later offer for the same host and key replaces it.
+pub fn hold_host_key(hostname: &str, port: u16, server_key: &RusshPublicKey, hash: bool) {
+    let pending = PendingHostKey {
+        port,
+        key: server_key.clone(),
+        hash,
+    };
+    PENDING_HOST_KEYS
+        .lock()
+        .unwrap()
+        .insert((hostname.to_string(), server_key.fingerprint()), pending);
+}
+
+/// Save a held host key to known_hosts once the user has checked its fingerprint.
+/// `fingerprint` may carry OpenSSH's `SHA256:` prefix. For a changed key, remove
+/// the old entry first or the host keeps failing verification.
+pub fn trust_host_key(hostname: &str, fingerprint: &str) -> Result<(), ContainerError> {
+    let fingerprint = fingerprint.trim().trim_start_matches("SHA256:");
+    let pending = PENDING_HOST_KEYS
+        .lock()
+        .unwrap()
+        .remove(&(hostname.to_string(), fingerprint.to_string()))
+        .ok_or_else(|| ContainerError::NotFound {
+            resource: "Pending host key".to_string(),
+            id: format!("{} {}", hostname, fingerprint),
+        })?;
+    add_host_key(hostname, pending.port, &pending.key, pending.hash)
+}
+
 /// Check if the entry's host patterns match the given hostname.
 fn host_matches(
     patterns: &ssh_key::known_hosts::HostPatterns,
@@ -225,6 +280,18 @@ fn hash_matches(salt: &[u8], expected_hash: &[u8; 20], name: &str) -> bool {
     result.as_slice() == expected_hash
 }
 
+/// Hash a host label the way OpenSSH does: `|1|base64(salt)|base64(HMAC-SHA1(salt, host))`
+fn hash_host(host_label: &str, salt: &[u8]) -> String {
+    let mut mac = Hmac::<Sha1>::new_from_slice(salt).expect("HMAC accepts keys of any length");
+    mac.update(host_label.as_bytes());
+    let hash = mac.finalize().into_bytes();
+    format!(
+        "|1|{}|{}",
+        data_encoding::BASE64.encode(salt),
+        data_encoding::BASE64.encode(&hash)
+    )
+}
+
 /// Simple glob pattern matching supporting * and ? wildcards.
 fn glob_match(pattern: &str, text: &str) -> bool {
     let p: Vec<char> = pattern.chars().collect();
@@ -520,6 +587,44 @@ mod tests {
         assert!(!host_matches(&patterns, "other.com", "other.com"));
     }
 
+    // Written by `ssh-keygen -H` for "example.com" and "[example.com]:2222"
+    const HASHED_KNOWN_HOSTS: &str = "\
+|1|SR8fvDn3FRdDIojqmsXaiGtO3jU=|QYCHPYeu8o066AdFfWCLjLLJInA= ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAID7Dwt9xGiUSPRK28A7upQ4zWQ6paOOy8s5K0zHKzZyu
+|1|dr/cpKEFlmfMkD5f5f1G3edWWF4=|vxIb947z2AywhZndF7U8JveOPq8= ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAID7Dwt9xGiUSPRK28A7upQ4zWQ6paOOy8s5K0zHKzZyu
+";
+    const HASHED_HOST_KEY: &str = "AAAAC3NzaC1lZDI1NTE5AAAAID7Dwt9xGiUSPRK28A7upQ4zWQ6paOOy8s5K0zHKzZyu";
+
+    #[test]
+    fn test_check_openssh_hashed_entries() {
+        let pub_key = russh_keys::parse_public_key_base64(HASHED_HOST_KEY).unwrap();
+
+        let result = check_host_key_against_content("example.com", 22, &pub_key, HASHED_KNOWN_HOSTS).unwrap();
+        assert!(matches!(result, HostKeyCheckResult::Matched));
+
+        let result = check_host_key_against_content("example.com", 2222, &pub_key, HASHED_KNOWN_HOSTS).unwrap();
+        assert!(matches!(result, HostKeyCheckResult::Matched));
+
+        let result = check_host_key_against_content("other.com", 22, &pub_key, HASHED_KNOWN_HOSTS).unwrap();
+        assert!(matches!(result, HostKeyCheckResult::Unknown { .. }));
+
+        let other_key = russh_keys::key::KeyPair::generate_ed25519().clone_public_key().unwrap();
+        let result = check_host_key_against_content("example.com", 22, &other_key, HASHED_KNOWN_HOSTS).unwrap();
+        assert!(matches!(result, HostKeyCheckResult::Mismatch { .. }));
+    }
+
+    #[test]
+    fn test_hash_host_roundtrip() {
+        let salt = [7u8; 20];
+        let hashed = hash_host("[example.com]:2222", &salt);
+        assert!(hashed.starts_with("|1|"));
+
+        let pub_key = russh_keys::key::KeyPair::generate_ed25519().clone_public_key().unwrap();
+        let content = format!("{} {} {}\n", hashed, pub_key.name(), pub_key.public_key_base64());
+        let result = check_host_key_against_content("example.com", 2222, &pub_key, &content).unwrap();
+        assert!(matches!(result, HostKeyCheckResult::Matched));
+        assert!(!content.contains("example.com"));
+    }
+
     #[test]
     fn test_check_unknown_host() {
         // Empty known_hosts → everything is unknown
@@ -674,4 +779,25 @@ mod tests {
         let result = check_host_key_against_content("myhost.com", 22, &pub_key, &content).unwrap();
         assert!(matches!(result, HostKeyCheckResult::Matched));
     }
+
+    #[test]
+    fn test_trust_host_key_requires_held_key() {
+        let key = russh_keys::key::KeyPair::generate_ed25519();
+        let pub_key = key.clone_public_key().unwrap();
+
+        // Nothing was offered for this host, so there is nothing to save
+        let result = trust_host_key("never-offered.example", &pub_key.fingerprint());
+        assert!(matches!(result, Err(ContainerError::NotFound { .. })));
+
+        // A held key is only released for the fingerprint the user was shown
+        hold_host_key("held.example", 2222, &pub_key, false);
+        let result = trust_host_key("held.example", "SHA256:not-the-key");
+        assert!(matches!(result, Err(ContainerError::NotFound { .. })));
+        let held = PENDING_HOST_KEYS
+            .lock()
+            .unwrap()
+            .remove(&("held.example".to_string(), pub_key.fingerprint()))
+            .unwrap();
+        assert_eq!(held.port, 2222);
+    }
 }
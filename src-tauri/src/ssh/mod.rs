@@ -1,22 +1,25 @@
 pub mod client;
 pub mod config;
+pub mod keys;
 pub mod known_hosts;
 pub mod pool;
 pub mod port_forward;
+pub mod sftp;
 
 use once_cell::sync::Lazy;
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
-use crate::executor::CommandResult;
+use crate::executor::{CommandResult, OutputStream};
 use crate::keyring_store::JumpHostCredentials;
 use crate::models::error::ContainerError;
 use crate::models::system::ContainerSystem;
 
 pub use client::SshClient;
 pub use config::{has_ssh_config, list_hosts, list_hosts_multi, resolve_host, resolve_host_multi, resolve_jump_hosts, SshHostEntry};
-pub use pool::SshConnectionPool;
+pub use keys::{inspect_identity_file, inspect_private_key, list_identity_keys, SshKeyInfo};
+pub use pool::{ConnectionLease, PoolStats, SshConnectionPool};
 pub use port_forward::PortForwardManager;
 
 /// Global SSH connection pool
@@ -40,7 +43,8 @@ pub async fn connect(
     private_key_content: Option<&str>,
     jump_host_creds: &HashMap<String, JumpHostCredentials>,
 ) -> Result<(), ContainerError> {
-    let mut pool = SSH_POOL.write().await;
+    // The pool's maps are concurrent, so a read lock lets slow handshakes run side by side
+    let pool = SSH_POOL.read().await;
     pool.connect(system, password, passphrase, private_key_content, jump_host_creds).await
 }
 
@@ -65,6 +69,49 @@ pub async fn execute_on_system(
     pool.execute(system_id, command).await
 }
 
+/// Execute a command on a remote system with an explicit time limit (None waits indefinitely)
+pub async fn execute_on_system_with_timeout(
+    system_id: &str,
+    command: &str,
+    limit: Option<std::time::Duration>,
+) -> Result<CommandResult, ContainerError> {
+    let pool = SSH_POOL.read().await;
+    pool.execute_with_timeout(system_id, command, limit).await
+}
+
+/// Start a command on a remote system and stream its output
+pub async fn execute_stream_on_system(
+    system_id: &str,
+    command: &str,
+) -> Result<OutputStream, ContainerError> {
+    let pool = SSH_POOL.read().await;
+    pool.execute_stream(system_id, command).await
+}
+
+/// Keep a system's pooled connection from being evicted while the lease lives
+pub async fn lease_connection(system_id: &str) -> ConnectionLease {
+    let pool = SSH_POOL.read().await;
+    pool.lease(system_id)
+}
+
+/// Count the pool's active and idle connections
+pub async fn pool_stats() -> PoolStats {
+    let pool = SSH_POOL.read().await;
+    pool.stats()
+}
+
+/// Change how many connections the pool keeps open before evicting
+pub async fn set_max_connections(max_connections: usize) {
+    let mut pool = SSH_POOL.write().await;
+    pool.set_max_connections(max_connections);
+}
+
+/// Subscribe to the IDs of systems the pool reconnected after a dropped session
+pub async fn subscribe_reconnects() -> tokio::sync::broadcast::Receiver<String> {
+    let pool = SSH_POOL.read().await;
+    pool.subscribe_reconnects()
+}
+
 /// Validate a connection by running a simple command
 pub async fn validate_connection(system_id: &str) -> Result<bool, ContainerError> {
     let pool = SSH_POOL.read().await;
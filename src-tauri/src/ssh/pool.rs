@@ -1,14 +1,17 @@
 use dashmap::DashMap;
+use serde::Serialize;
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::sync::Mutex;
+use russh::ChannelMsg;
+use tokio::sync::{broadcast, mpsc, Mutex};
+use tokio_stream::wrappers::UnboundedReceiverStream;
 
 use super::client::SshClient;
-use crate::executor::CommandResult;
+use crate::executor::{CommandResult, OutputChunk, OutputStream};
 use crate::keyring_store::JumpHostCredentials;
 use crate::models::error::ContainerError;
-use crate::models::system::ContainerSystem;
+use crate::models::system::{ContainerSystem, DEFAULT_COMMAND_TIMEOUT_SECS};
 
 /// Configuration for the SSH connection pool
 pub struct PoolConfig {
@@ -18,6 +21,8 @@ pub struct PoolConfig {
     pub max_idle_time: Duration,
     /// Connection timeout (default: 30 seconds)
     pub connection_timeout: Duration,
+    /// Open connections before the least recently used idle one is evicted (default: 16)
+    pub max_connections: usize,
 }
 
 impl Default for PoolConfig {
@@ -26,37 +31,173 @@ impl Default for PoolConfig {
             keep_alive_interval: Duration::from_secs(30),
             max_idle_time: Duration::from_secs(300),
             connection_timeout: Duration::from_secs(30),
+            max_connections: 16,
         }
     }
 }
 
+/// Snapshot of what the pool currently holds open
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolStats {
+    /// Connections running a command or backing a terminal/port forward
+    pub active: usize,
+    /// Connections that could be evicted right now
+    pub idle: usize,
+    pub max_connections: usize,
+}
+
+/// Marks a pooled connection as backing a long-lived user (terminal session,
+/// port forward) so eviction skips it. Released on drop.
+pub struct ConnectionLease {
+    system_id: String,
+    leases: Arc<DashMap<String, usize>>,
+}
+
+impl Drop for ConnectionLease {
+    fn drop(&mut self) {
+        self.leases.remove_if_mut(&self.system_id, |_, count| {
+            *count -= 1;
+            *count == 0
+        });
+    }
+}
+
+type ConnectionMap = DashMap<String, Arc<Mutex<SshClient>>>;
+
+/// Everything needed to rebuild a session after the connection drops
+#[derive(Clone)]
+struct ConnectParams {
+    system: ContainerSystem,
+    password: Option<String>,
+    passphrase: Option<String>,
+    private_key_content: Option<String>,
+    jump_host_creds: HashMap<String, JumpHostCredentials>,
+}
+
 /// SSH connection pool managing multiple SSH connections
 pub struct SshConnectionPool {
-    connections: DashMap<String, Arc<Mutex<SshClient>>>,
+    connections: Arc<ConnectionMap>,
     config: PoolConfig,
+    /// Connect parameters of every system connected through the pool, kept
+    /// until an explicit disconnect so dropped sessions can be rebuilt
+    connect_params: DashMap<String, ConnectParams>,
+    /// Serializes reconnect attempts so concurrent failures rebuild only once
+    reconnect_lock: Mutex<()>,
+    /// Announces the ID of each system that was transparently reconnected
+    reconnected_tx: broadcast::Sender<String>,
+    /// When each pooled connection was last handed out, for LRU eviction
+    last_used: DashMap<String, Instant>,
+    /// Outstanding leases per system
+    leases: Arc<DashMap<String, usize>>,
 }
 
 impl SshConnectionPool {
     pub fn new() -> Self {
-        Self {
-            connections: DashMap::new(),
-            config: PoolConfig::default(),
-        }
+        Self::with_config(PoolConfig::default())
     }
 
     pub fn with_config(config: PoolConfig) -> Self {
+        let (reconnected_tx, _) = broadcast::channel(16);
         Self {
-            connections: DashMap::new(),
+            connections: Arc::new(DashMap::new()),
             config,
+            connect_params: DashMap::new(),
+            reconnect_lock: Mutex::new(()),
+            reconnected_tx,
+            last_used: DashMap::new(),
+            leases: Arc::new(DashMap::new()),
+        }
+    }
+
+    /// Change the connection limit; takes effect on the next connect
+    pub fn set_max_connections(&mut self, max_connections: usize) {
+        self.config.max_connections = max_connections.max(1);
+    }
+
+    /// Keep a system's connection from being evicted while the lease is held
+    pub fn lease(&self, system_id: &str) -> ConnectionLease {
+        *self.leases.entry(system_id.to_string()).or_insert(0) += 1;
+        ConnectionLease {
+            system_id: system_id.to_string(),
+            leases: self.leases.clone(),
+        }
+    }
+
+    fn is_leased(&self, system_id: &str) -> bool {
+        self.leases.contains_key(system_id)
+    }
+
+    /// Whether a pooled connection can be closed without disturbing anyone
+    fn is_idle(&self, system_id: &str, client: &Arc<Mutex<SshClient>>) -> bool {
+        !self.is_leased(system_id) && client.try_lock().is_ok()
+    }
+
+    fn touch(&self, system_id: &str) {
+        self.last_used.insert(system_id.to_string(), Instant::now());
+    }
+
+    /// Count active and idle connections
+    pub fn stats(&self) -> PoolStats {
+        let idle = self
+            .connections
+            .iter()
+            .filter(|entry| self.is_idle(entry.key(), entry.value()))
+            .count();
+        PoolStats {
+            active: self.connections.len() - idle,
+            idle,
+            max_connections: self.config.max_connections,
+        }
+    }
+
+    /// Evict the least recently used idle connection if adding `incoming` would exceed the limit
+    ///
+    /// Evicted systems keep their connect parameters, so their next command
+    /// reconnects transparently. When every connection is busy the limit is
+    /// exceeded rather than breaking an active user.
+    fn make_room(&self, incoming: &str) {
+        if self.connections.contains_key(incoming)
+            || self.connections.len() < self.config.max_connections
+        {
+            return;
+        }
+
+        let victim = self
+            .connections
+            .iter()
+            .filter(|entry| self.is_idle(entry.key(), entry.value()))
+            .min_by_key(|entry| self.last_used.get(entry.key()).map(|t| *t))
+            .map(|entry| entry.key().clone());
+
+        match victim {
+            Some(system_id) => {
+                self.connections.remove(&system_id);
+                self.last_used.remove(&system_id);
+                tracing::info!(
+                    "Connection pool full ({}), evicted least recently used system {}",
+                    self.config.max_connections,
+                    system_id
+                );
+            }
+            None => tracing::warn!(
+                "Connection pool over its limit of {}: every connection is in use",
+                self.config.max_connections
+            ),
         }
     }
 
+    /// Subscribe to the IDs of systems the pool reconnected on its own
+    pub fn subscribe_reconnects(&self) -> broadcast::Receiver<String> {
+        self.reconnected_tx.subscribe()
+    }
+
     /// Connect to a system and add to the pool
     /// password: Optional password for authentication (used on mobile)
     /// passphrase: Optional passphrase for SSH key authentication (used on mobile)
     /// private_key_content: Optional PEM-encoded private key content (for mobile/imported keys)
     pub async fn connect(
-        &mut self,
+        &self,
         system: &ContainerSystem,
         password: Option<&str>,
         passphrase: Option<&str>,
@@ -71,7 +212,31 @@ impl SshConnectionPool {
             return Ok(());
         }
 
-        // Create new connection - route through proxy methods if configured
+        let params = ConnectParams {
+            system: system.clone(),
+            password: password.map(str::to_string),
+            passphrase: passphrase.map(str::to_string),
+            private_key_content: private_key_content.map(str::to_string),
+            jump_host_creds: jump_host_creds.clone(),
+        };
+
+        let client = Self::open_client(&params).await?;
+        self.insert_client(&system_id, system, client);
+        self.connect_params.insert(system_id.clone(), params);
+
+        tracing::info!("Added connection for system {} to pool", system_id);
+        Ok(())
+    }
+
+    /// Open a new SSH session, routing through proxy methods if configured
+    async fn open_client(params: &ConnectParams) -> Result<SshClient, ContainerError> {
+        let system = &params.system;
+        let system_id = &system.id.0;
+        let password = params.password.as_deref();
+        let passphrase = params.passphrase.as_deref();
+        let private_key_content = params.private_key_content.as_deref();
+        let jump_host_creds = &params.jump_host_creds;
+
         let client = if let Some(ssh_config) = &system.ssh_config {
             if let Some(ref jump_hosts) = ssh_config.proxy_jump {
                 if !jump_hosts.is_empty() {
@@ -90,15 +255,136 @@ impl SshConnectionPool {
             SshClient::connect(system, password, passphrase, private_key_content).await?
         };
 
-        self.connections
-            .insert(system_id.clone(), Arc::new(Mutex::new(client)));
+        Ok(client)
+    }
 
-        tracing::info!("Added connection for system {} to pool", system_id);
-        Ok(())
+    /// Pool a freshly opened client and start watching its keepalives
+    fn insert_client(
+        &self,
+        system_id: &str,
+        system: &ContainerSystem,
+        client: SshClient,
+    ) -> Arc<Mutex<SshClient>> {
+        self.make_room(system_id);
+        let client = Arc::new(Mutex::new(client));
+        self.connections.insert(system_id.to_string(), client.clone());
+        self.touch(system_id);
+
+        let keepalive_secs = system
+            .ssh_config
+            .as_ref()
+            .map(|c| c.keepalive_interval_secs)
+            .unwrap_or(self.config.keep_alive_interval.as_secs());
+        if keepalive_secs > 0 {
+            Self::spawn_keepalive_watch(
+                self.connections.clone(),
+                system_id.to_string(),
+                client.clone(),
+                Duration::from_secs(keepalive_secs),
+            );
+        }
+
+        client
+    }
+
+    /// Rebuild the session for a system from its cached connect parameters
+    ///
+    /// `stale` is the client that just failed; if another caller already
+    /// replaced it with a live one, that client is returned instead.
+    async fn reconnect(
+        &self,
+        system_id: &str,
+        stale: Option<&Arc<Mutex<SshClient>>>,
+    ) -> Result<Arc<Mutex<SshClient>>, ContainerError> {
+        let _guard = self.reconnect_lock.lock().await;
+
+        if let Some(current) = self.get_client(system_id) {
+            let replaced = stale.is_none_or(|stale| !Arc::ptr_eq(stale, &current));
+            let closed = current.try_lock().is_ok_and(|client| client.is_closed());
+            if replaced && !closed {
+                return Ok(current);
+            }
+        }
+
+        let params = self
+            .connect_params
+            .get(system_id)
+            .map(|entry| entry.value().clone())
+            .ok_or_else(|| ContainerError::NotConnected(system_id.to_string()))?;
+
+        tracing::info!("Reconnecting to system {}", system_id);
+        let client = Self::open_client(&params).await?;
+        let client = self.insert_client(system_id, &params.system, client);
+
+        let _ = self.reconnected_tx.send(system_id.to_string());
+        tracing::info!("Reconnected to system {}", system_id);
+        Ok(client)
+    }
+
+    /// Whether a failed command points at a dead session rather than the command itself
+    fn is_connection_lost(error: &ContainerError, session_closed: bool) -> bool {
+        session_closed
+            || matches!(error, ContainerError::ConnectionFailed(..))
+            || matches!(error, ContainerError::Internal(msg) if msg.starts_with("Failed to open SSH channel"))
+    }
+
+    /// Watch a pooled connection and evict it once keepalives stop being answered
+    ///
+    /// The keepalives themselves are sent by russh (see `SshClient` config); after
+    /// too many missed replies it closes the session, and this task removes the
+    /// dead client so the next call reconnects instead of failing on a stale one.
+    fn spawn_keepalive_watch(
+        connections: Arc<ConnectionMap>,
+        system_id: String,
+        client: Arc<Mutex<SshClient>>,
+        interval: Duration,
+    ) {
+        tracing::debug!(
+            "Starting keepalive watch for system {} (every {:?})",
+            system_id,
+            interval
+        );
+
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.tick().await; // first tick completes immediately
+
+            loop {
+                ticker.tick().await;
+
+                // Stop once this client was disconnected or replaced
+                let still_pooled = connections
+                    .get(&system_id)
+                    .is_some_and(|entry| Arc::ptr_eq(entry.value(), &client));
+                if !still_pooled {
+                    tracing::debug!("Keepalive watch for system {} stopped", system_id);
+                    return;
+                }
+
+                // A busy client is in use, so its session is evidently alive
+                let closed = match client.try_lock() {
+                    Ok(guard) => guard.is_closed(),
+                    Err(_) => false,
+                };
+
+                if closed {
+                    connections.remove_if(&system_id, |_, entry| Arc::ptr_eq(entry, &client));
+                    tracing::debug!(
+                        "SSH connection for system {} stopped answering keepalives; removed from pool",
+                        system_id
+                    );
+                    return;
+                }
+
+                tracing::debug!("Keepalive check passed for system {}", system_id);
+            }
+        });
     }
 
     /// Disconnect from a system and remove from the pool
     pub async fn disconnect(&mut self, system_id: &str) -> Result<(), ContainerError> {
+        self.connect_params.remove(system_id);
+        self.last_used.remove(system_id);
         if let Some((_, _client)) = self.connections.remove(system_id) {
             tracing::info!("Disconnected from system {}", system_id);
         }
@@ -110,19 +396,159 @@ impl SshConnectionPool {
         self.connections.contains_key(system_id)
     }
 
-    /// Execute a command on a connected system
+    /// Execute a command on a connected system within the system's command time limit
     pub async fn execute(
         &self,
         system_id: &str,
         command: &str,
     ) -> Result<CommandResult, ContainerError> {
-        let client = self
-            .connections
-            .get(system_id)
-            .ok_or_else(|| ContainerError::SystemNotFound(system_id.to_string()))?;
+        let limit = match self.connect_params.get(system_id) {
+            Some(params) => params.system.command_timeout(),
+            None => Some(Duration::from_secs(DEFAULT_COMMAND_TIMEOUT_SECS)),
+        };
+        self.execute_with_timeout(system_id, command, limit).await
+    }
 
-        let mut client_guard = client.lock().await;
-        client_guard.execute(command).await
+    /// Execute a command on a connected system, giving up after `limit` (None waits indefinitely)
+    pub async fn execute_with_timeout(
+        &self,
+        system_id: &str,
+        command: &str,
+        limit: Option<Duration>,
+    ) -> Result<CommandResult, ContainerError> {
+        let Some(limit) = limit else {
+            return self.execute_unbounded(system_id, command).await;
+        };
+        tokio::time::timeout(limit, self.execute_unbounded(system_id, command))
+            .await
+            .unwrap_or_else(|_| {
+                Err(ContainerError::NetworkTimeout(format!(
+                    "SSH command timed out after {}ms: {}",
+                    limit.as_millis(),
+                    command
+                )))
+            })
+    }
+
+    /// Execute a command on a connected system with no time limit
+    ///
+    /// If the pooled session has dropped (evicted, channel open failure, or
+    /// EOF mid-command), the pool reconnects once with the cached connect
+    /// parameters and retries the command before giving up.
+    async fn execute_unbounded(
+        &self,
+        system_id: &str,
+        command: &str,
+    ) -> Result<CommandResult, ContainerError> {
+        let client = match self.get_client(system_id) {
+            Some(client) => client,
+            None if self.connect_params.contains_key(system_id) => {
+                self.reconnect(system_id, None).await?
+            }
+            None => return Err(ContainerError::SystemNotFound(system_id.to_string())),
+        };
+        self.touch(system_id);
+
+        let (result, session_closed) = {
+            let mut client_guard = client.lock().await;
+            let result = client_guard.execute(command).await;
+            (result, client_guard.is_closed())
+        };
+
+        match result {
+            Err(e)
+                if Self::is_connection_lost(&e, session_closed)
+                    && self.connect_params.contains_key(system_id) =>
+            {
+                tracing::warn!("SSH connection to system {} lost: {}", system_id, e);
+                let client = self.reconnect(system_id, Some(&client)).await.map_err(|reconnect_err| {
+                    tracing::warn!("Reconnect to system {} failed: {}", system_id, reconnect_err);
+                    e
+                })?;
+                let mut client_guard = client.lock().await;
+                client_guard.execute(command).await
+            }
+            result => result,
+        }
+    }
+
+    /// Start a command on a connected system and stream its output as it arrives
+    ///
+    /// The client lock is only held while the channel is opened; the connection
+    /// stays leased until the command exits or the stream is dropped, at which
+    /// point the channel is closed.
+    pub async fn execute_stream(
+        &self,
+        system_id: &str,
+        command: &str,
+    ) -> Result<OutputStream, ContainerError> {
+        let client = match self.get_client(system_id) {
+            Some(client) => client,
+            None if self.connect_params.contains_key(system_id) => {
+                self.reconnect(system_id, None).await?
+            }
+            None => return Err(ContainerError::SystemNotFound(system_id.to_string())),
+        };
+
+        let (result, session_closed) = {
+            let mut client_guard = client.lock().await;
+            let result = client_guard.open_exec_channel(command).await;
+            (result, client_guard.is_closed())
+        };
+
+        let mut channel = match result {
+            Err(e)
+                if Self::is_connection_lost(&e, session_closed)
+                    && self.connect_params.contains_key(system_id) =>
+            {
+                tracing::warn!("SSH connection to system {} lost: {}", system_id, e);
+                let client = self.reconnect(system_id, Some(&client)).await.map_err(|reconnect_err| {
+                    tracing::warn!("Reconnect to system {} failed: {}", system_id, reconnect_err);
+                    e
+                })?;
+                let mut client_guard = client.lock().await;
+                client_guard.open_exec_channel(command).await?
+            }
+            result => result?,
+        };
+
+        let lease = self.lease(system_id);
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            let _lease = lease;
+            // The exit status can arrive before the last data, so it is sent once the channel closes
+            let mut exit_code = -1;
+            loop {
+                let msg = tokio::select! {
+                    msg = channel.wait() => msg,
+                    _ = tx.closed() => {
+                        let _ = channel.close().await;
+                        return;
+                    }
+                };
+                let chunk = match msg {
+                    Some(ChannelMsg::Data { data }) => {
+                        OutputChunk::Stdout(String::from_utf8_lossy(&data).to_string())
+                    }
+                    Some(ChannelMsg::ExtendedData { data, ext: 1 }) => {
+                        OutputChunk::Stderr(String::from_utf8_lossy(&data).to_string())
+                    }
+                    Some(ChannelMsg::ExitStatus { exit_status }) => {
+                        exit_code = exit_status as i32;
+                        continue;
+                    }
+                    Some(ChannelMsg::Close) | None => break,
+                    _ => continue,
+                };
+                if tx.send(chunk).is_err() {
+                    let _ = channel.close().await;
+                    return;
+                }
+            }
+            let _ = tx.send(OutputChunk::Exit(exit_code));
+        });
+
+        Ok(Box::pin(UnboundedReceiverStream::new(rx)))
     }
 
     /// Validate a connection by running a simple command
@@ -157,6 +583,9 @@ impl SshConnectionPool {
         let mut to_remove = Vec::new();
 
         for entry in self.connections.iter() {
+            if self.is_leased(entry.key()) {
+                continue;
+            }
             let client = entry.value().lock().await;
             if now.duration_since(client.last_used()) > max_idle {
                 to_remove.push(entry.key().clone());
@@ -201,7 +630,9 @@ impl SshConnectionPool {
     /// Get a reference to an SSH client by system ID
     /// Used for terminal sessions that need direct access to the client
     pub fn get_client(&self, system_id: &str) -> Option<Arc<Mutex<SshClient>>> {
-        self.connections.get(system_id).map(|r| r.value().clone())
+        let client = self.connections.get(system_id).map(|r| r.value().clone())?;
+        self.touch(system_id);
+        Some(client)
     }
 }
 
@@ -1,10 +1,14 @@
 use dashmap::DashMap;
 use tokio::net::TcpListener;
-use tokio::sync::broadcast;
+use tokio::sync::{broadcast, mpsc};
 use tokio_util::sync::CancellationToken;
 
 use crate::models::error::ContainerError;
-use crate::models::port_forward::{PortForward, PortForwardStatus};
+use crate::models::port_forward::{ForwardDirection, PortForward, PortForwardStatus};
+use super::client::ForwardedChannel;
+
+/// Address remote forwards listen on, matching the `ssh -R` default of loopback only
+const REMOTE_BIND_ADDRESS: &str = "localhost";
 
 /// Manages active port forwards
 pub struct PortForwardManager {
@@ -142,8 +146,11 @@ impl PortForwardManager {
         // Spawn the listener task
         let forward_id = forward.id.clone();
         let forwards = self.forwards.clone();
+        let lease = super::lease_connection(&system_id).await;
 
         tokio::spawn(async move {
+            // Keep the pool from evicting the connection this forward tunnels over
+            let _lease = lease;
             Self::run_listener(
                 listener,
                 system_id,
@@ -219,15 +226,13 @@ impl PortForwardManager {
 
     /// Handle a single incoming connection by forwarding through SSH
     async fn handle_connection(
-        mut local_socket: tokio::net::TcpStream,
+        local_socket: tokio::net::TcpStream,
         system_id: &str,
         remote_host: &str,
         remote_port: u16,
         cancel_token: CancellationToken,
     ) -> Result<(), ContainerError> {
-        use russh::ChannelMsg;
         use std::time::Duration;
-        use tokio::io::{AsyncReadExt, AsyncWriteExt};
         use tokio::time::timeout;
 
         tracing::debug!(
@@ -259,7 +264,7 @@ impl PortForwardManager {
 
         // Open direct-tcpip channel WITH TIMEOUT
         // This is equivalent to: ssh -L local_port:remote_host:remote_port
-        let mut channel = timeout(
+        let channel = timeout(
             Duration::from_secs(10),
             client.session.channel_open_direct_tcpip(
                 remote_host,
@@ -289,6 +294,19 @@ impl PortForwardManager {
         drop(client);
         tracing::debug!("[PORT_FWD] Channel opened, starting data relay");
 
+        Self::relay(local_socket, channel, cancel_token).await;
+        Ok(())
+    }
+
+    /// Shuttle bytes between a TCP socket and an SSH channel until either side closes
+    async fn relay(
+        mut local_socket: tokio::net::TcpStream,
+        mut channel: russh::Channel<russh::client::Msg>,
+        cancel_token: CancellationToken,
+    ) {
+        use russh::ChannelMsg;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
         // Split TCP socket for concurrent read/write
         let (mut tcp_reader, mut tcp_writer) = local_socket.split();
 
@@ -374,8 +392,136 @@ impl PortForwardManager {
             bytes_from_client,
             bytes_from_server
         );
+    }
 
-        Ok(())
+    /// Start a remote (reverse) forward: the server listens on `remote_bind_port`
+    /// and each connection is tunnelled back to `local_host:local_port`
+    pub async fn start_remote_forward(
+        &self,
+        system_id: String,
+        remote_bind_port: u16,
+        local_host: String,
+        local_port: u16,
+    ) -> Result<PortForward, ContainerError> {
+        if remote_bind_port != 0
+            && self.forwards.iter().any(|entry| {
+                let f = &entry.forward;
+                f.direction == ForwardDirection::Remote
+                    && f.system_id == system_id
+                    && f.remote_port == remote_bind_port
+                    && f.status == PortForwardStatus::Active
+            })
+        {
+            return Err(ContainerError::Internal(format!(
+                "Remote port {} is already forwarded on system {}",
+                remote_bind_port, system_id
+            )));
+        }
+
+        let client_arc = super::get_pool()
+            .read()
+            .await
+            .get_client(&system_id)
+            .ok_or_else(|| ContainerError::NotConnected(system_id.clone()))?;
+
+        let (bound_port, incoming) = client_arc
+            .lock()
+            .await
+            .request_remote_forward(REMOTE_BIND_ADDRESS, remote_bind_port as u32)
+            .await?;
+
+        let forward = PortForward::remote(
+            system_id.clone(),
+            REMOTE_BIND_ADDRESS.to_string(),
+            bound_port as u16,
+            local_host.clone(),
+            local_port,
+        );
+
+        let (shutdown_tx, _) = broadcast::channel(1);
+        let cancel_token = CancellationToken::new();
+        self.forwards.insert(
+            forward.id.clone(),
+            PortForwardEntry {
+                forward: forward.clone(),
+                shutdown_tx,
+                cancel_token: cancel_token.clone(),
+            },
+        );
+
+        let forward_id = forward.id.clone();
+        let forwards = self.forwards.clone();
+        let lease = super::lease_connection(&system_id).await;
+
+        tokio::spawn(async move {
+            let _lease = lease;
+            Self::run_remote_listener(incoming, local_host, local_port, &forward_id, cancel_token).await;
+
+            // Stop the server listening; the connection may already be gone
+            if let Some(client) = super::get_pool().read().await.get_client(&system_id) {
+                if let Err(e) = client
+                    .lock()
+                    .await
+                    .cancel_remote_forward(REMOTE_BIND_ADDRESS, bound_port)
+                    .await
+                {
+                    tracing::warn!("{}", e);
+                }
+            }
+
+            if let Some(mut entry) = forwards.get_mut(&forward_id) {
+                entry.forward.status = PortForwardStatus::Stopped;
+            }
+        });
+
+        tracing::info!(
+            "Remote forward {} listening on {}:{} -> {}:{}",
+            forward.id,
+            REMOTE_BIND_ADDRESS,
+            bound_port,
+            forward.local_host,
+            local_port
+        );
+        Ok(forward)
+    }
+
+    /// Accept channels the server opens for a remote forward until stopped
+    async fn run_remote_listener(
+        mut incoming: mpsc::UnboundedReceiver<ForwardedChannel>,
+        local_host: String,
+        local_port: u16,
+        forward_id: &str,
+        cancel_token: CancellationToken,
+    ) {
+        loop {
+            tokio::select! {
+                channel = incoming.recv() => {
+                    let Some(channel) = channel else {
+                        tracing::info!("Remote forward {} lost its SSH connection", forward_id);
+                        break;
+                    };
+
+                    let local_host = local_host.clone();
+                    let token = cancel_token.clone();
+                    tokio::spawn(async move {
+                        match tokio::net::TcpStream::connect((local_host.as_str(), local_port)).await {
+                            Ok(socket) => Self::relay(socket, channel, token).await,
+                            Err(e) => {
+                                tracing::error!(
+                                    "[PORT_FWD] Failed to connect to {}:{}: {}",
+                                    local_host, local_port, e
+                                );
+                                let _ = channel.close().await;
+                            }
+                        }
+                    });
+                }
+                _ = cancel_token.cancelled() => {
+                    tracing::info!("Remote forward {} shutting down", forward_id);
+                    break;
+                }
+            }
+        }
     }
 
     /// Stop a port forward
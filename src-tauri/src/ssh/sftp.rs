@@ -0,0 +1,301 @@
+//! SFTP file transfers over pooled SSH connections
+//!
+//! Bytes move through the SFTP subsystem instead of base64 over exec, so
+//! binary files arrive intact and large transfers are copied chunk by chunk
+//! rather than buffered in memory.
+
+use russh_sftp::client::error::Error as SftpError;
+use russh_sftp::client::SftpSession;
+use russh_sftp::protocol::StatusCode;
+use std::io::SeekFrom;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncSeekExt, AsyncWrite, AsyncWriteExt};
+
+use crate::models::error::ContainerError;
+
+/// Size of each read/write while copying a file
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Minimum number of bytes between two progress reports
+const PROGRESS_INTERVAL: u64 = 1024 * 1024;
+
+/// Open an SFTP session on the pooled connection for a system
+pub async fn open_session(system_id: &str) -> Result<SftpSession, ContainerError> {
+    let client = super::get_pool()
+        .read()
+        .await
+        .get_client(system_id)
+        .ok_or_else(|| ContainerError::NotConnected(system_id.to_string()))?;
+
+    let mut client = client.lock().await;
+    client.open_sftp().await
+}
+
+/// Read a whole remote file, refusing files larger than `max_size` bytes
+pub async fn read_file(
+    sftp: &SftpSession,
+    path: &str,
+    max_size: u64,
+) -> Result<Vec<u8>, ContainerError> {
+    let size = sftp
+        .metadata(path)
+        .await
+        .map_err(|e| map_sftp_error(path, e))?
+        .len();
+    if size > max_size {
+        return Err(ContainerError::InvalidOperation {
+            message: format!(
+                "File is too large to edit in-app ({} bytes, max {} bytes)",
+                size, max_size
+            ),
+        });
+    }
+
+    sftp.read(path).await.map_err(|e| map_sftp_error(path, e))
+}
+
+/// Size of a remote file in bytes
+pub async fn file_size(sftp: &SftpSession, path: &str) -> Result<u64, ContainerError> {
+    Ok(sftp
+        .metadata(path)
+        .await
+        .map_err(|e| map_sftp_error(path, e))?
+        .len())
+}
+
+/// Read up to `length` bytes of a remote file starting at `offset`
+///
+/// The offset is clamped to the file size. Returns the file size, the
+/// clamped offset and the bytes read.
+pub async fn read_range(
+    sftp: &SftpSession,
+    path: &str,
+    offset: u64,
+    length: u64,
+) -> Result<(u64, u64, Vec<u8>), ContainerError> {
+    let mut file = sftp.open(path).await.map_err(|e| map_sftp_error(path, e))?;
+    let size = file
+        .metadata()
+        .await
+        .map_err(|e| map_sftp_error(path, e))?
+        .len();
+    let offset = offset.min(size);
+
+    let read_err =
+        |e: std::io::Error| ContainerError::Internal(format!("Failed to read {}: {}", path, e));
+    file.seek(SeekFrom::Start(offset)).await.map_err(read_err)?;
+    let mut bytes = Vec::new();
+    file.take(length)
+        .read_to_end(&mut bytes)
+        .await
+        .map_err(read_err)?;
+    Ok((size, offset, bytes))
+}
+
+/// Create or truncate a remote file and write `data` to it
+pub async fn write_file(sftp: &SftpSession, path: &str, data: &[u8]) -> Result<(), ContainerError> {
+    let mut file = sftp.create(path).await.map_err(|e| map_sftp_error(path, e))?;
+    file.write_all(data)
+        .await
+        .map_err(|e| ContainerError::Internal(format!("Failed to write {}: {}", path, e)))?;
+    file.shutdown()
+        .await
+        .map_err(|e| ContainerError::Internal(format!("Failed to close {}: {}", path, e)))
+}
+
+/// Stream a remote file to a local path
+///
+/// `on_progress` receives (bytes copied, total bytes) roughly every megabyte
+/// and once at the end. Returns the number of bytes copied.
+pub async fn download(
+    sftp: &SftpSession,
+    remote_path: &str,
+    local_path: &str,
+    mut on_progress: impl FnMut(u64, u64),
+) -> Result<u64, ContainerError> {
+    let mut remote = sftp
+        .open(remote_path)
+        .await
+        .map_err(|e| map_sftp_error(remote_path, e))?;
+    let total = remote
+        .metadata()
+        .await
+        .map_err(|e| map_sftp_error(remote_path, e))?
+        .len();
+    let mut local = tokio::fs::File::create(local_path)
+        .await
+        .map_err(|e| ContainerError::Internal(format!("Failed to write local file: {}", e)))?;
+
+    copy_with_progress(&mut remote, &mut local, total, &mut on_progress)
+        .await
+        .map_err(|e| ContainerError::Internal(format!("Download of {} failed: {}", remote_path, e)))
+}
+
+/// Stream a local file to a remote path, replacing any existing file
+///
+/// Progress is reported like [`download`]. Returns the number of bytes copied.
+pub async fn upload(
+    sftp: &SftpSession,
+    local_path: &str,
+    remote_path: &str,
+    mut on_progress: impl FnMut(u64, u64),
+) -> Result<u64, ContainerError> {
+    let mut local = tokio::fs::File::open(local_path)
+        .await
+        .map_err(|e| ContainerError::Internal(format!("Failed to read local file: {}", e)))?;
+    let total = local
+        .metadata()
+        .await
+        .map_err(|e| ContainerError::Internal(format!("Failed to read local file: {}", e)))?
+        .len();
+    let mut remote = sftp
+        .create(remote_path)
+        .await
+        .map_err(|e| map_sftp_error(remote_path, e))?;
+
+    copy_with_progress(&mut local, &mut remote, total, &mut on_progress)
+        .await
+        .map_err(|e| ContainerError::Internal(format!("Upload to {} failed: {}", remote_path, e)))
+}
+
+/// Stream a file from one system's SFTP session to another's
+///
+/// The copy is written beside `dst_path` and only renamed over it once
+/// complete, so a failed transfer never leaves a truncated file in its place.
+/// Progress is reported like [`download`]. Returns the number of bytes copied.
+pub async fn transfer(
+    src: &SftpSession,
+    src_path: &str,
+    dst: &SftpSession,
+    dst_path: &str,
+    mut on_progress: impl FnMut(u64, u64),
+) -> Result<u64, ContainerError> {
+    let mut source = src
+        .open(src_path)
+        .await
+        .map_err(|e| map_sftp_error(src_path, e))?;
+    let total = source
+        .metadata()
+        .await
+        .map_err(|e| map_sftp_error(src_path, e))?
+        .len();
+
+    let partial = partial_path(dst_path);
+    let mut dest = dst
+        .create(partial.as_str())
+        .await
+        .map_err(|e| map_sftp_error(dst_path, e))?;
+
+    let copied = match copy_with_progress(&mut source, &mut dest, total, &mut on_progress).await {
+        Ok(copied) => copied,
+        Err(e) => {
+            let _ = dst.remove_file(partial.as_str()).await;
+            return Err(ContainerError::Internal(format!(
+                "Transfer of {} failed: {}",
+                src_path, e
+            )));
+        }
+    };
+
+    // SFTP v3 servers refuse to rename over an existing file
+    if dst.try_exists(dst_path).await.unwrap_or(false) {
+        if let Err(e) = dst.remove_file(dst_path).await {
+            let _ = dst.remove_file(partial.as_str()).await;
+            return Err(map_sftp_error(dst_path, e));
+        }
+    }
+    dst.rename(partial.as_str(), dst_path).await.map_err(|e| {
+        ContainerError::Internal(format!(
+            "Transfer finished but {} could not be renamed to {}: {}",
+            partial, dst_path, e
+        ))
+    })?;
+    Ok(copied)
+}
+
+/// Hidden file next to `path` that a transfer writes into before renaming
+pub fn partial_path(path: &str) -> String {
+    match path.rsplit_once('/') {
+        Some((dir, name)) => format!("{}/.{}.partial", dir, name),
+        None => format!(".{}.partial", path),
+    }
+}
+
+/// Copy `reader` into `writer` in fixed-size chunks, reporting progress as it goes
+async fn copy_with_progress<R, W>(
+    reader: &mut R,
+    writer: &mut W,
+    total: u64,
+    on_progress: &mut impl FnMut(u64, u64),
+) -> std::io::Result<u64>
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    let mut buf = vec![0u8; CHUNK_SIZE];
+    let mut copied = 0u64;
+    let mut last_reported = 0u64;
+
+    loop {
+        let n = reader.read(&mut buf).await?;
+        if n == 0 {
+            break;
+        }
+        writer.write_all(&buf[..n]).await?;
+        copied += n as u64;
+
+        if copied - last_reported >= PROGRESS_INTERVAL {
+            on_progress(copied, total);
+            last_reported = copied;
+        }
+    }
+
+    writer.shutdown().await?;
+    on_progress(copied, total);
+    Ok(copied)
+}
+
+/// Translate an SFTP failure for `path` into the app's error type
+fn map_sftp_error(path: &str, error: SftpError) -> ContainerError {
+    match error {
+        SftpError::Status(status) if status.status_code == StatusCode::PermissionDenied => {
+            ContainerError::PermissionDenied(path.to_string())
+        }
+        other => ContainerError::Internal(format!("SFTP error on {}: {}", path, other)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partial_path_is_hidden_sibling() {
+        assert_eq!(
+            partial_path("/srv/data/dump.sql"),
+            "/srv/data/.dump.sql.partial"
+        );
+        assert_eq!(partial_path("/dump.sql"), "/.dump.sql.partial");
+    }
+
+    #[tokio::test]
+    async fn test_copy_with_progress_preserves_binary_data() {
+        let data: Vec<u8> = (0..3 * PROGRESS_INTERVAL as usize + 17)
+            .map(|i| (i % 256) as u8)
+            .collect();
+        let mut reader = data.as_slice();
+        let mut writer = Vec::new();
+        let mut reports = Vec::new();
+
+        let copied = copy_with_progress(&mut reader, &mut writer, data.len() as u64, &mut |done, total| {
+            reports.push((done, total))
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(copied, data.len() as u64);
+        assert_eq!(writer, data);
+        assert_eq!(reports.len(), 4);
+        assert_eq!(reports.last(), Some(&(copied, copied)));
+        assert!(reports.windows(2).all(|w| w[0].0 < w[1].0));
+    }
+}
@@ -1,23 +1,47 @@
 use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
-use std::sync::Mutex;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
 
 use rusqlite::Connection;
+use tauri::{AppHandle, Emitter};
 use uuid::Uuid;
 
-use crate::database;
+use crate::credential_crypto::CredentialCipher;
+use crate::database::{self, CommandHistoryEntry};
 use crate::keyring_store::SshCredentials;
-use crate::models::command_template::{CommandTemplate, CreateCommandTemplateRequest, UpdateCommandTemplateRequest};
+use crate::models::command_template::{
+    CommandTemplate, CreateCommandTemplateRequest, TemplateExport, TemplateImportResult,
+    TemplateSortBy, UpdateCommandTemplateRequest,
+};
 use crate::models::container::ContainerRuntime;
 use crate::models::error::ContainerError;
 use crate::models::system::{ConnectionState, ContainerSystem, SystemId};
 
+/// Event emitted whenever a system's connection state changes
+pub const CONNECTION_CHANGED_EVENT: &str = "system:connection-changed";
+
+/// How current the cached systems' runtimes are
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SystemsCache {
+    /// Loaded from the database or changed since the last refresh
+    Stale,
+    Refreshing,
+    Fresh,
+}
+
 pub struct AppState {
     pub db: Mutex<Connection>,
+    /// Systems with their last-known runtimes, served while a refresh runs
     systems: Mutex<Vec<ContainerSystem>>,
+    systems_cache: Mutex<SystemsCache>,
     connection_states: Mutex<HashMap<String, ConnectionState>>,
     ssh_credential_cache: Mutex<HashMap<String, SshCredentials>>,
     ai_key_cache: Mutex<HashMap<String, String>>,
+    /// Key for credentials kept in the database, loaded from the OS keychain at startup
+    credential_cipher: OnceLock<CredentialCipher>,
+    /// Used to notify the frontend of connection state changes, set once the app is running
+    app_handle: OnceLock<AppHandle>,
 }
 
 impl AppState {
@@ -49,9 +73,12 @@ impl AppState {
         Self {
             db: Mutex::new(conn),
             systems: Mutex::new(systems),
+            systems_cache: Mutex::new(SystemsCache::Stale),
             connection_states: Mutex::new(connection_states),
             ssh_credential_cache: Mutex::new(HashMap::new()),
             ai_key_cache: Mutex::new(HashMap::new()),
+            credential_cipher: OnceLock::new(),
+            app_handle: OnceLock::new(),
         }
     }
 }
@@ -71,15 +98,52 @@ impl AppState {
             });
         }
 
-        self.connection_states
-            .lock()
-            .unwrap()
-            .insert(system.id.0.clone(), ConnectionState::Disconnected);
+        self.set_connection_state(&system.id.0, ConnectionState::Disconnected);
 
         self.systems.lock().unwrap().push(system.clone());
+        self.invalidate_systems_cache();
         Ok(system)
     }
 
+    /// Add several systems in one database transaction, so either all of them
+    /// are added or none are
+    pub fn add_systems(
+        &self,
+        systems: Vec<ContainerSystem>,
+    ) -> Result<Vec<ContainerSystem>, ContainerError> {
+        let systems: Vec<ContainerSystem> = systems
+            .into_iter()
+            .map(|mut system| {
+                if system.id.0.trim().is_empty() {
+                    system.id = SystemId(Uuid::new_v4().to_string());
+                }
+                system
+            })
+            .collect();
+
+        let save_error = |e: rusqlite::Error| {
+            tracing::error!("Failed to persist systems to database: {}", e);
+            ContainerError::DatabaseError {
+                message: format!("Failed to save systems: {}", e),
+            }
+        };
+        {
+            let mut db = self.db.lock().unwrap();
+            let tx = db.transaction().map_err(save_error)?;
+            for system in &systems {
+                database::insert_system(&tx, system).map_err(save_error)?;
+            }
+            tx.commit().map_err(save_error)?;
+        }
+
+        for system in &systems {
+            self.set_connection_state(&system.id.0, ConnectionState::Disconnected);
+        }
+        self.systems.lock().unwrap().extend(systems.iter().cloned());
+        self.invalidate_systems_cache();
+        Ok(systems)
+    }
+
     /// Get a system by ID
     pub fn get_system(&self, system_id: &str) -> Option<ContainerSystem> {
         self.systems
@@ -108,6 +172,7 @@ impl AppState {
             }
 
             self.connection_states.lock().unwrap().remove(system_id);
+            self.invalidate_systems_cache();
             true
         } else {
             false
@@ -127,6 +192,7 @@ impl AppState {
 
             // Update in memory
             *system = updated_system.clone();
+            self.invalidate_systems_cache();
             Some(updated_system)
         } else {
             None
@@ -150,12 +216,87 @@ impl AppState {
         }
     }
 
+    /// Remember that a system just connected, so the hint survives restarts
+    pub fn record_connected(&self, system_id: &str) {
+        let now = chrono::Utc::now().timestamp();
+        if let Err(e) =
+            database::update_system_last_connected(&self.db.lock().unwrap(), system_id, now)
+        {
+            tracing::error!("Failed to record last connection in database: {}", e);
+        }
+
+        let mut systems = self.systems.lock().unwrap();
+        if let Some(system) = systems.iter_mut().find(|s| s.id.0 == system_id) {
+            system.last_connected_at = Some(now);
+        }
+    }
+
+    /// Mark the cached systems as needing a refresh
+    pub fn invalidate_systems_cache(&self) {
+        *self.systems_cache.lock().unwrap() = SystemsCache::Stale;
+    }
+
+    /// Claim a refresh of the cached systems; false if they are fresh or
+    /// another refresh is already running
+    pub fn begin_systems_refresh(&self) -> bool {
+        let mut cache = self.systems_cache.lock().unwrap();
+        if *cache != SystemsCache::Stale {
+            return false;
+        }
+        *cache = SystemsCache::Refreshing;
+        true
+    }
+
+    /// Mark the cached systems fresh, unless they changed while refreshing
+    pub fn finish_systems_refresh(&self) {
+        let mut cache = self.systems_cache.lock().unwrap();
+        if *cache == SystemsCache::Refreshing {
+            *cache = SystemsCache::Fresh;
+        }
+    }
+
+    /// Attach the app handle used to emit connection state events
+    pub fn set_app_handle(&self, app: AppHandle) {
+        let _ = self.app_handle.set(app);
+    }
+
     /// Set connection state for a system
     pub fn set_connection_state(&self, system_id: &str, state: ConnectionState) {
-        self.connection_states
+        self.set_connection_state_with_error(system_id, state, None);
+    }
+
+    /// Set connection state for a system, with the error that caused it if any.
+    /// Every connection state change goes through here so the frontend hears
+    /// about each transition exactly once.
+    pub fn set_connection_state_with_error(
+        &self,
+        system_id: &str,
+        state: ConnectionState,
+        error: Option<String>,
+    ) {
+        let previous = self
+            .connection_states
             .lock()
             .unwrap()
-            .insert(system_id.to_string(), state);
+            .insert(system_id.to_string(), state)
+            .unwrap_or(ConnectionState::Disconnected);
+
+        if previous == state {
+            return;
+        }
+
+        if let Some(app) = self.app_handle.get() {
+            if let Err(e) = app.emit(
+                CONNECTION_CHANGED_EVENT,
+                serde_json::json!({
+                    "systemId": system_id,
+                    "state": state,
+                    "error": error,
+                }),
+            ) {
+                tracing::warn!("Failed to emit connection change for {}: {}", system_id, e);
+            }
+        }
     }
 
     /// Get connection state for a system (public API)
@@ -193,6 +334,33 @@ impl AppState {
         })
     }
 
+    /// List all command templates in the requested order
+    pub fn list_command_templates_sorted(&self, sort_by: TemplateSortBy) -> Result<Vec<CommandTemplate>, ContainerError> {
+        let mut templates = self.list_command_templates()?;
+        sort_by.sort(&mut templates);
+        Ok(templates)
+    }
+
+    /// Record an execution of a command template, returning the updated template
+    pub fn record_template_use(&self, id: &str) -> Result<CommandTemplate, ContainerError> {
+        let db = self.db.lock().map_err(|_| ContainerError::DatabaseError {
+            message: "Failed to acquire database lock".to_string(),
+        })?;
+
+        database::record_template_use(&db, id).map_err(|e| ContainerError::DatabaseError {
+            message: e.to_string(),
+        })?;
+
+        database::get_command_template(&db, id)
+            .map_err(|e| ContainerError::DatabaseError {
+                message: e.to_string(),
+            })?
+            .ok_or_else(|| ContainerError::NotFound {
+                resource: "CommandTemplate".to_string(),
+                id: id.to_string(),
+            })
+    }
+
     /// Get a single command template by ID
     pub fn get_command_template(&self, id: &str) -> Result<Option<CommandTemplate>, ContainerError> {
         let db = self.db.lock().map_err(|_| ContainerError::DatabaseError {
@@ -213,6 +381,7 @@ impl AppState {
             name: request.name,
             description: request.description,
             command: request.command,
+            steps: request.steps.filter(|steps| !steps.is_empty()),
             category: request.category,
             tags: request.tags,
             variables: request.variables,
@@ -221,6 +390,8 @@ impl AppState {
             is_built_in: false,
             created_at: now.clone(),
             updated_at: now,
+            use_count: 0,
+            last_used_at: None,
         };
 
         let db = self.db.lock().map_err(|_| ContainerError::DatabaseError {
@@ -257,6 +428,10 @@ impl AppState {
             name: request.name.unwrap_or(existing.name),
             description: request.description.unwrap_or(existing.description),
             command: request.command.unwrap_or(existing.command),
+            steps: match request.steps {
+                Some(steps) => Some(steps).filter(|steps| !steps.is_empty()),
+                None => existing.steps,
+            },
             category: request.category.unwrap_or(existing.category),
             tags: request.tags.unwrap_or(existing.tags),
             variables: request.variables.unwrap_or(existing.variables),
@@ -265,6 +440,8 @@ impl AppState {
             is_built_in: existing.is_built_in,
             created_at: existing.created_at,
             updated_at: now,
+            use_count: existing.use_count,
+            last_used_at: existing.last_used_at,
         };
 
         database::update_command_template(&db, &updated).map_err(|e| ContainerError::DatabaseError {
@@ -342,6 +519,7 @@ impl AppState {
             name: format!("{} (Copy)", existing.name),
             description: existing.description,
             command: existing.command,
+            steps: existing.steps,
             category: existing.category,
             tags: existing.tags,
             variables: existing.variables,
@@ -350,6 +528,8 @@ impl AppState {
             is_built_in: false, // Duplicates are never built-in
             created_at: now.clone(),
             updated_at: now,
+            use_count: 0,
+            last_used_at: None,
         };
 
         database::insert_command_template(&db, &duplicate).map_err(|e| ContainerError::DatabaseError {
@@ -359,6 +539,149 @@ impl AppState {
         Ok(duplicate)
     }
 
+    /// Export custom templates (all of them, or only `ids`) as a versioned JSON document.
+    /// Built-in templates are never exported since every install already has them.
+    pub fn export_command_templates(&self, ids: Option<Vec<String>>) -> Result<String, ContainerError> {
+        let templates = self
+            .list_command_templates()?
+            .into_iter()
+            .filter(|t| !t.is_built_in)
+            .filter(|t| ids.as_ref().is_none_or(|ids| ids.contains(&t.id)))
+            .collect();
+
+        serde_json::to_string_pretty(&TemplateExport::new(templates))
+            .map_err(|e| ContainerError::Internal(e.to_string()))
+    }
+
+    /// Import templates from an export document, skipping or overwriting same-named templates
+    pub fn import_command_templates(&self, json: &str, overwrite: bool) -> Result<TemplateImportResult, ContainerError> {
+        let export = TemplateExport::from_json(json)?;
+
+        let db = self.db.lock().map_err(|_| ContainerError::DatabaseError {
+            message: "Failed to acquire database lock".to_string(),
+        })?;
+
+        database::import_command_templates(&db, export.templates, overwrite).map_err(|e| {
+            ContainerError::DatabaseError {
+                message: e.to_string(),
+            }
+        })
+    }
+
+    // ============================================================================
+    // Command History Methods
+    // ============================================================================
+
+    /// Record a finished command in the persistent history. History is best-effort,
+    /// so failures are logged rather than surfaced to the caller.
+    pub fn record_command_history(
+        &self,
+        system_id: &str,
+        command: &str,
+        exit_code: Option<i32>,
+        duration: Duration,
+    ) {
+        let Ok(db) = self.db.lock() else {
+            return;
+        };
+
+        if let Err(e) = database::record_command_history(
+            &db,
+            system_id,
+            command,
+            exit_code,
+            duration.as_millis() as u64,
+            database::MAX_HISTORY_PER_SYSTEM,
+        ) {
+            tracing::warn!("Failed to record command history: {}", e);
+        }
+    }
+
+    /// Get recent command history, newest first
+    pub fn get_command_history(
+        &self,
+        system_id: Option<&str>,
+        limit: usize,
+        search: Option<&str>,
+    ) -> Result<Vec<CommandHistoryEntry>, ContainerError> {
+        let db = self.db.lock().map_err(|_| ContainerError::DatabaseError {
+            message: "Failed to acquire database lock".to_string(),
+        })?;
+
+        database::get_command_history(&db, system_id, limit, search).map_err(|e| {
+            ContainerError::DatabaseError {
+                message: e.to_string(),
+            }
+        })
+    }
+
+    /// Clear command history for one system, or all systems when `system_id` is `None`
+    pub fn clear_command_history(&self, system_id: Option<&str>) -> Result<usize, ContainerError> {
+        let db = self.db.lock().map_err(|_| ContainerError::DatabaseError {
+            message: "Failed to acquire database lock".to_string(),
+        })?;
+
+        database::clear_command_history(&db, system_id).map_err(|e| ContainerError::DatabaseError {
+            message: e.to_string(),
+        })
+    }
+
+    // ============================================================================
+    // Database Backup Methods
+    // ============================================================================
+
+    /// Write a consistent snapshot of the database to `dest`
+    pub fn backup_database(&self, dest: &Path) -> Result<(), ContainerError> {
+        let db = self.db.lock().map_err(|_| ContainerError::DatabaseError {
+            message: "Failed to acquire database lock".to_string(),
+        })?;
+
+        database::backup_database(&db, dest).map_err(|e| ContainerError::DatabaseError {
+            message: format!("Failed to back up database: {}", e),
+        })
+    }
+
+    /// Replace the database with a backup and reload the systems held in memory
+    pub fn restore_database(&self, src: &Path) -> Result<(), ContainerError> {
+        let systems = {
+            let mut db = self.db.lock().map_err(|_| ContainerError::DatabaseError {
+                message: "Failed to acquire database lock".to_string(),
+            })?;
+
+            database::restore_database(&mut db, src).map_err(ContainerError::InvalidConfiguration)?;
+            database::get_all_systems(&db).map_err(|e| ContainerError::DatabaseError {
+                message: format!("Failed to load restored systems: {}", e),
+            })?
+        };
+
+        tracing::info!("Restored database with {} systems", systems.len());
+        let restored_ids: Vec<String> = systems.iter().map(|s| s.id.0.clone()).collect();
+        *self.systems.lock().unwrap() = systems;
+        self.invalidate_systems_cache();
+
+        // Go through set_connection_state so listeners hear about systems the
+        // restore dropped; systems it added start out disconnected
+        let removed_ids: Vec<String> = self
+            .connection_states
+            .lock()
+            .unwrap()
+            .keys()
+            .filter(|id| !restored_ids.contains(id))
+            .cloned()
+            .collect();
+        for id in &removed_ids {
+            self.set_connection_state(id, ConnectionState::Disconnected);
+            self.connection_states.lock().unwrap().remove(id);
+        }
+        for id in &restored_ids {
+            if !self.connection_states.lock().unwrap().contains_key(id) {
+                self.set_connection_state(id, ConnectionState::Disconnected);
+            }
+        }
+
+        Ok(())
+    }
+
     // ============================================================================
     // SSH Credentials Methods
     // ============================================================================
@@ -375,7 +698,12 @@ impl AppState {
             message: "Failed to acquire database lock".to_string(),
         })?;
 
-        database::store_ssh_credentials(&db, system_id, password, passphrase, private_key)
+        // Never fall back to storing credentials the keychain key doesn't protect
+        let cipher = self.credential_cipher().ok_or_else(|| {
+            ContainerError::CredentialError("Credential encryption key is unavailable".to_string())
+        })?;
+
+        database::store_ssh_credentials(&db, cipher, system_id, password, passphrase, private_key)
             .map_err(|e| ContainerError::DatabaseError {
                 message: e.to_string(),
             })
@@ -387,7 +715,7 @@ impl AppState {
             message: "Failed to acquire database lock".to_string(),
         })?;
 
-        database::get_ssh_credentials(&db, system_id)
+        database::get_ssh_credentials(&db, self.credential_cipher(), system_id)
             .map_err(|e| ContainerError::DatabaseError {
                 message: e.to_string(),
             })
@@ -405,6 +733,15 @@ impl AppState {
             })
     }
 
+    /// Set the key for database credentials once it has been loaded from the keychain
+    pub fn set_credential_cipher(&self, cipher: CredentialCipher) {
+        let _ = self.credential_cipher.set(cipher);
+    }
+
+    pub fn credential_cipher(&self) -> Option<&CredentialCipher> {
+        self.credential_cipher.get()
+    }
+
     // ============================================================================
     // Credential Cache Methods (in-memory, populated at startup from keyring)
     // ============================================================================
@@ -453,6 +790,7 @@ impl AppState {
             version: 1,
             ssh_credentials: ssh,
             ai_api_keys: ai,
+            credential_key: self.credential_cipher().map(CredentialCipher::key),
         };
         crate::keyring_store::save_vault(&vault)
     }
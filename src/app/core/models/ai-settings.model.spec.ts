@@ -3,8 +3,8 @@ import { AI_PROVIDERS, AiProviderType, ProviderInfo } from './ai-settings.model'
 
 describe('AI Settings Model', () => {
   describe('AI_PROVIDERS', () => {
-    it('should contain all 8 providers', () => {
-      expect(AI_PROVIDERS).toHaveLength(8);
+    it('should contain all 9 providers', () => {
+      expect(AI_PROVIDERS).toHaveLength(9);
     });
 
     it('should have unique IDs', () => {
@@ -14,7 +14,7 @@ describe('AI Settings Model', () => {
 
     const providerIds: AiProviderType[] = [
       'ollama', 'openai', 'anthropic', 'azure_openai',
-      'groq', 'gemini', 'deepseek', 'mistral',
+      'groq', 'gemini', 'deepseek', 'mistral', 'bedrock',
     ];
 
     it('should include all expected provider types', () => {
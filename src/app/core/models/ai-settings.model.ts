@@ -1,14 +1,17 @@
 /**
  * AI Provider type
  */
-export type AiProviderType = 'ollama' | 'openai' | 'anthropic' | 'azure_openai' | 'groq' | 'gemini' | 'deepseek' | 'mistral';
+export type AiProviderType = 'ollama' | 'openai' | 'anthropic' | 'azure_openai' | 'groq' | 'gemini' | 'deepseek' | 'mistral' | 'bedrock';
 
 /**
  * AI settings stored in the database
  */
 export interface AiSettings {
   provider: AiProviderType;
+  /** Key to save; loaded settings never include it, see hasApiKey */
   apiKey?: string;
+  /** Whether a key is saved for the provider (in the OS keychain on desktop) */
+  hasApiKey?: boolean;
   modelName: string;
   endpointUrl: string;
   temperature: number;
@@ -19,8 +22,17 @@ export interface AiSettings {
   summaryModel?: string;
   /** Max tokens for each summary (default: 100) */
   summaryMaxTokens: number;
+  /** Inputs shorter than this many characters are remembered without a summary call (backend default is 100) */
+  summaryMinInputLength?: number;
   /** API version for Azure OpenAI (e.g., "2024-10-21") */
   apiVersion?: string;
+  /** Retries on rate limits (429) and server errors (5xx); backend default is 2 */
+  maxRetries?: number;
+  /**
+   * Seconds to wait for a provider before giving up; backend default is 120.
+   * Ollama users running large local models may need to raise it.
+   */
+  requestTimeoutSecs?: number;
 }
 
 /**
@@ -33,6 +45,33 @@ export interface AiModel {
   contextWindow?: number;
   parameterSize?: string;
   quantizationLevel?: string;
+  /** Download size in bytes (locally installed Ollama models) */
+  size?: number;
+  /** Model family, e.g. "llama" (locally installed Ollama models) */
+  family?: string;
+}
+
+/**
+ * Progress update from an Ollama model pull; percent is null while Ollama
+ * reports no sizes (e.g. while verifying or writing the manifest)
+ */
+export interface OllamaPullProgress {
+  pullId: string;
+  model: string;
+  status: string;
+  digest: string | null;
+  completed: number | null;
+  total: number | null;
+  percent: number | null;
+}
+
+/**
+ * Token counts reported by the provider for one completion
+ */
+export interface TokenUsage {
+  prompt_tokens: number;
+  completion_tokens: number;
+  total_tokens: number;
 }
 
 /**
@@ -79,7 +118,10 @@ export interface UpdateAiSettingsRequest {
   memory_enabled: boolean;
   summary_model?: string;
   summary_max_tokens: number;
+  summary_min_input_length?: number;
   api_version?: string;
+  max_retries?: number;
+  request_timeout_secs?: number;
 }
 
 /**
@@ -172,4 +214,13 @@ export const AI_PROVIDERS: ProviderInfo[] = [
     defaultModel: 'mistral-large-latest',
     defaultSummaryModel: 'mistral-small-latest',
   },
+  {
+    id: 'bedrock',
+    name: 'AWS Bedrock',
+    description: 'Claude, Titan and other models via AWS Bedrock',
+    requiresApiKey: true,
+    defaultEndpoint: 'us-east-1',
+    defaultModel: 'anthropic.claude-3-5-haiku-20241022-v1:0',
+    defaultSummaryModel: 'anthropic.claude-3-haiku-20240307-v1:0',
+  },
 ];
@@ -6,6 +6,7 @@ import {
   getRuntimePrefix,
   substituteVariables,
   hasUnresolvedVariables,
+  getTemplateCommand,
   getCategoryLabel,
   getCategoryIcon,
   getRuntimeLabel,
@@ -31,6 +32,7 @@ function makeTemplate(overrides: Partial<CommandTemplate> = {}): CommandTemplate
     isBuiltIn: false,
     createdAt: '2024-01-01T00:00:00Z',
     updatedAt: '2024-01-01T00:00:00Z',
+    useCount: 0,
     ...overrides,
   };
 }
@@ -102,6 +104,24 @@ describe('Command Template Model Utilities', () => {
     });
   });
 
+  describe('getTemplateCommand', () => {
+    it('should return the command of a plain template', () => {
+      expect(getTemplateCommand(makeTemplate())).toBe('docker ps');
+    });
+
+    it('should chain the steps of a composite template', () => {
+      const template = makeTemplate({
+        steps: [
+          { description: 'Stop', command: 'docker stop ${CONTAINER_NAME}' },
+          { description: 'Remove', command: 'docker rm ${CONTAINER_NAME}' },
+        ],
+      });
+      expect(getTemplateCommand(template)).toBe(
+        'docker stop ${CONTAINER_NAME} && docker rm ${CONTAINER_NAME}'
+      );
+    });
+  });
+
   describe('hasUnresolvedVariables', () => {
     it('should return true when variables remain', () => {
       expect(hasUnresolvedVariables('docker run ${IMAGE_NAME}')).toBe(true);
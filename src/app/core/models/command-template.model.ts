@@ -1,4 +1,5 @@
 import { ContainerRuntime } from './container.model';
+import type { DangerLevel } from '../../features/warp-terminal/models/agent-events';
 
 export type CommandCategory =
   | 'container-management'
@@ -10,11 +11,37 @@ export type CommandCategory =
   | 'pods'
   | 'custom';
 
+export type VarType =
+  | { kind: 'string' }
+  | { kind: 'integer' }
+  | { kind: 'port' }
+  | { kind: 'path' }
+  | { kind: 'enum'; options: string[] };
+
 export interface TemplateVariable {
   name: string;
   description: string;
   defaultValue?: string;
   required: boolean;
+  varType?: VarType;
+}
+
+export interface VariableValidationError {
+  name: string;
+  message: string;
+}
+
+export interface TemplateImportResult {
+  imported: number;
+  overwritten: number;
+  /** Names of templates skipped because one with the same name already exists */
+  skipped: string[];
+}
+
+/** One command of a composite template */
+export interface TemplateStep {
+  description: string;
+  command: string;
 }
 
 export interface CommandCompatibility {
@@ -27,6 +54,8 @@ export interface CommandTemplate {
   name: string;
   description: string;
   command: string;
+  /** Ordered sub-commands; when present, `command` is ignored */
+  steps?: TemplateStep[];
   category: CommandCategory;
   tags: string[];
   variables: TemplateVariable[];
@@ -35,12 +64,42 @@ export interface CommandTemplate {
   isBuiltIn: boolean;
   createdAt: string;
   updatedAt: string;
+  /** Number of times the template has been executed */
+  useCount: number;
+  lastUsedAt?: string;
+}
+
+export type TemplateSortBy = 'name' | 'mostUsed' | 'recent';
+
+/** A template step with variables substituted, classified before it runs */
+export interface PlannedStep {
+  index: number;
+  description: string;
+  command: string;
+  dangerLevel: DangerLevel;
+  dangerExplanation: string;
+}
+
+export interface StepResult {
+  index: number;
+  description: string;
+  command: string;
+  exitCode: number;
+  stdout: string;
+  stderr: string;
+}
+
+export interface TemplateRunResult {
+  steps: StepResult[];
+  /** Index of the step that exited non-zero, or null if every step succeeded */
+  failedStep: number | null;
 }
 
 export interface CreateCommandTemplateRequest {
   name: string;
   description: string;
   command: string;
+  steps?: TemplateStep[];
   category: CommandCategory;
   tags: string[];
   variables: TemplateVariable[];
@@ -53,6 +112,8 @@ export interface UpdateCommandTemplateRequest {
   name?: string;
   description?: string;
   command?: string;
+  /** An empty list turns a composite template back into a plain one */
+  steps?: TemplateStep[];
   category?: CommandCategory;
   tags?: string[];
   variables?: TemplateVariable[];
@@ -92,11 +153,23 @@ export const getRuntimePrefix = (runtime: ContainerRuntime): string => {
       return 'podman';
     case 'apple':
       return 'container';
+    case 'nerdctl':
+      return 'nerdctl';
     default:
       return 'docker';
   }
 };
 
+/**
+ * Get the shell command a template runs. Composite templates chain their steps
+ * with `&&` so the shell stops at the first failing step.
+ */
+export const getTemplateCommand = (template: CommandTemplate): string => {
+  return template.steps?.length
+    ? template.steps.map((s) => s.command).join(' && ')
+    : template.command;
+};
+
 /**
  * Substitute variable placeholders with actual values
  * @param command - The command string with ${VARIABLE_NAME} placeholders
@@ -193,6 +266,8 @@ export const getRuntimeLabel = (runtime: ContainerRuntime): string => {
       return 'Podman';
     case 'apple':
       return 'Apple';
+    case 'nerdctl':
+      return 'nerdctl';
     default:
       return runtime;
   }
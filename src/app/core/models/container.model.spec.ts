@@ -25,6 +25,7 @@ function makeContainer(overrides: Partial<Container> = {}): Container {
     systemId: 'sys-1',
     createdAt: new Date().toISOString(),
     ports: [],
+    uptime: 'Up 5 minutes',
     environmentVariables: {},
     volumes: [],
     networkSettings: { networks: {}, portBindings: [] },
@@ -1,3 +1,5 @@
+import { AppError } from './error.model';
+
 export type ContainerStatus =
   | 'running'
   | 'exited'
@@ -7,7 +9,7 @@ export type ContainerStatus =
   | 'dead'
   | 'created';
 
-export type ContainerRuntime = 'docker' | 'podman' | 'apple';
+export type ContainerRuntime = 'docker' | 'podman' | 'apple' | 'nerdctl';
 
 export type ContainerAction =
   | 'start'
@@ -17,6 +19,12 @@ export type ContainerAction =
   | 'unpause'
   | 'remove';
 
+/** Per-container output of perform_container_action_bulk */
+export interface ContainerActionResult {
+  success: boolean;
+  error: AppError | null;
+}
+
 export interface PortMapping {
   hostIp: string;
   hostPort: number;
@@ -123,6 +131,8 @@ export interface Container {
   systemId: string;
   createdAt: string;
   ports: PortMapping[];
+  /** Uptime as `docker ps` shows it, e.g. "Up 3 days" or "Exited (1) 5 minutes ago" */
+  uptime: string | null;
 
   // Full details (always available)
   environmentVariables: Record<string, string>;
@@ -152,6 +162,111 @@ export type ContainerDetails = Pick<
   | 'hostConfig'
 >;
 
+/** A port create_container publishes */
+export interface PortSpec {
+  /** Host address to bind; all interfaces when omitted */
+  hostIp?: string | null;
+  /** Host port; picked by the runtime when omitted */
+  hostPort?: number | null;
+  containerPort: number;
+  /** tcp (default), udp or sctp */
+  protocol?: 'tcp' | 'udp' | 'sctp' | null;
+}
+
+/** A bind mount (absolute source path) or named volume create_container mounts */
+export interface VolumeSpec {
+  source: string;
+  destination: string;
+  readOnly?: boolean;
+}
+
+/** Structured input for create_container */
+export interface ContainerCreateSpec {
+  image: string;
+  name?: string | null;
+  ports?: PortSpec[];
+  env?: Record<string, string>;
+  volumes?: VolumeSpec[];
+  /** no, always, unless-stopped, on-failure or on-failure:<max retries>; not supported by Apple */
+  restartPolicy?: string | null;
+  network?: string | null;
+  /** Command and arguments replacing the image's default command */
+  command?: string[];
+  /** Start the container right away (default true); otherwise it is only created */
+  detach?: boolean;
+}
+
+/** Filters list_containers has the runtime apply; omitted fields don't filter */
+export interface ContainerListFilter {
+  /** Include stopped containers (default true); ignored when status is set */
+  all?: boolean;
+  status?: ContainerStatus | null;
+  /** `key` to require a label, or `key=value` to require its value */
+  label?: string | null;
+  /** Part of the container name */
+  name?: string | null;
+  /** Image the container was created from, with or without a tag */
+  ancestor?: string | null;
+}
+
+/** Options for stream_container_logs; omitted fields use the backend defaults */
+export interface ContainerLogOptions {
+  tail?: number | null;
+  /** Timestamp (e.g. 2024-01-01T00:00:00Z) or duration (e.g. 10m) */
+  since?: string | null;
+  timestamps?: boolean;
+  /** Forward stdout lines (default true) */
+  stdout?: boolean;
+  /** Forward stderr lines (default true) */
+  stderr?: boolean;
+}
+
+/** One line of a followed container log */
+export interface ContainerLogLine {
+  streamId: string;
+  systemId: string;
+  containerId: string;
+  stream: 'stdout' | 'stderr';
+  line: string;
+}
+
+/** Emitted once a followed log stops, either on request or because the container went away */
+export interface ContainerLogStreamEnded {
+  streamId: string;
+  systemId: string;
+  containerId: string;
+  cancelled: boolean;
+  exitCode: number | null;
+  error: string | null;
+}
+
+/** A lifecycle event reported by `<runtime> events` */
+export interface ContainerEvent {
+  /** Kind of object: container, image, network, volume, ... */
+  type: string;
+  /** What happened, e.g. start, die or pull */
+  action: string;
+  actorId: string;
+  actorName: string | null;
+  /** Unix timestamp in seconds */
+  time: number;
+}
+
+export interface RuntimeEventMessage {
+  streamId: string;
+  systemId: string;
+  event: ContainerEvent;
+}
+
+/** Emitted once a runtime event stream stops */
+export interface RuntimeEventStreamEnded {
+  streamId: string;
+  systemId: string;
+  cancelled: boolean;
+  exitCode: number | null;
+  error: string | null;
+}
+
 export const getDisplayName = (container: Container): string =>
   container.name?.length ? container.name : container.id.slice(0, 12);
 
@@ -182,6 +297,8 @@ export const getRuntimeColor = (runtime: ContainerRuntime): string => {
       return 'text-orange-500';
     case 'apple':
       return 'text-purple-500';
+    case 'nerdctl':
+      return 'text-cyan-500';
     default:
       return 'text-gray-500';
   }
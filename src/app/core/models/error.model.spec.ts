@@ -0,0 +1,52 @@
+import { describe, it, expect } from 'vitest';
+import { AppError, errorMessage, fieldErrors, isAppError } from './error.model';
+
+describe('error model', () => {
+  const appError = (overrides: Partial<AppError> = {}): AppError => ({
+    kind: 'connection',
+    message: 'System not connected: sys-1',
+    detail: null,
+    ...overrides,
+  });
+
+  it('should recognise command errors', () => {
+    expect(isAppError(appError())).toBe(true);
+    expect(isAppError(new Error('boom'))).toBe(false);
+    expect(isAppError('boom')).toBe(false);
+    expect(isAppError(null)).toBe(false);
+  });
+
+  it('should use the message of a command error', () => {
+    expect(errorMessage(appError())).toBe('System not connected: sys-1');
+  });
+
+  it('should prefer stderr for failed commands', () => {
+    const err = appError({
+      kind: 'commandFailed',
+      message: 'Command execution failed: docker ps (exit code: 1)\nStderr: permission denied',
+      detail: 'permission denied',
+    });
+    expect(errorMessage(err)).toBe('permission denied');
+  });
+
+  it('should handle errors and strings', () => {
+    expect(errorMessage(new Error('boom'))).toBe('boom');
+    expect(errorMessage('plain')).toBe('plain');
+  });
+
+  it('should fall back for anything else', () => {
+    expect(errorMessage(undefined)).toBe('Unknown error');
+    expect(errorMessage({ unexpected: true }, 'Failed to load')).toBe('Failed to load');
+  });
+
+  it('should key field errors by field name', () => {
+    const err = appError({
+      kind: 'invalidInput',
+      message: 'Invalid AI settings',
+      fieldErrors: [{ field: 'model_name', message: 'Model name is required' }],
+    });
+    expect(fieldErrors(err)).toEqual({ model_name: 'Model name is required' });
+    expect(fieldErrors(appError())).toEqual({});
+    expect(fieldErrors(new Error('boom'))).toEqual({});
+  });
+});
@@ -0,0 +1,71 @@
+/** Broad error category, used to pick a recovery action */
+export type ErrorKind =
+  | 'connection'
+  | 'auth'
+  | 'hostKey'
+  | 'hostKeyUnknown'
+  | 'hostKeyChanged'
+  | 'timeout'
+  | 'notFound'
+  | 'parse'
+  | 'runtimeNotAvailable'
+  | 'commandFailed'
+  | 'invalidInput'
+  | 'permissionDenied'
+  | 'unsupported'
+  | 'cancelled'
+  | 'internal';
+
+/** Error every Tauri command rejects with */
+export interface AppError {
+  kind: ErrorKind;
+  message: string;
+  /** Raw cause where there is one: stderr of a failed command, why a connection or host key check failed */
+  detail: string | null;
+  /** Key the server presented, for unknown and changed host keys */
+  hostKey?: HostKeyDetails | null;
+  /** Each rejected field, for invalid form input */
+  fieldErrors?: FieldError[] | null;
+}
+
+/** A submitted field that failed validation, named as the request names it */
+export interface FieldError {
+  field: string;
+  message: string;
+}
+
+/** Host key a server presented that isn't trusted yet */
+export interface HostKeyDetails {
+  hostname: string;
+  port: number;
+  keyType: string;
+  /** SHA256 fingerprint without the `SHA256:` prefix */
+  fingerprint: string;
+  /** What known_hosts has for the host, when the key changed */
+  expectedFingerprint: string | null;
+}
+
+export const isAppError = (err: unknown): err is AppError =>
+  !!err &&
+  typeof err === 'object' &&
+  typeof (err as AppError).kind === 'string' &&
+  typeof (err as AppError).message === 'string';
+
+/**
+ * Human-readable message for anything a command or promise rejected with.
+ * Failed commands show their stderr rather than the full command line.
+ */
+export const errorMessage = (err: unknown, fallback = 'Unknown error'): string => {
+  if (isAppError(err)) {
+    return err.kind === 'commandFailed' && err.detail ? err.detail : err.message;
+  }
+  if (err instanceof Error) return err.message;
+  if (typeof err === 'string' && err) return err;
+  return fallback;
+};
+
+/** Validation messages keyed by field name, empty unless the error rejected form input */
+export const fieldErrors = (err: unknown): Record<string, string> =>
+  Object.fromEntries(
+    (isAppError(err) ? err.fieldErrors ?? [] : []).map((e) => [e.field, e.message])
+  );
@@ -1,5 +1,5 @@
 import { describe, it, expect } from 'vitest';
-import { formatFileSize, isTextFile } from './file-browser.model';
+import { archiveBaseName, formatFileSize, isTextFile, permissionsToMode } from './file-browser.model';
 
 describe('File Browser Model Utilities', () => {
   describe('formatFileSize', () => {
@@ -118,4 +118,34 @@ describe('File Browser Model Utilities', () => {
       expect(isTextFile('Dockerfile')).toBe(true);
     });
   });
+
+  describe('permissionsToMode', () => {
+    it('should convert basic permissions', () => {
+      expect(permissionsToMode('rwxr-xr-x')).toBe('755');
+      expect(permissionsToMode('-rw-r-----')).toBe('640');
+    });
+
+    it('should include special bits', () => {
+      expect(permissionsToMode('rwxrwsr-x')).toBe('2775');
+      expect(permissionsToMode('rwxrwxrwt')).toBe('1777');
+      expect(permissionsToMode('rwSr--r--')).toBe('4644');
+    });
+
+    it('should return empty string for unrecognised input', () => {
+      expect(permissionsToMode('')).toBe('');
+    });
+  });
+
+  describe('archiveBaseName', () => {
+    it('should strip supported archive extensions', () => {
+      expect(archiveBaseName('app.tar.gz')).toBe('app');
+      expect(archiveBaseName('backup-1.2.TGZ')).toBe('backup-1.2');
+      expect(archiveBaseName('site.zip')).toBe('site');
+    });
+
+    it('should return null for other files', () => {
+      expect(archiveBaseName('notes.txt')).toBeNull();
+      expect(archiveBaseName('.zip')).toBeNull();
+    });
+  });
 });
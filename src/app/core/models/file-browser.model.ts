@@ -3,6 +3,7 @@ import { ContainerRuntime } from './container.model';
 export type FileType = 'file' | 'directory' | 'symlink' | 'other';
 export type FileSortOption = 'name' | 'size' | 'modified' | 'type';
 export type SortDirection = 'asc' | 'desc';
+export type ArchiveFormat = 'tar.gz' | 'zip';
 
 export interface FileEntry {
   name: string;
@@ -30,11 +31,87 @@ export interface FileContent {
   isBinary: boolean;
 }
 
+/** A byte range of a file too large to open whole; offsets past the end are clamped */
+export interface FileChunk {
+  path: string;
+  offset: number;
+  bytesRead: number;
+  fileSize: number;
+  content: string;
+  isBinary: boolean;
+}
+
+/** Largest file `read_file` will open; bigger files are paged with `read_file_range` */
+export const MAX_EDITABLE_FILE_SIZE = 1024 * 1024;
+
+/** Bytes per page when viewing a large file */
+export const FILE_PAGE_SIZE = 256 * 1024;
+
+/** Disk usage of one path, in bytes */
+export interface DiskUsageEntry {
+  path: string;
+  size: number;
+}
+
+/** Total size of a directory tree; `partial` when some subdirectories were unreadable */
+export interface DirectorySize {
+  path: string;
+  size: number;
+  partial: boolean;
+}
+
+/** Size of a directory broken down by its children, largest first */
+export interface DirectoryTreeSizes {
+  path: string;
+  total: number;
+  children: DiskUsageEntry[];
+  partial: boolean;
+}
+
+/** How searchFileContents matches the query */
+export interface SearchOptions {
+  caseInsensitive?: boolean;
+  /** Match the query literally instead of as an extended regular expression */
+  fixedString?: boolean;
+  maxResults?: number;
+}
+
+/** A line matching a content search */
+export interface SearchHit {
+  file: string;
+  lineNumber: number;
+  line: string;
+}
+
+export interface SearchResults {
+  hits: SearchHit[];
+  /** More hits exist than were returned */
+  truncated: boolean;
+}
+
 export interface Breadcrumb {
   name: string;
   path: string;
 }
 
+/** Byte progress of an SFTP upload or download */
+export interface FileTransferProgress {
+  systemId: string;
+  path: string;
+  direction: 'upload' | 'download';
+  transferred: number;
+  total: number;
+}
+
+export interface SystemTransferProgress {
+  srcSystemId: string;
+  srcPath: string;
+  dstSystemId: string;
+  dstPath: string;
+  transferred: number;
+  total: number;
+}
+
 export const formatFileSize = (bytes: number): string => {
   if (bytes === 0) return '0 B';
   const units = ['B', 'KB', 'MB', 'GB', 'TB'];
@@ -55,3 +132,22 @@ export const isTextFile = (name: string): boolean => {
   return textExtensions.has(ext) ||
     ['dockerfile', 'makefile', 'readme', 'license', 'changelog', '.gitignore', '.env', '.dockerignore'].includes(baseName);
 };
+
+/** Convert an `ls` permission string (`rwxr-xr-x`, optionally with a type prefix) to an octal mode */
+export const permissionsToMode = (permissions: string): string => {
+  const perms = permissions.length === 10 ? permissions.slice(1) : permissions;
+  if (perms.length !== 9) return '';
+  let special = 0;
+  const digits = [0, 1, 2].map((i) => {
+    const [r, w, x] = perms.slice(i * 3, i * 3 + 3);
+    if ('sStT'.includes(x)) special |= 4 >> i;
+    return (r === 'r' ? 4 : 0) + (w === 'w' ? 2 : 0) + ('xst'.includes(x) ? 1 : 0);
+  });
+  return (special ? String(special) : '') + digits.join('');
+};
+
+/** Name without its archive extension, or null if the file isn't a supported archive */
+export const archiveBaseName = (name: string): string | null => {
+  const match = /^(.+?)\.(tar\.gz|tgz|zip)$/i.exec(name);
+  return match ? match[1] : null;
+};
@@ -14,6 +14,53 @@ export interface ContainerImage {
   os?: string | null;
 }
 
+/** One build step of an image, newest first; size is null when the runtime doesn't report it */
+export interface ImageHistoryEntry {
+  id: string | null;
+  created: string | null;
+  createdBy: string;
+  size: number | null;
+  comment: string | null;
+}
+
+/** Full configuration of an image, from inspect_image */
+export interface ImageDetails {
+  id: string;
+  repoTags: string[];
+  architecture: string | null;
+  os: string | null;
+  created: string | null;
+  size: number | null;
+  env: string[];
+  cmd: string[];
+  entrypoint: string[];
+  exposedPorts: string[];
+  workingDir: string | null;
+  user: string | null;
+  labels: Record<string, string>;
+  layers: string[];
+  history: ImageHistoryEntry[];
+}
+
+/** Outcome of a background pull started with pull_image */
+export interface ImagePullFinished {
+  executionId: string;
+  systemId: string;
+  image: string;
+  cancelled: boolean;
+  error?: string | null;
+}
+
+/** Progress line from a background pull; layerId is null for image-wide messages */
+export interface ImagePullProgress {
+  executionId: string;
+  systemId: string;
+  image: string;
+  layerId: string | null;
+  status: string;
+  percent: number | null;
+}
+
 export const getImageFullName = (image: ContainerImage): string => {
   if (!image.tag || image.tag === '<none>') {
     return image.name;
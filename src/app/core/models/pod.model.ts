@@ -0,0 +1,21 @@
+export type PodStatus =
+  | 'created'
+  | 'running'
+  | 'degraded'
+  | 'paused'
+  | 'stopped'
+  | 'exited'
+  | 'dead'
+  | 'unknown';
+
+export interface Pod {
+  id: string;
+  name: string;
+  status: PodStatus;
+  /** Number of containers in the pod, including the infra container */
+  containerCount: number;
+  createdAt?: string | null;
+  /** Infra container holding the shared namespaces, if the pod has one */
+  infraId?: string | null;
+  systemId: string;
+}
@@ -1,13 +1,18 @@
 export type PortForwardStatus = 'active' | 'stopped' | 'error';
 
+/** 'local' listens on this machine (ssh -L); 'remote' listens on the server (ssh -R) */
+export type ForwardDirection = 'local' | 'remote';
+
 export interface PortForward {
   id: string;
   systemId: string;
   containerId: string;
   containerPort: number;
   localPort: number;
+  localHost: string;
   remoteHost: string;
   remotePort: number;
+  direction: ForwardDirection;
   protocol: string;
   status: PortForwardStatus;
   createdAt: string;
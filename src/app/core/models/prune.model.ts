@@ -0,0 +1,7 @@
+/** Outcome of a prune command, summed over the system's runtimes */
+export interface PruneResult {
+  /** IDs or names of the removed objects */
+  deleted: string[];
+  /** Space freed in bytes; 0 when the runtime doesn't report it */
+  reclaimedBytes: number;
+}
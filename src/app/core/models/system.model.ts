@@ -1,9 +1,38 @@
 import { ContainerRuntime } from './container.model';
+import { AppError } from './error.model';
 
 export type ConnectionType = 'local' | 'remote';
 export type ConnectionState = 'disconnected' | 'connecting' | 'connected' | 'error';
+
+/** Payload of `system:connection-changed`, emitted whenever a system's connection state changes */
+export interface ConnectionChangedEvent {
+  systemId: string;
+  state: ConnectionState;
+  error: string | null;
+}
+
+/** Per-system outcome of connect_systems / disconnect_systems */
+export interface SystemConnectionResult {
+  state: ConnectionState;
+  error: AppError | null;
+}
+
+/** Per-system output of execute_on_all_systems */
+export interface HostCommandResult {
+  systemName: string;
+  /** null when the command couldn't run or timed out; see `error` */
+  exitCode: number | null;
+  stdout: string;
+  stderr: string;
+  executionTimeMs: number;
+  error: AppError | null;
+}
+
 export type SshAuthMethod = 'password' | 'publicKey';
 
+/** File format of a system inventory export */
+export type ExportFormat = 'json' | 'yaml';
+
 export interface SshConfig {
   username: string;
   port: number;
@@ -18,6 +47,43 @@ export interface SshConfig {
   proxyJump?: JumpHost[] | null;
   /** Reference to the original SSH config host name (if imported from ~/.ssh/config) */
   sshConfigHost?: string | null;
+  /** Seconds between SSH keepalives (0 disables; backend defaults to 30) */
+  keepaliveIntervalSecs?: number;
+  /** Store newly accepted host keys hashed in known_hosts */
+  hashKnownHosts?: boolean;
+  /** Ask before trusting the key of a host not yet in known_hosts */
+  confirmNewHostKeys?: boolean;
+}
+
+/** Open SSH connections held by the backend pool */
+export interface PoolStats {
+  /** Running a command or backing a terminal / port forward */
+  active: number;
+  /** Eligible for eviction when the pool is full */
+  idle: number;
+  maxConnections: number;
+}
+
+/** Working directory and extra environment a backend command runs with */
+export interface ExecContext {
+  cwd?: string | null;
+  env?: Record<string, string>;
+}
+
+/** A piece of output from a command started with executeStreaming */
+export type OutputChunk =
+  | { stream: 'stdout' | 'stderr'; data: string }
+  | { stream: 'exit'; data: number };
+
+/** A command previously run on a system, persisted across app restarts */
+export interface CommandHistoryEntry {
+  id: number;
+  systemId: string;
+  command: string;
+  /** null when the command was cancelled */
+  exitCode: number | null;
+  durationMs: number;
+  executedAt: string;
 }
 
 /** Configuration for a jump host in a ProxyJump chain */
@@ -64,6 +130,10 @@ export interface AppSettings {
   sshConfigPaths?: string[];
   /** Last app version the user has seen the "What's New" dialog for */
   lastSeenVersion?: string | null;
+  /** Port serving Prometheus metrics at http://127.0.0.1:<port>/metrics (null = off) */
+  metricsExporterPort?: number | null;
+  /** Minutes a terminal may sit idle before it is closed (null = 30, 0 = never) */
+  terminalIdleTimeoutMins?: number | null;
 }
 
 export interface ContainerSystem {
@@ -75,6 +145,30 @@ export interface ContainerSystem {
   availableRuntimes: ContainerRuntime[];
   sshConfig?: SshConfig | null;
   autoConnect: boolean;
+  tags: string[];
+  /** Longest a single command may run, in seconds; null uses the 60s default, 0 means no limit */
+  commandTimeoutSecs: number | null;
+  /** When the system last connected successfully, in unix seconds */
+  lastConnectedAt: number | null;
+}
+
+export interface RuntimeDetection {
+  runtimes: ContainerRuntime[];
+  rootless: boolean;
+  /** API socket for Docker-compatible clients, e.g. $XDG_RUNTIME_DIR/podman/podman.sock */
+  socketPath: string | null;
+}
+
+/** Public details of an SSH key; never includes the private key material */
+export interface SshKeyInfo {
+  /** RSA, ECDSA or ED25519 */
+  keyType: string;
+  /** OpenSSH fingerprint, e.g. SHA256:... */
+  fingerprint: string;
+  /** Private key file with ~ for the home directory; null for imported keys */
+  path: string | null;
+  /** Comment from the public key, usually user@host */
+  comment: string | null;
 }
 
 export interface NewSystemRequest {
@@ -85,6 +179,8 @@ export interface NewSystemRequest {
   availableRuntimes: ContainerRuntime[];
   sshConfig?: SshConfig | null;
   autoConnect: boolean;
+  tags?: string[];
+  commandTimeoutSecs?: number | null;
 }
 
 export interface UpdateSystemRequest {
@@ -96,6 +192,10 @@ export interface UpdateSystemRequest {
   availableRuntimes: ContainerRuntime[];
   sshConfig?: SshConfig | null;
   autoConnect: boolean;
+  /** Omit to keep the system's current tags */
+  tags?: string[];
+  /** Omit to keep the system's current limit */
+  commandTimeoutSecs?: number;
 }
 
 export interface SystemHealth {
@@ -134,6 +234,27 @@ export interface LiveSystemMetrics {
   loadAverage?: [number, number, number] | null;
   /** Swap usage percentage (0-100) */
   swapUsagePercent?: number | null;
+  /** Disk read throughput in bytes/sec (null on the first sample) */
+  diskReadBytesPerSec?: number | null;
+  /** Disk write throughput in bytes/sec (null on the first sample) */
+  diskWriteBytesPerSec?: number | null;
+  /** Combined disk read and write throughput in bytes/sec; the only disk rate on macOS */
+  diskIoBytesPerSec?: number | null;
+  /** Network receive throughput in bytes/sec (null on the first sample) */
+  netRxBytesPerSec?: number | null;
+  /** Network transmit throughput in bytes/sec (null on the first sample) */
+  netTxBytesPerSec?: number | null;
+  /** Root filesystem usage percentage (0-100) */
+  diskUsagePercent?: number | null;
+}
+
+export interface GpuInfo {
+  /** GPU model (e.g., "NVIDIA GeForce RTX 4090") */
+  name: string;
+  /** Total GPU memory (formatted string, e.g., "24G") */
+  memoryTotal?: string | null;
+  /** Driver version (e.g., "535.129.03") */
+  driver?: string | null;
 }
 
 export interface ExtendedSystemInfo {
@@ -165,4 +286,6 @@ export interface ExtendedSystemInfo {
   totalImages?: number | null;
   /** Container runtime version (e.g., "Docker 24.0.5") */
   runtimeVersion?: string | null;
+  /** GPUs detected on the system (empty when there is no GPU or driver) */
+  gpus?: GpuInfo[];
 }
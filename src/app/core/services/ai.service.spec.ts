@@ -58,7 +58,7 @@ describe('AiService', () => {
     it('should load and transform settings from backend', async () => {
       mockTauri.invoke.mockResolvedValue({
         provider: 'openai',
-        api_key: 'sk-test',
+        has_api_key: true,
         model_name: 'gpt-4o',
         endpoint_url: 'https://api.openai.com',
         temperature: 0.7,
@@ -66,17 +66,20 @@ describe('AiService', () => {
         memory_enabled: true,
         summary_model: 'gpt-4o-mini',
         summary_max_tokens: 100,
+        summary_min_input_length: 250,
       });
 
       const result = await service.loadSettings();
       expect(result.provider).toBe('openai');
-      expect(result.apiKey).toBe('sk-test');
+      expect(result.hasApiKey).toBe(true);
+      expect(result.apiKey).toBeUndefined();
       expect(result.modelName).toBe('gpt-4o');
       expect(result.endpointUrl).toBe('https://api.openai.com');
       expect(result.temperature).toBe(0.7);
       expect(result.maxTokens).toBe(1024);
       expect(result.memoryEnabled).toBe(true);
       expect(result.summaryModel).toBe('gpt-4o-mini');
+      expect(result.summaryMinInputLength).toBe(250);
     });
 
     it('should set error on failure', async () => {
@@ -97,6 +100,7 @@ describe('AiService', () => {
         maxTokens: 256,
         memoryEnabled: true,
         summaryMaxTokens: 100,
+        summaryMinInputLength: 500,
       });
 
       expect(mockTauri.invoke).toHaveBeenCalledWith('update_ai_settings_cmd', {
@@ -104,8 +108,31 @@ describe('AiService', () => {
           provider: 'anthropic',
           api_key: 'sk-ant-test',
           model_name: 'claude-3-5-sonnet',
+          summary_min_input_length: 500,
         }),
       });
+      // The key is not kept in the frontend once saved
+      expect(service.settings()?.apiKey).toBeUndefined();
+      expect(service.settings()?.hasApiKey).toBe(true);
+    });
+
+    it('should treat an empty key as removing the saved one', async () => {
+      mockTauri.invoke.mockResolvedValue(undefined);
+      await service.updateSettings({
+        provider: 'openai',
+        apiKey: '',
+        modelName: 'gpt-4o',
+        endpointUrl: 'https://api.openai.com',
+        temperature: 0.3,
+        maxTokens: 256,
+        memoryEnabled: true,
+        summaryMaxTokens: 100,
+      });
+
+      expect(mockTauri.invoke).toHaveBeenCalledWith('update_ai_settings_cmd', {
+        request: expect.objectContaining({ api_key: '' }),
+      });
+      expect(service.settings()?.hasApiKey).toBe(false);
     });
   });
 
@@ -133,6 +160,7 @@ describe('AiService', () => {
         apiKey: 'sk-test',
         endpointUrl: 'https://api.openai.com',
         apiVersion: undefined,
+        modelName: undefined,
       });
     });
   });
@@ -1,14 +1,18 @@
 import { Injectable, inject, signal, computed } from '@angular/core';
+import { listen } from '@tauri-apps/api/event';
 import { TauriService } from './tauri.service';
 import {
   AiSettings,
   AiModel,
   AiProviderType,
+  OllamaPullProgress,
   ShellSuggestionRequest,
   ShellCommandResponse,
+  TokenUsage,
   UpdateAiSettingsRequest,
   AI_PROVIDERS,
 } from '../models/ai-settings.model';
+import { errorMessage } from '../models/error.model';
 
 @Injectable({
   providedIn: 'root',
@@ -22,6 +26,7 @@ export class AiService {
   private _isLoading = signal(false);
   private _error = signal<string | null>(null);
   private _isConnected = signal(false);
+  private _lastTestUsage = signal<TokenUsage | null>(null);
 
   // Public readonly signals
   readonly settings = this._settings.asReadonly();
@@ -29,6 +34,8 @@ export class AiService {
   readonly isLoading = this._isLoading.asReadonly();
   readonly error = this._error.asReadonly();
   readonly isConnected = this._isConnected.asReadonly();
+  /** Token usage reported by the last successful connection test */
+  readonly lastTestUsage = this._lastTestUsage.asReadonly();
 
   // Computed signals
   readonly currentProvider = computed(() => this._settings()?.provider ?? 'ollama');
@@ -40,7 +47,7 @@ export class AiService {
     const settings = this._settings();
     if (!settings) return false;
     if (settings.provider === 'ollama') return true;
-    return !!settings.apiKey;
+    return !!settings.hasApiKey || !!settings.apiKey;
   });
 
   /**
@@ -53,7 +60,7 @@ export class AiService {
     try {
       const response = await this.tauri.invoke<{
         provider: string;
-        api_key?: string;
+        has_api_key: boolean;
         model_name: string;
         endpoint_url: string;
         temperature: number;
@@ -61,12 +68,15 @@ export class AiService {
         memory_enabled: boolean;
         summary_model?: string;
         summary_max_tokens: number;
+        summary_min_input_length?: number;
         api_version?: string;
+        max_retries?: number;
+        request_timeout_secs?: number;
       }>('get_ai_settings_cmd');
 
       const settings: AiSettings = {
         provider: response.provider as AiProviderType,
-        apiKey: response.api_key,
+        hasApiKey: response.has_api_key,
         modelName: response.model_name,
         endpointUrl: response.endpoint_url,
         temperature: response.temperature,
@@ -74,13 +84,16 @@ export class AiService {
         memoryEnabled: response.memory_enabled ?? true,
         summaryModel: response.summary_model,
         summaryMaxTokens: response.summary_max_tokens ?? 100,
+        summaryMinInputLength: response.summary_min_input_length,
         apiVersion: response.api_version,
+        maxRetries: response.max_retries,
+        requestTimeoutSecs: response.request_timeout_secs,
       };
 
       this._settings.set(settings);
       return settings;
     } catch (err) {
-      const message = err instanceof Error ? err.message : String(err);
+      const message = errorMessage(err);
       this._error.set(message);
       throw err;
     } finally {
@@ -89,7 +102,8 @@ export class AiService {
   }
 
   /**
-   * Update AI settings
+   * Update AI settings. Leaving `apiKey` undefined keeps the saved key and an
+   * empty string removes it.
    */
   async updateSettings(settings: AiSettings): Promise<void> {
     this._isLoading.set(true);
@@ -106,13 +120,18 @@ export class AiService {
         memory_enabled: settings.memoryEnabled,
         summary_model: settings.summaryModel,
         summary_max_tokens: settings.summaryMaxTokens,
+        summary_min_input_length: settings.summaryMinInputLength,
         api_version: settings.apiVersion,
+        max_retries: settings.maxRetries,
+        request_timeout_secs: settings.requestTimeoutSecs,
       };
 
       await this.tauri.invoke<void>('update_ai_settings_cmd', { request });
-      this._settings.set(settings);
+      // Saving succeeds without a key only for Ollama; other providers must have one saved
+      const hasApiKey = settings.apiKey !== '' && (!!settings.apiKey || settings.provider !== 'ollama');
+      this._settings.set({ ...settings, apiKey: undefined, hasApiKey });
     } catch (err) {
-      const message = err instanceof Error ? err.message : String(err);
+      const message = errorMessage(err);
       this._error.set(message);
       throw err;
     } finally {
@@ -128,11 +147,12 @@ export class AiService {
     this._error.set(null);
 
     try {
-      await this.tauri.invoke<void>('test_ai_connection');
+      const usage = await this.tauri.invoke<TokenUsage | null>('test_ai_connection');
+      this._lastTestUsage.set(usage ?? null);
       this._isConnected.set(true);
       return true;
     } catch (err) {
-      const message = err instanceof Error ? err.message : String(err);
+      const message = errorMessage(err);
       this._error.set(message);
       this._isConnected.set(false);
       return false;
@@ -148,21 +168,24 @@ export class AiService {
     provider: AiProviderType,
     apiKey?: string,
     endpointUrl?: string,
-    apiVersion?: string
+    apiVersion?: string,
+    modelName?: string
   ): Promise<boolean> {
     this._isLoading.set(true);
     this._error.set(null);
 
     try {
-      await this.tauri.invoke<void>('test_ai_connection_with_settings', {
+      const usage = await this.tauri.invoke<TokenUsage | null>('test_ai_connection_with_settings', {
         providerType: provider,
         apiKey,
         endpointUrl,
         apiVersion,
+        modelName,
       });
+      this._lastTestUsage.set(usage ?? null);
       return true;
     } catch (err) {
-      const message = err instanceof Error ? err.message : String(err);
+      const message = errorMessage(err);
       this._error.set(message);
       return false;
     } finally {
@@ -182,7 +205,7 @@ export class AiService {
       this._availableModels.set(models);
       return models;
     } catch (err) {
-      const message = err instanceof Error ? err.message : String(err);
+      const message = errorMessage(err);
       this._error.set(message);
       throw err;
     } finally {
@@ -208,7 +231,7 @@ export class AiService {
       });
       return models;
     } catch (err) {
-      const message = err instanceof Error ? err.message : String(err);
+      const message = errorMessage(err);
       this._error.set(message);
       throw err;
     }
@@ -234,12 +257,51 @@ export class AiService {
 
       return await this.tauri.invoke<ShellCommandResponse>('get_shell_suggestion', { request });
     } catch (err) {
-      const message = err instanceof Error ? err.message : String(err);
+      const message = errorMessage(err);
       this._error.set(message);
       throw err;
     }
   }
 
+  /**
+   * Get a shell command suggestion, calling `onToken` with partial output as it streams in.
+   * Resolves with the same structured response as getSuggestion once the stream ends.
+   */
+  async getSuggestionStream(
+    query: string,
+    onToken: (token: string) => void,
+    context?: string
+  ): Promise<ShellCommandResponse> {
+    this._error.set(null);
+
+    const requestId = crypto.randomUUID();
+    const unlisten = await listen<{ requestId: string; token: string }>('ai:token', (event) => {
+      if (event.payload.requestId === requestId) {
+        onToken(event.payload.token);
+      }
+    });
+
+    try {
+      const request: ShellSuggestionRequest = {
+        query,
+        context,
+        os: this.detectOS(),
+        shell: this.detectShell(),
+      };
+
+      return await this.tauri.invoke<ShellCommandResponse>('get_shell_suggestion_stream', {
+        request,
+        requestId,
+      });
+    } catch (err) {
+      const message = errorMessage(err);
+      this._error.set(message);
+      throw err;
+    } finally {
+      unlisten();
+    }
+  }
+
   /**
    * Detect the current OS
    */
@@ -271,24 +333,46 @@ export class AiService {
   }
 
   /**
-   * Pull/download a model from Ollama
+   * Pull/download a model from Ollama, calling `onProgress` as layers download.
+   * Pass your own `pullId` to be able to stop it with cancelOllamaPull.
    */
-  async pullOllamaModel(modelName: string, endpointUrl?: string): Promise<string> {
+  async pullOllamaModel(
+    modelName: string,
+    endpointUrl?: string,
+    onProgress?: (progress: OllamaPullProgress) => void,
+    pullId: string = crypto.randomUUID()
+  ): Promise<string> {
     this._error.set(null);
 
+    const unlisten = await listen<OllamaPullProgress>('ollama:pull-progress', (event) => {
+      if (event.payload.pullId === pullId) {
+        onProgress?.(event.payload);
+      }
+    });
+
     try {
       const result = await this.tauri.invoke<string>('pull_ollama_model', {
         modelName,
         endpointUrl,
+        pullId,
       });
       return result;
     } catch (err) {
-      const message = err instanceof Error ? err.message : String(err);
+      const message = errorMessage(err);
       this._error.set(message);
       throw err;
+    } finally {
+      unlisten();
     }
   }
 
+  /**
+   * Stop a pull started by pullOllamaModel
+   */
+  cancelOllamaPull(pullId: string): Promise<void> {
+    return this.tauri.invoke<void>('cancel_execution', { executionId: pullId });
+  }
+
   /**
    * Delete a model from Ollama
    */
@@ -301,7 +385,7 @@ export class AiService {
         endpointUrl,
       });
     } catch (err) {
-      const message = err instanceof Error ? err.message : String(err);
+      const message = errorMessage(err);
       this._error.set(message);
       throw err;
     }
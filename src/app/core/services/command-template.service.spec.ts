@@ -19,6 +19,22 @@ describe('CommandTemplateService', () => {
     expect(mockTauri.invoke).toHaveBeenCalledWith('list_command_templates', {});
   });
 
+  it('should list templates sorted by usage', async () => {
+    mockTauri.invoke.mockResolvedValue([]);
+
+    await service.listTemplates('mostUsed');
+    expect(mockTauri.invoke).toHaveBeenCalledWith('list_command_templates', { sortBy: 'mostUsed' });
+  });
+
+  it('should record a template use', async () => {
+    const template = { id: '1', useCount: 3 };
+    mockTauri.invoke.mockResolvedValue(template);
+
+    const result = await service.recordUse('1');
+    expect(result).toEqual(template);
+    expect(mockTauri.invoke).toHaveBeenCalledWith('record_template_use', { id: '1' });
+  });
+
   it('should get a template by id', async () => {
     const template = { id: '1', name: 'Test' };
     mockTauri.invoke.mockResolvedValue(template);
@@ -73,4 +89,77 @@ describe('CommandTemplateService', () => {
     expect(result).toEqual(duplicate);
     expect(mockTauri.invoke).toHaveBeenCalledWith('duplicate_command_template', { id: '1' });
   });
+
+  it('should validate variables', async () => {
+    const errors = [{ name: 'HOST_PORT', message: 'Must be a port number between 1 and 65535' }];
+    mockTauri.invoke.mockResolvedValue(errors);
+
+    const result = await service.validateVariables('1', { HOST_PORT: '99999' });
+    expect(result).toEqual(errors);
+    expect(mockTauri.invoke).toHaveBeenCalledWith('validate_template_variables', {
+      templateId: '1',
+      values: { HOST_PORT: '99999' },
+    });
+  });
+
+  it('should export templates', async () => {
+    mockTauri.invoke.mockResolvedValue('{"version":1}');
+
+    const result = await service.exportTemplates(['1']);
+    expect(result).toBe('{"version":1}');
+    expect(mockTauri.invoke).toHaveBeenCalledWith('export_command_templates', { ids: ['1'] });
+  });
+
+  it('should import templates', async () => {
+    const summary = { imported: 2, overwritten: 0, skipped: ['Deploy'] };
+    mockTauri.invoke.mockResolvedValue(summary);
+
+    const result = await service.importTemplates('{"version":1}', false);
+    expect(result).toEqual(summary);
+    expect(mockTauri.invoke).toHaveBeenCalledWith('import_command_templates', {
+      json: '{"version":1}',
+      overwrite: false,
+    });
+  });
+
+  it('should plan template steps', async () => {
+    const plan = [{ index: 0, description: 'Stop', command: 'docker stop web', dangerLevel: 'moderate', dangerExplanation: '' }];
+    mockTauri.invoke.mockResolvedValue(plan);
+
+    const result = await service.planSteps('1', { CONTAINER_NAME: 'web' });
+    expect(result).toEqual(plan);
+    expect(mockTauri.invoke).toHaveBeenCalledWith('plan_template_steps', {
+      templateId: '1',
+      values: { CONTAINER_NAME: 'web' },
+    });
+  });
+
+  it('should run template steps', async () => {
+    const run = { steps: [], failedStep: null };
+    mockTauri.invoke.mockResolvedValue(run);
+
+    const result = await service.runSteps('sys-1', '1', {}, true);
+    expect(result).toEqual(run);
+    expect(mockTauri.invoke).toHaveBeenCalledWith('run_template_steps', {
+      systemId: 'sys-1',
+      templateId: '1',
+      values: {},
+      confirmed: true,
+      context: undefined,
+    });
+  });
+
+  it('should run template steps with an execution context', async () => {
+    mockTauri.invoke.mockResolvedValue({ steps: [], failedStep: null });
+    const context = { cwd: '/srv/app', env: { STAGE: 'prod' } };
+
+    await service.runSteps('sys-1', '1', {}, false, context);
+    expect(mockTauri.invoke).toHaveBeenCalledWith('run_template_steps', {
+      systemId: 'sys-1',
+      templateId: '1',
+      values: {},
+      confirmed: false,
+      context,
+    });
+  });
 });
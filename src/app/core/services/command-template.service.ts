@@ -2,8 +2,14 @@ import { Injectable } from '@angular/core';
 import {
   CommandTemplate,
   CreateCommandTemplateRequest,
+  PlannedStep,
+  TemplateImportResult,
+  TemplateRunResult,
+  TemplateSortBy,
   UpdateCommandTemplateRequest,
+  VariableValidationError,
 } from '../models/command-template.model';
+import { ExecContext } from '../models/system.model';
 import { TauriService } from './tauri.service';
 
 @Injectable({
@@ -13,10 +19,10 @@ export class CommandTemplateService {
   constructor(private tauri: TauriService) {}
 
   /**
-   * List all command templates
+   * List all command templates, optionally sorted by usage
    */
-  listTemplates(): Promise<CommandTemplate[]> {
-    return this.tauri.invoke<CommandTemplate[]>('list_command_templates', {});
+  listTemplates(sortBy?: TemplateSortBy): Promise<CommandTemplate[]> {
+    return this.tauri.invoke<CommandTemplate[]>('list_command_templates', sortBy ? { sortBy } : {});
   }
 
   /**
@@ -54,10 +60,75 @@ export class CommandTemplateService {
     return this.tauri.invoke<CommandTemplate>('toggle_command_favorite', { id });
   }
 
+  /**
+   * Record that a command template was executed
+   */
+  recordUse(id: string): Promise<CommandTemplate> {
+    return this.tauri.invoke<CommandTemplate>('record_template_use', { id });
+  }
+
   /**
    * Duplicate a command template
    */
   duplicateTemplate(id: string): Promise<CommandTemplate> {
     return this.tauri.invoke<CommandTemplate>('duplicate_command_template', { id });
   }
+
+  /**
+   * Validate variable values against a template's variable types
+   */
+  validateVariables(
+    templateId: string,
+    values: Record<string, string>
+  ): Promise<VariableValidationError[]> {
+    return this.tauri.invoke<VariableValidationError[]>('validate_template_variables', {
+      templateId,
+      values,
+    });
+  }
+
+  /**
+   * Export custom templates (all, or only the given IDs) as a JSON document
+   */
+  exportTemplates(ids?: string[]): Promise<string> {
+    return this.tauri.invoke<string>('export_command_templates', { ids });
+  }
+
+  /**
+   * Import templates from an exported JSON document
+   */
+  importTemplates(json: string, overwrite: boolean): Promise<TemplateImportResult> {
+    return this.tauri.invoke<TemplateImportResult>('import_command_templates', {
+      json,
+      overwrite,
+    });
+  }
+
+  /**
+   * Preview a template's steps with variables substituted and danger levels
+   */
+  planSteps(templateId: string, values: Record<string, string>): Promise<PlannedStep[]> {
+    return this.tauri.invoke<PlannedStep[]>('plan_template_steps', { templateId, values });
+  }
+
+  /**
+   * Run a template's steps in order on a system, stopping at the first failure.
+   * Dangerous steps are refused unless `confirmed` is true.
+   * Every step runs in the working directory and environment from `context`.
+   */
+  runSteps(
+    systemId: string,
+    templateId: string,
+    values: Record<string, string>,
+    confirmed: boolean,
+    context?: ExecContext
+  ): Promise<TemplateRunResult> {
+    return this.tauri.invoke<TemplateRunResult>('run_template_steps', {
+      systemId,
+      templateId,
+      values,
+      confirmed,
+      context,
+    });
+  }
 }
@@ -18,6 +18,13 @@ describe('ContainerService', () => {
       expect(tauriMock.invoke).toHaveBeenCalledWith('list_containers', { systemId: 'sys-1' });
     });
 
+    it('should pass a filter through', async () => {
+      tauriMock.invoke.mockResolvedValue([]);
+      const filter = { all: false, label: 'com.docker.compose.project=shop' };
+      await service.listContainers('sys-1', filter);
+      expect(tauriMock.invoke).toHaveBeenCalledWith('list_containers', { systemId: 'sys-1', filter });
+    });
+
     it('should return containers from backend', async () => {
       const containers = [{ id: 'c1', name: 'web' }];
       tauriMock.invoke.mockResolvedValue(containers);
@@ -42,6 +49,98 @@ describe('ContainerService', () => {
         runtime: 'docker',
       });
     });
+
+    it('should pass an execution context through', async () => {
+      tauriMock.invoke.mockResolvedValue(undefined);
+      const context = { cwd: '/srv/app', env: { DOCKER_HOST: 'tcp://10.0.0.2:2375' } };
+      await service.performAction('sys-1', 'c1', 'start', 'docker', context);
+      expect(tauriMock.invoke).toHaveBeenCalledWith('perform_container_action', {
+        systemId: 'sys-1',
+        containerId: 'c1',
+        action: 'start',
+        runtime: 'docker',
+        context,
+      });
+    });
+  });
+
+  describe('createContainer', () => {
+    it('should send the spec and return the new id', async () => {
+      tauriMock.invoke.mockResolvedValue('4a7f7eeb');
+      const spec = {
+        image: 'nginx:latest',
+        name: 'web',
+        ports: [{ hostPort: 8080, containerPort: 80 }],
+        env: { GREETING: 'hello world' },
+      };
+      const id = await service.createContainer('sys-1', 'docker', spec);
+      expect(id).toBe('4a7f7eeb');
+      expect(tauriMock.invoke).toHaveBeenCalledWith('create_container', {
+        systemId: 'sys-1',
+        runtime: 'docker',
+        spec,
+        context: undefined,
+      });
+    });
+  });
+
+  describe('renameContainer', () => {
+    it('should call with correct arguments', async () => {
+      const container = { id: 'c1', name: 'api' };
+      tauriMock.invoke.mockResolvedValue(container);
+      const result = await service.renameContainer('sys-1', 'c1', 'api', 'docker');
+      expect(result).toEqual(container);
+      expect(tauriMock.invoke).toHaveBeenCalledWith('rename_container', {
+        systemId: 'sys-1',
+        containerId: 'c1',
+        newName: 'api',
+        runtime: 'docker',
+      });
+    });
+  });
+
+  describe('updateRestartPolicy', () => {
+    it('should call with correct arguments', async () => {
+      tauriMock.invoke.mockResolvedValue({ id: 'c1' });
+      await service.updateRestartPolicy('sys-1', 'c1', 'on-failure', 'podman', 3);
+      expect(tauriMock.invoke).toHaveBeenCalledWith('update_container_restart_policy', {
+        systemId: 'sys-1',
+        containerId: 'c1',
+        policy: 'on-failure',
+        maxRetries: 3,
+        runtime: 'podman',
+      });
+    });
+  });
+
+  describe('performBulkAction', () => {
+    it('should call with correct arguments', async () => {
+      const results = { c1: { success: true, error: null } };
+      tauriMock.invoke.mockResolvedValue(results);
+      const result = await service.performBulkAction('sys-1', ['c1', 'c2'], 'remove', 'docker', {
+        force: true,
+        confirmed: true,
+      });
+      expect(result).toEqual(results);
+      expect(tauriMock.invoke).toHaveBeenCalledWith('perform_container_action_bulk', {
+        systemId: 'sys-1',
+        containerIds: ['c1', 'c2'],
+        action: 'remove',
+        runtime: 'docker',
+        force: true,
+        confirmed: true,
+        context: undefined,
+      });
+    });
+
+    it('should not confirm by default', async () => {
+      tauriMock.invoke.mockResolvedValue({});
+      await service.performBulkAction('sys-1', ['c1'], 'stop', 'podman');
+      expect(tauriMock.invoke).toHaveBeenCalledWith(
+        'perform_container_action_bulk',
+        expect.objectContaining({ confirmed: false })
+      );
+    });
   });
 
   describe('getLogs', () => {
@@ -69,6 +168,69 @@ describe('ContainerService', () => {
         timestamps: false,
       });
     });
+
+    it('should pass a time range without a tail', async () => {
+      tauriMock.invoke.mockResolvedValue('');
+      await service.getLogs('sys-1', 'c1', 'docker', null, true, '10m', '2024-01-01T00:00:00Z');
+      expect(tauriMock.invoke).toHaveBeenCalledWith('get_container_logs', {
+        systemId: 'sys-1',
+        containerId: 'c1',
+        runtime: 'docker',
+        tail: null,
+        since: '10m',
+        until: '2024-01-01T00:00:00Z',
+        timestamps: true,
+      });
+    });
+  });
+
+  describe('streamLogs', () => {
+    it('should start a stream with options', async () => {
+      tauriMock.invoke.mockResolvedValue('stream-1');
+      const streamId = await service.streamLogs('sys-1', 'c1', 'podman', { tail: 20, stderr: false });
+      expect(streamId).toBe('stream-1');
+      expect(tauriMock.invoke).toHaveBeenCalledWith('stream_container_logs', {
+        systemId: 'sys-1',
+        containerId: 'c1',
+        runtime: 'podman',
+        options: { tail: 20, stderr: false },
+      });
+    });
+
+    it('should stop a stream by id', async () => {
+      tauriMock.invoke.mockResolvedValue(undefined);
+      await service.stopLogStream('stream-1');
+      expect(tauriMock.invoke).toHaveBeenCalledWith('stop_container_log_stream', {
+        streamId: 'stream-1',
+      });
+    });
+  });
+
+  describe('streamEvents', () => {
+    it('should start a stream on the primary runtime by default', async () => {
+      tauriMock.invoke.mockResolvedValue('events-1');
+      const streamId = await service.streamEvents('sys-1');
+      expect(streamId).toBe('events-1');
+      expect(tauriMock.invoke).toHaveBeenCalledWith('stream_docker_events', {
+        systemId: 'sys-1',
+        runtime: undefined,
+      });
+    });
+
+    it('should pass an explicit runtime', async () => {
+      tauriMock.invoke.mockResolvedValue('events-2');
+      await service.streamEvents('sys-1', 'podman');
+      expect(tauriMock.invoke).toHaveBeenCalledWith('stream_docker_events', {
+        systemId: 'sys-1',
+        runtime: 'podman',
+      });
+    });
+
+    it('should stop a stream by id', async () => {
+      tauriMock.invoke.mockResolvedValue(undefined);
+      await service.stopEvents('events-1');
+      expect(tauriMock.invoke).toHaveBeenCalledWith('stop_docker_events', { streamId: 'events-1' });
+    });
   });
 
   describe('inspectContainer', () => {
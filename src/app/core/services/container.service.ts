@@ -1,10 +1,20 @@
 import { Injectable } from '@angular/core';
+import { listen, UnlistenFn } from '@tauri-apps/api/event';
 import {
   Container,
   ContainerAction,
+  ContainerActionResult,
+  ContainerCreateSpec,
   ContainerDetails,
+  ContainerListFilter,
+  ContainerLogLine,
+  ContainerLogOptions,
+  ContainerLogStreamEnded,
   ContainerRuntime,
+  RuntimeEventMessage,
+  RuntimeEventStreamEnded,
 } from '../models/container.model';
+import { ExecContext } from '../models/system.model';
 import { TauriService } from './tauri.service';
 
 @Injectable({
@@ -13,40 +23,171 @@ import { TauriService } from './tauri.service';
 export class ContainerService {
   constructor(private tauri: TauriService) {}
 
-  listContainers(systemId: string): Promise<Container[]> {
-    return this.tauri.invoke<Container[]>('list_containers', { systemId });
+  /** List a system's containers; `filter` is applied by the runtime */
+  listContainers(systemId: string, filter?: ContainerListFilter): Promise<Container[]> {
+    return this.tauri.invoke<Container[]>('list_containers', { systemId, filter });
   }
 
   performAction(
     systemId: string,
     containerId: string,
     action: ContainerAction,
-    runtime: ContainerRuntime
+    runtime: ContainerRuntime,
+    context?: ExecContext
   ): Promise<void> {
     return this.tauri.invoke<void>('perform_container_action', {
       systemId,
       containerId,
       action,
       runtime,
+      context,
+    });
+  }
+
+  /** Create a container from a structured spec; resolves to its ID */
+  createContainer(
+    systemId: string,
+    runtime: ContainerRuntime,
+    spec: ContainerCreateSpec,
+    context?: ExecContext
+  ): Promise<string> {
+    return this.tauri.invoke<string>('create_container', { systemId, runtime, spec, context });
+  }
+
+  /** Rename a container; resolves to the re-inspected container */
+  renameContainer(
+    systemId: string,
+    containerId: string,
+    newName: string,
+    runtime: ContainerRuntime
+  ): Promise<Container> {
+    return this.tauri.invoke<Container>('rename_container', { systemId, containerId, newName, runtime });
+  }
+
+  /** Change a container's restart policy; `maxRetries` only applies to 'on-failure' */
+  updateRestartPolicy(
+    systemId: string,
+    containerId: string,
+    policy: 'no' | 'always' | 'unless-stopped' | 'on-failure',
+    runtime: ContainerRuntime,
+    maxRetries?: number
+  ): Promise<Container> {
+    return this.tauri.invoke<Container>('update_container_restart_policy', {
+      systemId,
+      containerId,
+      policy,
+      maxRetries,
+      runtime,
+    });
+  }
+
+  /**
+   * Apply an action to several containers, in one runtime call where possible.
+   * `force` makes 'remove' a forced removal; the backend refuses commands its
+   * danger classifier flags unless `confirmed` is set.
+   */
+  performBulkAction(
+    systemId: string,
+    containerIds: string[],
+    action: ContainerAction,
+    runtime: ContainerRuntime,
+    options: { force?: boolean; confirmed?: boolean; context?: ExecContext } = {}
+  ): Promise<Record<string, ContainerActionResult>> {
+    return this.tauri.invoke<Record<string, ContainerActionResult>>('perform_container_action_bulk', {
+      systemId,
+      containerIds,
+      action,
+      runtime,
+      force: options.force,
+      confirmed: options.confirmed ?? false,
+      context: options.context,
     });
   }
 
+  /**
+   * Fetch a snapshot of a container's logs.
+   * since/until take a duration (10m) or an RFC3339 timestamp.
+   */
   getLogs(
     systemId: string,
     containerId: string,
     runtime: ContainerRuntime,
-    tail: number = 100,
-    timestamps: boolean = true
+    tail: number | null = 100,
+    timestamps: boolean = true,
+    since?: string,
+    until?: string
   ): Promise<string> {
     return this.tauri.invoke<string>('get_container_logs', {
       systemId,
       containerId,
       runtime,
       tail,
+      since,
+      until,
       timestamps,
     });
   }
 
+  /**
+   * Follow a container's logs in the background.
+   * Resolves with a stream ID; lines arrive through onLogLine until stopLogStream.
+   */
+  streamLogs(
+    systemId: string,
+    containerId: string,
+    runtime: ContainerRuntime,
+    options?: ContainerLogOptions
+  ): Promise<string> {
+    return this.tauri.invoke<string>('stream_container_logs', {
+      systemId,
+      containerId,
+      runtime,
+      options,
+    });
+  }
+
+  /** Stop a log stream; the backend also kills the `logs -f` process on the host */
+  stopLogStream(streamId: string): Promise<void> {
+    return this.tauri.invoke<void>('stop_container_log_stream', { streamId });
+  }
+
+  onLogLine(handler: (event: ContainerLogLine) => void): Promise<UnlistenFn> {
+    return listen<ContainerLogLine>('container:logs', (event) => {
+      handler(event.payload);
+    });
+  }
+
+  onLogStreamEnded(handler: (event: ContainerLogStreamEnded) => void): Promise<UnlistenFn> {
+    return listen<ContainerLogStreamEnded>('container:logs-ended', (event) => {
+      handler(event.payload);
+    });
+  }
+
+  /**
+   * Follow runtime events (container start/stop, image pulls, ...) in the background.
+   * Uses the system's primary runtime unless one is given.
+   * Resolves with a stream ID; events arrive through onRuntimeEvent until stopEvents.
+   */
+  streamEvents(systemId: string, runtime?: ContainerRuntime): Promise<string> {
+    return this.tauri.invoke<string>('stream_docker_events', { systemId, runtime });
+  }
+
+  stopEvents(streamId: string): Promise<void> {
+    return this.tauri.invoke<void>('stop_docker_events', { streamId });
+  }
+
+  onRuntimeEvent(handler: (event: RuntimeEventMessage) => void): Promise<UnlistenFn> {
+    return listen<RuntimeEventMessage>('runtime:event', (event) => {
+      handler(event.payload);
+    });
+  }
+
+  onEventStreamEnded(handler: (event: RuntimeEventStreamEnded) => void): Promise<UnlistenFn> {
+    return listen<RuntimeEventStreamEnded>('runtime:events-ended', (event) => {
+      handler(event.payload);
+    });
+  }
+
   inspectContainer(
     systemId: string,
     containerId: string,
@@ -50,6 +50,48 @@ describe('FileBrowserService', () => {
     });
   });
 
+  it('should get a file size', async () => {
+    mockTauri.invoke.mockResolvedValue(5_000_000_000);
+
+    const size = await service.getFileSize('sys-1', '/var/log/big.log');
+    expect(size).toBe(5_000_000_000);
+    expect(mockTauri.invoke).toHaveBeenCalledWith('get_file_size', {
+      systemId: 'sys-1',
+      path: '/var/log/big.log',
+      containerId: undefined,
+      runtime: undefined,
+    });
+  });
+
+  it('should read a file range', async () => {
+    const chunk = { path: '/var/log/big.log', offset: 1024, bytesRead: 10, fileSize: 1034, content: '0123456789', isBinary: false };
+    mockTauri.invoke.mockResolvedValue(chunk);
+
+    const result = await service.readFileRange('sys-1', '/var/log/big.log', 1024, 4096);
+    expect(result).toEqual(chunk);
+    expect(mockTauri.invoke).toHaveBeenCalledWith('read_file_range', {
+      systemId: 'sys-1',
+      path: '/var/log/big.log',
+      offsetBytes: 1024,
+      lengthBytes: 4096,
+      containerId: undefined,
+      runtime: undefined,
+    });
+  });
+
+  it('should tail a file', async () => {
+    mockTauri.invoke.mockResolvedValue({ path: '/var/log/big.log', content: 'last\n', size: 5, isBinary: false });
+
+    await service.tailFile('sys-1', '/var/log/big.log', 100);
+    expect(mockTauri.invoke).toHaveBeenCalledWith('tail_file', {
+      systemId: 'sys-1',
+      path: '/var/log/big.log',
+      lines: 100,
+      containerId: undefined,
+      runtime: undefined,
+    });
+  });
+
   it('should write a file', async () => {
     mockTauri.invoke.mockResolvedValue(undefined);
 
@@ -101,6 +143,103 @@ describe('FileBrowserService', () => {
     });
   });
 
+  it('should get a directory size', async () => {
+    mockTauri.invoke.mockResolvedValue({ path: '/var', size: 4096, partial: false });
+
+    const result = await service.getDirectorySize('sys-1', '/var');
+    expect(result.size).toBe(4096);
+    expect(mockTauri.invoke).toHaveBeenCalledWith('get_directory_size', {
+      systemId: 'sys-1',
+      path: '/var',
+      containerId: undefined,
+      runtime: undefined,
+    });
+  });
+
+  it('should get directory tree sizes', async () => {
+    mockTauri.invoke.mockResolvedValue({ path: '/var', total: 0, children: [], partial: false });
+
+    await service.getDirectoryTreeSizes('sys-1', '/var', 1, 'c1', 'docker');
+    expect(mockTauri.invoke).toHaveBeenCalledWith('get_directory_tree_sizes', {
+      systemId: 'sys-1',
+      path: '/var',
+      depth: 1,
+      containerId: 'c1',
+      runtime: 'docker',
+    });
+  });
+
+  it('should search file contents', async () => {
+    mockTauri.invoke.mockResolvedValue({ hits: [], truncated: false });
+
+    await service.searchFileContents('sys-1', '/srv', 'error', { caseInsensitive: true });
+    expect(mockTauri.invoke).toHaveBeenCalledWith('search_file_contents', {
+      systemId: 'sys-1',
+      path: '/srv',
+      query: 'error',
+      options: { caseInsensitive: true },
+      containerId: undefined,
+      runtime: undefined,
+    });
+  });
+
+  it('should change permissions', async () => {
+    mockTauri.invoke.mockResolvedValue({ path: '/srv/app', permissions: 'rwxr-xr-x' });
+
+    const result = await service.changePermissions('sys-1', '/srv/app', '755', true);
+    expect(result.permissions).toBe('rwxr-xr-x');
+    expect(mockTauri.invoke).toHaveBeenCalledWith('change_permissions', {
+      systemId: 'sys-1',
+      path: '/srv/app',
+      mode: '755',
+      recursive: true,
+      containerId: undefined,
+      runtime: undefined,
+    });
+  });
+
+  it('should change owner', async () => {
+    mockTauri.invoke.mockResolvedValue({ path: '/srv/app', owner: 'www-data', group: 'staff' });
+
+    await service.changeOwner('sys-1', '/srv/app', 'www-data', 'staff');
+    expect(mockTauri.invoke).toHaveBeenCalledWith('change_owner', {
+      systemId: 'sys-1',
+      path: '/srv/app',
+      owner: 'www-data',
+      group: 'staff',
+      recursive: undefined,
+      containerId: undefined,
+      runtime: undefined,
+    });
+  });
+
+  it('should create an archive', async () => {
+    mockTauri.invoke.mockResolvedValue({ path: '/tmp/app.tar.gz' });
+
+    await service.createArchive('sys-1', ['/srv/app'], '/tmp/app.tar.gz', 'tar.gz');
+    expect(mockTauri.invoke).toHaveBeenCalledWith('create_archive', {
+      systemId: 'sys-1',
+      paths: ['/srv/app'],
+      dest: '/tmp/app.tar.gz',
+      format: 'tar.gz',
+      containerId: undefined,
+      runtime: undefined,
+    });
+  });
+
+  it('should extract an archive', async () => {
+    mockTauri.invoke.mockResolvedValue({ path: '/srv/app' });
+
+    await service.extractArchive('sys-1', '/tmp/app.zip', '/srv/app');
+    expect(mockTauri.invoke).toHaveBeenCalledWith('extract_archive', {
+      systemId: 'sys-1',
+      archivePath: '/tmp/app.zip',
+      dest: '/srv/app',
+      containerId: undefined,
+      runtime: undefined,
+    });
+  });
+
   it('should download a file', async () => {
     mockTauri.invoke.mockResolvedValue(undefined);
 
@@ -126,4 +265,16 @@ describe('FileBrowserService', () => {
       runtime: undefined,
     });
   });
+
+  it('should transfer a file between systems', async () => {
+    mockTauri.invoke.mockResolvedValue(undefined);
+
+    await service.transferBetweenSystems('sys-1', '/srv/dump.sql', 'sys-2', '/backup/dump.sql');
+    expect(mockTauri.invoke).toHaveBeenCalledWith('transfer_between_systems', {
+      srcSystemId: 'sys-1',
+      srcPath: '/srv/dump.sql',
+      dstSystemId: 'sys-2',
+      dstPath: '/backup/dump.sql',
+    });
+  });
 });
@@ -1,6 +1,19 @@
 import { Injectable } from '@angular/core';
+import { listen, UnlistenFn } from '@tauri-apps/api/event';
 import { ContainerRuntime } from '../models/container.model';
-import { DirectoryListing, FileContent } from '../models/file-browser.model';
+import {
+  ArchiveFormat,
+  DirectoryListing,
+  DirectorySize,
+  DirectoryTreeSizes,
+  FileChunk,
+  FileContent,
+  FileEntry,
+  FileTransferProgress,
+  SearchOptions,
+  SearchResults,
+  SystemTransferProgress,
+} from '../models/file-browser.model';
 import { TauriService } from './tauri.service';
 
 @Injectable({ providedIn: 'root' })
@@ -29,6 +42,42 @@ export class FileBrowserService {
     });
   }
 
+  getFileSize(
+    systemId: string,
+    path: string,
+    containerId?: string | null,
+    runtime?: ContainerRuntime | null,
+  ): Promise<number> {
+    return this.tauri.invoke<number>('get_file_size', {
+      systemId, path, containerId, runtime,
+    });
+  }
+
+  readFileRange(
+    systemId: string,
+    path: string,
+    offsetBytes: number,
+    lengthBytes: number,
+    containerId?: string | null,
+    runtime?: ContainerRuntime | null,
+  ): Promise<FileChunk> {
+    return this.tauri.invoke<FileChunk>('read_file_range', {
+      systemId, path, offsetBytes, lengthBytes, containerId, runtime,
+    });
+  }
+
+  tailFile(
+    systemId: string,
+    path: string,
+    lines: number,
+    containerId?: string | null,
+    runtime?: ContainerRuntime | null,
+  ): Promise<FileContent> {
+    return this.tauri.invoke<FileContent>('tail_file', {
+      systemId, path, lines, containerId, runtime,
+    });
+  }
+
   writeFile(
     systemId: string,
     path: string,
@@ -76,6 +125,94 @@ export class FileBrowserService {
     });
   }
 
+  getDirectorySize(
+    systemId: string,
+    path: string,
+    containerId?: string | null,
+    runtime?: ContainerRuntime | null,
+  ): Promise<DirectorySize> {
+    return this.tauri.invoke<DirectorySize>('get_directory_size', {
+      systemId, path, containerId, runtime,
+    });
+  }
+
+  getDirectoryTreeSizes(
+    systemId: string,
+    path: string,
+    depth?: number,
+    containerId?: string | null,
+    runtime?: ContainerRuntime | null,
+  ): Promise<DirectoryTreeSizes> {
+    return this.tauri.invoke<DirectoryTreeSizes>('get_directory_tree_sizes', {
+      systemId, path, depth, containerId, runtime,
+    });
+  }
+
+  searchFileContents(
+    systemId: string,
+    path: string,
+    query: string,
+    options?: SearchOptions,
+    containerId?: string | null,
+    runtime?: ContainerRuntime | null,
+  ): Promise<SearchResults> {
+    return this.tauri.invoke<SearchResults>('search_file_contents', {
+      systemId, path, query, options, containerId, runtime,
+    });
+  }
+
+  changePermissions(
+    systemId: string,
+    path: string,
+    mode: string,
+    recursive?: boolean,
+    containerId?: string | null,
+    runtime?: ContainerRuntime | null,
+  ): Promise<FileEntry> {
+    return this.tauri.invoke<FileEntry>('change_permissions', {
+      systemId, path, mode, recursive, containerId, runtime,
+    });
+  }
+
+  changeOwner(
+    systemId: string,
+    path: string,
+    owner: string,
+    group?: string,
+    recursive?: boolean,
+    containerId?: string | null,
+    runtime?: ContainerRuntime | null,
+  ): Promise<FileEntry> {
+    return this.tauri.invoke<FileEntry>('change_owner', {
+      systemId, path, owner, group, recursive, containerId, runtime,
+    });
+  }
+
+  createArchive(
+    systemId: string,
+    paths: string[],
+    dest: string,
+    format: ArchiveFormat,
+    containerId?: string | null,
+    runtime?: ContainerRuntime | null,
+  ): Promise<FileEntry> {
+    return this.tauri.invoke<FileEntry>('create_archive', {
+      systemId, paths, dest, format, containerId, runtime,
+    });
+  }
+
+  extractArchive(
+    systemId: string,
+    archivePath: string,
+    dest: string,
+    containerId?: string | null,
+    runtime?: ContainerRuntime | null,
+  ): Promise<FileEntry> {
+    return this.tauri.invoke<FileEntry>('extract_archive', {
+      systemId, archivePath, dest, containerId, runtime,
+    });
+  }
+
   downloadFile(
     systemId: string,
     remotePath: string,
@@ -88,6 +225,15 @@ export class FileBrowserService {
     });
   }
 
+  /**
+   * Listen for progress of uploads/downloads that go over SFTP
+   */
+  onTransferProgress(handler: (progress: FileTransferProgress) => void): Promise<UnlistenFn> {
+    return listen<FileTransferProgress>('file-transfer:progress', (event) => {
+      handler(event.payload);
+    });
+  }
+
   uploadFile(
     systemId: string,
     localPath: string,
@@ -99,4 +245,30 @@ export class FileBrowserService {
       systemId, localPath, remotePath, containerId, runtime,
     });
   }
+
+  /**
+   * Copy a file from one system to another without downloading it first.
+   * The destination is only replaced once the copy is complete.
+   */
+  transferBetweenSystems(
+    srcSystemId: string,
+    srcPath: string,
+    dstSystemId: string,
+    dstPath: string,
+  ): Promise<void> {
+    return this.tauri.invoke<void>('transfer_between_systems', {
+      srcSystemId, srcPath, dstSystemId, dstPath,
+    });
+  }
+
+  /**
+   * Listen for progress of copies between systems
+   */
+  onSystemTransferProgress(
+    handler: (progress: SystemTransferProgress) => void
+  ): Promise<UnlistenFn> {
+    return listen<SystemTransferProgress>('transfer:progress', (event) => {
+      handler(event.payload);
+    });
+  }
 }
@@ -19,19 +19,40 @@ describe('ImageService', () => {
     });
   });
 
+  describe('inspectImage', () => {
+    it('should call with image and runtime', async () => {
+      tauriMock.invoke.mockResolvedValue({ id: 'sha256:abc', history: [] });
+      const details = await service.inspectImage('sys-1', 'sha256:abc', 'podman');
+      expect(details.id).toBe('sha256:abc');
+      expect(tauriMock.invoke).toHaveBeenCalledWith('inspect_image', {
+        systemId: 'sys-1',
+        imageId: 'sha256:abc',
+        runtime: 'podman',
+      });
+    });
+  });
+
   describe('pullImage', () => {
     it('should call with all required parameters', async () => {
-      tauriMock.invoke.mockResolvedValue(undefined);
-      await service.pullImage('sys-1', 'nginx', 'latest', 'docker');
+      tauriMock.invoke.mockResolvedValue('exec-1');
+      const executionId = await service.pullImage('sys-1', 'nginx', 'latest', 'docker');
+      expect(executionId).toBe('exec-1');
       expect(tauriMock.invoke).toHaveBeenCalledWith('pull_image', {
         systemId: 'sys-1',
-        name: 'nginx',
-        tag: 'latest',
+        image: 'nginx:latest',
         runtime: 'docker',
       });
     });
   });
 
+  describe('cancelPull', () => {
+    it('should cancel the execution', async () => {
+      tauriMock.invoke.mockResolvedValue(undefined);
+      await service.cancelPull('exec-1');
+      expect(tauriMock.invoke).toHaveBeenCalledWith('cancel_execution', { executionId: 'exec-1' });
+    });
+  });
+
   describe('removeImage', () => {
     it('should call with correct parameters', async () => {
       tauriMock.invoke.mockResolvedValue(undefined);
@@ -1,6 +1,12 @@
 import { Injectable } from '@angular/core';
+import { listen, UnlistenFn } from '@tauri-apps/api/event';
 import { ContainerRuntime } from '../models/container.model';
-import { ContainerImage } from '../models/image.model';
+import {
+  ContainerImage,
+  ImageDetails,
+  ImagePullFinished,
+  ImagePullProgress,
+} from '../models/image.model';
 import { TauriService } from './tauri.service';
 
 @Injectable({
@@ -13,20 +19,53 @@ export class ImageService {
     return this.tauri.invoke<ContainerImage[]>('list_images', { systemId });
   }
 
+  inspectImage(
+    systemId: string,
+    imageId: string,
+    runtime: ContainerRuntime
+  ): Promise<ImageDetails> {
+    return this.tauri.invoke<ImageDetails>('inspect_image', { systemId, imageId, runtime });
+  }
+
+  /**
+   * Start pulling an image in the background.
+   * Resolves with an execution ID; the outcome arrives through onPullFinished.
+   */
   pullImage(
     systemId: string,
     name: string,
     tag: string,
     runtime: ContainerRuntime
-  ): Promise<void> {
-    return this.tauri.invoke<void>('pull_image', {
+  ): Promise<string> {
+    return this.tauri.invoke<string>('pull_image', {
       systemId,
-      name,
-      tag,
+      image: tag ? `${name}:${tag}` : name,
       runtime,
     });
   }
 
+  /**
+   * Listen for per-layer progress of background pulls
+   */
+  onPullProgress(handler: (event: ImagePullProgress) => void): Promise<UnlistenFn> {
+    return listen<ImagePullProgress>('image:pull-progress', (event) => {
+      handler(event.payload);
+    });
+  }
+
+  /**
+   * Listen for background pulls finishing, failing, or being cancelled
+   */
+  onPullFinished(handler: (event: ImagePullFinished) => void): Promise<UnlistenFn> {
+    return listen<ImagePullFinished>('image:pull-finished', (event) => {
+      handler(event.payload);
+    });
+  }
+
+  cancelPull(executionId: string): Promise<void> {
+    return this.tauri.invoke<void>('cancel_execution', { executionId });
+  }
+
   removeImage(
     systemId: string,
     imageId: string,
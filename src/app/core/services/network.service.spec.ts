@@ -43,6 +43,25 @@ describe('NetworkService', () => {
         subnet: '172.18.0.0/16',
       });
     });
+
+    it('should pass gateway, internal and labels', async () => {
+      tauriMock.invoke.mockResolvedValue(undefined);
+      await service.createNetwork('sys-1', 'my-net', 'docker', undefined, '172.18.0.0/16', {
+        gateway: '172.18.0.1',
+        internal: true,
+        labels: { env: 'test' },
+      });
+      expect(tauriMock.invoke).toHaveBeenCalledWith('create_network', {
+        systemId: 'sys-1',
+        name: 'my-net',
+        runtime: 'docker',
+        driver: undefined,
+        subnet: '172.18.0.0/16',
+        gateway: '172.18.0.1',
+        internal: true,
+        labels: { env: 'test' },
+      });
+    });
   });
 
   describe('removeNetwork', () => {
@@ -13,12 +13,17 @@ export class NetworkService {
     return this.tauri.invoke<Network[]>('list_networks', { systemId });
   }
 
+  /**
+   * Create a network. `subnet` is CIDR and `gateway` must lie inside it;
+   * Apple Container rejects everything but the name.
+   */
   createNetwork(
     systemId: string,
     name: string,
     runtime: ContainerRuntime,
     driver?: string,
-    subnet?: string
+    subnet?: string,
+    options: { gateway?: string; internal?: boolean; labels?: Record<string, string> } = {}
   ): Promise<void> {
     return this.tauri.invoke<void>('create_network', {
       systemId,
@@ -26,6 +31,9 @@ export class NetworkService {
       runtime,
       driver,
       subnet,
+      gateway: options.gateway,
+      internal: options.internal,
+      labels: options.labels,
     });
   }
 
@@ -0,0 +1,35 @@
+import { describe, it, expect, vi, beforeEach } from 'vitest';
+import { PodService } from './pod.service';
+import { TauriService } from './tauri.service';
+
+describe('PodService', () => {
+  let service: PodService;
+  let tauriMock: { invoke: ReturnType<typeof vi.fn> };
+
+  beforeEach(() => {
+    tauriMock = { invoke: vi.fn() };
+    service = new PodService(tauriMock as unknown as TauriService);
+  });
+
+  describe('listPods', () => {
+    it('should call with systemId', async () => {
+      const pods = [{ id: 'p1', name: 'webapp', status: 'running', containerCount: 2, systemId: 'sys-1' }];
+      tauriMock.invoke.mockResolvedValue(pods);
+      const result = await service.listPods('sys-1');
+      expect(tauriMock.invoke).toHaveBeenCalledWith('list_pods', { systemId: 'sys-1' });
+      expect(result).toEqual(pods);
+    });
+  });
+
+  describe('performAction', () => {
+    it('should pass pod id and action', async () => {
+      tauriMock.invoke.mockResolvedValue(undefined);
+      await service.performAction('sys-1', 'webapp', 'stop');
+      expect(tauriMock.invoke).toHaveBeenCalledWith('perform_pod_action', {
+        systemId: 'sys-1',
+        podId: 'webapp',
+        action: 'stop',
+      });
+    });
+  });
+});
@@ -0,0 +1,28 @@
+import { Injectable } from '@angular/core';
+import { ContainerAction } from '../models/container.model';
+import { Pod } from '../models/pod.model';
+import { TauriService } from './tauri.service';
+
+/** Podman pods; the backend rejects systems without the Podman runtime. */
+@Injectable({
+  providedIn: 'root',
+})
+export class PodService {
+  constructor(private tauri: TauriService) {}
+
+  listPods(systemId: string): Promise<Pod[]> {
+    return this.tauri.invoke<Pod[]>('list_pods', { systemId });
+  }
+
+  performAction(
+    systemId: string,
+    podId: string,
+    action: ContainerAction
+  ): Promise<void> {
+    return this.tauri.invoke<void>('perform_pod_action', {
+      systemId,
+      podId,
+      action,
+    });
+  }
+}
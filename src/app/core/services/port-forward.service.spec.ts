@@ -27,6 +27,20 @@ describe('PortForwardService', () => {
     expect(mockInvoke).toHaveBeenCalledWith('create_port_forward', { request });
   });
 
+  it('should create a remote forward', async () => {
+    const forward = { id: 'fwd-2', systemId: 'sys-1', remotePort: 9000, localPort: 3000, direction: 'remote' };
+    mockInvoke.mockResolvedValue(forward as any);
+
+    const result = await service.createRemoteForward('sys-1', 9000, 3000);
+    expect(result).toEqual(forward);
+    expect(mockInvoke).toHaveBeenCalledWith('create_remote_forward', {
+      systemId: 'sys-1',
+      remoteBindPort: 9000,
+      localHost: undefined,
+      localPort: 3000,
+    });
+  });
+
   it('should stop a port forward', async () => {
     mockInvoke.mockResolvedValue(undefined as any);
 
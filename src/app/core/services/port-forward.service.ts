@@ -11,6 +11,23 @@ export class PortForwardService {
     return invoke<PortForward>('create_port_forward', { request });
   }
 
+  /**
+   * Expose a local service on the remote host; remoteBindPort 0 lets the server pick
+   */
+  async createRemoteForward(
+    systemId: string,
+    remoteBindPort: number,
+    localPort: number,
+    localHost?: string
+  ): Promise<PortForward> {
+    return invoke<PortForward>('create_remote_forward', {
+      systemId,
+      remoteBindPort,
+      localHost,
+      localPort,
+    });
+  }
+
   async stopForward(forwardId: string): Promise<void> {
     return invoke('stop_port_forward', { forwardId });
   }
@@ -0,0 +1,59 @@
+import { describe, it, expect, vi, beforeEach } from 'vitest';
+import { PruneService } from './prune.service';
+import { TauriService } from './tauri.service';
+
+describe('PruneService', () => {
+  let service: PruneService;
+  let tauriMock: { invoke: ReturnType<typeof vi.fn> };
+
+  beforeEach(() => {
+    tauriMock = { invoke: vi.fn().mockResolvedValue({ deleted: [], reclaimedBytes: 0 }) };
+    service = new PruneService(tauriMock as unknown as TauriService);
+  });
+
+  describe('pruneImages', () => {
+    it('should default to dangling images without filters', async () => {
+      await service.pruneImages('sys-1');
+      expect(tauriMock.invoke).toHaveBeenCalledWith('prune_images', {
+        systemId: 'sys-1',
+        all: false,
+        filters: [],
+      });
+    });
+
+    it('should pass all and filters', async () => {
+      tauriMock.invoke.mockResolvedValue({ deleted: ['sha256:abc'], reclaimedBytes: 1024 });
+      const result = await service.pruneImages('sys-1', true, ['until=24h']);
+      expect(result.reclaimedBytes).toBe(1024);
+      expect(tauriMock.invoke).toHaveBeenCalledWith('prune_images', {
+        systemId: 'sys-1',
+        all: true,
+        filters: ['until=24h'],
+      });
+    });
+  });
+
+  describe('single resource prunes', () => {
+    it('should call the matching command', async () => {
+      await service.pruneContainers('sys-1');
+      await service.pruneVolumes('sys-1');
+      await service.pruneNetworks('sys-1');
+      expect(tauriMock.invoke.mock.calls).toEqual([
+        ['prune_containers', { systemId: 'sys-1' }],
+        ['prune_volumes', { systemId: 'sys-1' }],
+        ['prune_networks', { systemId: 'sys-1' }],
+      ]);
+    });
+  });
+
+  describe('pruneSystem', () => {
+    it('should pass all and volumes', async () => {
+      await service.pruneSystem('sys-1', true, true);
+      expect(tauriMock.invoke).toHaveBeenCalledWith('prune_system', {
+        systemId: 'sys-1',
+        all: true,
+        volumes: true,
+      });
+    });
+  });
+});
@@ -0,0 +1,32 @@
+import { Injectable } from '@angular/core';
+import { PruneResult } from '../models/prune.model';
+import { TauriService } from './tauri.service';
+
+/** Clean-up of unused images, containers, volumes and networks on every runtime of a system. */
+@Injectable({
+  providedIn: 'root',
+})
+export class PruneService {
+  constructor(private tauri: TauriService) {}
+
+  /** Remove dangling images, or all unused ones with `all`; filters use runtime syntax, e.g. `until=24h` */
+  pruneImages(systemId: string, all = false, filters: string[] = []): Promise<PruneResult> {
+    return this.tauri.invoke<PruneResult>('prune_images', { systemId, all, filters });
+  }
+
+  pruneContainers(systemId: string): Promise<PruneResult> {
+    return this.tauri.invoke<PruneResult>('prune_containers', { systemId });
+  }
+
+  pruneVolumes(systemId: string): Promise<PruneResult> {
+    return this.tauri.invoke<PruneResult>('prune_volumes', { systemId });
+  }
+
+  pruneNetworks(systemId: string): Promise<PruneResult> {
+    return this.tauri.invoke<PruneResult>('prune_networks', { systemId });
+  }
+
+  pruneSystem(systemId: string, all = false, volumes = false): Promise<PruneResult> {
+    return this.tauri.invoke<PruneResult>('prune_system', { systemId, all, volumes });
+  }
+}
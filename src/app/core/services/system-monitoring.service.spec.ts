@@ -86,6 +86,14 @@ describe('SystemMonitoringService', () => {
     expect(service.getMetrics('sys-1')).toEqual(metrics);
   });
 
+  it('should export metrics in Prometheus format', async () => {
+    const text = 'containerus_cpu_usage_percent{system="sys-1",name="web"} 42.1 1717012895000\n';
+    mockTauri.invoke.mockResolvedValue(text);
+
+    expect(await service.exportPrometheus()).toBe(text);
+    expect(mockTauri.invoke).toHaveBeenCalledWith('export_metrics_prometheus');
+  });
+
   it('should return null on fetch metrics failure', async () => {
     mockTauri.invoke.mockRejectedValue(new Error('failed'));
 
@@ -158,6 +158,13 @@ export class SystemMonitoringService {
     }
   }
 
+  /**
+   * Latest metrics of every monitored system as Prometheus text exposition.
+   */
+  exportPrometheus(): Promise<string> {
+    return this.tauri.invoke<string>('export_metrics_prometheus');
+  }
+
   /**
    * Update metrics state (called from event listener or fetchMetricsOnce).
    */
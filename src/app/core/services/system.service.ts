@@ -1,14 +1,24 @@
 import { Injectable } from '@angular/core';
+import { listen, UnlistenFn } from '@tauri-apps/api/event';
 import { open } from '@tauri-apps/plugin-dialog';
-import { ContainerRuntime } from '../models/container.model';
 import {
   AppSettings,
+  CommandHistoryEntry,
+  ConnectionChangedEvent,
   ConnectionState,
   ContainerSystem,
+  ExecContext,
+  ExportFormat,
+  PoolStats,
   ExtendedSystemInfo,
+  HostCommandResult,
   JumpHostCredentials,
   NewSystemRequest,
+  OutputChunk,
+  RuntimeDetection,
   SshHostEntry,
+  SshKeyInfo,
+  SystemConnectionResult,
   UpdateSystemRequest,
 } from '../models/system.model';
 import { TauriService } from './tauri.service';
@@ -19,14 +29,29 @@ import { TauriService } from './tauri.service';
 export class SystemService {
   constructor(private tauri: TauriService) {}
 
-  listSystems(): Promise<ContainerSystem[]> {
-    return this.tauri.invoke<ContainerSystem[]>('list_systems');
+  /** List systems, optionally only those carrying every one of `tags` (case-insensitive) */
+  listSystems(tags?: string[]): Promise<ContainerSystem[]> {
+    return this.tauri.invoke<ContainerSystem[]>('list_systems', { tags });
+  }
+
+  listSystemsByTag(tag: string): Promise<ContainerSystem[]> {
+    return this.tauri.invoke<ContainerSystem[]>('list_systems_by_tag', { tag });
   }
 
   addSystem(payload: NewSystemRequest): Promise<ContainerSystem> {
     return this.tauri.invoke<ContainerSystem>('add_system', { payload });
   }
 
+  /** Export all systems without credentials, e.g. to save as a file */
+  exportSystems(format: ExportFormat): Promise<string> {
+    return this.tauri.invoke<string>('export_systems', { format });
+  }
+
+  /** Recreate the systems of an export with new ids; credentials are asked for on connect */
+  importSystems(data: string, format: ExportFormat): Promise<ContainerSystem[]> {
+    return this.tauri.invoke<ContainerSystem[]>('import_systems', { data, format });
+  }
+
   updateSystem(payload: UpdateSystemRequest): Promise<ContainerSystem> {
     return this.tauri.invoke<ContainerSystem>('update_system', { payload });
   }
@@ -45,14 +70,138 @@ export class SystemService {
     });
   }
 
+  /** Connect several systems with their stored credentials; failures are reported per system */
+  connectSystems(systemIds: string[]): Promise<Record<string, SystemConnectionResult>> {
+    return this.tauri.invoke<Record<string, SystemConnectionResult>>('connect_systems', { systemIds });
+  }
+
+  disconnectSystems(systemIds: string[]): Promise<Record<string, SystemConnectionResult>> {
+    return this.tauri.invoke<Record<string, SystemConnectionResult>>('disconnect_systems', { systemIds });
+  }
+
   getConnectionState(systemId: string): Promise<ConnectionState> {
     return this.tauri.invoke<ConnectionState>('get_connection_state', {
       systemId,
     });
   }
 
-  detectRuntimes(systemId: string): Promise<ContainerRuntime[]> {
-    return this.tauri.invoke<ContainerRuntime[]>('detect_runtimes', {
+  getPoolStats(): Promise<PoolStats> {
+    return this.tauri.invoke<PoolStats>('pool_stats');
+  }
+
+  setPoolMaxConnections(maxConnections: number): Promise<void> {
+    return this.tauri.invoke<void>('set_pool_max_connections', { maxConnections });
+  }
+
+  /**
+   * Listen for systems the backend reconnected after their SSH session dropped
+   */
+  onReconnected(handler: (systemId: string) => void): Promise<UnlistenFn> {
+    return listen<{ systemId: string }>('system:reconnected', (event) => {
+      handler(event.payload.systemId);
+    });
+  }
+
+  /**
+   * Listen for the system list after the backend refreshed its cached runtimes
+   */
+  onSystemsRefreshed(handler: (systems: ContainerSystem[]) => void): Promise<UnlistenFn> {
+    return listen<ContainerSystem[]>('systems:refreshed', (event) => {
+      handler(event.payload);
+    });
+  }
+
+  /**
+   * Periodically validate connected systems in the backend (default every 30s).
+   * Calling it again restarts polling with the new interval.
+   */
+  startHealthPolling(intervalMs?: number): Promise<void> {
+    return this.tauri.invoke<void>('start_health_polling', { intervalMs });
+  }
+
+  stopHealthPolling(): Promise<boolean> {
+    return this.tauri.invoke<boolean>('stop_health_polling');
+  }
+
+  /**
+   * Listen for connection state changes (connect, disconnect, health checks, reconnects)
+   */
+  onConnectionChanged(handler: (event: ConnectionChangedEvent) => void): Promise<UnlistenFn> {
+    return listen<ConnectionChangedEvent>('system:connection-changed', (event) => {
+      handler(event.payload);
+    });
+  }
+
+  /**
+   * Run a command on a system, calling `onChunk` with its output as it arrives.
+   * Resolves with the exit code once the command finishes; pass your own
+   * `executionId` to be able to stop it with cancelExecution.
+   */
+  async executeStreaming(
+    systemId: string,
+    command: string,
+    onChunk: (chunk: OutputChunk) => void,
+    context?: ExecContext,
+    executionId: string = crypto.randomUUID()
+  ): Promise<number> {
+    const unlisten = await listen<{ executionId: string; chunk: OutputChunk }>('exec:output', (event) => {
+      if (event.payload.executionId === executionId) {
+        onChunk(event.payload.chunk);
+      }
+    });
+
+    try {
+      return await this.tauri.invoke<number>('execute_streaming', {
+        systemId,
+        command,
+        executionId,
+        context,
+      });
+    } finally {
+      unlisten();
+    }
+  }
+
+  cancelExecution(executionId: string): Promise<void> {
+    return this.tauri.invoke<void>('cancel_execution', { executionId });
+  }
+
+  /**
+   * Run the same command on every system at once (or only connected ones),
+   * keyed by system ID. Each system gets at most `timeoutSecs` (default 30).
+   */
+  executeOnAllSystems(
+    command: string,
+    onlyConnected: boolean = true,
+    timeoutSecs?: number
+  ): Promise<Record<string, HostCommandResult>> {
+    return this.tauri.invoke<Record<string, HostCommandResult>>('execute_on_all_systems', {
+      command,
+      onlyConnected,
+      timeoutSecs,
+    });
+  }
+
+  /**
+   * Get recently run commands, newest first. Omit `systemId` for all systems.
+   */
+  getCommandHistory(systemId?: string, limit?: number, search?: string): Promise<CommandHistoryEntry[]> {
+    return this.tauri.invoke<CommandHistoryEntry[]>('get_command_history', {
+      systemId: systemId ?? null,
+      limit: limit ?? null,
+      search: search?.trim() || null,
+    });
+  }
+
+  /**
+   * Clear command history for a system, or all systems; resolves with the number of entries removed
+   */
+  clearCommandHistory(systemId?: string): Promise<number> {
+    return this.tauri.invoke<number>('clear_command_history', { systemId: systemId ?? null });
+  }
+
+  detectRuntimes(systemId: string): Promise<RuntimeDetection> {
+    return this.tauri.invoke<RuntimeDetection>('detect_runtimes', {
       systemId,
     });
   }
@@ -81,6 +230,29 @@ export class SystemService {
     return this.tauri.invoke<string>('import_ssh_key_from_file', { filePath });
   }
 
+  /**
+   * Validate a pasted PEM private key and store it for a system.
+   * Resolves with the key's fingerprint (SHA256:...); rejects on a wrong
+   * passphrase or an unsupported key type.
+   */
+  importSshKeyFromContent(systemId: string, pem: string, passphrase?: string): Promise<string> {
+    return this.tauri.invoke<string>('import_ssh_key_from_content', { systemId, pem, passphrase });
+  }
+
+  /**
+   * Type and fingerprint of the key a system authenticates with
+   */
+  getSshKeyFingerprint(systemId: string): Promise<SshKeyInfo> {
+    return this.tauri.invoke<SshKeyInfo>('get_ssh_key_fingerprint', { systemId });
+  }
+
+  /**
+   * Identity keys in ~/.ssh that have both a private and a public file
+   */
+  listAvailableIdentityKeys(): Promise<SshKeyInfo[]> {
+    return this.tauri.invoke<SshKeyInfo[]>('list_available_identity_keys');
+  }
+
   /**
    * Browse for SSH key file and import its content
    * Returns the PEM content of the key, or null if cancelled
@@ -106,9 +278,16 @@ export class SystemService {
     return selected;
   }
 
-  getExtendedSystemInfo(systemId: string): Promise<ExtendedSystemInfo> {
+  /**
+   * Get extended system info. Disk usage covers `mountPoint` (defaults to `/`).
+   */
+  getExtendedSystemInfo(
+    systemId: string,
+    mountPoint?: string
+  ): Promise<ExtendedSystemInfo> {
     return this.tauri.invoke<ExtendedSystemInfo>('get_extended_system_info', {
       systemId,
+      mountPoint,
     });
   }
 
@@ -119,6 +298,14 @@ export class SystemService {
     return this.tauri.invoke<number>('remove_known_host', { hostname, port });
   }
 
+  /**
+   * Add the host key a connection stopped at to ~/.ssh/known_hosts.
+   * Only the key the server presented with this fingerprint is accepted.
+   */
+  trustHostKey(hostname: string, fingerprint: string): Promise<void> {
+    return this.tauri.invoke<void>('trust_host_key', { hostname, fingerprint });
+  }
+
   // ========================================================================
   // SSH Config Methods (for importing hosts from ~/.ssh/config)
   // ========================================================================
@@ -162,4 +349,22 @@ export class SystemService {
   updateAppSettings(settings: AppSettings): Promise<void> {
     return this.tauri.invoke<void>('update_app_settings', { settings });
   }
+
+  // ========================================================================
+  // Database Backup Methods
+  // ========================================================================
+
+  /**
+   * Write a snapshot of the database to a file (safe while the app is running)
+   */
+  backupDatabase(destPath: string): Promise<void> {
+    return this.tauri.invoke<void>('backup_database', { destPath });
+  }
+
+  /**
+   * Replace the database with a backup; rejects files that aren't compatible Containerus databases
+   */
+  restoreDatabase(srcPath: string): Promise<void> {
+    return this.tauri.invoke<void>('restore_database', { srcPath });
+  }
 }
@@ -1,4 +1,5 @@
 import { describe, it, expect, vi, beforeEach } from 'vitest';
+import { listen } from '@tauri-apps/api/event';
 import { TerminalService, TerminalSession } from './terminal.service';
 
 // Mock @tauri-apps/api/event
@@ -35,6 +36,8 @@ describe('TerminalService', () => {
       systemId: 'sys-1',
       containerId: undefined,
       shell: '/bin/bash',
+      cols: undefined,
+      rows: undefined,
     });
     expect(service.getSession('sess-1')).toEqual(session);
   });
@@ -53,8 +56,43 @@ describe('TerminalService', () => {
     expect(mockTauri.invoke).toHaveBeenCalledWith('start_terminal_session', {
       systemId: 'sys-1',
       containerId: 'container-1',
-      shell: '/bin/sh',
+      shell: undefined,
+      cols: undefined,
+      rows: undefined,
+    });
+  });
+
+  it('should open a session at the terminal size', async () => {
+    mockTauri.invoke.mockResolvedValue({ id: 'sess-4', systemId: 'sys-1', shell: '/bin/sh' });
+
+    await service.startSession('sys-1', undefined, undefined, { cols: 132, rows: 43 });
+    expect(mockTauri.invoke).toHaveBeenCalledWith('start_terminal_session', {
+      systemId: 'sys-1',
+      containerId: undefined,
+      shell: undefined,
+      cols: 132,
+      rows: 43,
+    });
+  });
+
+  it('should start a container terminal with a detected shell', async () => {
+    const session: TerminalSession = {
+      id: 'sess-3',
+      systemId: 'sys-1',
+      containerId: 'container-1',
+      shell: '/bin/ash',
+    };
+    mockTauri.invoke.mockResolvedValue(session);
+
+    const result = await service.startContainerSession('sys-1', 'container-1');
+    expect(result).toEqual(session);
+    expect(mockTauri.invoke).toHaveBeenCalledWith('start_container_terminal', {
+      systemId: 'sys-1',
+      containerId: 'container-1',
+      cols: undefined,
+      rows: undefined,
     });
+    expect(service.getSession('sess-3')).toEqual(session);
   });
 
   it('should send input to a session', async () => {
@@ -78,6 +116,58 @@ describe('TerminalService', () => {
     });
   });
 
+  it('should start and stop a recording', async () => {
+    mockTauri.invoke.mockResolvedValue('/data/recordings/sess-1.cast');
+
+    const started = await service.startRecording('sess-1', 120, 40);
+    expect(started).toBe('/data/recordings/sess-1.cast');
+    expect(mockTauri.invoke).toHaveBeenCalledWith('start_terminal_recording', {
+      sessionId: 'sess-1',
+      cols: 120,
+      rows: 40,
+      recordInput: false,
+    });
+
+    const stopped = await service.stopRecording('sess-1');
+    expect(stopped).toBe('/data/recordings/sess-1.cast');
+    expect(mockTauri.invoke).toHaveBeenCalledWith('stop_terminal_recording', {
+      sessionId: 'sess-1',
+    });
+  });
+
+  it('should export a recording', async () => {
+    mockTauri.invoke.mockResolvedValue(undefined);
+
+    await service.exportRecording('sess-1', '/home/me/incident.cast');
+    expect(mockTauri.invoke).toHaveBeenCalledWith('export_terminal_recording', {
+      sessionId: 'sess-1',
+      path: '/home/me/incident.cast',
+    });
+  });
+
+  it('should get scrollback', async () => {
+    mockTauri.invoke.mockResolvedValue('line 1\nline 2');
+
+    const text = await service.getScrollback('sess-1', 200, true);
+    expect(text).toBe('line 1\nline 2');
+    expect(mockTauri.invoke).toHaveBeenCalledWith('get_terminal_scrollback', {
+      sessionId: 'sess-1',
+      maxLines: 200,
+      stripAnsi: true,
+    });
+  });
+
+  it('should export scrollback keeping escapes by default', async () => {
+    mockTauri.invoke.mockResolvedValue(undefined);
+
+    await service.exportScrollback('sess-1', '/home/me/session.log');
+    expect(mockTauri.invoke).toHaveBeenCalledWith('export_terminal_scrollback', {
+      sessionId: 'sess-1',
+      path: '/home/me/session.log',
+      stripAnsi: false,
+    });
+  });
+
   it('should close a session and clean up', async () => {
     const session: TerminalSession = {
       id: 'sess-1',
@@ -96,6 +186,30 @@ describe('TerminalService', () => {
     expect(service.getSession('sess-1')).toBeUndefined();
   });
 
+  it('should forget a session the backend closed for being idle', async () => {
+    mockTauri.invoke.mockResolvedValue({ id: 'sess-1', systemId: 'sys-1', shell: '/bin/sh' });
+    await service.startSession('sys-1');
+
+    const callback = vi.fn();
+    await service.onSessionClosed(callback);
+    const handler = vi.mocked(listen).mock.calls.at(-1)![1] as Function;
+    handler({ payload: { sessionId: 'sess-1', reason: 'idle' } });
+
+    expect(listen).toHaveBeenCalledWith('terminal:closed', expect.any(Function));
+    expect(callback).toHaveBeenCalledWith({ sessionId: 'sess-1', reason: 'idle' });
+    expect(service.getSession('sess-1')).toBeUndefined();
+  });
+
+  it('should get session stats', async () => {
+    const stats = [
+      { sessionId: 's1', systemId: 'sys-1', containerId: null, idleSecs: 120, agentAttached: false },
+    ];
+    mockTauri.invoke.mockResolvedValue(stats);
+
+    expect(await service.getSessionStats()).toEqual(stats);
+    expect(mockTauri.invoke).toHaveBeenCalledWith('terminal_session_stats');
+  });
+
   it('should return undefined for non-existent session', () => {
     expect(service.getSession('nonexistent')).toBeUndefined();
   });
@@ -114,7 +228,10 @@ describe('TerminalService', () => {
   });
 
   it('should fetch shell history', async () => {
-    const history = ['ls', 'cd /tmp', 'docker ps'];
+    const history = [
+      { command: 'docker ps', timestamp: 1700000000 },
+      { command: 'docker images', timestamp: null },
+    ];
     mockTauri.invoke.mockResolvedValue(history);
 
     const result = await service.fetchShellHistory('sys-1', 100, 'docker');
@@ -136,4 +253,15 @@ describe('TerminalService', () => {
       filter: undefined,
     });
   });
+
+  it('should detect shells in a container', async () => {
+    mockTauri.invoke.mockResolvedValue(['/bin/ash', '/bin/sh']);
+
+    const result = await service.detectShells('sys-1', 'container-1');
+    expect(result).toEqual(['/bin/ash', '/bin/sh']);
+    expect(mockTauri.invoke).toHaveBeenCalledWith('detect_shells', {
+      systemId: 'sys-1',
+      containerId: 'container-1',
+    });
+  });
 });
@@ -9,6 +9,34 @@ export interface TerminalSession {
   shell: string;
 }
 
+/** An open terminal session and how long it has been idle */
+export interface TerminalSessionStats {
+  sessionId: string;
+  systemId: string | null;
+  containerId: string | null;
+  idleSecs: number;
+  /** An agent runs through this terminal, so it is never closed for being idle */
+  agentAttached: boolean;
+}
+
+/** Payload of `terminal:closed`, emitted when the backend closes a session itself */
+export interface TerminalClosedEvent {
+  sessionId: string;
+  reason: 'idle';
+}
+
+/** Terminal dimensions in character cells */
+export interface TerminalSize {
+  cols: number;
+  rows: number;
+}
+
+export interface ShellHistoryEntry {
+  command: string;
+  /** Unix timestamp in seconds, when the shell's history format records one */
+  timestamp: number | null;
+}
+
 @Injectable({
   providedIn: 'root',
 })
@@ -21,10 +49,17 @@ export class TerminalService {
     private zone: NgZone
   ) {}
 
+  /**
+   * Start a terminal on a system or in a container. Without a shell, containers
+   * get their preferred installed shell; a requested shell that the container
+   * lacks is replaced the same way. Passing the terminal's size opens the PTY
+   * at that size, avoiding a reflow from the 80x24 default.
+   */
   async startSession(
     systemId: string,
     containerId?: string,
-    shell: string = '/bin/sh'
+    shell?: string,
+    size?: TerminalSize
   ): Promise<TerminalSession> {
     const session = await this.tauri.invoke<TerminalSession>(
       'start_terminal_session',
@@ -32,6 +67,8 @@ export class TerminalService {
         systemId,
         containerId,
         shell,
+        cols: size?.cols,
+        rows: size?.rows,
       }
     );
 
@@ -39,6 +76,26 @@ export class TerminalService {
     return session;
   }
 
+  /**
+   * Start a terminal in a container with its preferred installed shell. Fails
+   * with a clear error when the container has no shell at all.
+   */
+  async startContainerSession(
+    systemId: string,
+    containerId: string,
+    size?: TerminalSize
+  ): Promise<TerminalSession> {
+    const session = await this.tauri.invoke<TerminalSession>('start_container_terminal', {
+      systemId,
+      containerId,
+      cols: size?.cols,
+      rows: size?.rows,
+    });
+
+    this.sessions.set(session.id, session);
+    return session;
+  }
+
   async sendInput(sessionId: string, data: string): Promise<void> {
     return this.tauri.invoke<void>('send_terminal_input', {
       sessionId,
@@ -66,6 +123,61 @@ export class TerminalService {
     this.sessions.delete(sessionId);
   }
 
+  /**
+   * Start recording a session as an asciicast v2 file; resolves with its path
+   */
+  async startRecording(
+    sessionId: string,
+    cols?: number,
+    rows?: number,
+    recordInput = false
+  ): Promise<string> {
+    return this.tauri.invoke<string>('start_terminal_recording', {
+      sessionId,
+      cols,
+      rows,
+      recordInput,
+    });
+  }
+
+  async stopRecording(sessionId: string): Promise<string> {
+    return this.tauri.invoke<string>('stop_terminal_recording', { sessionId });
+  }
+
+  async exportRecording(sessionId: string, path: string): Promise<void> {
+    return this.tauri.invoke<void>('export_terminal_recording', {
+      sessionId,
+      path,
+    });
+  }
+
+  /**
+   * Recent output of a session (all kept lines when maxLines is omitted)
+   */
+  async getScrollback(
+    sessionId: string,
+    maxLines?: number,
+    stripAnsi = false
+  ): Promise<string> {
+    return this.tauri.invoke<string>('get_terminal_scrollback', {
+      sessionId,
+      maxLines,
+      stripAnsi,
+    });
+  }
+
+  async exportScrollback(
+    sessionId: string,
+    path: string,
+    stripAnsi = false
+  ): Promise<void> {
+    return this.tauri.invoke<void>('export_terminal_scrollback', {
+      sessionId,
+      path,
+      stripAnsi,
+    });
+  }
+
   async onOutput(
     sessionId: string,
     callback: (data: string) => void
@@ -82,6 +194,28 @@ export class TerminalService {
     this.listeners.set(sessionId, unlisten);
   }
 
+  /**
+   * Listen for sessions the backend closed on its own, such as idle ones
+   */
+  async onSessionClosed(
+    callback: (event: TerminalClosedEvent) => void
+  ): Promise<UnlistenFn> {
+    return listen<TerminalClosedEvent>('terminal:closed', (event) => {
+      const { sessionId } = event.payload;
+      this.listeners.get(sessionId)?.();
+      this.listeners.delete(sessionId);
+      this.sessions.delete(sessionId);
+      this.zone.run(() => callback(event.payload));
+    });
+  }
+
+  /**
+   * Open sessions with their idle time, longest idle first
+   */
+  async getSessionStats(): Promise<TerminalSessionStats[]> {
+    return this.tauri.invoke<TerminalSessionStats[]>('terminal_session_stats');
+  }
+
   getSession(sessionId: string): TerminalSession | undefined {
     return this.sessions.get(sessionId);
   }
@@ -90,12 +224,23 @@ export class TerminalService {
     return Array.from(this.sessions.values());
   }
 
+  /**
+   * Shells installed on a system, or inside a container, preferred first
+   * (bash, zsh, fish, ash, sh). Cached per system for the app session.
+   */
+  async detectShells(systemId: string, containerId?: string): Promise<string[]> {
+    return this.tauri.invoke<string[]>('detect_shells', { systemId, containerId });
+  }
+
+  /**
+   * Fetch the remote user's bash, zsh or fish history, newest first.
+   */
   async fetchShellHistory(
     systemId: string,
     maxEntries = 500,
     filter?: string
-  ): Promise<string[]> {
-    return this.tauri.invoke<string[]>('fetch_shell_history', {
+  ): Promise<ShellHistoryEntry[]> {
+    return this.tauri.invoke<ShellHistoryEntry[]>('fetch_shell_history', {
       systemId,
       maxEntries,
       filter,
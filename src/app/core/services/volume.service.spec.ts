@@ -41,6 +41,22 @@ describe('VolumeService', () => {
         driver: 'local',
       });
     });
+
+    it('should pass driver options and labels', async () => {
+      tauriMock.invoke.mockResolvedValue(undefined);
+      await service.createVolume('sys-1', 'share', 'docker', 'local', {
+        driverOpts: { type: 'nfs', o: 'addr=10.0.0.5,rw' },
+        labels: { team: 'data' },
+      });
+      expect(tauriMock.invoke).toHaveBeenCalledWith('create_volume', {
+        systemId: 'sys-1',
+        name: 'share',
+        runtime: 'docker',
+        driver: 'local',
+        driverOpts: { type: 'nfs', o: 'addr=10.0.0.5,rw' },
+        labels: { team: 'data' },
+      });
+    });
   });
 
   describe('removeVolume', () => {
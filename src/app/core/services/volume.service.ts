@@ -13,17 +13,21 @@ export class VolumeService {
     return this.tauri.invoke<Volume[]>('list_volumes', { systemId });
   }
 
+  /** Create a volume; Apple Container rejects drivers, driver options and labels */
   createVolume(
     systemId: string,
     name: string,
     runtime: ContainerRuntime,
-    driver?: string
+    driver?: string,
+    options: { driverOpts?: Record<string, string>; labels?: Record<string, string> } = {}
   ): Promise<void> {
     return this.tauri.invoke<void>('create_volume', {
       systemId,
       name,
       runtime,
       driver,
+      driverOpts: options.driverOpts,
+      labels: options.labels,
     });
   }
 
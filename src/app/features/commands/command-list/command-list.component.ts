@@ -24,6 +24,8 @@ import {
   HardDrive,
   Settings,
   Boxes,
+  Download,
+  Upload,
 } from 'lucide-angular';
 import {
   CommandTemplate,
@@ -88,6 +90,8 @@ export class CommandListComponent implements OnInit {
   readonly HardDrive = HardDrive;
   readonly Settings = Settings;
   readonly Boxes = Boxes;
+  readonly Download = Download;
+  readonly Upload = Upload;
 
   // Utility functions
   readonly getCategoryLabel = getCategoryLabel;
@@ -99,6 +103,7 @@ export class CommandListComponent implements OnInit {
   readonly showCreateModal = signal(false);
   readonly editingTemplate = signal<CommandTemplate | null>(null);
   readonly collapsedCategories = signal<Set<CommandCategory>>(new Set());
+  readonly importMessage = signal<string | null>(null);
   refreshing = false;
 
   // Category icon mapping
@@ -182,6 +187,43 @@ export class CommandListComponent implements OnInit {
     await this.refresh();
   }
 
+  async exportTemplates(): Promise<void> {
+    const json = await this.commandState.exportTemplates();
+    if (!json) return;
+
+    const blob = new Blob([json], { type: 'application/json' });
+    const url = URL.createObjectURL(blob);
+
+    const a = document.createElement('a');
+    a.href = url;
+    a.download = `containerus-commands-${new Date().toISOString().slice(0, 10)}.json`;
+    a.click();
+
+    URL.revokeObjectURL(url);
+  }
+
+  async importTemplates(event: Event): Promise<void> {
+    const input = event.target as HTMLInputElement;
+    const file = input.files?.[0];
+    input.value = '';
+    if (!file) return;
+
+    const overwrite = confirm(
+      'Overwrite existing custom commands that have the same name? Choose Cancel to skip them.'
+    );
+    const result = await this.commandState.importTemplates(await file.text(), overwrite);
+    if (!result) {
+      this.importMessage.set(this.commandState.error() ?? 'Import failed');
+      return;
+    }
+
+    let message = `Imported ${result.imported}, overwrote ${result.overwritten}`;
+    if (result.skipped.length > 0) {
+      message += `, skipped ${result.skipped.length} existing: ${result.skipped.join(', ')}`;
+    }
+    this.importMessage.set(message);
+  }
+
   async refresh(): Promise<void> {
     this.refreshing = true;
     try {
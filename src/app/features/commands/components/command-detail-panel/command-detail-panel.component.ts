@@ -21,6 +21,7 @@ import {
   getCategoryLabel,
   getCategoryIcon,
   getRuntimeLabel,
+  getTemplateCommand,
   parseVariables,
 } from '../../../../core/models/command-template.model';
 import { ContainerRuntime } from '../../../../core/models/container.model';
@@ -75,11 +76,22 @@ import { ContainerRuntime } from '../../../../core/models/container.model';
         <div class="space-y-2">
           <h3 class="text-sm font-medium text-zinc-300 flex items-center gap-2">
             <lucide-icon [img]="Play" class="w-4 h-4 text-zinc-500"></lucide-icon>
-            Command
+            {{ template().steps?.length ? 'Steps' : 'Command' }}
           </h3>
-          <div class="bg-zinc-800 rounded-lg p-3">
-            <code class="text-sm text-green-400 font-mono whitespace-pre-wrap break-all">{{ template().command }}</code>
-          </div>
+          @if (template().steps?.length) {
+            <ol class="space-y-2">
+              @for (step of template().steps; track $index) {
+                <li class="bg-zinc-800 rounded-lg p-3 space-y-1">
+                  <p class="text-xs text-zinc-400">{{ $index + 1 }}. {{ step.description }}</p>
+                  <code class="block text-sm text-green-400 font-mono whitespace-pre-wrap break-all">{{ step.command }}</code>
+                </li>
+              }
+            </ol>
+          } @else {
+            <div class="bg-zinc-800 rounded-lg p-3">
+              <code class="text-sm text-green-400 font-mono whitespace-pre-wrap break-all">{{ template().command }}</code>
+            </div>
+          }
         </div>
 
         <!-- Variables Section -->
@@ -232,7 +244,7 @@ export class CommandDetailPanelComponent {
   readonly getRuntimeLabel = getRuntimeLabel;
 
   get detectedVariables(): string[] {
-    return parseVariables(this.template().command);
+    return parseVariables(getTemplateCommand(this.template()));
   }
 
   getRuntimeIcon(runtime: ContainerRuntime): typeof Ship {
@@ -11,7 +11,9 @@ import {
 import {
   CommandTemplate,
   CommandCategory,
+  TemplateStep,
   TemplateVariable,
+  VarType,
   CreateCommandTemplateRequest,
   UpdateCommandTemplateRequest,
   parseVariables,
@@ -24,9 +26,12 @@ interface FormData {
   name: string;
   description: string;
   command: string;
+  /** When set, the template runs `steps` in order instead of `command` */
+  composite: boolean;
+  steps: TemplateStep[];
   category: CommandCategory;
   tags: string;
-  runtimes: { docker: boolean; podman: boolean; apple: boolean };
+  runtimes: { docker: boolean; podman: boolean; apple: boolean; nerdctl: boolean };
   isFavorite: boolean;
   variables: TemplateVariable[];
 }
@@ -59,9 +64,11 @@ export class CommandFormModalComponent implements OnInit {
     name: '',
     description: '',
     command: '',
+    composite: false,
+    steps: [],
     category: 'custom',
     tags: '',
-    runtimes: { docker: false, podman: false, apple: false },
+    runtimes: { docker: false, podman: false, apple: false, nerdctl: false },
     isFavorite: false,
     variables: [],
   };
@@ -77,12 +84,15 @@ export class CommandFormModalComponent implements OnInit {
         name: t.name,
         description: t.description,
         command: t.command,
+        composite: !!t.steps?.length,
+        steps: (t.steps ?? []).map((s) => ({ ...s })),
         category: t.category,
         tags: t.tags.join(', '),
         runtimes: {
           docker: t.compatibility.runtimes.includes('docker'),
           podman: t.compatibility.runtimes.includes('podman'),
           apple: t.compatibility.runtimes.includes('apple'),
+          nerdctl: t.compatibility.runtimes.includes('nerdctl'),
         },
         isFavorite: t.isFavorite,
         variables: [...t.variables],
@@ -91,8 +101,31 @@ export class CommandFormModalComponent implements OnInit {
     }
   }
 
+  private commandText(): string {
+    return this.form.composite
+      ? this.form.steps.map((s) => s.command).join(' && ')
+      : this.form.command;
+  }
+
+  setComposite(composite: boolean): void {
+    this.form.composite = composite;
+    if (composite && this.form.steps.length === 0) {
+      this.form.steps = [{ description: '', command: this.form.command }];
+    }
+    this.detectVariables();
+  }
+
+  addStep(): void {
+    this.form.steps = [...this.form.steps, { description: '', command: '' }];
+  }
+
+  removeStep(index: number): void {
+    this.form.steps = this.form.steps.filter((_, i) => i !== index);
+    this.detectVariables();
+  }
+
   detectVariables(): void {
-    const vars = parseVariables(this.form.command);
+    const vars = parseVariables(this.commandText());
     this.detectedVariables.set(vars);
 
     // Sync variables array
@@ -108,11 +141,38 @@ export class CommandFormModalComponent implements OnInit {
     this.form.variables = newVariables;
   }
 
+  setVarKind(index: number, kind: VarType['kind']): void {
+    const variable = this.form.variables[index];
+    if (kind === 'string') {
+      variable.varType = undefined;
+    } else if (kind === 'enum') {
+      variable.varType = { kind, options: [] };
+    } else {
+      variable.varType = { kind };
+    }
+  }
+
+  getEnumOptions(index: number): string {
+    const varType = this.form.variables[index].varType;
+    return varType?.kind === 'enum' ? varType.options.join(', ') : '';
+  }
+
+  setEnumOptions(index: number, text: string): void {
+    const options = text
+      .split(',')
+      .map((o) => o.trim())
+      .filter((o) => o.length > 0);
+    this.form.variables[index].varType = { kind: 'enum', options };
+  }
+
   isValid(): boolean {
+    const hasCommand = this.form.composite
+      ? this.form.steps.length > 0 && this.form.steps.every((s) => s.command.trim().length > 0)
+      : this.form.command.trim().length > 0;
     return (
       this.form.name.trim().length > 0 &&
       this.form.description.trim().length > 0 &&
-      this.form.command.trim().length > 0
+      hasCommand
     );
   }
 
@@ -123,12 +183,17 @@ export class CommandFormModalComponent implements OnInit {
     if (this.form.runtimes.docker) runtimes.push('docker');
     if (this.form.runtimes.podman) runtimes.push('podman');
     if (this.form.runtimes.apple) runtimes.push('apple');
+    if (this.form.runtimes.nerdctl) runtimes.push('nerdctl');
 
     const tags = this.form.tags
       .split(',')
       .map((t) => t.trim())
       .filter((t) => t.length > 0);
 
+    const steps = this.form.composite
+      ? this.form.steps.map((s) => ({ description: s.description.trim(), command: s.command.trim() }))
+      : [];
+
     const t = this.template();
     if (t) {
       // Update existing
@@ -136,7 +201,8 @@ export class CommandFormModalComponent implements OnInit {
         id: t.id,
         name: this.form.name.trim(),
         description: this.form.description.trim(),
-        command: this.form.command.trim(),
+        command: this.commandText().trim(),
+        steps,
         category: this.form.category,
         tags,
         variables: this.form.variables,
@@ -149,7 +215,8 @@ export class CommandFormModalComponent implements OnInit {
       const request: CreateCommandTemplateRequest = {
         name: this.form.name.trim(),
         description: this.form.description.trim(),
-        command: this.form.command.trim(),
+        command: this.commandText().trim(),
+        steps: steps.length > 0 ? steps : undefined,
         category: this.form.category,
         tags,
         variables: this.form.variables,
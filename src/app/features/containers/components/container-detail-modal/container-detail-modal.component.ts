@@ -61,7 +61,7 @@ export class ContainerDetailModalComponent {
     const system = this.systemState.systems().find(s => s.id === c.systemId);
     if (!system) return;
     try {
-      const session = await this.terminalService.startSession(c.systemId, c.id);
+      const session = await this.terminalService.startContainerSession(c.systemId, c.id);
       this.terminalState.addTerminal({
         id: this.terminalState.generateTerminalId(),
         session,
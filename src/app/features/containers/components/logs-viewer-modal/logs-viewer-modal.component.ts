@@ -28,6 +28,7 @@ import {
 import { Container, getDisplayName } from '../../../../core/models/container.model';
 import { ContainerService } from '../../../../core/services/container.service';
 import { ClipboardService } from '../../../../core/services/clipboard.service';
+import { errorMessage } from '../../../../core/models/error.model';
 
 @Component({
   selector: 'app-logs-viewer-modal',
@@ -134,7 +135,7 @@ export class LogsViewerModalComponent {
       // Auto-scroll to bottom after logs load
       setTimeout(() => this.scrollToBottom(), 50);
     } catch (err) {
-      this.error.set(err instanceof Error ? err.message : 'Unknown error');
+      this.error.set(errorMessage(err, 'Unknown error'));
       this.logs.set('');
     } finally {
       this.isLoading.set(false);
@@ -1,8 +1,12 @@
 import { Component, inject, signal, effect, output, ChangeDetectionStrategy } from '@angular/core';
 import { CommonModule } from '@angular/common';
 import { FormsModule } from '@angular/forms';
-import { LucideAngularModule, X, Save, FileText, AlertTriangle } from 'lucide-angular';
+import {
+  LucideAngularModule, X, Save, FileText, AlertTriangle,
+  ChevronsLeft, ChevronLeft, ChevronRight, ChevronsRight,
+} from 'lucide-angular';
 import { FileBrowserState } from '../../../../state/file-browser.state';
+import { FILE_PAGE_SIZE, formatFileSize } from '../../../../core/models/file-browser.model';
 
 @Component({
   selector: 'app-file-editor-modal',
@@ -19,6 +23,11 @@ export class FileEditorModalComponent {
   readonly Save = Save;
   readonly FileText = FileText;
   readonly AlertTriangle = AlertTriangle;
+  readonly ChevronsLeft = ChevronsLeft;
+  readonly ChevronLeft = ChevronLeft;
+  readonly ChevronRight = ChevronRight;
+  readonly ChevronsRight = ChevronsRight;
+  readonly formatFileSize = formatFileSize;
 
   editableContent = signal('');
 
@@ -52,8 +61,30 @@ export class FileEditorModalComponent {
     }
   }
 
+  getFilePath(): string {
+    return this.state.editorContent()?.path ?? this.state.fileChunk()?.path ?? '';
+  }
+
   getFileName(): string {
-    const path = this.state.editorContent()?.path ?? '';
+    const path = this.getFilePath();
     return path.split('/').pop() ?? path;
   }
+
+  /** Move through a large file a page at a time */
+  goToPage(page: 'first' | 'prev' | 'next' | 'last'): void {
+    const chunk = this.state.fileChunk();
+    if (!chunk) return;
+    const offsets = {
+      first: 0,
+      prev: chunk.offset - FILE_PAGE_SIZE,
+      next: chunk.offset + chunk.bytesRead,
+      last: chunk.fileSize - FILE_PAGE_SIZE,
+    };
+    this.state.loadFileChunk(chunk.path, Math.max(0, offsets[page]));
+  }
+
+  isLastPage(): boolean {
+    const chunk = this.state.fileChunk();
+    return !chunk || chunk.offset + chunk.bytesRead >= chunk.fileSize;
+  }
 }
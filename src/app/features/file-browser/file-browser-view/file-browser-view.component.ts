@@ -9,11 +9,12 @@ import {
   Folder, File, FileText, FileCode, FileImage, FileArchive,
   Link, Settings, Trash2, Pencil, Download, MoreVertical,
   ChevronRight, ChevronDown, X, FolderOpen, PanelBottomOpen, Box, Globe,
+  HardDrive, Loader2, FileSearch, KeyRound,
 } from 'lucide-angular';
 import { FileBrowserState } from '../../../state/file-browser.state';
 import { SystemState } from '../../../state/system.state';
 import { ContainerState } from '../../../state/container.state';
-import { FileEntry, formatFileSize, isTextFile } from '../../../core/models/file-browser.model';
+import { ArchiveFormat, FileEntry, SearchHit, archiveBaseName, formatFileSize, isTextFile, permissionsToMode } from '../../../core/models/file-browser.model';
 import { Container, ContainerRuntime, getDisplayName, isRunning } from '../../../core/models/container.model';
 import { TerminalState, DockedFileBrowser } from '../../../state/terminal.state';
 import { FileEditorModalComponent } from '../components/file-editor-modal/file-editor-modal.component';
@@ -58,6 +59,10 @@ export class FileBrowserViewComponent implements OnInit, OnDestroy {
   readonly Download = Download;
   readonly MoreVertical = MoreVertical;
   readonly ChevronRight = ChevronRight;
+  readonly HardDrive = HardDrive;
+  readonly Loader2 = Loader2;
+  readonly FileSearch = FileSearch;
+  readonly KeyRound = KeyRound;
   readonly X = X;
   readonly FolderOpen = FolderOpen;
   readonly PanelBottomOpen = PanelBottomOpen;
@@ -78,8 +83,19 @@ export class FileBrowserViewComponent implements OnInit, OnDestroy {
   renameValue = signal('');
   confirmDeleteEntry = signal<FileEntry | null>(null);
   contextMenuEntry = signal<FileEntry | null>(null);
+  permissionsEntry = signal<FileEntry | null>(null);
+  permMode = signal('');
+  permOwner = signal('');
+  permGroup = signal('');
+  permRecursive = signal(false);
   contextMenuPos = signal({ x: 0, y: 0 });
   refreshing = signal(false);
+
+  // Content search (grep) inputs
+  contentSearchMode = signal(false);
+  contentQuery = signal('');
+  contentIgnoreCase = signal(false);
+  contentRegex = signal(false);
   expandedSystemId = signal<string | null>(null);
   embedded = false;
 
@@ -326,6 +342,96 @@ export class FileBrowserViewComponent implements OnInit, OnDestroy {
     this.confirmDeleteEntry.set(null);
   }
 
+  startPermissions(entry: FileEntry): void {
+    this.permissionsEntry.set(entry);
+    this.permMode.set(permissionsToMode(entry.permissions));
+    this.permOwner.set(entry.owner);
+    this.permGroup.set(entry.group);
+    this.permRecursive.set(false);
+    this.closeContextMenu();
+  }
+
+  isValidMode(mode: string): boolean {
+    return /^[0-7]{3,4}$/.test(mode);
+  }
+
+  /** Apply only the attributes that were edited: chmod for the mode, chown for owner/group */
+  async confirmPermissions(): Promise<void> {
+    const entry = this.permissionsEntry();
+    if (!entry) return;
+    const mode = this.permMode().trim();
+    const owner = this.permOwner().trim();
+    const group = this.permGroup().trim();
+    const recursive = entry.fileType === 'directory' && this.permRecursive();
+    if (!this.isValidMode(mode) || !owner) return;
+
+    let ok = true;
+    if (mode !== permissionsToMode(entry.permissions) || recursive) {
+      ok = await this.state.changePermissions(entry, mode, recursive);
+    }
+    if (ok && (owner !== entry.owner || group !== entry.group || recursive)) {
+      ok = await this.state.changeOwner(entry, owner, group, recursive);
+    }
+    if (ok) {
+      this.permissionsEntry.set(null);
+    }
+  }
+
+  cancelPermissions(): void {
+    this.permissionsEntry.set(null);
+  }
+
+  toggleContentSearch(): void {
+    this.contentSearchMode.update((v) => !v);
+    if (!this.contentSearchMode()) {
+      this.state.clearContentSearch();
+    }
+  }
+
+  runContentSearch(): void {
+    this.state.searchContents(this.contentQuery(), {
+      caseInsensitive: this.contentIgnoreCase(),
+      fixedString: !this.contentRegex(),
+    });
+  }
+
+  /** Path of a search hit relative to the searched directory */
+  getHitPath(hit: SearchHit): string {
+    const base = this.state.currentPath();
+    const prefix = base === '/' ? '/' : `${base}/`;
+    return hit.file.startsWith(prefix) ? hit.file.slice(prefix.length) : hit.file;
+  }
+
+  openSearchHit(hit: SearchHit): void {
+    this.state.openFile({ path: hit.file });
+  }
+
+  isArchive(entry: FileEntry): boolean {
+    return entry.fileType !== 'directory' && archiveBaseName(entry.name) !== null;
+  }
+
+  compressEntry(entry: FileEntry, format: ArchiveFormat): void {
+    this.closeContextMenu();
+    this.state.createArchive(entry, format);
+  }
+
+  extractEntry(entry: FileEntry): void {
+    this.closeContextMenu();
+    this.state.extractArchive(entry);
+  }
+
+  calculateSize(entry: FileEntry): void {
+    this.closeContextMenu();
+    this.state.calculateDirectorySize(entry);
+  }
+
+  /** Computed size of a directory, with `+` when some of it couldn't be read */
+  getDirectorySizeLabel(entry: FileEntry): string | null {
+    const size = this.state.directorySizes().get(entry.path);
+    if (!size) return null;
+    return this.formatSize(size.size) + (size.partial ? '+' : '');
+  }
+
   async downloadEntry(entry: FileEntry): Promise<void> {
     this.closeContextMenu();
     try {
@@ -38,6 +38,7 @@ export class ImageListComponent implements OnInit {
   readonly showMobileFilters = signal(false);
   refreshing = false;
   showPullDialog = false;
+  pulling = false;
 
   pullForm = {
     name: '',
@@ -85,12 +86,17 @@ export class ImageListComponent implements OnInit {
   async pullImage(): Promise<void> {
     if (!this.pullForm.name || !this.pullForm.systemId) return;
 
-    await this.imageState.pullImage(
-      this.pullForm.systemId,
-      this.pullForm.name,
-      this.pullForm.tag,
-      this.pullForm.runtime
-    );
+    this.pulling = true;
+    try {
+      await this.imageState.pullImage(
+        this.pullForm.systemId,
+        this.pullForm.name,
+        this.pullForm.tag,
+        this.pullForm.runtime
+      );
+    } finally {
+      this.pulling = false;
+    }
 
     this.showPullDialog = false;
     this.pullForm = {
@@ -101,6 +107,15 @@ export class ImageListComponent implements OnInit {
     };
   }
 
+  /** Close the pull dialog, stopping the pull if one is running */
+  async cancelPullDialog(): Promise<void> {
+    if (!this.pulling) {
+      this.showPullDialog = false;
+      return;
+    }
+    await this.imageState.cancelPull(this.pullForm.systemId, this.pullForm.name, this.pullForm.tag);
+  }
+
   async onImageDeleted(image: ContainerImage): Promise<void> {
     if (confirm(`Remove image "${getImageFullName(image)}"? This action cannot be undone.`)) {
       await this.imageState.removeImage(image);
@@ -30,19 +30,23 @@ import {
   Link,
   Minus,
   Sparkles,
+  Database,
+  Upload,
 } from 'lucide-angular';
-import { open } from '@tauri-apps/plugin-dialog';
+import { open, save } from '@tauri-apps/plugin-dialog';
 import { AiService } from '../../../../core/services/ai.service';
 import { SystemService } from '../../../../core/services/system.service';
 import { AiSettingsState } from '../../../../state/ai-settings.state';
 import { UpdateState } from '../../../../state/update.state';
 import { ChangelogState } from '../../../../state/changelog.state';
+import { SystemState } from '../../../../state/system.state';
 import {
   AI_PROVIDERS,
   AiModel,
   AiProviderType,
   ProviderInfo,
 } from '../../../../core/models/ai-settings.model';
+import { errorMessage, fieldErrors } from '../../../../core/models/error.model';
 
 @Component({
   selector: 'app-settings-page',
@@ -55,6 +59,7 @@ export class SettingsPageComponent implements OnInit {
   private aiState = inject(AiSettingsState);
   private aiService = inject(AiService);
   private systemService = inject(SystemService);
+  private systemState = inject(SystemState);
   readonly updateState = inject(UpdateState);
   readonly changelogState = inject(ChangelogState);
 
@@ -79,6 +84,8 @@ export class SettingsPageComponent implements OnInit {
   readonly Link = Link;
   readonly Minus = Minus;
   readonly Sparkles = Sparkles;
+  readonly Database = Database;
+  readonly Upload = Upload;
 
   // Tab state
   activeTab = signal<'ai' | 'ssh' | 'general'>('ai');
@@ -92,6 +99,10 @@ export class SettingsPageComponent implements OnInit {
   // Form state
   selectedProvider = signal<AiProviderType>('ollama');
   apiKey = signal<string>('');
+  /** A key is saved in the OS keychain; the key itself is never sent to the UI */
+  hasSavedApiKey = signal(false);
+  /** Remove the saved key on the next save */
+  removeSavedApiKey = signal(false);
   endpointUrl = signal<string>('http://localhost:11434');
   modelName = signal<string>('llama3.2');
   temperature = signal<number>(0.3);
@@ -101,6 +112,7 @@ export class SettingsPageComponent implements OnInit {
   memoryEnabled = signal<boolean>(true);
   summaryModel = signal<string>('');
   summaryMaxTokens = signal<number>(100);
+  summaryMinInputLength = signal<number>(100);
 
   // Azure-specific settings
   apiVersion = signal<string>('');
@@ -113,10 +125,13 @@ export class SettingsPageComponent implements OnInit {
   testResult = signal<'success' | 'error' | null>(null);
   testMessage = signal<string>('');
   saveMessage = signal<string>('');
+  /** Validation messages from the last save, keyed by request field name */
+  fieldErrors = signal<Record<string, string>>({});
 
   // Model management state (Ollama only)
   newModelName = signal<string>('');
   isPullingModel = signal(false);
+  pullId = signal<string | null>(null);
   isDeletingModel = signal<string | null>(null);
   pullMessage = signal<string>('');
 
@@ -128,8 +143,14 @@ export class SettingsPageComponent implements OnInit {
   sshSaveMessage = signal<string>('');
   isSavingSsh = signal(false);
 
+  // Database backup
+  isBackingUp = signal(false);
+  isRestoring = signal(false);
+  backupMessage = signal<string>('');
+  backupError = signal(false);
+
   // Saved state tracking for dirty detection
-  private savedAi = signal<{ provider: string; apiKey: string; endpoint: string; model: string; temp: number; maxTokens: number; memoryEnabled: boolean; summaryModel: string; summaryMaxTokens: number; apiVersion: string } | null>(null);
+  private savedAi = signal<{ provider: string; apiKey: string; endpoint: string; model: string; temp: number; maxTokens: number; memoryEnabled: boolean; summaryModel: string; summaryMaxTokens: number; summaryMinInputLength: number; apiVersion: string } | null>(null);
   private savedSshPaths = signal<string[]>([]);
 
   readonly isAiDirty = computed(() => {
@@ -137,6 +158,7 @@ export class SettingsPageComponent implements OnInit {
     if (!saved) return false;
     return saved.provider !== this.selectedProvider()
       || saved.apiKey !== this.apiKey()
+      || this.removeSavedApiKey()
       || saved.endpoint !== this.endpointUrl()
       || saved.model !== this.modelName()
       || saved.temp !== this.temperature()
@@ -144,6 +166,7 @@ export class SettingsPageComponent implements OnInit {
       || saved.memoryEnabled !== this.memoryEnabled()
       || saved.summaryModel !== this.summaryModel()
       || saved.summaryMaxTokens !== this.summaryMaxTokens()
+      || saved.summaryMinInputLength !== this.summaryMinInputLength()
       || saved.apiVersion !== this.apiVersion();
   });
 
@@ -204,7 +227,9 @@ export class SettingsPageComponent implements OnInit {
       const settings = this.aiState.settings();
       if (settings) {
         this.selectedProvider.set(settings.provider);
-        this.apiKey.set(settings.apiKey ?? '');
+        this.apiKey.set('');
+        this.hasSavedApiKey.set(!!settings.hasApiKey);
+        this.removeSavedApiKey.set(false);
 
         // Use provider's default endpoint if stored endpoint is for a different provider
         const providerInfo = this.providers.find((p) => p.id === settings.provider);
@@ -220,6 +245,7 @@ export class SettingsPageComponent implements OnInit {
         this.memoryEnabled.set(settings.memoryEnabled ?? true);
         this.summaryModel.set(settings.summaryModel ?? '');
         this.summaryMaxTokens.set(settings.summaryMaxTokens ?? 100);
+        this.summaryMinInputLength.set(settings.summaryMinInputLength ?? 100);
         this.apiVersion.set(settings.apiVersion ?? '');
       }
       await this.loadModels();
@@ -240,6 +266,7 @@ export class SettingsPageComponent implements OnInit {
       memoryEnabled: this.memoryEnabled(),
       summaryModel: this.summaryModel(),
       summaryMaxTokens: this.summaryMaxTokens(),
+      summaryMinInputLength: this.summaryMinInputLength(),
       apiVersion: this.apiVersion(),
     });
   }
@@ -258,7 +285,8 @@ export class SettingsPageComponent implements OnInit {
 
     if (saved.provider === providerId) {
       // Switching back to the saved provider — restore all saved values
-      this.apiKey.set(saved.apiKey ?? '');
+      this.apiKey.set('');
+      this.hasSavedApiKey.set(!!saved.hasApiKey);
       this.endpointUrl.set(saved.endpointUrl);
       this.modelName.set(saved.modelName);
       this.apiVersion.set(saved.apiVersion ?? '');
@@ -267,11 +295,13 @@ export class SettingsPageComponent implements OnInit {
       this.maxTokens.set(saved.maxTokens);
       this.memoryEnabled.set(saved.memoryEnabled ?? true);
       this.summaryMaxTokens.set(saved.summaryMaxTokens ?? 100);
+      this.summaryMinInputLength.set(saved.summaryMinInputLength ?? 100);
     } else {
       // Different provider — use defaults
       this.endpointUrl.set(provider.defaultEndpoint ?? '');
       this.modelName.set(provider.defaultModel);
       this.apiKey.set('');
+      this.hasSavedApiKey.set(false);
       this.apiVersion.set('');
       this.summaryModel.set('');
     }
@@ -335,6 +365,10 @@ export class SettingsPageComponent implements OnInit {
     return tokens.toString();
   }
 
+  formatModelSize(bytes: number): string {
+    return this.aiService.formatBytes(bytes);
+  }
+
   async testConnection(): Promise<void> {
     this.isTesting.set(true);
     this.testResult.set(null);
@@ -349,12 +383,18 @@ export class SettingsPageComponent implements OnInit {
         this.selectedProvider(),
         this.apiKey() || undefined,
         endpointToSend,
-        this.apiVersion() || undefined
+        this.apiVersion() || undefined,
+        this.modelName() || undefined
       );
 
       if (success) {
         this.testResult.set('success');
-        this.testMessage.set('Connection successful!');
+        const usage = this.aiState.lastTestUsage();
+        this.testMessage.set(
+          usage
+            ? `Connection successful! Test request used ${usage.total_tokens} tokens (${usage.prompt_tokens} prompt, ${usage.completion_tokens} completion).`
+            : 'Connection successful!'
+        );
         await this.loadModels();
       } else {
         this.testResult.set('error');
@@ -362,7 +402,7 @@ export class SettingsPageComponent implements OnInit {
       }
     } catch (err) {
       this.testResult.set('error');
-      this.testMessage.set(err instanceof Error ? err.message : String(err));
+      this.testMessage.set(errorMessage(err));
     } finally {
       this.isTesting.set(false);
     }
@@ -371,11 +411,13 @@ export class SettingsPageComponent implements OnInit {
   async saveSettings(): Promise<void> {
     this.isSaving.set(true);
     this.saveMessage.set('');
+    this.fieldErrors.set({});
 
     try {
+      // A blank key keeps the saved one; an empty string asks the backend to remove it
       await this.aiState.updateSettings(
         this.selectedProvider(),
-        this.apiKey() || undefined,
+        this.apiKey() || (this.removeSavedApiKey() ? '' : undefined),
         this.modelName(),
         this.endpointUrl(),
         this.temperature(),
@@ -383,13 +425,18 @@ export class SettingsPageComponent implements OnInit {
         this.memoryEnabled(),
         this.summaryModel() || undefined,
         this.summaryMaxTokens(),
-        this.apiVersion() || undefined
+        this.apiVersion() || undefined,
+        this.summaryMinInputLength()
       );
+      this.hasSavedApiKey.set(this.aiState.hasApiKey());
+      this.removeSavedApiKey.set(false);
+      this.apiKey.set('');
       this.snapshotAiState();
       this.saveMessage.set('Settings saved successfully!');
       setTimeout(() => this.saveMessage.set(''), 3000);
     } catch (err) {
-      this.saveMessage.set(err instanceof Error ? err.message : 'Failed to save settings');
+      this.fieldErrors.set(fieldErrors(err));
+      this.saveMessage.set(errorMessage(err, 'Failed to save settings'));
     } finally {
       this.isSaving.set(false);
     }
@@ -399,19 +446,41 @@ export class SettingsPageComponent implements OnInit {
     const modelName = this.newModelName().trim();
     if (!modelName) return;
 
+    const pullId = crypto.randomUUID();
     this.isPullingModel.set(true);
-    this.pullMessage.set(`Pulling ${modelName}... This may take a few minutes.`);
+    this.pullId.set(pullId);
+    this.pullMessage.set(`Pulling ${modelName}...`);
 
     try {
-      await this.aiService.pullOllamaModel(modelName, this.endpointUrl());
+      await this.aiService.pullOllamaModel(
+        modelName,
+        this.endpointUrl(),
+        (progress) => {
+          const percent = progress.percent === null ? '' : ` (${Math.round(progress.percent)}%)`;
+          this.pullMessage.set(`Pulling ${modelName}: ${progress.status}${percent}`);
+        },
+        pullId
+      );
       this.pullMessage.set(`Successfully pulled ${modelName}!`);
       this.newModelName.set('');
       await this.loadModels();
       setTimeout(() => this.pullMessage.set(''), 5000);
     } catch (err) {
-      this.pullMessage.set(err instanceof Error ? err.message : 'Failed to pull model');
+      this.pullMessage.set(errorMessage(err, 'Failed to pull model'));
     } finally {
       this.isPullingModel.set(false);
+      this.pullId.set(null);
+    }
+  }
+
+  async cancelPull(): Promise<void> {
+    const pullId = this.pullId();
+    if (!pullId) return;
+
+    try {
+      await this.aiService.cancelOllamaPull(pullId);
+    } catch (err) {
+      console.error('Failed to cancel model pull:', err);
     }
   }
 
@@ -485,7 +554,10 @@ export class SettingsPageComponent implements OnInit {
 
     try {
       const paths = this.sshConfigPaths().filter(p => p.trim());
+      // Settings are saved as a whole; keep the ones this section doesn't edit
+      const current = await this.systemService.getAppSettings();
       await this.systemService.updateAppSettings({
+        ...current,
         sshConfigPaths: paths,
       });
       this.sshConfigPaths.set(paths);
@@ -493,7 +565,7 @@ export class SettingsPageComponent implements OnInit {
       this.sshSaveMessage.set('SSH settings saved!');
       setTimeout(() => this.sshSaveMessage.set(''), 3000);
     } catch (err) {
-      this.sshSaveMessage.set(err instanceof Error ? err.message : 'Failed to save SSH settings');
+      this.sshSaveMessage.set(errorMessage(err, 'Failed to save SSH settings'));
     } finally {
       this.isSavingSsh.set(false);
     }
@@ -528,4 +600,62 @@ export class SettingsPageComponent implements OnInit {
       console.error('Failed to browse for SSH config:', err);
     }
   }
+
+  // ========================================================================
+  // Database Backup
+  // ========================================================================
+
+  async backupDatabase(): Promise<void> {
+    const date = new Date().toISOString().slice(0, 10);
+    const dest = await save({
+      title: 'Back Up Database',
+      defaultPath: `containerus-backup-${date}.db`,
+      filters: [{ name: 'SQLite Database', extensions: ['db'] }],
+    });
+    if (!dest) return;
+
+    this.isBackingUp.set(true);
+    this.backupMessage.set('');
+    try {
+      await this.systemService.backupDatabase(dest);
+      this.showBackupMessage('Backup saved', false);
+    } catch (err) {
+      this.showBackupMessage(errorMessage(err), true);
+    } finally {
+      this.isBackingUp.set(false);
+    }
+  }
+
+  async restoreDatabase(): Promise<void> {
+    const src = await open({
+      title: 'Restore Database',
+      multiple: false,
+      directory: false,
+      filters: [{ name: 'SQLite Database', extensions: ['db'] }],
+    });
+    if (!src) return;
+    if (!confirm('Restoring replaces all systems, command templates and settings with the backup. Continue?')) {
+      return;
+    }
+
+    this.isRestoring.set(true);
+    this.backupMessage.set('');
+    try {
+      await this.systemService.restoreDatabase(src);
+      await Promise.all([this.systemState.loadSystems(), this.loadSettings(), this.loadSshSettings()]);
+      this.showBackupMessage('Database restored', false);
+    } catch (err) {
+      this.showBackupMessage(errorMessage(err), true);
+    } finally {
+      this.isRestoring.set(false);
+    }
+  }
+
+  private showBackupMessage(message: string, isError: boolean): void {
+    this.backupMessage.set(message);
+    this.backupError.set(isError);
+    if (!isError) {
+      setTimeout(() => this.backupMessage.set(''), 3000);
+    }
+  }
 }
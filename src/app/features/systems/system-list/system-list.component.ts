@@ -45,6 +45,7 @@ import { SystemState } from '../../../state/system.state';
 import { AppState } from '../../../state/app.state';
 import { TerminalState, DEFAULT_TERMINAL_OPTIONS } from '../../../state/terminal.state';
 import { ToastState } from '../../../state/toast.state';
+import { errorMessage } from '../../../core/models/error.model';
 
 @Component({
   selector: 'app-system-list',
@@ -428,8 +429,7 @@ export class SystemListComponent implements OnInit {
       }
     } catch (err) {
       console.error('[SystemList] Unexpected error in addSystem:', err);
-      const message = err instanceof Error ? err.message :
-        typeof err === 'string' ? err : 'An unexpected error occurred while adding the system';
+      const message = errorMessage(err, 'An unexpected error occurred while adding the system');
       this.systemState.setError(message);
     } finally {
       this.addingSystem.set(false);
@@ -721,7 +721,7 @@ export class SystemListComponent implements OnInit {
         }
       }
     } catch (err) {
-      const message = err instanceof Error ? err.message : 'Failed to import SSH key';
+      const message = errorMessage(err, 'Failed to import SSH key');
       this.systemState.setError(message);
       console.error('Failed to import SSH key:', err);
     } finally {
@@ -25,6 +25,7 @@ import { AiService } from '../../../../core/services/ai.service';
 import { BlockFactoryService } from '../../services/block-factory.service';
 import { BlockState } from '../../../../state/block.state';
 import { CommandPreviewCardComponent } from '../command-preview-card/command-preview-card.component';
+import { errorMessage } from '../../../../core/models/error.model';
 
 @Component({
   selector: 'app-ai-input-bar',
@@ -142,7 +143,7 @@ export class AiInputBarComponent implements OnInit {
         this.currentCommandBlockId = null;
       }
     } catch (err) {
-      const message = err instanceof Error ? err.message : String(err);
+      const message = errorMessage(err);
       // Remove loading block and show error as a response block
       if (loadingBlockId) {
         this.blockFactory.removeBlock(loadingBlockId);
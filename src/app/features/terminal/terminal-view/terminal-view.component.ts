@@ -12,7 +12,7 @@ import {
 } from '@angular/core';
 import { ActivatedRoute, Router } from '@angular/router';
 import { FormsModule } from '@angular/forms';
-import { LucideAngularModule, X, Maximize2, Minimize2, Command, Ship, Container, Apple, Search, ChevronUp, ChevronDown, PanelBottomOpen, Sparkles } from 'lucide-angular';
+import { LucideAngularModule, X, Maximize2, Minimize2, Command, Ship, Container, Apple, Search, ChevronUp, ChevronDown, PanelBottomOpen, Sparkles, Circle, FileDown } from 'lucide-angular';
 import { Terminal } from '@xterm/xterm';
 import { FitAddon } from '@xterm/addon-fit';
 import { WebLinksAddon } from '@xterm/addon-web-links';
@@ -20,6 +20,7 @@ import { SearchAddon } from '@xterm/addon-search';
 import { SerializeAddon } from '@xterm/addon-serialize';
 import { TerminalService, TerminalSession } from '../../../core/services/terminal.service';
 import { SystemState } from '../../../state/system.state';
+import { CommandTemplateState } from '../../../state/command-template.state';
 import { TerminalState, DockedTerminal, DEFAULT_TERMINAL_OPTIONS } from '../../../state/terminal.state';
 import { CommandPaletteComponent } from '../../../shared/components/command-palette/command-palette.component';
 import { VariableInputModalComponent } from '../../../shared/components/variable-input-modal/variable-input-modal.component';
@@ -28,6 +29,7 @@ import { ContainerRuntime } from '../../../core/models/container.model';
 import { WarpTerminalViewComponent } from '../../warp-terminal/warp-terminal-view/warp-terminal-view.component';
 import { CommandHistoryService } from '../../warp-terminal/state/command-history.service';
 import { TerminalEventBus } from '../../warp-terminal/state/warp-terminal.bus';
+import { errorMessage } from '../../../core/models/error.model';
 
 @Component({
   selector: 'app-terminal-view',
@@ -47,6 +49,7 @@ export class TerminalViewComponent implements OnInit, AfterViewInit, OnDestroy {
   private terminalState = inject(TerminalState);
   private historyService = inject(CommandHistoryService);
   private eventBus = inject(TerminalEventBus);
+  private commandState = inject(CommandTemplateState);
 
   readonly X = X;
   readonly Maximize2 = Maximize2;
@@ -60,6 +63,8 @@ export class TerminalViewComponent implements OnInit, AfterViewInit, OnDestroy {
   readonly ChevronDown = ChevronDown;
   readonly PanelBottomOpen = PanelBottomOpen;
   readonly Sparkles = Sparkles;
+  readonly Circle = Circle;
+  readonly FileDown = FileDown;
 
   showCommandPalette = false;
   showVariableInput = false;
@@ -73,6 +78,7 @@ export class TerminalViewComponent implements OnInit, AfterViewInit, OnDestroy {
   private searchAddon: SearchAddon | null = null;
   private serializeAddon: SerializeAddon | null = null;
   private resizeObserver: ResizeObserver | null = null;
+  private unlistenClosed: (() => void) | null = null;
 
   // Buffer to accumulate typed characters for command history
   private inputBuffer = '';
@@ -82,6 +88,7 @@ export class TerminalViewComponent implements OnInit, AfterViewInit, OnDestroy {
   containerId: string | null = null;
   isFullscreen = false;
   showWarpTerminal = signal(false);
+  isRecording = signal(false);
 
   @HostBinding('class.warp-enabled')
   get warpEnabled(): boolean {
@@ -204,7 +211,8 @@ export class TerminalViewComponent implements OnInit, AfterViewInit, OnDestroy {
       this.session = await this.terminalService.startSession(
         this.systemId,
         this.containerId ?? undefined,
-        '/bin/sh'
+        undefined,
+        this.terminal ? { cols: this.terminal.cols, rows: this.terminal.rows } : undefined
       );
 
       await this.terminalService.onOutput(this.session.id, (data) => {
@@ -221,6 +229,13 @@ export class TerminalViewComponent implements OnInit, AfterViewInit, OnDestroy {
         });
       });
 
+      this.unlistenClosed = await this.terminalService.onSessionClosed(({ sessionId }) => {
+        if (sessionId === this.session?.id) {
+          this.terminal?.writeln('\r\n[Session closed after being idle]');
+          this.session = null;
+        }
+      });
+
       if (this.terminal) {
         await this.terminalService.resize(
           this.session.id,
@@ -234,7 +249,7 @@ export class TerminalViewComponent implements OnInit, AfterViewInit, OnDestroy {
       // Load shell history from remote system for Warp composer
       this.historyService.loadRemoteHistory(this.systemId);
     } catch (error) {
-      const message = error instanceof Error ? error.message : 'Unknown error';
+      const message = errorMessage(error, 'Unknown error');
       this.terminal?.writeln(`\r\nFailed to connect: ${message}`);
     }
   }
@@ -285,6 +300,58 @@ export class TerminalViewComponent implements OnInit, AfterViewInit, OnDestroy {
     }, 0);
   }
 
+  /** Start recording the session, or stop and offer to save the recording */
+  async toggleRecording(): Promise<void> {
+    if (!this.session) return;
+    const sessionId = this.session.id;
+
+    try {
+      if (!this.isRecording()) {
+        await this.terminalService.startRecording(sessionId, this.terminal?.cols, this.terminal?.rows);
+        this.isRecording.set(true);
+        return;
+      }
+
+      await this.terminalService.stopRecording(sessionId);
+      this.isRecording.set(false);
+
+      const { save } = await import('@tauri-apps/plugin-dialog');
+      const stamp = new Date().toISOString().slice(0, 19).replace(/[:T]/g, '-');
+      const path = await save({
+        defaultPath: `terminal-${stamp}.cast`,
+        filters: [{ name: 'Asciicast', extensions: ['cast'] }],
+      });
+      if (path) {
+        await this.terminalService.exportRecording(sessionId, path);
+      }
+    } catch (error) {
+      const message = errorMessage(error);
+      this.terminal?.writeln(`\r\nRecording failed: ${message}`);
+    }
+  }
+
+  /** Save everything the session has printed; `.ansi` files keep colors, others are plain text */
+  async saveScrollback(): Promise<void> {
+    if (!this.session) return;
+
+    try {
+      const { save } = await import('@tauri-apps/plugin-dialog');
+      const path = await save({
+        defaultPath: 'terminal-output.log',
+        filters: [
+          { name: 'Plain text', extensions: ['log', 'txt'] },
+          { name: 'Text with colors', extensions: ['ansi'] },
+        ],
+      });
+      if (path) {
+        await this.terminalService.exportScrollback(this.session.id, path, !path.endsWith('.ansi'));
+      }
+    } catch (error) {
+      const message = errorMessage(error);
+      this.terminal?.writeln(`\r\nSaving output failed: ${message}`);
+    }
+  }
+
   async close(): Promise<void> {
     if (this.session) {
       await this.terminalService.closeSession(this.session.id);
@@ -294,6 +361,7 @@ export class TerminalViewComponent implements OnInit, AfterViewInit, OnDestroy {
 
   private cleanup(): void {
     this.resizeObserver?.disconnect();
+    this.unlistenClosed?.();
     this.terminal?.dispose();
 
     if (this.session) {
@@ -321,6 +389,7 @@ export class TerminalViewComponent implements OnInit, AfterViewInit, OnDestroy {
       this.showVariableInput = true;
     } else {
       // No variables - execute directly
+      void this.commandState.recordUse(event.template.id);
       this.executeCommand(event.command);
     }
   }
@@ -332,6 +401,14 @@ export class TerminalViewComponent implements OnInit, AfterViewInit, OnDestroy {
     this.terminal?.focus();
   }
 
+  /** Run the command built from the pending template and count it as a use of that template */
+  executeTemplateCommand(command: string): void {
+    if (this.pendingTemplate) {
+      void this.commandState.recordUse(this.pendingTemplate.id);
+    }
+    this.executeCommand(command);
+  }
+
   executeCommand(command: string): void {
     if (this.session && command) {
       if (this.showWarpTerminal()) {
@@ -24,6 +24,10 @@ export interface AgentQueryRequest {
   streaming: boolean;
   /** Optional query ID - frontend generates to avoid race condition */
   queryId?: string;
+  /** Preview the agent's plan without running any commands */
+  dryRun?: boolean;
+  /** Most tool-calling rounds for this query (backend default: 10) */
+  maxIterations?: number;
 }
 
 /** Chunk types for streaming responses */
@@ -101,6 +105,16 @@ export interface AgentConfirmationRequiredEvent extends AgentEventBase {
   affectedResources: string[];
   warning?: string;
   alternatives: CommandAlternative[];
+  /** Unix millis after which the command is cancelled and can't be approved */
+  expiresAt: number;
+}
+
+/** Nobody answered a confirmation in time, so its command was cancelled */
+export interface AgentConfirmationExpiredEvent extends AgentEventBase {
+  type: 'confirmationExpired';
+  queryId: string;
+  confirmationId: string;
+  command: string;
 }
 
 /** Command execution started */
@@ -187,6 +201,7 @@ export type AgentEvent =
   | AgentResponseChunkEvent
   | AgentCommandProposedEvent
   | AgentConfirmationRequiredEvent
+  | AgentConfirmationExpiredEvent
   | AgentCommandStartedEvent
   | AgentCommandOutputEvent
   | AgentCommandCompletedEvent
@@ -221,4 +236,20 @@ export interface AgentPreferences {
   confirmAllCommands: boolean;
   maxAutoExecuteSteps: number;
   confirmationTimeoutSecs: number;
+  /** Highest danger level run without asking; dangerous and critical commands always ask */
+  autoApproveLevel: DangerLevel;
+}
+
+/** Danger level assigned by the command classifier */
+export type DangerLevel = 'safe' | 'moderate' | 'dangerous' | 'critical';
+
+/** User-defined rule for the command classifier (can only raise the danger level) */
+export interface DangerRule {
+  /** Generated by the backend when the rule is added */
+  id?: string;
+  /** Literal substring, or a regular expression when isRegex is set */
+  pattern: string;
+  isRegex: boolean;
+  level: DangerLevel;
+  warning: string;
 }
@@ -724,6 +724,33 @@ export class AgentBackendService implements OnDestroy {
     }
   }
 
+  /**
+   * Clear the agent's conversation history for this terminal, including persisted history
+   */
+  async clearHistory(): Promise<void> {
+    if (!this.agentSessionId) return;
+
+    try {
+      await this.tauri.invoke('clear_agent_history', {
+        sessionId: this.agentSessionId,
+      });
+    } catch (error) {
+      console.error('[AgentBackend] Error clearing history:', error);
+    }
+  }
+
+  /**
+   * Export the conversation as a shareable transcript, with secrets redacted
+   */
+  async exportTranscript(format: 'markdown' | 'json' = 'markdown'): Promise<string | null> {
+    if (!this.agentSessionId) return null;
+
+    return this.tauri.invoke<string>('export_agent_transcript', {
+      sessionId: this.agentSessionId,
+      format,
+    });
+  }
+
   /**
    * Check if the agent backend is initialized
    */
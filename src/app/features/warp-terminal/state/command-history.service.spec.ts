@@ -3,6 +3,9 @@ import { Injector, runInInjectionContext } from '@angular/core';
 import { CommandHistoryService } from './command-history.service';
 import { TerminalService } from '../../../core/services/terminal.service';
 
+const shellHistory = (...commands: string[]) =>
+  commands.map((command) => ({ command, timestamp: null }));
+
 describe('CommandHistoryService', () => {
   let service: CommandHistoryService;
   let mockTerminalService: any;
@@ -91,7 +94,7 @@ describe('CommandHistoryService', () => {
   });
 
   it('should load remote history', async () => {
-    mockTerminalService.fetchShellHistory.mockResolvedValue(['git status', 'npm install']);
+    mockTerminalService.fetchShellHistory.mockResolvedValue(shellHistory('git status', 'npm install'));
 
     await service.loadRemoteHistory('sys-1');
 
@@ -99,8 +102,22 @@ describe('CommandHistoryService', () => {
     expect(service.history()[0].source).toBe('shell');
   });
 
+  it('should use shell timestamps from remote history when present', async () => {
+    mockTerminalService.fetchShellHistory.mockResolvedValue([
+      { command: 'git push', timestamp: 1700000000 },
+      { command: 'git commit', timestamp: null },
+    ]);
+
+    await service.loadRemoteHistory('sys-1');
+
+    const history = service.history();
+    expect(history.map((e) => e.text)).toEqual(['git push', 'git commit']);
+    expect(history[0].timestamp).toBe(1700000000 * 1000);
+    expect(history[1].timestamp).toBeLessThan(Date.now());
+  });
+
   it('should not load remote history twice for same system', async () => {
-    mockTerminalService.fetchShellHistory.mockResolvedValue(['git status']);
+    mockTerminalService.fetchShellHistory.mockResolvedValue(shellHistory('git status'));
 
     await service.loadRemoteHistory('sys-1');
     await service.loadRemoteHistory('sys-1');
@@ -109,7 +126,7 @@ describe('CommandHistoryService', () => {
   });
 
   it('should load remote history for different systems', async () => {
-    mockTerminalService.fetchShellHistory.mockResolvedValue(['cmd1']);
+    mockTerminalService.fetchShellHistory.mockResolvedValue(shellHistory('cmd1'));
 
     await service.loadRemoteHistory('sys-1');
     await service.loadRemoteHistory('sys-2');
@@ -119,7 +136,7 @@ describe('CommandHistoryService', () => {
 
   it('should not duplicate remote commands that already exist locally', async () => {
     service.add('git status');
-    mockTerminalService.fetchShellHistory.mockResolvedValue(['git status', 'npm install']);
+    mockTerminalService.fetchShellHistory.mockResolvedValue(shellHistory('git status', 'npm install'));
 
     await service.loadRemoteHistory('sys-1');
 
@@ -139,12 +156,12 @@ describe('CommandHistoryService', () => {
   });
 
   it('should search remote history', async () => {
-    mockTerminalService.fetchShellHistory.mockResolvedValue(['git log']);
+    mockTerminalService.fetchShellHistory.mockResolvedValue(shellHistory('git log'));
 
     // Need to set currentSystemId first via loadRemoteHistory
     await service.loadRemoteHistory('sys-1');
 
-    mockTerminalService.fetchShellHistory.mockResolvedValue(['git diff', 'git log']);
+    mockTerminalService.fetchShellHistory.mockResolvedValue(shellHistory('git diff', 'git log'));
     const results = await service.searchRemoteHistory('git');
 
     expect(results).toEqual(['git diff', 'git log']);
@@ -61,22 +61,25 @@ export class CommandHistoryService {
     this.loadedSystems.add(systemId);
 
     try {
-      const remoteCommands = await this.terminalService.fetchShellHistory(
+      // Remote history arrives newest first
+      const remoteEntries = await this.terminalService.fetchShellHistory(
         systemId
       );
 
-      if (remoteCommands.length === 0) return;
+      if (remoteEntries.length === 0) return;
 
-      // Add remote commands (with older timestamps so they sort after recent commands)
       this._history.update((entries) => {
         const existingTexts = new Set(entries.map((e) => e.text));
-        const newEntries = remoteCommands
-          .filter((cmd) => !existingTexts.has(cmd))
-          .map((text, i) => ({
-            text,
+        const newEntries = remoteEntries
+          .filter((entry) => !existingTexts.has(entry.command))
+          .map((entry, i) => ({
+            text: entry.command,
             source: 'shell' as const,
-            // Older timestamps so they appear after recent commands
-            timestamp: Date.now() - (remoteCommands.length - i) * 1000,
+            // Use the shell's own timestamp when available, otherwise older
+            // timestamps so they appear after recent commands
+            timestamp: entry.timestamp
+              ? entry.timestamp * 1000
+              : Date.now() - (i + 1) * 1000,
           }));
 
         const updated = [...entries, ...newEntries].slice(0, this.MAX_ENTRIES);
@@ -89,8 +92,8 @@ export class CommandHistoryService {
   }
 
   /**
-   * Search remote shell history using grep on the remote system.
-   * Returns matching commands from the entire history file.
+   * Search remote shell history on the current system.
+   * Returns matching commands, newest first.
    */
   async searchRemoteHistory(query: string): Promise<string[]> {
     if (!this.currentSystemId || !query.trim()) {
@@ -98,11 +101,12 @@ export class CommandHistoryService {
     }
 
     try {
-      return await this.terminalService.fetchShellHistory(
+      const entries = await this.terminalService.fetchShellHistory(
         this.currentSystemId,
         100, // Limit search results
         query
       );
+      return entries.map((entry) => entry.command);
     } catch (error) {
       console.warn('Failed to search remote history:', error);
       return [];
@@ -27,6 +27,7 @@ import {
 import {
   CommandTemplate,
   substituteVariables,
+  getTemplateCommand,
   parseVariables,
   getRuntimeLabel,
   getRuntimePrefix,
@@ -182,18 +183,19 @@ export class CommandPaletteComponent implements OnInit {
   }
 
   selectTemplate(template: CommandTemplate, closeAfter = false): void {
-    const variables = parseVariables(template.command);
+    const templateCommand = getTemplateCommand(template);
+    const variables = parseVariables(templateCommand);
 
     // Check if RUNTIME is the only variable - auto-substitute it
     const nonRuntimeVars = variables.filter((v) => v !== 'RUNTIME');
 
     if (nonRuntimeVars.length > 0) {
       // Has variables besides RUNTIME - emit for parent to handle variable input
-      this.execute.emit({ command: template.command, template });
+      this.execute.emit({ command: templateCommand, template });
     } else {
       // No variables or only RUNTIME - auto-substitute and execute
       const command = substituteVariables(
-        template.command,
+        templateCommand,
         {},
         this.effectiveRuntime()
       );
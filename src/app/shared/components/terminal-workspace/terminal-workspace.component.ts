@@ -460,7 +460,7 @@ export class TerminalWorkspaceComponent implements AfterViewInit, OnDestroy {
     if (!system) return;
 
     try {
-      const session = await this.terminalService.startSession(systemId, container.id);
+      const session = await this.terminalService.startContainerSession(systemId, container.id);
       const id = this.terminalState.generateTerminalId();
       this.terminalState.addTerminal({
         id,
@@ -27,6 +27,7 @@ import {
   VARIABLE_SUGGESTIONS,
 } from '../../../core/models/command-template.model';
 import { ContainerRuntime } from '../../../core/models/container.model';
+import { CommandTemplateService } from '../../../core/services/command-template.service';
 import { ContainerState } from '../../../state/container.state';
 import { ImageState } from '../../../state/image.state';
 import { VolumeState } from '../../../state/volume.state';
@@ -68,6 +69,7 @@ export class VariableInputModalComponent implements OnInit {
   readonly volumeState = inject(VolumeState);
   readonly networkState = inject(NetworkState);
   readonly systemState = inject(SystemState);
+  private readonly templateService = inject(CommandTemplateService);
 
   // Icons
   readonly X = X;
@@ -75,18 +77,22 @@ export class VariableInputModalComponent implements OnInit {
   readonly Variable = Variable;
 
   readonly variables = signal<VariableInput[]>([]);
+  /** Validation errors from the backend, keyed by variable name */
+  readonly errors = signal<Record<string, string>>({});
 
-  // Computed preview of the command with substituted values
-  readonly previewCommand = computed(() => {
+  private readonly values = computed(() => {
     const values: Record<string, string> = {};
     for (const v of this.variables()) {
       if (v.value) {
         values[v.name] = v.value;
       }
     }
-    return substituteVariables(this.command(), values);
+    return values;
   });
 
+  // Computed preview of the command with substituted values
+  readonly previewCommand = computed(() => substituteVariables(this.command(), this.values()));
+
   readonly isValid = computed(() => {
     return this.variables().every((v) => !v.required || v.value.trim().length > 0);
   });
@@ -116,7 +122,9 @@ export class VariableInputModalComponent implements OnInit {
         description: templateVar?.description ?? VARIABLE_SUGGESTIONS[name] ?? '',
         value: defaultValue,
         required: templateVar?.required ?? true,
-        suggestions: this.getSuggestionsForVariable(name),
+        suggestions: templateVar?.varType?.kind === 'enum'
+          ? templateVar.varType.options
+          : this.getSuggestionsForVariable(name),
       };
     });
 
@@ -186,10 +194,20 @@ export class VariableInputModalComponent implements OnInit {
       updated[index] = { ...updated[index], value };
       return updated;
     });
+    const name = this.variables()[index].name;
+    if (this.errors()[name]) {
+      this.errors.update(({ [name]: _, ...rest }) => rest);
+    }
   }
 
-  onExecute(): void {
+  async onExecute(): Promise<void> {
     if (!this.isValid()) return;
+
+    const errors = await this.templateService.validateVariables(this.template().id, this.values());
+    if (errors.length > 0) {
+      this.errors.set(Object.fromEntries(errors.map((e) => [e.name, e.message])));
+      return;
+    }
     this.execute.emit(this.previewCommand());
   }
 
@@ -76,6 +76,10 @@ describe('AiSettingsState', () => {
 
     mockAiService.settings.set({ apiKey: '' });
     expect(state.hasApiKey()).toBe(false);
+
+    // Loaded settings only say whether a key is saved
+    mockAiService.settings.set({ hasApiKey: true });
+    expect(state.hasApiKey()).toBe(true);
   });
 
   it('should compute status message - loading', () => {
@@ -133,7 +137,10 @@ describe('AiSettingsState', () => {
       memoryEnabled: true,
       summaryModel: undefined,
       summaryMaxTokens: 100,
+      summaryMinInputLength: undefined,
       apiVersion: undefined,
+      maxRetries: undefined,
+      requestTimeoutSecs: undefined,
     });
   });
 
@@ -151,7 +158,7 @@ describe('AiSettingsState', () => {
     const result = await state.testConnectionWithSettings('openai', 'sk-123', 'https://api.openai.com');
 
     expect(result).toBe(true);
-    expect(mockAiService.testConnectionWithSettings).toHaveBeenCalledWith('openai', 'sk-123', 'https://api.openai.com', undefined);
+    expect(mockAiService.testConnectionWithSettings).toHaveBeenCalledWith('openai', 'sk-123', 'https://api.openai.com', undefined, undefined);
   });
 
   it('should load models for provider', async () => {
@@ -15,6 +15,7 @@ export class AiSettingsState {
   readonly error = this.aiService.error;
   readonly isConnected = this.aiService.isConnected;
   readonly isConfigured = this.aiService.isConfigured;
+  readonly lastTestUsage = this.aiService.lastTestUsage;
 
   // Derived state
   readonly providerName = computed(() => {
@@ -37,7 +38,7 @@ export class AiSettingsState {
 
   readonly hasApiKey = computed(() => {
     const settings = this.settings();
-    return !!settings?.apiKey;
+    return !!settings?.hasApiKey || !!settings?.apiKey;
   });
 
   readonly statusMessage = computed(() => {
@@ -72,7 +73,8 @@ export class AiSettingsState {
     memoryEnabled: boolean = true,
     summaryModel?: string,
     summaryMaxTokens: number = 100,
-    apiVersion?: string
+    apiVersion?: string,
+    summaryMinInputLength?: number
   ): Promise<void> {
     await this.aiService.updateSettings({
       provider: provider as 'ollama' | 'openai' | 'anthropic',
@@ -84,7 +86,11 @@ export class AiSettingsState {
       memoryEnabled,
       summaryModel,
       summaryMaxTokens,
+      summaryMinInputLength,
       apiVersion,
+      // Not editable in the settings UI; keep whatever is stored
+      maxRetries: this.settings()?.maxRetries,
+      requestTimeoutSecs: this.settings()?.requestTimeoutSecs,
     });
   }
 
@@ -102,13 +108,15 @@ export class AiSettingsState {
     provider: string,
     apiKey?: string,
     endpointUrl?: string,
-    apiVersion?: string
+    apiVersion?: string,
+    modelName?: string
   ): Promise<boolean> {
     return this.aiService.testConnectionWithSettings(
       provider as 'ollama' | 'openai' | 'anthropic',
       apiKey,
       endpointUrl,
-      apiVersion
+      apiVersion,
+      modelName
     );
   }
 
@@ -18,7 +18,8 @@ describe('AppState', () => {
       stats: signal({ total: 0, connected: 0, disconnected: 0 }),
       connectedSystems: signal([]),
       loadSystems: vi.fn().mockResolvedValue(undefined),
-      connectSystem: vi.fn().mockResolvedValue(true),
+      connectSystems: vi.fn().mockImplementation(async (ids: string[]) => ids),
+      startHealthPolling: vi.fn().mockResolvedValue(undefined),
       detectRuntimes: vi.fn().mockResolvedValue(undefined),
       clearError: vi.fn(),
     };
@@ -142,6 +143,11 @@ describe('AppState', () => {
     expect(stats).toHaveProperty('networks');
   });
 
+  it('should start connection health polling on initialize', async () => {
+    await appState.initialize();
+    expect(mockSystemState.startHealthPolling).toHaveBeenCalled();
+  });
+
   it('should load systems on initialize', async () => {
     await appState.initialize();
     expect(mockSystemState.loadSystems).toHaveBeenCalled();
@@ -156,8 +162,21 @@ describe('AppState', () => {
 
     await appState.initialize();
 
-    expect(mockSystemState.connectSystem).toHaveBeenCalledWith('sys-1');
-    expect(mockSystemState.connectSystem).not.toHaveBeenCalledWith('sys-2');
+    expect(mockSystemState.connectSystems).toHaveBeenCalledWith(['sys-1']);
+  });
+
+  it('should only load data for systems that connected', async () => {
+    mockSystemState.systems.set([
+      { id: 'sys-1', autoConnect: true },
+      { id: 'sys-2', autoConnect: true },
+    ]);
+    mockSystemState.connectSystems.mockResolvedValue(['sys-2']);
+
+    await appState.initialize();
+
+    expect(mockContainerState.loadContainers).toHaveBeenCalledWith('sys-2');
+    expect(mockContainerState.loadContainers).not.toHaveBeenCalledWith('sys-1');
+    expect(mockSystemState.detectRuntimes).toHaveBeenCalledWith('sys-2');
   });
 
   it('should load data for system after auto-connect', async () => {
@@ -46,15 +46,13 @@ export class AppState {
   async initialize(): Promise<void> {
     await this.system.loadSystems();
 
-    // Auto-connect systems that have autoConnect enabled (in parallel)
+    // Auto-connect systems that have autoConnect enabled in one batch
     const systemsToAutoConnect = this.system.systems().filter((s) => s.autoConnect);
+    const connectedIds = await this.system.connectSystems(systemsToAutoConnect.map((s) => s.id));
     await Promise.all(
-      systemsToAutoConnect.map(async (system) => {
-        const success = await this.system.connectSystem(system.id);
-        if (success) {
-          await this.loadAllDataForSystem(system.id);
-          await this.system.detectRuntimes(system.id);
-        }
+      connectedIds.map(async (systemId) => {
+        await this.loadAllDataForSystem(systemId);
+        await this.system.detectRuntimes(systemId);
       })
     );
 
@@ -63,6 +61,8 @@ export class AppState {
     if (connectedSystems.length > 0) {
       await this.loadAllDataForSystems(connectedSystems.map((s) => s.id));
     }
+
+    await this.system.startHealthPolling();
   }
 
   async loadAllDataForSystems(systemIds: string[]): Promise<void> {
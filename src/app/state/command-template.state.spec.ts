@@ -19,6 +19,7 @@ describe('CommandTemplateState', () => {
     isBuiltIn: true,
     createdAt: '2024-01-01T00:00:00Z',
     updatedAt: '2024-01-01T00:00:00Z',
+    useCount: 0,
     ...overrides,
   } as any);
 
@@ -31,6 +32,9 @@ describe('CommandTemplateState', () => {
       deleteTemplate: vi.fn(),
       toggleFavorite: vi.fn(),
       duplicateTemplate: vi.fn(),
+      exportTemplates: vi.fn(),
+      importTemplates: vi.fn(),
+      recordUse: vi.fn(),
     };
     state = new CommandTemplateState(mockService);
   });
@@ -120,6 +124,26 @@ describe('CommandTemplateState', () => {
     expect(state.templates()).toContainEqual(duplicate);
   });
 
+  it('should import templates and reload the list', async () => {
+    const summary = { imported: 1, overwritten: 0, skipped: [] };
+    const imported = makeTemplate({ id: 'tpl-imp', name: 'Deploy', isBuiltIn: false });
+    mockService.importTemplates.mockResolvedValue(summary);
+    mockService.listTemplates.mockResolvedValue([makeTemplate(), imported]);
+
+    const result = await state.importTemplates('{"version":1}', true);
+    expect(result).toEqual(summary);
+    expect(mockService.importTemplates).toHaveBeenCalledWith('{"version":1}', true);
+    expect(state.templates()).toContainEqual(imported);
+  });
+
+  it('should surface import errors', async () => {
+    mockService.importTemplates.mockRejectedValue(new Error('Unsupported template export version 2'));
+
+    const result = await state.importTemplates('{}', false);
+    expect(result).toBeNull();
+    expect(state.error()).toBe('Unsupported template export version 2');
+  });
+
   it('should select template', () => {
     state.selectTemplate('tpl-1');
     expect(state.selectedTemplateId()).toBe('tpl-1');
@@ -208,6 +232,32 @@ describe('CommandTemplateState', () => {
     expect(state.favorites()).toHaveLength(1);
   });
 
+  it('should sort by usage', async () => {
+    mockService.listTemplates.mockResolvedValue([
+      makeTemplate({ id: 'a', name: 'A', useCount: 1, lastUsedAt: '2026-01-03T00:00:00Z' }),
+      makeTemplate({ id: 'b', name: 'B', useCount: 7, lastUsedAt: '2026-01-01T00:00:00Z' }),
+      makeTemplate({ id: 'c', name: 'C', useCount: 0 }),
+    ]);
+    await state.loadTemplates();
+
+    state.setSortOption('mostUsed');
+    expect(state.filteredTemplates().map((t) => t.id)).toEqual(['b', 'a', 'c']);
+
+    state.setSortOption('recent');
+    expect(state.filteredTemplates().map((t) => t.id)).toEqual(['a', 'b', 'c']);
+  });
+
+  it('should record a template use', async () => {
+    mockService.listTemplates.mockResolvedValue([makeTemplate()]);
+    await state.loadTemplates();
+
+    mockService.recordUse.mockResolvedValue(makeTemplate({ useCount: 1, lastUsedAt: '2026-01-01T00:00:00Z' }));
+    await state.recordUse('tpl-1');
+
+    expect(mockService.recordUse).toHaveBeenCalledWith('tpl-1');
+    expect(state.templates()[0].useCount).toBe(1);
+  });
+
   it('should clear all filters', () => {
     state.setCategoryFilter('debugging' as any);
     state.setRuntimeFilter('docker' as any);
@@ -3,6 +3,7 @@ import {
   CommandCategory,
   CommandTemplate,
   CreateCommandTemplateRequest,
+  TemplateImportResult,
   UpdateCommandTemplateRequest,
   groupByCategory,
   isCompatibleWithRuntime,
@@ -10,8 +11,9 @@ import {
 } from '../core/models/command-template.model';
 import { ContainerRuntime } from '../core/models/container.model';
 import { CommandTemplateService } from '../core/services/command-template.service';
+import { errorMessage } from '../core/models/error.model';
 
-export type SortOption = 'name' | 'category' | 'recent';
+export type SortOption = 'name' | 'category' | 'mostUsed' | 'recent';
 
 @Injectable({ providedIn: 'root' })
 export class CommandTemplateState {
@@ -98,8 +100,11 @@ export class CommandTemplateState {
         case 'category':
           const catCompare = a.category.localeCompare(b.category);
           return catCompare !== 0 ? catCompare : a.name.localeCompare(b.name);
+        case 'mostUsed':
+          return b.useCount - a.useCount || a.name.localeCompare(b.name);
         case 'recent':
-          return new Date(b.updatedAt).getTime() - new Date(a.updatedAt).getTime();
+          // Never-used templates sort last
+          return (b.lastUsedAt ?? '').localeCompare(a.lastUsedAt ?? '') || a.name.localeCompare(b.name);
         default:
           return 0;
       }
@@ -154,7 +159,7 @@ export class CommandTemplateState {
       this._templates.set(templates);
     } catch (err) {
       this._error.set(
-        err instanceof Error ? err.message : 'Failed to load command templates'
+        errorMessage(err, 'Failed to load command templates')
       );
     } finally {
       this._loading.set(false);
@@ -174,7 +179,7 @@ export class CommandTemplateState {
       return template;
     } catch (err) {
       this._error.set(
-        err instanceof Error ? err.message : 'Failed to create command template'
+        errorMessage(err, 'Failed to create command template')
       );
       return null;
     } finally {
@@ -197,7 +202,7 @@ export class CommandTemplateState {
       return updated;
     } catch (err) {
       this._error.set(
-        err instanceof Error ? err.message : 'Failed to update command template'
+        errorMessage(err, 'Failed to update command template')
       );
       return null;
     } finally {
@@ -223,7 +228,7 @@ export class CommandTemplateState {
       return success;
     } catch (err) {
       this._error.set(
-        err instanceof Error ? err.message : 'Failed to delete command template'
+        errorMessage(err, 'Failed to delete command template')
       );
       return false;
     } finally {
@@ -245,7 +250,7 @@ export class CommandTemplateState {
       return true;
     } catch (err) {
       this._error.set(
-        err instanceof Error ? err.message : 'Failed to toggle favorite'
+        errorMessage(err, 'Failed to toggle favorite')
       );
       return false;
     }
@@ -264,7 +269,7 @@ export class CommandTemplateState {
       return duplicate;
     } catch (err) {
       this._error.set(
-        err instanceof Error ? err.message : 'Failed to duplicate command template'
+        errorMessage(err, 'Failed to duplicate command template')
       );
       return null;
     } finally {
@@ -272,6 +277,57 @@ export class CommandTemplateState {
     }
   }
 
+  /**
+   * Export custom templates as a JSON document
+   */
+  async exportTemplates(ids?: string[]): Promise<string | null> {
+    this._error.set(null);
+
+    try {
+      return await this.templateService.exportTemplates(ids);
+    } catch (err) {
+      this._error.set(
+        errorMessage(err, 'Failed to export command templates')
+      );
+      return null;
+    }
+  }
+
+  /**
+   * Import templates from a JSON document and reload the list
+   */
+  async importTemplates(json: string, overwrite: boolean): Promise<TemplateImportResult | null> {
+    this._loading.set(true);
+    this._error.set(null);
+
+    try {
+      const result = await this.templateService.importTemplates(json, overwrite);
+      this._templates.set(await this.templateService.listTemplates());
+      return result;
+    } catch (err) {
+      this._error.set(
+        errorMessage(err, 'Failed to import command templates')
+      );
+      return null;
+    } finally {
+      this._loading.set(false);
+    }
+  }
+
+  /**
+   * Count an execution of a template so usage-based sorting stays current
+   */
+  async recordUse(id: string): Promise<void> {
+    try {
+      const updated = await this.templateService.recordUse(id);
+      this._templates.update((templates) =>
+        templates.map((t) => (t.id === updated.id ? updated : t))
+      );
+    } catch (err) {
+      console.warn('Failed to record template use:', err);
+    }
+  }
+
   // Selection
   selectTemplate(id: string | null): void {
     this._selectedTemplateId.set(id);
@@ -6,6 +6,7 @@ import {
   ContainerStatus,
 } from '../core/models/container.model';
 import { ContainerService } from '../core/services/container.service';
+import { errorMessage } from '../core/models/error.model';
 
 export type SortOption = 'name' | 'status' | 'created';
 
@@ -120,7 +121,7 @@ export class ContainerState {
         return [...filtered, ...containers];
       });
     } catch (err) {
-      this._error.set(err instanceof Error ? err.message : 'Failed to load containers');
+      this._error.set(errorMessage(err, 'Failed to load containers'));
     } finally {
       this._loading.update((l) => ({ ...l, [systemId]: false }));
     }
@@ -155,7 +156,7 @@ export class ContainerState {
 
       return true;
     } catch (err) {
-      this._error.set(err instanceof Error ? err.message : `Failed to ${action} container`);
+      this._error.set(errorMessage(err, `Failed to ${action} container`));
       return false;
     } finally {
       this._loading.update((l) => ({ ...l, [container.id]: false }));
@@ -176,7 +177,7 @@ export class ContainerState {
         timestamps
       );
     } catch (err) {
-      this._error.set(err instanceof Error ? err.message : 'Failed to get logs');
+      this._error.set(errorMessage(err, 'Failed to get logs'));
       return null;
     }
   }
@@ -30,6 +30,14 @@ describe('FileBrowserState', () => {
       renamePath: vi.fn(),
       downloadFile: vi.fn(),
       uploadFile: vi.fn(),
+      getDirectorySize: vi.fn(),
+      getDirectoryTreeSizes: vi.fn(),
+      searchFileContents: vi.fn(),
+      changePermissions: vi.fn(),
+      changeOwner: vi.fn(),
+      readFileRange: vi.fn(),
+      createArchive: vi.fn(),
+      extractArchive: vi.fn(),
     };
     state = new FileBrowserState(mockService);
   });
@@ -198,6 +206,22 @@ describe('FileBrowserState', () => {
     expect(state.editorDirty()).toBe(false);
   });
 
+  it('should page large files instead of reading them whole', async () => {
+    state.setContext('sys-1');
+    const chunk = { path: '/var/log/big.log', offset: 0, bytesRead: 262144, fileSize: 5e9, content: 'log', isBinary: false };
+    mockService.readFileRange.mockResolvedValue(chunk);
+
+    await state.openFile(makeEntry({ path: '/var/log/big.log', size: 5e9 }));
+
+    expect(mockService.readFile).not.toHaveBeenCalled();
+    expect(mockService.readFileRange).toHaveBeenCalledWith('sys-1', '/var/log/big.log', 0, 256 * 1024, null, null);
+    expect(state.fileChunk()).toEqual(chunk);
+    expect(state.editorContent()).toBeNull();
+
+    state.closeEditor();
+    expect(state.fileChunk()).toBeNull();
+  });
+
   it('should save a file', async () => {
     state.setContext('sys-1');
     mockService.readFile.mockResolvedValue({ path: '/test.txt', content: 'Old', size: 3, isBinary: false });
@@ -270,6 +294,70 @@ describe('FileBrowserState', () => {
     expect(mockService.renamePath).toHaveBeenCalledWith('sys-1', '/home/old.txt', '/home/new.txt', null, null);
   });
 
+  it('should change permissions and update the entry in place', async () => {
+    state.setContext('sys-1');
+    const entry = makeEntry({ path: '/srv/app', permissions: 'rw-r--r--' });
+    mockService.listDirectory.mockResolvedValue({ path: '/srv', entries: [entry], parentPath: '/' });
+    await state.navigateTo('/srv');
+
+    mockService.changePermissions.mockResolvedValue({ ...entry, permissions: 'rwxr-xr-x' });
+    const result = await state.changePermissions(entry, '755', true);
+
+    expect(result).toBe(true);
+    expect(mockService.changePermissions).toHaveBeenCalledWith('sys-1', '/srv/app', '755', true, null, null);
+    expect(state.listing()!.entries[0].permissions).toBe('rwxr-xr-x');
+    expect(mockService.listDirectory).toHaveBeenCalledTimes(1);
+  });
+
+  it('should surface errors when changing owner fails', async () => {
+    state.setContext('sys-1');
+    mockService.changeOwner.mockRejectedValue('Permission denied: /srv/app');
+
+    const result = await state.changeOwner(makeEntry({ path: '/srv/app' }), 'www-data', '');
+
+    expect(result).toBe(false);
+    expect(mockService.changeOwner).toHaveBeenCalledWith('sys-1', '/srv/app', 'www-data', undefined, false, null, null);
+    expect(state.error()).toBeTruthy();
+  });
+
+  it('should create an archive next to the entry and refresh', async () => {
+    state.setContext('sys-1');
+    mockService.listDirectory.mockResolvedValue({ path: '/srv', entries: [], parentPath: '/' });
+    await state.navigateTo('/srv');
+
+    mockService.createArchive.mockResolvedValue({ path: '/srv/app.zip' });
+    const result = await state.createArchive(makeEntry({ path: '/srv/app', fileType: 'directory' }), 'zip');
+
+    expect(result).toBe(true);
+    expect(mockService.createArchive).toHaveBeenCalledWith('sys-1', ['/srv/app'], '/srv/app.zip', 'zip', null, null);
+    expect(mockService.listDirectory).toHaveBeenCalledTimes(2);
+  });
+
+  it('should extract an archive into a folder named after it', async () => {
+    state.setContext('sys-1');
+    mockService.extractArchive.mockResolvedValue({ path: '/srv/app' });
+    mockService.listDirectory.mockResolvedValue({ path: '/srv', entries: [], parentPath: '/' });
+
+    const result = await state.extractArchive(makeEntry({ name: 'app.tar.gz', path: '/srv/app.tar.gz' }));
+
+    expect(result).toBe(true);
+    expect(mockService.extractArchive).toHaveBeenCalledWith('sys-1', '/srv/app.tar.gz', '/srv/app', null, null);
+  });
+
+  it('should surface a missing archive tool error', async () => {
+    state.setContext('sys-1');
+    mockService.extractArchive.mockRejectedValue({
+      kind: 'unsupported',
+      message: "Unsupported operation: 'unzip' is not installed on this host",
+      detail: null,
+    });
+
+    const result = await state.extractArchive(makeEntry({ name: 'app.zip', path: '/srv/app.zip' }));
+
+    expect(result).toBe(false);
+    expect(state.error()).toBe("Unsupported operation: 'unzip' is not installed on this host");
+  });
+
   it('should navigate back and forward in history', async () => {
     state.setContext('sys-1');
     mockService.listDirectory.mockResolvedValue({ path: '/', entries: [], parentPath: null });
@@ -314,8 +402,12 @@ describe('FileBrowserState', () => {
     await state.navigateTo('/test');
     expect(state.error()).toBe('string error');
 
-    // Object with stderr
-    mockService.listDirectory.mockRejectedValue({ CommandFailed: { stderr: 'cmd failed' } });
+    // Failed command shows its stderr
+    mockService.listDirectory.mockRejectedValue({
+      kind: 'commandFailed',
+      message: 'Command execution failed: ls (exit code: 2)\nStderr: cmd failed',
+      detail: 'cmd failed',
+    });
     await state.navigateTo('/test2');
     expect(state.error()).toBe('cmd failed');
   });
@@ -338,4 +430,91 @@ describe('FileBrowserState', () => {
     state.clearError();
     expect(state.error()).toBeNull();
   });
+
+  it('should calculate a single directory size', async () => {
+    state.setContext('sys-1');
+    mockService.getDirectorySize.mockResolvedValue({ path: '/var', size: 8192, partial: true });
+
+    await state.calculateDirectorySize(makeEntry({ path: '/var', fileType: 'directory' }));
+
+    expect(mockService.getDirectorySize).toHaveBeenCalledWith('sys-1', '/var', null, null);
+    expect(state.directorySizes().get('/var')).toEqual({ path: '/var', size: 8192, partial: true });
+    expect(state.sizingPaths().size).toBe(0);
+  });
+
+  it('should calculate child sizes and sort directories by them', async () => {
+    state.setContext('sys-1');
+    mockService.listDirectory.mockResolvedValue({
+      path: '/srv',
+      entries: [
+        makeEntry({ name: 'small', path: '/srv/small', fileType: 'directory', size: 4096 }),
+        makeEntry({ name: 'big', path: '/srv/big', fileType: 'directory', size: 4096 }),
+      ],
+    });
+    await state.navigateTo('/srv');
+    mockService.getDirectoryTreeSizes.mockResolvedValue({
+      path: '/srv',
+      total: 1_000_000,
+      children: [
+        { path: '/srv/big', size: 900_000 },
+        { path: '/srv/small', size: 100_000 },
+      ],
+      partial: false,
+    });
+
+    await state.calculateChildSizes();
+    state.setSortOption('size');
+    state.setSortOption('size'); // descending
+
+    expect(mockService.getDirectoryTreeSizes).toHaveBeenCalledWith('sys-1', '/srv', 1, null, null);
+    expect(state.directorySizes().get('/srv')?.size).toBe(1_000_000);
+    expect(state.visibleEntries().map((e) => e.name)).toEqual(['big', 'small']);
+  });
+
+  it('should surface size calculation errors', async () => {
+    state.setContext('sys-1');
+    mockService.getDirectorySize.mockRejectedValue({
+      kind: 'permissionDenied',
+      message: 'Permission denied: /root',
+      detail: null,
+    });
+
+    await state.calculateDirectorySize(makeEntry({ path: '/root', fileType: 'directory' }));
+
+    expect(state.error()).toBe('Permission denied: /root');
+  });
+
+  it('should search contents of the current directory', async () => {
+    state.setContext('sys-1');
+    mockService.listDirectory.mockResolvedValue({ path: '/srv', entries: [] });
+    await state.navigateTo('/srv');
+    const results = {
+      hits: [{ file: '/srv/app.log', lineNumber: 3, line: 'ERROR: boom' }],
+      truncated: false,
+    };
+    mockService.searchFileContents.mockResolvedValue(results);
+
+    await state.searchContents('ERROR', { fixedString: true });
+
+    expect(mockService.searchFileContents).toHaveBeenCalledWith(
+      'sys-1', '/srv', 'ERROR', { fixedString: true }, null, null,
+    );
+    expect(state.contentResults()).toEqual(results);
+    expect(state.contentSearching()).toBe(false);
+
+    // Navigating elsewhere drops the results
+    await state.navigateTo('/etc');
+    expect(state.contentResults()).toBeNull();
+  });
+
+  it('should clear content results for an empty query', async () => {
+    state.setContext('sys-1');
+    mockService.searchFileContents.mockResolvedValue({ hits: [], truncated: false });
+    await state.searchContents('x');
+    expect(state.contentResults()).not.toBeNull();
+
+    await state.searchContents('');
+    expect(state.contentResults()).toBeNull();
+    expect(mockService.searchFileContents).toHaveBeenCalledTimes(1);
+  });
 });
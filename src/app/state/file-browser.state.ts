@@ -1,14 +1,23 @@
 import { computed, Injectable, signal } from '@angular/core';
 import { ContainerRuntime } from '../core/models/container.model';
 import {
+  ArchiveFormat,
   Breadcrumb,
   DirectoryListing,
+  DirectorySize,
+  FileChunk,
   FileContent,
   FileEntry,
   FileSortOption,
+  SearchOptions,
+  SearchResults,
   SortDirection,
+  FILE_PAGE_SIZE,
+  MAX_EDITABLE_FILE_SIZE,
+  archiveBaseName,
 } from '../core/models/file-browser.model';
 import { FileBrowserService } from '../core/services/file-browser.service';
+import { errorMessage } from '../core/models/error.model';
 
 @Injectable({ providedIn: 'root' })
 export class FileBrowserState {
@@ -23,6 +32,7 @@ export class FileBrowserState {
   private _editorContent = signal<FileContent | null>(null);
   private _editorDirty = signal(false);
   private _editorLoading = signal(false);
+  private _fileChunk = signal<FileChunk | null>(null);
 
   // Navigation history
   private _history = signal<string[]>([]);
@@ -39,6 +49,14 @@ export class FileBrowserState {
   private _containerId = signal<string | null>(null);
   private _runtime = signal<ContainerRuntime | null>(null);
 
+  // Computed directory sizes (du), keyed by path
+  private _directorySizes = signal<Map<string, DirectorySize>>(new Map());
+  private _sizingPaths = signal<Set<string>>(new Set());
+
+  // Content search (grep) results for the current directory; null when not searching
+  private _contentResults = signal<SearchResults | null>(null);
+  private _contentSearching = signal(false);
+
   // Public readonly
   readonly listing = this._listing.asReadonly();
   readonly currentPath = this._currentPath.asReadonly();
@@ -48,6 +66,7 @@ export class FileBrowserState {
   readonly editorContent = this._editorContent.asReadonly();
   readonly editorDirty = this._editorDirty.asReadonly();
   readonly editorLoading = this._editorLoading.asReadonly();
+  readonly fileChunk = this._fileChunk.asReadonly();
   readonly showHiddenFiles = this._showHiddenFiles.asReadonly();
   readonly sortOption = this._sortOption.asReadonly();
   readonly sortDirection = this._sortDirection.asReadonly();
@@ -55,6 +74,10 @@ export class FileBrowserState {
   readonly systemId = this._systemId.asReadonly();
   readonly containerId = this._containerId.asReadonly();
   readonly runtime = this._runtime.asReadonly();
+  readonly directorySizes = this._directorySizes.asReadonly();
+  readonly sizingPaths = this._sizingPaths.asReadonly();
+  readonly contentResults = this._contentResults.asReadonly();
+  readonly contentSearching = this._contentSearching.asReadonly();
 
   // Computed: breadcrumbs
   readonly breadcrumbs = computed<Breadcrumb[]>(() => {
@@ -86,6 +109,8 @@ export class FileBrowserState {
     }
 
     const dir = this._sortDirection() === 'asc' ? 1 : -1;
+    const sizes = this._directorySizes();
+    const sizeOf = (e: FileEntry) => sizes.get(e.path)?.size ?? e.size;
     entries.sort((a, b) => {
       // Directories always first
       if (a.fileType === 'directory' && b.fileType !== 'directory') return -1;
@@ -95,7 +120,7 @@ export class FileBrowserState {
         case 'name':
           return dir * a.name.localeCompare(b.name);
         case 'size':
-          return dir * (a.size - b.size);
+          return dir * (sizeOf(a) - sizeOf(b));
         case 'modified':
           return dir * a.modified.localeCompare(b.modified);
         case 'type': {
@@ -118,23 +143,18 @@ export class FileBrowserState {
   constructor(private service: FileBrowserService) {}
 
   /** Extract a readable error message from Tauri serialized errors or standard Errors */
-  private extractError(err: unknown, fallback: string): string {
-    if (err instanceof Error) return err.message;
-    if (typeof err === 'string') return err;
-    if (typeof err === 'object' && err !== null) {
-      // Tauri serialized enum: { "VariantName": "message" } or { "VariantName": { command, stderr, ... } }
-      const keys = Object.keys(err);
-      if (keys.length === 1) {
-        const value = (err as Record<string, unknown>)[keys[0]];
-        if (typeof value === 'string') return value;
-        if (typeof value === 'object' && value !== null) {
-          const obj = value as Record<string, unknown>;
-          if (typeof obj['stderr'] === 'string' && obj['stderr']) return obj['stderr'];
-          if (typeof obj['message'] === 'string' && obj['message']) return obj['message'];
-        }
-      }
+  /** Swap an updated entry into the current listing without reloading it */
+  private replaceEntry(updated: FileEntry): void {
+    const listing = this._listing();
+    if (listing) {
+      this._listing.set({
+        ...listing,
+        entries: listing.entries.map((e) => (e.path === updated.path ? updated : e)),
+      });
+    }
+    if (this._selectedEntry()?.path === updated.path) {
+      this._selectedEntry.set(updated);
     }
-    return fallback;
   }
 
   /** Set the target system (and optionally container) */
@@ -150,6 +170,8 @@ export class FileBrowserState {
     this._selectedEntry.set(null);
     this._currentPath.set('/');
     this._searchQuery.set('');
+    this._directorySizes.set(new Map());
+    this._contentResults.set(null);
   }
 
   /** Navigate to a directory path */
@@ -157,6 +179,7 @@ export class FileBrowserState {
     this._loading.set(true);
     this._error.set(null);
     this._selectedEntry.set(null);
+    this._contentResults.set(null);
 
     try {
       const listing = await this.service.listDirectory(
@@ -175,7 +198,7 @@ export class FileBrowserState {
       this._history.set(newHistory);
       this._historyIndex.set(newHistory.length - 1);
     } catch (err: any) {
-      this._error.set(this.extractError(err, 'Operation failed'));
+      this._error.set(errorMessage(err, 'Operation failed'));
     } finally {
       this._loading.set(false);
     }
@@ -204,7 +227,11 @@ export class FileBrowserState {
   }
 
   /** Open a file for viewing/editing */
-  async openFile(entry: FileEntry): Promise<void> {
+  async openFile(entry: Pick<FileEntry, 'path'> & Partial<Pick<FileEntry, 'size'>>): Promise<void> {
+    if ((entry.size ?? 0) > MAX_EDITABLE_FILE_SIZE) {
+      return this.loadFileChunk(entry.path, 0);
+    }
+
     this._editorLoading.set(true);
     this._error.set(null);
 
@@ -218,7 +245,7 @@ export class FileBrowserState {
       this._editorContent.set(content);
       this._editorDirty.set(false);
     } catch (err: any) {
-      this._error.set(this.extractError(err, 'Operation failed'));
+      this._error.set(errorMessage(err, 'Operation failed'));
     } finally {
       this._editorLoading.set(false);
     }
@@ -242,15 +269,34 @@ export class FileBrowserState {
       this._editorDirty.set(false);
       return true;
     } catch (err: any) {
-      this._error.set(this.extractError(err, 'Operation failed'));
+      this._error.set(errorMessage(err, 'Operation failed'));
       return false;
     } finally {
       this._editorLoading.set(false);
     }
   }
 
+  /** Load one read-only page of a file too large to edit */
+  async loadFileChunk(path: string, offset: number): Promise<void> {
+    this._editorLoading.set(true);
+    this._error.set(null);
+
+    try {
+      const chunk = await this.service.readFileRange(
+        this._systemId()!, path, Math.max(0, offset), FILE_PAGE_SIZE,
+        this._containerId(), this._runtime(),
+      );
+      this._fileChunk.set(chunk);
+    } catch (err: any) {
+      this._error.set(errorMessage(err, 'Operation failed'));
+    } finally {
+      this._editorLoading.set(false);
+    }
+  }
+
   closeEditor(): void {
     this._editorContent.set(null);
+    this._fileChunk.set(null);
     this._editorDirty.set(false);
   }
 
@@ -266,7 +312,7 @@ export class FileBrowserState {
       await this.refresh();
       return true;
     } catch (err: any) {
-      this._error.set(this.extractError(err, 'Operation failed'));
+      this._error.set(errorMessage(err, 'Operation failed'));
       return false;
     }
   }
@@ -281,7 +327,7 @@ export class FileBrowserState {
       await this.refresh();
       return true;
     } catch (err: any) {
-      this._error.set(this.extractError(err, 'Operation failed'));
+      this._error.set(errorMessage(err, 'Operation failed'));
       return false;
     }
   }
@@ -299,7 +345,74 @@ export class FileBrowserState {
       await this.refresh();
       return true;
     } catch (err: any) {
-      this._error.set(this.extractError(err, 'Operation failed'));
+      this._error.set(errorMessage(err, 'Operation failed'));
+      return false;
+    }
+  }
+
+  /** Change the octal mode of a file or directory */
+  async changePermissions(entry: FileEntry, mode: string, recursive = false): Promise<boolean> {
+    try {
+      const updated = await this.service.changePermissions(
+        this._systemId()!, entry.path, mode, recursive,
+        this._containerId(), this._runtime(),
+      );
+      this.replaceEntry(updated);
+      return true;
+    } catch (err: any) {
+      this._error.set(errorMessage(err, 'Operation failed'));
+      return false;
+    }
+  }
+
+  /** Change the owner (and optionally group) of a file or directory */
+  async changeOwner(entry: FileEntry, owner: string, group?: string, recursive = false): Promise<boolean> {
+    try {
+      const updated = await this.service.changeOwner(
+        this._systemId()!, entry.path, owner, group || undefined, recursive,
+        this._containerId(), this._runtime(),
+      );
+      this.replaceEntry(updated);
+      return true;
+    } catch (err: any) {
+      this._error.set(errorMessage(err, 'Operation failed'));
+      return false;
+    }
+  }
+
+  /** Compress an entry into `<name>.<format>` next to it */
+  async createArchive(entry: FileEntry, format: ArchiveFormat): Promise<boolean> {
+    const dest = `${entry.path.replace(/\/+$/, '')}.${format}`;
+
+    try {
+      await this.service.createArchive(
+        this._systemId()!, [entry.path], dest, format,
+        this._containerId(), this._runtime(),
+      );
+      await this.refresh();
+      return true;
+    } catch (err: any) {
+      this._error.set(errorMessage(err, 'Operation failed'));
+      return false;
+    }
+  }
+
+  /** Extract an archive into a folder named after it, next to the archive */
+  async extractArchive(entry: FileEntry): Promise<boolean> {
+    const baseName = archiveBaseName(entry.name);
+    if (!baseName) return false;
+    const parent = entry.path.substring(0, entry.path.lastIndexOf('/'));
+    const dest = `${parent}/${baseName}`;
+
+    try {
+      await this.service.extractArchive(
+        this._systemId()!, entry.path, dest,
+        this._containerId(), this._runtime(),
+      );
+      await this.refresh();
+      return true;
+    } catch (err: any) {
+      this._error.set(errorMessage(err, 'Operation failed'));
       return false;
     }
   }
@@ -313,7 +426,7 @@ export class FileBrowserState {
       );
       return true;
     } catch (err: any) {
-      this._error.set(this.extractError(err, 'Operation failed'));
+      this._error.set(errorMessage(err, 'Operation failed'));
       return false;
     }
   }
@@ -331,11 +444,88 @@ export class FileBrowserState {
       await this.refresh();
       return true;
     } catch (err: any) {
-      this._error.set(this.extractError(err, 'Operation failed'));
+      this._error.set(errorMessage(err, 'Operation failed'));
       return false;
     }
   }
 
+  /** Search the contents of files below the current directory */
+  async searchContents(query: string, options?: SearchOptions): Promise<void> {
+    if (!query) {
+      this.clearContentSearch();
+      return;
+    }
+
+    this._contentSearching.set(true);
+    this._error.set(null);
+    try {
+      const results = await this.service.searchFileContents(
+        this._systemId()!, this._currentPath(), query, options,
+        this._containerId(), this._runtime(),
+      );
+      this._contentResults.set(results);
+    } catch (err: any) {
+      this._error.set(errorMessage(err, 'Search failed'));
+    } finally {
+      this._contentSearching.set(false);
+    }
+  }
+
+  clearContentSearch(): void {
+    this._contentResults.set(null);
+  }
+
+  /** Compute the total size of one directory */
+  async calculateDirectorySize(entry: FileEntry): Promise<void> {
+    this.setSizing(entry.path, true);
+    try {
+      const size = await this.service.getDirectorySize(
+        this._systemId()!, entry.path, this._containerId(), this._runtime(),
+      );
+      this.storeSizes([size]);
+    } catch (err: any) {
+      this._error.set(errorMessage(err, 'Failed to compute size'));
+    } finally {
+      this.setSizing(entry.path, false);
+    }
+  }
+
+  /** Compute the size of every directory in the current directory in a single pass */
+  async calculateChildSizes(): Promise<void> {
+    const path = this._currentPath();
+    this.setSizing(path, true);
+    try {
+      const tree = await this.service.getDirectoryTreeSizes(
+        this._systemId()!, path, 1, this._containerId(), this._runtime(),
+      );
+      this.storeSizes([
+        { path: tree.path, size: tree.total, partial: tree.partial },
+        ...tree.children.map((c) => ({ ...c, partial: tree.partial })),
+      ]);
+    } catch (err: any) {
+      this._error.set(errorMessage(err, 'Failed to compute sizes'));
+    } finally {
+      this.setSizing(path, false);
+    }
+  }
+
+  private storeSizes(sizes: DirectorySize[]): void {
+    this._directorySizes.update((map) => {
+      const next = new Map(map);
+      for (const size of sizes) next.set(size.path, size);
+      return next;
+    });
+  }
+
+  private setSizing(path: string, sizing: boolean): void {
+    this._sizingPaths.update((paths) => {
+      const next = new Set(paths);
+      if (sizing) next.add(path);
+      else next.delete(path);
+      return next;
+    });
+  }
+
   /** Refresh the current directory listing */
   async refresh(): Promise<void> {
     await this.loadDirectory(this._currentPath());
@@ -375,6 +565,9 @@ export class FileBrowserState {
   private async loadDirectory(path: string): Promise<void> {
     this._loading.set(true);
     this._error.set(null);
+    if (path !== this._currentPath()) {
+      this._contentResults.set(null);
+    }
     try {
       const listing = await this.service.listDirectory(
         this._systemId()!, path, this._containerId(), this._runtime(),
@@ -382,7 +575,7 @@ export class FileBrowserState {
       this._listing.set(listing);
       this._currentPath.set(path);
     } catch (err: any) {
-      this._error.set(this.extractError(err, 'Operation failed'));
+      this._error.set(errorMessage(err, 'Operation failed'));
     } finally {
       this._loading.set(false);
     }
@@ -25,6 +25,9 @@ describe('ImageState', () => {
     mockImageService = {
       listImages: vi.fn(),
       pullImage: vi.fn(),
+      onPullFinished: vi.fn(),
+      onPullProgress: vi.fn().mockResolvedValue(vi.fn()),
+      cancelPull: vi.fn(),
       removeImage: vi.fn(),
     };
     mockContainerState = {
@@ -84,17 +87,39 @@ describe('ImageState', () => {
     expect(state.images()).toHaveLength(2);
   });
 
+  /** Make the mocked backend report the pull's outcome as soon as it starts */
+  const finishPullWith = (outcome: { cancelled?: boolean; error?: string | null }) => {
+    let handler: (event: any) => void = () => {};
+    mockImageService.onPullFinished.mockImplementation(async (h: (event: any) => void) => {
+      handler = h;
+      return vi.fn();
+    });
+    mockImageService.pullImage.mockImplementation(async () => {
+      handler({
+        executionId: 'exec-1',
+        systemId: 'sys-1',
+        image: 'nginx:latest',
+        cancelled: false,
+        error: null,
+        ...outcome,
+      });
+      return 'exec-1';
+    });
+  };
+
   it('should pull an image', async () => {
-    mockImageService.pullImage.mockResolvedValue(undefined);
+    finishPullWith({});
     mockImageService.listImages.mockResolvedValue([makeImage()]);
 
     const result = await state.pullImage('sys-1', 'nginx', 'latest', 'docker');
 
     expect(result).toBe(true);
     expect(mockImageService.pullImage).toHaveBeenCalledWith('sys-1', 'nginx', 'latest', 'docker');
+    expect(mockImageService.listImages).toHaveBeenCalledWith('sys-1');
   });
 
   it('should handle pull error', async () => {
+    mockImageService.onPullFinished.mockResolvedValue(vi.fn());
     mockImageService.pullImage.mockRejectedValue(new Error('Pull failed'));
 
     const result = await state.pullImage('sys-1', 'nginx', 'latest', 'docker');
@@ -103,6 +128,72 @@ describe('ImageState', () => {
     expect(state.error()).toBe('Pull failed');
   });
 
+  it('should report a pull that failed in the background', async () => {
+    finishPullWith({ error: 'manifest unknown' });
+
+    const result = await state.pullImage('sys-1', 'nginx', 'latest', 'docker');
+
+    expect(result).toBe(false);
+    expect(state.error()).toBe('manifest unknown');
+  });
+
+  it('should treat a cancelled pull as no error', async () => {
+    finishPullWith({ cancelled: true, error: 'Command cancelled: docker pull nginx:latest' });
+
+    const result = await state.pullImage('sys-1', 'nginx', 'latest', 'docker');
+
+    expect(result).toBe(false);
+    expect(state.error()).toBeNull();
+    expect(mockImageService.listImages).not.toHaveBeenCalled();
+  });
+
+  it('should cancel a running pull by its execution id', async () => {
+    let handler: (event: any) => void = () => {};
+    mockImageService.onPullFinished.mockImplementation(async (h: (event: any) => void) => {
+      handler = h;
+      return vi.fn();
+    });
+    mockImageService.pullImage.mockResolvedValue('exec-1');
+    mockImageService.cancelPull.mockImplementation(async () => {
+      handler({ executionId: 'exec-1', systemId: 'sys-1', image: 'nginx:latest', cancelled: true });
+    });
+
+    const pulling = state.pullImage('sys-1', 'nginx', 'latest', 'docker');
+    await new Promise((resolve) => setTimeout(resolve, 0));
+    await state.cancelPull('sys-1', 'nginx', 'latest');
+
+    expect(mockImageService.cancelPull).toHaveBeenCalledWith('exec-1');
+    expect(await pulling).toBe(false);
+  });
+
+  it('should summarise layer progress while pulling', async () => {
+    let finished: (event: any) => void = () => {};
+    let progress: (event: any) => void = () => {};
+    mockImageService.onPullFinished.mockImplementation(async (h: (event: any) => void) => {
+      finished = h;
+      return vi.fn();
+    });
+    mockImageService.onPullProgress.mockImplementation(async (h: (event: any) => void) => {
+      progress = h;
+      return vi.fn();
+    });
+    mockImageService.pullImage.mockResolvedValue('exec-1');
+    mockImageService.listImages.mockResolvedValue([]);
+    const event = { executionId: 'exec-1', systemId: 'sys-1', image: 'nginx:latest' };
+
+    const pulling = state.pullImage('sys-1', 'nginx', 'latest', 'docker');
+    await new Promise((resolve) => setTimeout(resolve, 0));
+    progress({ ...event, layerId: 'a1', status: 'Pull complete', percent: 100 });
+    progress({ ...event, layerId: 'b2', status: 'Downloading', percent: 50 });
+    progress({ ...event, executionId: 'other', layerId: 'c3', status: 'Downloading', percent: 0 });
+
+    expect(state.pullProgress()['sys-1:nginx:latest']).toBe('Downloading (2 layers, 75%)');
+
+    finished({ ...event, cancelled: false });
+    expect(await pulling).toBe(true);
+    expect(state.pullProgress()['sys-1:nginx:latest']).toBeUndefined();
+  });
+
   it('should remove an image', async () => {
     mockImageService.listImages.mockResolvedValue([makeImage()]);
     await state.loadImages('sys-1');
@@ -1,8 +1,13 @@
 import { computed, inject, Injectable, signal } from '@angular/core';
 import { ContainerRuntime } from '../core/models/container.model';
-import { ContainerImage } from '../core/models/image.model';
+import {
+  ContainerImage,
+  ImagePullFinished,
+  ImagePullProgress,
+} from '../core/models/image.model';
 import { ImageService } from '../core/services/image.service';
 import { ContainerState } from './container.state';
+import { errorMessage } from '../core/models/error.model';
 
 export type ImageUsageFilter = 'all' | 'in-use' | 'unused' | 'dangling';
 
@@ -14,6 +19,8 @@ export class ImageState {
   private _loading = signal<Record<string, boolean>>({});
   private _error = signal<string | null>(null);
   private _pullProgress = signal<Record<string, string>>({});
+  /** Execution IDs of running pulls, keyed like _pullProgress */
+  private _pullExecutions = signal<Record<string, string>>({});
 
   private _runtimeFilter = signal<ContainerRuntime | null>(null);
   private _searchQuery = signal<string>('');
@@ -149,7 +156,7 @@ export class ImageState {
         return [...filtered, ...images];
       });
     } catch (err) {
-      this._error.set(err instanceof Error ? err.message : 'Failed to load images');
+      this._error.set(errorMessage(err, 'Failed to load images'));
     } finally {
       this._loading.update((l) => ({ ...l, [systemId]: false }));
     }
@@ -166,20 +173,73 @@ export class ImageState {
     this._pullProgress.update((p) => ({ ...p, [key]: 'Starting pull...' }));
     this._error.set(null);
 
+    // The pull can finish before its execution ID comes back, so early events are kept
+    let executionId: string | null = null;
+    const early = new Map<string, ImagePullFinished>();
+    let finish!: (event: ImagePullFinished) => void;
+    const finished = new Promise<ImagePullFinished>((resolve) => (finish = resolve));
+    const unlisten = await this.imageService.onPullFinished((event) => {
+      if (event.executionId === executionId) {
+        finish(event);
+      } else {
+        early.set(event.executionId, event);
+      }
+    });
+    const layers = new Map<string, number>();
+    const unlistenProgress = await this.imageService.onPullProgress((event) => {
+      if (event.executionId !== executionId) return;
+      this._pullProgress.update((p) => ({ ...p, [key]: this.describeProgress(event, layers) }));
+    });
+
     try {
-      await this.imageService.pullImage(systemId, name, tag, runtime);
+      executionId = await this.imageService.pullImage(systemId, name, tag, runtime);
+      this._pullExecutions.update((p) => ({ ...p, [key]: executionId! }));
+      const earlyEvent = early.get(executionId);
+      if (earlyEvent) finish(earlyEvent);
+
+      const result = await finished;
+      if (result.cancelled) return false;
+      if (result.error) throw new Error(result.error);
+
       await this.loadImages(systemId);
       return true;
     } catch (err) {
-      this._error.set(err instanceof Error ? err.message : 'Failed to pull image');
+      this._error.set(errorMessage(err, 'Failed to pull image'));
       return false;
     } finally {
+      unlisten();
+      unlistenProgress();
       this._loading.update((l) => ({ ...l, [key]: false }));
       this._pullProgress.update((p) => {
         const updated = { ...p };
         delete updated[key];
         return updated;
       });
+      this._pullExecutions.update((p) => {
+        const updated = { ...p };
+        delete updated[key];
+        return updated;
+      });
+    }
+  }
+
+  /** Summarise a pull as its latest status plus the average progress of the layers seen so far */
+  private describeProgress(event: ImagePullProgress, layers: Map<string, number>): string {
+    if (event.layerId === null) return event.status;
+    layers.set(event.layerId, event.percent ?? layers.get(event.layerId) ?? 0);
+    const total = [...layers.values()].reduce((sum, percent) => sum + percent, 0);
+    const overall = Math.round(total / layers.size);
+    return `${event.status} (${layers.size} layers, ${overall}%)`;
+  }
+
+  async cancelPull(systemId: string, name: string, tag: string): Promise<void> {
+    const executionId = this._pullExecutions()[`${systemId}:${name}:${tag}`];
+    if (!executionId) return;
+
+    try {
+      await this.imageService.cancelPull(executionId);
+    } catch (err) {
+      this._error.set(errorMessage(err, 'Failed to cancel pull'));
     }
   }
 
@@ -196,7 +256,7 @@ export class ImageState {
       this._images.update((images) => images.filter((i) => i.id !== image.id));
       return true;
     } catch (err) {
-      this._error.set(err instanceof Error ? err.message : 'Failed to remove image');
+      this._error.set(errorMessage(err, 'Failed to remove image'));
       return false;
     } finally {
       this._loading.update((l) => ({ ...l, [image.id]: false }));
@@ -3,6 +3,7 @@ import { Container, ContainerRuntime } from '../core/models/container.model';
 import { Network } from '../core/models/network.model';
 import { NetworkService } from '../core/services/network.service';
 import { ContainerState } from './container.state';
+import { errorMessage } from '../core/models/error.model';
 
 export type NetworkConnectionFilter = 'all' | 'active' | 'empty' | 'internal';
 export type NetworkDriverFilter = 'all' | 'bridge' | 'host' | 'overlay' | 'custom';
@@ -156,7 +157,7 @@ export class NetworkState {
         return [...filtered, ...networks];
       });
     } catch (err) {
-      this._error.set(err instanceof Error ? err.message : 'Failed to load networks');
+      this._error.set(errorMessage(err, 'Failed to load networks'));
     } finally {
       this._loading.update((l) => ({ ...l, [systemId]: false }));
     }
@@ -183,7 +184,7 @@ export class NetworkState {
       await this.loadNetworks(systemId);
       return true;
     } catch (err) {
-      this._error.set(err instanceof Error ? err.message : 'Failed to create network');
+      this._error.set(errorMessage(err, 'Failed to create network'));
       return false;
     } finally {
       this._loading.update((l) => ({ ...l, create: false }));
@@ -205,7 +206,7 @@ export class NetworkState {
       );
       return true;
     } catch (err) {
-      this._error.set(err instanceof Error ? err.message : 'Failed to remove network');
+      this._error.set(errorMessage(err, 'Failed to remove network'));
       return false;
     } finally {
       this._loading.update((l) => ({ ...l, [network.id]: false }));
@@ -294,7 +295,7 @@ export class NetworkState {
       return true;
     } catch (err) {
       this._error.set(
-        err instanceof Error ? err.message : 'Failed to connect container to network'
+        errorMessage(err, 'Failed to connect container to network')
       );
       return false;
     } finally {
@@ -320,7 +321,7 @@ export class NetworkState {
       return true;
     } catch (err) {
       this._error.set(
-        err instanceof Error ? err.message : 'Failed to disconnect container from network'
+        errorMessage(err, 'Failed to disconnect container from network')
       );
       return false;
     } finally {
@@ -12,8 +12,10 @@ describe('PortForwardState', () => {
     containerId: 'c-1',
     containerPort: 80,
     localPort: 8080,
+    localHost: '127.0.0.1',
     remoteHost: 'localhost',
     remotePort: 80,
+    direction: 'local',
     protocol: 'tcp',
     status: 'active',
     createdAt: '2024-01-01T00:00:00Z',
@@ -4,6 +4,7 @@ import {
   PortForward,
 } from '../core/models/port-forward.model';
 import { PortForwardService } from '../core/services/port-forward.service';
+import { errorMessage } from '../core/models/error.model';
 
 @Injectable({ providedIn: 'root' })
 export class PortForwardState {
@@ -41,7 +42,7 @@ export class PortForwardState {
       this._forwards.set(forwards);
     } catch (err) {
       this._error.set(
-        err instanceof Error ? err.message : 'Failed to load port forwards'
+        errorMessage(err, 'Failed to load port forwards')
       );
     }
   }
@@ -59,7 +60,7 @@ export class PortForwardState {
       return forward;
     } catch (err) {
       this._error.set(
-        err instanceof Error ? err.message : 'Failed to create port forward'
+        errorMessage(err, 'Failed to create port forward')
       );
       return null;
     } finally {
@@ -79,7 +80,7 @@ export class PortForwardState {
       return true;
     } catch (err) {
       this._error.set(
-        err instanceof Error ? err.message : 'Failed to stop port forward'
+        errorMessage(err, 'Failed to stop port forward')
       );
       return false;
     } finally {
@@ -92,7 +93,7 @@ export class PortForwardState {
       await this.portForwardService.openInBrowser(forwardId);
     } catch (err) {
       this._error.set(
-        err instanceof Error ? err.message : 'Failed to open in browser'
+        errorMessage(err, 'Failed to open in browser')
       );
     }
   }
@@ -28,9 +28,16 @@ describe('SystemState', () => {
       removeSystem: vi.fn(),
       connectSystem: vi.fn(),
       disconnectSystem: vi.fn(),
+      connectSystems: vi.fn(),
+      disconnectSystems: vi.fn(),
       getConnectionState: vi.fn(),
       detectRuntimes: vi.fn(),
       getExtendedSystemInfo: vi.fn(),
+      removeKnownHost: vi.fn(),
+      trustHostKey: vi.fn(),
+      onReconnected: vi.fn().mockResolvedValue(() => {}),
+      onConnectionChanged: vi.fn().mockResolvedValue(() => {}),
+      onSystemsRefreshed: vi.fn().mockResolvedValue(() => {}),
     };
     mockMonitoringService = {
       startListening: vi.fn(),
@@ -49,6 +56,26 @@ describe('SystemState', () => {
     expect(mockMonitoringService.startListening).toHaveBeenCalled();
   });
 
+  it('should mark a system connected when the backend reconnects it', () => {
+    const handler = mockSystemService.onReconnected.mock.calls[0][0];
+    handler('sys-1');
+    expect(state.connectionStates()['sys-1']).toBe('connected');
+  });
+
+  it('should replace systems with the refreshed list', () => {
+    const handler = mockSystemService.onSystemsRefreshed.mock.calls[0][0];
+    const refreshed = makeSystem({ availableRuntimes: ['docker', 'podman'], lastConnectedAt: 1700000000 });
+    handler([refreshed]);
+    expect(state.systems()).toEqual([refreshed]);
+  });
+
+  it('should apply connection changes found by health checks', () => {
+    const handler = mockSystemService.onConnectionChanged.mock.calls[0][0];
+    handler({ systemId: 'sys-1', state: 'error', error: 'Network timeout' });
+    expect(state.connectionStates()['sys-1']).toBe('error');
+    expect(state.error()).toBe('sys-1: Network timeout');
+  });
+
   it('should start with empty state', () => {
     expect(state.systems()).toEqual([]);
     expect(state.loading()).toBe(false);
@@ -141,6 +168,87 @@ describe('SystemState', () => {
     expect(state.error()).toBe('Refused');
   });
 
+  it('should offer to trust a changed host key', async () => {
+    mockSystemService.connectSystem.mockRejectedValue({
+      kind: 'hostKeyChanged',
+      message: 'SSH host key for example.com has changed!',
+      detail: null,
+      hostKey: {
+        hostname: 'example.com',
+        port: 22,
+        keyType: 'ssh-ed25519',
+        fingerprint: 'new',
+        expectedFingerprint: 'old',
+      },
+    });
+
+    const result = await state.connectSystem('sys-1');
+
+    expect(result).toBe(false);
+    expect(state.error()).toBeNull();
+    expect(state.hostKeyMismatch()).toEqual({
+      systemId: 'sys-1',
+      reason: 'changed',
+      hostname: 'example.com',
+      port: 22,
+      keyType: 'ssh-ed25519',
+      expected: 'SHA256:old',
+      actual: 'SHA256:new',
+    });
+  });
+
+  it('should trust an unknown host key and reconnect', async () => {
+    mockSystemService.connectSystem.mockRejectedValueOnce({
+      kind: 'hostKeyUnknown',
+      message: 'Unknown SSH host key for jump.example.com',
+      detail: null,
+      hostKey: {
+        hostname: 'jump.example.com',
+        port: 2222,
+        keyType: 'ssh-ed25519',
+        fingerprint: 'abc',
+        expectedFingerprint: null,
+      },
+    });
+
+    await state.connectSystem('sys-1', 'secret');
+    expect(state.hostKeyMismatch()?.reason).toBe('unknown');
+    expect(state.hostKeyMismatch()?.expected).toBeNull();
+
+    mockSystemService.trustHostKey.mockResolvedValue(undefined);
+    mockSystemService.connectSystem.mockResolvedValue('connected');
+    mockSystemService.getExtendedSystemInfo.mockResolvedValue({});
+    await state.trustNewHostKey();
+
+    expect(mockSystemService.removeKnownHost).not.toHaveBeenCalled();
+    expect(mockSystemService.trustHostKey).toHaveBeenCalledWith('jump.example.com', 'SHA256:abc');
+    expect(mockSystemService.connectSystem).toHaveBeenLastCalledWith(
+      'sys-1', 'secret', undefined, undefined, undefined
+    );
+    expect(state.hostKeyMismatch()).toBeNull();
+  });
+
+  it('should apply batch connect results per system', async () => {
+    mockSystemService.listSystems.mockResolvedValue([makeSystem(), makeSystem({ id: 'sys-2', name: 'Other' })]);
+    mockSystemService.getConnectionState.mockResolvedValue('disconnected');
+    await state.loadSystems();
+
+    mockSystemService.connectSystems.mockResolvedValue({
+      'sys-1': { state: 'connected', error: null },
+      'sys-2': { state: 'error', error: { kind: 'connection', message: 'Refused', detail: null } },
+    });
+    mockSystemService.getExtendedSystemInfo.mockResolvedValue({});
+
+    const connected = await state.connectSystems(['sys-1', 'sys-2']);
+
+    expect(connected).toEqual(['sys-1']);
+    expect(state.getConnectionState('sys-1')).toBe('connected');
+    expect(state.getConnectionState('sys-2')).toBe('error');
+    expect(mockMonitoringService.startMonitoring).toHaveBeenCalledWith('sys-1');
+    expect(mockMonitoringService.startMonitoring).not.toHaveBeenCalledWith('sys-2');
+    expect(state.error()).toBe('Other: Refused');
+  });
+
   it('should disconnect a system', async () => {
     mockSystemService.listSystems.mockResolvedValue([makeSystem()]);
     mockSystemService.getConnectionState.mockResolvedValue('connected');
@@ -187,7 +295,11 @@ describe('SystemState', () => {
     mockSystemService.getConnectionState.mockResolvedValue('connected');
     await state.loadSystems();
 
-    mockSystemService.detectRuntimes.mockResolvedValue(['docker', 'podman']);
+    mockSystemService.detectRuntimes.mockResolvedValue({
+      runtimes: ['docker', 'podman'],
+      rootless: true,
+      socketPath: '$XDG_RUNTIME_DIR/docker.sock',
+    });
 
     const runtimes = await state.detectRuntimes('sys-1');
 
@@ -291,6 +403,14 @@ describe('SystemState', () => {
     expect(state.getExtendedInfo('sys-1')).toEqual(info);
   });
 
+  it('should fetch extended info for a mount point', async () => {
+    mockSystemService.getExtendedSystemInfo.mockResolvedValue({ diskUsagePercent: 69 });
+
+    await state.fetchExtendedInfo('sys-1', '/srv/data');
+
+    expect(mockSystemService.getExtendedSystemInfo).toHaveBeenCalledWith('sys-1', '/srv/data');
+  });
+
   it('should return null for extended info on error', async () => {
     mockSystemService.getExtendedSystemInfo.mockRejectedValue(new Error('fail'));
 
@@ -7,10 +7,12 @@ import {
   JumpHostCredentials,
   LiveSystemMetrics,
   NewSystemRequest,
+  SystemConnectionResult,
   UpdateSystemRequest,
 } from '../core/models/system.model';
 import { SystemMonitoringService } from '../core/services/system-monitoring.service';
 import { SystemService } from '../core/services/system.service';
+import { errorMessage, isAppError } from '../core/models/error.model';
 
 @Injectable({ providedIn: 'root' })
 export class SystemState {
@@ -24,9 +26,13 @@ export class SystemState {
   private _statusFilter = signal<ConnectionState | null>(null);
   private _hostKeyMismatch = signal<{
     systemId: string;
+    /** First connection to the host, or a key that differs from known_hosts */
+    reason: 'unknown' | 'changed';
     hostname: string;
     port: number;
-    expected: string;
+    keyType: string;
+    /** null for an unknown host */
+    expected: string | null;
     actual: string;
   } | null>(null);
 
@@ -118,6 +124,39 @@ export class SystemState {
   ) {
     // Start listening to monitoring events
     this.monitoringService.startListening();
+
+    // The backend reconnects dropped SSH sessions on its own
+    this.systemService.onReconnected((systemId) => {
+      this._connectionStates.update((states) => ({
+        ...states,
+        [systemId]: 'connected',
+      }));
+    });
+
+    // list_systems answers from the backend's cache and refreshes it afterwards
+    this.systemService.onSystemsRefreshed((systems) => {
+      this._systems.set(systems);
+    });
+
+    // Every backend connection transition, including health checks that find a connection gone
+    this.systemService.onConnectionChanged(({ systemId, state, error }) => {
+      this._connectionStates.update((states) => ({
+        ...states,
+        [systemId]: state,
+      }));
+      if (error) {
+        const name = this._systems().find((s) => s.id === systemId)?.name ?? systemId;
+        this._error.set(`${name}: ${error}`);
+      }
+    });
+  }
+
+  async startHealthPolling(intervalMs?: number): Promise<void> {
+    try {
+      await this.systemService.startHealthPolling(intervalMs);
+    } catch (err) {
+      this._error.set(errorMessage(err, 'Failed to start health polling'));
+    }
   }
 
   async loadSystems(): Promise<void> {
@@ -138,7 +177,7 @@ export class SystemState {
       );
       this._connectionStates.set(states);
     } catch (err) {
-      this._error.set(this.extractError(err) || 'Failed to load systems');
+      this._error.set(errorMessage(err, 'Failed to load systems'));
     } finally {
       this._loading.set(false);
     }
@@ -169,7 +208,7 @@ export class SystemState {
 
       return system;
     } catch (err) {
-      this._error.set(this.extractError(err) || 'Failed to add system');
+      this._error.set(errorMessage(err, 'Failed to add system'));
       return null;
     } finally {
       this._loading.set(false);
@@ -210,37 +249,66 @@ export class SystemState {
         [systemId]: 'error',
       }));
 
-      const hostKeyErr = this.extractHostKeyError(err);
-      if (hostKeyErr) {
-        const system = this._systems().find(s => s.id === systemId);
-        const port = system?.sshConfig?.port ?? 22;
-        const hostname = hostKeyErr.hostname || system?.hostname || '';
-        const reason = hostKeyErr.reason;
-        const expectedMatch = reason.match(/Expected:\s*(\S+)/);
-        const actualMatch = reason.match(/Received:\s*(\S+)/);
-        this._pendingCredentials = { password, passphrase, privateKey, jumpHostCredentials };
-        this._hostKeyMismatch.set({
-          systemId,
-          hostname,
-          port,
-          expected: expectedMatch?.[1] ?? 'unknown',
-          actual: actualMatch?.[1] ?? 'unknown',
-        });
-      } else {
-        const errStr = this.extractError(err);
-        this._error.set(errStr || 'Connection failed');
+      if (!this.offerHostKeyTrust(systemId, err, { password, passphrase, privateKey, jumpHostCredentials })) {
+        this._error.set(errorMessage(err, 'Connection failed'));
       }
       return false;
     }
   }
 
+  /**
+   * Connect several systems at once with their stored credentials.
+   * Each system gets its own outcome, so one failing host doesn't affect the rest.
+   * @returns The IDs of the systems that connected
+   */
+  async connectSystems(systemIds: string[]): Promise<string[]> {
+    if (systemIds.length === 0) return [];
+    this._connectionStates.update((states) => ({
+      ...states,
+      ...Object.fromEntries(systemIds.map((id) => [id, 'connecting' as ConnectionState])),
+    }));
+
+    let results: Record<string, SystemConnectionResult>;
+    try {
+      results = await this.systemService.connectSystems(systemIds);
+    } catch (err) {
+      results = Object.fromEntries(systemIds.map((id) => [id, { state: 'error', error: err }]));
+    }
+
+    const connected: string[] = [];
+    const failures: string[] = [];
+    for (const systemId of systemIds) {
+      const { state, error } = results[systemId] ?? { state: 'error', error: null };
+      this._connectionStates.update((states) => ({ ...states, [systemId]: state }));
+      if (state === 'connected') {
+        connected.push(systemId);
+        this.fetchExtendedInfo(systemId);
+        this.monitoringService.startMonitoring(systemId);
+      } else if (!this.offerHostKeyTrust(systemId, error, {})) {
+        const name = this._systems().find((s) => s.id === systemId)?.name ?? systemId;
+        failures.push(`${name}: ${errorMessage(error, 'Connection failed')}`);
+      }
+    }
+    if (failures.length > 0) {
+      this._error.set(failures.join('\n'));
+    }
+    return connected;
+  }
+
   /**
    * Fetch extended system information for a connected system.
    * This is called automatically on connection but can be called manually to refresh.
+   * Pass `mountPoint` to report disk usage for a volume other than `/`.
    */
-  async fetchExtendedInfo(systemId: string): Promise<ExtendedSystemInfo | null> {
+  async fetchExtendedInfo(
+    systemId: string,
+    mountPoint?: string
+  ): Promise<ExtendedSystemInfo | null> {
     try {
-      const info = await this.systemService.getExtendedSystemInfo(systemId);
+      const info = await this.systemService.getExtendedSystemInfo(
+        systemId,
+        mountPoint
+      );
       this._extendedInfo.update((infoMap) => ({
         ...infoMap,
         [systemId]: info,
@@ -312,13 +380,37 @@ export class SystemState {
         return newMap;
       });
     } catch (err) {
-      this._error.set(this.extractError(err) || 'Disconnect failed');
+      this._error.set(errorMessage(err, 'Disconnect failed'));
+    }
+  }
+
+  async disconnectSystems(systemIds: string[]): Promise<void> {
+    if (systemIds.length === 0) return;
+    try {
+      await Promise.all(systemIds.map((id) => this.monitoringService.stopMonitoring(id)));
+
+      const results = await this.systemService.disconnectSystems(systemIds);
+      const failures: string[] = [];
+      for (const [systemId, { state, error }] of Object.entries(results)) {
+        this._connectionStates.update((states) => ({ ...states, [systemId]: state }));
+        if (error) failures.push(errorMessage(error));
+      }
+      this._extendedInfo.update((infoMap) => {
+        const newMap = { ...infoMap };
+        systemIds.forEach((id) => delete newMap[id]);
+        return newMap;
+      });
+      if (failures.length > 0) {
+        this._error.set(failures.join('\n'));
+      }
+    } catch (err) {
+      this._error.set(errorMessage(err, 'Disconnect failed'));
     }
   }
 
   async detectRuntimes(systemId: string): Promise<ContainerRuntime[]> {
     try {
-      const runtimes = await this.systemService.detectRuntimes(systemId);
+      const { runtimes } = await this.systemService.detectRuntimes(systemId);
       this._systems.update((systems) =>
         systems.map((s) =>
           s.id === systemId ? { ...s, availableRuntimes: runtimes } : s
@@ -326,7 +418,7 @@ export class SystemState {
       );
       return runtimes;
     } catch (err) {
-      this._error.set(this.extractError(err) || 'Failed to detect runtimes');
+      this._error.set(errorMessage(err, 'Failed to detect runtimes'));
       return [];
     }
   }
@@ -342,7 +434,7 @@ export class SystemState {
       );
       return system;
     } catch (err) {
-      this._error.set(this.extractError(err) || 'Failed to update system');
+      this._error.set(errorMessage(err, 'Failed to update system'));
       return null;
     } finally {
       this._loading.set(false);
@@ -365,7 +457,7 @@ export class SystemState {
       }
       return removed;
     } catch (err) {
-      this._error.set(this.extractError(err) || 'Failed to remove system');
+      this._error.set(errorMessage(err, 'Failed to remove system'));
       return false;
     } finally {
       this._loading.set(false);
@@ -402,51 +494,31 @@ export class SystemState {
   }
 
   /**
-   * Extract a human-readable error message from Tauri command errors.
-   * Tauri serializes Rust enums as objects like { "VariantName": data }.
-   */
-  private extractError(err: unknown): string {
-    if (typeof err === 'string') return err;
-    if (err instanceof Error) return err.message;
-    if (err && typeof err === 'object') {
-      // Tauri serializes Rust enums as { VariantName: data }
-      const entries = Object.entries(err as Record<string, unknown>);
-      if (entries.length === 1) {
-        const [, value] = entries[0];
-        if (typeof value === 'string') return value;
-        if (Array.isArray(value)) return value.filter(v => typeof v === 'string').join(': ');
-        if (value && typeof value === 'object') {
-          const inner = value as Record<string, unknown>;
-          // Struct variants: try common fields
-          if (typeof inner['reason'] === 'string') return inner['reason'] as string;
-          if (typeof inner['message'] === 'string') return inner['message'] as string;
-          if (typeof inner['stderr'] === 'string') return `${inner['command'] ?? 'Command'}: ${inner['stderr']}`;
-          return JSON.stringify(value);
-        }
-      }
-      return JSON.stringify(err);
-    }
-    return String(err);
-  }
-
-  /**
-   * Check if a Tauri error is a HostKeyVerificationFailed variant.
-   * Returns the parsed hostname/reason if so, null otherwise.
+   * Ask the user to trust the host key if `err` is an unknown or changed host key.
+   * The key may belong to a jump host rather than the system itself.
+   * The credentials are kept to retry the connection once the key is trusted.
+   * @returns Whether the error was a host key the user can trust
    */
-  private extractHostKeyError(err: unknown): { hostname: string; reason: string } | null {
-    if (err && typeof err === 'object' && !Array.isArray(err)) {
-      const obj = err as Record<string, unknown>;
-      const hk = obj['HostKeyVerificationFailed'] as Record<string, string> | undefined;
-      if (hk && typeof hk === 'object' && typeof hk['reason'] === 'string') {
-        return { hostname: hk['hostname'] ?? '', reason: hk['reason'] };
-      }
-    }
-    // Fallback: check if string contains the phrase
-    const str = typeof err === 'string' ? err : (err instanceof Error ? err.message : '');
-    if (str.toLowerCase().includes('host key verification failed')) {
-      return { hostname: '', reason: str };
-    }
-    return null;
+  private offerHostKeyTrust(
+    systemId: string,
+    err: unknown,
+    credentials: NonNullable<SystemState['_pendingCredentials']>
+  ): boolean {
+    if (!isAppError(err) || !err.hostKey) return false;
+    if (err.kind !== 'hostKeyUnknown' && err.kind !== 'hostKeyChanged') return false;
+
+    const hostKey = err.hostKey;
+    this._pendingCredentials = credentials;
+    this._hostKeyMismatch.set({
+      systemId,
+      reason: err.kind === 'hostKeyChanged' ? 'changed' : 'unknown',
+      hostname: hostKey.hostname,
+      port: hostKey.port,
+      keyType: hostKey.keyType,
+      expected: hostKey.expectedFingerprint ? `SHA256:${hostKey.expectedFingerprint}` : null,
+      actual: `SHA256:${hostKey.fingerprint}`,
+    });
+    return true;
   }
 
   async trustNewHostKey(): Promise<void> {
@@ -463,7 +535,10 @@ export class SystemState {
     // user must provide credentials again (intentional single-trust-per-action).
     this._pendingCredentials = null;
     try {
-      await this.systemService.removeKnownHost(mismatch.hostname, mismatch.port);
+      if (mismatch.reason === 'changed') {
+        await this.systemService.removeKnownHost(mismatch.hostname, mismatch.port);
+      }
+      await this.systemService.trustHostKey(mismatch.hostname, mismatch.actual);
       this._hostKeyMismatch.set(null);
       await this.connectSystem(
         mismatch.systemId,
@@ -473,7 +548,7 @@ export class SystemState {
         creds?.jumpHostCredentials,
       );
     } catch (err) {
-      this._error.set(this.extractError(err) || 'Failed to remove known host');
+      this._error.set(errorMessage(err, 'Failed to trust host key'));
       this._hostKeyMismatch.set(null);
     }
   }
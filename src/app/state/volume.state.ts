@@ -3,6 +3,7 @@ import { ContainerRuntime } from '../core/models/container.model';
 import { Volume } from '../core/models/volume.model';
 import { VolumeService } from '../core/services/volume.service';
 import { ContainerState } from './container.state';
+import { errorMessage } from '../core/models/error.model';
 
 export type VolumeMountFilter = 'all' | 'mounted' | 'orphaned';
 
@@ -134,7 +135,7 @@ export class VolumeState {
         return [...filtered, ...volumes];
       });
     } catch (err) {
-      this._error.set(err instanceof Error ? err.message : 'Failed to load volumes');
+      this._error.set(errorMessage(err, 'Failed to load volumes'));
     } finally {
       this._loading.update((l) => ({ ...l, [systemId]: false }));
     }
@@ -154,7 +155,7 @@ export class VolumeState {
       await this.loadVolumes(systemId);
       return true;
     } catch (err) {
-      this._error.set(err instanceof Error ? err.message : 'Failed to create volume');
+      this._error.set(errorMessage(err, 'Failed to create volume'));
       return false;
     } finally {
       this._loading.update((l) => ({ ...l, create: false }));
@@ -178,7 +179,7 @@ export class VolumeState {
       );
       return true;
     } catch (err) {
-      this._error.set(err instanceof Error ? err.message : 'Failed to remove volume');
+      this._error.set(errorMessage(err, 'Failed to remove volume'));
       return false;
     } finally {
       this._loading.update((l) => ({ ...l, [volume.name]: false }));